@@ -0,0 +1,39 @@
+mod helpers;
+
+use crate::helpers::init_logging;
+use clap::Parser;
+use esm_parser::{Export, ParseOptions, dissect_from_file};
+
+#[derive(Parser, Default, Debug)]
+#[clap(version, author = "Milan Bolaric", about = "Annotated hex dissection of ESM files (*.DDD)", name = "esm2dissect")]
+pub struct Args {
+    #[clap(short, long)]
+    pub ddd_file: String,
+
+    #[clap(global = true, short, long, help = "Print the dissection as JSON instead of text.")]
+    pub json: bool,
+
+    #[clap(global = true, short, long, help = "Dissect the data which could be parsed, errors are printed as diagnostics.")]
+    pub lenient: bool,
+}
+
+fn main() {
+    init_logging();
+    let args = Args::parse();
+    let options = if args.lenient { ParseOptions::lenient() } else { ParseOptions::strict() };
+    let dissection = match dissect_from_file(&args.ddd_file, &options) {
+        Ok(dissection) => dissection,
+        Err(error) => {
+            eprintln!("[-] Dissect failed: {error}");
+            std::process::exit(1);
+        }
+    };
+    if args.json {
+        match dissection.to_json_pretty() {
+            Ok(json) => println!("{json}"),
+            Err(error) => eprintln!("[-] JSON export failed: {error}"),
+        }
+    } else {
+        print!("{dissection}");
+    }
+}
@@ -15,6 +15,13 @@ pub struct Args {
     #[clap(global = true, short, long)]
     pub pretty: bool,
 
+    #[clap(
+        global = true,
+        long,
+        help = "Export the data in a versioned envelope with the schema version, the data type, the generation, the parser version and the SHA-256 hash of the DDD file."
+    )]
+    pub envelope: bool,
+
     #[clap(
         global = true,
         short = 'e',
@@ -37,5 +44,13 @@ pub struct Args {
 fn main() {
     init_logging();
     let args = Args::parse();
-    export(&ExportType::Json, &args.ddd_file, &args.json_file, &args.erca_gen1_file, &args.erca_gen2_file, args.pretty);
+    export(
+        &ExportType::Json,
+        &args.ddd_file,
+        &args.json_file,
+        &args.erca_gen1_file,
+        &args.erca_gen2_file,
+        args.pretty,
+        args.envelope,
+    );
 }
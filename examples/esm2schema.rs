@@ -0,0 +1,36 @@
+use clap::Parser;
+use esm_parser::{EXPORT_SCHEMA_VERSION, export_json_schema};
+
+#[derive(Parser, Default, Debug)]
+#[clap(
+    version,
+    author = "Milan Bolaric",
+    about = "JSON Schema of the versioned export of ESM files (*.DDD)",
+    name = "esm2schema"
+)]
+pub struct Args {
+    #[clap(short, long, default_value = "", help = "Path of the schema file, the schema is printed when it is not provided.")]
+    pub schema_file: String,
+}
+
+fn main() {
+    let args = Args::parse();
+    let schema = match serde_json::to_string_pretty(&export_json_schema()) {
+        Ok(schema) => schema,
+        Err(error) => {
+            eprintln!("[-] Schema export failed: {error}");
+            std::process::exit(1);
+        }
+    };
+    if args.schema_file.is_empty() {
+        println!("{schema}");
+        return;
+    }
+    match std::fs::write(&args.schema_file, schema) {
+        Ok(_) => println!("[+] Saved the schema version {EXPORT_SCHEMA_VERSION} to file ({})", args.schema_file),
+        Err(error) => {
+            eprintln!("[-] Save of {} failed: {error}", args.schema_file);
+            std::process::exit(1);
+        }
+    }
+}
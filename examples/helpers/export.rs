@@ -5,9 +5,10 @@ use std::path::PathBuf;
 
 use esm_parser::TachographData;
 use esm_parser::gen1::CardResponseParameterData as CardResponseParameterDataGen1;
-use esm_parser::tacho::DataFiles;
-use esm_parser::verify_card_with_erca_path;
-use esm_parser::{Export, parse_from_file};
+use esm_parser::gen2::{CardResponseParameterData as CardResponseParameterDataGen2, ParsedCard};
+use esm_parser::tacho::{CardGeneration, CardParser, DataFiles};
+use esm_parser::{Export, ExportEnvelope, parse_from_memory};
+use esm_parser::{verify_card_with_erca_path, verify_vu_with_erca_path};
 use indicatif::ProgressBar;
 use serde::Serialize;
 use std::fmt;
@@ -97,11 +98,48 @@ fn verify_inner(
     Ok(())
 }
 
+fn verify_card_files(
+    export_type: &ExportType,
+    generation: CardGeneration,
+    card: &dyn DataFiles,
+    erca_file: &str,
+    out_verify_path: &str,
+    pb: &ProgressBar,
+    pretty: bool,
+) {
+    if erca_file.is_empty() {
+        pb.println(format!("[-] ERCA public key of the card generation {:?} is not provided.", generation));
+        return;
+    }
+    match verify_card_with_erca_path(generation, card.get_data_files(), erca_file) {
+        Ok(result) => match verify_inner(export_type, &result, out_verify_path, pb, pretty) {
+            Ok(_) => pb.println("[+] Certificate verification Done."),
+            Err(err) => pb.println(format!("[-] {:}", err)),
+        },
+        Err(err) => {
+            pb.println(format!("[-] Certificate verification error: {}.", err));
+            pb.println("[+] Certificate verification Done.")
+        }
+    }
+}
+
+/// Returns the Gen1 and the Gen2 application of a parsed Gen2 card.
+fn parsed_card_applications<TGen1: CardParser<TGen1> + DataFiles, TGen2: CardParser<TGen2> + DataFiles>(
+    card: &ParsedCard<TGen1, TGen2>,
+) -> (Option<&dyn DataFiles>, Option<&dyn DataFiles>) {
+    match card {
+        ParsedCard::Gen1(card_gen1) => (Some(card_gen1.as_ref()), None),
+        ParsedCard::Gen2(card_gen2) => (None, Some(card_gen2.as_ref())),
+        ParsedCard::Combined(card_gen1, card_gen2) => (Some(card_gen1.as_ref()), Some(card_gen2.as_ref())),
+        ParsedCard::None => (None, None),
+    }
+}
+
 fn verify(
     export_type: &ExportType,
     data: &TachographData,
     erca_gen1_file: &str,
-    _erca_gen2_file: &str,
+    erca_gen2_file: &str,
     out_verify_path: &str,
     pb: &ProgressBar,
     pretty: bool,
@@ -119,26 +157,47 @@ fn verify(
             };
 
             if let Some(card) = card_type {
-                match verify_card_with_erca_path(esm_parser::tacho::CardGeneration::Gen1, card.get_data_files(), erca_gen1_file) {
-                    Ok(result) => match verify_inner(export_type, &result, out_verify_path, pb, pretty) {
-                        Ok(_) => pb.println("[+] Certificate verification Done."),
-                        Err(err) => pb.println(format!("[-] {:}", err)),
-                    },
-                    Err(err) => {
-                        pb.println(format!("[-] Certificate verification error: {}.", err));
-                        pb.println("[+] Certificate verification Done.")
-                    }
-                }
+                verify_card_files(export_type, CardGeneration::Gen1, card, erca_gen1_file, out_verify_path, pb, pretty);
                 return;
             }
             pb.println("[-] Unsupported Card Type verification is not possible.");
         }
-        TachographData::CardGen2(_card_gen1) => {
-            pb.println("[-] Certificate verification is not implemented.");
+        TachographData::CardGen2(card_gen2) => {
+            let (card_gen1, card_gen2) = match &card_gen2.card_data_responses {
+                CardResponseParameterDataGen2::DriverCard(card) => parsed_card_applications(card),
+                CardResponseParameterDataGen2::WorkshopCard(card) => parsed_card_applications(card),
+                CardResponseParameterDataGen2::ControlCard(card) => parsed_card_applications(card),
+                CardResponseParameterDataGen2::CompanyCard(card) => parsed_card_applications(card),
+                CardResponseParameterDataGen2::Unsupported => (None, None),
+            };
+
+            // The Gen2 application is verified when the card has one, otherwise the Gen1 application.
+            if let Some(card) = card_gen2 {
+                verify_card_files(export_type, CardGeneration::Gen2, card, erca_gen2_file, out_verify_path, pb, pretty);
+                return;
+            }
+            if let Some(card) = card_gen1 {
+                verify_card_files(export_type, CardGeneration::Gen1, card, erca_gen1_file, out_verify_path, pb, pretty);
+                return;
+            }
+            pb.println("[-] Unsupported Card Type verification is not possible.");
         }
-        _ => {
-            pb.println("[-] Certificate Verification not supported");
-            pb.println("[-] Certificate verification disabled.");
+        TachographData::VUGen1(_) | TachographData::VUGen2(_) => {
+            let erca_file = if matches!(data, TachographData::VUGen1(_)) { erca_gen1_file } else { erca_gen2_file };
+            if erca_file.is_empty() {
+                pb.println("[-] ERCA public key of the VU generation is not provided.");
+                return;
+            }
+            match verify_vu_with_erca_path(data, erca_file) {
+                Ok(result) => match verify_inner(export_type, &result, out_verify_path, pb, pretty) {
+                    Ok(_) => pb.println("[+] Certificate verification Done."),
+                    Err(err) => pb.println(format!("[-] {:}", err)),
+                },
+                Err(err) => {
+                    pb.println(format!("[-] Certificate verification error: {}.", err));
+                    pb.println("[+] Certificate verification Done.")
+                }
+            }
         }
     }
 }
@@ -151,6 +210,7 @@ pub fn export(
     erca_gen1_file: &str,
     erca_gen2_file: &str,
     pretty: bool,
+    envelope: bool,
 ) {
     #[cfg(debug_assertions)]
     let pb = ProgressBar::hidden();
@@ -168,7 +228,22 @@ pub fn export(
 
     let (out_path, out_verify_path) = prepare_out_path(ddd_file, out_file, export_type);
     pb.println(format!("[+] Prepared output path: {}, {}.", out_path, out_verify_path));
-    match parse_from_file(ddd_file) {
+    let source_data = match std::fs::read(ddd_file) {
+        Ok(source_data) => source_data,
+        Err(err) => {
+            pb.println(format!("[-] {:}", err));
+            pb.println("[+] Parsing Done");
+            return;
+        }
+    };
+    match parse_from_memory(&source_data) {
+        Ok(data) if envelope => {
+            let envelope = ExportEnvelope::new(data, &source_data);
+            parse(export_type, &envelope, &out_path, &pb, pretty);
+            if !erca_gen1_file.is_empty() || !erca_gen2_file.is_empty() {
+                verify(export_type, &envelope.data, erca_gen1_file, erca_gen2_file, &out_verify_path, &pb, pretty);
+            }
+        }
         Ok(data) => {
             parse(export_type, &data, &out_path, &pb, pretty);
             if !erca_gen1_file.is_empty() || !erca_gen2_file.is_empty() {
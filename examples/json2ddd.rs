@@ -0,0 +1,60 @@
+mod helpers;
+
+use std::path::PathBuf;
+
+use crate::helpers::init_logging;
+use clap::Parser;
+use esm_parser::write_ddd_from_json;
+
+#[derive(Parser, Default, Debug)]
+#[clap(
+    version,
+    author = "Milan Bolaric",
+    about = "Write JSON exported by esm2json back into ESM files (*.DDD)",
+    name = "json2ddd"
+)]
+pub struct Args {
+    #[clap(short, long)]
+    pub json_file: String,
+
+    #[clap(
+        global = true,
+        short,
+        long,
+        default_value = "",
+        help = "Path of the DDD file, the JSON file path with the DDD extension by default."
+    )]
+    pub ddd_file: String,
+}
+
+fn main() {
+    init_logging();
+    let args = Args::parse();
+    let ddd_file = if args.ddd_file.is_empty() {
+        PathBuf::from(&args.json_file).with_extension("DDD").display().to_string()
+    } else {
+        args.ddd_file
+    };
+
+    let json = match std::fs::read_to_string(&args.json_file) {
+        Ok(json) => json,
+        Err(error) => {
+            eprintln!("[-] Read of {} failed: {error}", args.json_file);
+            std::process::exit(1);
+        }
+    };
+    let data = match write_ddd_from_json(&json) {
+        Ok(data) => data,
+        Err(error) => {
+            eprintln!("[-] Write of the DDD data failed: {error}");
+            std::process::exit(1);
+        }
+    };
+    match std::fs::write(&ddd_file, data) {
+        Ok(_) => println!("[+] Saved the DDD data to file ({ddd_file})"),
+        Err(error) => {
+            eprintln!("[-] Save of {ddd_file} failed: {error}");
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,304 @@
+use std::{any::type_name, cell::RefCell};
+
+use binary_data::BinSeek;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{Result, ResultExt};
+
+/// Location of a parsed structure in the DDD data, with the locations of the
+/// structures it was parsed from.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ByteSpan {
+    /// Type name of the structure, card file (EF) or TREP name for the sections.
+    pub name: String,
+    /// Name of the field in the parent structure, missing for the sections and the
+    /// structures which are not read as a field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    /// Absolute byte offset in the parsed data.
+    pub offset: usize,
+    /// Length in bytes. The records of a cyclic buffer (e.g. `CardDriverActivity`)
+    /// can wrap, then `offset + len` passes the end of the buffer and the rest
+    /// of the record continues at the start of the buffer.
+    pub len: usize,
+    /// Parsed value of a span without children, recorded only for the dissection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+    pub children: Vec<ByteSpan>,
+}
+
+impl ByteSpan {
+    pub fn new(name: String, offset: usize, len: usize, children: Vec<ByteSpan>) -> Self {
+        Self { name, field: None, offset, len, value: None, children }
+    }
+
+    /// Returns the smallest span which contains the absolute offset.
+    pub fn find(&self, offset: usize) -> Option<&ByteSpan> {
+        if offset < self.offset || offset >= self.offset + self.len {
+            return None;
+        }
+        self.children.iter().filter_map(|child| child.find(offset)).min_by_key(|span| span.len).or(Some(self))
+    }
+}
+
+/// Start of the data of the current reader in the parsed data.
+struct SpanBase {
+    offset: usize,
+    /// Length of the data when the reader is a ring buffer.
+    ring_len: Option<usize>,
+}
+
+#[derive(Default)]
+struct SpanRecorderData {
+    enabled: bool,
+    with_values: bool,
+    bases: Vec<SpanBase>,
+    frames: Vec<Vec<ByteSpan>>,
+}
+
+impl SpanRecorderData {
+    fn absolute(&self, pos: usize) -> usize {
+        self.bases.last().map_or(pos, |base| base.offset + pos)
+    }
+
+    fn push_span(&mut self, span: ByteSpan) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.push(span);
+        }
+    }
+}
+
+thread_local! {
+    static SPAN_RECORDER: RefCell<SpanRecorderData> = RefCell::new(SpanRecorderData::default());
+}
+
+/// Restores the span recorder which was installed before a parse when it is dropped,
+/// also when the parse panics.
+struct SpanRecorderGuard {
+    previous: Option<SpanRecorderData>,
+}
+
+impl SpanRecorderGuard {
+    /// Restores the previous recorder and returns the recorder of the finished parse.
+    fn finish(mut self) -> SpanRecorderData {
+        let previous = self.previous.take().unwrap_or_default();
+        SPAN_RECORDER.with(|rec| rec.replace(previous))
+    }
+}
+
+impl Drop for SpanRecorderGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            SPAN_RECORDER.with(|rec| rec.replace(previous));
+        }
+    }
+}
+
+/// Records the byte spans of all structures read on the current thread.
+///
+/// The structures are read from readers over a part of the data (card file,
+/// TREP data), a `SpanSection` sets the offset of that part, so the recorded
+/// offsets are absolute.
+pub(crate) struct SpanRecorder;
+
+impl SpanRecorder {
+    /// Runs `parse` and returns its result together with the recorded spans,
+    /// the spans are recorded only when `enabled` is set. With `with_values`
+    /// the spans keep the parsed values too.
+    pub(crate) fn run<T>(enabled: bool, with_values: bool, parse: impl FnOnce() -> T) -> (T, Option<ByteSpan>) {
+        let recorder = SpanRecorderData { enabled, with_values, bases: Vec::new(), frames: vec![Vec::new()] };
+        let guard = SpanRecorderGuard { previous: Some(SPAN_RECORDER.with(|rec| rec.replace(recorder))) };
+        let result = parse();
+        let recorder = guard.finish();
+
+        let spans = recorder.enabled.then(|| {
+            let children = recorder.frames.into_iter().next().unwrap_or_default();
+            let len = children.iter().map(|span| span.offset + span.len).max().unwrap_or(0);
+            ByteSpan::new("DDD".to_owned(), 0, len, children)
+        });
+        (result, spans)
+    }
+
+    pub(crate) fn is_enabled() -> bool {
+        SPAN_RECORDER.with(|rec| rec.borrow().enabled)
+    }
+
+    /// Converts the position of the current reader to the offset in the parsed data.
+    pub(crate) fn absolute(pos: usize) -> usize {
+        SPAN_RECORDER.with(|rec| rec.borrow().absolute(pos))
+    }
+
+    /// Reads `T` and records its span named by the type.
+    pub(crate) fn read<T: Serialize, R: BinSeek>(reader: &mut R, read: impl FnOnce(&mut R) -> Result<T>) -> Result<T> {
+        if !SpanRecorder::is_enabled() {
+            return read(reader);
+        }
+        SpanRecorder::record_value(short_type_name::<T>(), None, reader, read, |value| serde_json::to_value(value).ok())
+    }
+
+    /// Reads the field `field` of the structure which is being read and records its span
+    /// named by `type_name`, the data type of the field. An error gets the field in its path.
+    pub(crate) fn field<T: Serialize, R: BinSeek>(
+        field: &str,
+        type_name: &str,
+        reader: &mut R,
+        read: impl FnOnce(&mut R) -> Result<T>,
+    ) -> Result<T> {
+        if !SpanRecorder::is_enabled() {
+            return read(reader).in_path(field);
+        }
+        let to_value = |value: &T| serde_json::to_value(value).ok();
+        SpanRecorder::record_value(type_name.to_owned(), Some(field.to_owned()), reader, read, to_value).in_path(field)
+    }
+
+    /// Runs `read` and records the span of the bytes it consumed.
+    pub(crate) fn record<T, R: BinSeek>(name: String, reader: &mut R, read: impl FnOnce(&mut R) -> Result<T>) -> Result<T> {
+        SpanRecorder::record_value(name, None, reader, read, |_| None)
+    }
+
+    fn record_value<T, R: BinSeek>(
+        name: String,
+        field: Option<String>,
+        reader: &mut R,
+        read: impl FnOnce(&mut R) -> Result<T>,
+        to_value: impl FnOnce(&T) -> Option<Value>,
+    ) -> Result<T> {
+        let Some(start) = SpanRecorder::is_enabled().then(|| reader.pos().ok()).flatten() else {
+            return read(reader);
+        };
+        SPAN_RECORDER.with(|rec| rec.borrow_mut().frames.push(Vec::new()));
+        let result = read(reader);
+        let end = reader.pos().ok();
+        SPAN_RECORDER.with(|rec| {
+            let mut rec = rec.borrow_mut();
+            let children = rec.frames.pop().unwrap_or_default();
+            if let (Ok(value), Some(end)) = (&result, end) {
+                let ring_len = rec.bases.last().and_then(|base| base.ring_len);
+                let len = span_len(start, end, ring_len);
+                let mut span = ByteSpan::new(name, rec.absolute(start), len, children);
+                span.field = field;
+                // The values of the structures are in their fields, only the leaves are serialized.
+                if rec.with_values && span.children.is_empty() {
+                    span.value = to_value(value);
+                }
+                rec.push_span(span);
+            }
+        });
+        result
+    }
+
+    /// Starts the section of the data at `offset`, the positions of the readers
+    /// are relative to it until the returned guard is dropped.
+    pub(crate) fn section(name: String, offset: usize, len: usize) -> SpanSection {
+        SpanRecorder::push_section(name, SpanBase { offset, ring_len: None }, len)
+    }
+
+    /// Like `section`, for the data read through a ring buffer of `len` bytes.
+    pub(crate) fn ring_section(name: String, offset: usize, len: usize) -> SpanSection {
+        SpanRecorder::push_section(name, SpanBase { offset, ring_len: Some(len) }, len)
+    }
+
+    fn push_section(name: String, base: SpanBase, len: usize) -> SpanSection {
+        let offset = base.offset;
+        SPAN_RECORDER.with(|rec| {
+            let mut rec = rec.borrow_mut();
+            rec.bases.push(base);
+            if rec.enabled {
+                rec.frames.push(Vec::new());
+            }
+        });
+        SpanSection { name, offset, len }
+    }
+}
+
+/// Guard of a section started with `SpanRecorder::section`.
+pub(crate) struct SpanSection {
+    name: String,
+    offset: usize,
+    len: usize,
+}
+
+impl Drop for SpanSection {
+    fn drop(&mut self) {
+        SPAN_RECORDER.with(|rec| {
+            let mut rec = rec.borrow_mut();
+            rec.bases.pop();
+            if rec.enabled {
+                let children = rec.frames.pop().unwrap_or_default();
+                let span = ByteSpan::new(std::mem::take(&mut self.name), self.offset, self.len, children);
+                rec.push_span(span);
+            }
+        });
+    }
+}
+
+fn span_len(start: usize, end: usize, ring_len: Option<usize>) -> usize {
+    match ring_len {
+        Some(ring_len) if end < start => ring_len - start + end,
+        _ => end.saturating_sub(start),
+    }
+}
+
+/// Returns the type name without the module paths, also for the generic arguments.
+pub(crate) fn short_type_name<T>() -> String {
+    let name = type_name::<T>();
+    let mut short = String::with_capacity(name.len());
+    let mut path = String::new();
+    for c in name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            path.push(c);
+        } else {
+            short.push_str(path.rsplit("::").next().unwrap_or_default());
+            path.clear();
+            short.push(c);
+        }
+    }
+    short.push_str(path.rsplit("::").next().unwrap_or_default());
+    short
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_type_name() {
+        assert_eq!(short_type_name::<ByteSpan>(), "ByteSpan");
+        assert_eq!(short_type_name::<Vec<Option<ByteSpan>>>(), "Vec<Option<ByteSpan>>");
+    }
+
+    #[test]
+    fn test_span_len_wraps_in_ring() {
+        assert_eq!(span_len(10, 14, Some(20)), 4);
+        assert_eq!(span_len(18, 2, Some(20)), 4);
+        assert_eq!(span_len(18, 2, None), 0);
+    }
+
+    #[test]
+    fn test_section_offsets_are_absolute() {
+        let (offset, spans) = SpanRecorder::run(true, false, || {
+            let _section = SpanRecorder::section("EF".to_owned(), 100, 10);
+            SpanRecorder::absolute(4)
+        });
+        assert_eq!(offset, 104);
+        let spans = spans.unwrap();
+        assert_eq!(spans.children, vec![ByteSpan::new("EF".to_owned(), 100, 10, vec![])]);
+        assert_eq!(spans.find(105).map(|span| span.name.as_str()), Some("EF"));
+        assert_eq!(SpanRecorder::absolute(4), 4);
+    }
+
+    #[test]
+    fn test_recorder_is_restored_after_panic() {
+        let panicked = std::panic::catch_unwind(|| {
+            SpanRecorder::run(true, false, || {
+                let section = SpanRecorder::section("EF".to_owned(), 100, 10);
+                std::mem::forget(section);
+                panic!("parse panicked")
+            })
+        });
+        assert!(panicked.is_err());
+        assert!(!SpanRecorder::is_enabled());
+        assert_eq!(SpanRecorder::absolute(4), 4);
+    }
+}
@@ -1,13 +1,23 @@
 mod bcd_string;
+mod byte_span;
 mod export;
 mod hex;
+mod parse_options;
 mod readable;
 pub(crate) mod string_encoding;
 mod writable;
 
 pub use bcd_string::BCDString;
+pub use byte_span::ByteSpan;
+pub(crate) use byte_span::{SpanRecorder, SpanSection, short_type_name};
 pub use export::Export;
 pub use hex::{HexDisplay, HexHelper};
+pub(crate) use parse_options::ParseContext;
+pub use parse_options::{Diagnostic, ParseMode, ParseOptions, Severity};
 pub use readable::{Readable, ReadableWithParams};
+pub(crate) use readable::{ReadableField, ReadableWithParamsField};
 pub use string_encoding::*;
-pub use writable::Writable;
+pub use writable::{Writable, WritableWithParams};
+pub(crate) use writable::{
+    field_value, record_count, write_bcd_string, write_ia5_string, write_octet_string, write_string, write_to_vec,
+};
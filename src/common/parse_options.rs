@@ -0,0 +1,193 @@
+use std::{cell::RefCell, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Error, Result,
+    tacho::{TrepHandler, TrepHandlers},
+};
+
+/// Defines how the parser reacts on corrupted data.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum ParseMode {
+    /// The first error aborts the whole parse.
+    #[default]
+    Strict,
+    /// Errors are collected as diagnostics and parsing continues with the next
+    /// record, elementary file or TREP.
+    Lenient,
+}
+
+/// Options which control the parsing of a DDD file.
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    pub mode: ParseMode,
+    /// Parsers for the TREPs which are not known to the parser.
+    pub trep_handlers: TrepHandlers,
+    /// Records the byte spans of the parsed structures, returned in `ParseOutcome::spans`.
+    pub record_spans: bool,
+}
+
+impl ParseOptions {
+    pub fn new(mode: ParseMode) -> Self {
+        Self { mode, trep_handlers: TrepHandlers::default(), record_spans: false }
+    }
+
+    /// Options for the strict parse mode, the default behaviour of the parser.
+    pub fn strict() -> Self {
+        Self::new(ParseMode::Strict)
+    }
+
+    /// Options for the lenient parse mode, returns the best-effort data plus diagnostics.
+    pub fn lenient() -> Self {
+        Self::new(ParseMode::Lenient)
+    }
+
+    /// Registers the handler for a TREP id which is not known to the parser.
+    /// Without a handler such TREP is kept as a `RawTrepBlock`.
+    pub fn with_trep_handler(mut self, trep: u8, handler: impl TrepHandler + 'static) -> Self {
+        self.trep_handlers.register(trep, handler);
+        self
+    }
+
+    /// Records the byte span of every parsed structure.
+    pub fn with_spans(mut self) -> Self {
+        self.record_spans = true;
+        self
+    }
+}
+
+/// Severity of a `Diagnostic`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum Severity {
+    /// Data was skipped, but nothing was lost.
+    Warning,
+    /// Data could not be parsed and is missing from the result.
+    Error,
+}
+
+/// A problem found in the data while parsing in lenient mode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Byte offset of the problem in the parsed data, if known.
+    pub offset: Option<usize>,
+    /// Card file (EF) or TREP in which the problem was found.
+    pub section: Option<String>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, offset: Option<usize>, section: Option<String>, message: String) -> Self {
+        Self { severity, offset, section, message }
+    }
+
+    /// Creates the diagnostic with the location taken from the error context.
+    pub fn from_error(severity: Severity, error: &Error) -> Self {
+        let context = error.context().cloned().unwrap_or_default();
+        Self::new(severity, context.offset, context.section, error.to_string())
+    }
+}
+
+struct ParseContextData {
+    mode: ParseMode,
+    trep_handlers: TrepHandlers,
+    diagnostics: Vec<Diagnostic>,
+}
+
+thread_local! {
+    static PARSE_CONTEXT: RefCell<Option<ParseContextData>> = const { RefCell::new(None) };
+}
+
+/// Restores the parse context which was installed before a parse when it is dropped,
+/// also when the parse panics.
+struct ParseContextGuard {
+    previous: Option<Option<ParseContextData>>,
+}
+
+impl ParseContextGuard {
+    /// Restores the previous context and returns the context of the finished parse.
+    fn finish(mut self) -> Option<ParseContextData> {
+        let previous = self.previous.take().flatten();
+        PARSE_CONTEXT.with(|ctx| ctx.replace(previous))
+    }
+}
+
+impl Drop for ParseContextGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            PARSE_CONTEXT.with(|ctx| ctx.replace(previous));
+        }
+    }
+}
+
+/// Parse state shared by all readers of the current thread while a parse
+/// started with `ParseOptions` is running.
+pub(crate) struct ParseContext;
+
+impl ParseContext {
+    /// Runs `parse` with the given options and returns its result together with
+    /// the collected diagnostics.
+    pub(crate) fn run<T>(options: &ParseOptions, parse: impl FnOnce() -> Result<T>) -> (Result<T>, Vec<Diagnostic>) {
+        let context = ParseContextData {
+            mode: options.mode.clone(),
+            trep_handlers: options.trep_handlers.clone(),
+            diagnostics: Vec::new(),
+        };
+        let guard = ParseContextGuard { previous: Some(PARSE_CONTEXT.with(|ctx| ctx.replace(Some(context)))) };
+        let result = parse();
+        let context = guard.finish();
+        (result, context.map(|ctx| ctx.diagnostics).unwrap_or_default())
+    }
+
+    pub(crate) fn is_lenient() -> bool {
+        PARSE_CONTEXT.with(|ctx| ctx.borrow().as_ref().is_some_and(|ctx| ctx.mode == ParseMode::Lenient))
+    }
+
+    /// Returns the registered handler for the TREP id.
+    pub(crate) fn trep_handler(trep: u8) -> Option<Arc<dyn TrepHandler>> {
+        PARSE_CONTEXT.with(|ctx| ctx.borrow().as_ref().and_then(|ctx| ctx.trep_handlers.get(trep)))
+    }
+
+    /// Adds the diagnostic to the running parse, it is dropped when no parse with options is running.
+    pub(crate) fn report(diagnostic: Diagnostic) {
+        PARSE_CONTEXT.with(|ctx| {
+            if let Some(ctx) = ctx.borrow_mut().as_mut() {
+                ctx.diagnostics.push(diagnostic);
+            }
+        })
+    }
+
+    /// Returns the error back in strict mode. In lenient mode the error is
+    /// recorded as a diagnostic and the caller continues with the next item.
+    pub(crate) fn recover(error: Error) -> Result<()> {
+        if !ParseContext::is_lenient() {
+            return Err(error);
+        }
+        ParseContext::report(Diagnostic::from_error(Severity::Error, &error));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_is_restored_after_panic() {
+        let panicked = std::panic::catch_unwind(|| {
+            ParseContext::run(&ParseOptions::lenient(), || -> Result<()> {
+                ParseContext::report(Diagnostic::new(Severity::Warning, None, None, "skipped".to_owned()));
+                panic!("parse panicked")
+            })
+        });
+        assert!(panicked.is_err());
+        assert!(!ParseContext::is_lenient());
+
+        let (result, diagnostics) = ParseContext::run(&ParseOptions::strict(), || Ok(ParseContext::is_lenient()));
+        assert!(!result.unwrap());
+        assert!(diagnostics.is_empty());
+    }
+}
@@ -1,6 +1,8 @@
 use binary_data::{BinSeek, ReadBytes};
+use serde::Serialize;
 
-use crate::Result;
+use super::short_type_name;
+use crate::{Result, ResultExt, SpanRecorder};
 
 /// A trait for types that can be deserialized from a binary stream.
 /// The trait are designed to work with any reader that implements `binary_data::ReadBytes`
@@ -74,3 +76,34 @@ pub trait ReadableWithParams<T> {
     /// A `Result` containing the deserialized type `T` or an `Error` if reading fails.
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<T>;
 }
+
+/// Reads a `Readable` type as a field of the structure which is being read. The byte span
+/// of the field is recorded when the spans are requested with `ParseOptions::with_spans`.
+pub(crate) trait ReadableField<T>: Readable<T> {
+    fn read_field<R: ReadBytes + BinSeek>(reader: &mut R, field: &str) -> Result<T>
+    where
+        T: Serialize,
+    {
+        if !SpanRecorder::is_enabled() {
+            return Self::read(reader).in_path(field);
+        }
+        SpanRecorder::field(field, &short_type_name::<T>(), reader, |reader| Self::read(reader))
+    }
+}
+
+impl<T, U: Readable<T> + ?Sized> ReadableField<T> for U {}
+
+/// Reads a `ReadableWithParams` type as a field of the structure which is being read, like `ReadableField`.
+pub(crate) trait ReadableWithParamsField<T>: ReadableWithParams<T> {
+    fn read_field_with<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P, field: &str) -> Result<T>
+    where
+        T: Serialize,
+    {
+        if !SpanRecorder::is_enabled() {
+            return Self::read(reader, params).in_path(field);
+        }
+        SpanRecorder::field(field, &short_type_name::<T>(), reader, |reader| Self::read(reader, params))
+    }
+}
+
+impl<T, U: ReadableWithParams<T> + ?Sized> ReadableWithParamsField<T> for U {}
@@ -1,5 +1,7 @@
-use crate::Result;
-use binary_data::{BinSeek, WriteBytes};
+use std::fmt;
+
+use crate::{BCDString, CodePage, Error, Result, ia5_fix_string_to_bytes, string_to_bytes};
+use binary_data::{BinMemoryBuffer, BinSeek, WriteBytes};
 
 /// A trait for types that can be serialized and written to a binary stream.
 /// The `Writable` trait provides a standardized way to write data structures to a
@@ -52,3 +54,67 @@ pub trait Writable {
     /// on success or an `Error` if writing fails.
     fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()>;
 }
+
+/// A trait for types that need the same parameters to be written as to be read
+/// with `ReadableWithParams`, e.g. the number of records of a card file.
+pub trait WritableWithParams {
+    type P;
+
+    /// Writes the binary representation of `self` to the given writer.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W, params: &Self::P) -> Result<()>;
+}
+
+/// Returns the bytes written by `write` to a memory buffer.
+pub(crate) fn write_to_vec(write: impl FnOnce(&mut BinMemoryBuffer) -> Result<()>) -> Result<Vec<u8>> {
+    let mut writer = BinMemoryBuffer::new();
+    write(&mut writer)?;
+    Ok(writer.as_slice().to_vec())
+}
+
+/// Returns the number of records `len` as the type of a record count, e.g. `u8` or `u16`.
+pub(crate) fn record_count<T: TryFrom<usize>>(len: usize) -> Result<T> {
+    T::try_from(len).map_err(|_| Error::InvalidDataEncode(format!("{len} records do not fit in the record count")))
+}
+
+/// Returns the value of the field `name` as the type it is written with, e.g. `u8` or `u16`.
+pub(crate) fn field_value<T: TryFrom<V>, V: Copy + fmt::Display>(name: &str, value: V) -> Result<T> {
+    T::try_from(value).map_err(|_| Error::InvalidDataEncode(format!("{name} {value} does not fit in its field")))
+}
+
+/// Writes `value` encoded with `code_page` as a fixed length string of `len` bytes.
+/// The parsed strings are trimmed, so the string is padded with spaces, a longer
+/// string is an error.
+pub(crate) fn write_string<W: WriteBytes>(writer: &mut W, value: &str, code_page: &CodePage, len: usize) -> Result<()> {
+    write_padded(writer, string_to_bytes(value, code_page), b' ', len)
+}
+
+/// Writes `value` as a fixed length IA5 string of `len` bytes, padded with spaces.
+pub(crate) fn write_ia5_string<W: WriteBytes>(writer: &mut W, value: &str, len: usize) -> Result<()> {
+    write_padded(writer, ia5_fix_string_to_bytes(value)?, b' ', len)
+}
+
+/// Writes the decimal digits of `value` as a BCD string of `len` bytes, the
+/// missing leading digits are written as zeros.
+pub(crate) fn write_bcd_string<W: WriteBytes>(writer: &mut W, value: &str, len: usize) -> Result<()> {
+    let bytes = BCDString::encode(value)?;
+    if bytes.len() > len {
+        return Err(Error::InvalidDataEncode(format!("BCDString {value} is longer than {len} bytes")));
+    }
+    writer.write_all(&vec![0; len - bytes.len()])?;
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+/// Writes `bytes` as an octet string of `len` bytes, padded with zeros.
+pub(crate) fn write_octet_string<W: WriteBytes>(writer: &mut W, bytes: &[u8], len: usize) -> Result<()> {
+    write_padded(writer, bytes.to_vec(), 0, len)
+}
+
+fn write_padded<W: WriteBytes>(writer: &mut W, mut bytes: Vec<u8>, padding: u8, len: usize) -> Result<()> {
+    if bytes.len() > len {
+        return Err(Error::InvalidDataEncode(format!("{} bytes do not fit in a field of {len} bytes", bytes.len())));
+    }
+    bytes.resize(len, padding);
+    writer.write_all(&bytes)?;
+    Ok(())
+}
@@ -0,0 +1,226 @@
+use std::{cmp::Reverse, fmt, mem};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{ByteSpan, Diagnostic, Export, HexDisplay, ParseContext, ParseOptions, Result, SpanRecorder};
+
+/// Number of bytes shown in the hex column of the text output.
+const TEXT_HEX_BYTES: usize = 16;
+/// Type name of the byte ranges which were not read as a field, e.g. headers and padding.
+const RAW_TYPE_NAME: &str = "raw";
+
+/// A byte range of the DDD data labelled with the structure parsed from it.
+#[derive(Debug, Clone, Serialize)]
+pub struct DissectionNode {
+    /// Name of the field in the parent structure, missing for the groups and the bytes
+    /// which were not read as a field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    /// Data type of the range (e.g. `TimeReal`), card file (EF) or TREP for the groups.
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub offset: usize,
+    pub len: usize,
+    pub hex: String,
+    /// Decoded value, only for the ranges without children.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<DissectionNode>,
+}
+
+impl DissectionNode {
+    fn from_span(span: ByteSpan) -> Self {
+        let children = span.children.into_iter().map(DissectionNode::from_span).collect();
+        Self {
+            field: span.field,
+            type_name: span.name,
+            offset: span.offset,
+            len: span.len,
+            hex: String::new(),
+            value: span.value,
+            children,
+        }
+    }
+
+    fn raw(offset: usize, len: usize) -> Self {
+        Self {
+            field: None,
+            type_name: RAW_TYPE_NAME.to_owned(),
+            offset,
+            len,
+            hex: String::new(),
+            value: None,
+            children: Vec::new(),
+        }
+    }
+
+    fn end(&self) -> usize {
+        self.offset + self.len
+    }
+
+    fn contains(&self, other: &DissectionNode) -> bool {
+        self.offset <= other.offset && other.end() <= self.end()
+    }
+
+    /// Labels the bytes between the children, sets the hex and keeps the values only on the leaves.
+    /// `parent` is the range of the parent, the records of a cyclic buffer continue at its start.
+    fn finish(&mut self, data: &[u8], parent: (usize, usize)) {
+        self.hex = range_hex(data, self.offset, self.len, parent);
+        if self.children.is_empty() {
+            return;
+        }
+
+        let end = self.end();
+        let mut position = self.offset + self.children.iter().map(|child| child.end().saturating_sub(end)).max().unwrap_or(0);
+        let mut children: Vec<DissectionNode> = Vec::with_capacity(self.children.len());
+        for child in mem::take(&mut self.children) {
+            if child.offset > position {
+                children.push(DissectionNode::raw(position, child.offset - position));
+            }
+            position = position.max(child.end().min(end));
+            children.push(child);
+        }
+        if position < end {
+            children.push(DissectionNode::raw(position, end - position));
+        }
+
+        for child in children.iter_mut() {
+            child.finish(data, (self.offset, self.len));
+        }
+        self.children = children;
+        self.value = None;
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(f, "{:08X} {:>6}  {:indent$}", self.offset, self.len, "", indent = depth * 2)?;
+        match &self.field {
+            Some(field) => write!(f, "{field}: {}", self.type_name)?,
+            None => write!(f, "{}", self.type_name)?,
+        }
+        if self.children.is_empty() {
+            let hex_len = self.hex.len().min(TEXT_HEX_BYTES * 2);
+            let more = if self.hex.len() > hex_len { "..." } else { "" };
+            write!(f, "  [{}{more}]", &self.hex[..hex_len])?;
+            if let Some(value) = &self.value {
+                write!(f, " = {value}")?;
+            }
+        }
+        writeln!(f)?;
+        self.children.iter().try_for_each(|child| child.write_text(f, depth + 1))
+    }
+}
+
+/// Wireshark like dissection of a DDD file, every byte range is labelled with
+/// the field and type parsed from it, grouped by TREP or card file (EF).
+#[derive(Debug, Serialize)]
+pub struct Dissection {
+    pub len: usize,
+    pub nodes: Vec<DissectionNode>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Dissection {
+    fn new(data: &[u8], spans: Option<ByteSpan>, diagnostics: Vec<Diagnostic>) -> Self {
+        let mut root = DissectionNode::from_span(spans.unwrap_or_else(|| ByteSpan::new(String::new(), 0, 0, vec![])));
+        root.len = data.len();
+        root.children = nest(root.children);
+        root.finish(data, (0, data.len()));
+        Self { len: data.len(), nodes: root.children, diagnostics }
+    }
+
+    /// Returns the innermost node which contains the absolute offset.
+    pub fn find(&self, offset: usize) -> Option<&DissectionNode> {
+        let mut found = None;
+        let mut nodes = &self.nodes;
+        while let Some(node) = nodes.iter().find(|node| node.offset <= offset && offset < node.end()) {
+            found = Some(node);
+            nodes = &node.children;
+        }
+        found
+    }
+}
+
+impl Export for Dissection {}
+
+impl fmt::Display for Dissection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.nodes.iter().try_for_each(|node| node.write_text(f, 0))?;
+        for diagnostic in &self.diagnostics {
+            writeln!(f, "{:?} at {:?}: {}", diagnostic.severity, diagnostic.offset, diagnostic.message)?;
+        }
+        Ok(())
+    }
+}
+
+/// Moves the nodes which lie inside another node (e.g. the card file data inside the card file)
+/// into that node, the nodes are sorted by the offset. A range parsed twice as the same type
+/// is merged, as different types the nodes stay side by side.
+fn nest(mut nodes: Vec<DissectionNode>) -> Vec<DissectionNode> {
+    nodes.sort_by_key(|node| (node.offset, Reverse(node.len)));
+    let mut nested: Vec<DissectionNode> = Vec::with_capacity(nodes.len());
+    for node in nodes {
+        match nested.last_mut() {
+            Some(last) if last.offset == node.offset && last.len == node.len => {
+                if last.type_name == node.type_name {
+                    last.children.extend(node.children);
+                    last.field = last.field.take().or(node.field);
+                    last.value = last.value.take().or(node.value);
+                } else {
+                    nested.push(node);
+                }
+            }
+            Some(last) if last.contains(&node) => last.children.push(node),
+            _ => nested.push(node),
+        }
+    }
+    for node in nested.iter_mut() {
+        node.children = nest(mem::take(&mut node.children));
+    }
+    nested
+}
+
+/// Returns the hex of the range, the part which passes the end of the parent continues at its start.
+fn range_hex(data: &[u8], offset: usize, len: usize, parent: (usize, usize)) -> String {
+    let (parent_offset, parent_len) = parent;
+    let parent_end = (parent_offset + parent_len).min(data.len());
+    let start = offset.min(parent_end);
+    let end = (offset + len).min(parent_end);
+    let mut bytes = data[start..end].to_vec();
+    let wrapped = (offset + len).saturating_sub(parent_end).min(parent_end - parent_offset.min(parent_end));
+    bytes.extend_from_slice(&data[parent_offset.min(parent_end)..parent_offset.min(parent_end) + wrapped]);
+    bytes.to_hex_string()
+}
+
+/// Parses the DDD data with the byte spans and values of all structures and returns its dissection.
+///
+/// # Arguments
+///
+/// * `esm_data` - The binary data from DDD file.
+/// * `options` - The parse options, `ParseMode::Lenient` dissects the data which could be parsed.
+///
+/// # Returns
+///
+/// A `Result` containing the `Dissection` or an `Error` if the data could not be parsed at all.
+pub fn dissect_from_memory(esm_data: &[u8], options: &ParseOptions) -> Result<Dissection> {
+    let ((result, diagnostics), spans) =
+        SpanRecorder::run(true, true, || ParseContext::run(options, || crate::parse_from_memory(esm_data)));
+    result?;
+    Ok(Dissection::new(esm_data, spans, diagnostics))
+}
+
+/// Dissects a DDD file like `dissect_from_memory`.
+///
+/// # Arguments
+///
+/// * `esm_file_path` - The path to the DDD file.
+/// * `options` - The parse options, `ParseMode::Lenient` dissects the data which could be parsed.
+///
+/// # Returns
+///
+/// A `Result` containing the `Dissection` or an `Error` if the data could not be parsed at all.
+pub fn dissect_from_file(esm_file_path: &str, options: &ParseOptions) -> Result<Dissection> {
+    let esm_data = std::fs::read(esm_file_path)?;
+    dissect_from_memory(&esm_data, options)
+}
@@ -44,15 +44,155 @@ pub enum Error {
     CardActivityDailyRecord(String),
     /// An error occurred when we try to export data with serde.
     Export(String),
+    /// An error occurred when we try to import exported data with serde.
+    Import(String),
     /// Input data into function are empty.
     EmptyInputData(String),
     /// Errors in process of verification
     VerifyError(String),
+    /// An error with the location in the data where it happened.
+    Context {
+        /// The location of the error.
+        context: ErrorContext,
+        /// The underlying error.
+        source: Box<Error>,
+    },
+}
+
+/// Location in the parsed data where an error happened.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ErrorContext {
+    /// Reader position (byte offset) at which the error was detected, if known.
+    pub offset: Option<usize>,
+    /// TREP id or `CardFileID` of the section in which the error was detected.
+    pub section: Option<String>,
+    /// Breadcrumb path of the structures being read, outermost first.
+    pub path: Vec<String>,
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts: Vec<String> = Vec::new();
+        if let Some(offset) = self.offset {
+            parts.push(format!("offset {offset} (0x{offset:X})"));
+        }
+        if let Some(section) = &self.section {
+            parts.push(format!("section {section}"));
+        }
+        if !self.path.is_empty() {
+            parts.push(format!("path {}", self.path.join(" > ")));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+impl Error {
+    /// Returns the location of the error, if it is known.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Error::Context { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Returns the underlying error without the location.
+    pub fn kind(&self) -> &Error {
+        match self {
+            Error::Context { source, .. } => source.kind(),
+            _ => self,
+        }
+    }
+
+    fn into_context(self) -> (ErrorContext, Box<Error>) {
+        match self {
+            Error::Context { context, source } => (context, source),
+            error => (ErrorContext::default(), Box::new(error)),
+        }
+    }
+
+    /// Sets the reader position, the innermost (first) position is kept. Readers over a part of the
+    /// data (card file, TREP data) pass the position converted with `SpanRecorder::absolute`.
+    pub(crate) fn at_offset(self, offset: usize) -> Self {
+        let (mut context, source) = self.into_context();
+        context.offset.get_or_insert(offset);
+        Error::Context { context, source }
+    }
+
+    /// Moves the offset by `base`, used when the data was parsed from a copy of a part of the file.
+    pub(crate) fn offset_by(self, base: usize) -> Self {
+        match self {
+            Error::Context { mut context, source } => {
+                context.offset = context.offset.map(|offset| offset + base);
+                Error::Context { context, source }
+            }
+            error => error,
+        }
+    }
+
+    /// Sets the TREP or card file section, the innermost (first) section is kept.
+    pub(crate) fn in_section(self, section: String) -> Self {
+        let (mut context, source) = self.into_context();
+        context.section.get_or_insert(section);
+        Error::Context { context, source }
+    }
+
+    /// Prepends a segment to the breadcrumb path, errors are annotated from the inside out.
+    pub(crate) fn in_path(self, segment: String) -> Self {
+        let (mut context, source) = self.into_context();
+        context.path.insert(0, segment);
+        Error::Context { context, source }
+    }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{self:?}")
+        match self {
+            Error::File(error) => write!(f, "I/O error: {error}"),
+            Error::Binary(error) => write!(f, "binary data error: {error}"),
+            Error::InvalidHeaderLength => write!(f, "invalid header length"),
+            Error::InvalidHeaderData => write!(f, "invalid header data"),
+            Error::InvalidDataGeneration => write!(f, "invalid data generation"),
+            Error::InvalidDataParse(message) => write!(f, "invalid data: {message}"),
+            Error::InvalidDataEncode(message) => write!(f, "data encoding failed: {message}"),
+            Error::DuplicateCardFile => write!(f, "duplicate card file"),
+            Error::SignatureBeforeCardFile => write!(f, "signature found before card file"),
+            Error::PartialCardFile => write!(f, "partial card file"),
+            Error::UnsupportedCardType => write!(f, "unsupported card type"),
+            Error::UnknownCardType => write!(f, "unknown card type"),
+            Error::NotImplemented => write!(f, "not implemented"),
+            Error::MissingCardFile(card_file) => write!(f, "missing card file: {card_file}"),
+            Error::NotSupportedCardType(card_type) => write!(f, "not supported card type: {card_type}"),
+            Error::CorruptedDrivingLicenceNumber => write!(f, "corrupted driving licence number"),
+            Error::UnknownCardTypeDecoding => write!(f, "unknown card type decoding"),
+            Error::RecordOutOfRange(record) => write!(f, "record out of range: {record}"),
+            Error::CardActivityDailyRecord(message) => write!(f, "card activity daily record: {message}"),
+            Error::Export(message) => write!(f, "export failed: {message}"),
+            Error::Import(message) => write!(f, "import failed: {message}"),
+            Error::EmptyInputData(message) => write!(f, "empty input data: {message}"),
+            Error::VerifyError(message) => write!(f, "verification failed: {message}"),
+            Error::Context { context, source } => write!(f, "{source} at {context}"),
+        }
+    }
+}
+
+/// Adds the location of an error to a `Result`.
+pub(crate) trait ResultExt<T> {
+    fn at_offset(self, offset: usize) -> Result<T>;
+    fn in_section(self, section: &str) -> Result<T>;
+    fn in_path(self, segment: &str) -> Result<T>;
+}
+
+impl<T> ResultExt<T> for Result<T> {
+    fn at_offset(self, offset: usize) -> Result<T> {
+        self.map_err(|error| error.at_offset(offset))
+    }
+
+    fn in_section(self, section: &str) -> Result<T> {
+        self.map_err(|error| error.in_section(section.to_owned()))
+    }
+
+    fn in_path(self, segment: &str) -> Result<T> {
+        self.map_err(|error| error.in_path(segment.to_owned()))
     }
 }
 
@@ -104,7 +244,17 @@ impl From<quick_xml::se::SeError> for Error {
     }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::File(error) => Some(error),
+            Error::Binary(error) => Some(error),
+            // The message of the underlying error is part of the context's message.
+            Error::Context { source, .. } => source.source(),
+            _ => None,
+        }
+    }
+}
 
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -118,3 +268,49 @@ mod wasm_support {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as _;
+
+    #[test]
+    fn test_context_keeps_innermost_location() {
+        let result: Result<()> = Err(Error::InvalidDataParse("Non-digit in BCD string.".to_owned()));
+        let error = result
+            .in_path("previousVehicleInfo")
+            .at_offset(42)
+            .in_path("VuCardIWRecord[3]")
+            .at_offset(7)
+            .in_section("Activities");
+
+        let error = error.unwrap_err();
+        let context = error.context().unwrap();
+        assert_eq!(context.offset, Some(42));
+        assert_eq!(context.section.as_deref(), Some("Activities"));
+        assert_eq!(context.path, vec!["VuCardIWRecord[3]", "previousVehicleInfo"]);
+        assert!(matches!(error.kind(), Error::InvalidDataParse(_)));
+        assert!(error.source().is_none());
+        assert_eq!(
+            error.to_string(),
+            "invalid data: Non-digit in BCD string. at offset 42 (0x2A), section Activities, path VuCardIWRecord[3] > previousVehicleInfo"
+        );
+    }
+
+    #[test]
+    fn test_context_source_skips_the_located_error() {
+        let error = Error::File(std::io::Error::from(std::io::ErrorKind::UnexpectedEof)).at_offset(3);
+
+        // Each message of the chain is printed once
+        let source = error.source().unwrap();
+        assert!(source.downcast_ref::<std::io::Error>().is_some());
+        assert_eq!(error.to_string(), format!("I/O error: {source} at offset 3 (0x3)"));
+    }
+
+    #[test]
+    fn test_offset_by() {
+        let error = Error::PartialCardFile.at_offset(10).offset_by(100);
+        assert_eq!(error.context().unwrap().offset, Some(110));
+        assert!(Error::PartialCardFile.offset_by(100).context().is_none());
+    }
+}
@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    Error, Export, HexDisplay, Result, TachographData,
+    tacho::{TachographDataGeneration, TachographDataType},
+};
+
+/// Version of the structure of the exported data, it is increased with each release
+/// which changes the structure, e.g. renames or removes a field.
+pub const EXPORT_SCHEMA_VERSION: u32 = 1;
+
+const SCHEMA_VERSION_FIELD: &str = "schemaVersion";
+
+/// # Versioned Export of Tachograph Data
+/// The parsed data of a DDD file with the version of its structure and the origin of
+/// the data.
+///
+/// `TachographData` is exported without a tag of its variant, the envelope states the
+/// data type and the generation, so a consumer reads the data without inspecting its
+/// fields. The `schemaVersion` identifies the structure of the data, the JSON Schema of
+/// the envelope is generated with `export_json_schema` when the `schemars` feature is
+/// enabled.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ExportEnvelope {
+    /// Version of the structure of `data`, `EXPORT_SCHEMA_VERSION` when it was exported.
+    #[serde(rename = "schemaVersion")]
+    pub schema_version: u32,
+    #[serde(rename = "dataType")]
+    pub data_type: TachographDataType,
+    pub generation: TachographDataGeneration,
+    /// Version of the parser which parsed the data.
+    #[serde(rename = "parserVersion")]
+    pub parser_version: String,
+    /// Lowercase hexadecimal SHA-256 hash of the parsed DDD file.
+    #[serde(rename = "sourceSha256")]
+    pub source_sha256: String,
+    pub data: TachographData,
+}
+
+impl ExportEnvelope {
+    /// Creates the envelope of the data parsed from `source_data`, the content of the DDD file.
+    pub fn new(data: TachographData, source_data: &[u8]) -> Self {
+        let header = data.get_header();
+        Self {
+            schema_version: EXPORT_SCHEMA_VERSION,
+            data_type: header.data_type.clone(),
+            generation: header.generation.clone(),
+            parser_version: env!("CARGO_PKG_VERSION").to_owned(),
+            source_sha256: Sha256::digest(source_data).as_slice().to_hex().to_lower_hex_string(),
+            data,
+        }
+    }
+
+    /// Reads the envelope exported with `Export::to_json`, the envelope of another schema
+    /// version is not read.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let envelope: ExportEnvelope = serde_json::from_str(json).map_err(|error| Error::Import(error.to_string()))?;
+        if envelope.schema_version != EXPORT_SCHEMA_VERSION {
+            return Err(Error::Import(format!(
+                "schema version {} is not supported, the supported version is {EXPORT_SCHEMA_VERSION}",
+                envelope.schema_version
+            )));
+        }
+        Ok(envelope)
+    }
+}
+
+impl Export for ExportEnvelope {}
+
+/// Reads the tachograph data from JSON exported with `Export::to_json`, either the data
+/// itself or its `ExportEnvelope`.
+pub(crate) fn tachograph_data_from_json(json: &str) -> Result<TachographData> {
+    let value: Value = serde_json::from_str(json).map_err(|error| Error::Import(error.to_string()))?;
+    if value.get(SCHEMA_VERSION_FIELD).is_some() {
+        return Ok(ExportEnvelope::from_json(json)?.data);
+    }
+    serde_json::from_value(value).map_err(|error| Error::Import(error.to_string()))
+}
+
+/// Returns the JSON Schema of `ExportEnvelope`, the structure of the exported data of
+/// the current `EXPORT_SCHEMA_VERSION`.
+#[cfg(feature = "schemars")]
+pub fn export_json_schema() -> schemars::Schema {
+    schemars::schema_for!(ExportEnvelope)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_from_memory;
+    use crate::tacho::{CardGeneration, EquipmentType};
+
+    #[test]
+    fn test_export_envelope_round_trip() {
+        let source_data = crate::fixtures::CardDddBuilder::new(EquipmentType::CompanyCard, CardGeneration::Gen2)
+            .build()
+            .expect("Card fixture should be built");
+        let envelope = ExportEnvelope::new(parse_from_memory(&source_data).unwrap(), &source_data);
+        assert!(matches!(envelope.data_type, TachographDataType::Card));
+        assert!(matches!(envelope.generation, TachographDataGeneration::SecondGeneration));
+        assert_eq!(envelope.source_sha256, Sha256::digest(&source_data).as_slice().to_hex().to_lower_hex_string());
+
+        let json = envelope.to_json().unwrap();
+        assert!(json.starts_with(r#"{"schemaVersion":1,"dataType":"Card","generation":"SecondGeneration","parserVersion":"#));
+        let read = ExportEnvelope::from_json(&json).unwrap();
+        assert!(matches!(read.data, TachographData::CardGen2(_)));
+        assert!(matches!(tachograph_data_from_json(&json), Ok(TachographData::CardGen2(_))));
+
+        let newer = json.replacen(r#""schemaVersion":1"#, r#""schemaVersion":2"#, 1);
+        assert!(matches!(ExportEnvelope::from_json(&newer), Err(Error::Import(_))));
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_export_json_schema() {
+        let schema = serde_json::to_string(&export_json_schema()).unwrap();
+        for field in
+            ["schemaVersion", "dataType", "generation", "parserVersion", "sourceSha256", "memberStateCertificateRecordArray"]
+        {
+            assert!(schema.contains(&format!("\"{field}\"")), "{field} should be in the schema");
+        }
+        assert!(!schema.contains("nemberStateCertificateRecordArray"));
+    }
+}
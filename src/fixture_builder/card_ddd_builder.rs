@@ -0,0 +1,548 @@
+use binary_data::{BigEndian, BinSeek, WriteBytes};
+
+use crate::{
+    CodePage, Error, Result, Writable,
+    fixture_builder::{ActivityDay, EventRecord},
+    record_count,
+    tacho::{CardFileID, CardGeneration, EquipmentType, EventFaultType, Name},
+    write_ia5_string, write_to_vec,
+};
+
+const CARD_NUMBER_LENGTH: usize = 16;
+const CARD_ICC_IDENTIFICATION_LENGTH: usize = 25;
+const CARD_CHIP_IDENTIFICATION_LENGTH: usize = 8;
+const CARD_IDENTIFICATION_LENGTH: usize = 65;
+const NAME_LENGTH: usize = 36;
+const DATE_F_LENGTH: usize = 4;
+const LANGUAGE_LENGTH: usize = 2;
+const CARD_EVENT_RECORD_LENGTH: usize = 24;
+const CARD_ACTIVITY_DAILY_RECORD_HEADER_LENGTH: usize = 12;
+const GEN1_CERTIFICATE_LENGTH: usize = 194;
+const GEN2_CERTIFICATE_LENGTH: usize = 204;
+const GEN1_SIGNATURE_LENGTH: usize = 128;
+const GEN2_SIGNATURE_LENGTH: usize = 64;
+const GEN1_EVENT_TYPES: usize = 6;
+const GEN2_EVENT_TYPES: usize = 11;
+const FAULT_TYPES: usize = 2;
+
+/// The number of records of the cyclic card files, written to the application
+/// identification of the card.
+#[derive(Debug, Clone)]
+pub struct CardCapacity {
+    pub no_events_per_type: u8,
+    pub no_faults_per_type: u8,
+    pub activity_structure_length: u16,
+    pub no_of_card_vehicle_records: u16,
+    pub no_of_card_place_records: u16,
+    pub no_of_calibration_records: u8,
+    pub no_of_gnssad_records: u16,
+    pub no_of_specific_condition_records: u16,
+    pub no_of_card_vehicle_unit_records: u16,
+    pub no_of_border_crossing_records: u16,
+    pub no_of_load_unload_records: u16,
+    pub no_of_load_type_entry_records: u16,
+    pub vu_configuration_length_range: u16,
+    /// Records of the controller activity data of a control card or of the company
+    /// activity data of a company card.
+    pub no_of_activity_records: u16,
+}
+
+impl CardCapacity {
+    /// Returns the capacity typical for cards of `card_type` and `generation` in use,
+    /// the values are in the ranges allowed by Annex 1C.
+    pub fn typical(card_type: &EquipmentType, generation: &CardGeneration) -> Self {
+        let vu_configuration_length_range = if *generation == CardGeneration::Gen2v2 { 3072 } else { 0 };
+        match card_type {
+            EquipmentType::WorkshopCard => Self {
+                no_events_per_type: 3,
+                no_faults_per_type: 6,
+                activity_structure_length: 198,
+                no_of_card_vehicle_records: 4,
+                no_of_card_place_records: 6,
+                no_of_calibration_records: 255,
+                no_of_gnssad_records: 18,
+                no_of_specific_condition_records: 2,
+                no_of_card_vehicle_unit_records: 4,
+                no_of_border_crossing_records: 4,
+                no_of_load_unload_records: 4,
+                no_of_load_type_entry_records: 4,
+                vu_configuration_length_range,
+                no_of_activity_records: 0,
+            },
+            EquipmentType::ControlCard | EquipmentType::CompanyCard => Self {
+                no_events_per_type: 0,
+                no_faults_per_type: 0,
+                activity_structure_length: 0,
+                no_of_card_vehicle_records: 0,
+                no_of_card_place_records: 0,
+                no_of_calibration_records: 0,
+                no_of_gnssad_records: 0,
+                no_of_specific_condition_records: 0,
+                no_of_card_vehicle_unit_records: 0,
+                no_of_border_crossing_records: 0,
+                no_of_load_unload_records: 0,
+                no_of_load_type_entry_records: 0,
+                vu_configuration_length_range,
+                no_of_activity_records: 520,
+            },
+            _ => Self {
+                no_events_per_type: 12,
+                no_faults_per_type: 24,
+                activity_structure_length: 13776,
+                no_of_card_vehicle_records: 200,
+                no_of_card_place_records: 112,
+                no_of_calibration_records: 0,
+                no_of_gnssad_records: 252,
+                no_of_specific_condition_records: 56,
+                no_of_card_vehicle_unit_records: 200,
+                no_of_border_crossing_records: 1100,
+                no_of_load_unload_records: 1100,
+                no_of_load_type_entry_records: 80,
+                vu_configuration_length_range,
+                no_of_activity_records: 0,
+            },
+        }
+    }
+}
+
+/// Builds the DDD file of a driver, workshop, control or company card of
+/// generation 1, generation 2 or generation 2 version 2.
+///
+/// The card files are structurally valid and sized by the [`CardCapacity`] of the
+/// card, the records not set on the builder are empty. The certificates and the
+/// signatures are filled with zeros, `Gen1TestPki::sign_card_ddd` and
+/// `Gen2TestPki::sign_card_ddd` sign the built files with a test PKI.
+#[derive(Debug)]
+pub struct CardDddBuilder {
+    card_type: EquipmentType,
+    generation: CardGeneration,
+    capacity: CardCapacity,
+    card_number: String,
+    holder_surname: String,
+    holder_first_names: String,
+    activity_days: Vec<ActivityDay>,
+    events: Vec<EventRecord>,
+    faults: Vec<EventRecord>,
+}
+
+impl CardDddBuilder {
+    /// Returns a builder of a card of `card_type` and `generation` with the typical
+    /// capacity. Only driver, workshop, control and company cards of a single
+    /// generation can be built.
+    pub fn new(card_type: EquipmentType, generation: CardGeneration) -> Self {
+        let capacity = CardCapacity::typical(&card_type, &generation);
+        Self {
+            card_type,
+            generation,
+            capacity,
+            card_number: "0000000000000100".to_owned(),
+            holder_surname: String::new(),
+            holder_first_names: String::new(),
+            activity_days: Vec::new(),
+            events: Vec::new(),
+            faults: Vec::new(),
+        }
+    }
+
+    pub fn driver_gen1() -> Self {
+        Self::new(EquipmentType::DriverCard, CardGeneration::Gen1)
+    }
+
+    pub fn driver_gen2() -> Self {
+        Self::new(EquipmentType::DriverCard, CardGeneration::Gen2)
+    }
+
+    pub fn driver_gen2v2() -> Self {
+        Self::new(EquipmentType::DriverCard, CardGeneration::Gen2v2)
+    }
+
+    pub fn with_capacity(mut self, capacity: CardCapacity) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Sets the card number, including the replacement and renewal indexes of driver
+    /// and workshop cards.
+    pub fn with_card_number(mut self, card_number: &str) -> Self {
+        self.card_number = card_number.to_owned();
+        self
+    }
+
+    /// Sets the name of the card holder, the surname is the company name of a company card.
+    pub fn with_holder_name(mut self, surname: &str, first_names: &str) -> Self {
+        self.holder_surname = surname.to_owned();
+        self.holder_first_names = first_names.to_owned();
+        self
+    }
+
+    /// Adds a day to the driver activity data, the days are stored in the order they
+    /// are added, oldest first.
+    pub fn with_activity_day(mut self, activity_day: ActivityDay) -> Self {
+        self.activity_days.push(activity_day);
+        self
+    }
+
+    /// Adds an event, the events are grouped by the event type in the order the
+    /// types are added.
+    pub fn with_event(mut self, event: EventRecord) -> Self {
+        self.events.push(event);
+        self
+    }
+
+    /// Adds a fault, the faults are grouped by the fault type in the order the
+    /// types are added.
+    pub fn with_fault(mut self, fault: EventRecord) -> Self {
+        self.faults.push(fault);
+        self
+    }
+
+    /// Returns the DDD file of the card.
+    pub fn build(&self) -> Result<Vec<u8>> {
+        let is_driver_or_workshop = matches!(self.card_type, EquipmentType::DriverCard | EquipmentType::WorkshopCard);
+        if !is_driver_or_workshop && !matches!(self.card_type, EquipmentType::ControlCard | EquipmentType::CompanyCard) {
+            return Err(Error::InvalidDataEncode(format!("DDD files of {} can not be built", self.card_type)));
+        }
+        if self.generation == CardGeneration::Combined {
+            return Err(Error::InvalidDataEncode("DDD files of combined cards can not be built".to_owned()));
+        }
+        let has_driver_records = !(self.activity_days.is_empty() && self.events.is_empty() && self.faults.is_empty());
+        if !is_driver_or_workshop && has_driver_records {
+            return Err(Error::InvalidDataEncode(format!("{} has no activities, events or faults", self.card_type)));
+        }
+
+        write_to_vec(|writer| {
+            self.write_unsigned(writer, CardFileID::ICC, &[0; CARD_ICC_IDENTIFICATION_LENGTH])?;
+            self.write_unsigned(writer, CardFileID::IC, &[0; CARD_CHIP_IDENTIFICATION_LENGTH])?;
+            self.write_signed(writer, CardFileID::ApplicationIdentification, &self.application_identification()?)?;
+            if self.generation == CardGeneration::Gen2v2 {
+                self.write_signed(writer, CardFileID::ApplicationIdentificationV2, &self.application_identification_v2())?;
+            }
+            self.write_certificates(writer)?;
+            self.write_signed(writer, CardFileID::Identification, &self.identification()?)?;
+            match self.card_type {
+                EquipmentType::ControlCard => {
+                    let controller_activity_data = self.cyclic_records(self.capacity.no_of_activity_records, 46);
+                    self.write_signed(writer, CardFileID::ControllerActivityData, &controller_activity_data)?;
+                }
+                EquipmentType::CompanyCard => {
+                    let company_activity_data = self.cyclic_records(self.capacity.no_of_activity_records, 46);
+                    self.write_signed(writer, CardFileID::CompanyActivityData, &company_activity_data)?;
+                }
+                _ => self.write_driver_or_workshop_files(writer)?,
+            }
+            if self.generation == CardGeneration::Gen2v2 && !is_driver_or_workshop {
+                let vu_configurations = vec![0; self.capacity.vu_configuration_length_range as usize];
+                self.write_signed(writer, CardFileID::VUConfiguration, &vu_configurations)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn is_gen1(&self) -> bool {
+        self.generation == CardGeneration::Gen1
+    }
+
+    fn is_workshop(&self) -> bool {
+        self.card_type == EquipmentType::WorkshopCard
+    }
+
+    /// Returns the appendix of the data card files, the appendix of a signature is the
+    /// following one.
+    fn appendix(&self) -> u8 {
+        if self.is_gen1() { 0 } else { 2 }
+    }
+
+    fn write_card_file<W: WriteBytes>(writer: &mut W, card_file_id: CardFileID, appendix: u8, data: &[u8]) -> Result<()> {
+        let size: u16 = data
+            .len()
+            .try_into()
+            .map_err(|_| Error::InvalidDataEncode(format!("card file {card_file_id} of {} bytes is too long", data.len())))?;
+        writer.write_u16::<BigEndian>(u16::from(&card_file_id))?;
+        writer.write_u8(appendix)?;
+        writer.write_u16::<BigEndian>(size)?;
+        writer.write_all(data)?;
+        Ok(())
+    }
+
+    fn write_unsigned<W: WriteBytes>(&self, writer: &mut W, card_file_id: CardFileID, data: &[u8]) -> Result<()> {
+        Self::write_card_file(writer, card_file_id, self.appendix(), data)
+    }
+
+    fn write_signed<W: WriteBytes>(&self, writer: &mut W, card_file_id: CardFileID, data: &[u8]) -> Result<()> {
+        let signature_length = if self.is_gen1() { GEN1_SIGNATURE_LENGTH } else { GEN2_SIGNATURE_LENGTH };
+        Self::write_card_file(writer, card_file_id.clone(), self.appendix(), data)?;
+        Self::write_card_file(writer, card_file_id, self.appendix() + 1, &vec![0; signature_length])
+    }
+
+    fn write_certificates<W: WriteBytes>(&self, writer: &mut W) -> Result<()> {
+        if self.is_gen1() {
+            self.write_unsigned(writer, CardFileID::CardCertificate, &[0; GEN1_CERTIFICATE_LENGTH])?;
+            return self.write_unsigned(writer, CardFileID::CACertificate, &[0; GEN1_CERTIFICATE_LENGTH]);
+        }
+        self.write_unsigned(writer, CardFileID::CardCertificate, &[0; GEN2_CERTIFICATE_LENGTH])?;
+        if matches!(self.card_type, EquipmentType::DriverCard | EquipmentType::WorkshopCard) {
+            self.write_unsigned(writer, CardFileID::CardSignCertificate, &[0; GEN2_CERTIFICATE_LENGTH])?;
+        }
+        self.write_unsigned(writer, CardFileID::CACertificate, &[0; GEN2_CERTIFICATE_LENGTH])
+    }
+
+    fn write_driver_or_workshop_files<W: WriteBytes>(&self, writer: &mut W) -> Result<()> {
+        let capacity = &self.capacity;
+        let places_pointer_length = if self.is_gen1() { 1 } else { 2 };
+        if self.is_workshop() {
+            // The number of calibrations since the last download.
+            self.write_signed(writer, CardFileID::CardDownload, &[0; 2])?;
+            let calibration_record_length = if self.is_gen1() { 105 } else { 122 };
+            let mut calibration = vec![0; 3];
+            calibration.resize(3 + capacity.no_of_calibration_records as usize * calibration_record_length, 0);
+            self.write_signed(writer, CardFileID::Calibration, &calibration)?;
+        } else {
+            // The time of the last card download.
+            self.write_signed(writer, CardFileID::CardDownload, &[0; 4])?;
+            self.write_signed(writer, CardFileID::DrivingLicenseInfo, &[0; 53])?;
+        }
+        let event_types = if self.is_gen1() { GEN1_EVENT_TYPES } else { GEN2_EVENT_TYPES };
+        self.write_signed(
+            writer,
+            CardFileID::EventsData,
+            &self.event_data(&self.events, event_types, capacity.no_events_per_type)?,
+        )?;
+        self.write_signed(
+            writer,
+            CardFileID::FaultsData,
+            &self.event_data(&self.faults, FAULT_TYPES, capacity.no_faults_per_type)?,
+        )?;
+        self.write_signed(writer, CardFileID::DriverActivityData, &self.driver_activity_data()?)?;
+        let vehicle_record_length = if self.is_gen1() { 31 } else { 48 };
+        self.write_signed(
+            writer,
+            CardFileID::VehiclesUsed,
+            &self.cyclic_records(capacity.no_of_card_vehicle_records, vehicle_record_length),
+        )?;
+        let place_record_length = if self.is_gen1() { 10 } else { 21 };
+        let places = vec![0; places_pointer_length + capacity.no_of_card_place_records as usize * place_record_length];
+        self.write_signed(writer, CardFileID::Places, &places)?;
+        self.write_signed(writer, CardFileID::CurrentUsage, &[0; 19])?;
+        self.write_signed(writer, CardFileID::ControlActivityData, &[0; 46])?;
+        if self.is_gen1() {
+            // Generation 1 cards store 56 specific condition records without a pointer.
+            return self.write_signed(writer, CardFileID::SpecificConditions, &[0; 56 * 5]);
+        }
+
+        self.write_signed(
+            writer,
+            CardFileID::SpecificConditions,
+            &self.cyclic_records(capacity.no_of_specific_condition_records, 5),
+        )?;
+        self.write_signed(
+            writer,
+            CardFileID::VehicleUnitsUsed,
+            &self.cyclic_records(capacity.no_of_card_vehicle_unit_records, 10),
+        )?;
+        self.write_signed(writer, CardFileID::GnssPlaces, &self.cyclic_records(capacity.no_of_gnssad_records, 18))?;
+        if self.generation == CardGeneration::Gen2v2 {
+            self.write_signed(
+                writer,
+                CardFileID::BorderCrossings,
+                &self.cyclic_records(capacity.no_of_border_crossing_records, 17),
+            )?;
+            self.write_signed(
+                writer,
+                CardFileID::LoadUnloadOperations,
+                &self.cyclic_records(capacity.no_of_load_unload_records, 20),
+            )?;
+            self.write_signed(
+                writer,
+                CardFileID::LoadTypeEntries,
+                &self.cyclic_records(capacity.no_of_load_type_entry_records, 5),
+            )?;
+            self.write_signed(writer, CardFileID::VUConfiguration, &vec![0; capacity.vu_configuration_length_range as usize])?;
+            if self.is_workshop() {
+                let calibration_add_data = vec![0; 1 + capacity.no_of_calibration_records as usize * 27];
+                self.write_signed(writer, CardFileID::CalibrationAddData, &calibration_add_data)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns empty cyclic records with a two bytes pointer to the newest record.
+    fn cyclic_records(&self, no_of_records: u16, record_length: usize) -> Vec<u8> {
+        vec![0; 2 + no_of_records as usize * record_length]
+    }
+
+    fn application_identification(&self) -> Result<Vec<u8>> {
+        write_to_vec(|writer| self.write_application_identification(writer))
+    }
+
+    fn write_application_identification<W: WriteBytes>(&self, writer: &mut W) -> Result<()> {
+        let capacity = &self.capacity;
+        writer.write_u8(u8::from(&self.card_type))?;
+        writer.write_all(&self.card_structure_version())?;
+        if !matches!(self.card_type, EquipmentType::DriverCard | EquipmentType::WorkshopCard) {
+            writer.write_u16::<BigEndian>(capacity.no_of_activity_records)?;
+            return Ok(());
+        }
+
+        writer.write_u8(capacity.no_events_per_type)?;
+        writer.write_u8(capacity.no_faults_per_type)?;
+        writer.write_u16::<BigEndian>(capacity.activity_structure_length)?;
+        writer.write_u16::<BigEndian>(capacity.no_of_card_vehicle_records)?;
+        if self.is_workshop() || self.is_gen1() {
+            writer.write_u8(record_count(capacity.no_of_card_place_records as usize)?)?;
+        } else {
+            writer.write_u16::<BigEndian>(capacity.no_of_card_place_records)?;
+        }
+        if self.is_workshop() {
+            writer.write_u8(capacity.no_of_calibration_records)?;
+        }
+        if !self.is_gen1() {
+            writer.write_u16::<BigEndian>(capacity.no_of_gnssad_records)?;
+            writer.write_u16::<BigEndian>(capacity.no_of_specific_condition_records)?;
+            writer.write_u16::<BigEndian>(capacity.no_of_card_vehicle_unit_records)?;
+        }
+        Ok(())
+    }
+
+    fn application_identification_v2(&self) -> Vec<u8> {
+        let capacity = &self.capacity;
+        let records = if matches!(self.card_type, EquipmentType::DriverCard | EquipmentType::WorkshopCard) {
+            vec![
+                capacity.no_of_border_crossing_records,
+                capacity.no_of_load_unload_records,
+                capacity.no_of_load_type_entry_records,
+                capacity.vu_configuration_length_range,
+            ]
+        } else {
+            vec![capacity.vu_configuration_length_range]
+        };
+        let mut application_identification_v2 = ((records.len() * 2) as u16).to_be_bytes().to_vec();
+        application_identification_v2.extend(records.iter().flat_map(|records| records.to_be_bytes()));
+        application_identification_v2
+    }
+
+    fn card_structure_version(&self) -> [u8; 2] {
+        match self.generation {
+            CardGeneration::Gen1 => [0x00, 0x00],
+            CardGeneration::Gen2v2 => [0x01, 0x01],
+            _ => [0x01, 0x00],
+        }
+    }
+
+    fn identification(&self) -> Result<Vec<u8>> {
+        write_to_vec(|writer| self.write_identification(writer))
+    }
+
+    fn write_identification<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        // Card issuing member state, card number, issuing authority and the dates.
+        writer.write_u8(0)?;
+        write_ia5_string(writer, &self.card_number, CARD_NUMBER_LENGTH)?;
+        writer.write_all(&[0; CARD_IDENTIFICATION_LENGTH - 1 - CARD_NUMBER_LENGTH])?;
+        match self.card_type {
+            EquipmentType::DriverCard => {
+                self.write_holder_name(writer)?;
+                writer.write_all(&[0; DATE_F_LENGTH])?;
+            }
+            EquipmentType::CompanyCard => {
+                write_name(writer, &self.holder_surname)?;
+                writer.write_all(&[0; NAME_LENGTH])?;
+            }
+            _ => {
+                // Name and address of the workshop or the control body.
+                writer.write_all(&[0; 2 * NAME_LENGTH])?;
+                self.write_holder_name(writer)?;
+            }
+        }
+        write_ia5_string(writer, "", LANGUAGE_LENGTH)
+    }
+
+    fn write_holder_name<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        write_name(writer, &self.holder_surname)?;
+        write_name(writer, &self.holder_first_names)
+    }
+
+    /// Returns the events or the faults data of `event_types` groups of `no_per_type` records.
+    fn event_data(&self, events: &[EventRecord], event_types: usize, no_per_type: u8) -> Result<Vec<u8>> {
+        let mut groups: Vec<(&EventFaultType, Vec<&EventRecord>)> = Vec::new();
+        for event in events {
+            match groups.iter_mut().find(|(event_type, _)| **event_type == event.event_type) {
+                Some((_, group)) => group.push(event),
+                None => groups.push((&event.event_type, vec![event])),
+            }
+        }
+        if groups.len() > event_types {
+            return Err(Error::InvalidDataEncode(format!("more than {event_types} event or fault types")));
+        }
+        if let Some((event_type, _)) = groups.iter().find(|(_, group)| group.len() > no_per_type as usize) {
+            return Err(Error::InvalidDataEncode(format!("more than {no_per_type} records of {event_type:?}")));
+        }
+
+        write_to_vec(|writer| write_event_groups(writer, &groups, event_types, no_per_type))
+    }
+
+    /// Returns the driver activity data with the activity days stored from the start of
+    /// the cyclic buffer.
+    fn driver_activity_data(&self) -> Result<Vec<u8>> {
+        let mut activity_daily_records: Vec<u8> = Vec::new();
+        let mut previous_record_length: u16 = 0;
+        let mut pointer_newest_record = 0;
+        for activity_day in self.activity_days.iter() {
+            let activity_change_infos = activity_day.activity_change_infos()?;
+            let record_length = (CARD_ACTIVITY_DAILY_RECORD_HEADER_LENGTH + 2 * activity_change_infos.len()) as u16;
+            pointer_newest_record = activity_daily_records.len();
+            activity_daily_records.extend(previous_record_length.to_be_bytes());
+            activity_daily_records.extend(record_length.to_be_bytes());
+            activity_daily_records.extend(activity_day.date.to_be_bytes());
+            activity_daily_records.extend(bcd_counter(activity_day.daily_presence_counter)?);
+            activity_daily_records.extend(activity_day.distance.to_be_bytes());
+            activity_daily_records
+                .extend(activity_change_infos.iter().flat_map(|activity_change_info| activity_change_info.to_be_bytes()));
+            previous_record_length = record_length;
+        }
+        let activity_structure_length = self.capacity.activity_structure_length as usize;
+        if activity_daily_records.len() > activity_structure_length {
+            return Err(Error::InvalidDataEncode(format!(
+                "{} bytes of activity days do not fit in the activity structure of {activity_structure_length} bytes",
+                activity_daily_records.len()
+            )));
+        }
+        activity_daily_records.resize(activity_structure_length, 0);
+
+        let mut driver_activity_data = vec![0; 2];
+        driver_activity_data.extend((pointer_newest_record as u16).to_be_bytes());
+        driver_activity_data.extend(activity_daily_records);
+        Ok(driver_activity_data)
+    }
+}
+
+fn write_name<W: WriteBytes + BinSeek>(writer: &mut W, name: &str) -> Result<()> {
+    Name { code_page: CodePage::IsoIec8859_1, name: name.to_owned() }.write(writer)
+}
+
+/// Returns `counter` as a BCD string of 2 bytes.
+fn bcd_counter(counter: u16) -> Result<[u8; 2]> {
+    if counter > 9999 {
+        return Err(Error::InvalidDataEncode(format!("daily presence counter {counter} has more than 4 digits")));
+    }
+    let digits = [counter / 1000, counter / 100 % 10, counter / 10 % 10, counter % 10];
+    Ok([(digits[0] << 4 | digits[1]) as u8, (digits[2] << 4 | digits[3]) as u8])
+}
+
+/// Writes `event_types` groups of `no_per_type` records, the groups and the records
+/// missing in `groups` are empty.
+fn write_event_groups<W: WriteBytes>(
+    writer: &mut W,
+    groups: &[(&EventFaultType, Vec<&EventRecord>)],
+    event_types: usize,
+    no_per_type: u8,
+) -> Result<()> {
+    for group_index in 0..event_types {
+        let group = groups.get(group_index).map(|(_, group)| group.as_slice()).unwrap_or_default();
+        for record_index in 0..no_per_type as usize {
+            match group.get(record_index) {
+                Some(event) => event.write_card_record(writer, CARD_EVENT_RECORD_LENGTH)?,
+                None => writer.write_all(&[0; CARD_EVENT_RECORD_LENGTH])?,
+            }
+        }
+    }
+    Ok(())
+}
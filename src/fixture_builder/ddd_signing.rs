@@ -0,0 +1,107 @@
+use crate::{
+    DddStreamReader, Error, Result,
+    tacho::{CardFileID, EquipmentType, RecordType, TachographDataType},
+};
+
+/// Size of the card file ID, the appendix and the size at the start of a card file.
+const CARD_FILE_HEADER_LENGTH: usize = 5;
+
+/// A card file of a card DDD file, as it is stored in the file.
+#[derive(Debug)]
+struct RawCardFile {
+    card_file_id: CardFileID,
+    appendix: u8,
+    data: Vec<u8>,
+}
+
+fn split_card_files(ddd: &[u8]) -> Result<Vec<RawCardFile>> {
+    let mut card_files = Vec::new();
+    let mut offset = 0;
+    while offset < ddd.len() {
+        let header = ddd.get(offset..offset + CARD_FILE_HEADER_LENGTH).ok_or(Error::PartialCardFile)?;
+        let size = u16::from_be_bytes([header[3], header[4]]) as usize;
+        let data_offset = offset + CARD_FILE_HEADER_LENGTH;
+        let data = ddd.get(data_offset..data_offset + size).ok_or(Error::PartialCardFile)?;
+        card_files.push(RawCardFile {
+            card_file_id: CardFileID::from(u16::from_be_bytes([header[0], header[1]])),
+            appendix: header[2],
+            data: data.to_vec(),
+        });
+        offset = data_offset + size;
+    }
+    Ok(card_files)
+}
+
+/// Returns the card type from the application identification of the card files with
+/// the `appendix` of a generation.
+fn card_type(card_files: &[RawCardFile], appendix: u8) -> Result<EquipmentType> {
+    card_files
+        .iter()
+        .find(|card_file| card_file.card_file_id == CardFileID::ApplicationIdentification && card_file.appendix == appendix)
+        .and_then(|card_file| card_file.data.first())
+        .map(|card_type| EquipmentType::from(*card_type))
+        .ok_or_else(|| Error::MissingCardFile(CardFileID::ApplicationIdentification.to_string()))
+}
+
+/// Replaces the certificates and the signatures of the card files with the `appendix`
+/// of a generation, the files of the other generation of a combined card are kept.
+///
+/// `certificate` returns the certificate stored in a certificate file for the card type
+/// and `sign` the signature of the data of a file by a card of the card type.
+pub(crate) fn sign_card_files(
+    ddd: &[u8],
+    appendix: u8,
+    certificate: impl Fn(&CardFileID, &EquipmentType) -> Option<Vec<u8>>,
+    sign: impl Fn(&EquipmentType, &[u8]) -> Vec<u8>,
+) -> Result<Vec<u8>> {
+    let mut card_files = split_card_files(ddd)?;
+    let card_type = card_type(&card_files, appendix)?;
+    for index in 0..card_files.len() {
+        if card_files[index].appendix == appendix {
+            if let Some(certificate) = certificate(&card_files[index].card_file_id, &card_type) {
+                card_files[index].data = certificate;
+            }
+        } else if card_files[index].appendix == appendix + 1 {
+            let signed_file = index
+                .checked_sub(1)
+                .map(|previous| &card_files[previous])
+                .filter(|previous| previous.card_file_id == card_files[index].card_file_id && previous.appendix == appendix)
+                .ok_or(Error::SignatureBeforeCardFile)?;
+            card_files[index].data = sign(&card_type, &signed_file.data);
+        }
+    }
+
+    let mut signed = Vec::with_capacity(ddd.len());
+    for card_file in card_files.iter() {
+        let size = u16::try_from(card_file.data.len())
+            .map_err(|_| Error::InvalidDataEncode(format!("card file {} is too large", card_file.card_file_id)))?;
+        signed.extend_from_slice(&u16::from(&card_file.card_file_id).to_be_bytes());
+        signed.push(card_file.appendix);
+        signed.extend_from_slice(&size.to_be_bytes());
+        signed.extend_from_slice(&card_file.data);
+    }
+    Ok(signed)
+}
+
+/// Returns the TREPs of a VU DDD file, each with the `76 Hex` prefix and its TREP ID.
+pub(crate) fn split_treps(ddd: &[u8]) -> Result<Vec<&[u8]>> {
+    let mut stream_reader = DddStreamReader::new(ddd)?;
+    if !matches!(stream_reader.get_header().data_type, TachographDataType::VU) {
+        return Err(Error::InvalidDataParse("the DDD file is not a VU download".to_owned()));
+    }
+    let mut treps = Vec::new();
+    let mut start = 0;
+    while let Some(item) = stream_reader.next() {
+        item?;
+        treps.push(&ddd[start..stream_reader.offset()]);
+        start = stream_reader.offset();
+    }
+    Ok(treps)
+}
+
+/// Returns a record array of a single record.
+pub(crate) fn single_record_array(record_type: &RecordType, record: &[u8]) -> Result<Vec<u8>> {
+    let record_size = u16::try_from(record.len())
+        .map_err(|_| Error::InvalidDataEncode(format!("record of {} bytes is too large", record.len())))?;
+    Ok([&[u8::from(record_type)][..], &record_size.to_be_bytes(), &1u16.to_be_bytes(), record].concat())
+}
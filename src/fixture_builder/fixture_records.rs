@@ -0,0 +1,241 @@
+use binary_data::{BigEndian, WriteBytes};
+
+use crate::{
+    Error, Result,
+    tacho::{ActivityType, EquipmentType, EventFaultType},
+    write_ia5_string,
+};
+
+/// Minutes in a day, the time of an activity change is a minute of the day.
+const MINUTES_PER_DAY: u16 = 1440;
+
+/// Size of the type, purpose and the begin and end times at the start of an event or fault record.
+const EVENT_RECORD_HEADER_LENGTH: usize = 10;
+
+/// Length of the card number of a `FullCardNumber`.
+const CARD_NUMBER_LENGTH: usize = 16;
+
+/// Size of the `FullCardNumberAndGeneration` of a card slot.
+const CARD_NUMBER_AND_GENERATION_LENGTH: usize = 19;
+
+/// Sign bit of a `GeoCoordinate`, the other 23 bits are the magnitude.
+const GEO_COORDINATE_SIGN: u32 = 0x80_0000;
+
+/// Activities of a driver on a calendar day, stored in the driver activity data of a
+/// card or in the activities TREP of a VU.
+#[derive(Debug)]
+pub struct ActivityDay {
+    /// Midnight of the day, in seconds since 00h00mn00s on 1 January 1970 GMT.
+    pub date: u32,
+    /// The daily presence counter of the card, written as a 4 digit BCD string.
+    pub daily_presence_counter: u16,
+    /// The distance travelled on the day in km.
+    pub distance: u16,
+    /// The activity changes of the day as the activity and the minute of the day it starts at.
+    pub activity_changes: Vec<(ActivityType, u16)>,
+    /// The positions of the accumulated driving of the day, only written by a generation 2 VU.
+    pub gnss_records: Vec<GnssRecord>,
+}
+
+impl ActivityDay {
+    /// Returns an activity day without activity changes starting at `date`.
+    pub fn new(date: u32) -> Self {
+        Self { date, daily_presence_counter: 0, distance: 0, activity_changes: Vec::new(), gnss_records: Vec::new() }
+    }
+
+    pub fn with_daily_presence_counter(mut self, daily_presence_counter: u16) -> Self {
+        self.daily_presence_counter = daily_presence_counter;
+        self
+    }
+
+    pub fn with_distance(mut self, distance: u16) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    /// Adds an activity change to `activity_type` at `minute` of the day.
+    pub fn with_activity(mut self, activity_type: ActivityType, minute: u16) -> Self {
+        self.activity_changes.push((activity_type, minute));
+        self
+    }
+
+    pub fn with_gnss_record(mut self, gnss_record: GnssRecord) -> Self {
+        self.gnss_records.push(gnss_record);
+        self
+    }
+
+    /// Returns the activity changes encoded as `ActivityChangeInfo` of a driver in the
+    /// driver slot, single driving, with the card inserted.
+    pub(crate) fn activity_change_infos(&self) -> Result<Vec<u16>> {
+        self.activity_changes
+            .iter()
+            .map(|(activity_type, minute)| {
+                if *minute >= MINUTES_PER_DAY {
+                    return Err(Error::InvalidDataEncode(format!("activity change at minute {minute} is not in a day")));
+                }
+                Ok((u16::from(activity_type) << 11) | minute)
+            })
+            .collect()
+    }
+}
+
+/// An event or a fault, stored in the events and faults data of a card or in the
+/// events and faults TREP of a VU.
+#[derive(Debug)]
+pub struct EventRecord {
+    pub event_type: EventFaultType,
+    /// Begin of the event, in seconds since 00h00mn00s on 1 January 1970 GMT.
+    pub begin_time: u32,
+    /// End of the event, in seconds since 00h00mn00s on 1 January 1970 GMT.
+    pub end_time: u32,
+}
+
+impl EventRecord {
+    pub fn new(event_type: EventFaultType, begin_time: u32, end_time: u32) -> Self {
+        Self { event_type, begin_time, end_time }
+    }
+
+    /// Writes a card event or fault record, the vehicle registration is left empty.
+    pub(crate) fn write_card_record<W: WriteBytes>(&self, writer: &mut W, record_length: usize) -> Result<()> {
+        writer.write_u8(u8::from(&self.event_type))?;
+        writer.write_u32::<BigEndian>(self.begin_time)?;
+        writer.write_u32::<BigEndian>(self.end_time)?;
+        writer.write_all(&vec![0; record_length - 9])?;
+        Ok(())
+    }
+
+    /// Writes a VU event or fault record, the record purpose and the card numbers are
+    /// left empty.
+    pub(crate) fn write_vu_record<W: WriteBytes>(&self, writer: &mut W, record_length: usize) -> Result<()> {
+        writer.write_u8(u8::from(&self.event_type))?;
+        writer.write_u8(0)?;
+        writer.write_u32::<BigEndian>(self.begin_time)?;
+        writer.write_u32::<BigEndian>(self.end_time)?;
+        writer.write_all(&vec![0; record_length - EVENT_RECORD_HEADER_LENGTH])?;
+        Ok(())
+    }
+}
+
+/// A position of the vehicle when the accumulated driving time reaches a multiple of
+/// three hours, stored in the activities TREP of a generation 2 VU.
+#[derive(Debug, Clone)]
+pub struct GnssRecord {
+    /// Time of the position, in seconds since 00h00mn00s on 1 January 1970 GMT.
+    pub time_stamp: u32,
+    /// The latitude in 1/10000 minutes, negative in the south.
+    pub latitude: i32,
+    /// The longitude in 1/10000 minutes, negative in the west.
+    pub longitude: i32,
+    /// The `authenticationStatus` of the position, only written by a generation 2,
+    /// version 2 VU.
+    pub authentication_status: u8,
+    /// The odometer value in km.
+    pub odometer: u32,
+}
+
+impl GnssRecord {
+    pub fn new(time_stamp: u32, latitude: i32, longitude: i32) -> Self {
+        Self { time_stamp, latitude, longitude, authentication_status: 0, odometer: 0 }
+    }
+
+    pub fn with_authentication_status(mut self, authentication_status: u8) -> Self {
+        self.authentication_status = authentication_status;
+        self
+    }
+
+    pub fn with_odometer(mut self, odometer: u32) -> Self {
+        self.odometer = odometer;
+        self
+    }
+
+    /// Writes a `VuGNSSADRecord`, the card numbers are left empty and the position is
+    /// taken at `time_stamp` with a GNSS accuracy of 0. The authentication status is only
+    /// written with `is_gen2_v2`.
+    pub(crate) fn write_vu_record<W: WriteBytes>(&self, writer: &mut W, is_gen2_v2: bool) -> Result<()> {
+        writer.write_u32::<BigEndian>(self.time_stamp)?;
+        writer.write_all(&[0; 2 * CARD_NUMBER_AND_GENERATION_LENGTH])?;
+        writer.write_u32::<BigEndian>(self.time_stamp)?;
+        writer.write_u8(0)?;
+        writer.write_all(&geo_coordinate_bytes(self.latitude)?)?;
+        writer.write_all(&geo_coordinate_bytes(self.longitude)?)?;
+        if is_gen2_v2 {
+            writer.write_u8(self.authentication_status)?;
+        }
+        writer.write_all(&self.odometer.to_be_bytes()[1..])?;
+        Ok(())
+    }
+}
+
+/// Returns the 3 bytes of a `GeoCoordinate` of `coordinate` in 1/10000 minutes.
+fn geo_coordinate_bytes(coordinate: i32) -> Result<[u8; 3]> {
+    let magnitude = coordinate.unsigned_abs();
+    if magnitude >= GEO_COORDINATE_SIGN {
+        return Err(Error::InvalidDataEncode(format!("geo coordinate {coordinate} does not fit in 3 bytes")));
+    }
+    let raw = if coordinate < 0 { magnitude | GEO_COORDINATE_SIGN } else { magnitude };
+    Ok([(raw >> 16) as u8, (raw >> 8) as u8, raw as u8])
+}
+
+/// A control performed with a control card, stored in the overview TREP of a VU.
+#[derive(Debug)]
+pub struct ControlRecord {
+    /// The `ControlType` bits of the activities carried out during the control.
+    pub control_type: u8,
+    /// Time of the control, in seconds since 00h00mn00s on 1 January 1970 GMT.
+    pub control_time: u32,
+    /// The `NationNumeric` code of the nation issuing the control card.
+    pub control_card_nation: u8,
+    pub control_card_number: String,
+}
+
+impl ControlRecord {
+    pub fn new(control_type: u8, control_time: u32, control_card_nation: u8, control_card_number: &str) -> Self {
+        Self { control_type, control_time, control_card_nation, control_card_number: control_card_number.to_owned() }
+    }
+
+    /// Writes a VU control activity record, the generation of the control card is only
+    /// written by a generation 2 VU. The downloaded period is `download_period`.
+    pub(crate) fn write_vu_record<W: WriteBytes>(
+        &self,
+        writer: &mut W,
+        card_generation: Option<u8>,
+        download_period: &[u8; 8],
+    ) -> Result<()> {
+        writer.write_u8(self.control_type)?;
+        writer.write_u32::<BigEndian>(self.control_time)?;
+        writer.write_u8(u8::from(&EquipmentType::ControlCard))?;
+        writer.write_u8(self.control_card_nation)?;
+        write_ia5_string(writer, &self.control_card_number, CARD_NUMBER_LENGTH)?;
+        if let Some(card_generation) = card_generation {
+            writer.write_u8(card_generation)?;
+        }
+        writer.write_all(download_period)?;
+        Ok(())
+    }
+}
+
+/// Speeds of a minute, stored in the detailed speed TREP of a VU.
+#[derive(Debug)]
+pub struct SpeedBlock {
+    /// Begin of the minute, in seconds since 00h00mn00s on 1 January 1970 GMT.
+    pub begin_time: u32,
+    /// The speed in km/h of each second of the minute.
+    pub speeds: [u8; 60],
+}
+
+impl SpeedBlock {
+    pub fn new(begin_time: u32, speeds: [u8; 60]) -> Self {
+        Self { begin_time, speeds }
+    }
+
+    /// Returns a block of a minute driven at a constant `speed`.
+    pub fn constant(begin_time: u32, speed: u8) -> Self {
+        Self { begin_time, speeds: [speed; 60] }
+    }
+
+    pub(crate) fn write<W: WriteBytes>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u32::<BigEndian>(self.begin_time)?;
+        writer.write_all(&self.speeds)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,18 @@
+//! Builders of synthetic DDD files of cards and vehicle units, for regression tests
+//! without downloads of real tachographs, and test PKIs signing them. The keys of the
+//! test PKIs are not secret, the module is only built for the tests and with the
+//! `test-fixtures` feature.
+
+mod card_ddd_builder;
+mod ddd_signing;
+mod fixture_records;
+mod test_pki_gen1;
+mod test_pki_gen2;
+mod test_rng;
+mod vu_ddd_builder;
+
+pub use card_ddd_builder::{CardCapacity, CardDddBuilder};
+pub use fixture_records::{ActivityDay, ControlRecord, EventRecord, GnssRecord, SpeedBlock};
+pub use test_pki_gen1::Gen1TestPki;
+pub use test_pki_gen2::Gen2TestPki;
+pub use vu_ddd_builder::VuDddBuilder;
@@ -0,0 +1,241 @@
+use num_bigint::BigUint;
+use sha1::{Digest, Sha1};
+
+use crate::{
+    Result,
+    fixture_builder::{
+        ddd_signing::{sign_card_files, split_treps},
+        test_rng::TestRng,
+    },
+    tacho::{CardFileID, EquipmentType},
+};
+
+const MODULUS_LENGTH: usize = 128;
+const EXPONENT_LENGTH: usize = 8;
+const KEY_IDENTIFIER_LENGTH: usize = 8;
+const CERTIFICATE_LENGTH: usize = 194;
+const RECOVERABLE_CONTENT_LENGTH: usize = 106;
+const MILLER_RABIN_ROUNDS: usize = 12;
+const PUBLIC_EXPONENT: u32 = 65537;
+const CERTIFICATE_PROFILE_IDENTIFIER: u8 = 0x01;
+const TACHOGRAPH_APPLICATION_ID: [u8; 6] = [0xFF, 0x54, 0x41, 0x43, 0x48, 0x4F];
+/// The end of validity of the certificates, 1 January 2040.
+const END_OF_VALIDITY: u32 = 2208988800;
+const DIGEST_INFO: [u8; 15] = [48, 33, 48, 9, 6, 5, 43, 14, 3, 2, 26, 5, 0, 4, 20];
+const SIGNATURE_PADDING_LENGTH: usize = 90;
+const ERCA_KEY_IDENTIFIER: [u8; 8] = [0xFD, 0x45, 0x43, 0x20, 0x01, 0xFF, 0xFF, 0x01];
+const MSCA_KEY_IDENTIFIER: [u8; 8] = [0x11, 0x54, 0x53, 0x54, 0x01, 0xFF, 0xFF, 0x01];
+const CARD_KEY_IDENTIFIER: [u8; 8] = [0x00, 0x00, 0x00, 0x01, 0x01, 0x25, 0x00, 0xFF];
+const VU_KEY_IDENTIFIER: [u8; 8] = [0x00, 0x00, 0x00, 0x02, 0x01, 0x25, 0x06, 0xFF];
+const VU_SIGNATURE_LENGTH: usize = 128;
+const OVERVIEW_TREP_ID: u8 = 0x01;
+const TECHNICAL_DATA_TREP_ID: u8 = 0x05;
+
+const SMALL_PRIMES: [u32; 24] = [3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97];
+
+/// A 1024 bit RSA key pair.
+#[derive(Debug)]
+struct RsaKey {
+    modulus: BigUint,
+    private_exponent: BigUint,
+}
+
+impl RsaKey {
+    fn generate(rng: &mut TestRng) -> Self {
+        let public_exponent = BigUint::from(PUBLIC_EXPONENT);
+        loop {
+            let p = random_prime(rng, MODULUS_LENGTH / 2);
+            let q = random_prime(rng, MODULUS_LENGTH / 2);
+            let phi = (&p - 1u8) * (&q - 1u8);
+            if let Some(private_exponent) = public_exponent.modinv(&phi) {
+                return Self { modulus: p * q, private_exponent };
+            }
+        }
+    }
+
+    /// Returns the public key as stored in a certificate, the modulus followed by the
+    /// public exponent.
+    fn public_key(&self) -> Vec<u8> {
+        [left_pad(&self.modulus.to_bytes_be(), MODULUS_LENGTH), left_pad(&PUBLIC_EXPONENT.to_be_bytes(), EXPONENT_LENGTH)]
+            .concat()
+    }
+
+    /// Returns the private key operation on a message block of the modulus size.
+    fn sign_block(&self, block: &[u8]) -> Vec<u8> {
+        let signature = BigUint::from_bytes_be(block).modpow(&self.private_exponent, &self.modulus);
+        left_pad(&signature.to_bytes_be(), MODULUS_LENGTH)
+    }
+
+    /// Returns the PKCS #1 v1.5 signature of the SHA-1 hash of `data`.
+    fn sign(&self, data: &[u8]) -> Vec<u8> {
+        let block = [&[0x00, 0x01][..], &[0xFF; SIGNATURE_PADDING_LENGTH], &[0x00], &DIGEST_INFO, &Sha1::digest(data)].concat();
+        self.sign_block(&block)
+    }
+}
+
+fn left_pad(bytes: &[u8], length: usize) -> Vec<u8> {
+    let mut padded = vec![0; length.saturating_sub(bytes.len())];
+    padded.extend_from_slice(bytes);
+    padded
+}
+
+/// Returns a prime of `length` bytes with the two most significant bits set, so the
+/// product of two such primes has the full modulus size.
+fn random_prime(rng: &mut TestRng, length: usize) -> BigUint {
+    loop {
+        let mut bytes = rng.bytes(length);
+        bytes[0] |= 0xC0;
+        bytes[length - 1] |= 0x01;
+        let candidate = BigUint::from_bytes_be(&bytes);
+        if SMALL_PRIMES.iter().any(|prime| &candidate % *prime == BigUint::ZERO) {
+            continue;
+        }
+        if is_probable_prime(rng, &candidate) {
+            return candidate;
+        }
+    }
+}
+
+/// Miller-Rabin test of an odd `candidate` with random bases.
+fn is_probable_prime(rng: &mut TestRng, candidate: &BigUint) -> bool {
+    let candidate_minus_one = candidate - 1u8;
+    let shift = candidate_minus_one.trailing_zeros().unwrap_or(0);
+    let odd_part = &candidate_minus_one >> shift;
+    'rounds: for _ in 0..MILLER_RABIN_ROUNDS {
+        let base = rng.below(&candidate_minus_one) + 1u8;
+        let mut value = base.modpow(&odd_part, candidate);
+        if value == BigUint::from(1u8) || value == candidate_minus_one {
+            continue;
+        }
+        for _ in 1..shift {
+            value = value.modpow(&BigUint::from(2u8), candidate);
+            if value == candidate_minus_one {
+                continue 'rounds;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// A test public key infrastructure of generation 1, a European root, a member state
+/// CA and the keys of a card and of a VU, all generated from a seed.
+///
+/// The certificates have the 194 byte format of Appendix 11 and the signatures are
+/// RSA signatures of the SHA-1 hash of the data, so the files signed with this PKI pass
+/// `verify_card` with the public key of the test root. The keys are not secret and
+/// must not be used outside of tests.
+#[derive(Debug)]
+pub struct Gen1TestPki {
+    erca: RsaKey,
+    msca: RsaKey,
+    card: RsaKey,
+    vu: RsaKey,
+}
+
+impl Gen1TestPki {
+    /// Generates the keys of the PKI, the same `seed` always gives the same keys.
+    pub fn new(seed: u64) -> Self {
+        let mut rng = TestRng::new(seed);
+        Self {
+            erca: RsaKey::generate(&mut rng),
+            msca: RsaKey::generate(&mut rng),
+            card: RsaKey::generate(&mut rng),
+            vu: RsaKey::generate(&mut rng),
+        }
+    }
+
+    /// Returns the 144 byte public key of the test root, the key identifier followed by
+    /// the RSA public key.
+    pub fn erca_public_key(&self) -> Vec<u8> {
+        [&ERCA_KEY_IDENTIFIER[..], &self.erca.public_key()].concat()
+    }
+
+    /// Returns the certificate of the member state CA, signed by the test root.
+    pub fn msca_certificate(&self) -> Vec<u8> {
+        certificate(&self.erca, &ERCA_KEY_IDENTIFIER, &EquipmentType::MemberStateCA, &MSCA_KEY_IDENTIFIER, &self.msca)
+    }
+
+    /// Returns the certificate of a card of `card_type`, signed by the member state CA.
+    pub fn card_certificate(&self, card_type: &EquipmentType) -> Vec<u8> {
+        certificate(&self.msca, &MSCA_KEY_IDENTIFIER, card_type, &CARD_KEY_IDENTIFIER, &self.card)
+    }
+
+    /// Returns the certificate of the VU, signed by the member state CA.
+    pub fn vu_certificate(&self) -> Vec<u8> {
+        certificate(&self.msca, &MSCA_KEY_IDENTIFIER, &EquipmentType::VehicleUnit, &VU_KEY_IDENTIFIER, &self.vu)
+    }
+
+    /// Returns the 128 byte signature of `data` by the card.
+    pub fn sign_card_data(&self, data: &[u8]) -> Vec<u8> {
+        self.card.sign(data)
+    }
+
+    /// Returns the 128 byte signature of `data` by the VU.
+    pub fn sign_vu_data(&self, data: &[u8]) -> Vec<u8> {
+        self.vu.sign(data)
+    }
+
+    /// Returns the card DDD file with the certificates of the PKI and each signed file
+    /// signed by the card. Only the generation 1 files of a combined card are signed.
+    pub fn sign_card_ddd(&self, ddd: &[u8]) -> Result<Vec<u8>> {
+        sign_card_files(
+            ddd,
+            0,
+            |card_file_id, card_type| match card_file_id {
+                CardFileID::CardCertificate => Some(self.card_certificate(card_type)),
+                CardFileID::CACertificate => Some(self.msca_certificate()),
+                _ => None,
+            },
+            |_, data| self.sign_card_data(data),
+        )
+    }
+
+    /// Returns the VU DDD file with the certificates of the PKI in the overview and each
+    /// TREP signed by the VU, the signature of the overview is over the data after the
+    /// certificates. The card downloads in the file are kept as they are.
+    pub fn sign_vu_ddd(&self, ddd: &[u8]) -> Result<Vec<u8>> {
+        let mut signed = Vec::with_capacity(ddd.len());
+        for trep in split_treps(ddd)? {
+            if !(OVERVIEW_TREP_ID..=TECHNICAL_DATA_TREP_ID).contains(&trep[1]) {
+                signed.extend_from_slice(trep);
+                continue;
+            }
+            let mut data = trep[2..trep.len() - VU_SIGNATURE_LENGTH].to_vec();
+            let mut signed_data_start = 0;
+            if trep[1] == OVERVIEW_TREP_ID {
+                data[..CERTIFICATE_LENGTH].copy_from_slice(&self.msca_certificate());
+                data[CERTIFICATE_LENGTH..2 * CERTIFICATE_LENGTH].copy_from_slice(&self.vu_certificate());
+                signed_data_start = 2 * CERTIFICATE_LENGTH;
+            }
+            signed.extend_from_slice(&trep[..2]);
+            signed.extend_from_slice(&data);
+            signed.extend_from_slice(&self.sign_vu_data(&data[signed_data_start..]));
+        }
+        Ok(signed)
+    }
+}
+
+/// Returns the certificate of the public key of `holder` signed by `authority`, with the
+/// signature giving message recovery of ISO/IEC 9796-2 with SHA-1.
+fn certificate(
+    authority: &RsaKey,
+    authority_reference: &[u8; KEY_IDENTIFIER_LENGTH],
+    holder_type: &EquipmentType,
+    holder_reference: &[u8; KEY_IDENTIFIER_LENGTH],
+    holder: &RsaKey,
+) -> Vec<u8> {
+    let content = [
+        &[CERTIFICATE_PROFILE_IDENTIFIER][..],
+        authority_reference,
+        &TACHOGRAPH_APPLICATION_ID,
+        &[u8::from(holder_type)],
+        &END_OF_VALIDITY.to_be_bytes(),
+        holder_reference,
+        &holder.public_key(),
+    ]
+    .concat();
+    let (recoverable, remainder) = content.split_at(RECOVERABLE_CONTENT_LENGTH);
+    let block = [&[0x6A][..], recoverable, &Sha1::digest(&content), &[0xBC]].concat();
+    [&authority.sign_block(&block)[..], remainder, authority_reference].concat()
+}
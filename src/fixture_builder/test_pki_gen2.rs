@@ -0,0 +1,280 @@
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    Error, Result,
+    fixture_builder::{
+        ddd_signing::{sign_card_files, single_record_array, split_treps},
+        test_rng::TestRng,
+    },
+    helpers::{EccCurve, EccPoint, split_record_arrays},
+    tacho::{CardFileID, EquipmentType, RecordType},
+};
+
+const KEY_IDENTIFIER_LENGTH: usize = 8;
+const CERTIFICATE_PROFILE_IDENTIFIER: u8 = 0x00;
+const TACHOGRAPH_APPLICATION_ID: [u8; 6] = [0xFF, 0x53, 0x4D, 0x52, 0x44, 0x54];
+/// The effective date of the certificates, 1 January 2020.
+const EFFECTIVE_DATE: u32 = 1577836800;
+/// The expiration date of the certificates, 1 January 2040.
+const EXPIRATION_DATE: u32 = 2208988800;
+const ERCA_KEY_IDENTIFIER: [u8; 8] = [0xFD, 0x45, 0x43, 0x20, 0x01, 0xFF, 0xFF, 0x02];
+const MSCA_KEY_IDENTIFIER: [u8; 8] = [0x11, 0x54, 0x53, 0x54, 0x01, 0xFF, 0xFF, 0x02];
+const CARD_KEY_IDENTIFIER: [u8; 8] = [0x00, 0x00, 0x00, 0x01, 0x01, 0x25, 0x00, 0xFF];
+const CARD_SIGN_KEY_IDENTIFIER: [u8; 8] = [0x00, 0x00, 0x00, 0x01, 0x01, 0x25, 0x01, 0xFF];
+const VU_KEY_IDENTIFIER: [u8; 8] = [0x00, 0x00, 0x00, 0x02, 0x01, 0x25, 0x06, 0xFF];
+const GEN2_APPENDIX: u8 = 2;
+const OVERVIEW_TREP_IDS: [u8; 2] = [0x21, 0x31];
+const SIGNED_TREP_IDS: [u8; 10] = [0x21, 0x22, 0x23, 0x24, 0x25, 0x31, 0x32, 0x33, 0x34, 0x35];
+
+const TAG_CERTIFICATE: [u8; 2] = [0x7F, 0x21];
+const TAG_CERTIFICATE_BODY: [u8; 2] = [0x7F, 0x4E];
+const TAG_CERTIFICATE_PROFILE_IDENTIFIER: [u8; 2] = [0x5F, 0x29];
+const TAG_CERTIFICATE_AUTHORITY_REFERENCE: [u8; 1] = [0x42];
+const TAG_CERTIFICATE_HOLDER_AUTHORISATION: [u8; 2] = [0x5F, 0x4C];
+const TAG_PUBLIC_KEY: [u8; 2] = [0x7F, 0x49];
+const TAG_DOMAIN_PARAMETERS: [u8; 1] = [0x06];
+const TAG_PUBLIC_POINT: [u8; 1] = [0x86];
+const TAG_CERTIFICATE_HOLDER_REFERENCE: [u8; 2] = [0x5F, 0x20];
+const TAG_CERTIFICATE_EFFECTIVE_DATE: [u8; 2] = [0x5F, 0x25];
+const TAG_CERTIFICATE_EXPIRATION_DATE: [u8; 2] = [0x5F, 0x24];
+const TAG_SIGNATURE: [u8; 2] = [0x5F, 0x37];
+
+/// An ECC key pair on the curve of the PKI.
+#[derive(Debug)]
+struct EccKey {
+    private_key: BigUint,
+    public_point: EccPoint,
+}
+
+impl EccKey {
+    fn generate(rng: &mut TestRng, curve: &EccCurve) -> Self {
+        let private_key = rng.below(&curve.n);
+        let public_point = curve.multiply(&private_key, &curve.g);
+        debug_assert!(curve.is_on_curve(&public_point));
+        Self { private_key, public_point }
+    }
+
+    /// Returns the ECDSA signature `r || s` of the SHA-256 hash of `data`. The nonce is
+    /// derived from the private key and the hash, so signatures are reproducible.
+    fn sign(&self, curve: &EccCurve, data: &[u8]) -> Vec<u8> {
+        let hash = Sha256::digest(data);
+        let e = BigUint::from_bytes_be(&hash);
+        let mut counter: u32 = 0;
+        loop {
+            counter += 1;
+            let nonce = Sha256::new()
+                .chain_update(curve.encode_integer(&self.private_key))
+                .chain_update(hash)
+                .chain_update(counter.to_be_bytes())
+                .finalize();
+            let k = BigUint::from_bytes_be(&nonce) % (&curve.n - 1u8) + 1u8;
+            let EccPoint::Affine(x, _) = curve.multiply(&k, &curve.g) else {
+                continue;
+            };
+            let r = x % &curve.n;
+            let Some(k_inverse) = k.modinv(&curve.n) else {
+                continue;
+            };
+            let s = k_inverse * (&e + &r * &self.private_key) % &curve.n;
+            if r != BigUint::ZERO && s != BigUint::ZERO {
+                return [curve.encode_integer(&r), curve.encode_integer(&s)].concat();
+            }
+        }
+    }
+}
+
+/// Returns the BER-TLV encoding of `value` with `tag`.
+fn tlv(tag: &[u8], value: &[u8]) -> Vec<u8> {
+    let length = value.len();
+    let encoded_length = if length < 0x80 {
+        vec![length as u8]
+    } else if length <= 0xFF {
+        vec![0x81, length as u8]
+    } else {
+        vec![0x82, (length >> 8) as u8, length as u8]
+    };
+    [tag, &encoded_length, value].concat()
+}
+
+/// A test public key infrastructure of generation 2, a European root, a member state
+/// CA and the keys of a card and of a VU on the brainpoolP256r1 curve, all generated
+/// from a seed.
+///
+/// The certificates have the ECC certificate format of Appendix 11, read by
+/// `CertificateProfile`, and the signatures are ECDSA signatures of the SHA-256 hash of
+/// the data. The root certificate is the 205 byte ERCA key of `verify_card`. The keys
+/// are not secret and must not be used outside of tests.
+#[derive(Debug)]
+pub struct Gen2TestPki {
+    curve: EccCurve,
+    erca_key_identifier: [u8; KEY_IDENTIFIER_LENGTH],
+    erca: EccKey,
+    msca: EccKey,
+    card: EccKey,
+    card_sign: EccKey,
+    vu: EccKey,
+}
+
+impl Gen2TestPki {
+    /// Generates the keys of the PKI, the same `seed` always gives the same keys.
+    pub fn new(seed: u64) -> Self {
+        let mut rng = TestRng::new(seed);
+        let curve = EccCurve::brainpool_p256r1();
+        Self {
+            erca_key_identifier: ERCA_KEY_IDENTIFIER,
+            erca: EccKey::generate(&mut rng, &curve),
+            msca: EccKey::generate(&mut rng, &curve),
+            card: EccKey::generate(&mut rng, &curve),
+            card_sign: EccKey::generate(&mut rng, &curve),
+            vu: EccKey::generate(&mut rng, &curve),
+            curve,
+        }
+    }
+
+    /// Sets the key identifier of the test root, the certificate holder reference of the
+    /// root certificate and the certification authority reference of the member state CA.
+    pub fn with_erca_key_identifier(mut self, erca_key_identifier: [u8; KEY_IDENTIFIER_LENGTH]) -> Self {
+        self.erca_key_identifier = erca_key_identifier;
+        self
+    }
+
+    /// Returns the self-signed certificate of the test root.
+    pub fn erca_certificate(&self) -> Vec<u8> {
+        self.certificate(
+            &self.erca,
+            &self.erca_key_identifier,
+            &EquipmentType::EuropeanRootCA,
+            &self.erca_key_identifier,
+            &self.erca,
+        )
+    }
+
+    /// Returns the link certificate of the root of `next`, signed by the test root.
+    pub fn link_certificate(&self, next: &Gen2TestPki) -> Vec<u8> {
+        self.certificate(
+            &self.erca,
+            &self.erca_key_identifier,
+            &EquipmentType::EuropeanRootCA,
+            &next.erca_key_identifier,
+            &next.erca,
+        )
+    }
+
+    /// Returns the certificate of the member state CA, signed by the test root.
+    pub fn msca_certificate(&self) -> Vec<u8> {
+        self.certificate(&self.erca, &self.erca_key_identifier, &EquipmentType::MemberStateCA, &MSCA_KEY_IDENTIFIER, &self.msca)
+    }
+
+    /// Returns the mutual authentication certificate of a card of `card_type`, signed by
+    /// the member state CA.
+    pub fn card_certificate(&self, card_type: &EquipmentType) -> Vec<u8> {
+        self.certificate(&self.msca, &MSCA_KEY_IDENTIFIER, card_type, &CARD_KEY_IDENTIFIER, &self.card)
+    }
+
+    /// Returns the signature certificate of a driver or a workshop card, signed by the
+    /// member state CA.
+    pub fn card_sign_certificate(&self, card_type: &EquipmentType) -> Vec<u8> {
+        let holder_type = match card_type {
+            EquipmentType::WorkshopCard => EquipmentType::WorkshopCardSign,
+            _ => EquipmentType::DriverCardSign,
+        };
+        self.certificate(&self.msca, &MSCA_KEY_IDENTIFIER, &holder_type, &CARD_SIGN_KEY_IDENTIFIER, &self.card_sign)
+    }
+
+    /// Returns the signature certificate of the VU, signed by the member state CA.
+    pub fn vu_certificate(&self) -> Vec<u8> {
+        self.certificate(&self.msca, &MSCA_KEY_IDENTIFIER, &EquipmentType::VehicleUnitSign, &VU_KEY_IDENTIFIER, &self.vu)
+    }
+
+    /// Returns the 64 byte signature of `data` by a card of `card_type`. Driver and
+    /// workshop cards sign with the key of the signature certificate, the other cards
+    /// with the key of the mutual authentication certificate.
+    pub fn sign_card_data(&self, card_type: &EquipmentType, data: &[u8]) -> Vec<u8> {
+        match card_type {
+            EquipmentType::DriverCard | EquipmentType::WorkshopCard => self.card_sign.sign(&self.curve, data),
+            _ => self.card.sign(&self.curve, data),
+        }
+    }
+
+    /// Returns the 64 byte signature of `data` by the VU.
+    pub fn sign_vu_data(&self, data: &[u8]) -> Vec<u8> {
+        self.vu.sign(&self.curve, data)
+    }
+
+    /// Returns the card DDD file with the certificates of the PKI and each signed file
+    /// signed by the card. Only the generation 2 files of a combined card are signed.
+    pub fn sign_card_ddd(&self, ddd: &[u8]) -> Result<Vec<u8>> {
+        sign_card_files(
+            ddd,
+            GEN2_APPENDIX,
+            |card_file_id, card_type| match card_file_id {
+                CardFileID::CardCertificate => Some(self.card_certificate(card_type)),
+                CardFileID::CardSignCertificate => Some(self.card_sign_certificate(card_type)),
+                CardFileID::CACertificate => Some(self.msca_certificate()),
+                _ => None,
+            },
+            |card_type, data| self.sign_card_data(card_type, data),
+        )
+    }
+
+    /// Returns the VU DDD file with the certificates of the PKI in the overview and each
+    /// TREP signed by the VU, the signature of the overview is over the record arrays
+    /// after the certificates. The card downloads in the file are kept as they are.
+    pub fn sign_vu_ddd(&self, ddd: &[u8]) -> Result<Vec<u8>> {
+        let mut signed = Vec::with_capacity(ddd.len());
+        for trep in split_treps(ddd)? {
+            if !SIGNED_TREP_IDS.contains(&trep[1]) {
+                signed.extend_from_slice(trep);
+                continue;
+            }
+            let mut record_arrays: Vec<Vec<u8>> =
+                split_record_arrays(&trep[2..])?.into_iter().map(|record_array| record_array.to_vec()).collect();
+            if record_arrays.pop().and_then(|record_array| record_array.first().copied())
+                != Some(u8::from(&RecordType::Signature))
+            {
+                return Err(Error::InvalidDataParse(format!("TREP {:02X} does not end with a signature", trep[1])));
+            }
+            let mut signed_record_arrays = 0;
+            if OVERVIEW_TREP_IDS.contains(&trep[1]) {
+                record_arrays[0] = single_record_array(&RecordType::MemberStateCertificate, &self.msca_certificate())?;
+                record_arrays[1] = single_record_array(&RecordType::VuCertificate, &self.vu_certificate())?;
+                signed_record_arrays = 2;
+            }
+            let signature = self.sign_vu_data(&record_arrays[signed_record_arrays..].concat());
+            signed.extend_from_slice(&trep[..2]);
+            signed.extend_from_slice(&record_arrays.concat());
+            signed.extend_from_slice(&single_record_array(&RecordType::Signature, &signature)?);
+        }
+        Ok(signed)
+    }
+
+    /// Returns the certificate of the public key of `holder` signed by `authority`.
+    fn certificate(
+        &self,
+        authority: &EccKey,
+        authority_reference: &[u8; KEY_IDENTIFIER_LENGTH],
+        holder_type: &EquipmentType,
+        holder_reference: &[u8; KEY_IDENTIFIER_LENGTH],
+        holder: &EccKey,
+    ) -> Vec<u8> {
+        let public_key =
+            [tlv(&TAG_DOMAIN_PARAMETERS, self.curve.oid), tlv(&TAG_PUBLIC_POINT, &self.curve.encode_point(&holder.public_point))]
+                .concat();
+        let body = tlv(
+            &TAG_CERTIFICATE_BODY,
+            &[
+                tlv(&TAG_CERTIFICATE_PROFILE_IDENTIFIER, &[CERTIFICATE_PROFILE_IDENTIFIER]),
+                tlv(&TAG_CERTIFICATE_AUTHORITY_REFERENCE, authority_reference),
+                tlv(&TAG_CERTIFICATE_HOLDER_AUTHORISATION, &[&TACHOGRAPH_APPLICATION_ID[..], &[u8::from(holder_type)]].concat()),
+                tlv(&TAG_PUBLIC_KEY, &public_key),
+                tlv(&TAG_CERTIFICATE_HOLDER_REFERENCE, holder_reference),
+                tlv(&TAG_CERTIFICATE_EFFECTIVE_DATE, &EFFECTIVE_DATE.to_be_bytes()),
+                tlv(&TAG_CERTIFICATE_EXPIRATION_DATE, &EXPIRATION_DATE.to_be_bytes()),
+            ]
+            .concat(),
+        );
+        let signature = tlv(&TAG_SIGNATURE, &authority.sign(&self.curve, &body));
+        tlv(&TAG_CERTIFICATE, &[body, signature].concat())
+    }
+}
@@ -0,0 +1,38 @@
+use num_bigint::BigUint;
+
+/// A deterministic SplitMix64 generator for the key material of the test PKIs, the same
+/// seed always gives the same keys, certificates and signatures. It is not suitable for
+/// keys protecting anything.
+#[derive(Debug)]
+pub(crate) struct TestRng {
+    state: u64,
+}
+
+impl TestRng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        value ^ (value >> 31)
+    }
+
+    pub(crate) fn bytes(&mut self, length: usize) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(length + 8);
+        while bytes.len() < length {
+            bytes.extend_from_slice(&self.next_u64().to_be_bytes());
+        }
+        bytes.truncate(length);
+        bytes
+    }
+
+    /// Returns an integer in the range `1..bound`.
+    pub(crate) fn below(&mut self, bound: &BigUint) -> BigUint {
+        let length = bound.to_bytes_be().len() + 8;
+        BigUint::from_bytes_be(&self.bytes(length)) % (bound - 1u8) + 1u8
+    }
+}
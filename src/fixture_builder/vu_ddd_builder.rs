@@ -0,0 +1,464 @@
+use binary_data::{BigEndian, BinMemoryBuffer, BinSeek, WriteBytes};
+
+use crate::{
+    CodePage, Result,
+    fixture_builder::{ActivityDay, ControlRecord, EventRecord, SpeedBlock},
+    record_count,
+    tacho::{RecordType, VUTransferResponseParameterID},
+    tachograph_gen2::data_info::write_record_array,
+    write_ia5_string, write_octet_string, write_string, write_to_vec,
+};
+
+const TREP_PREFIX: u8 = 0x76;
+const SECONDS_PER_DAY: u32 = 86400;
+const VEHICLE_IDENTIFICATION_NUMBER_LENGTH: usize = 17;
+const VEHICLE_REGISTRATION_NUMBER_LENGTH: usize = 13;
+const GEN1_CERTIFICATE_LENGTH: usize = 194;
+const GEN2_CERTIFICATE_LENGTH: u16 = 204;
+const GEN1_SIGNATURE_LENGTH: usize = 128;
+const GEN2_SIGNATURE_LENGTH: u16 = 64;
+const GEN1_VU_EVENT_RECORD_LENGTH: usize = 83;
+const GEN1_VU_FAULT_RECORD_LENGTH: usize = 82;
+const GEN2_VU_EVENT_RECORD_LENGTH: u16 = 91;
+const GEN2_VU_FAULT_RECORD_LENGTH: u16 = 90;
+const GEN2_VU_CONTROL_ACTIVITY_RECORD_LENGTH: u16 = 32;
+/// Generation of the control cards of the control activities of a generation 2 VU.
+const GEN2_CONTROL_CARD_GENERATION: u8 = 2;
+const GEN2_VU_CARD_IW_RECORD_LENGTH: u16 = 131;
+const SPEED_BLOCK_LENGTH: u16 = 64;
+/// An empty record array of the records written with `write_bytes`.
+const NO_RECORDS: &[&[u8]] = &[];
+const CARD_NUMBER_LENGTH: usize = 16;
+const NAME_LENGTH: usize = 35;
+const EXTENDED_SERIAL_NUMBER_LENGTH: usize = 8;
+const VU_PART_NUMBER_LENGTH: usize = 16;
+const VU_SOFTWARE_VERSION_LENGTH: usize = 4;
+const GEN1_APPROVAL_NUMBER_LENGTH: usize = 8;
+const GEN2_APPROVAL_NUMBER_LENGTH: usize = 16;
+const VU_DIGITAL_MAP_VERSION_LENGTH: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum VuGeneration {
+    Gen1,
+    Gen2,
+    Gen2v2,
+}
+
+/// Builds the DDD file of a vehicle unit download of generation 1, generation 2 or
+/// generation 2 version 2.
+///
+/// The file contains the overview with the controls, an activities TREP for each activity
+/// day, the events and faults, the detailed speed and the technical data TREPs. The records not
+/// set on the builder are empty, with the text fields padded with spaces as a VU writes
+/// them. The certificates and the signatures are filled with
+/// zeros, `Gen1TestPki::sign_vu_ddd` and `Gen2TestPki::sign_vu_ddd` sign the built
+/// files with a test PKI.
+#[derive(Debug)]
+pub struct VuDddBuilder {
+    generation: VuGeneration,
+    vehicle_identification_number: String,
+    vehicle_registration_nation: u8,
+    vehicle_registration_number: String,
+    current_date_time: u32,
+    activity_days: Vec<ActivityDay>,
+    controls: Vec<ControlRecord>,
+    events: Vec<EventRecord>,
+    faults: Vec<EventRecord>,
+    speed_blocks: Vec<SpeedBlock>,
+}
+
+impl VuDddBuilder {
+    fn new(generation: VuGeneration) -> Self {
+        Self {
+            generation,
+            vehicle_identification_number: String::new(),
+            vehicle_registration_nation: 0,
+            vehicle_registration_number: String::new(),
+            current_date_time: 0,
+            activity_days: Vec::new(),
+            controls: Vec::new(),
+            events: Vec::new(),
+            faults: Vec::new(),
+            speed_blocks: Vec::new(),
+        }
+    }
+
+    pub fn gen1() -> Self {
+        Self::new(VuGeneration::Gen1)
+    }
+
+    pub fn gen2() -> Self {
+        Self::new(VuGeneration::Gen2)
+    }
+
+    pub fn gen2v2() -> Self {
+        Self::new(VuGeneration::Gen2v2)
+    }
+
+    pub fn with_vehicle_identification_number(mut self, vehicle_identification_number: &str) -> Self {
+        self.vehicle_identification_number = vehicle_identification_number.to_owned();
+        self
+    }
+
+    /// Sets the vehicle registration, the nation is a `NationNumeric` code. A generation 2
+    /// overview only contains the registration number.
+    pub fn with_vehicle_registration(mut self, nation: u8, number: &str) -> Self {
+        self.vehicle_registration_nation = nation;
+        self.vehicle_registration_number = number.to_owned();
+        self
+    }
+
+    /// Sets the time of the download, in seconds since 00h00mn00s on 1 January 1970 GMT.
+    pub fn with_current_date_time(mut self, current_date_time: u32) -> Self {
+        self.current_date_time = current_date_time;
+        self
+    }
+
+    /// Adds a downloaded day, the days are written in the order they are added. The
+    /// odometer at midnight of a day is the sum of the distances of the previous days.
+    pub fn with_activity_day(mut self, activity_day: ActivityDay) -> Self {
+        self.activity_days.push(activity_day);
+        self
+    }
+
+    /// Adds a control performed with the VU, the downloaded period of the control is the
+    /// downloadable period of the VU.
+    pub fn with_control(mut self, control: ControlRecord) -> Self {
+        self.controls.push(control);
+        self
+    }
+
+    pub fn with_event(mut self, event: EventRecord) -> Self {
+        self.events.push(event);
+        self
+    }
+
+    pub fn with_fault(mut self, fault: EventRecord) -> Self {
+        self.faults.push(fault);
+        self
+    }
+
+    pub fn with_speed_block(mut self, speed_block: SpeedBlock) -> Self {
+        self.speed_blocks.push(speed_block);
+        self
+    }
+
+    /// Returns the DDD file of the download.
+    pub fn build(&self) -> Result<Vec<u8>> {
+        write_to_vec(|writer| {
+            self.write_trep(writer, self.trep_id(VUTransferResponseParameterID::Overview), |writer| self.write_overview(writer))?;
+            let mut odometer_value_midnight: u32 = 0;
+            for activity_day in self.activity_days.iter() {
+                self.write_trep(writer, self.trep_id(VUTransferResponseParameterID::Activities), |writer| {
+                    self.write_activities(writer, activity_day, odometer_value_midnight)
+                })?;
+                odometer_value_midnight += activity_day.distance as u32;
+            }
+            self.write_trep(writer, self.trep_id(VUTransferResponseParameterID::EventsAndFaults), |writer| {
+                self.write_events_and_faults(writer)
+            })?;
+            self.write_trep(writer, self.trep_id(VUTransferResponseParameterID::Speed), |writer| self.write_speed(writer))?;
+            self.write_trep(writer, self.trep_id(VUTransferResponseParameterID::TechnicalData), |writer| {
+                self.write_technical_data(writer)
+            })
+        })
+    }
+
+    fn is_gen1(&self) -> bool {
+        self.generation == VuGeneration::Gen1
+    }
+
+    fn is_gen2_v2(&self) -> bool {
+        self.generation == VuGeneration::Gen2v2
+    }
+
+    /// Returns the TREP of the generation of the VU for the generation 1 `trep_id`.
+    fn trep_id(&self, trep_id: VUTransferResponseParameterID) -> u8 {
+        let trep_id = trep_id as u8;
+        match self.generation {
+            VuGeneration::Gen1 => trep_id,
+            VuGeneration::Gen2 => trep_id + 0x20,
+            VuGeneration::Gen2v2 => trep_id + 0x30,
+        }
+    }
+
+    fn write_trep<W: WriteBytes + BinSeek>(
+        &self,
+        writer: &mut W,
+        trep_id: u8,
+        write: impl FnOnce(&mut W) -> Result<()>,
+    ) -> Result<()> {
+        writer.write_u8(TREP_PREFIX)?;
+        writer.write_u8(trep_id)?;
+        write(writer)?;
+        if self.is_gen1() {
+            writer.write_all(&[0; GEN1_SIGNATURE_LENGTH])?;
+        } else {
+            write_record_array(
+                writer,
+                &RecordType::Signature,
+                GEN2_SIGNATURE_LENGTH,
+                &[[0; GEN2_SIGNATURE_LENGTH as usize]],
+                write_bytes,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Returns the first and the last downloadable time, the days of the activities or the
+    /// current time without activities.
+    fn downloadable_period(&self) -> [u8; 8] {
+        let min_downloadable_time =
+            self.activity_days.iter().map(|activity_day| activity_day.date).min().unwrap_or(self.current_date_time);
+        let max_downloadable_time = self
+            .activity_days
+            .iter()
+            .map(|activity_day| activity_day.date + SECONDS_PER_DAY - 1)
+            .max()
+            .unwrap_or(self.current_date_time);
+        let mut downloadable_period = [0; 8];
+        downloadable_period[..4].copy_from_slice(&min_downloadable_time.to_be_bytes());
+        downloadable_period[4..].copy_from_slice(&max_downloadable_time.to_be_bytes());
+        downloadable_period
+    }
+
+    fn vehicle_registration_number(&self) -> Result<Vec<u8>> {
+        write_to_vec(|writer| {
+            writer.write_u8(CodePage::IsoIec8859_1 as u8)?;
+            write_string(writer, &self.vehicle_registration_number, &CodePage::IsoIec8859_1, VEHICLE_REGISTRATION_NUMBER_LENGTH)
+        })
+    }
+
+    /// Returns the download activity data of a VU which was not downloaded yet.
+    fn download_activity_data(&self) -> Result<Vec<u8>> {
+        write_to_vec(|writer| self.write_download_activity_data(writer))
+    }
+
+    fn write_download_activity_data<W: WriteBytes>(&self, writer: &mut W) -> Result<()> {
+        // The downloading time, the card type and the nation of the card.
+        writer.write_all(&[0; 4 + 1 + 1])?;
+        write_ia5_string(writer, "", CARD_NUMBER_LENGTH)?;
+        if !self.is_gen1() {
+            writer.write_u8(0)?;
+        }
+        writer.write_u8(CodePage::IsoIec8859_1 as u8)?;
+        write_string(writer, "", &CodePage::IsoIec8859_1, NAME_LENGTH)
+    }
+
+    /// Returns the identification of a VU without manufacturer, numbers and versions.
+    fn vu_identification(&self) -> Result<Vec<u8>> {
+        write_to_vec(|writer| self.write_vu_identification(writer))
+    }
+
+    fn write_vu_identification<W: WriteBytes>(&self, writer: &mut W) -> Result<()> {
+        // The name and the address of the manufacturer.
+        for _ in 0..2 {
+            writer.write_u8(CodePage::IsoIec8859_1 as u8)?;
+            write_string(writer, "", &CodePage::IsoIec8859_1, NAME_LENGTH)?;
+        }
+        write_ia5_string(writer, "", VU_PART_NUMBER_LENGTH)?;
+        writer.write_all(&[0; EXTENDED_SERIAL_NUMBER_LENGTH])?;
+        write_ia5_string(writer, "", VU_SOFTWARE_VERSION_LENGTH)?;
+        // The software installation date and the manufacturing date.
+        writer.write_all(&[0; 4 + 4])?;
+        if self.is_gen1() {
+            return write_ia5_string(writer, "", GEN1_APPROVAL_NUMBER_LENGTH);
+        }
+        write_ia5_string(writer, "", GEN2_APPROVAL_NUMBER_LENGTH)?;
+        // The generation and the ability of the VU.
+        writer.write_all(&[0; 2])?;
+        if self.is_gen2_v2() {
+            write_ia5_string(writer, "", VU_DIGITAL_MAP_VERSION_LENGTH)?;
+        }
+        Ok(())
+    }
+
+    fn write_overview<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        let vehicle_identification_number = write_to_vec(|writer| {
+            write_ia5_string(writer, &self.vehicle_identification_number, VEHICLE_IDENTIFICATION_NUMBER_LENGTH)
+        })?;
+        let mut vehicle_registration_identification = vec![self.vehicle_registration_nation];
+        vehicle_registration_identification.extend(self.vehicle_registration_number()?);
+        let current_date_time = self.current_date_time.to_be_bytes();
+
+        if self.is_gen1() {
+            writer.write_all(&[0; 2 * GEN1_CERTIFICATE_LENGTH])?;
+            writer.write_all(&vehicle_identification_number)?;
+            writer.write_all(&vehicle_registration_identification)?;
+            writer.write_all(&current_date_time)?;
+            writer.write_all(&self.downloadable_period())?;
+            // Card slot status.
+            writer.write_u8(0)?;
+            writer.write_all(&self.download_activity_data()?)?;
+            // No company locks.
+            writer.write_u8(0)?;
+            writer.write_u8(record_count(self.controls.len())?)?;
+            for control in self.controls.iter() {
+                control.write_vu_record(writer, None, &self.downloadable_period())?;
+            }
+            return Ok(());
+        }
+
+        let certificate = [[0; GEN2_CERTIFICATE_LENGTH as usize]];
+        write_record_array(writer, &RecordType::MemberStateCertificate, GEN2_CERTIFICATE_LENGTH, &certificate, write_bytes)?;
+        write_record_array(writer, &RecordType::VuCertificate, GEN2_CERTIFICATE_LENGTH, &certificate, write_bytes)?;
+        write_record_array(writer, &RecordType::VehicleIdentificationNumber, 17, &[vehicle_identification_number], write_bytes)?;
+        if self.is_gen2_v2() {
+            write_record_array(
+                writer,
+                &RecordType::VehicleRegistrationIdentification,
+                15,
+                &[vehicle_registration_identification],
+                write_bytes,
+            )?;
+        } else {
+            write_record_array(
+                writer,
+                &RecordType::VehicleRegistrationNumber,
+                14,
+                &[self.vehicle_registration_number()?],
+                write_bytes,
+            )?;
+        }
+        write_record_array(writer, &RecordType::CurrentDateTime, 4, &[current_date_time], write_bytes)?;
+        write_record_array(writer, &RecordType::VuDownloadablePeriod, 8, &[self.downloadable_period()], write_bytes)?;
+        write_record_array(writer, &RecordType::CardSlotStatus, 1, &[[0]], write_bytes)?;
+        write_record_array(writer, &RecordType::VuDownloadActivityData, 59, &[self.download_activity_data()?], write_bytes)?;
+        write_record_array(writer, &RecordType::VuCompanyLocksRecord, 99, NO_RECORDS, write_bytes)?;
+        write_record_array(
+            writer,
+            &RecordType::VuControlActivityRecord,
+            GEN2_VU_CONTROL_ACTIVITY_RECORD_LENGTH,
+            &self.controls,
+            |writer, control| control.write_vu_record(writer, Some(GEN2_CONTROL_CARD_GENERATION), &self.downloadable_period()),
+        )
+    }
+
+    fn write_activities<W: WriteBytes + BinSeek>(
+        &self,
+        writer: &mut W,
+        activity_day: &ActivityDay,
+        odometer_value_midnight: u32,
+    ) -> Result<()> {
+        let date_of_day_downloaded = activity_day.date.to_be_bytes();
+        let odometer_value_midnight = &odometer_value_midnight.to_be_bytes()[1..];
+        let activity_change_infos = activity_day.activity_change_infos()?;
+
+        if self.is_gen1() {
+            writer.write_all(&date_of_day_downloaded)?;
+            writer.write_all(odometer_value_midnight)?;
+            // No card insertion and withdrawal records.
+            writer.write_u16::<BigEndian>(0)?;
+            writer.write_u16::<BigEndian>(record_count(activity_change_infos.len())?)?;
+            for activity_change_info in activity_change_infos.iter() {
+                writer.write_u16::<BigEndian>(*activity_change_info)?;
+            }
+            // No place records and no specific condition records.
+            writer.write_all(&[0; 3])?;
+            return Ok(());
+        }
+
+        let is_gen2_v2 = self.is_gen2_v2();
+        write_record_array(writer, &RecordType::DateOfDayDownloaded, 4, &[date_of_day_downloaded], write_bytes)?;
+        write_record_array(writer, &RecordType::OdometerValueMidnight, 3, &[odometer_value_midnight], write_bytes)?;
+        write_record_array(writer, &RecordType::VuCardIWRecord, GEN2_VU_CARD_IW_RECORD_LENGTH, NO_RECORDS, write_bytes)?;
+        write_record_array(
+            writer,
+            &RecordType::ActivityChangeInfo,
+            2,
+            &activity_change_infos,
+            |writer, activity_change_info| {
+                writer.write_u16::<BigEndian>(*activity_change_info)?;
+                Ok(())
+            },
+        )?;
+        let place_record_length = if is_gen2_v2 { 41 } else { 40 };
+        write_record_array(writer, &RecordType::VuPlaceDailyWorkPeriodRecord, place_record_length, NO_RECORDS, write_bytes)?;
+        let gnss_record_length = if is_gen2_v2 { 57 } else { 56 };
+        write_record_array(
+            writer,
+            &RecordType::VuGNSSADRecord,
+            gnss_record_length,
+            &activity_day.gnss_records,
+            |writer, record| record.write_vu_record(writer, is_gen2_v2),
+        )?;
+        write_record_array(writer, &RecordType::SpecificConditionRecord, 5, NO_RECORDS, write_bytes)?;
+        if is_gen2_v2 {
+            write_record_array(writer, &RecordType::VuBorderCrossingRecord, 55, NO_RECORDS, write_bytes)?;
+            write_record_array(writer, &RecordType::VuLoadUnloadRecord, 58, NO_RECORDS, write_bytes)?;
+        }
+        Ok(())
+    }
+
+    fn write_events_and_faults<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        if self.is_gen1() {
+            writer.write_u8(record_count(self.faults.len())?)?;
+            for fault in self.faults.iter() {
+                fault.write_vu_record(writer, GEN1_VU_FAULT_RECORD_LENGTH)?;
+            }
+            writer.write_u8(record_count(self.events.len())?)?;
+            for event in self.events.iter() {
+                event.write_vu_record(writer, GEN1_VU_EVENT_RECORD_LENGTH)?;
+            }
+            // The over speeding control data, no over speeding events and no time adjustments.
+            writer.write_all(&[0; 9 + 1 + 1])?;
+            return Ok(());
+        }
+
+        write_record_array(writer, &RecordType::VuFaultRecord, GEN2_VU_FAULT_RECORD_LENGTH, &self.faults, |writer, fault| {
+            fault.write_vu_record(writer, GEN2_VU_FAULT_RECORD_LENGTH as usize)
+        })?;
+        write_record_array(writer, &RecordType::VuEventRecord, GEN2_VU_EVENT_RECORD_LENGTH, &self.events, |writer, event| {
+            event.write_vu_record(writer, GEN2_VU_EVENT_RECORD_LENGTH as usize)
+        })?;
+        write_record_array(writer, &RecordType::VuOverSpeedingControlData, 9, &[[0; 9]], write_bytes)?;
+        write_record_array(writer, &RecordType::VuOverSpeedingEventRecord, 32, NO_RECORDS, write_bytes)?;
+        write_record_array(writer, &RecordType::VuTimeAdjustmentRecord, 99, NO_RECORDS, write_bytes)
+    }
+
+    fn write_speed<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        if self.is_gen1() {
+            writer.write_u16::<BigEndian>(record_count(self.speed_blocks.len())?)?;
+            for speed_block in self.speed_blocks.iter() {
+                speed_block.write(writer)?;
+            }
+            return Ok(());
+        }
+        write_record_array(
+            writer,
+            &RecordType::VuDetailedSpeedBlock,
+            SPEED_BLOCK_LENGTH,
+            &self.speed_blocks,
+            |writer, speed_block| speed_block.write(writer),
+        )
+    }
+
+    fn write_technical_data<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        let vu_identification = self.vu_identification()?;
+        if self.is_gen1() {
+            writer.write_all(&vu_identification)?;
+            // The paired sensor and no calibration records.
+            writer.write_all(&[0; EXTENDED_SERIAL_NUMBER_LENGTH])?;
+            write_ia5_string(writer, "", GEN1_APPROVAL_NUMBER_LENGTH)?;
+            writer.write_all(&[0; 4 + 1])?;
+            return Ok(());
+        }
+
+        let is_gen2_v2 = self.is_gen2_v2();
+        let vu_identification_length = if is_gen2_v2 { 138 } else { 126 };
+        write_record_array(writer, &RecordType::VuIdentification, vu_identification_length, &[vu_identification], write_bytes)?;
+        write_record_array(writer, &RecordType::SensorPairedRecord, 28, NO_RECORDS, write_bytes)?;
+        write_record_array(writer, &RecordType::SensorExternalGNSSCoupledRecord, 28, NO_RECORDS, write_bytes)?;
+        let calibration_record_length = if is_gen2_v2 { 252 } else { 222 };
+        write_record_array(writer, &RecordType::VuCalibrationRecord, calibration_record_length, NO_RECORDS, write_bytes)?;
+        write_record_array(writer, &RecordType::VuCardRecord, 45, NO_RECORDS, write_bytes)?;
+        write_record_array(writer, &RecordType::VuITSConsentRecord, 20, NO_RECORDS, write_bytes)?;
+        write_record_array(writer, &RecordType::VuPowerSupplyInterruptionRecord, 87, NO_RECORDS, write_bytes)
+    }
+}
+
+/// Writes a record of the bytes of `record`.
+fn write_bytes<B: AsRef<[u8]>>(writer: &mut BinMemoryBuffer, record: &B) -> Result<()> {
+    let record = record.as_ref();
+    write_octet_string(writer, record, record.len())
+}
@@ -0,0 +1,438 @@
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+/// Object identifier of the brainpoolP256r1 curve, as encoded in the domain parameters
+/// of a Gen2 certificate.
+pub const BRAINPOOL_P256R1_OID: [u8; 9] = [0x2B, 0x24, 0x03, 0x03, 0x02, 0x08, 0x01, 0x01, 0x07];
+/// Object identifier of the brainpoolP384r1 curve.
+pub const BRAINPOOL_P384R1_OID: [u8; 9] = [0x2B, 0x24, 0x03, 0x03, 0x02, 0x08, 0x01, 0x01, 0x0B];
+/// Object identifier of the brainpoolP512r1 curve.
+pub const BRAINPOOL_P512R1_OID: [u8; 9] = [0x2B, 0x24, 0x03, 0x03, 0x02, 0x08, 0x01, 0x01, 0x0D];
+/// Object identifier of the NIST P-256 curve (secp256r1).
+pub const NIST_P256_OID: [u8; 8] = [0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x03, 0x01, 0x07];
+/// Object identifier of the NIST P-384 curve (secp384r1).
+pub const NIST_P384_OID: [u8; 5] = [0x2B, 0x81, 0x04, 0x00, 0x22];
+/// Object identifier of the NIST P-521 curve (secp521r1).
+pub const NIST_P521_OID: [u8; 5] = [0x2B, 0x81, 0x04, 0x00, 0x23];
+
+/// A point of an elliptic curve in affine coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EccPoint {
+    Infinity,
+    Affine(BigUint, BigUint),
+}
+
+/// A point `(X, Y, Z)` in Jacobian coordinates, the affine point `(X / Z^2, Y / Z^3)`.
+type JacobianPoint = (BigUint, BigUint, BigUint);
+
+/// An elliptic curve `y^2 = x^3 + ax + b` over the prime field of `p`, with the base
+/// point of order `n`. The curves are the standardized domain parameters of Appendix 11.
+#[derive(Debug)]
+pub struct EccCurve {
+    /// Object identifier of the curve, only used to write the certificates of the test PKI.
+    #[cfg(any(test, feature = "test-fixtures"))]
+    pub oid: &'static [u8],
+    pub p: BigUint,
+    pub a: BigUint,
+    pub b: BigUint,
+    pub g: EccPoint,
+    pub n: BigUint,
+    /// Size in bytes of a coordinate of a point.
+    pub size: usize,
+}
+
+fn from_hex(hex: &str) -> BigUint {
+    BigUint::parse_bytes(hex.as_bytes(), 16).expect("valid curve parameter")
+}
+
+impl EccCurve {
+    pub fn brainpool_p256r1() -> Self {
+        Self {
+            #[cfg(any(test, feature = "test-fixtures"))]
+            oid: &BRAINPOOL_P256R1_OID,
+            p: from_hex("A9FB57DBA1EEA9BC3E660A909D838D726E3BF623D52620282013481D1F6E5377"),
+            a: from_hex("7D5A0975FC2C3057EEF67530417AFFE7FB8055C126DC5C6CE94A4B44F330B5D9"),
+            b: from_hex("26DC5C6CE94A4B44F330B5D9BBD77CBF958416295CF7E1CE6BCCDC18FF8C07B6"),
+            g: EccPoint::Affine(
+                from_hex("8BD2AEB9CB7E57CB2C4B482FFC81B7AFB9DE27E1E3BD23C23A4453BD9ACE3262"),
+                from_hex("547EF835C3DAC4FD97F8461A14611DC9C27745132DED8E545C1D54C72F046997"),
+            ),
+            n: from_hex("A9FB57DBA1EEA9BC3E660A909D838D718C397AA3B561A6F7901E0E82974856A7"),
+            size: 32,
+        }
+    }
+
+    pub fn brainpool_p384r1() -> Self {
+        Self {
+            #[cfg(any(test, feature = "test-fixtures"))]
+            oid: &BRAINPOOL_P384R1_OID,
+            p: from_hex("8CB91E82A3386D280F5D6F7E50E641DF152F7109ED5456B412B1DA197FB71123ACD3A729901D1A71874700133107EC53"),
+            a: from_hex("7BC382C63D8C150C3C72080ACE05AFA0C2BEA28E4FB22787139165EFBA91F90F8AA5814A503AD4EB04A8C7DD22CE2826"),
+            b: from_hex("04A8C7DD22CE28268B39B55416F0447C2FB77DE107DCD2A62E880EA53EEB62D57CB4390295DBC9943AB78696FA504C11"),
+            g: EccPoint::Affine(
+                from_hex("1D1C64F068CF45FFA2A63A81B7C13F6B8847A3E77EF14FE3DB7FCAFE0CBD10E8E826E03436D646AAEF87B2E247D4AF1E"),
+                from_hex("8ABE1D7520F9C2A45CB1EB8E95CFD55262B70B29FEEC5864E19C054FF99129280E4646217791811142820341263C5315"),
+            ),
+            n: from_hex("8CB91E82A3386D280F5D6F7E50E641DF152F7109ED5456B31F166E6CAC0425A7CF3AB6AF6B7FC3103B883202E9046565"),
+            size: 48,
+        }
+    }
+
+    pub fn brainpool_p512r1() -> Self {
+        Self {
+            #[cfg(any(test, feature = "test-fixtures"))]
+            oid: &BRAINPOOL_P512R1_OID,
+            p: from_hex(
+                "AADD9DB8DBE9C48B3FD4E6AE33C9FC07CB308DB3B3C9D20ED6639CCA703308717D4D9B009BC66842AECDA12AE6A380E62881FF2F2D82C68528AA6056583A48F3",
+            ),
+            a: from_hex(
+                "7830A3318B603B89E2327145AC234CC594CBDD8D3DF91610A83441CAEA9863BC2DED5D5AA8253AA10A2EF1C98B9AC8B57F1117A72BF2C7B9E7C1AC4D77FC94CA",
+            ),
+            b: from_hex(
+                "3DF91610A83441CAEA9863BC2DED5D5AA8253AA10A2EF1C98B9AC8B57F1117A72BF2C7B9E7C1AC4D77FC94CADC083E67984050B75EBAE5DD2809BD638016F723",
+            ),
+            g: EccPoint::Affine(
+                from_hex(
+                    "81AEE4BDD82ED9645A21322E9C4C6A9385ED9F70B5D916C1B43B62EEF4D0098EFF3B1F78E2D0D48D50D1687B93B97D5F7C6D5047406A5E688B352209BCB9F822",
+                ),
+                from_hex(
+                    "7DDE385D566332ECC0EABFA9CF7822FDF209F70024A57B1AA000C55B881F8111B2DCDE494A5F485E5BCA4BD88A2763AED1CA2B2FA8F0540678CD1E0F3AD80892",
+                ),
+            ),
+            n: from_hex(
+                "AADD9DB8DBE9C48B3FD4E6AE33C9FC07CB308DB3B3C9D20ED6639CCA70330870553E5C414CA92619418661197FAC10471DB1D381085DDADDB58796829CA90069",
+            ),
+            size: 64,
+        }
+    }
+
+    pub fn nist_p256() -> Self {
+        Self {
+            #[cfg(any(test, feature = "test-fixtures"))]
+            oid: &NIST_P256_OID,
+            p: from_hex("FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFF"),
+            a: from_hex("FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFC"),
+            b: from_hex("5AC635D8AA3A93E7B3EBBD55769886BC651D06B0CC53B0F63BCE3C3E27D2604B"),
+            g: EccPoint::Affine(
+                from_hex("6B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296"),
+                from_hex("4FE342E2FE1A7F9B8EE7EB4A7C0F9E162BCE33576B315ECECBB6406837BF51F5"),
+            ),
+            n: from_hex("FFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551"),
+            size: 32,
+        }
+    }
+
+    pub fn nist_p384() -> Self {
+        Self {
+            #[cfg(any(test, feature = "test-fixtures"))]
+            oid: &NIST_P384_OID,
+            p: from_hex("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFFFF0000000000000000FFFFFFFF"),
+            a: from_hex("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFFFF0000000000000000FFFFFFFC"),
+            b: from_hex("B3312FA7E23EE7E4988E056BE3F82D19181D9C6EFE8141120314088F5013875AC656398D8A2ED19D2A85C8EDD3EC2AEF"),
+            g: EccPoint::Affine(
+                from_hex("AA87CA22BE8B05378EB1C71EF320AD746E1D3B628BA79B9859F741E082542A385502F25DBF55296C3A545E3872760AB7"),
+                from_hex("3617DE4A96262C6F5D9E98BF9292DC29F8F41DBD289A147CE9DA3113B5F0B8C00A60B1CE1D7E819D7A431D7C90EA0E5F"),
+            ),
+            n: from_hex("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFC7634D81F4372DDF581A0DB248B0A77AECEC196ACCC52973"),
+            size: 48,
+        }
+    }
+
+    pub fn nist_p521() -> Self {
+        Self {
+            #[cfg(any(test, feature = "test-fixtures"))]
+            oid: &NIST_P521_OID,
+            p: from_hex(
+                "01FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+            ),
+            a: from_hex(
+                "01FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFC",
+            ),
+            b: from_hex(
+                "0051953EB9618E1C9A1F929A21A0B68540EEA2DA725B99B315F3B8B489918EF109E156193951EC7E937B1652C0BD3BB1BF073573DF883D2C34F1EF451FD46B503F00",
+            ),
+            g: EccPoint::Affine(
+                from_hex(
+                    "00C6858E06B70404E9CD9E3ECB662395B4429C648139053FB521F828AF606B4D3DBAA14B5E77EFE75928FE1DC127A2FFA8DE3348B3C1856A429BF97E7E31C2E5BD66",
+                ),
+                from_hex(
+                    "011839296A789A3BC0045C8A5FB42C7D1BD998F54449579B446817AFBD17273E662C97EE72995EF42640C550B9013FAD0761353C7086A272C24088BE94769FD16650",
+                ),
+            ),
+            n: from_hex(
+                "01FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFA51868783BF2F966B7FCC0148F709A5D03BB5C9B8899C47AEBB6FB71E91386409",
+            ),
+            size: 66,
+        }
+    }
+
+    /// Returns the curve with the object identifier `oid` of the domain parameters of a
+    /// certificate, or `None` when the curve is not one of the curves of Appendix 11.
+    pub fn from_oid(oid: &[u8]) -> Option<Self> {
+        match oid {
+            oid if oid == BRAINPOOL_P256R1_OID => Some(Self::brainpool_p256r1()),
+            oid if oid == BRAINPOOL_P384R1_OID => Some(Self::brainpool_p384r1()),
+            oid if oid == BRAINPOOL_P512R1_OID => Some(Self::brainpool_p512r1()),
+            oid if oid == NIST_P256_OID => Some(Self::nist_p256()),
+            oid if oid == NIST_P384_OID => Some(Self::nist_p384()),
+            oid if oid == NIST_P521_OID => Some(Self::nist_p521()),
+            _ => None,
+        }
+    }
+
+    fn sub(&self, x: &BigUint, y: &BigUint) -> BigUint {
+        (x + &self.p - (y % &self.p)) % &self.p
+    }
+
+    fn inverse(&self, x: &BigUint) -> BigUint {
+        x.modinv(&self.p).expect("non zero field element")
+    }
+
+    /// Returns true when `point` satisfies the equation of the curve.
+    pub fn is_on_curve(&self, point: &EccPoint) -> bool {
+        match point {
+            EccPoint::Infinity => true,
+            EccPoint::Affine(x, y) => (y * y) % &self.p == (x * x * x + &self.a * x + &self.b) % &self.p,
+        }
+    }
+
+    pub fn add(&self, point: &EccPoint, other: &EccPoint) -> EccPoint {
+        let (x1, y1, x2, y2) = match (point, other) {
+            (EccPoint::Infinity, _) => return other.clone(),
+            (_, EccPoint::Infinity) => return point.clone(),
+            (EccPoint::Affine(x1, y1), EccPoint::Affine(x2, y2)) => (x1, y1, x2, y2),
+        };
+        let lambda = if x1 == x2 {
+            if (y1 + y2) % &self.p == BigUint::ZERO {
+                return EccPoint::Infinity;
+            }
+            let numerator = (BigUint::from(3u8) * x1 * x1 + &self.a) % &self.p;
+            numerator * self.inverse(&(BigUint::from(2u8) * y1)) % &self.p
+        } else {
+            self.sub(y2, y1) * self.inverse(&self.sub(x2, x1)) % &self.p
+        };
+        let x3 = self.sub(&(&lambda * &lambda), &(x1 + x2));
+        let y3 = self.sub(&(&lambda * self.sub(x1, &x3)), y1);
+        EccPoint::Affine(x3, y3)
+    }
+
+    /// Returns `scalar` times `point`, computed by double and add in Jacobian coordinates,
+    /// so only the result is inverted.
+    pub fn multiply(&self, scalar: &BigUint, point: &EccPoint) -> EccPoint {
+        let EccPoint::Affine(x, y) = point else {
+            return EccPoint::Infinity;
+        };
+        let mut result: Option<JacobianPoint> = None;
+        for bit in (0..scalar.bits()).rev() {
+            result = result.and_then(|result| self.double_jacobian(&result));
+            if scalar.bit(bit) {
+                result = match result {
+                    Some(result) => self.add_jacobian(&result, x, y),
+                    None => Some((x.clone(), y.clone(), BigUint::from(1u8))),
+                };
+            }
+        }
+        match result {
+            None => EccPoint::Infinity,
+            Some((x, y, z)) => {
+                let z_inverse = self.inverse(&z);
+                let z_inverse_2 = &z_inverse * &z_inverse % &self.p;
+                EccPoint::Affine(&x * &z_inverse_2 % &self.p, y * z_inverse_2 * z_inverse % &self.p)
+            }
+        }
+    }
+
+    /// Returns twice the Jacobian `point`, `None` for the point at infinity.
+    fn double_jacobian(&self, (x, y, z): &JacobianPoint) -> Option<JacobianPoint> {
+        if *y == BigUint::ZERO {
+            return None;
+        }
+        let y_2 = y * y % &self.p;
+        let s = BigUint::from(4u8) * x * &y_2 % &self.p;
+        let z_2 = z * z % &self.p;
+        let m = (BigUint::from(3u8) * x * x + &self.a * &z_2 * &z_2) % &self.p;
+        let x3 = self.sub(&(&m * &m), &(BigUint::from(2u8) * &s));
+        let y3 = self.sub(&(&m * self.sub(&s, &x3)), &(BigUint::from(8u8) * &y_2 * &y_2));
+        let z3 = BigUint::from(2u8) * y * z % &self.p;
+        Some((x3, y3, z3))
+    }
+
+    /// Returns the sum of the Jacobian `point` and the affine point `(x2, y2)`, `None` for
+    /// the point at infinity.
+    fn add_jacobian(&self, point: &JacobianPoint, x2: &BigUint, y2: &BigUint) -> Option<JacobianPoint> {
+        let (x1, y1, z1) = point;
+        let z1_2 = z1 * z1 % &self.p;
+        let u2 = x2 * &z1_2 % &self.p;
+        let s2 = y2 * &z1_2 * z1 % &self.p;
+        if *x1 == u2 {
+            return if *y1 == s2 { self.double_jacobian(point) } else { None };
+        }
+        let h = self.sub(&u2, x1);
+        let r = self.sub(&s2, y1);
+        let h_2 = &h * &h % &self.p;
+        let h_3 = &h_2 * &h % &self.p;
+        let x1_h_2 = x1 * &h_2 % &self.p;
+        let x3 = self.sub(&self.sub(&(&r * &r), &h_3), &(BigUint::from(2u8) * &x1_h_2));
+        let y3 = self.sub(&(&r * self.sub(&x1_h_2, &x3)), &(y1 * &h_3));
+        let z3 = z1 * h % &self.p;
+        Some((x3, y3, z3))
+    }
+
+    /// Returns `value` as a big-endian integer of the size of a coordinate.
+    #[cfg(any(test, feature = "test-fixtures"))]
+    pub fn encode_integer(&self, value: &BigUint) -> Vec<u8> {
+        let bytes = value.to_bytes_be();
+        let mut encoded = vec![0; self.size.saturating_sub(bytes.len())];
+        encoded.extend_from_slice(&bytes);
+        encoded
+    }
+
+    /// Returns `point` in the uncompressed encoding `04 || x || y` of a public point.
+    #[cfg(any(test, feature = "test-fixtures"))]
+    pub fn encode_point(&self, point: &EccPoint) -> Vec<u8> {
+        match point {
+            EccPoint::Infinity => vec![0x00],
+            EccPoint::Affine(x, y) => [vec![0x04], self.encode_integer(x), self.encode_integer(y)].concat(),
+        }
+    }
+
+    /// Returns the point of the uncompressed encoding `04 || x || y`, or `None` when the
+    /// encoding is not valid or the point is not on the curve.
+    pub fn decode_point(&self, data: &[u8]) -> Option<EccPoint> {
+        if data.len() != 1 + 2 * self.size || data[0] != 0x04 {
+            return None;
+        }
+        let x = BigUint::from_bytes_be(&data[1..1 + self.size]);
+        let y = BigUint::from_bytes_be(&data[1 + self.size..]);
+        if x >= self.p || y >= self.p {
+            return None;
+        }
+        let point = EccPoint::Affine(x, y);
+        self.is_on_curve(&point).then_some(point)
+    }
+
+    /// Returns the hash of `data` with the algorithm of the key size of the curve, as
+    /// defined by the cipher suites of Appendix 11: SHA-256 for 256 bit keys, SHA-384
+    /// for 384 bit keys and SHA-512 for 512 and 521 bit keys.
+    pub fn hash(&self, data: &[u8]) -> Vec<u8> {
+        match self.size {
+            0..=32 => Sha256::digest(data).to_vec(),
+            33..=48 => Sha384::digest(data).to_vec(),
+            _ => Sha512::digest(data).to_vec(),
+        }
+    }
+
+    /// Returns true when `signature`, the plain encoding `r || s` of an ECDSA signature,
+    /// is the signature of `data` by the key of `public_point`.
+    pub fn verify(&self, public_point: &EccPoint, data: &[u8], signature: &[u8]) -> bool {
+        if signature.len() != 2 * self.size || *public_point == EccPoint::Infinity || !self.is_on_curve(public_point) {
+            return false;
+        }
+        let r = BigUint::from_bytes_be(&signature[..self.size]);
+        let s = BigUint::from_bytes_be(&signature[self.size..]);
+        if r == BigUint::ZERO || r >= self.n || s == BigUint::ZERO || s >= self.n {
+            return false;
+        }
+
+        let hash = self.hash(data);
+        let mut e = BigUint::from_bytes_be(&hash);
+        let hash_bits = 8 * hash.len() as u64;
+        if hash_bits > self.n.bits() {
+            e >>= hash_bits - self.n.bits();
+        }
+        let Some(w) = s.modinv(&self.n) else {
+            return false;
+        };
+        let u1 = e * &w % &self.n;
+        let u2 = &r * &w % &self.n;
+        match self.add(&self.multiply(&u1, &self.g), &self.multiply(&u2, public_point)) {
+            EccPoint::Infinity => false,
+            EccPoint::Affine(x, _) => x % &self.n == r,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_brainpool_p256r1_base_point() {
+        let curve = EccCurve::brainpool_p256r1();
+        assert!(curve.is_on_curve(&curve.g));
+        assert_eq!(curve.multiply(&curve.n, &curve.g), EccPoint::Infinity);
+
+        let double = curve.add(&curve.g, &curve.g);
+        assert!(curve.is_on_curve(&double));
+        assert_eq!(curve.multiply(&BigUint::from(3u8), &curve.g), curve.add(&double, &curve.g));
+    }
+
+    fn bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_curves_of_appendix_11() {
+        for curve in [
+            EccCurve::brainpool_p384r1(),
+            EccCurve::brainpool_p512r1(),
+            EccCurve::nist_p256(),
+            EccCurve::nist_p384(),
+            EccCurve::nist_p521(),
+        ] {
+            assert!(curve.is_on_curve(&curve.g));
+            assert_eq!(curve.multiply(&curve.n, &curve.g), EccPoint::Infinity);
+            assert_eq!(EccCurve::from_oid(curve.oid).map(|found| found.size), Some(curve.size));
+            assert_eq!(curve.decode_point(&curve.encode_point(&curve.g)), Some(curve.g.clone()));
+        }
+        assert!(EccCurve::from_oid(&[0x2B, 0x24]).is_none());
+    }
+
+    #[test]
+    fn test_verify_ecdsa_signatures() {
+        // Keys and signatures of "Tachograph card file" generated with an independent
+        // ECDSA implementation.
+        let vectors = [
+            (
+                EccCurve::brainpool_p256r1(),
+                "049C8C0E874FB36DB146760AD08C2CD665FB80586BD20A71F45813CE63695F9CBB07F643A2EA7EF3E0E849189E332A05D2B188F8C05754D668D8BDA12ABD35C2E5",
+                "0FBADB3928035FCFEF4F5CA0928067D4B7B924AFFE0AEB40C65BE26F2DFA63A56D81B5799626388A43CEAF896B1F8C746E1324A0D2724C86DC28AF3EA2DBF97F",
+            ),
+            (
+                EccCurve::brainpool_p384r1(),
+                "04215FD8CDE3637C693965303DD7F094BC51CC4399D0116AAFEBFD9FD74FA63CA64950EA031DD2793404E776D9A246A8967025BAFCB779E2F24C1F1EF6B0C75D9B66E6E73945AF8A36F0AE2CF8F1E415B192D6DC54A51D62DAF1CB63925029B23E",
+                "1BD6DE39F4A222366636F5F514268AE255CE53C9262017E47699BB2B00D6871D399C36B62969A7209FE6F80DC2EF4826110E4A6509C2A95B95CC2651319AD459970E6E5DEC10523E0C0824FEA596AE17942C3022F3AE62C28EFE1D05DCBFD2C4",
+            ),
+            (
+                EccCurve::brainpool_p512r1(),
+                "04428CD2AE1BFC40D01ECA8A34A08EC0D1C53ECE222C31A204D07E8CACF6AD98BA2CFD6FA815E8FFC2F0FD659D63B8D478FF58AB8DDFEE46A533B8D01DBC74ABDD7E22D4856756193E4F85FC5240B5BD4C3DD9D5FF2458BCE33A9C3CDD5353BAD770E9CA346DAE6C97BC8252ADB9858149FFA0346A604ECCCD1FBD5F4AB8ADF868",
+                "5625D55EFDEC782DCBC7BEC832D35DD01CA1E85C87BD25B3D34DF083932AFB057F935A3A9FEC0674FD1B5FE684E014340D1EAAAF956BB1B97AB8B75012E9CC351C013C93C4B690B4AA5EEEFD1F7B03C227EDA2D05EC2256EF1943F4063CA88765AC2EC91168BE6BCB893BCD0F184877EB0C045085C477BD468905E5E443C08CE",
+            ),
+            (
+                EccCurve::nist_p256(),
+                "04CCE312C66E4988C7382E86C202D04BD133D0EA124346422C9B11317622F27DC46E15F66AB9A78F4607AC0EA6E02CAE9975584DBE6B515F1FA000207F617BC08A",
+                "346C508F4EAE253F86C04B1498E381D6EA7357DD4F89D64872EA0EAAA5F9332D507DDB92D3D1ECCFB367B149CBE6F9BA5D77069E191E965EC074D7DB0F7DDE72",
+            ),
+            (
+                EccCurve::nist_p384(),
+                "047993B757133DD8575C84A5F2DA035BFA11D960EB0FCB96C36A325486208D59B3E9AF3BDC9B8E73563BBDECA8A559AD23FDEBF344756A9448EA41C20869C4668EC43F97103BB9FB71B7743A9FFF24460AB60BBB61A7845B0DCFE8F4539026C5B0",
+                "5EC2D508E72DEE24C77FCE046A0F018C21E1FB5FBA58D6B810A00C5D55D0DBCD0B83A70DCF18597C426A513362CA119ACA458B837667BDC6F02C633CACADB448FCE64DED9AEAFD0EAAF04CFF86A639D3250E7017C8BFFE8FFF7819596372EFDC",
+            ),
+            (
+                EccCurve::nist_p521(),
+                "0401C708F6E8BB247F98D5090E1AED9ED408E912EB4421C3381743ADEBE698A90FFA08B31D5878517C784C73FBA6D36BFFB0DDD2CB714A5527CDB917F7E22837FA65D701F664C75C68B74DF32B9E9AD049D26E13D36803C9CA3EF475DFB8637DCC239C1524944E89223CB1EE82C9A4B7D44A1588A18B215E5D8F7E9DD3C31DA035242836D5",
+                "019ECE85A52D8A633A71CA82D6764D912F30CE96632D78890AA7096A20F7D5127A3A83A0EA8358878466810EE852BB04B452F23D004FCBE6E1196120D7EE381C9B4C01C4FE3F322FE9AC6F7A75217F48762B01A56B5D8F593A479BF3BA23E24348323BDF7A35B712EF5D992D5F289D2A86717279195B037BCEFEB11168809A45E654C837",
+            ),
+        ];
+        for (curve, public_point, signature) in vectors {
+            let public_point = curve.decode_point(&bytes(public_point)).expect("public point should be on the curve");
+            let signature = bytes(signature);
+            assert!(curve.verify(&public_point, b"Tachograph card file", &signature));
+            assert!(!curve.verify(&public_point, b"Tachograph card filf", &signature));
+            let mut tampered = signature.clone();
+            tampered[curve.size] ^= 0x01;
+            assert!(!curve.verify(&public_point, b"Tachograph card file", &tampered));
+            assert!(!curve.verify(&curve.g, b"Tachograph card file", &signature));
+        }
+    }
+}
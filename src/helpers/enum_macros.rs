@@ -2,8 +2,11 @@
 ///
 /// This macro generates an implementation of `From<u8>` for a given enum,
 /// allowing for conversion from a `u8` value to an enum variant. It assumes
-/// that the enum has a variant named `Unknown` to handle cases where the
-/// `u8` value does not match any of the specified variants.
+/// that the enum has a variant `Unknown(u8)` to handle cases where the
+/// `u8` value does not match any of the specified variants, it keeps the value.
+///
+/// The reverse conversion `From<&Enum> for u8` is generated too, it is used
+/// when the enum is written. `Unknown` is written as the value it keeps.
 ///
 /// # Usage
 ///
@@ -15,7 +18,7 @@
 /// enum MyEnum {
 ///     A,
 ///     B,
-///     Unknown,
+///     Unknown(u8),
 /// }
 ///
 /// impl_enum_from_u8!(MyEnum {
@@ -25,7 +28,9 @@
 ///
 /// assert_eq!(MyEnum::from(0x01), MyEnum::A);
 /// assert_eq!(MyEnum::from(0x02), MyEnum::B);
-/// assert_eq!(MyEnum::from(0x03), MyEnum::Unknown);
+/// assert_eq!(MyEnum::from(0x03), MyEnum::Unknown(0x03));
+/// assert_eq!(u8::from(&MyEnum::B), 0x02);
+/// assert_eq!(u8::from(&MyEnum::Unknown(0x03)), 0x03);
 /// ```
 #[macro_export]
 #[doc(hidden)]
@@ -35,7 +40,16 @@ macro_rules! impl_enum_from_u8 {
             fn from(value: u8) -> Self {
                 match value {
                     $( $value => $enum_name::$variant, )+
-                    _ =>  $enum_name::Unknown,
+                    _ => $enum_name::Unknown(value),
+                }
+            }
+        }
+
+        impl From<&$enum_name> for u8 {
+            fn from(value: &$enum_name) -> Self {
+                match value {
+                    $( $enum_name::$variant => $value, )+
+                    $enum_name::Unknown(value) => *value,
                 }
             }
         }
@@ -46,8 +60,11 @@ macro_rules! impl_enum_from_u8 {
 ///
 /// This macro generates an implementation of `From<u16>` for a given enum,
 /// allowing for conversion from a `u16` value to an enum variant. It assumes
-/// that the enum has a variant named `Unknown` to handle cases where the
-/// `u16` value does not match any of the specified variants.
+/// that the enum has a variant `Unknown(u16)` to handle cases where the
+/// `u16` value does not match any of the specified variants, it keeps the value.
+///
+/// As with `impl_enum_from_u8!`, the reverse conversion `From<&Enum> for u16`
+/// is generated too.
 ///
 /// # Usage
 ///
@@ -59,7 +76,7 @@ macro_rules! impl_enum_from_u8 {
 /// enum MyEnum {
 ///     A,
 ///     B,
-///     Unknown,
+///     Unknown(u16),
 /// }
 ///
 /// impl_enum_from_u16!(MyEnum {
@@ -69,7 +86,8 @@ macro_rules! impl_enum_from_u8 {
 ///
 /// assert_eq!(MyEnum::from(0x0100), MyEnum::A);
 /// assert_eq!(MyEnum::from(0x0200), MyEnum::B);
-/// assert_eq!(MyEnum::from(0x0300), MyEnum::Unknown);
+/// assert_eq!(MyEnum::from(0x0300), MyEnum::Unknown(0x0300));
+/// assert_eq!(u16::from(&MyEnum::B), 0x0200);
 /// ```
 #[macro_export]
 #[doc(hidden)]
@@ -79,7 +97,16 @@ macro_rules! impl_enum_from_u16 {
             fn from(value: u16) -> Self {
                 match value {
                     $( $value => $enum_name::$variant, )+
-                    _ =>  $enum_name::Unknown,
+                    _ => $enum_name::Unknown(value),
+                }
+            }
+        }
+
+        impl From<&$enum_name> for u16 {
+            fn from(value: &$enum_name) -> Self {
+                match value {
+                    $( $enum_name::$variant => $value, )+
+                    $enum_name::Unknown(value) => *value,
                 }
             }
         }
@@ -95,7 +122,7 @@ mod tests {
         A,
         B,
         C,
-        Unknown,
+        Unknown(u8),
     }
 
     // Use the macro to implement From<u8>
@@ -110,8 +137,10 @@ mod tests {
         assert_eq!(TestEnumU8::from(0x01), TestEnumU8::A);
         assert_eq!(TestEnumU8::from(0x02), TestEnumU8::B);
         assert_eq!(TestEnumU8::from(0x05), TestEnumU8::C);
-        assert_eq!(TestEnumU8::from(0x00), TestEnumU8::Unknown); // Test the unknown case
-        assert_eq!(TestEnumU8::from(0xFF), TestEnumU8::Unknown); // Test another unknown case
+        assert_eq!(TestEnumU8::from(0x00), TestEnumU8::Unknown(0x00)); // Test the unknown case
+        assert_eq!(TestEnumU8::from(0xFF), TestEnumU8::Unknown(0xFF)); // Test another unknown case
+        assert_eq!(u8::from(&TestEnumU8::C), 0x05);
+        assert_eq!(u8::from(&TestEnumU8::Unknown(0xC0)), 0xC0); // Unknown is written as the value it keeps
     }
 
     // Define a test enum for the u16 macro
@@ -121,7 +150,7 @@ mod tests {
         X,
         Y,
         Z,
-        Unknown,
+        Unknown(u16),
     }
 
     // Use the macro to implement From<u16>
@@ -136,7 +165,9 @@ mod tests {
         assert_eq!(TestEnumU16::from(0x1000), TestEnumU16::X);
         assert_eq!(TestEnumU16::from(0x2000), TestEnumU16::Y);
         assert_eq!(TestEnumU16::from(0x3000), TestEnumU16::Z);
-        assert_eq!(TestEnumU16::from(0x0000), TestEnumU16::Unknown); // Test the unknown case
-        assert_eq!(TestEnumU16::from(0xFFFF), TestEnumU16::Unknown); // Test another unknown case
+        assert_eq!(TestEnumU16::from(0x0000), TestEnumU16::Unknown(0x0000)); // Test the unknown case
+        assert_eq!(TestEnumU16::from(0xFFFF), TestEnumU16::Unknown(0xFFFF)); // Test another unknown case
+        assert_eq!(u16::from(&TestEnumU16::Z), 0x3000);
+        assert_eq!(u16::from(&TestEnumU16::Unknown(0x0400)), 0x0400);
     }
 }
@@ -1,11 +1,12 @@
 mod convert;
+mod ecc;
 mod enum_macros;
 mod serde;
 mod verify;
 
 #[allow(unused_imports)]
 pub(crate) use convert::*;
-#[cfg(test)]
+pub(crate) use ecc::*;
 pub(crate) use serde::*;
 pub(crate) use verify::*;
 
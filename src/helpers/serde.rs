@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
-use serde::{Serialize, Serializer};
-use serde_json::json;
+use serde::{Deserialize, Serialize, Serializer, de};
+use serde_json::{Value, json};
+
+use crate::tacho::EquipmentType;
 
 #[allow(dead_code)]
 const FORMAT_UTC: &str = "%Y-%m-%d %H:%M:%S UTC";
@@ -24,3 +26,19 @@ where
         false => serializer.serialize_none(),
     }
 }
+
+/// Deserializes `value` into `T`, reporting a failure as an error of the deserializer `E`.
+pub fn from_json_value<T, E>(value: Value) -> Result<T, E>
+where
+    T: de::DeserializeOwned,
+    E: de::Error,
+{
+    serde_json::from_value(value).map_err(E::custom)
+}
+
+/// Returns the card type stored in the application identification of a serialized card.
+pub fn card_type_of_json_value(card: &Value) -> Option<EquipmentType> {
+    card.get("applicationIdentification")
+        .and_then(|application_identification| application_identification.get("typeOfTachographCardId"))
+        .and_then(|card_type| EquipmentType::deserialize(card_type).ok())
+}
@@ -1,3 +1,25 @@
+use crate::{Error, Result};
+
+/// Size of the record type, the record size and the number of records at the start of
+/// a record array.
+pub(crate) const RECORD_ARRAY_HEADER_LENGTH: usize = 5;
+
 pub fn get_sub_array(data: &[u8], offset: usize, len: usize) -> &[u8] {
     &data[offset..offset + len]
 }
+
+/// Returns the record arrays of the data of a generation 2 TREP, each with its header.
+pub(crate) fn split_record_arrays(data: &[u8]) -> Result<Vec<&[u8]>> {
+    let mut record_arrays = Vec::new();
+    let mut offset = 0;
+    while offset < data.len() {
+        let header = data
+            .get(offset..offset + RECORD_ARRAY_HEADER_LENGTH)
+            .ok_or_else(|| Error::InvalidDataParse("partial record array header".to_owned()))?;
+        let size = u16::from_be_bytes([header[1], header[2]]) as usize * u16::from_be_bytes([header[3], header[4]]) as usize;
+        let end = offset + RECORD_ARRAY_HEADER_LENGTH + size;
+        record_arrays.push(data.get(offset..end).ok_or_else(|| Error::InvalidDataParse("partial record array".to_owned()))?);
+        offset = end;
+    }
+    Ok(record_arrays)
+}
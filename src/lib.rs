@@ -13,6 +13,7 @@
 //! - Extracts a wide range of data including driver activities, events, faults,
 //!   vehicle usage, and calibration data.
 //! - Provides structured Rust types for all parsed data for easy access.
+//! - Builds synthetic card and VU DDD files for regression tests.
 //!
 //! ## Usage
 //!
@@ -60,21 +61,51 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! To walk a large DDD file item by item from any `std::io::Read`:
+//!
+//! ```rust,no_run
+//! use esm_parser::{DddStreamItem, DddStreamReader};
+//!
+//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let file = std::fs::File::open("path/to/your_file.ddd")?;
+//!     for item in DddStreamReader::new(std::io::BufReader::new(file))? {
+//!         match item? {
+//!             DddStreamItem::CardFile(card_file) => println!("Card file: {}", card_file.card_file_id),
+//!             DddStreamItem::VUGen1(trep) => println!("Gen1 TREP: {:?}", trep.type_id),
+//!             DddStreamItem::VUGen2(trep) => println!("Gen2 TREP: {:?}", trep.type_id),
+//!         }
+//!     }
+//!     Ok(())
+//! }
+//! ```
 
 mod common;
 mod consts;
+mod dissector;
 mod error;
+mod export_envelope;
+#[cfg(any(test, feature = "test-fixtures"))]
+mod fixture_builder;
 mod helpers;
 mod parser;
+mod stream_reader;
 mod tachograph;
 mod tachograph_data;
 mod tachograph_gen1;
 mod tachograph_gen2;
+mod trust_store;
 mod verification;
+mod writer;
 
 pub use common::*;
 pub(crate) use consts::*;
-pub use error::{Error, Result};
+pub use dissector::{Dissection, DissectionNode, dissect_from_file, dissect_from_memory};
+pub(crate) use error::ResultExt;
+pub use error::{Error, ErrorContext, Result};
+#[cfg(feature = "schemars")]
+pub use export_envelope::export_json_schema;
+pub use export_envelope::{EXPORT_SCHEMA_VERSION, ExportEnvelope};
 
 pub mod tacho {
     //! # Common Tachograph Data Structures
@@ -101,9 +132,24 @@ pub mod gen2 {
     //! support for new features like GNSS data and updated record formats.
     pub use super::tachograph_gen2::*;
 }
-pub use parser::{parse_from_file, parse_from_memory};
+#[cfg(any(test, feature = "test-fixtures"))]
+pub mod fixtures {
+    //! # Synthetic DDD Fixtures
+    //!
+    //! This module contains builders of structurally valid DDD files of cards and
+    //! vehicle units, with the activities, events and faults set on the builder.
+    //! The module is only built for the tests and with the `test-fixtures` feature.
+    pub use super::fixture_builder::*;
+}
+pub use parser::{
+    ParseOutcome, parse_from_file, parse_from_file_with_options, parse_from_memory, parse_from_memory_with_options,
+};
+pub use stream_reader::{DddStreamItem, DddStreamReader};
 pub use tachograph_data::TachographData;
-pub use verification::{verify_card, verify_card_with_erca_path};
+pub use trust_store::TrustStore;
+pub use verification::{verify_card, verify_card_with_erca_path, verify_vu, verify_vu_with_erca_path};
+pub(crate) use writer::write_card_data_files;
+pub use writer::{CardDataWriter, write_card_ddd, write_ddd, write_ddd_from_json, write_vu_ddd};
 
 #[cfg(target_arch = "wasm32")]
 pub use helpers::{LogLevel, init_console_logging};
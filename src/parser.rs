@@ -1,11 +1,23 @@
 use binary_data::{BinMemoryBuffer, BinReader, BinSeek, ReadBytes};
 use log::{debug, trace};
+use serde::Serialize;
 
 use crate::{
-    Error, Result, TachographData, gen1, gen2,
-    tacho::{TachographDataGeneration, TachographDataType, TachographHeader},
+    ByteSpan, Diagnostic, Error, ParseContext, ParseOptions, Result, SpanRecorder, TachographData, gen1, gen2,
+    tacho::{Card, TachographDataGeneration, TachographDataType, TachographHeader},
 };
 
+/// Result of parsing with `ParseOptions`, the parsed data plus everything
+/// which was skipped in lenient mode.
+#[derive(Debug, Serialize)]
+pub struct ParseOutcome {
+    pub data: TachographData,
+    pub diagnostics: Vec<Diagnostic>,
+    /// Byte spans of the parsed structures, when requested with `ParseOptions::with_spans`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spans: Option<ByteSpan>,
+}
+
 /// Reads the tachograph data based on the data type and generation specified in the header.
 ///
 /// # Arguments
@@ -16,7 +28,7 @@ use crate::{
 /// # Returns
 ///
 /// A `Result` containing the parsed `TachographData` or an `Error` if parsing fails.
-fn read_by_data_type<R: ReadBytes + BinSeek>(header: TachographHeader, reader: &mut R) -> Result<TachographData> {
+fn read_by_data_type<R: ReadBytes + BinSeek>(mut header: TachographHeader, reader: &mut R) -> Result<TachographData> {
     debug!("EsmParser::read_by_data_type - Type: {:?}, Generation: {:?}", header.data_type, header.generation);
     match header.data_type {
         TachographDataType::VU => match header.generation {
@@ -29,12 +41,16 @@ fn read_by_data_type<R: ReadBytes + BinSeek>(header: TachographHeader, reader: &
                 // We skip 2 bytes
                 let _ = reader.read_bytes::<2>();
             }
+            // The generation is detected from the card files, not from the header.
+            let card_data_files = <dyn Card<TachographData>>::read_card_files(reader)?;
+            header.generation = card_data_files.get_data_generation();
+            debug!("EsmParser::read_by_data_type - Card Generation: {:?}", card_data_files.get_card_generation());
             match header.generation {
                 TachographDataGeneration::FirstGeneration => {
-                    Ok(TachographData::CardGen1(gen1::CardData::from_data(header, reader)?))
+                    Ok(TachographData::CardGen1(gen1::CardData::from_card_files(header, &card_data_files)?))
                 }
                 TachographDataGeneration::SecondGeneration => {
-                    Ok(TachographData::CardGen2(gen2::CardData::from_data(header, reader)?))
+                    Ok(TachographData::CardGen2(gen2::CardData::from_card_files(header, &card_data_files)?))
                 }
                 _ => Err(Error::InvalidDataGeneration),
             }
@@ -47,19 +63,24 @@ fn read_by_data_type<R: ReadBytes + BinSeek>(header: TachographHeader, reader: &
 /// # Arguments
 ///
 /// * `header_data` - A byte slice containing the first 2 bytes of the file, used to determine the data type and generation.
-/// * `data_len` - The total length of the data.
 /// * `reader` - A mutable reference to the reader to read the data from.
 ///
 /// # Returns
 ///
 /// A `Result` containing the parsed `TachographData` or an `Error` if parsing fails.
-fn parse_inner<R: ReadBytes + BinSeek>(header_data: &[u8; 2], data_len: u64, reader: &mut R) -> Result<TachographData> {
-    let header = TachographHeader::from_data(header_data, data_len)?;
+fn parse_inner<R: ReadBytes + BinSeek>(header_data: &[u8; 2], reader: &mut R) -> Result<TachographData> {
+    let header = TachographHeader::from_header_data(header_data)?;
     reader.seek(0)?;
 
     read_by_data_type(header, reader)
 }
 
+/// Runs `parse` with the parse context and the span recorder set up from `options`.
+fn parse_with_options(options: &ParseOptions, parse: impl FnOnce() -> Result<TachographData>) -> Result<ParseOutcome> {
+    let ((result, diagnostics), spans) = SpanRecorder::run(options.record_spans, false, || ParseContext::run(options, parse));
+    Ok(ParseOutcome { data: result?, diagnostics, spans })
+}
+
 /// This methods provides the entry point for parsing DDD files. It automatically
 /// detects the generation of the tachograph data (Gen1 or Gen2) and the type of
 /// data (Vehicle Unit or Driver Card) and parses it accordingly.
@@ -75,7 +96,7 @@ pub fn parse_from_file(esm_file_path: &str) -> Result<TachographData> {
     let mut file = BinReader::open(esm_file_path)?;
     debug!("EsmParser::parse_inner - File: {file:?}");
 
-    parse_inner(&file.read_n_bytes::<2>()?, file.metadata().len(), &mut file)
+    parse_inner(&file.read_n_bytes::<2>()?, &mut file)
 }
 
 /// This methods provides the entry point for parsing DDD files. It automatically
@@ -93,7 +114,37 @@ pub fn parse_from_memory(esm_data: &[u8]) -> Result<TachographData> {
     let mut reader = BinMemoryBuffer::from(esm_data);
     trace!("EsmParser::parse_inner - File: {reader:?}");
 
-    parse_inner(&reader.read_bytes::<2>()?, reader.len()? as u64, &mut reader)
+    parse_inner(&reader.read_bytes::<2>()?, &mut reader)
+}
+
+/// Parses a DDD file like `parse_from_file`, with the behaviour on corrupted
+/// data controlled by `options`.
+///
+/// # Arguments
+///
+/// * `esm_file_path` - The path to the DDD file.
+/// * `options` - The parse options, `ParseMode::Lenient` collects errors as diagnostics.
+///
+/// # Returns
+///
+/// A `Result` containing the `ParseOutcome` or an `Error` if the data could not be parsed at all.
+pub fn parse_from_file_with_options(esm_file_path: &str, options: &ParseOptions) -> Result<ParseOutcome> {
+    parse_with_options(options, || parse_from_file(esm_file_path))
+}
+
+/// Parses DDD data like `parse_from_memory`, with the behaviour on corrupted
+/// data controlled by `options`.
+///
+/// # Arguments
+///
+/// * `esm_data` - The binary data from DDD file.
+/// * `options` - The parse options, `ParseMode::Lenient` collects errors as diagnostics.
+///
+/// # Returns
+///
+/// A `Result` containing the `ParseOutcome` or an `Error` if the data could not be parsed at all.
+pub fn parse_from_memory_with_options(esm_data: &[u8], options: &ParseOptions) -> Result<ParseOutcome> {
+    parse_with_options(options, || parse_from_memory(esm_data))
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -0,0 +1,432 @@
+use std::io::{ErrorKind, Read};
+
+use binary_data::{BinMemoryBuffer, BinSeek};
+use log::{debug, trace};
+use serde::Serialize;
+
+use crate::{
+    Diagnostic, Error, Result, ResultExt, Severity, VU_HEADER_MAGIC_NUMBER, gen1, gen2,
+    tacho::{
+        CardFileData, CardFileID, RecordType, TachographDataGeneration, TachographDataType, TachographHeader,
+        VUTransferResponseParameterID, VUTransferResponseParameterItem,
+    },
+};
+
+const CARD_FILE_HEADER_LENGTH: usize = 5;
+const DATA_INFO_HEADER_LENGTH: usize = 5;
+
+/// Describes one part of a Gen1 TREP, used to find where the TREP ends
+/// without having the data in a seekable buffer.
+enum Segment {
+    /// Fixed number of bytes.
+    Fixed(usize),
+    /// Big endian counter of `count_length` bytes followed by `count` records of `record_size` bytes.
+    Counted { count_length: usize, record_size: usize },
+}
+
+/// Data structure generation 1 (TREP 01 Hex)
+const GEN1_OVERVIEW_LAYOUT: &[Segment] = &[
+    // Certificates, VIN, VRN, current time, downloadable period, card slots status, download activity data
+    Segment::Fixed(194 + 194 + 17 + 15 + 4 + 8 + 1 + 58),
+    Segment::Counted { count_length: 1, record_size: 98 },
+    Segment::Counted { count_length: 1, record_size: 31 },
+    Segment::Fixed(128),
+];
+
+/// Data structure generation 1 (TREP 02 Hex)
+const GEN1_ACTIVITIES_LAYOUT: &[Segment] = &[
+    // Date of day downloaded, odometer value midnight
+    Segment::Fixed(4 + 3),
+    Segment::Counted { count_length: 2, record_size: 129 },
+    Segment::Counted { count_length: 2, record_size: 2 },
+    Segment::Counted { count_length: 1, record_size: 28 },
+    Segment::Counted { count_length: 2, record_size: 5 },
+    Segment::Fixed(128),
+];
+
+/// Data structure generation 1 (TREP 03 Hex)
+const GEN1_EVENTS_AND_FAULTS_LAYOUT: &[Segment] = &[
+    Segment::Counted { count_length: 1, record_size: 82 },
+    Segment::Counted { count_length: 1, record_size: 83 },
+    Segment::Fixed(9),
+    Segment::Counted { count_length: 1, record_size: 31 },
+    Segment::Counted { count_length: 1, record_size: 98 },
+    Segment::Fixed(128),
+];
+
+/// Data structure generation 1 (TREP 04 Hex)
+const GEN1_SPEED_LAYOUT: &[Segment] = &[Segment::Counted { count_length: 2, record_size: 64 }, Segment::Fixed(128)];
+
+/// Data structure generation 1 (TREP 05 Hex)
+const GEN1_TECHNICAL_DATA_LAYOUT: &[Segment] = &[
+    // VU identification, sensor paired
+    Segment::Fixed(116 + 20),
+    Segment::Counted { count_length: 1, record_size: 167 },
+    Segment::Fixed(128),
+];
+
+/// A single item produced by the `DddStreamReader`.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum DddStreamItem {
+    /// Card elementary file, with the signature attached when one follows it.
+    CardFile(CardFileData),
+    /// Transfer response parameter of a Gen1 vehicle unit download.
+    VUGen1(VUTransferResponseParameterItem<gen1::VUTransferResponseParameterData>),
+    /// Transfer response parameter of a Gen2 vehicle unit download.
+    VUGen2(VUTransferResponseParameterItem<gen2::VUTransferResponseParameterData>),
+}
+
+/// Pull-style reader which walks a DDD file from any `std::io::Read` and
+/// yields card elementary files or VU transfer response parameters one by one.
+///
+/// Only the item which is currently being decoded is held in memory, so
+/// large VU downloads can be processed directly from sockets or object stores.
+///
+/// For card files the generation is not known from the file header, it is
+/// given by the `appendix` of every yielded `CardFileData` (0/1 - Gen1, 2/3 - Gen2).
+pub struct DddStreamReader<R: Read> {
+    reader: R,
+    header: TachographHeader,
+    peeked: Vec<u8>,
+    offset: usize,
+    position: u32,
+    pending_card_file: Option<CardFileData>,
+    diagnostics: Vec<Diagnostic>,
+    finished: bool,
+}
+
+impl<R: Read> DddStreamReader<R> {
+    /// Creates a new `DddStreamReader` and reads the file header.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The source of the DDD data.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `DddStreamReader` or an `Error` if the header is invalid.
+    pub fn new(reader: R) -> Result<DddStreamReader<R>> {
+        let mut stream_reader = DddStreamReader {
+            reader,
+            header: TachographHeader {
+                generation: TachographDataGeneration::Unknown,
+                data_type: TachographDataType::Card,
+                card_in_vu_data: false,
+            },
+            peeked: Vec::new(),
+            offset: 0,
+            position: 0,
+            pending_card_file: None,
+            diagnostics: Vec::new(),
+            finished: false,
+        };
+
+        let mut header_data: Vec<u8> = Vec::with_capacity(2);
+        stream_reader.read_into(&mut header_data, 2)?;
+        stream_reader.header = TachographHeader::from_header_data(&header_data)?;
+        debug!("DddStreamReader::new - Header: {:?}", stream_reader.header);
+
+        // The VU and card header bytes are part of the first TREP / EF.
+        if !stream_reader.header.card_in_vu_data {
+            stream_reader.peeked = header_data;
+            stream_reader.offset = 0;
+        }
+
+        Ok(stream_reader)
+    }
+
+    /// Returns the header of the DDD file.
+    pub fn get_header(&self) -> &TachographHeader {
+        &self.header
+    }
+
+    /// Returns the number of bytes consumed from the underlying reader so far.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the warnings collected so far, e.g. about bytes skipped between the TREPs
+    /// of a VU download.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    fn read_byte(&mut self) -> Result<Option<u8>> {
+        if !self.peeked.is_empty() {
+            self.offset += 1;
+            return Ok(Some(self.peeked.remove(0)));
+        }
+        let mut buffer = [0u8; 1];
+        loop {
+            match self.reader.read(&mut buffer) {
+                Ok(0) => return Ok(None),
+                Ok(_) => {
+                    self.offset += 1;
+                    return Ok(Some(buffer[0]));
+                }
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(Error::File(e)),
+            }
+        }
+    }
+
+    fn peek_byte(&mut self) -> Result<Option<u8>> {
+        if let Some(value) = self.peeked.first() {
+            return Ok(Some(*value));
+        }
+        let value = self.read_byte()?;
+        if let Some(value) = value {
+            self.offset -= 1;
+            self.peeked.push(value);
+        }
+        Ok(value)
+    }
+
+    fn read_into(&mut self, buffer: &mut Vec<u8>, length: usize) -> Result<()> {
+        let from_peeked = length.min(self.peeked.len());
+        buffer.extend(self.peeked.drain(..from_peeked));
+        let remaining = (length - from_peeked) as u64;
+        let read = self.reader.by_ref().take(remaining).read_to_end(buffer)? as u64;
+        self.offset += from_peeked + read as usize;
+        if read != remaining {
+            return Err(Error::File(std::io::Error::from(ErrorKind::UnexpectedEof)));
+        }
+        Ok(())
+    }
+
+    fn read_card_file(&mut self) -> Result<Option<CardFileData>> {
+        if self.peek_byte()?.is_none() {
+            return Ok(None);
+        }
+        let mut header: Vec<u8> = Vec::with_capacity(CARD_FILE_HEADER_LENGTH);
+        self.read_into(&mut header, CARD_FILE_HEADER_LENGTH)?;
+        let card_file_id: CardFileID = u16::from_be_bytes([header[0], header[1]]).into();
+        let appendix = header[2];
+        let size = u16::from_be_bytes([header[3], header[4]]) as u32;
+
+        let mut card_file_notes: String = "".to_owned();
+        let offset = self.offset;
+        let data = if self.peek_byte()?.is_none() {
+            card_file_notes = "Missing card file data after header".to_owned();
+            None
+        } else {
+            let mut data: Vec<u8> = Vec::with_capacity(size as usize);
+            self.read_into(&mut data, size as usize)?;
+            Some(data)
+        };
+
+        Ok(Some(CardFileData { card_file_id, appendix, card_file_notes, size, signature: None, data, offset }))
+    }
+
+    fn next_card_file(&mut self) -> Result<Option<CardFileData>> {
+        loop {
+            let Some(card_file) = self.read_card_file()? else {
+                return Ok(self.pending_card_file.take());
+            };
+            debug!("DddStreamReader::next_card_file - {:?}, Appendix: {:?}", card_file.card_file_id, card_file.appendix);
+            if matches!(card_file.card_file_id, CardFileID::Unknown(_)) {
+                return Err(Error::UnknownCardType);
+            }
+
+            // 0, 2 - data, 1, 3 - signature
+            if card_file.appendix % 2 == 0 {
+                if let Some(previous) = self.pending_card_file.replace(card_file) {
+                    return Ok(Some(previous));
+                }
+                continue;
+            }
+
+            match self.pending_card_file.take() {
+                Some(mut pending)
+                    if pending.card_file_id == card_file.card_file_id && pending.appendix + 1 == card_file.appendix =>
+                {
+                    if !card_file.card_file_notes.is_empty() {
+                        pending.card_file_notes.push_str(&format!("(signature) {}", &card_file.card_file_notes));
+                    }
+                    pending.signature = card_file.data;
+                    return Ok(Some(pending));
+                }
+                _ => return Err(Error::SignatureBeforeCardFile),
+            }
+        }
+    }
+
+    fn read_segments(&mut self, buffer: &mut Vec<u8>, layout: &[Segment]) -> Result<()> {
+        for segment in layout {
+            match segment {
+                Segment::Fixed(length) => self.read_into(buffer, *length)?,
+                Segment::Counted { count_length, record_size } => {
+                    let start = buffer.len();
+                    self.read_into(buffer, *count_length)?;
+                    let count = buffer[start..].iter().fold(0usize, |acc, value| (acc << 8) | *value as usize);
+                    self.read_into(buffer, count * record_size)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads card elementary files (embedded into a VU card download) until the next TREP or end of data.
+    fn read_card_files_block(&mut self, buffer: &mut Vec<u8>) -> Result<()> {
+        while let Some(value) = self.peek_byte()? {
+            if value == VU_HEADER_MAGIC_NUMBER {
+                break;
+            }
+            let start = buffer.len();
+            self.read_into(buffer, CARD_FILE_HEADER_LENGTH)?;
+            let size = u16::from_be_bytes([buffer[start + 3], buffer[start + 4]]) as usize;
+            self.read_into(buffer, size)?;
+        }
+        Ok(())
+    }
+
+    /// Reads `DataInfo` blocks until the signature record array, the next TREP or end of data.
+    fn read_data_info_blocks(&mut self, buffer: &mut Vec<u8>) -> Result<()> {
+        while let Some(value) = self.peek_byte()? {
+            if value == VU_HEADER_MAGIC_NUMBER {
+                break;
+            }
+            let start = buffer.len();
+            self.read_into(buffer, DATA_INFO_HEADER_LENGTH)?;
+            let record_type = RecordType::from(buffer[start]);
+            let record_size = u16::from_be_bytes([buffer[start + 1], buffer[start + 2]]) as usize;
+            let no_of_records = u16::from_be_bytes([buffer[start + 3], buffer[start + 4]]) as usize;
+            self.read_into(buffer, record_size * no_of_records)?;
+            if matches!(record_type, RecordType::Signature) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn read_gen1_trep(&mut self, trep_id: &VUTransferResponseParameterID) -> Result<Vec<u8>> {
+        let mut buffer: Vec<u8> = Vec::new();
+        match trep_id {
+            VUTransferResponseParameterID::Overview => self.read_segments(&mut buffer, GEN1_OVERVIEW_LAYOUT)?,
+            VUTransferResponseParameterID::Activities => self.read_segments(&mut buffer, GEN1_ACTIVITIES_LAYOUT)?,
+            VUTransferResponseParameterID::EventsAndFaults => self.read_segments(&mut buffer, GEN1_EVENTS_AND_FAULTS_LAYOUT)?,
+            VUTransferResponseParameterID::Speed => self.read_segments(&mut buffer, GEN1_SPEED_LAYOUT)?,
+            VUTransferResponseParameterID::TechnicalData => self.read_segments(&mut buffer, GEN1_TECHNICAL_DATA_LAYOUT)?,
+            VUTransferResponseParameterID::CardDownload => self.read_card_files_block(&mut buffer)?,
+            _ => trace!("DddStreamReader::read_gen1_trep - No data for: {trep_id:?}"),
+        }
+        Ok(buffer)
+    }
+
+    fn read_gen2_trep(&mut self, trep_id: &VUTransferResponseParameterID) -> Result<Vec<u8>> {
+        let mut buffer: Vec<u8> = Vec::new();
+        match trep_id {
+            VUTransferResponseParameterID::OddballCrashDump => {
+                trace!("DddStreamReader::read_gen2_trep - No data for: {trep_id:?}")
+            }
+            _ => self.read_data_info_blocks(&mut buffer)?,
+        }
+        Ok(buffer)
+    }
+
+    /// Reports the bytes from `from` to `to` which were skipped while searching for the next TREP.
+    fn report_skipped(&mut self, from: usize, to: usize) {
+        if to > from {
+            let skipped = to - from;
+            debug!("DddStreamReader::next_vu_item - Skipped {} bytes on offset: {}", skipped, from);
+            self.diagnostics.push(Diagnostic::new(
+                Severity::Warning,
+                Some(from),
+                None,
+                format!("skipped {skipped} bytes while searching for the next TREP"),
+            ));
+        }
+    }
+
+    fn next_vu_item(&mut self) -> Result<Option<DddStreamItem>> {
+        let search_offset = self.offset;
+        loop {
+            let block_offset = self.offset;
+            let Some(magic_number) = self.read_byte()? else {
+                self.report_skipped(search_offset, block_offset);
+                return Ok(None);
+            };
+            if magic_number != VU_HEADER_MAGIC_NUMBER {
+                continue;
+            }
+            let Some(trep) = self.read_byte()? else {
+                self.report_skipped(search_offset, self.offset);
+                return Ok(None);
+            };
+            let trep_id = VUTransferResponseParameterID::from(trep);
+            if trep_id.is_unknown() {
+                if trep == VU_HEADER_MAGIC_NUMBER {
+                    self.offset -= 1;
+                    self.peeked.insert(0, trep);
+                }
+                continue;
+            }
+
+            self.report_skipped(search_offset, block_offset);
+            self.position += 1;
+            debug!("DddStreamReader::next_vu_item - Trep ID: {:?} on offset: {}", trep_id, self.offset);
+            if trep_id == VUTransferResponseParameterID::OddballCrashDump {
+                self.finished = true;
+            }
+
+            // Errors of the TREP are reported with the offset in the stream, not in the TREP copy.
+            let data_offset = self.offset;
+            let section = format!("{trep_id:?}");
+            let item = match self.header.generation {
+                TachographDataGeneration::FirstGeneration => {
+                    let mut reader = BinMemoryBuffer::from(self.read_gen1_trep(&trep_id)?);
+                    let result = gen1::VUData::parse_trep(trep_id.clone(), &mut reader);
+                    let data =
+                        result.at_offset(reader.pos()?).map_err(|error| error.offset_by(data_offset)).in_section(&section)?;
+                    DddStreamItem::VUGen1(VUTransferResponseParameterItem {
+                        type_id: trep_id,
+                        position: self.position,
+                        data,
+                        raw_data: reader.into_vec(),
+                    })
+                }
+                TachographDataGeneration::SecondGeneration => {
+                    let mut reader = BinMemoryBuffer::from(self.read_gen2_trep(&trep_id)?);
+                    let result = gen2::VUData::parse_trep(trep_id.clone(), &mut reader);
+                    let data =
+                        result.at_offset(reader.pos()?).map_err(|error| error.offset_by(data_offset)).in_section(&section)?;
+                    DddStreamItem::VUGen2(VUTransferResponseParameterItem {
+                        type_id: trep_id,
+                        position: self.position,
+                        data,
+                        raw_data: reader.into_vec(),
+                    })
+                }
+                _ => return Err(Error::InvalidDataGeneration),
+            };
+            return Ok(Some(item));
+        }
+    }
+}
+
+impl<R: Read> Iterator for DddStreamReader<R> {
+    type Item = Result<DddStreamItem>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let result = match self.header.data_type {
+            TachographDataType::Card => self.next_card_file().map(|card_file| card_file.map(DddStreamItem::CardFile)),
+            TachographDataType::VU => self.next_vu_item(),
+        };
+        match result {
+            Ok(Some(item)) => Some(Ok(item)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => {
+                self.finished = true;
+                self.pending_card_file = None;
+                Some(Err(e))
+            }
+        }
+    }
+}
@@ -1,8 +1,8 @@
 use binary_data::{BigEndian, BinSeek, ReadBytes};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    ReadableWithParams, Result,
+    ReadableWithParams, Result, SpanRecorder, Writable,
     tacho::{ActivitySource, ActivityType, CardSlotNumber, CardStatus, DrivingStatus},
 };
 
@@ -17,7 +17,8 @@ impl ActivityChangeInfoParams {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum ActivityCard {
     Vu,
     Card,
@@ -26,7 +27,8 @@ pub enum ActivityCard {
 /// This data type enables to code, within a two bytes word, a slot status at
 /// 00:00 and/or a driver status at 00:00 and/or changes of activity and/or
 /// changes of driving status and/or changes of card status for a driver or a co-driver.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ActivityChangeInfo {
     /// The source of the activity, either from the VU or a card.
     #[serde(rename = "activityCard")]
@@ -89,7 +91,7 @@ impl ActivityChangeInfo {
     /// Determines the type of activity.
     fn get_activity_type(activity_card: &ActivityCard, activity_source: &ActivitySource, activity_info: u16) -> ActivityType {
         if *activity_source == ActivitySource::Unknown && *activity_card == ActivityCard::Card {
-            return ActivityType::Unknown;
+            return ActivityType::Unknown(0xFF);
         }
         ((activity_info >> 11) & 0b11).into()
     }
@@ -97,7 +99,7 @@ impl ActivityChangeInfo {
     /// Determines the card slot number.
     fn get_card_slot(card_slot_valid: bool, activity_info: u16) -> CardSlotNumber {
         if !card_slot_valid {
-            return CardSlotNumber::Unknown;
+            return CardSlotNumber::Unknown(0xFF);
         }
         if (activity_info & (1 << 15)) == 0 {
             return CardSlotNumber::Driver;
@@ -133,7 +135,15 @@ impl ReadableWithParams<ActivityChangeInfo> for ActivityChangeInfo {
 
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<ActivityChangeInfo> {
         let activity_card = params.activity_card.clone();
-        let activity_info = reader.read_u16::<BigEndian>()?;
+        let activity_info = SpanRecorder::field("activityInfo", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
         Ok(ActivityChangeInfo::new(activity_card, activity_info))
     }
 }
+
+impl Writable for ActivityChangeInfo {
+    /// Writes the raw `activity_info` of the `ActivityChangeInfo` to a binary stream.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u16::<BigEndian>(self.activity_info)?;
+        Ok(())
+    }
+}
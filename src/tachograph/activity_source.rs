@@ -1,8 +1,9 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 /// Represents the source of a driver's activity, indicating whether it was
 /// recorded automatically by the VU or entered manually by the driver.
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(u8)]
 pub enum ActivitySource {
     /// The activity was recorded automatically by the Vehicle Unit.
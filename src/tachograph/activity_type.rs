@@ -1,9 +1,10 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::impl_enum_from_u16;
 
 /// Represents the type of activity a driver is engaged in, such as driving, working, or resting.
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(u16)]
 pub enum ActivityType {
     /// The driver is on a break or resting.
@@ -15,7 +16,7 @@ pub enum ActivityType {
     /// The driver is driving the vehicle.
     Driving = 3,
     /// The activity type is unknown or not specified.
-    Unknown = 255,
+    Unknown(u16) = 255,
 }
 
 impl_enum_from_u16!(
@@ -24,6 +25,5 @@ impl_enum_from_u16!(
         Availability = 1,
         Work = 2,
         Driving = 3,
-        Unknown = 255,
     }
 );
@@ -1,12 +1,13 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable,
+    Readable, ReadableField, SpanRecorder, Writable,
     tacho::{CardStructureVersion, EquipmentType},
 };
 
 /// Base Information, stored in a card related to the identification of the application of the card.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ApplicationIdentification {
     #[serde(rename = "typeOfTachographCardId")]
     pub type_of_tachograph_card_id: EquipmentType,
@@ -16,8 +17,18 @@ pub struct ApplicationIdentification {
 
 impl Readable<ApplicationIdentification> for ApplicationIdentification {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<ApplicationIdentification> {
-        let type_of_tachograph_card_id = reader.read_u8()?.into();
-        let card_structure_version = CardStructureVersion::read(reader)?;
+        let type_of_tachograph_card_id: EquipmentType =
+            SpanRecorder::field("typeOfTachographCardId", "EquipmentType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let card_structure_version = CardStructureVersion::read_field(reader, "cardStructureVersion")?;
         Ok(Self { type_of_tachograph_card_id, card_structure_version })
     }
 }
+
+impl Writable for ApplicationIdentification {
+    /// Writes an `ApplicationIdentification` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8((&self.type_of_tachograph_card_id).into())?;
+        self.card_structure_version.write(writer)?;
+        Ok(())
+    }
+}
@@ -1,11 +1,13 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::impl_enum_from_u8;
 
 /// Code explaining why a set of calibration parameters was recorded. This
 // data type is related to Annex 1B requirements 097 and 098 and
 // Annex 1C requirements 119.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[repr(u8)]
 pub enum CalibrationPurpose {
     Reserved = 0,
     Activation = 1,
@@ -14,7 +16,7 @@ pub enum CalibrationPurpose {
     PeriodicInspection = 4,
     VRNEntryByCompany = 5,
     TimeAdjustmentWithoutCalibration = 6,
-    Unknown = 7,
+    Unknown(u8) = 7,
 }
 
 impl_enum_from_u8!(
@@ -26,6 +28,5 @@ impl_enum_from_u8!(
         PeriodicInspection = 4,
         VRNEntryByCompany = 5,
         TimeAdjustmentWithoutCalibration = 6,
-        Unknown = 7,
     }
 );
@@ -1,12 +1,18 @@
 use core::fmt;
 use std::collections::HashMap;
 
-use binary_data::{BigEndian, BinMemoryBuffer, BinSeek, ReadBytes};
+use binary_data::{BigEndian, BinMemoryBuffer, BinSeek, ReadBytes, WriteBytes};
 use log::debug;
 use serde::{Deserialize, Serialize};
 
-use crate::tacho::{ApplicationIdentification, CardChipIdentification, CardFileID, CardIccIdentification, TachographHeader};
-use crate::{Error, Readable, Result};
+use crate::tacho::{
+    ApplicationIdentification, CardChipIdentification, CardFileID, CardIccIdentification, TachographDataGeneration,
+    TachographHeader,
+};
+use crate::{
+    Diagnostic, Error, ParseContext, Readable, Result, Severity, SpanRecorder, SpanSection, Writable, WritableWithParams,
+    write_to_vec,
+};
 
 pub type CardParseFunc<D> = dyn Fn(&CardFilesDataByCardGeneration) -> Result<D>;
 pub type CardFilesMap = HashMap<CardFileID, CardFileData>;
@@ -17,24 +23,41 @@ pub trait DataFiles {
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen::prelude::wasm_bindgen)]
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum CardGeneration {
     Gen1,
     Gen2,
+    /// Gen2 card with `ApplicationIdentificationV2` (Gen2 version 2).
+    Gen2v2,
+    /// Card with Gen1 and Gen2 applications, the Gen2 application can be Gen2v2.
     Combined,
 }
 
+impl CardGeneration {
+    /// Returns the generation of a Gen2 card application from its card files.
+    pub fn from_card_files(card_files: &CardFilesMap) -> CardGeneration {
+        if card_files.contains_key(&CardFileID::ApplicationIdentificationV2) {
+            CardGeneration::Gen2v2
+        } else {
+            CardGeneration::Gen2
+        }
+    }
+}
+
 impl fmt::Display for CardGeneration {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
             CardGeneration::Combined => "Combined",
             CardGeneration::Gen1 => "Gen1",
             CardGeneration::Gen2 => "Gen2",
+            CardGeneration::Gen2v2 => "Gen2v2",
         };
         write!(f, "{}", s)
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardFileData {
     #[serde(rename = "cardFileId")]
     pub card_file_id: CardFileID,
@@ -44,6 +67,10 @@ pub struct CardFileData {
     pub size: u32,
     pub signature: Option<Vec<u8>>,
     pub data: Option<Vec<u8>>,
+    /// Position of `data` in the source data, it keeps the order of the card files when
+    /// the card is written back from its deserialized form.
+    #[serde(default)]
+    pub offset: usize,
 }
 
 impl CardFileData {
@@ -66,6 +93,22 @@ impl CardFileData {
     pub fn data_len(&self) -> usize {
         if let Some(data) = &self.data { data.len() } else { 0 }
     }
+
+    /// Starts the span section of the card file, the positions of the data reader are relative to it.
+    pub(crate) fn span_section(&self) -> SpanSection {
+        SpanRecorder::section(self.card_file_id.to_string(), self.offset, self.data_len())
+    }
+
+    /// Reads the data of the card file with `read` in the span section of the card file. An error
+    /// gets the offset of the failed read in the source data and the card file in its path.
+    pub(crate) fn read_data_with<T>(&self, read: impl FnOnce(&mut BinMemoryBuffer) -> Result<T>) -> Result<T> {
+        let mut reader = self.data_into_reader()?;
+        let _section = self.span_section();
+        match read(&mut reader) {
+            Ok(value) => Ok(value),
+            Err(error) => Err(error.at_offset(SpanRecorder::absolute(reader.pos()?)).in_path(self.card_file_id.to_string())),
+        }
+    }
 }
 
 impl Readable<CardFileData> for CardFileData {
@@ -75,6 +118,7 @@ impl Readable<CardFileData> for CardFileData {
         let size = reader.read_u16::<BigEndian>()? as u32;
 
         let mut card_file_notes: String = "".to_owned();
+        let offset = SpanRecorder::absolute(reader.pos()?);
         let data = if reader.pos()? == reader.len()? {
             card_file_notes = "Missing card file data after header".to_owned();
             None
@@ -82,7 +126,38 @@ impl Readable<CardFileData> for CardFileData {
             Some(reader.read_into_vec(size)?)
         };
 
-        Ok(Self { card_file_id, appendix, card_file_notes, size, signature: None, data })
+        Ok(Self { card_file_id, appendix, card_file_notes, size, signature: None, data, offset })
+    }
+}
+
+impl CardFileData {
+    fn write_header<W: WriteBytes>(&self, writer: &mut W, appendix: u8, size: usize) -> Result<()> {
+        let size = u16::try_from(size)
+            .map_err(|_| Error::InvalidDataEncode(format!("{} is longer than {} bytes", self.card_file_id, u16::MAX)))?;
+        writer.write_u16::<BigEndian>(u16::from(&self.card_file_id))?;
+        writer.write_u8(appendix)?;
+        writer.write_u16::<BigEndian>(size)?;
+        Ok(())
+    }
+}
+
+impl Writable for CardFileData {
+    /// Writes a `CardFileData` to a binary stream, conforming to the DDD file format.
+    /// The signature is written as the following card file with the signature appendix.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        match &self.data {
+            Some(data) => {
+                self.write_header(writer, self.appendix, data.len())?;
+                writer.write_all(data)?;
+            }
+            // Card file header without data at the end of a partial file.
+            None => self.write_header(writer, self.appendix, self.size as usize)?,
+        }
+        if let Some(signature) = &self.signature {
+            self.write_header(writer, self.appendix + 1, signature.len())?;
+            writer.write_all(signature)?;
+        }
+        Ok(())
     }
 }
 
@@ -122,6 +197,8 @@ impl CardFilesDataByCardGeneration {
         }
     }
 
+    /// Returns the card generation detected from the appendix of the card files,
+    /// 0/1 are Gen1 files and 2/3 are Gen2 files.
     pub fn get_card_generation(&self) -> CardGeneration {
         if !self.card_files_data_gen1.is_empty() && !self.card_files_data_gen2.is_empty() {
             return CardGeneration::Combined;
@@ -129,8 +206,24 @@ impl CardFilesDataByCardGeneration {
         if !self.card_files_data_gen1.is_empty() {
             return CardGeneration::Gen1;
         }
+        if self.is_gen2_v2() {
+            return CardGeneration::Gen2v2;
+        }
         CardGeneration::Gen2
     }
+
+    /// Returns true when the Gen2 application contains `ApplicationIdentificationV2`.
+    pub fn is_gen2_v2(&self) -> bool {
+        CardGeneration::from_card_files(&self.card_files_data_gen2.card_files_data) == CardGeneration::Gen2v2
+    }
+
+    /// Returns the `TachographDataGeneration` of the data, Gen2 for combined cards.
+    pub fn get_data_generation(&self) -> TachographDataGeneration {
+        match self.get_card_generation() {
+            CardGeneration::Gen1 => TachographDataGeneration::FirstGeneration,
+            _ => TachographDataGeneration::SecondGeneration,
+        }
+    }
 }
 
 impl Default for CardFilesDataByCardGeneration {
@@ -156,38 +249,47 @@ impl<D> dyn Card<D> {
     }
 
     pub fn parse_ic(card_data_files: &CardFilesMap) -> Result<CardChipIdentification> {
-        let mut reader = <dyn Card<D>>::get_mem_reader(&CardFileID::IC, card_data_files)?;
-        let card_chip_identification = CardChipIdentification::read(&mut reader)?;
-        Ok(card_chip_identification)
+        <dyn Card<D>>::parse_by_card_file_id(&CardFileID::IC, card_data_files)
     }
 
     pub fn parse_icc(card_data_files: &CardFilesMap) -> Result<CardIccIdentification> {
-        let mut reader = <dyn Card<D>>::get_mem_reader(&CardFileID::ICC, card_data_files)?;
-        let card_icc_identification = CardIccIdentification::read(&mut reader)?;
-        Ok(card_icc_identification)
+        <dyn Card<D>>::parse_by_card_file_id(&CardFileID::ICC, card_data_files)
     }
 
-    pub fn parse_card_application_identification<T: Readable<T>>(card_data_files: &CardFilesMap) -> Result<T> {
-        let mut reader = <dyn Card<D>>::get_mem_reader(&CardFileID::ApplicationIdentification, card_data_files)?;
-        let application_identification = T::read(&mut reader)?;
-        Ok(application_identification)
+    pub fn parse_card_application_identification<T: Readable<T> + Serialize>(card_data_files: &CardFilesMap) -> Result<T> {
+        <dyn Card<D>>::parse_by_card_file_id(&CardFileID::ApplicationIdentification, card_data_files)
     }
 
     pub fn parse_application_identification(card_data_files: &CardFilesMap) -> Result<ApplicationIdentification> {
-        let mut reader = <dyn Card<D>>::get_mem_reader(&CardFileID::ApplicationIdentification, card_data_files)?;
-        let application_identification = ApplicationIdentification::read(&mut reader)?;
-        Ok(application_identification)
+        <dyn Card<D>>::parse_by_card_file_id(&CardFileID::ApplicationIdentification, card_data_files)
     }
 
-    pub fn parse_by_card_file_id<T: Readable<T>>(card_file_id: &CardFileID, card_data_files: &CardFilesMap) -> Result<T> {
-        let mut reader = <dyn Card<D>>::get_mem_reader(card_file_id, card_data_files)?;
-        let result = T::read(&mut reader)?;
-        Ok(result)
+    pub fn parse_by_card_file_id<T: Readable<T> + Serialize>(
+        card_file_id: &CardFileID,
+        card_data_files: &CardFilesMap,
+    ) -> Result<T> {
+        let card_file = card_data_files.get(card_file_id).ok_or_else(|| Error::MissingCardFile(card_file_id.to_string()))?;
+        card_file.read_data_with(|reader| SpanRecorder::read(reader, |reader| T::read(reader)))
+    }
+
+    /// Returns the error of a card file (EF) back in strict mode. In lenient mode the error is
+    /// kept in `ef_errors` and reported as a diagnostic, the rest of the card is still parsed.
+    pub fn add_card_file_error(
+        ef_errors: &mut HashMap<CardFileID, Diagnostic>,
+        card_file_id: &CardFileID,
+        error: Error,
+    ) -> Result<()> {
+        debug!("Card::add_card_file_error - ID: {card_file_id:?}, Error: {error}");
+        let error = error.in_section(card_file_id.to_string());
+        if ParseContext::is_lenient() {
+            ef_errors.insert(card_file_id.clone(), Diagnostic::from_error(Severity::Error, &error));
+        }
+        ParseContext::recover(error)
     }
 
     fn procces_card_data_file(data_file: CardFileData, card_items: &mut CardFilesDataByCardGeneration) -> Result<()> {
         match data_file.card_file_id {
-            CardFileID::Unknown => {
+            CardFileID::Unknown(_) => {
                 debug!("Card::procces_card_data_file - CardDataFile: {data_file:?}");
                 return Err(Error::UnknownCardType);
             }
@@ -241,24 +343,57 @@ impl<D> dyn Card<D> {
     }
 
     pub fn from_data<R: ReadBytes + BinSeek>(reader: &mut R, parse_card: &CardParseFunc<D>) -> Result<D> {
+        let card_data_files = <dyn Card<D>>::read_card_files(reader)?;
+        let data = parse_card(&card_data_files)?;
+        Ok(data)
+    }
+
+    /// Reads all card files (EF) and sorts them by the generation of the card application.
+    pub fn read_card_files<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<CardFilesDataByCardGeneration> {
         let mut card_data_files = CardFilesDataByCardGeneration::new();
 
         while reader.pos()? < reader.len()? {
-            let current_data_file = CardFileData::read(reader)?;
+            let offset = reader.pos()?;
+            let current_data_file = match SpanRecorder::read(reader, |reader| CardFileData::read(reader)) {
+                Ok(current_data_file) => current_data_file,
+                Err(error) => {
+                    ParseContext::recover(error.at_offset(SpanRecorder::absolute(offset)))?;
+                    break;
+                }
+            };
             debug!("Card::from_data - {:?}, Length : {:?}", current_data_file.card_file_id.clone(), current_data_file.data_len());
-            <dyn Card<D>>::procces_card_data_file(current_data_file, &mut card_data_files)?;
+            let card_file_id = current_data_file.card_file_id.to_string();
+            if let Err(error) = <dyn Card<D>>::procces_card_data_file(current_data_file, &mut card_data_files) {
+                ParseContext::recover(error.at_offset(SpanRecorder::absolute(offset)).in_section(card_file_id))?;
+            }
         }
 
         // Card Data is Partial
         if reader.pos()? != reader.len()? {
-            return Err(Error::PartialCardFile);
+            ParseContext::recover(Error::PartialCardFile.at_offset(SpanRecorder::absolute(reader.pos()?)))?;
         }
 
-        let data = parse_card(&card_data_files)?;
-        Ok(data)
+        Ok(card_data_files)
     }
 }
 
 pub trait CardParser<T> {
     fn parse(card_data_files: &HashMap<CardFileID, CardFileData>, card_notes: &str) -> Result<Box<T>>;
 }
+
+/// Writes the card files (EF) of a parsed card application, the counterpart of `CardParser`.
+pub trait CardWriter {
+    /// Returns the data of the card file written from the parsed structure, `None` when
+    /// the card file is not parsed by the card application.
+    fn write_card_file(&self, card_file_id: &CardFileID) -> Result<Option<Vec<u8>>>;
+}
+
+/// Returns the data of an optional card file written with `Writable`.
+pub(crate) fn write_card_file<T: Writable>(value: &Option<T>) -> Result<Option<Vec<u8>>> {
+    value.as_ref().map(|value| write_to_vec(|writer| value.write(writer))).transpose()
+}
+
+/// Returns the data of an optional card file written with `WritableWithParams`.
+pub(crate) fn write_card_file_with_params<T: WritableWithParams>(value: &Option<T>, params: &T::P) -> Result<Option<Vec<u8>>> {
+    value.as_ref().map(|value| write_to_vec(|writer| value.write(writer, params))).transpose()
+}
@@ -1,12 +1,13 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{HexDisplay, Readable};
+use crate::{HexDisplay, Readable, SpanRecorder, Writable, write_octet_string};
 
 /// Information, stored in a card, related to the identification of the card's
 /// Integrated Circuit (IC) (Annex 1C requirement 249). The icSerialNumber
 /// together with the icManufacturingReferences identifies the card chip
 /// uniquely. The icSerialNumber alone does not uniquely identify the card chip.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardChipIdentification {
     #[serde(rename = "icSerialNumber")]
     pub ic_serial_number: Vec<u8>,
@@ -20,8 +21,10 @@ pub struct CardChipIdentification {
 
 impl Readable<CardChipIdentification> for CardChipIdentification {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<CardChipIdentification> {
-        let ic_serial_number = reader.read_into_vec(4)?;
-        let ic_manufacturing_references = reader.read_into_vec(4)?;
+        let ic_serial_number =
+            SpanRecorder::field("icSerialNumber", "OCTET STRING", reader, |reader| Ok(reader.read_into_vec(4)?))?;
+        let ic_manufacturing_references =
+            SpanRecorder::field("icManufacturingReferences", "OCTET STRING", reader, |reader| Ok(reader.read_into_vec(4)?))?;
 
         let ic_serial_number_hex = ic_serial_number.to_hex_string();
         let ic_manufacturing_references_hex = ic_manufacturing_references.to_hex_string();
@@ -29,3 +32,12 @@ impl Readable<CardChipIdentification> for CardChipIdentification {
         Ok(Self { ic_serial_number, ic_manufacturing_references, ic_serial_number_hex, ic_manufacturing_references_hex })
     }
 }
+
+impl Writable for CardChipIdentification {
+    /// Writes a `CardChipIdentification` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        write_octet_string(writer, &self.ic_serial_number, 4)?;
+        write_octet_string(writer, &self.ic_manufacturing_references, 4)?;
+        Ok(())
+    }
+}
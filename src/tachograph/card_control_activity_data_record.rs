@@ -1,14 +1,15 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable,
+    Readable, ReadableField, SpanRecorder, Writable,
     tacho::{ControlType, FullCardNumber, TimeReal, VehicleRegistrationIdentification},
 };
 
 /// Information, stored in a driver or workshop card, related to the last
 /// control the driver has been subject to (Annex 1C requirements 274,
 /// 299, 327, and 350).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardControlActivityDataRecord {
     #[serde(rename = "controlType")]
     pub control_type: ControlType,
@@ -28,12 +29,13 @@ pub struct CardControlActivityDataRecord {
 
 impl Readable<CardControlActivityDataRecord> for CardControlActivityDataRecord {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<CardControlActivityDataRecord> {
-        let control_type: ControlType = reader.read_u8()?.into();
-        let control_time = TimeReal::read(reader)?;
-        let control_card_number = FullCardNumber::read(reader)?;
-        let control_vehicle_registration = VehicleRegistrationIdentification::read(reader)?;
-        let control_download_period_begin = TimeReal::read(reader)?;
-        let control_download_period_end = TimeReal::read(reader)?;
+        let control_type: ControlType =
+            SpanRecorder::field("controlType", "ControlType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let control_time = TimeReal::read_field(reader, "controlTime")?;
+        let control_card_number = FullCardNumber::read_field(reader, "controlCardNumber")?;
+        let control_vehicle_registration = VehicleRegistrationIdentification::read_field(reader, "controlVehicleRegistration")?;
+        let control_download_period_begin = TimeReal::read_field(reader, "controlDownloadPeriodBegin")?;
+        let control_download_period_end = TimeReal::read_field(reader, "controlDownloadPeriodEnd")?;
         Ok(Self {
             control_type,
             control_time,
@@ -44,3 +46,16 @@ impl Readable<CardControlActivityDataRecord> for CardControlActivityDataRecord {
         })
     }
 }
+
+impl Writable for CardControlActivityDataRecord {
+    /// Writes a `CardControlActivityDataRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8((&self.control_type).into())?;
+        self.control_time.write(writer)?;
+        self.control_card_number.write(writer)?;
+        self.control_vehicle_registration.write(writer)?;
+        self.control_download_period_begin.write(writer)?;
+        self.control_download_period_end.write(writer)?;
+        Ok(())
+    }
+}
@@ -1,13 +1,14 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable,
+    Readable, ReadableField, Writable,
     tacho::{TimeReal, VehicleRegistrationIdentification},
 };
 
 /// Information about the actual usage of the card (Annex 1C requirement
 /// 273, 298, 326, and 349).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardCurrentUse {
     #[serde(rename = "sessionOpenTime")]
     pub session_open_time: TimeReal,
@@ -17,8 +18,17 @@ pub struct CardCurrentUse {
 
 impl Readable<CardCurrentUse> for CardCurrentUse {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<CardCurrentUse> {
-        let session_open_time = TimeReal::read(reader)?;
-        let session_open_vehicle = VehicleRegistrationIdentification::read(reader)?;
+        let session_open_time = TimeReal::read_field(reader, "sessionOpenTime")?;
+        let session_open_vehicle = VehicleRegistrationIdentification::read_field(reader, "sessionOpenVehicle")?;
         Ok(Self { session_open_time, session_open_vehicle })
     }
 }
+
+impl Writable for CardCurrentUse {
+    /// Writes a `CardCurrentUse` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.session_open_time.write(writer)?;
+        self.session_open_vehicle.write(writer)?;
+        Ok(())
+    }
+}
@@ -1,15 +1,18 @@
-use binary_data::{BigEndian, BinRingMemoryBuffer, BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BigEndian, BinRingMemoryBuffer, BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    BCDString, Error, Readable, ReadableWithParams, Result,
+    BCDString, Error, ParseContext, Readable, ReadableField, ReadableWithParams, ReadableWithParamsField, Result, SpanRecorder,
+    Writable, WritableWithParams, field_value,
     tacho::{ActivityCard, ActivityChangeInfo, ActivityChangeInfoParams, TimeReal},
+    write_bcd_string, write_to_vec,
 };
 
 /// Information, stored in a card, related to the driver activities for a
 /// particular calendar day. This data type is related to Annex 1C
 /// requirements 266, 291, 320 and 343.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardActivityDailyRecord {
     #[serde(rename = "activityPreviousRecordLength")]
     pub activity_previous_record_length: u16,
@@ -29,14 +32,19 @@ impl Readable<CardActivityDailyRecord> for CardActivityDailyRecord {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<CardActivityDailyRecord> {
         let position = reader.pos()?;
         let reader_length = reader.len()?;
-        let activity_previous_record_length = reader.read_u16::<BigEndian>()?;
-        let activity_record_length = reader.read_u16::<BigEndian>()?;
+        let activity_previous_record_length =
+            SpanRecorder::field("activityPreviousRecordLength", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
+        let activity_record_length =
+            SpanRecorder::field("activityRecordLength", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
         if activity_record_length % 2 != 0 {
             return Err(Error::CardActivityDailyRecord("Card Activity Record Length is not even".to_owned()));
         }
-        let record_date = TimeReal::read(reader)?;
-        let daily_presence_counter = BCDString::decode(&reader.read_into_vec(2)?)?;
-        let day_distance = reader.read_u16::<BigEndian>()?;
+        let record_date = TimeReal::read_field(reader, "activityRecordDate")?;
+        let daily_presence_counter = SpanRecorder::field("activityDailyPresenceCounter", "BCDString", reader, |reader| {
+            BCDString::decode(&reader.read_into_vec(2)?)
+        })?;
+        let day_distance =
+            SpanRecorder::field("activityDayDistance", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
 
         if activity_record_length == 0 {
             return Ok(Self {
@@ -57,7 +65,8 @@ impl Readable<CardActivityDailyRecord> for CardActivityDailyRecord {
         let mut activity_counter = 0;
         let params = ActivityChangeInfoParams::new(ActivityCard::Card);
         while reader.pos()? != end_pos {
-            let activity_info = ActivityChangeInfo::read(reader, &params)?;
+            let activity_info =
+                ActivityChangeInfo::read_field_with(reader, &params, &format!("activityChangeInfo[{activity_counter}]"))?;
             activity_infos.push(activity_info);
             activity_counter += 1;
             if activity_counter > 1440 {
@@ -91,7 +100,8 @@ impl CardDriverActivityParams {
 
 /// Information, stored in a driver or a workshop card, related to the
 /// activities of the driver
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardDriverActivity {
     #[serde(rename = "activityPointerOldestDayRecord")]
     pub activity_pointer_oldest_day_record: u32,
@@ -106,24 +116,53 @@ impl ReadableWithParams<CardDriverActivity> for CardDriverActivity {
 
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<CardDriverActivity> {
         let card_activity_length_range = params.card_activity_length_range;
-        let activity_pointer_oldest_day_record = reader.read_u16::<BigEndian>()? as u32;
-        let activity_pointer_newest_record = reader.read_u16::<BigEndian>()? as u32;
+        let activity_pointer_oldest_day_record =
+            SpanRecorder::field("activityPointerOldestDayRecord", "INTEGER", reader, |reader| {
+                Ok(reader.read_u16::<BigEndian>()? as u32)
+            })?;
+        let activity_pointer_newest_record = SpanRecorder::field("activityPointerNewestRecord", "INTEGER", reader, |reader| {
+            Ok(reader.read_u16::<BigEndian>()? as u32)
+        })?;
+        let activity_daily_records_offset = SpanRecorder::absolute(reader.pos()?);
         let activity_daily_records_raw = reader.read_into_vec(card_activity_length_range)?;
 
-        if activity_pointer_oldest_day_record >= card_activity_length_range {
-            return Err(Error::RecordOutOfRange("Oldest Day Record".to_owned()));
-        }
-        if activity_pointer_newest_record >= card_activity_length_range {
-            return Err(Error::RecordOutOfRange("Newest Day Record".to_owned()));
+        let mut daily_records: Vec<CardActivityDailyRecord> = Vec::new();
+        let out_of_range = if activity_pointer_oldest_day_record >= card_activity_length_range {
+            Some("Oldest Day Record")
+        } else if activity_pointer_newest_record >= card_activity_length_range {
+            Some("Newest Day Record")
+        } else {
+            None
+        };
+        if let Some(pointer) = out_of_range {
+            ParseContext::recover(Error::RecordOutOfRange(pointer.to_owned()))?;
+            return Ok(Self {
+                activity_pointer_oldest_day_record,
+                activity_pointer_newest_record,
+                activity_daily_records: daily_records,
+            });
         }
 
-        let mut daily_records: Vec<CardActivityDailyRecord> = Vec::new();
+        // The positions of the daily records are positions in the ring buffer.
+        let _section = SpanRecorder::ring_section(
+            "activityDailyRecords".to_owned(),
+            activity_daily_records_offset,
+            card_activity_length_range as usize,
+        );
         let mut activity_reader =
             BinRingMemoryBuffer::new_with_offset(activity_daily_records_raw, activity_pointer_oldest_day_record as usize);
 
         loop {
             let position = activity_reader.pos()?;
-            let record = CardActivityDailyRecord::read(&mut activity_reader)?;
+            let field = format!("activityDailyRecords[{}]", daily_records.len());
+            let record = match CardActivityDailyRecord::read_field(&mut activity_reader, &field) {
+                Ok(record) => record,
+                Err(error) => {
+                    // The ring buffer can not be followed after a broken record, keep the records read so far.
+                    ParseContext::recover(error.at_offset(SpanRecorder::absolute(activity_reader.pos()?)))?;
+                    break;
+                }
+            };
             daily_records.push(record);
 
             if position == activity_pointer_newest_record as usize {
@@ -134,3 +173,51 @@ impl ReadableWithParams<CardDriverActivity> for CardDriverActivity {
         Ok(Self { activity_pointer_oldest_day_record, activity_pointer_newest_record, activity_daily_records: daily_records })
     }
 }
+
+impl Writable for CardActivityDailyRecord {
+    /// Writes a `CardActivityDailyRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u16::<BigEndian>(self.activity_previous_record_length)?;
+        writer.write_u16::<BigEndian>(self.activity_record_length)?;
+        self.activity_record_date.write(writer)?;
+        write_bcd_string(writer, &self.activity_daily_presence_counter, 2)?;
+        writer.write_u16::<BigEndian>(self.activity_day_distance)?;
+        for activity_change_info in self.activity_change_info.iter() {
+            activity_change_info.write(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl WritableWithParams for CardDriverActivity {
+    type P = CardDriverActivityParams;
+
+    /// Writes the pointers and the cyclic buffer of `card_activity_length_range` bytes, the daily
+    /// records are written from the oldest day record pointer, the rest of the buffer is zero.
+    /// The record lengths and the newest record pointer are derived from the written records,
+    /// the previous record length of the oldest record is kept.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W, params: &Self::P) -> Result<()> {
+        let mut activity_daily_records = vec![0; params.card_activity_length_range as usize];
+        let ring_len = activity_daily_records.len();
+        let mut position = self.activity_pointer_oldest_day_record as usize;
+        let mut newest_record = self.activity_pointer_newest_record as usize;
+        let mut previous_record_length = self.activity_daily_records.first().map(|record| record.activity_previous_record_length);
+        for record in self.activity_daily_records.iter().filter(|_| ring_len > 0) {
+            let mut bytes = write_to_vec(|buffer| record.write(buffer))?;
+            let record_length: u16 = field_value("activityRecordLength", bytes.len())?;
+            bytes[0..2].copy_from_slice(&previous_record_length.unwrap_or_default().to_be_bytes());
+            bytes[2..4].copy_from_slice(&record_length.to_be_bytes());
+            previous_record_length = Some(record_length);
+            newest_record = position % ring_len;
+            for byte in bytes {
+                activity_daily_records[position % ring_len] = byte;
+                position += 1;
+            }
+        }
+
+        writer.write_u16::<BigEndian>(field_value("activityPointerOldestDayRecord", self.activity_pointer_oldest_day_record)?)?;
+        writer.write_u16::<BigEndian>(field_value("activityPointerNewestRecord", newest_record)?)?;
+        writer.write_all(&activity_daily_records)?;
+        Ok(())
+    }
+}
@@ -1,15 +1,17 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    CodePage, Error, Readable, bytes_to_string,
+    CodePage, Error, Readable, ReadableField, SpanRecorder, Writable, bytes_to_string,
     tacho::{Name, NationNumeric},
+    write_string,
 };
 
 const DRIVING_LICENSE_NUMBER_LENGTH: u32 = 16;
 
 /// Information, stored in a driver card, related to the card holder driver
 /// licence data (Annex 1C requirement 259 and 284).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardDrivingLicenceInformation {
     #[serde(rename = "drivingLicenceIssuingAuthority")]
     pub driving_licence_issuing_authority: Name,
@@ -21,10 +23,12 @@ pub struct CardDrivingLicenceInformation {
 
 impl Readable<CardDrivingLicenceInformation> for CardDrivingLicenceInformation {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<CardDrivingLicenceInformation> {
-        let driving_licence_issuing_authority = Name::read(reader)?;
-        let driving_licence_issuing_nation: NationNumeric = reader.read_u8()?.into();
-        let driving_licence_number =
-            bytes_to_string(&reader.read_into_vec(DRIVING_LICENSE_NUMBER_LENGTH)?, &CodePage::IsoIec8859_1);
+        let driving_licence_issuing_authority = Name::read_field(reader, "drivingLicenceIssuingAuthority")?;
+        let driving_licence_issuing_nation: NationNumeric =
+            SpanRecorder::field("drivingLicenceIssuingNation", "NationNumeric", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let driving_licence_number = SpanRecorder::field("drivingLicenceNumber", "IA5String", reader, |reader| {
+            Ok(bytes_to_string(&reader.read_into_vec(DRIVING_LICENSE_NUMBER_LENGTH)?, &CodePage::IsoIec8859_1))
+        })?;
         if !driving_licence_issuing_authority.name.is_empty() && driving_licence_number.is_empty() {
             return Err(Error::CorruptedDrivingLicenceNumber);
         }
@@ -32,3 +36,13 @@ impl Readable<CardDrivingLicenceInformation> for CardDrivingLicenceInformation {
         Ok(Self { driving_licence_issuing_authority, driving_licence_issuing_nation, driving_licence_number })
     }
 }
+
+impl Writable for CardDrivingLicenceInformation {
+    /// Writes a `CardDrivingLicenceInformation` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.driving_licence_issuing_authority.write(writer)?;
+        writer.write_u8((&self.driving_licence_issuing_nation).into())?;
+        write_string(writer, &self.driving_licence_number, &CodePage::IsoIec8859_1, DRIVING_LICENSE_NUMBER_LENGTH as usize)?;
+        Ok(())
+    }
+}
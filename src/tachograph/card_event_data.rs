@@ -1,11 +1,13 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, ReadableWithParams, Result,
+    Readable, ReadableField, ReadableWithParams, Result, SpanRecorder, Writable, WritableWithParams,
     tacho::{EventFaultType, TimeReal, VehicleRegistrationIdentification},
 };
 
+const CARD_EVENT_RECORD_LENGTH: usize = 24;
+
 #[derive(Debug)]
 pub struct CardEventDataParams {
     pub no_of_records: u8,
@@ -20,7 +22,8 @@ impl CardEventDataParams {
 
 /// Information, stored in a driver or a workshop card, related to an event
 /// associated to the card holder (Annex 1C requirements 261, 286, 318 and 341).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardEventRecord {
     #[serde(rename = "eventType")]
     pub event_type: EventFaultType,
@@ -34,10 +37,11 @@ pub struct CardEventRecord {
 
 impl Readable<CardEventRecord> for CardEventRecord {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<CardEventRecord> {
-        let event_type = reader.read_u8()?.into();
-        let event_begin_time = TimeReal::read(reader)?;
-        let event_end_time = TimeReal::read(reader)?;
-        let event_vehicle_registration = VehicleRegistrationIdentification::read(reader)?;
+        let event_type: EventFaultType =
+            SpanRecorder::field("eventType", "EventFaultType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let event_begin_time = TimeReal::read_field(reader, "eventBeginTime")?;
+        let event_end_time = TimeReal::read_field(reader, "eventEndTime")?;
+        let event_vehicle_registration = VehicleRegistrationIdentification::read_field(reader, "eventVehicleRegistration")?;
 
         Ok(Self { event_type, event_begin_time, event_end_time, event_vehicle_registration })
     }
@@ -46,7 +50,8 @@ impl Readable<CardEventRecord> for CardEventRecord {
 /// This is a sequence, ordered by ascending value of EventFaultType,
 /// of cardEventRecords (except security breach attempts related
 /// records which are gathered in the last set of the sequence).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardEventData {
     #[serde(rename = "noOfRecords")]
     pub no_of_records: u8,
@@ -62,11 +67,11 @@ impl ReadableWithParams<CardEventData> for CardEventData {
         let no_of_events_per_type = params.no_of_events_per_type;
 
         let mut card_event_records: Vec<Vec<CardEventRecord>> = Vec::new();
-        for _ in 0..no_of_records {
+        for group in 0..no_of_records {
             let mut records: Vec<CardEventRecord> = Vec::new();
 
-            for _ in 0..no_of_events_per_type {
-                let record = CardEventRecord::read(reader)?;
+            for index in 0..no_of_events_per_type {
+                let record = CardEventRecord::read_field(reader, &format!("cardEventRecords[{group}][{index}]"))?;
                 if record.event_type != EventFaultType::NoFurtherDetails
                     || record.event_begin_time.get_data() != 0
                     || record.event_end_time.get_data() != 0
@@ -82,3 +87,33 @@ impl ReadableWithParams<CardEventData> for CardEventData {
         Ok(Self { no_of_records, card_event_records })
     }
 }
+
+impl Writable for CardEventRecord {
+    /// Writes a `CardEventRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8((&self.event_type).into())?;
+        self.event_begin_time.write(writer)?;
+        self.event_end_time.write(writer)?;
+        self.event_vehicle_registration.write(writer)?;
+        Ok(())
+    }
+}
+
+impl WritableWithParams for CardEventData {
+    type P = CardEventDataParams;
+
+    /// Writes the event records by the event groups, the empty records and groups, which are not
+    /// kept when the data is read, are written as zeros after the records of the groups.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W, params: &Self::P) -> Result<()> {
+        let no_of_events_per_type = params.no_of_events_per_type as usize;
+        for index in 0..params.no_of_records as usize {
+            let records = self.card_event_records.get(index).map_or(&[][..], |records| records.as_slice());
+            for record in records.iter().take(no_of_events_per_type) {
+                record.write(writer)?;
+            }
+            let no_of_empty_records = no_of_events_per_type.saturating_sub(records.len());
+            writer.write_all(&vec![0; no_of_empty_records * CARD_EVENT_RECORD_LENGTH])?;
+        }
+        Ok(())
+    }
+}
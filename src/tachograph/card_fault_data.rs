@@ -1,14 +1,18 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, ReadableWithParams, Result,
+    Readable, ReadableField, ReadableWithParams, Result, SpanRecorder, Writable, WritableWithParams,
     tacho::{EventFaultType, TimeReal, VehicleRegistrationIdentification},
 };
 
+const CARD_FAULT_RECORD_LENGTH: usize = 24;
+const CARD_FAULT_GROUPS: usize = 2;
+
 /// Information, stored in a driver or a workshop card, related to a fault
 /// associated to the card holder (Annex 1C requirement 264, 289, 318, and 341).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardFaultRecord {
     #[serde(rename = "faultType")]
     pub fault_type: EventFaultType,
@@ -22,10 +26,11 @@ pub struct CardFaultRecord {
 
 impl Readable<CardFaultRecord> for CardFaultRecord {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<CardFaultRecord> {
-        let fault_type = reader.read_u8()?.into();
-        let fault_begin_time = TimeReal::read(reader)?;
-        let fault_end_time = TimeReal::read(reader)?;
-        let fault_vehicle_registration = VehicleRegistrationIdentification::read(reader)?;
+        let fault_type: EventFaultType =
+            SpanRecorder::field("faultType", "EventFaultType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let fault_begin_time = TimeReal::read_field(reader, "faultBeginTime")?;
+        let fault_end_time = TimeReal::read_field(reader, "faultEndTime")?;
+        let fault_vehicle_registration = VehicleRegistrationIdentification::read_field(reader, "faultVehicleRegistration")?;
 
         Ok(Self { fault_type, fault_begin_time, fault_end_time, fault_vehicle_registration })
     }
@@ -44,7 +49,8 @@ impl CardFaultDataParams {
 
 /// Information, stored in a driver or a workshop card, related to the faults
 /// associated to the card holder (Annex 1C requirements 263, 288, 318, and 341).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardFaultData {
     #[serde(rename = "noFaultsPerType")]
     pub no_faults_per_type: u8,
@@ -59,10 +65,10 @@ impl ReadableWithParams<CardFaultData> for CardFaultData {
         let no_faults_per_type = params.no_faults_per_type;
 
         let mut card_fault_records: Vec<Vec<CardFaultRecord>> = Vec::new();
-        for _ in 0..2 {
+        for group in 0..CARD_FAULT_GROUPS {
             let mut records: Vec<CardFaultRecord> = Vec::new();
-            for _ in 0..no_faults_per_type {
-                let record = CardFaultRecord::read(reader)?;
+            for index in 0..no_faults_per_type {
+                let record = CardFaultRecord::read_field(reader, &format!("cardFaultRecords[{group}][{index}]"))?;
                 if record.fault_type != EventFaultType::NoFurtherDetails
                     || record.fault_begin_time.data != 0
                     || record.fault_end_time.data != 0
@@ -77,3 +83,33 @@ impl ReadableWithParams<CardFaultData> for CardFaultData {
         Ok(Self { no_faults_per_type, card_fault_records })
     }
 }
+
+impl Writable for CardFaultRecord {
+    /// Writes a `CardFaultRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8((&self.fault_type).into())?;
+        self.fault_begin_time.write(writer)?;
+        self.fault_end_time.write(writer)?;
+        self.fault_vehicle_registration.write(writer)?;
+        Ok(())
+    }
+}
+
+impl WritableWithParams for CardFaultData {
+    type P = CardFaultDataParams;
+
+    /// Writes the fault records by the fault groups, the empty records and groups, which are not
+    /// kept when the data is read, are written as zeros after the records of the groups.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W, params: &Self::P) -> Result<()> {
+        let no_faults_per_type = params.no_faults_per_type as usize;
+        for index in 0..CARD_FAULT_GROUPS {
+            let records = self.card_fault_records.get(index).map_or(&[][..], |records| records.as_slice());
+            for record in records.iter().take(no_faults_per_type) {
+                record.write(writer)?;
+            }
+            let no_of_empty_records = no_faults_per_type.saturating_sub(records.len());
+            writer.write_all(&vec![0; no_of_empty_records * CARD_FAULT_RECORD_LENGTH])?;
+        }
+        Ok(())
+    }
+}
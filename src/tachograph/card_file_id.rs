@@ -7,9 +7,10 @@ use crate::impl_enum_from_u16;
 /// Identifiers for files on a tachograph card.
 /// These files are also known as "tacho blocks".
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(u16)]
 pub enum CardFileID {
-    Unknown = 0x00,
+    Unknown(u16) = 0x00,
     /// Integrated Circuit Card.
     ICC = 0x02,
     /// Integrated Circuit.
@@ -56,6 +57,10 @@ pub enum CardFileID {
     GnssPlaces = 0x524,
     /// Border crossings.
     BorderCrossings = 0x528,
+    /// Load/unload operations.
+    LoadUnloadOperations = 0x529,
+    /// Load type entries.
+    LoadTypeEntries = 0x530,
     /// Calibration additional data.
     CalibrationAddData = 0x531,
     /// VU configuration.
@@ -75,7 +80,7 @@ pub enum CardFileID {
 impl fmt::Display for CardFileID {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
-            CardFileID::Unknown => "Unknown",
+            CardFileID::Unknown(_) => "Unknown",
             CardFileID::ICC => "ICC",
             CardFileID::IC => "IC",
             CardFileID::Tachograph => "Tachograph",
@@ -99,6 +104,8 @@ impl fmt::Display for CardFileID {
             CardFileID::VehicleUnitsUsed => "VehicleUnitsUsed",
             CardFileID::GnssPlaces => "GnssPlaces",
             CardFileID::BorderCrossings => "BorderCrossings",
+            CardFileID::LoadUnloadOperations => "LoadUnloadOperations",
+            CardFileID::LoadTypeEntries => "LoadTypeEntries",
             CardFileID::CalibrationAddData => "CalibrationAddData",
             CardFileID::VUConfiguration => "VUConfiguration",
             CardFileID::CardCertificate => "CardCertificate",
@@ -113,7 +120,6 @@ impl fmt::Display for CardFileID {
 
 impl_enum_from_u16!(
     CardFileID {
-        Unknown = 0,
         ICC = 2,
         IC = 5,
         Tachograph = 0x500,
@@ -137,6 +143,8 @@ impl_enum_from_u16!(
         VehicleUnitsUsed = 0x523,
         GnssPlaces = 0x524,
         BorderCrossings = 0x528,
+        LoadUnloadOperations = 0x529,
+        LoadTypeEntries = 0x530,
         CalibrationAddData = 0x531,
         VUConfiguration = 0x540,
         CardCertificate = 0xC100,
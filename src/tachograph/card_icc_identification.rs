@@ -1,8 +1,9 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    CodePage, Readable, bytes_to_string,
+    CodePage, Readable, ReadableField, SpanRecorder, Writable, bytes_to_string,
     tacho::{EmbedderIcAssemblerId, ExtendedSerialNumber},
+    write_octet_string, write_string,
 };
 
 const CARD_APPROVAL_NUMBER_LENGTH: u32 = 8;
@@ -10,7 +11,8 @@ const IC_IDENTIFIER_NUMBER_LENGTH: u32 = 2;
 
 /// Information, stored in a card, related to the identification of the integrated
 /// circuit (IC) card (Annex 1C requirement 248).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardIccIdentification {
     #[serde(rename = "clockStop")]
     pub clock_stop: u8,
@@ -28,18 +30,22 @@ pub struct CardIccIdentification {
 
 impl Readable<CardIccIdentification> for CardIccIdentification {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<CardIccIdentification> {
-        let clock_stop = reader.read_u8()?;
-        let card_extended_serial_number = ExtendedSerialNumber::read(reader)?;
+        let clock_stop = SpanRecorder::field("clockStop", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let card_extended_serial_number = ExtendedSerialNumber::read_field(reader, "cardExtendedSerialNumber")?;
         // It's a string encoded using IA5, which corresponds to ASCII characters.
-        let card_approval_number = bytes_to_string(&reader.read_into_vec(CARD_APPROVAL_NUMBER_LENGTH)?, &CodePage::IsoIec8859_1);
-        let card_personaliser_id = reader.read_u8()?;
-        let embedder_ic_assembler_id = EmbedderIcAssemblerId::read(reader)?;
+        let card_approval_number = SpanRecorder::field("cardApprovalNumber", "IA5String", reader, |reader| {
+            Ok(bytes_to_string(&reader.read_into_vec(CARD_APPROVAL_NUMBER_LENGTH)?, &CodePage::IsoIec8859_1))
+        })?;
+        let card_personaliser_id = SpanRecorder::field("cardPersonaliserID", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let embedder_ic_assembler_id = EmbedderIcAssemblerId::read_field(reader, "embedderIcAssemblerId")?;
         // OCTET STRING(SIZE(l))
         // You should interpret it as two raw bytes that represent a Identifier of the IC on the card (not a printable character). So:
         //  - Do not decode it as ASCII or UTF-8
         //  - Instead, treat it like a numeric or binary ID
         //  - usually interpreted as a hex code
-        let ic_identifier = reader.read_into_vec(IC_IDENTIFIER_NUMBER_LENGTH)?;
+        let ic_identifier = SpanRecorder::field("icIdentifier", "OCTET STRING", reader, |reader| {
+            Ok(reader.read_into_vec(IC_IDENTIFIER_NUMBER_LENGTH)?)
+        })?;
 
         Ok(Self {
             clock_stop,
@@ -51,3 +57,16 @@ impl Readable<CardIccIdentification> for CardIccIdentification {
         })
     }
 }
+
+impl Writable for CardIccIdentification {
+    /// Writes a `CardIccIdentification` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8(self.clock_stop)?;
+        self.card_extended_serial_number.write(writer)?;
+        write_string(writer, &self.card_approval_number, &CodePage::IsoIec8859_1, CARD_APPROVAL_NUMBER_LENGTH as usize)?;
+        writer.write_u8(self.card_personaliser_id)?;
+        self.embedder_ic_assembler_id.write(writer)?;
+        write_octet_string(writer, &self.ic_identifier, IC_IDENTIFIER_NUMBER_LENGTH as usize)?;
+        Ok(())
+    }
+}
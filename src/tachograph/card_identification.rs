@@ -1,15 +1,16 @@
 use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, ReadableWithParams, Result,
+    ReadableField, ReadableWithParams, ReadableWithParamsField, Result, SpanRecorder, Writable,
     tacho::{CardNumber, Name, NationNumeric, TimeReal},
     tachograph::card_number::CardNumberParams,
 };
 
 /// Information, stored in a card, related to the identification of the card
 /// (Annex 1C requirements 255, 280, 310, 333, 359, 365, 371, and 377).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardIdentification {
     #[serde(rename = "cardIssuingMemberState")]
     pub card_issuing_member_state: NationNumeric,
@@ -29,12 +30,13 @@ impl ReadableWithParams<CardIdentification> for CardIdentification {
     type P = CardNumberParams;
 
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<CardIdentification> {
-        let card_issuing_member_state: NationNumeric = reader.read_u8()?.into();
-        let card_number = CardNumber::read(reader, params)?;
-        let card_issuing_authority_name = Name::read(reader)?;
-        let card_issue_date = TimeReal::read(reader)?;
-        let card_validity_begin = TimeReal::read(reader)?;
-        let card_expiry_date = TimeReal::read(reader)?;
+        let card_issuing_member_state: NationNumeric =
+            SpanRecorder::field("cardIssuingMemberState", "NationNumeric", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let card_number = CardNumber::read_field_with(reader, params, "cardNumber")?;
+        let card_issuing_authority_name = Name::read_field(reader, "cardIssuingAuthorityName")?;
+        let card_issue_date = TimeReal::read_field(reader, "cardIssueDate")?;
+        let card_validity_begin = TimeReal::read_field(reader, "cardValidityBegin")?;
+        let card_expiry_date = TimeReal::read_field(reader, "cardExpiryDate")?;
 
         Ok(Self {
             card_issuing_member_state,
@@ -46,3 +48,16 @@ impl ReadableWithParams<CardIdentification> for CardIdentification {
         })
     }
 }
+
+impl Writable for CardIdentification {
+    /// Writes a `CardIdentification` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8((&self.card_issuing_member_state).into())?;
+        self.card_number.write(writer)?;
+        self.card_issuing_authority_name.write(writer)?;
+        self.card_issue_date.write(writer)?;
+        self.card_validity_begin.write(writer)?;
+        self.card_expiry_date.write(writer)?;
+        Ok(())
+    }
+}
@@ -1,7 +1,9 @@
 use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{ReadableWithParams, Result, bytes_to_ia5_fix_string, tacho::EquipmentType};
+use crate::{
+    ReadableWithParams, Result, SpanRecorder, Writable, bytes_to_ia5_fix_string, tacho::EquipmentType, write_ia5_string,
+};
 
 const CARD_NUMBER_LENGTH: u32 = 16;
 
@@ -30,7 +32,8 @@ impl CardNumberParams {
 ///         cardRenewalindex        CardRenewalindex
 ///     }
 /// }
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardNumber {
     #[serde(rename = "cardIssuingMemberState")]
     pub equipment_type: EquipmentType,
@@ -91,7 +94,9 @@ impl ReadableWithParams<CardNumber> for CardNumber {
 
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<CardNumber> {
         let equipment_type = params.equipment_type.clone();
-        let number = bytes_to_ia5_fix_string(&reader.read_into_vec(CARD_NUMBER_LENGTH)?)?;
+        let number = SpanRecorder::field("number", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(CARD_NUMBER_LENGTH)?)?)
+        })?;
         let identification = CardNumber::get_identification(&number, &equipment_type).to_string();
         let card_replacement_index = CardNumber::get_replacement_index(&number).to_string();
         let card_consecutive_index = CardNumber::get_consecutive_index(&number, &equipment_type).to_string();
@@ -100,6 +105,14 @@ impl ReadableWithParams<CardNumber> for CardNumber {
     }
 }
 
+impl Writable for CardNumber {
+    /// Writes a `CardNumber` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        write_ia5_string(writer, &self.number, CARD_NUMBER_LENGTH as usize)?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -133,6 +146,17 @@ mod tests {
         assert_eq!(card_number.card_renewal_index, "7");
     }
 
+    #[test]
+    fn test_write_card_number() {
+        let data: &[u8] = b"ABC1234567890123";
+        let mut reader = BinMemoryBuffer::from(data.to_vec());
+        let params = CardNumberParams::new(EquipmentType::DriverCard);
+        let card_number = CardNumber::read(&mut reader, &params).unwrap();
+
+        let written = crate::write_to_vec(|writer| card_number.write(writer)).unwrap();
+        assert_eq!(written, data);
+    }
+
     #[test]
     fn test_read_short_card_number() {
         let data: &[u8] = b"SHORT";
@@ -1,7 +1,9 @@
-use binary_data::{BigEndian, BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BigEndian, BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, ReadableWithParams, Result, tacho::TimeReal};
+use crate::{
+    Readable, ReadableField, ReadableWithParams, Result, SpanRecorder, Writable, WritableWithParams, field_value, tacho::TimeReal,
+};
 
 pub trait CardPlace {
     fn get_entry_time(&self) -> &TimeReal;
@@ -22,7 +24,8 @@ impl CardPlaceDailyWorkPeriodParams {
 /// Information, stored in a driver or a workshop card, related to the places
 /// where daily work periods begin and/or end (Annex 1C requirements 272,
 /// 297, 325, and 348).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardPlaceDailyWorkPeriod<T> {
     #[serde(rename = "placePointerNewestRecord")]
     pub place_pointer_newest_record: i32,
@@ -30,19 +33,21 @@ pub struct CardPlaceDailyWorkPeriod<T> {
     pub place_records: Vec<T>,
 }
 
-impl<T: Readable<T> + CardPlace> ReadableWithParams<CardPlaceDailyWorkPeriod<T>> for CardPlaceDailyWorkPeriod<T> {
+impl<T: Readable<T> + Serialize + CardPlace> ReadableWithParams<CardPlaceDailyWorkPeriod<T>> for CardPlaceDailyWorkPeriod<T> {
     type P = CardPlaceDailyWorkPeriodParams;
 
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<CardPlaceDailyWorkPeriod<T>> {
-        let place_pointer_newest_record = if params.size_of_newest_place_record == 1 {
-            reader.read_u8()? as i32
-        } else {
-            reader.read_u16::<BigEndian>()? as i32
-        };
+        let place_pointer_newest_record = SpanRecorder::field("placePointerNewestRecord", "INTEGER", reader, |reader| {
+            if params.size_of_newest_place_record == 1 {
+                Ok(reader.read_u8()? as i32)
+            } else {
+                Ok(reader.read_u16::<BigEndian>()? as i32)
+            }
+        })?;
 
         let mut place_records: Vec<T> = Vec::new();
-        for _ in 0..params.no_of_records {
-            let record = T::read(reader)?;
+        for index in 0..params.no_of_records {
+            let record = T::read_field(reader, &format!("placeRecords[{index}]"))?;
             if record.get_entry_time().has_data() {
                 place_records.push(record);
             }
@@ -51,3 +56,21 @@ impl<T: Readable<T> + CardPlace> ReadableWithParams<CardPlaceDailyWorkPeriod<T>>
         Ok(Self { place_pointer_newest_record, place_records })
     }
 }
+
+impl<T: Writable> WritableWithParams for CardPlaceDailyWorkPeriod<T> {
+    type P = CardPlaceDailyWorkPeriodParams;
+
+    /// Writes the pointer and the kept place records, the card writer pads the card file
+    /// with the empty records.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W, params: &Self::P) -> Result<()> {
+        if params.size_of_newest_place_record == 1 {
+            writer.write_u8(field_value("placePointerNewestRecord", self.place_pointer_newest_record)?)?;
+        } else {
+            writer.write_u16::<BigEndian>(field_value("placePointerNewestRecord", self.place_pointer_newest_record)?)?;
+        }
+        for record in self.place_records.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
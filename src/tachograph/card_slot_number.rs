@@ -1,9 +1,10 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::impl_enum_from_u8;
 
 /// Represents the card slot in the Vehicle Unit, distinguishing between the driver and co-driver slots.
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(u8)]
 pub enum CardSlotNumber {
     /// The slot designated for the main driver.
@@ -11,13 +12,12 @@ pub enum CardSlotNumber {
     /// The slot designated for the co-driver.
     CoDriver = 1,
     /// The card slot is unknown or not applicable.
-    Unknown = 255,
+    Unknown(u8) = 255,
 }
 
 impl_enum_from_u8!(
     CardSlotNumber {
         Driver = 0,
         CoDriver = 1,
-        Unknown = 255,
     }
 );
@@ -1,8 +1,9 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, tacho::CardSlotStatusType};
+use crate::{Readable, SpanRecorder, Writable, tacho::CardSlotStatusType};
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardSlotStatus {
     pub data: u8,
     #[serde(rename = "driverSlot")]
@@ -13,9 +14,17 @@ pub struct CardSlotStatus {
 
 impl Readable<CardSlotStatus> for CardSlotStatus {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<CardSlotStatus> {
-        let data = reader.read_u8()?;
+        let data = SpanRecorder::field("data", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
         let co_driver_slot: CardSlotStatusType = ((data >> 4) & 0xF).into();
         let driver_slot: CardSlotStatusType = (data & 0xF).into();
         Ok(Self { data, driver_slot, co_driver_slot })
     }
 }
+
+impl Writable for CardSlotStatus {
+    /// Writes a `CardSlotStatus` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8(self.data)?;
+        Ok(())
+    }
+}
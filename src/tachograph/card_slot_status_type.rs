@@ -1,11 +1,12 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::impl_enum_from_u8;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(u8)]
 pub enum CardSlotStatusType {
-    Unknown = 0,
+    Unknown(u8) = 0,
     DriverCard = 1,
     WorkshopCard = 2,
     ControlCard = 3,
@@ -14,7 +15,6 @@ pub enum CardSlotStatusType {
 
 impl_enum_from_u8!(
     CardSlotStatusType {
-        Unknown = 0,
         DriverCard = 1,
         WorkshopCard = 2,
         ControlCard = 3,
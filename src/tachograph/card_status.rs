@@ -1,9 +1,10 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::impl_enum_from_u8;
 
 /// Indicates the status of a tachograph card, specifically whether it is inserted or removed from a card slot.
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(u8)]
 pub enum CardStatus {
     /// The card is currently inserted in a card slot.
@@ -11,13 +12,12 @@ pub enum CardStatus {
     /// The card has been removed from the card slot.
     Removed = 1,
     /// The card status is unknown.
-    Unknown = 255,
+    Unknown(u8) = 255,
 }
 
 impl_enum_from_u8!(
     CardStatus {
         Inserted = 0,
         Removed = 1,
-        Unknown = 255
     }
 );
@@ -1,9 +1,10 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::Readable;
+use crate::{Readable, SpanRecorder, Writable};
 
 /// Code indicating the version of the implemented structure in a tachograph card.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardStructureVersion {
     #[serde(rename = "structureVersion")]
     pub structure_version: u8,
@@ -13,8 +14,18 @@ pub struct CardStructureVersion {
 
 impl Readable<CardStructureVersion> for CardStructureVersion {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<CardStructureVersion> {
-        let structure_version = reader.read_u8()?;
-        let data_element_use_version = reader.read_u8()?;
+        let structure_version = SpanRecorder::field("structureVersion", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let data_element_use_version =
+            SpanRecorder::field("dataElementUseVersion", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
         Ok(Self { structure_version, data_element_use_version })
     }
 }
+
+impl Writable for CardStructureVersion {
+    /// Writes a `CardStructureVersion` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8(self.structure_version)?;
+        writer.write_u8(self.data_element_use_version)?;
+        Ok(())
+    }
+}
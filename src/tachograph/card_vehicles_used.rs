@@ -1,7 +1,7 @@
 use binary_data::{BigEndian, BinSeek, ReadBytes};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, ReadableWithParams, Result, tacho::TimeReal};
+use crate::{Readable, ReadableField, ReadableWithParams, Result, SpanRecorder, Writable, tacho::TimeReal};
 
 pub trait VehicleUse {
     fn get_vehicle_first_use(&self) -> &TimeReal;
@@ -21,7 +21,8 @@ impl VehiclesUsedParams {
 
 /// Information, stored in a driver or workshop card, related to the vehicles
 /// used by the card holder (Annex 1C requirements 270, 295, 323, and 346).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardVehiclesUsed<T> {
     #[serde(rename = "vehiclePointerNewestRecord")]
     pub vehicle_pointer_newest_record: u16,
@@ -29,14 +30,15 @@ pub struct CardVehiclesUsed<T> {
     pub vehicle_records: Vec<T>,
 }
 
-impl<T: Readable<T> + VehicleUse> ReadableWithParams<CardVehiclesUsed<T>> for CardVehiclesUsed<T> {
+impl<T: Readable<T> + Serialize + VehicleUse> ReadableWithParams<CardVehiclesUsed<T>> for CardVehiclesUsed<T> {
     type P = VehiclesUsedParams;
 
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<CardVehiclesUsed<T>> {
-        let vehicle_pointer_newest_record = reader.read_u16::<BigEndian>()?;
+        let vehicle_pointer_newest_record =
+            SpanRecorder::field("vehiclePointerNewestRecord", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
         let mut vehicle_records: Vec<T> = Vec::new();
-        for _ in 0..params.no_of_card_vehicle_records {
-            let record = T::read(reader)?;
+        for index in 0..params.no_of_card_vehicle_records {
+            let record = T::read_field(reader, &format!("cardVehicleRecords[{index}]"))?;
             if record.get_vehicle_first_use().has_data() || record.get_vehicle_last_use().has_data() {
                 vehicle_records.push(record);
             }
@@ -45,3 +47,15 @@ impl<T: Readable<T> + VehicleUse> ReadableWithParams<CardVehiclesUsed<T>> for Ca
         Ok(Self { vehicle_pointer_newest_record, vehicle_records })
     }
 }
+
+impl<T: Writable> Writable for CardVehiclesUsed<T> {
+    /// Writes the pointer and the kept vehicle records, the card writer pads the card file
+    /// with the empty records.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u16::<BigEndian>(self.vehicle_pointer_newest_record)?;
+        for record in self.vehicle_records.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
@@ -1,11 +1,12 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::impl_enum_from_u16;
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(u16)]
 pub enum CertificateContentType {
-    Unknown = 0x0,
+    Unknown(u16) = 0x0,
     CertificateAuthorityReference = 0x42,
     ECCCertificate = 0x7F21,
     PublicKey = 0x7F49,
@@ -19,7 +20,6 @@ pub enum CertificateContentType {
 
 impl_enum_from_u16!(
     CertificateContentType {
-        Unknown = 0x0,
         CertificateAuthorityReference = 0x42,
         ECCCertificate = 0x7F21,
         PublicKey = 0x7F49,
@@ -1,15 +1,17 @@
-use binary_data::{BigEndian, BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BigEndian, BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, Result,
+    Readable, Result, SpanRecorder, Writable, field_value,
     tacho::{CertificateContentType, EquipmentType},
+    write_octet_string,
 };
 
 const TACHOGRAPH_APPLICATION_ID_LENGTH: u32 = 6;
 
 /// Identification of the rights of a certificate holder.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CertificateHolderAuthorisation {
     #[serde(rename = "recordType")]
     pub record_type: CertificateContentType,
@@ -23,11 +25,28 @@ pub struct CertificateHolderAuthorisation {
 
 impl Readable<CertificateHolderAuthorisation> for CertificateHolderAuthorisation {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<CertificateHolderAuthorisation> {
-        let record_type: CertificateContentType = (reader.read_u16::<BigEndian>()?).into();
-        let record_size = reader.read_u8()? as u16;
-        let tachograph_application_id = reader.read_into_vec(TACHOGRAPH_APPLICATION_ID_LENGTH)?;
-        let equipment_type: EquipmentType = reader.read_u8()?.into();
+        let record_type: CertificateContentType =
+            SpanRecorder::field("recordType", "CertificateContentType", reader, |reader| {
+                Ok((reader.read_u16::<BigEndian>()?).into())
+            })?;
+        let record_size = SpanRecorder::field("recordSize", "INTEGER", reader, |reader| Ok(reader.read_u8()? as u16))?;
+        let tachograph_application_id = SpanRecorder::field("tachographApplicationID", "OCTET STRING", reader, |reader| {
+            Ok(reader.read_into_vec(TACHOGRAPH_APPLICATION_ID_LENGTH)?)
+        })?;
+        let equipment_type: EquipmentType =
+            SpanRecorder::field("equipmentType", "EquipmentType", reader, |reader| Ok(reader.read_u8()?.into()))?;
 
         Ok(Self { record_type, record_size, tachograph_application_id, equipment_type })
     }
 }
+
+impl Writable for CertificateHolderAuthorisation {
+    /// Writes a `CertificateHolderAuthorisation` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u16::<BigEndian>((&self.record_type).into())?;
+        writer.write_u8(field_value("recordSize", self.record_size)?)?;
+        write_octet_string(writer, &self.tachograph_application_id, TACHOGRAPH_APPLICATION_ID_LENGTH as usize)?;
+        writer.write_u8((&self.equipment_type).into())?;
+        Ok(())
+    }
+}
@@ -1,13 +1,14 @@
 use binary_data::BigEndian;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, bytes_to_ia5_fix_string, tacho::NationNumeric};
+use crate::{Readable, SpanRecorder, Writable, bytes_to_ia5_fix_string, tacho::NationNumeric, write_ia5_string};
 
 const NATION_ALPHA_LENGTH: u32 = 3;
 
 /// Identifier of the Public Key of a Certification Authority (a Member State
 /// or the European Certification Authority).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CertificationAuthorityKid {
     #[serde(rename = "nationNumeric")]
     pub nation_numeric: NationNumeric,
@@ -23,12 +24,28 @@ pub struct CertificationAuthorityKid {
 
 impl Readable<CertificationAuthorityKid> for CertificationAuthorityKid {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<CertificationAuthorityKid> {
-        let nation_numeric: NationNumeric = reader.read_u8()?.into();
-        let nation_alpha = bytes_to_ia5_fix_string(&reader.read_into_vec(NATION_ALPHA_LENGTH)?)?;
-        let key_serial_number = reader.read_u8()?;
-        let additional_info = reader.read_u16::<BigEndian>()?;
-        let ca_identifier = reader.read_u8()?;
+        let nation_numeric: NationNumeric =
+            SpanRecorder::field("nationNumeric", "NationNumeric", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let nation_alpha = SpanRecorder::field("nationAlpha", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(NATION_ALPHA_LENGTH)?)?)
+        })?;
+        let key_serial_number = SpanRecorder::field("keySerialNumber", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let additional_info =
+            SpanRecorder::field("additionalInfo", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
+        let ca_identifier = SpanRecorder::field("caIdentifier", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
 
         Ok(Self { nation_numeric, nation_alpha, key_serial_number, additional_info, ca_identifier })
     }
 }
+
+impl Writable for CertificationAuthorityKid {
+    /// Writes a `CertificationAuthorityKid` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8((&self.nation_numeric).into())?;
+        write_ia5_string(writer, &self.nation_alpha, NATION_ALPHA_LENGTH as usize)?;
+        writer.write_u8(self.key_serial_number)?;
+        writer.write_u16::<BigEndian>(self.additional_info)?;
+        writer.write_u8(self.ca_identifier)?;
+        Ok(())
+    }
+}
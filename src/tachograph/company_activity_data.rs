@@ -1,7 +1,7 @@
 use binary_data::{BigEndian, BinSeek, ReadBytes};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, ReadableWithParams, Result};
+use crate::{Readable, ReadableField, ReadableWithParams, Result, SpanRecorder, Writable};
 
 #[derive(Debug)]
 pub struct CompanyActivityDataParams {
@@ -16,7 +16,8 @@ impl CompanyActivityDataParams {
 
 /// Information, stored in a company card, related to activities performed
 /// with the card (Annex 1C requirement 373 and 379).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CompanyActivityData<T> {
     #[serde(rename = "companyPointerNewestRecord")]
     pub company_pointer_newest_record: u16,
@@ -24,16 +25,28 @@ pub struct CompanyActivityData<T> {
     pub company_activity_records: Vec<T>,
 }
 
-impl<T: Readable<T>> ReadableWithParams<CompanyActivityData<T>> for CompanyActivityData<T> {
+impl<T: Readable<T> + Serialize> ReadableWithParams<CompanyActivityData<T>> for CompanyActivityData<T> {
     type P = CompanyActivityDataParams;
 
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<CompanyActivityData<T>> {
-        let company_pointer_newest_record = reader.read_u16::<BigEndian>()?;
+        let company_pointer_newest_record =
+            SpanRecorder::field("companyPointerNewestRecord", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
         let mut company_activity_records: Vec<T> = Vec::new();
-        for _ in 0..params.no_of_company_activity_records {
-            let record = T::read(reader)?;
+        for index in 0..params.no_of_company_activity_records {
+            let record = T::read_field(reader, &format!("companyActivityRecords[{index}]"))?;
             company_activity_records.push(record);
         }
         Ok(Self { company_pointer_newest_record, company_activity_records })
     }
 }
+
+impl<T: Writable> Writable for CompanyActivityData<T> {
+    /// Writes a `CompanyActivityData` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u16::<BigEndian>(self.company_pointer_newest_record)?;
+        for record in self.company_activity_records.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
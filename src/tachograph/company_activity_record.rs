@@ -1,13 +1,14 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable,
+    Readable, ReadableField, SpanRecorder, Writable,
     tacho::{CompanyActivityType, FullCardNumber, TimeReal, VehicleRegistrationIdentification},
 };
 
 /// Information, stored in a company card, related to activities performed
 /// with the card (Annex 1C requirement 373 and 379).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CompanyActivityRecord {
     #[serde(rename = "companyActivityType")]
     pub company_activity_type: CompanyActivityType,
@@ -25,12 +26,14 @@ pub struct CompanyActivityRecord {
 
 impl Readable<CompanyActivityRecord> for CompanyActivityRecord {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<CompanyActivityRecord> {
-        let company_activity_type: CompanyActivityType = reader.read_u8()?.into();
-        let company_activity_time = TimeReal::read(reader)?;
-        let card_number_information = FullCardNumber::read(reader)?;
-        let vehicle_registration_information = VehicleRegistrationIdentification::read(reader)?;
-        let download_period_begin = TimeReal::read(reader)?;
-        let download_period_end = TimeReal::read(reader)?;
+        let company_activity_type: CompanyActivityType =
+            SpanRecorder::field("companyActivityType", "CompanyActivityType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let company_activity_time = TimeReal::read_field(reader, "companyActivityTime")?;
+        let card_number_information = FullCardNumber::read_field(reader, "cardNumberInformation")?;
+        let vehicle_registration_information =
+            VehicleRegistrationIdentification::read_field(reader, "vehicleRegistrationInformation")?;
+        let download_period_begin = TimeReal::read_field(reader, "downloadPeriodBegin")?;
+        let download_period_end = TimeReal::read_field(reader, "downloadPeriodEnd")?;
 
         Ok(Self {
             company_activity_type,
@@ -42,3 +45,16 @@ impl Readable<CompanyActivityRecord> for CompanyActivityRecord {
         })
     }
 }
+
+impl Writable for CompanyActivityRecord {
+    /// Writes a `CompanyActivityRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8((&self.company_activity_type).into())?;
+        self.company_activity_time.write(writer)?;
+        self.card_number_information.write(writer)?;
+        self.vehicle_registration_information.write(writer)?;
+        self.download_period_begin.write(writer)?;
+        self.download_period_end.write(writer)?;
+        Ok(())
+    }
+}
@@ -1,12 +1,13 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::impl_enum_from_u8;
 
 /// Code indicating an activity carried out by a company using its company card.
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(u8)]
 pub enum CompanyActivityType {
-    Unknown = 0,
+    Unknown(u8) = 0,
     CardDownloading = 1,
     VUDownloading = 2,
     VULockin = 3,
@@ -15,7 +16,6 @@ pub enum CompanyActivityType {
 
 impl_enum_from_u8!(
     CompanyActivityType {
-        Unknown = 0,
         CardDownloading = 1,
         VUDownloading = 2,
         VULockin = 3,
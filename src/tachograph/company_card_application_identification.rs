@@ -1,14 +1,15 @@
 use binary_data::BigEndian;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable,
+    Readable, ReadableField, SpanRecorder, Writable, field_value,
     tacho::{CardStructureVersion, EquipmentType},
 };
 
 /// Information, stored in a company card related to the identification of the
 /// application of the card (Annex 1C requirement 369 and 375).
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CompanyCardApplicationIdentification {
     #[serde(rename = "typeOfTachographCardId")]
     pub type_of_tachograph_card_id: EquipmentType,
@@ -22,10 +23,23 @@ impl Readable<CompanyCardApplicationIdentification> for CompanyCardApplicationId
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(
         reader: &mut R,
     ) -> crate::Result<CompanyCardApplicationIdentification> {
-        let type_of_tachograph_card_id = reader.read_u8()?.into();
-        let card_structure_version = CardStructureVersion::read(reader)?;
-        let no_of_company_activity_records = reader.read_u16::<BigEndian>()? as u32;
+        let type_of_tachograph_card_id: EquipmentType =
+            SpanRecorder::field("typeOfTachographCardId", "EquipmentType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let card_structure_version = CardStructureVersion::read_field(reader, "cardStructureVersion")?;
+        let no_of_company_activity_records = SpanRecorder::field("noOfCompanyActivityRecords", "INTEGER", reader, |reader| {
+            Ok(reader.read_u16::<BigEndian>()? as u32)
+        })?;
 
         Ok(Self { type_of_tachograph_card_id, card_structure_version, no_of_company_activity_records })
     }
 }
+
+impl Writable for CompanyCardApplicationIdentification {
+    /// Writes a `CompanyCardApplicationIdentification` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8((&self.type_of_tachograph_card_id).into())?;
+        self.card_structure_version.write(writer)?;
+        writer.write_u16::<BigEndian>(field_value("noOfCompanyActivityRecords", self.no_of_company_activity_records)?)?;
+        Ok(())
+    }
+}
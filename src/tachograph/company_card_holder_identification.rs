@@ -1,15 +1,17 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, bytes_to_ia5_fix_string,
+    Readable, ReadableField, SpanRecorder, Writable, bytes_to_ia5_fix_string,
     tacho::{Address, Name},
+    write_ia5_string,
 };
 
 const CARD_HOLDER_PREFERRED_LANGUAGE_LENGTH: u32 = 2;
 
 /// Information, stored in a company card, related to the cardholder identification
 /// (Annex 1C requirement 372 and 378).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CompanyCardHolderIdentification {
     #[serde(rename = "companyName")]
     pub company_name: Name,
@@ -21,11 +23,22 @@ pub struct CompanyCardHolderIdentification {
 
 impl Readable<CompanyCardHolderIdentification> for CompanyCardHolderIdentification {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<CompanyCardHolderIdentification> {
-        let company_name = Name::read(reader)?;
-        let company_address = Address::read(reader)?;
-        let card_holder_preferred_language =
-            bytes_to_ia5_fix_string(&reader.read_into_vec(CARD_HOLDER_PREFERRED_LANGUAGE_LENGTH)?)?;
+        let company_name = Name::read_field(reader, "companyName")?;
+        let company_address = Address::read_field(reader, "companyAddress")?;
+        let card_holder_preferred_language = SpanRecorder::field("cardHolderPreferredLanguage", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(CARD_HOLDER_PREFERRED_LANGUAGE_LENGTH)?)?)
+        })?;
 
         Ok(Self { company_name, company_address, card_holder_preferred_language })
     }
 }
+
+impl Writable for CompanyCardHolderIdentification {
+    /// Writes a `CompanyCardHolderIdentification` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.company_name.write(writer)?;
+        self.company_address.write(writer)?;
+        write_ia5_string(writer, &self.card_holder_preferred_language, CARD_HOLDER_PREFERRED_LANGUAGE_LENGTH as usize)?;
+        Ok(())
+    }
+}
@@ -1,13 +1,14 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable,
+    Readable, ReadableField, SpanRecorder, Writable,
     tacho::{ControlType, FullCardNumber, TimeReal, VehicleRegistrationIdentification},
 };
 
 /// Information, stored in a control card, related to control activity
 /// performed with the card (Annex 1C requirement 361 and 367).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ControlCardActivityRecord {
     #[serde(rename = "controlType")]
     pub control_type: ControlType,
@@ -25,12 +26,14 @@ pub struct ControlCardActivityRecord {
 
 impl Readable<ControlCardActivityRecord> for ControlCardActivityRecord {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<ControlCardActivityRecord> {
-        let control_type: ControlType = reader.read_u8()?.into();
-        let control_time = TimeReal::read(reader)?;
-        let controlled_card_number = FullCardNumber::read(reader)?;
-        let controlled_vehicle_registration = VehicleRegistrationIdentification::read(reader)?;
-        let control_download_period_begin = TimeReal::read(reader)?;
-        let control_download_period_end = TimeReal::read(reader)?;
+        let control_type: ControlType =
+            SpanRecorder::field("controlType", "ControlType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let control_time = TimeReal::read_field(reader, "controlTime")?;
+        let controlled_card_number = FullCardNumber::read_field(reader, "controlledCardNumber")?;
+        let controlled_vehicle_registration =
+            VehicleRegistrationIdentification::read_field(reader, "controlledVehicleRegistration")?;
+        let control_download_period_begin = TimeReal::read_field(reader, "controlDownloadPeriodBegin")?;
+        let control_download_period_end = TimeReal::read_field(reader, "controlDownloadPeriodEnd")?;
 
         Ok(Self {
             control_type,
@@ -42,3 +45,16 @@ impl Readable<ControlCardActivityRecord> for ControlCardActivityRecord {
         })
     }
 }
+
+impl Writable for ControlCardActivityRecord {
+    /// Writes a `ControlCardActivityRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8((&self.control_type).into())?;
+        self.control_time.write(writer)?;
+        self.controlled_card_number.write(writer)?;
+        self.controlled_vehicle_registration.write(writer)?;
+        self.control_download_period_begin.write(writer)?;
+        self.control_download_period_end.write(writer)?;
+        Ok(())
+    }
+}
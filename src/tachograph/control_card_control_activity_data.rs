@@ -1,7 +1,7 @@
 use binary_data::{BigEndian, BinSeek, ReadBytes};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, ReadableWithParams, Result};
+use crate::{Readable, ReadableField, ReadableWithParams, Result, SpanRecorder, Writable};
 
 #[derive(Debug)]
 pub struct ControlCardControlActivityDataParams {
@@ -16,7 +16,8 @@ impl ControlCardControlActivityDataParams {
 
 /// Information, stored in a control card, related to control activity
 /// performed with the card (Annex 1C requirement 361 and 367).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ControlCardControlActivityData<T> {
     #[serde(rename = "controlPointerNewestRecord")]
     pub control_pointer_newest_record: u16,
@@ -24,16 +25,28 @@ pub struct ControlCardControlActivityData<T> {
     pub control_activity_records: Vec<T>,
 }
 
-impl<T: Readable<T>> ReadableWithParams<ControlCardControlActivityData<T>> for ControlCardControlActivityData<T> {
+impl<T: Readable<T> + Serialize> ReadableWithParams<ControlCardControlActivityData<T>> for ControlCardControlActivityData<T> {
     type P = ControlCardControlActivityDataParams;
 
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<ControlCardControlActivityData<T>> {
-        let control_pointer_newest_record = reader.read_u16::<BigEndian>()?;
+        let control_pointer_newest_record =
+            SpanRecorder::field("controlPointerNewestRecord", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
         let mut control_activity_records: Vec<T> = Vec::new();
-        for _ in 0..params.no_of_control_card_activity_records {
-            let record = T::read(reader)?;
+        for index in 0..params.no_of_control_card_activity_records {
+            let record = T::read_field(reader, &format!("controlActivityRecords[{index}]"))?;
             control_activity_records.push(record);
         }
         Ok(Self { control_pointer_newest_record, control_activity_records })
     }
 }
+
+impl<T: Writable> Writable for ControlCardControlActivityData<T> {
+    /// Writes a `ControlCardControlActivityData` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u16::<BigEndian>(self.control_pointer_newest_record)?;
+        for record in self.control_activity_records.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
@@ -1,15 +1,17 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, bytes_to_ia5_fix_string,
+    Readable, ReadableField, SpanRecorder, Writable, bytes_to_ia5_fix_string,
     tacho::{Address, HolderName, Name},
+    write_ia5_string,
 };
 
 const CARD_HOLDER_PREFERRED_LANGUAGE_LENGTH: u32 = 2;
 
 /// Information, stored in a control card, related to the identification of the
 /// cardholder (Annex 1C requirement 360 and 366).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ControlCardHolderIdentification {
     #[serde(rename = "controlBodyName")]
     pub control_body_name: Name,
@@ -23,12 +25,24 @@ pub struct ControlCardHolderIdentification {
 
 impl Readable<ControlCardHolderIdentification> for ControlCardHolderIdentification {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<ControlCardHolderIdentification> {
-        let control_body_name = Name::read(reader)?;
-        let control_body_address = Address::read(reader)?;
-        let card_holder_name = HolderName::read(reader)?;
-        let card_holder_preferred_language =
-            bytes_to_ia5_fix_string(&reader.read_into_vec(CARD_HOLDER_PREFERRED_LANGUAGE_LENGTH)?)?;
+        let control_body_name = Name::read_field(reader, "controlBodyName")?;
+        let control_body_address = Address::read_field(reader, "controlBodyAddress")?;
+        let card_holder_name = HolderName::read_field(reader, "cardHolderName")?;
+        let card_holder_preferred_language = SpanRecorder::field("cardHolderPreferredLanguage", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(CARD_HOLDER_PREFERRED_LANGUAGE_LENGTH)?)?)
+        })?;
 
         Ok(Self { control_body_name, control_body_address, card_holder_name, card_holder_preferred_language })
     }
 }
+
+impl Writable for ControlCardHolderIdentification {
+    /// Writes a `ControlCardHolderIdentification` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.control_body_name.write(writer)?;
+        self.control_body_address.write(writer)?;
+        self.card_holder_name.write(writer)?;
+        write_ia5_string(writer, &self.card_holder_preferred_language, CARD_HOLDER_PREFERRED_LANGUAGE_LENGTH as usize)?;
+        Ok(())
+    }
+}
@@ -1,13 +1,14 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, ReadableWithParams,
+    Readable, ReadableField, ReadableWithParamsField, Writable,
     tacho::{CardIdentification, CardNumberParams, ControlCardHolderIdentification, EquipmentType},
 };
 
 /// Information, stored in a card, related to the identification of the card
 /// (Annex 1C requirements 255, 280, 310, 333, 359, 365, 371, and 377).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ControlCardIdentification {
     #[serde(rename = "cardIdentification")]
     pub card_identification: CardIdentification,
@@ -18,8 +19,18 @@ pub struct ControlCardIdentification {
 impl Readable<ControlCardIdentification> for ControlCardIdentification {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<ControlCardIdentification> {
         let card_identification_params = CardNumberParams::new(EquipmentType::ControlCard);
-        let card_identification = CardIdentification::read(reader, &card_identification_params)?;
-        let control_card_holder_identification = ControlCardHolderIdentification::read(reader)?;
+        let card_identification = CardIdentification::read_field_with(reader, &card_identification_params, "cardIdentification")?;
+        let control_card_holder_identification =
+            ControlCardHolderIdentification::read_field(reader, "companyCardHolderIdentification")?;
         Ok(Self { card_identification, control_card_holder_identification })
     }
 }
+
+impl Writable for ControlCardIdentification {
+    /// Writes a `ControlCardIdentification` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.card_identification.write(writer)?;
+        self.control_card_holder_identification.write(writer)?;
+        Ok(())
+    }
+}
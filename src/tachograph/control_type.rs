@@ -1,34 +1,86 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::impl_enum_from_u8;
+const CARD_DOWNLOADED: u8 = 0x80;
+const VU_DOWNLOADED: u8 = 0x40;
+const PRINTING_DONE: u8 = 0x20;
+const DISPLAY_USED: u8 = 0x10;
+const CALIBRATION_PARAMETERS: u8 = 0x8;
+/// The bits reserved for future use.
+const RFU: u8 = 0x7;
 
 /// Code indicating the activities carried out during a control. This data type
 /// is related to Annex 1C requirements 126, 274, 299, 327, and 350.
-#[derive(Debug, PartialEq, Serialize)]
-#[repr(u8)]
-pub enum ControlType {
-    /// Card downloaded/not downloaded during this control activity,
-    CardDownloaded = 0x80,
-    /// VU downloaded/not downloaded during this control activity,
-    VUDownloaded = 0x40,
-    /// Printing done/no printing done during this control activity,
-    /// 0x20 (32)
-    PrintingDone = 0x20,
-    /// Display used/no display used during this control activity,
-    DisplayUsed = 0x10,
-    /// Calibration parameters checked/not checked during this control activity,
-    CalibrationParameters = 0x8,
-    /// Control Type are invalid
-    Unknown = 0x0,
+///
+/// The code is a bitmask, several activities are carried out during a control,
+/// e.g. `0xC0` is a control with the card and the VU downloaded.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ControlType {
+    /// Card downloaded/not downloaded during this control activity, 0x80.
+    #[serde(rename = "cardDownloaded")]
+    pub card_downloaded: bool,
+    /// VU downloaded/not downloaded during this control activity, 0x40.
+    #[serde(rename = "vuDownloaded")]
+    pub vu_downloaded: bool,
+    /// Printing done/no printing done during this control activity, 0x20.
+    #[serde(rename = "printingDone")]
+    pub printing_done: bool,
+    /// Display used/no display used during this control activity, 0x10.
+    #[serde(rename = "displayUsed")]
+    pub display_used: bool,
+    /// Calibration parameters checked/not checked during this control activity, 0x8.
+    #[serde(rename = "calibrationParameters")]
+    pub calibration_parameters: bool,
+    /// The bits reserved for future use, only the bits of 0x7 are written.
+    pub rfu: u8,
 }
 
-impl_enum_from_u8!(
-    ControlType {
-        CardDownloaded = 0x80,
-        VUDownloaded = 0x40,
-        PrintingDone = 0x20,
-        DisplayUsed = 0x10,
-        CalibrationParameters = 0x8,
-        Unknown = 0x0
+impl From<u8> for ControlType {
+    fn from(value: u8) -> Self {
+        Self {
+            card_downloaded: value & CARD_DOWNLOADED != 0,
+            vu_downloaded: value & VU_DOWNLOADED != 0,
+            printing_done: value & PRINTING_DONE != 0,
+            display_used: value & DISPLAY_USED != 0,
+            calibration_parameters: value & CALIBRATION_PARAMETERS != 0,
+            rfu: value & RFU,
+        }
     }
-);
+}
+
+impl From<&ControlType> for u8 {
+    fn from(value: &ControlType) -> Self {
+        let mut bits = value.rfu & RFU;
+        if value.card_downloaded {
+            bits |= CARD_DOWNLOADED;
+        }
+        if value.vu_downloaded {
+            bits |= VU_DOWNLOADED;
+        }
+        if value.printing_done {
+            bits |= PRINTING_DONE;
+        }
+        if value.display_used {
+            bits |= DISPLAY_USED;
+        }
+        if value.calibration_parameters {
+            bits |= CALIBRATION_PARAMETERS;
+        }
+        bits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_control_type_keeps_combined_bits() {
+        let control_type = ControlType::from(0xC0);
+        assert!(control_type.card_downloaded && control_type.vu_downloaded);
+        assert!(!control_type.printing_done && !control_type.display_used && !control_type.calibration_parameters);
+        for value in 0..=u8::MAX {
+            assert_eq!(u8::from(&ControlType::from(value)), value);
+        }
+    }
+}
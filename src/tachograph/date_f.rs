@@ -3,13 +3,14 @@
 
 use std::fmt::Display;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{BCDString, Readable};
+use crate::{BCDString, Readable, SpanRecorder, Writable, write_bcd_string};
 
 /// A date structure containing year, month, and day.
 /// The values are stored as strings, as they are decoded from BCD format.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Datef {
     pub year: String,
     pub month: String,
@@ -30,14 +31,24 @@ impl Readable<Datef> for Datef {
     /// - 1 byte for the month
     /// - 1 byte for the day
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<Datef> {
-        let year = BCDString::decode(&reader.read_into_vec(2)?)?;
-        let month = BCDString::decode(&reader.read_into_vec(1)?)?;
-        let day = BCDString::decode(&reader.read_into_vec(1)?)?;
+        let year = SpanRecorder::field("year", "BCDString", reader, |reader| BCDString::decode(&reader.read_into_vec(2)?))?;
+        let month = SpanRecorder::field("month", "BCDString", reader, |reader| BCDString::decode(&reader.read_into_vec(1)?))?;
+        let day = SpanRecorder::field("day", "BCDString", reader, |reader| BCDString::decode(&reader.read_into_vec(1)?))?;
 
         Ok(Self { year, month, day })
     }
 }
 
+impl Writable for Datef {
+    /// Writes a `Datef` to a binary stream as BCD encoded year, month and day.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        write_bcd_string(writer, &self.year, 2)?;
+        write_bcd_string(writer, &self.month, 1)?;
+        write_bcd_string(writer, &self.day, 1)?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -55,6 +66,14 @@ mod tests {
         assert_eq!(datef.day, "11");
     }
 
+    #[test]
+    fn test_datef_write() {
+        let datef = Datef { year: "2023".to_string(), month: "09".to_string(), day: "11".to_string() };
+        let data = crate::write_to_vec(|writer| datef.write(writer)).unwrap();
+
+        assert_eq!(data, vec![0x20, 0x23, 0x09, 0x11]);
+    }
+
     #[test]
     fn test_datef_display() {
         let datef = Datef { year: "2023".to_string(), month: "09".to_string(), day: "11".to_string() };
@@ -1,15 +1,17 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, bytes_to_ia5_fix_string,
+    Readable, ReadableField, SpanRecorder, Writable, bytes_to_ia5_fix_string,
     tacho::{Datef, HolderName},
+    write_ia5_string,
 };
 
 const CARD_HOLDER_PREFERRED_LANGUAGE_LENGTH: u32 = 2;
 
 /// Information, stored in a driver card, related to the identification of the
 /// cardholder (Annex 1C requirement 256 and 281).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DriverCardHolderIdentification {
     #[serde(rename = "cardHolderName")]
     pub card_holder_name: HolderName,
@@ -21,11 +23,22 @@ pub struct DriverCardHolderIdentification {
 
 impl Readable<DriverCardHolderIdentification> for DriverCardHolderIdentification {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<DriverCardHolderIdentification> {
-        let card_holder_name = HolderName::read(reader)?;
-        let card_holder_birth_date = Datef::read(reader)?;
-        let card_holder_preferred_language =
-            bytes_to_ia5_fix_string(&reader.read_into_vec(CARD_HOLDER_PREFERRED_LANGUAGE_LENGTH)?)?;
+        let card_holder_name = HolderName::read_field(reader, "cardHolderName")?;
+        let card_holder_birth_date = Datef::read_field(reader, "cardHolderBirthDate")?;
+        let card_holder_preferred_language = SpanRecorder::field("cardHolderPreferredLanguage", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(CARD_HOLDER_PREFERRED_LANGUAGE_LENGTH)?)?)
+        })?;
 
         Ok(Self { card_holder_name, card_holder_birth_date, card_holder_preferred_language })
     }
 }
+
+impl Writable for DriverCardHolderIdentification {
+    /// Writes a `DriverCardHolderIdentification` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.card_holder_name.write(writer)?;
+        self.card_holder_birth_date.write(writer)?;
+        write_ia5_string(writer, &self.card_holder_preferred_language, CARD_HOLDER_PREFERRED_LANGUAGE_LENGTH as usize)?;
+        Ok(())
+    }
+}
@@ -1,13 +1,16 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{BCDString, Readable, bytes_to_ia5_fix_string};
+use crate::{
+    BCDString, Readable, SpanRecorder, Writable, bytes_to_ia5_fix_string, write_bcd_string, write_ia5_string, write_octet_string,
+};
 
 const MANUFACTURER_INFORMATION_LENGTH: u32 = 2;
 const COUNTRY_CODE_LENGTH: u32 = 2;
 const MODULE_EMBEDDER_LENGTH: usize = 1;
 
 /// Provides information about the IC embedder.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct EmbedderIcAssemblerId {
     #[serde(rename = "countryCode")]
     pub country_code: String,
@@ -19,15 +22,31 @@ pub struct EmbedderIcAssemblerId {
 
 impl Readable<EmbedderIcAssemblerId> for EmbedderIcAssemblerId {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<EmbedderIcAssemblerId> {
-        let country_code = bytes_to_ia5_fix_string(&reader.read_into_vec(COUNTRY_CODE_LENGTH)?)?;
-        let module_embedder = BCDString::decode(&reader.read_bytes::<MODULE_EMBEDDER_LENGTH>()?)?;
+        let country_code = SpanRecorder::field("countryCode", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(COUNTRY_CODE_LENGTH)?)?)
+        })?;
+        let module_embedder = SpanRecorder::field("moduleEmbedder", "BCDString", reader, |reader| {
+            BCDString::decode(&reader.read_bytes::<MODULE_EMBEDDER_LENGTH>()?)
+        })?;
         // OCTET STRING(SIZE(l))
         // You should interpret it as two raw bytes that represent a manufacturer code (not a printable character). So:
         //  - Do not decode it as ASCII or UTF-8
         //  - Instead, treat it like a numeric or binary ID
         //  - usually interpreted as a hex code
-        let manufacturer_information = reader.read_into_vec(MANUFACTURER_INFORMATION_LENGTH)?;
+        let manufacturer_information = SpanRecorder::field("manufacturerInformation", "OCTET STRING", reader, |reader| {
+            Ok(reader.read_into_vec(MANUFACTURER_INFORMATION_LENGTH)?)
+        })?;
 
         Ok(Self { country_code, module_embedder, manufacturer_information })
     }
 }
+
+impl Writable for EmbedderIcAssemblerId {
+    /// Writes an `EmbedderIcAssemblerId` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        write_ia5_string(writer, &self.country_code, COUNTRY_CODE_LENGTH as usize)?;
+        write_bcd_string(writer, &self.module_embedder, MODULE_EMBEDDER_LENGTH)?;
+        write_octet_string(writer, &self.manufacturer_information, MANUFACTURER_INFORMATION_LENGTH as usize)?;
+        Ok(())
+    }
+}
@@ -1,8 +1,9 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::impl_enum_from_u8;
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(u8)]
 pub enum EntryTypeDailyWorkPeriod {
     BeginCardInsertion = 0,
@@ -105,7 +106,7 @@ pub enum EntryTypeDailyWorkPeriod {
     RFU97 = 97,
     RFU98 = 98,
     RFU100 = 100,
-    Unknown = 255,
+    Unknown(u8) = 255,
 }
 
 impl_enum_from_u8!(
@@ -210,6 +211,5 @@ impl_enum_from_u8!(
         RFU97 = 97,
         RFU98 = 98,
         RFU100 = 100,
-        Unknown = 255,
     }
 );
@@ -1,12 +1,13 @@
 use core::fmt;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::impl_enum_from_u8;
 
 // Code to distinguish different types of equipment for the tachograph
 // application.
-#[derive(Debug, PartialEq, Clone, Serialize)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(u8)]
 pub enum EquipmentType {
     Reserved = 0,
@@ -30,7 +31,7 @@ pub enum EquipmentType {
     WorkshopCardSign = 18,
     VehicleUnitSign = 19,
     DriverKey = 170,
-    Unknown = 254,
+    Unknown(u8) = 254,
     NullCard = 255,
 }
 
@@ -63,7 +64,6 @@ impl_enum_from_u8!(
         WorkshopCardSign = 18,
         VehicleUnitSign = 19,
         DriverKey = 170,
-        Unknown = 254,
         NullCard = 255,
     }
 );
@@ -1,9 +1,10 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::impl_enum_from_u8;
 
 /// Code explaining why an event or a fault has been recorded.
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(u8)]
 pub enum EventFaultRecordPurpose {
     OneOf10MostRecentOrLast = 0,
@@ -14,7 +15,7 @@ pub enum EventFaultRecordPurpose {
     OneOf5MostSeriousEventsOverLast365Days = 5,
     FirstEventorFaultAfterLastCalibration = 6,
     ActiveEventOrFault = 7,
-    Unknown = 255,
+    Unknown(u8) = 255,
 }
 
 impl_enum_from_u8!(
@@ -27,6 +28,5 @@ impl_enum_from_u8!(
         OneOf5MostSeriousEventsOverLast365Days = 5,
         FirstEventorFaultAfterLastCalibration = 6,
         ActiveEventOrFault = 7,
-        Unknown = 255,
     }
 );
@@ -1,9 +1,10 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::impl_enum_from_u8;
 
 /// Code qualifying an event or a fault.
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(u8)]
 pub enum EventFaultType {
     NoFurtherDetails = 0,
@@ -51,7 +52,7 @@ pub enum EventFaultType {
     RCMRemoteCommunicationModuleFault = 97,
     RCMRemoteCommunicationModuleCommunicationFault = 98,
     ITSNoFurtherDetails = 112,
-    Unknown = 255,
+    Unknown(u8) = 255,
 }
 
 impl_enum_from_u8!(
@@ -101,6 +102,5 @@ impl_enum_from_u8!(
         RCMRemoteCommunicationModuleFault = 97,
         RCMRemoteCommunicationModuleCommunicationFault = 98,
         ITSNoFurtherDetails = 112,
-        Unknown = 255
     }
 );
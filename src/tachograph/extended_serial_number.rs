@@ -1,13 +1,14 @@
 use binary_data::BigEndian;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{BCDString, Readable};
+use crate::{BCDString, Readable, SpanRecorder, Writable, write_bcd_string};
 
 const MONTH_YEAR_LENGTH: usize = 2;
 
 /// Unique identification of an equipment. It can also be used as an
 /// equipment Public Key Identifier.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ExtendedSerialNumber {
     #[serde(rename = "serialNumber")]
     pub serial_number: u32,
@@ -21,11 +22,24 @@ pub struct ExtendedSerialNumber {
 
 impl Readable<ExtendedSerialNumber> for ExtendedSerialNumber {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<ExtendedSerialNumber> {
-        let serial_number = reader.read_u32::<BigEndian>()?;
-        let month_year = BCDString::decode(&reader.read_bytes::<MONTH_YEAR_LENGTH>()?)?;
-        let serial_type = reader.read_u8()?;
-        let manufacturer_code = reader.read_u8()?;
+        let serial_number = SpanRecorder::field("serialNumber", "INTEGER", reader, |reader| Ok(reader.read_u32::<BigEndian>()?))?;
+        let month_year = SpanRecorder::field("monthYear", "BCDString", reader, |reader| {
+            BCDString::decode(&reader.read_bytes::<MONTH_YEAR_LENGTH>()?)
+        })?;
+        let serial_type = SpanRecorder::field("type", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let manufacturer_code = SpanRecorder::field("manufacturerCode", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
 
         Ok(Self { serial_number, month_year, serial_type, manufacturer_code })
     }
 }
+
+impl Writable for ExtendedSerialNumber {
+    /// Writes an `ExtendedSerialNumber` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u32::<BigEndian>(self.serial_number)?;
+        write_bcd_string(writer, &self.month_year, MONTH_YEAR_LENGTH)?;
+        writer.write_u8(self.serial_type)?;
+        writer.write_u8(self.manufacturer_code)?;
+        Ok(())
+    }
+}
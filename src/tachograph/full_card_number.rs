@@ -1,14 +1,17 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    CodePage, Readable, bytes_to_string,
+    CodePage, Readable, SpanRecorder, Writable, bytes_to_string,
     tacho::{EquipmentType, NationNumeric},
+    write_string, write_to_vec,
 };
 
 const CARD_NUMBER_LENGTH: u32 = 16;
 
-/// Code fully identifying a tachograph card.
-#[derive(Debug, Serialize)]
+/// Code fully identifying a tachograph card. The member state and the card number of a
+/// `NullCard`, e.g. of an empty card slot, are not decoded.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FullCardNumber {
     #[serde(rename = "cardType")]
     pub card_type: EquipmentType,
@@ -16,17 +19,93 @@ pub struct FullCardNumber {
     pub card_issuing_member_state: NationNumeric,
     #[serde(rename = "cardNumber")]
     pub card_number: String,
+    /// The bytes of the member state and the card number when they are not written back
+    /// as they are read, e.g. of a `NullCard` or of a card number padded with zeros. They
+    /// are written as they are while the decoded fields are not changed.
+    #[serde(rename = "rawData", default, skip_serializing_if = "Option::is_none")]
+    pub raw_data: Option<Vec<u8>>,
+}
+
+impl FullCardNumber {
+    /// Returns the member state and the card number decoded from `data`, the bytes after the card type.
+    fn decode(card_type: &EquipmentType, data: &[u8]) -> (u8, String) {
+        if *card_type == EquipmentType::NullCard {
+            return ((&NationNumeric::Unknown(0)).into(), String::new());
+        }
+        (data[0], bytes_to_string(&data[1..], &CodePage::IsoIec8859_1))
+    }
+
+    fn write_card_number<W: binary_data::WriteBytes>(
+        writer: &mut W,
+        card_issuing_member_state: &NationNumeric,
+        card_number: &str,
+    ) -> crate::Result<()> {
+        writer.write_u8(card_issuing_member_state.into())?;
+        write_string(writer, card_number, &CodePage::IsoIec8859_1, CARD_NUMBER_LENGTH as usize)
+    }
 }
 
 impl Readable<FullCardNumber> for FullCardNumber {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<FullCardNumber> {
-        let card_type: EquipmentType = reader.read_u8()?.into();
-        let mut card_issuing_member_state: NationNumeric = reader.read_u8()?.into();
-        let mut card_number: String = bytes_to_string(&reader.read_into_vec(CARD_NUMBER_LENGTH)?, &CodePage::IsoIec8859_1);
+        let card_type: EquipmentType =
+            SpanRecorder::field("cardType", "EquipmentType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let data_position = reader.pos()?;
+        let mut card_issuing_member_state: NationNumeric =
+            SpanRecorder::field("cardIssuingMemberState", "NationNumeric", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let mut card_number: String = SpanRecorder::field("cardNumber", "IA5String", reader, |reader| {
+            Ok(bytes_to_string(&reader.read_into_vec(CARD_NUMBER_LENGTH)?, &CodePage::IsoIec8859_1))
+        })?;
         if card_type == EquipmentType::NullCard {
-            card_issuing_member_state = NationNumeric::Unknown;
+            card_issuing_member_state = NationNumeric::Unknown(0);
             card_number = "".to_owned();
         }
-        Ok(Self { card_type, card_issuing_member_state, card_number })
+
+        let data_end_position = reader.pos()?;
+        reader.seek(data_position)?;
+        let data = reader.read_into_vec((data_end_position - data_position) as u32)?;
+        let written = write_to_vec(|writer| Self::write_card_number(writer, &card_issuing_member_state, &card_number)).ok();
+        let raw_data = (card_type == EquipmentType::NullCard || written.as_ref() != Some(&data)).then_some(data);
+        Ok(Self { card_type, card_issuing_member_state, card_number, raw_data })
+    }
+}
+
+impl Writable for FullCardNumber {
+    /// Writes a `FullCardNumber` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8((&self.card_type).into())?;
+        if let Some(raw_data) = &self.raw_data
+            && raw_data.len() == 1 + CARD_NUMBER_LENGTH as usize
+            && Self::decode(&self.card_type, raw_data) == ((&self.card_issuing_member_state).into(), self.card_number.clone())
+        {
+            writer.write_all(raw_data)?;
+            return Ok(());
+        }
+        Self::write_card_number(writer, &self.card_issuing_member_state, &self.card_number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use binary_data::BinMemoryBuffer;
+
+    use super::*;
+
+    #[test]
+    fn test_full_card_number_keeps_raw_data() {
+        let mut null_card = vec![0xFF, 0x0D];
+        null_card.extend_from_slice(&[0; 16]);
+        let mut zero_padded = vec![0x01, 0x0D];
+        zero_padded.extend_from_slice(b"1234");
+        zero_padded.extend_from_slice(&[0; 12]);
+        for data in [null_card, zero_padded] {
+            let mut card_number = FullCardNumber::read(&mut BinMemoryBuffer::from(data.clone())).unwrap();
+            assert_eq!(write_to_vec(|writer| card_number.write(writer)).unwrap(), data);
+
+            card_number.card_type = EquipmentType::DriverCard;
+            card_number.card_number = "5678".to_owned();
+            let mut expected = vec![0x01, (&card_number.card_issuing_member_state).into()];
+            expected.extend_from_slice(b"5678            ");
+            assert_eq!(write_to_vec(|writer| card_number.write(writer)).unwrap(), expected);
+        }
     }
 }
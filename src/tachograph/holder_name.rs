@@ -1,9 +1,10 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, tacho::Name};
+use crate::{Readable, ReadableField, Writable, tacho::Name};
 
 /// This is the name and first name(s) of the holder of the Card.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct HolderName {
     #[serde(rename = "holderSurname")]
     pub holder_surname: Name,
@@ -13,8 +14,17 @@ pub struct HolderName {
 
 impl Readable<HolderName> for HolderName {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<HolderName> {
-        let holder_surname = Name::read(reader)?;
-        let holder_first_names = Name::read(reader)?;
+        let holder_surname = Name::read_field(reader, "holderSurname")?;
+        let holder_first_names = Name::read_field(reader, "holderFirstNames")?;
         Ok(Self { holder_surname, holder_first_names })
     }
 }
+
+impl Writable for HolderName {
+    /// Writes a `HolderName` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.holder_surname.write(writer)?;
+        self.holder_first_names.write(writer)?;
+        Ok(())
+    }
+}
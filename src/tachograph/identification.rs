@@ -1,8 +1,8 @@
 use binary_data::{BinSeek, ReadBytes};
-use serde::{Serialize, ser::Serializer};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Error, Readable, ReadableWithParams, Result,
+    Error, Readable, ReadableWithParams, Result, Writable,
     tacho::{
         CompanyCardIdentification, ControlCardIdentification, DriverCardIdentification, EquipmentType, WorkshopCardIdentification,
     },
@@ -19,7 +19,11 @@ impl IdentificationParams {
     }
 }
 
-#[derive(Debug)]
+/// The identification of a card of any type. The variants are told apart on
+/// deserialization by the holder identification fields, which differ for each card type.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
 pub enum Identification {
     CompanyCard(Box<CompanyCardIdentification>),
     DriverCard(Box<DriverCardIdentification>),
@@ -27,20 +31,6 @@ pub enum Identification {
     WorkshopCard(Box<WorkshopCardIdentification>),
 }
 
-impl Serialize for Identification {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        match self {
-            Identification::CompanyCard(data) => data.serialize(serializer),
-            Identification::DriverCard(data) => data.serialize(serializer),
-            Identification::ControlCard(data) => data.serialize(serializer),
-            Identification::WorkshopCard(data) => data.serialize(serializer),
-        }
-    }
-}
-
 impl ReadableWithParams<Identification> for Identification {
     type P = IdentificationParams;
 
@@ -54,3 +44,16 @@ impl ReadableWithParams<Identification> for Identification {
         }
     }
 }
+
+impl Writable for Identification {
+    /// Writes the `Identification` of the card type to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        match self {
+            Identification::CompanyCard(data) => data.write(writer)?,
+            Identification::DriverCard(data) => data.write(writer)?,
+            Identification::ControlCard(data) => data.write(writer)?,
+            Identification::WorkshopCard(data) => data.write(writer)?,
+        }
+        Ok(())
+    }
+}
@@ -1,22 +1,22 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::impl_enum_from_u8;
 
 /// Code identifying whether a cardholder has manually entered driver
 /// activities at card insertion or not (Annex 1B requirement 081 and
 /// Annex 1C requirement 102).
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(u8)]
 pub enum ManualInputFlag {
     NoEntry = 0,
     ManualEntries = 1,
-    Unknown = 255,
+    Unknown(u8) = 255,
 }
 
 impl_enum_from_u8!(
     ManualInputFlag {
         NoEntry = 0,
         ManualEntries = 1,
-        Unknown = 255,
     }
 );
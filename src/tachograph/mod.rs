@@ -71,6 +71,7 @@ mod tachograph_data_generation;
 mod tachograph_data_type;
 mod tachograph_header;
 mod time_real;
+mod trep_handler;
 mod vehicle_registration_identification;
 mod vehicle_registration_number;
 mod verify_result;
@@ -151,9 +152,10 @@ pub use tachograph_data_generation::TachographDataGeneration;
 pub use tachograph_data_type::TachographDataType;
 pub use tachograph_header::TachographHeader;
 pub use time_real::TimeReal;
+pub use trep_handler::{RawTrepBlock, TrepHandler, TrepHandlers};
 pub use vehicle_registration_identification::VehicleRegistrationIdentification;
 pub use vehicle_registration_number::VehicleRegistrationNumber;
-pub use verify_result::{VerifyItem, VerifyResult, VerifyResultStatus, VerifyStatus};
+pub use verify_result::{VerifyItem, VerifyResult, VerifyResultStatus, VerifyStatus, VuVerifyItem, VuVerifyResult};
 pub use vu_data::VUData;
 pub use vu_detailed_speed_block::VuDetailedSpeedBlock;
 pub use vu_over_speeding_control_data::VuOverSpeedingControlData;
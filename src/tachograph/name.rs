@@ -1,13 +1,15 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{CodePage, Readable, bytes_to_string};
+use crate::{CodePage, Readable, SpanRecorder, Writable, bytes_to_string, write_string};
 
 const NAME_LENGTH: u32 = 35;
 
 /// A Name.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Name {
     /// Specifies a character set.
+    #[serde(rename = "codePage")]
     pub code_page: CodePage,
     /// This is a name encoded using the specified character set.
     pub name: String,
@@ -15,17 +17,19 @@ pub struct Name {
 
 impl Readable<Name> for Name {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<Name> {
-        let code_page: CodePage = reader.read_u8()?.into();
-        let name = bytes_to_string(&reader.read_into_vec(NAME_LENGTH)?, &code_page).trim().to_string();
+        let code_page: CodePage = SpanRecorder::field("codePage", "CodePage", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let name = SpanRecorder::field("name", "OCTET STRING", reader, |reader| {
+            Ok(bytes_to_string(&reader.read_into_vec(NAME_LENGTH)?, &code_page).trim().to_string())
+        })?;
         Ok(Self { code_page, name })
     }
 }
 
-impl Serialize for Name {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        serializer.serialize_str(&self.name)
+impl Writable for Name {
+    /// Writes a `Name` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8(self.code_page.clone() as u8)?;
+        write_string(writer, &self.name, &self.code_page, NAME_LENGTH as usize)?;
+        Ok(())
     }
 }
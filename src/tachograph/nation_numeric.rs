@@ -1,12 +1,13 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::impl_enum_from_u8;
 
 /// Numerical reference to a country.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(u8)]
 pub enum NationNumeric {
-    Unknown = 0,
+    Unknown(u8) = 0,
     Austria = 1,
     Albania = 2,
     Andorra = 3,
@@ -252,73 +253,6 @@ pub enum NationNumeric {
     RestOfTheWorld = 0xFF,
 }
 
-impl From<&NationNumeric> for u8 {
-    fn from(value: &NationNumeric) -> Self {
-        use NationNumeric::*;
-        match value {
-            Austria => 1,
-            Albania => 2,
-            Andorra => 3,
-            Armenia => 4,
-            Azerbaijan => 5,
-            Belgium => 6,
-            Bulgaria => 7,
-            BosniaAndHerzegovina => 8,
-            Belarus => 9,
-            Switzerland => 10,
-            Cyprus => 11,
-            CzechRepublic => 12,
-            Germany => 13,
-            Denmark => 14,
-            Spain => 15,
-            Estonia => 16,
-            France => 17,
-            Finland => 18,
-            Liechtenstein => 19,
-            FaeroeIslands => 20,
-            UnitedKingdom => 21,
-            Georgia => 22,
-            Greece => 23,
-            Hungary => 24,
-            Croatia => 25,
-            Italy => 26,
-            Ireland => 27,
-            Iceland => 28,
-            Kazakhstan => 29,
-            Luxembourg => 30,
-            Lithuania => 31,
-            Latvia => 32,
-            Malta => 33,
-            Monaco => 34,
-            RepublicOfMoldova => 35,
-            Macedonia => 36,
-            Norway => 37,
-            Netherlands => 38,
-            Portugal => 39,
-            Poland => 40,
-            Romania => 41,
-            SanMarino => 42,
-            RussianFederation => 43,
-            Sweden => 44,
-            Slovakia => 45,
-            Slovenia => 46,
-            Turkmenistan => 47,
-            Turkey => 48,
-            Ukraine => 49,
-            VaticanCity => 50,
-            Yugoslavia => 51,
-            Montenegro => 52,
-            Serbia => 53,
-            Uzbekistan => 54,
-            Tajikistan => 55,
-            EuropeanCommunity => 0xFD,
-            RestOfEurope => 0xFE,
-            RestOfTheWorld => 0xFF,
-            _ => 0,
-        }
-    }
-}
-
 impl NationNumeric {
     /// Convert nation numeric to nation alpha
     pub fn get_nation_alpha(&self) -> &'static str {
@@ -389,7 +323,6 @@ impl NationNumeric {
 
 impl_enum_from_u8!(
     NationNumeric {
-        Unknown = 0,
         Austria = 1,
         Albania = 2,
         Andorra = 3,
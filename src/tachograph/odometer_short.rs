@@ -1,7 +1,10 @@
 use binary_data::BigEndian;
-use serde::Serialize;
+use serde::{Deserialize, Deserializer, Serialize, de};
 
-use crate::Readable;
+use crate::{Error, Readable, Writable};
+
+/// The largest odometer value, `0xFFFFFF` is the missing value.
+const MAX_ODOMETER: u32 = 0xFFFFFE;
 
 /// the odometer value.
 #[derive(Debug)]
@@ -28,3 +31,66 @@ impl Serialize for OdometerShort {
         if let Some(val) = self.data { serializer.serialize_u32(val) } else { serializer.serialize_none() }
     }
 }
+
+impl<'de> Deserialize<'de> for OdometerShort {
+    /// Reads the odometer written by `Serialize`, `null` is the missing value.
+    /// Values which do not fit in the 3 bytes of the odometer are rejected.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let data = Option::<u32>::deserialize(deserializer)?;
+        if let Some(value) = data
+            && value > MAX_ODOMETER
+        {
+            return Err(de::Error::custom(format!("odometer value {value} is larger than {MAX_ODOMETER}")));
+        }
+        Ok(Self { data })
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for OdometerShort {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "OdometerShort".into()
+    }
+
+    fn schema_id() -> std::borrow::Cow<'static, str> {
+        concat!(module_path!(), "::OdometerShort").into()
+    }
+
+    fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Odometer value in km, `null` when it is not available.",
+            "type": ["integer", "null"],
+            "minimum": 0,
+            "maximum": MAX_ODOMETER
+        })
+    }
+}
+
+impl Writable for OdometerShort {
+    /// Writes an `OdometerShort` to a binary stream, a missing value is written as `0xFFFFFF`.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        if let Some(value) = self.data
+            && value > MAX_ODOMETER
+        {
+            return Err(Error::InvalidDataEncode(format!("odometer value {value} is larger than {MAX_ODOMETER}")));
+        }
+        writer.write_u24::<BigEndian>(self.data.unwrap_or(0xFFFFFF))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_odometer_short_rejects_values_larger_than_3_bytes() {
+        assert_eq!(serde_json::from_str::<OdometerShort>("16777214").unwrap().data, Some(0xFFFFFE));
+        assert_eq!(serde_json::from_str::<OdometerShort>("null").unwrap().data, None);
+        assert!(serde_json::from_str::<OdometerShort>("16777215").is_err());
+        assert!(serde_json::from_str::<OdometerShort>("16777216").is_err());
+    }
+}
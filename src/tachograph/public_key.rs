@@ -1,7 +1,7 @@
-use binary_data::{BigEndian, BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BigEndian, BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
-use crate::{ReadableWithParams, Result, tacho::CertificateContentType};
+use crate::{ReadableWithParams, Result, SpanRecorder, WritableWithParams, field_value, tacho::CertificateContentType};
 
 #[derive(Debug)]
 pub struct PublicKeyParams {
@@ -22,7 +22,8 @@ impl PublicKeyParams {
 /// The Public Key nests two data elements: the standardized domain
 /// parameters to be used with the public key in the certificate and the
 /// value of the public point.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PublicKey {
     #[serde(rename = "recordType")]
     pub record_type: CertificateContentType,
@@ -36,9 +37,28 @@ impl ReadableWithParams<PublicKey> for PublicKey {
     type P = PublicKeyParams;
 
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<PublicKey> {
-        let record_type: CertificateContentType = reader.read_u16::<BigEndian>()?.into();
+        let record_type: CertificateContentType =
+            SpanRecorder::field("recordType", "CertificateContentType", reader, |reader| {
+                Ok(reader.read_u16::<BigEndian>()?.into())
+            })?;
         let record_size = if let Some(length) = params.length { length } else { reader.read_u8()? };
-        let rsa_key_modulus = reader.read_into_vec(record_size as u32)?;
+        let rsa_key_modulus =
+            SpanRecorder::field("rsaKeyModulus", "OCTET STRING", reader, |reader| Ok(reader.read_into_vec(record_size as u32)?))?;
         Ok(Self { record_type, record_size: record_size as u16, rsa_key_modulus })
     }
 }
+
+impl WritableWithParams for PublicKey {
+    type P = PublicKeyParams;
+
+    /// Writes a `PublicKey` to a binary stream, the record size is written only when
+    /// the length is not given by the parameters, as when it is read.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W, params: &Self::P) -> Result<()> {
+        writer.write_u16::<BigEndian>((&self.record_type).into())?;
+        if params.length.is_none() {
+            writer.write_u8(field_value("rsaKeyModulus length", self.rsa_key_modulus.len())?)?;
+        }
+        writer.write_all(&self.rsa_key_modulus)?;
+        Ok(())
+    }
+}
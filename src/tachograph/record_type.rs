@@ -1,10 +1,14 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+
+use crate::impl_enum_from_u8;
 
 /// Represents the identifier of a data type.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[repr(u8)]
 pub enum RecordType {
-    /// Unknown data type.
-    Unknown = 0,
+    /// Unknown data type, with its value.
+    Unknown(u8) = 0,
     /// Information about activity changes.
     ActivityChangeInfo = 1,
     /// Status of the card slots.
@@ -82,53 +86,50 @@ pub enum RecordType {
 impl RecordType {
     /// Returns `true` if the data type is unknown.
     pub fn is_unknown(&self) -> bool {
-        matches!(*self, RecordType::Unknown)
+        matches!(*self, RecordType::Unknown(_))
     }
 }
 
-impl From<u8> for RecordType {
-    fn from(value: u8) -> Self {
-        match value {
-            1 => RecordType::ActivityChangeInfo,
-            2 => RecordType::CardSlotStatus,
-            3 => RecordType::CurrentDateTime,
-            4 => RecordType::MemberStateCertificate,
-            5 => RecordType::OdometerValueMidnight,
-            6 => RecordType::DateOfDayDownloaded,
-            7 => RecordType::SensorPaired,
-            8 => RecordType::Signature,
-            9 => RecordType::SpecificConditionRecord,
-            10 => RecordType::VehicleIdentificationNumber,
-            11 => RecordType::VehicleRegistrationNumber,
-            12 => RecordType::VuCalibrationRecord,
-            13 => RecordType::VuCardIWRecord,
-            14 => RecordType::VuCardRecord,
-            15 => RecordType::VuCertificate,
-            16 => RecordType::VuCompanyLocksRecord,
-            17 => RecordType::VuControlActivityRecord,
-            18 => RecordType::VuDetailedSpeedBlock,
-            19 => RecordType::VuDownloadablePeriod,
-            20 => RecordType::VuDownloadActivityData,
-            21 => RecordType::VuEventRecord,
-            22 => RecordType::VuGNSSADRecord,
-            23 => RecordType::VuITSConsentRecord,
-            24 => RecordType::VuFaultRecord,
-            25 => RecordType::VuIdentification,
-            26 => RecordType::VuOverSpeedingControlData,
-            27 => RecordType::VuOverSpeedingEventRecord,
-            28 => RecordType::VuPlaceDailyWorkPeriodRecord,
-            29 => RecordType::VuTimeAdjustmentGNSSRecord,
-            30 => RecordType::VuTimeAdjustmentRecord,
-            31 => RecordType::VuPowerSupplyInterruptionRecord,
-            32 => RecordType::SensorPairedRecord,
-            33 => RecordType::SensorExternalGNSSCoupledRecord,
-            34 => RecordType::VuBorderCrossingRecord,
-            35 => RecordType::VuLoadUnloadRecord,
-            36 => RecordType::VehicleRegistrationIdentification,
-            _ => RecordType::Unknown,
-        }
+impl_enum_from_u8!(
+    RecordType {
+        ActivityChangeInfo = 1,
+        CardSlotStatus = 2,
+        CurrentDateTime = 3,
+        MemberStateCertificate = 4,
+        OdometerValueMidnight = 5,
+        DateOfDayDownloaded = 6,
+        SensorPaired = 7,
+        Signature = 8,
+        SpecificConditionRecord = 9,
+        VehicleIdentificationNumber = 10,
+        VehicleRegistrationNumber = 11,
+        VuCalibrationRecord = 12,
+        VuCardIWRecord = 13,
+        VuCardRecord = 14,
+        VuCertificate = 15,
+        VuCompanyLocksRecord = 16,
+        VuControlActivityRecord = 17,
+        VuDetailedSpeedBlock = 18,
+        VuDownloadablePeriod = 19,
+        VuDownloadActivityData = 20,
+        VuEventRecord = 21,
+        VuGNSSADRecord = 22,
+        VuITSConsentRecord = 23,
+        VuFaultRecord = 24,
+        VuIdentification = 25,
+        VuOverSpeedingControlData = 26,
+        VuOverSpeedingEventRecord = 27,
+        VuPlaceDailyWorkPeriodRecord = 28,
+        VuTimeAdjustmentGNSSRecord = 29,
+        VuTimeAdjustmentRecord = 30,
+        VuPowerSupplyInterruptionRecord = 31,
+        SensorPairedRecord = 32,
+        SensorExternalGNSSCoupledRecord = 33,
+        VuBorderCrossingRecord = 34,
+        VuLoadUnloadRecord = 35,
+        VehicleRegistrationIdentification = 36,
     }
-}
+);
 
 #[cfg(test)]
 mod tests {
@@ -144,8 +145,9 @@ mod tests {
         let serialized = serde_json::to_string(&record_type).unwrap();
         assert_eq!(serialized, r#""VuCalibrationRecord""#);
 
-        let record_type = RecordType::Unknown;
+        let record_type = RecordType::Unknown(0x40);
         let serialized = serde_json::to_string(&record_type).unwrap();
-        assert_eq!(serialized, r#""Unknown""#);
+        assert_eq!(serialized, r#"{"Unknown":64}"#);
+        assert_eq!(u8::from(&serde_json::from_str::<RecordType>(&serialized).unwrap()), 0x40);
     }
 }
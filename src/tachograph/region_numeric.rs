@@ -1,12 +1,13 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::impl_enum_from_u8;
 
 /// Numerical reference to a region within a specified country.
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(u8)]
 pub enum RegionNumeric {
-    Unknown = 0,
+    Unknown(u8) = 0,
     Andalucia = 1,
     Aragon = 2,
     Asturias = 3,
@@ -30,7 +31,6 @@ pub enum RegionNumeric {
 
 impl_enum_from_u8!(
     RegionNumeric {
-        Unknown = 0,
         Andalucia = 1,
         Aragon = 2,
         Asturias = 3,
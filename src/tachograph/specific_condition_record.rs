@@ -1,13 +1,14 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable,
+    Readable, ReadableField, SpanRecorder, Writable,
     tacho::{SpecificConditionType, TimeReal},
 };
 
 /// Information, stored in a driver card, a workshop card or a vehicle unit,
 /// related to a specific condition (requirements Annex 1C 130, 276, 301, 328, and 355).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SpecificConditionRecord {
     #[serde(rename = "entryTime")]
     pub entry_time: TimeReal,
@@ -17,8 +18,18 @@ pub struct SpecificConditionRecord {
 
 impl Readable<SpecificConditionRecord> for SpecificConditionRecord {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<SpecificConditionRecord> {
-        let entry_time = TimeReal::read(reader)?;
-        let specific_condition_type: SpecificConditionType = reader.read_u8()?.into();
+        let entry_time = TimeReal::read_field(reader, "entryTime")?;
+        let specific_condition_type: SpecificConditionType =
+            SpanRecorder::field("specificConditionType", "SpecificConditionType", reader, |reader| Ok(reader.read_u8()?.into()))?;
         Ok(Self { entry_time, specific_condition_type })
     }
 }
+
+impl Writable for SpecificConditionRecord {
+    /// Writes a `SpecificConditionRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.entry_time.write(writer)?;
+        writer.write_u8((&self.specific_condition_type).into())?;
+        Ok(())
+    }
+}
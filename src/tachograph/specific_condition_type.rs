@@ -1,17 +1,18 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::impl_enum_from_u8;
 
 /// Code identifying a specific condition (Annex 1B requirements 050b,
 /// 105a, 212a and 230a and Annex 1C requirements 62).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(u8)]
 pub enum SpecificConditionType {
     OutOfScopeBegin = 1,
     OutOfScopeEnd = 2,
     FerryTrainCrossing = 3,
     FerryTrainCrossingEnd = 4,
-    Unknown = 0xFF,
+    Unknown(u8) = 0xFF,
 }
 
 impl_enum_from_u8!(
@@ -20,6 +21,5 @@ impl_enum_from_u8!(
         OutOfScopeEnd = 2,
         FerryTrainCrossing = 3,
         FerryTrainCrossingEnd = 4,
-        Unknown = 0xFF,
     }
 );
@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     CARD_HEADER, CARD_HEADER_VU_DATA, MINIMUM_G2_CARD_DATA_LENGTH, VU_HEADER_G1, VU_HEADER_G2, VU_HEADER_G2_V2,
@@ -7,7 +7,8 @@ use crate::{
 };
 
 /// Represents the header of a tachograph file.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TachographHeader {
     /// The generation of the tachograph data.
     pub generation: TachographDataGeneration,
@@ -22,20 +23,46 @@ pub struct TachographHeader {
 impl TachographHeader {
     /// Creates a new `TachographHeader` from the given data.
     ///
+    /// The generation of card data is `Unknown` here, it is detected later
+    /// from the card files (see `CardFilesDataByCardGeneration::get_card_generation`).
+    ///
     /// # Arguments
     ///
     /// * `data` - The header data.
-    /// * `data_length` - The length of the data.
     ///
     /// # Returns
     ///
     /// A new `TachographHeader` instance.
-    pub fn from_data(data: &[u8], data_length: u64) -> Result<TachographHeader> {
+    pub fn from_header_data(data: &[u8]) -> Result<TachographHeader> {
         if data.len() != 2 {
             return Err(Error::InvalidHeaderLength);
         }
 
-        TachographHeader::parse_header(data, data_length)
+        TachographHeader::parse_header(data)
+    }
+
+    /// Creates a new `TachographHeader` from the given data, the generation of card data
+    /// is guessed from the length of the data.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The header data.
+    /// * `data_length` - The length of the data.
+    ///
+    /// # Returns
+    ///
+    /// A new `TachographHeader` instance.
+    #[deprecated(note = "use `TachographHeader::from_header_data`, the generation of card data is detected from the card files")]
+    pub fn from_data(data: &[u8], data_length: u64) -> Result<TachographHeader> {
+        let mut header = TachographHeader::from_header_data(data)?;
+        if matches!(header.data_type, TachographDataType::Card) {
+            header.generation = if data_length >= MINIMUM_G2_CARD_DATA_LENGTH {
+                TachographDataGeneration::SecondGeneration
+            } else {
+                TachographDataGeneration::FirstGeneration
+            };
+        }
+        Ok(header)
     }
 
     /// Parses the header data and returns a new `TachographHeader`.
@@ -43,12 +70,11 @@ impl TachographHeader {
     /// # Arguments
     ///
     /// * `header` - The header data.
-    /// * `data_length` - The length of the data.
     ///
     /// # Returns
     ///
     /// A new `TachographHeader` instance.
-    fn parse_header(header: &[u8], data_length: u64) -> Result<TachographHeader> {
+    fn parse_header(header: &[u8]) -> Result<TachographHeader> {
         if header.eq(&VU_HEADER_G1) {
             return Ok(TachographHeader {
                 generation: TachographDataGeneration::FirstGeneration,
@@ -65,24 +91,38 @@ impl TachographHeader {
             });
         }
 
-        if header.eq(&CARD_HEADER) {
-            let generation: TachographDataGeneration = if data_length >= MINIMUM_G2_CARD_DATA_LENGTH {
-                TachographDataGeneration::SecondGeneration
-            } else {
-                TachographDataGeneration::FirstGeneration
-            };
-            return Ok(TachographHeader { generation, data_type: TachographDataType::Card, card_in_vu_data: false });
-        }
-
-        if header.eq(&CARD_HEADER_VU_DATA) {
-            let generation: TachographDataGeneration = if data_length >= MINIMUM_G2_CARD_DATA_LENGTH {
-                TachographDataGeneration::SecondGeneration
-            } else {
-                TachographDataGeneration::FirstGeneration
-            };
-            return Ok(TachographHeader { generation, data_type: TachographDataType::Card, card_in_vu_data: true });
+        if header.eq(&CARD_HEADER) || header.eq(&CARD_HEADER_VU_DATA) {
+            return Ok(TachographHeader {
+                generation: TachographDataGeneration::Unknown,
+                data_type: TachographDataType::Card,
+                card_in_vu_data: header.eq(&CARD_HEADER_VU_DATA),
+            });
         }
 
         Err(Error::InvalidHeaderData)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_from_data_guesses_card_generation_from_length() {
+        // --- Act ---
+        let gen1_card = TachographHeader::from_data(&CARD_HEADER, MINIMUM_G2_CARD_DATA_LENGTH - 1).unwrap();
+        let gen2_card = TachographHeader::from_data(&CARD_HEADER_VU_DATA, MINIMUM_G2_CARD_DATA_LENGTH).unwrap();
+        let vu = TachographHeader::from_data(&VU_HEADER_G2, 0).unwrap();
+
+        // --- Assert ---
+        assert!(matches!(gen1_card.generation, TachographDataGeneration::FirstGeneration));
+        assert!(matches!(gen2_card.generation, TachographDataGeneration::SecondGeneration));
+        assert!(gen2_card.card_in_vu_data);
+        assert!(matches!(vu.generation, TachographDataGeneration::SecondGeneration));
+        assert!(matches!(
+            TachographHeader::from_header_data(&CARD_HEADER).unwrap().generation,
+            TachographDataGeneration::Unknown
+        ));
+    }
+}
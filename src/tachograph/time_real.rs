@@ -1,8 +1,8 @@
 use binary_data::{BigEndian, BinSeek, WriteBytes};
 use chrono::Utc;
-use serde::Serialize;
+use serde::{Deserialize, Deserializer, Serialize, de};
 
-use crate::{Readable, Writable};
+use crate::{Readable, SpanRecorder, Writable};
 
 const FORMAT_UTC: &str = "%Y-%m-%d %H:%M:%S UTC";
 
@@ -53,7 +53,7 @@ impl TimeReal {
 impl Readable<TimeReal> for TimeReal {
     /// Reads a `TimeReal` from a binary stream of a DDD file.
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<TimeReal> {
-        let data = reader.read_u32::<BigEndian>()?;
+        let data = SpanRecorder::field("data", "INTEGER", reader, |reader| Ok(reader.read_u32::<BigEndian>()?))?;
         let date_time = chrono::DateTime::from_timestamp(data as i64, 0);
         Ok(Self { data, date_time })
     }
@@ -81,6 +81,56 @@ impl Serialize for TimeReal {
     }
 }
 
+/// Reads the time written by `Serialize`, the UTC date and time string is exact to the
+/// second so it gives back the same `data`. The raw value is accepted as a number.
+impl<'de> Deserialize<'de> for TimeReal {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TimeRealVisitor;
+
+        impl de::Visitor<'_> for TimeRealVisitor {
+            type Value = TimeReal;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a date and time in the format \"YYYY-MM-DD HH:MM:SS UTC\" or seconds since 1970")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<TimeReal, E> {
+                let date_time = chrono::NaiveDateTime::parse_from_str(value, FORMAT_UTC).map_err(E::custom)?;
+                let data = u32::try_from(date_time.and_utc().timestamp()).map_err(E::custom)?;
+                Ok(TimeReal::new(data))
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<TimeReal, E> {
+                u32::try_from(value).map(TimeReal::new).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(TimeRealVisitor)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for TimeReal {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "TimeReal".into()
+    }
+
+    fn schema_id() -> std::borrow::Cow<'static, str> {
+        concat!(module_path!(), "::TimeReal").into()
+    }
+
+    fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Date and time in the format \"YYYY-MM-DD HH:MM:SS UTC\".",
+            "type": "string",
+            "pattern": "^[0-9]{4}-[0-9]{2}-[0-9]{2} [0-9]{2}:[0-9]{2}:[0-9]{2} UTC$"
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::helpers::from_obj_to_string;
@@ -152,4 +202,15 @@ mod tests {
         let time_real = TimeReal::read(&mut reader).unwrap();
         assert_eq!(from_obj_to_string(&time_real), "\"2022-12-31 23:59:59 UTC\"");
     }
+
+    #[test]
+    fn test_deserialize_time_real() {
+        let time_real: TimeReal = serde_json::from_str("\"2022-12-31 23:59:59 UTC\"").unwrap();
+        assert_eq!(time_real.get_data(), 1672531199);
+        assert_eq!(time_real.get_date_time_str(), "2022-12-31 23:59:59");
+
+        let time_real: TimeReal = serde_json::from_str("4294967295").unwrap();
+        assert_eq!(from_obj_to_string(&time_real), "\"2106-02-07 06:28:15 UTC\"");
+        assert_eq!(serde_json::from_str::<TimeReal>(&from_obj_to_string(&time_real)).unwrap().get_data(), u32::MAX);
+    }
 }
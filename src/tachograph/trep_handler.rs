@@ -0,0 +1,67 @@
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+
+/// Parser for a manufacturer specific or future TREP, which is not known to
+/// the parser. Handlers are registered with `ParseOptions::with_trep_handler`.
+pub trait TrepHandler: Send + Sync {
+    /// Parses the raw data of a TREP block, the bytes after the TREP id up to
+    /// the next TREP or the end of the data.
+    fn parse(&self, trep: u8, data: &[u8]) -> Result<serde_json::Value>;
+}
+
+impl<F> TrepHandler for F
+where
+    F: Fn(u8, &[u8]) -> Result<serde_json::Value> + Send + Sync,
+{
+    fn parse(&self, trep: u8, data: &[u8]) -> Result<serde_json::Value> {
+        self(trep, data)
+    }
+}
+
+/// Handlers for TREPs which are not known to the parser, by TREP id.
+#[derive(Clone, Default)]
+pub struct TrepHandlers {
+    handlers: HashMap<u8, Arc<dyn TrepHandler>>,
+}
+
+impl TrepHandlers {
+    /// Registers the handler for the TREP id, a previous handler for the same id is replaced.
+    pub fn register(&mut self, trep: u8, handler: impl TrepHandler + 'static) {
+        self.handlers.insert(trep, Arc::new(handler));
+    }
+
+    pub fn get(&self, trep: u8) -> Option<Arc<dyn TrepHandler>> {
+        self.handlers.get(&trep).cloned()
+    }
+}
+
+impl fmt::Debug for TrepHandlers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut treps: Vec<&u8> = self.handlers.keys().collect();
+        treps.sort();
+        f.debug_struct("TrepHandlers").field("treps", &treps).finish()
+    }
+}
+
+/// TREP block which is not known to the parser, kept as raw bytes.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RawTrepBlock {
+    /// TREP id which follows the `0x76` marker.
+    pub trep: u8,
+    /// Byte offset of the `0x76` marker in the parsed data.
+    pub offset: usize,
+    /// Data of the block, without the marker and TREP id.
+    pub data: Vec<u8>,
+    /// Output of the registered `TrepHandler`, if there is one for the TREP.
+    pub parsed: Option<serde_json::Value>,
+}
+
+impl RawTrepBlock {
+    pub fn new(trep: u8, offset: usize, data: Vec<u8>) -> Self {
+        Self { trep, offset, data, parsed: None }
+    }
+}
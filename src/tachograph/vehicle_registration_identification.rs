@@ -1,12 +1,13 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable,
+    Readable, ReadableField, SpanRecorder, Writable,
     tacho::{NationNumeric, VehicleRegistrationNumber},
 };
 
 /// Identification of a vehicle, unique for Europe (VRN and Member State).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VehicleRegistrationIdentification {
     #[serde(rename = "vehicleRegistrationNation")]
     pub vehicle_registration_nation: NationNumeric,
@@ -18,9 +19,19 @@ impl Readable<VehicleRegistrationIdentification> for VehicleRegistrationIdentifi
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(
         reader: &mut R,
     ) -> crate::Result<VehicleRegistrationIdentification> {
-        let nation_numeric: NationNumeric = reader.read_u8()?.into();
-        let vehicle_registration_number = VehicleRegistrationNumber::read(reader)?;
+        let nation_numeric: NationNumeric =
+            SpanRecorder::field("vehicleRegistrationNation", "NationNumeric", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let vehicle_registration_number = VehicleRegistrationNumber::read_field(reader, "vehicleRegistrationNumber")?;
 
         Ok(Self { vehicle_registration_nation: nation_numeric, vehicle_registration_number })
     }
 }
+
+impl Writable for VehicleRegistrationIdentification {
+    /// Writes a `VehicleRegistrationIdentification` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8((&self.vehicle_registration_nation).into())?;
+        self.vehicle_registration_number.write(writer)?;
+        Ok(())
+    }
+}
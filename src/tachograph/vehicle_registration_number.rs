@@ -1,32 +1,37 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{CodePage, Readable, bytes_to_string};
+use crate::{CodePage, Readable, SpanRecorder, Writable, bytes_to_string, write_string};
 
 const VEHICLE_REG_NUMBER_LENGTH: u32 = 13;
 
 /// Registration number of the vehicle (VRN). The registration number is
 /// assigned by the vehicle licensing authority.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VehicleRegistrationNumber {
+    #[serde(rename = "codePage")]
     pub code_page: CodePage,
+    #[serde(rename = "vehicleRegNumber")]
     pub vehicle_reg_number: String,
 }
 
 impl Readable<VehicleRegistrationNumber> for VehicleRegistrationNumber {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VehicleRegistrationNumber> {
-        let code_page: CodePage = reader.read_u8()?.into();
-        let mut vehicle_reg_number = bytes_to_string(&reader.read_into_vec(VEHICLE_REG_NUMBER_LENGTH)?, &code_page);
+        let code_page: CodePage = SpanRecorder::field("codePage", "CodePage", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let mut vehicle_reg_number = SpanRecorder::field("vehicleRegNumber", "OCTET STRING", reader, |reader| {
+            Ok(bytes_to_string(&reader.read_into_vec(VEHICLE_REG_NUMBER_LENGTH)?, &code_page))
+        })?;
         vehicle_reg_number = if code_page == CodePage::Invalid { "".to_owned() } else { vehicle_reg_number };
         Ok(Self { code_page, vehicle_reg_number })
     }
 }
 
-impl Serialize for VehicleRegistrationNumber {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        serializer.serialize_str(&self.vehicle_reg_number)
+impl Writable for VehicleRegistrationNumber {
+    /// Writes a `VehicleRegistrationNumber` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8(self.code_page.clone() as u8)?;
+        write_string(writer, &self.vehicle_reg_number, &self.code_page, VEHICLE_REG_NUMBER_LENGTH as usize)?;
+        Ok(())
     }
 }
 
@@ -77,6 +82,8 @@ mod tests {
         let vrn = VehicleRegistrationNumber { code_page: CodePage::IsoIec8859_1, vehicle_reg_number: "TEST-VRN".to_string() };
 
         let serialized = serde_json::to_string(&vrn).unwrap();
-        assert_eq!(serialized, "\"TEST-VRN\"");
+        assert_eq!(serialized, r#"{"codePage":"IsoIec8859_1","vehicleRegNumber":"TEST-VRN"}"#);
+        let deserialized: VehicleRegistrationNumber = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, vrn);
     }
 }
@@ -1,11 +1,12 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     Export,
-    tacho::{CardFileID, TimeReal},
+    tacho::{CardFileID, TimeReal, VUTransferResponseParameterID},
 };
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum VerifyStatus {
     Invalid,
     Valid,
@@ -14,14 +15,16 @@ pub enum VerifyStatus {
     NotHaveData,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VerifyItem {
     pub card_file_id: CardFileID,
     pub status: VerifyStatus,
     pub end_of_validity: Option<TimeReal>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum VerifyResultStatus {
     Invalid,
     Valid,
@@ -29,10 +32,57 @@ pub enum VerifyResultStatus {
     PartialyValid,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VerifyResult {
     pub status: VerifyResultStatus,
     pub result: Vec<VerifyItem>,
 }
 
 impl Export for VerifyResult {}
+
+/// The verification of the signature of a TREP of a VU download.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct VuVerifyItem {
+    pub trep_id: VUTransferResponseParameterID,
+    /// Position of the TREP in the VU download, as `VUTransferResponseParameterItem::position`.
+    pub position: u32,
+    pub status: VerifyStatus,
+}
+
+/// The verification of a VU download, the certificates of the member state and of the
+/// VU and the signature of each TREP.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct VuVerifyResult {
+    pub status: VerifyResultStatus,
+    pub member_state_certificate_end_of_validity: Option<TimeReal>,
+    pub vu_certificate_end_of_validity: Option<TimeReal>,
+    pub result: Vec<VuVerifyItem>,
+}
+
+impl VuVerifyResult {
+    /// Creates the result of the TREPs, the status is `Valid` when all signed TREPs are
+    /// valid, `PartialyValid` when only some of them are valid and `Unsigned` when no
+    /// TREP is signed.
+    pub fn new(
+        member_state_certificate_end_of_validity: Option<TimeReal>,
+        vu_certificate_end_of_validity: Option<TimeReal>,
+        result: Vec<VuVerifyItem>,
+    ) -> Self {
+        let signed =
+            result.iter().filter(|item| !matches!(item.status, VerifyStatus::NotHaveSignature | VerifyStatus::NotHaveData));
+        let (signed_count, valid_count) = signed
+            .fold((0, 0), |(signed, valid), item| (signed + 1, valid + matches!(item.status, VerifyStatus::Valid) as usize));
+        let status = match (signed_count, valid_count) {
+            (0, _) => VerifyResultStatus::Unsigned,
+            (signed, valid) if signed == valid => VerifyResultStatus::Valid,
+            (_, 0) => VerifyResultStatus::Invalid,
+            _ => VerifyResultStatus::PartialyValid,
+        };
+        Self { status, member_state_certificate_end_of_validity, vu_certificate_end_of_validity, result }
+    }
+}
+
+impl Export for VuVerifyResult {}
@@ -3,14 +3,15 @@ use log::debug;
 
 use super::{TachographHeader, VUTransferResponseParameterID};
 use crate::{
-    VU_HEADER_MAGIC_NUMBER,
+    Diagnostic, ParseContext, Severity, SpanRecorder, VU_HEADER_MAGIC_NUMBER,
     error::Result,
-    tacho::{VUTransferResponseParameter, VUTransferResponseParameterItem},
+    tacho::{RawTrepBlock, VUTransferResponseParameter, VUTransferResponseParameterItem},
 };
 
 pub trait VUData<D> {
     fn get_header(&self) -> &TachographHeader;
     fn get_data(&self) -> &Vec<VUTransferResponseParameterItem<D>>;
+    fn get_data_mut(&mut self) -> &mut Vec<VUTransferResponseParameterItem<D>>;
 }
 
 impl<D: VUTransferResponseParameter> dyn VUData<D> {
@@ -19,39 +20,93 @@ impl<D: VUTransferResponseParameter> dyn VUData<D> {
         parse_trep: &dyn Fn(VUTransferResponseParameterID, &mut R) -> Result<D>,
     ) -> Result<Vec<VUTransferResponseParameterItem<D>>> {
         let mut position: u32 = 0;
-        let mut data_position: usize = 0;
         let mut transfer_res_params: Vec<VUTransferResponseParameterItem<D>> = Vec::new();
 
         while reader.pos()? < reader.len()? {
-            position += 1;
-            let mut vu_trep: VUTransferResponseParameterID = VUTransferResponseParameterID::Unknown;
-            let mut magic_number = reader.read_u8()?;
-            if magic_number == VU_HEADER_MAGIC_NUMBER {
-                vu_trep = VUTransferResponseParameterID::from(reader.read_u8()?);
-                data_position = reader.pos()?;
+            let block_position = reader.pos()?;
+            let magic_number = reader.read_u8()?;
+            if magic_number != VU_HEADER_MAGIC_NUMBER || reader.pos()? >= reader.len()? {
+                let next_block_position = <dyn VUData<D>>::find_next_trep(reader, block_position)?;
+                let skipped = next_block_position - block_position;
+                debug!("VUData::from_data - Skipped {} bytes on position: {}", skipped, block_position);
+                ParseContext::report(Diagnostic::new(
+                    Severity::Warning,
+                    Some(block_position),
+                    None,
+                    format!("skipped {skipped} bytes while searching for the next TREP"),
+                ));
+                reader.seek(next_block_position)?;
+                continue;
+            }
+
+            let trep = reader.read_u8()?;
+            let data_position = reader.pos()?;
+            let vu_trep = VUTransferResponseParameterID::from(trep);
+            debug!("VUData::from_data - Trep ID: {:?} on position: {}", vu_trep, data_position);
+            let data = if vu_trep.is_unknown() {
+                let name = format!("TREP 0x{trep:02X}");
+                D::from_raw(SpanRecorder::record(name, reader, |reader| {
+                    <dyn VUData<D>>::read_raw_block(reader, trep, block_position)
+                })?)
             } else {
-                while magic_number != VU_HEADER_MAGIC_NUMBER && reader.pos()? < reader.len()? - 1 {
-                    magic_number = reader.read_u8()?;
-                    if magic_number == VU_HEADER_MAGIC_NUMBER {
-                        vu_trep = VUTransferResponseParameterID::from(reader.read_u8()?);
-                        if !vu_trep.is_unknown() {
-                            break;
-                        }
+                match SpanRecorder::record(format!("{vu_trep:?}"), reader, |reader| parse_trep(vu_trep.clone(), reader)) {
+                    Ok(data) => data,
+                    Err(error) => {
+                        let error = error.at_offset(reader.pos()?).in_section(format!("{vu_trep:?}"));
+                        ParseContext::recover(error)?;
+                        // Continue searching for the next TREP right after the broken one's header.
+                        reader.seek(data_position)?;
+                        continue;
                     }
                 }
+            };
+            let data_end_position = reader.pos()?;
+            reader.seek(data_position)?;
+            let raw_data = reader.read_into_vec((data_end_position - data_position) as u32)?;
+            position += 1;
+            let is_oddball_crash_dump = data.is_oddball_crash_dump();
+            transfer_res_params.push(VUTransferResponseParameterItem::<D> { type_id: vu_trep, position, data, raw_data });
+
+            if is_oddball_crash_dump {
+                return Ok(transfer_res_params);
             }
+        }
+        Ok(transfer_res_params)
+    }
 
-            if !vu_trep.is_unknown() {
-                debug!("VUData::from_data - Trep ID: {:?} on position: {}", vu_trep, data_position);
-                let data = parse_trep(vu_trep.clone(), reader)?;
-                let is_oddball_crash_dump = data.is_oddball_crash_dump();
-                transfer_res_params.push(VUTransferResponseParameterItem::<D> { type_id: vu_trep.clone(), position, data });
+    /// Reads the data of a TREP which is not known to the parser, up to the next
+    /// TREP, and parses it with the registered `TrepHandler` if there is one.
+    fn read_raw_block<R: ReadBytes + BinSeek>(reader: &mut R, trep: u8, block_position: usize) -> Result<RawTrepBlock> {
+        let data_position = reader.pos()?;
+        let next_block_position = <dyn VUData<D>>::find_next_trep(reader, data_position)?;
+        reader.seek(data_position)?;
+        let mut block =
+            RawTrepBlock::new(trep, block_position, reader.read_into_vec((next_block_position - data_position) as u32)?);
+        if let Some(handler) = ParseContext::trep_handler(trep) {
+            match handler.parse(trep, &block.data) {
+                Ok(parsed) => block.parsed = Some(parsed),
+                Err(error) => ParseContext::recover(error.at_offset(block_position).in_section(format!("TREP 0x{trep:02X}")))?,
+            }
+        }
+        Ok(block)
+    }
 
-                if is_oddball_crash_dump {
-                    return Ok(transfer_res_params);
+    /// Returns the position of the next `0x76` marker followed by a known or
+    /// handled TREP id, or the end of the data when there is none.
+    fn find_next_trep<R: ReadBytes + BinSeek>(reader: &mut R, from: usize) -> Result<usize> {
+        let len = reader.len()?;
+        reader.seek(from)?;
+        let mut position = from;
+        while position + 1 < len {
+            if reader.read_u8()? == VU_HEADER_MAGIC_NUMBER {
+                let trep = reader.read_u8()?;
+                if !VUTransferResponseParameterID::from(trep).is_unknown() || ParseContext::trep_handler(trep).is_some() {
+                    return Ok(position);
                 }
+                reader.seek(position + 1)?;
             }
+            position += 1;
         }
-        Ok(transfer_res_params)
+        Ok(len)
     }
 }
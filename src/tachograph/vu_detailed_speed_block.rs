@@ -1,11 +1,14 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, tacho::TimeReal};
+use crate::{Readable, ReadableField, Writable, tacho::TimeReal, write_octet_string};
+
+const SPEEDS_PER_SECOND_LENGTH: usize = 60;
 
 /// Information, stored in a vehicle unit, related to the vehicle's detailed
 /// speed for a minute during which the vehicle has been moving
 /// (Annnex 1B requirement 093 and Annex 1C requirement 116).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuDetailedSpeedBlock {
     #[serde(rename = "speedBlockBeginDate")]
     pub speed_block_begin_date: TimeReal,
@@ -15,11 +18,19 @@ pub struct VuDetailedSpeedBlock {
 
 impl Readable<VuDetailedSpeedBlock> for VuDetailedSpeedBlock {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuDetailedSpeedBlock> {
-        let speed_block_begin_date = TimeReal::read(reader)?;
+        let speed_block_begin_date = TimeReal::read_field(reader, "speedBlockBeginDate")?;
         let mut speeds_per_second: Vec<u8> = Vec::new();
-        for _ in 0..60 {
+        for _ in 0..SPEEDS_PER_SECOND_LENGTH {
             speeds_per_second.push(reader.read_u8()?);
         }
         Ok(Self { speed_block_begin_date, speeds_per_second })
     }
 }
+
+impl Writable for VuDetailedSpeedBlock {
+    /// Writes a `VuDetailedSpeedBlock` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.speed_block_begin_date.write(writer)?;
+        write_octet_string(writer, &self.speeds_per_second, SPEEDS_PER_SECOND_LENGTH)
+    }
+}
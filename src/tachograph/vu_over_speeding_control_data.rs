@@ -1,11 +1,12 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, tacho::TimeReal};
+use crate::{Readable, ReadableField, SpanRecorder, Writable, tacho::TimeReal};
 
 /// Information, stored in a vehicle unit, related to over speeding events
 /// since the last over speeding control (Annex 1B requirement 095 and
 /// Annex 1C requirement 117).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuOverSpeedingControlData {
     #[serde(rename = "lastOverspeedControlTime")]
     pub last_overspeed_control_time: TimeReal,
@@ -17,9 +18,20 @@ pub struct VuOverSpeedingControlData {
 
 impl Readable<VuOverSpeedingControlData> for VuOverSpeedingControlData {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuOverSpeedingControlData> {
-        let last_overspeed_control_time = TimeReal::read(reader)?;
-        let first_overspeed_since = TimeReal::read(reader)?;
-        let number_of_overspeed_since = reader.read_u8()?;
+        let last_overspeed_control_time = TimeReal::read_field(reader, "lastOverspeedControlTime")?;
+        let first_overspeed_since = TimeReal::read_field(reader, "firstOverspeedSince")?;
+        let number_of_overspeed_since =
+            SpanRecorder::field("numberOfOverspeedSince", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
         Ok(Self { last_overspeed_control_time, first_overspeed_since, number_of_overspeed_since })
     }
 }
+
+impl Writable for VuOverSpeedingControlData {
+    /// Writes a `VuOverSpeedingControlData` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.last_overspeed_control_time.write(writer)?;
+        self.first_overspeed_since.write(writer)?;
+        writer.write_u8(self.number_of_overspeed_since)?;
+        Ok(())
+    }
+}
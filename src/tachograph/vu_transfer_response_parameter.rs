@@ -1,3 +1,16 @@
+use binary_data::{BinSeek, ReadBytes};
+
+use crate::{
+    Result,
+    tacho::{RawTrepBlock, VUTransferResponseParameterID},
+};
+
 pub trait VUTransferResponseParameter {
     fn is_oddball_crash_dump(&self) -> bool;
+    /// Creates the data of a TREP which is not known to the parser.
+    fn from_raw(block: RawTrepBlock) -> Self;
+    /// Parses the data of the TREP `trep_id` which follows the TREP id.
+    fn parse<R: ReadBytes + BinSeek>(trep_id: VUTransferResponseParameterID, reader: &mut R) -> Result<Self>
+    where
+        Self: Sized;
 }
@@ -1,7 +1,8 @@
 use log::debug;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum VUTransferResponseParameterID {
     Unknown = 0x00,
     Overview = 0x01,
@@ -58,3 +59,9 @@ impl From<u8> for VUTransferResponseParameterID {
         }
     }
 }
+
+impl From<&VUTransferResponseParameterID> for u8 {
+    fn from(value: &VUTransferResponseParameterID) -> Self {
+        value.clone() as u8
+    }
+}
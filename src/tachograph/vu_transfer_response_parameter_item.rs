@@ -1,11 +1,74 @@
-use serde::Serialize;
+use std::borrow::Cow;
 
-use crate::tacho::VUTransferResponseParameterID;
+use binary_data::{BinMemoryBuffer, BinSeek, WriteBytes};
+use log::debug;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize)]
+use crate::{
+    ParseContext, ParseOptions, Result, VU_HEADER_MAGIC_NUMBER, Writable, WritableWithParams,
+    tacho::{VUTransferResponseParameter, VUTransferResponseParameterID},
+    write_to_vec,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VUTransferResponseParameterItem<D> {
     #[serde(rename = "typeId")]
     pub type_id: VUTransferResponseParameterID,
     pub position: u32,
     pub data: D,
+    /// The data of the TREP after the TREP id, as it is read from the DDD file, for the
+    /// verification of the TREP signature and to write the unchanged TREP back. It is
+    /// exported, so the TREPs of an imported JSON are verified over the original bytes.
+    #[serde(rename = "rawData", default)]
+    pub raw_data: Vec<u8>,
+}
+
+impl<D: WritableWithParams<P = VUTransferResponseParameterID>> VUTransferResponseParameterItem<D> {
+    /// Returns the data of the TREP after the TREP id, as it is read from the DDD file or
+    /// written again from the parsed data when the TREP has no raw data, e.g. when it is
+    /// imported from JSON without `rawData`. The written data may differ from the signed
+    /// bytes, e.g. in the data which is not decoded.
+    pub fn get_raw_data(&self) -> Result<Cow<'_, [u8]>> {
+        if !self.raw_data.is_empty() {
+            return Ok(Cow::Borrowed(&self.raw_data));
+        }
+        let data = write_to_vec(|writer| self.data.write(writer, &self.type_id))?;
+        Ok(Cow::Owned(data.get(1..).unwrap_or_default().to_vec()))
+    }
+}
+
+impl<D: VUTransferResponseParameter + WritableWithParams<P = VUTransferResponseParameterID>> VUTransferResponseParameterItem<D> {
+    /// Returns true when `data`, the TREP id followed by the data written from the parsed
+    /// structure, is the same as written from the data parsed again from `raw_data`.
+    fn is_unchanged(&self, data: &[u8]) -> bool {
+        if self.raw_data.is_empty() || self.type_id.is_unknown() {
+            return false;
+        }
+        let (parsed, _) = ParseContext::run(&ParseOptions::lenient(), || {
+            D::parse(self.type_id.clone(), &mut BinMemoryBuffer::from(self.raw_data.clone()))
+        });
+        parsed.and_then(|parsed| write_to_vec(|writer| parsed.write(writer, &self.type_id))).is_ok_and(|parsed| parsed == data)
+    }
+}
+
+impl<D: VUTransferResponseParameter + WritableWithParams<P = VUTransferResponseParameterID>> Writable
+    for VUTransferResponseParameterItem<D>
+{
+    /// Writes the TREP header magic number followed by the TREP id and the data of the TREP.
+    /// The TREP which was not changed after parsing keeps the bytes of `raw_data`, e.g. the
+    /// data which is not decoded, so a parsed TREP is written byte identical. A changed TREP
+    /// is written from its parsed structure.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u8(VU_HEADER_MAGIC_NUMBER)?;
+        let data = write_to_vec(|writer| self.data.write(writer, &self.type_id))?;
+        if self.is_unchanged(&data) {
+            writer.write_u8((&self.type_id).into())?;
+            writer.write_all(&self.raw_data)?;
+            return Ok(());
+        }
+        debug!("VUTransferResponseParameterItem::write - Changed: {:?} on position: {}", self.type_id, self.position);
+        writer.write_all(&data)?;
+        Ok(())
+    }
 }
@@ -1,7 +1,7 @@
 use binary_data::{BigEndian, BinSeek, ReadBytes};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, ReadableWithParams, Result};
+use crate::{Readable, ReadableField, ReadableWithParams, Result, SpanRecorder, Writable};
 
 #[derive(Debug)]
 pub struct WorkshopCardCalibrationDataParams {
@@ -16,7 +16,8 @@ impl WorkshopCardCalibrationDataParams {
 
 /// Information, stored in a workshop card, related to workshop activity
 /// performed with the card (Annex 1C requirements 314, 316, 337, and 339).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct WorkshopCardCalibrationData<T> {
     #[serde(rename = "calibrationTotalNumber")]
     pub calibration_total_number: u16,
@@ -26,17 +27,31 @@ pub struct WorkshopCardCalibrationData<T> {
     pub calibration_records: Vec<T>,
 }
 
-impl<T: Readable<T>> ReadableWithParams<WorkshopCardCalibrationData<T>> for WorkshopCardCalibrationData<T> {
+impl<T: Readable<T> + Serialize> ReadableWithParams<WorkshopCardCalibrationData<T>> for WorkshopCardCalibrationData<T> {
     type P = WorkshopCardCalibrationDataParams;
 
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<WorkshopCardCalibrationData<T>> {
-        let calibration_total_number = reader.read_u16::<BigEndian>()?;
-        let calibration_pointer_newest_record = reader.read_u8()?;
+        let calibration_total_number =
+            SpanRecorder::field("calibrationTotalNumber", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
+        let calibration_pointer_newest_record =
+            SpanRecorder::field("calibrationPointerNewestRecord", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
         let mut calibration_records: Vec<T> = Vec::new();
-        for _ in 0..params.no_of_calibration_records {
-            let workshop_card_calibration_record = T::read(reader)?;
+        for index in 0..params.no_of_calibration_records {
+            let workshop_card_calibration_record = T::read_field(reader, &format!("calibrationRecords[{index}]"))?;
             calibration_records.push(workshop_card_calibration_record);
         }
         Ok(Self { calibration_total_number, calibration_pointer_newest_record, calibration_records })
     }
 }
+
+impl<T: Writable> Writable for WorkshopCardCalibrationData<T> {
+    /// Writes a `WorkshopCardCalibrationData` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u16::<BigEndian>(self.calibration_total_number)?;
+        writer.write_u8(self.calibration_pointer_newest_record)?;
+        for record in self.calibration_records.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
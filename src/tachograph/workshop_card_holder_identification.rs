@@ -1,15 +1,17 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, bytes_to_ia5_fix_string,
+    Readable, ReadableField, SpanRecorder, Writable, bytes_to_ia5_fix_string,
     tacho::{Address, HolderName, Name},
+    write_ia5_string,
 };
 
 const CARD_HOLDER_PREFERRED_LANGUAGE_LENGTH: u32 = 2;
 
 /// Information, stored in a workshop card, related to the identification of
 /// the cardholder (Annex 1C requirement 311 and 334).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct WorkshopCardHolderIdentification {
     #[serde(rename = "workshopName")]
     pub workshop_name: Name,
@@ -23,12 +25,24 @@ pub struct WorkshopCardHolderIdentification {
 
 impl Readable<WorkshopCardHolderIdentification> for WorkshopCardHolderIdentification {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<WorkshopCardHolderIdentification> {
-        let workshop_name = Name::read(reader)?;
-        let workshop_address = Address::read(reader)?;
-        let card_holder_name = HolderName::read(reader)?;
-        let card_holder_preferred_language =
-            bytes_to_ia5_fix_string(&reader.read_into_vec(CARD_HOLDER_PREFERRED_LANGUAGE_LENGTH)?)?;
+        let workshop_name = Name::read_field(reader, "workshopName")?;
+        let workshop_address = Address::read_field(reader, "calibrationTotalNumber")?;
+        let card_holder_name = HolderName::read_field(reader, "cardHolderName")?;
+        let card_holder_preferred_language = SpanRecorder::field("cardHolderPreferredLanguage", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(CARD_HOLDER_PREFERRED_LANGUAGE_LENGTH)?)?)
+        })?;
 
         Ok(Self { workshop_name, workshop_address, card_holder_name, card_holder_preferred_language })
     }
 }
+
+impl Writable for WorkshopCardHolderIdentification {
+    /// Writes a `WorkshopCardHolderIdentification` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.workshop_name.write(writer)?;
+        self.workshop_address.write(writer)?;
+        self.card_holder_name.write(writer)?;
+        write_ia5_string(writer, &self.card_holder_preferred_language, CARD_HOLDER_PREFERRED_LANGUAGE_LENGTH as usize)?;
+        Ok(())
+    }
+}
@@ -1,8 +1,11 @@
 use crate::Result;
+use crate::helpers::from_json_value;
+use crate::tacho::{self, TachographDataGeneration, TachographDataType, TachographHeader};
 use crate::{Export, gen1, gen2};
 use std::fmt;
 
-use serde::Serialize;
+use serde::{Deserialize, Deserializer, Serialize, de};
+use serde_json::Value;
 
 /// # Top-Level Tachograph Data Container
 /// Represents the parsed data from a tachograph file.
@@ -13,6 +16,7 @@ use serde::Serialize;
 /// It abstracts away the generation and data type (Vehicle Unit or Card),
 /// allowing for unified handling of the parsed result.
 #[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum TachographData {
     /// Vehicle Unit data from a Gen1 tachograph.
@@ -25,6 +29,48 @@ pub enum TachographData {
     CardGen2(gen2::CardData),
 }
 
+impl TachographData {
+    /// Returns the header with the data type and the generation of the data.
+    pub fn get_header(&self) -> &TachographHeader {
+        match self {
+            TachographData::VUGen1(data) => <dyn tacho::VUData<gen1::VUTransferResponseParameterData>>::get_header(data),
+            TachographData::VUGen2(data) => <dyn tacho::VUData<gen2::VUTransferResponseParameterData>>::get_header(data),
+            TachographData::CardGen1(data) => &data.header,
+            TachographData::CardGen2(data) => &data.header,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TachographData {
+    /// Deserializes the data of the data type and generation of its header, the variants
+    /// are serialized untagged and are not distinguishable by their fields alone.
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        let header: TachographHeader =
+            from_json_value(value.get("header").cloned().ok_or_else(|| de::Error::missing_field("header"))?)?;
+        match (header.data_type, header.generation) {
+            (TachographDataType::VU, TachographDataGeneration::FirstGeneration) => {
+                Ok(TachographData::VUGen1(from_json_value(value)?))
+            }
+            (TachographDataType::VU, TachographDataGeneration::SecondGeneration) => {
+                Ok(TachographData::VUGen2(from_json_value(value)?))
+            }
+            (TachographDataType::Card, TachographDataGeneration::FirstGeneration) => {
+                Ok(TachographData::CardGen1(from_json_value(value)?))
+            }
+            (TachographDataType::Card, TachographDataGeneration::SecondGeneration) => {
+                Ok(TachographData::CardGen2(from_json_value(value)?))
+            }
+            (data_type, generation) => {
+                Err(de::Error::custom(format!("unsupported tachograph data {data_type:?} {generation:?}")))
+            }
+        }
+    }
+}
+
 impl Export for TachographData {
     fn to_json(&self) -> Result<String>
     where
@@ -1,12 +1,13 @@
 use binary_data::{BinSeek, ReadBytes};
 use log::{debug, trace};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::gen1::{CardResponseParameterData, CompanyCard, ControlCard, DriverCard, WorkshopCard};
 use crate::tacho::{self, CardFilesDataByCardGeneration, CardGeneration, CardParser, EquipmentType, TachographHeader};
 use crate::{Error, Export, Result};
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardData {
     pub header: TachographHeader,
     #[serde(rename = "cardDataResponses")]
@@ -25,9 +26,18 @@ impl CardData {
         Ok(Self { header, card_data_responses })
     }
 
+    /// Creates the card data from already read card files.
+    pub fn from_card_files(header: TachographHeader, card_data_files: &CardFilesDataByCardGeneration) -> Result<CardData> {
+        let card_data_responses = CardData::parse_card(card_data_files)?;
+
+        trace!("CardData::from_card_files - Header: {header:?}, Note: {card_data_responses:?}");
+
+        Ok(Self { header, card_data_responses })
+    }
+
     fn parse_card(card_data_files_by_gen: &CardFilesDataByCardGeneration) -> Result<CardResponseParameterData> {
         let generation = card_data_files_by_gen.get_card_generation();
-        if generation == CardGeneration::Gen2 {
+        if generation == CardGeneration::Gen2 || generation == CardGeneration::Gen2v2 {
             return Err(Error::InvalidDataGeneration);
         }
 
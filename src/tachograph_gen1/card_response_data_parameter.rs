@@ -1,6 +1,13 @@
-use serde::{Serialize, ser::Serializer};
+use serde::{Deserialize, Deserializer, Serialize, de, ser::Serializer};
+use serde_json::Value;
 
-use crate::gen1::{CompanyCard, ControlCard, DriverCard, WorkshopCard};
+use crate::{
+    gen1::{CompanyCard, ControlCard, DriverCard, WorkshopCard},
+    helpers::{card_type_of_json_value, from_json_value},
+    tacho::EquipmentType,
+};
+
+const UNSUPPORTED: &str = "Unsupported";
 
 #[derive(Debug)]
 pub enum CardResponseParameterData {
@@ -21,7 +28,51 @@ impl Serialize for CardResponseParameterData {
             CardResponseParameterData::CompanyCard(data) => data.serialize(serializer),
             CardResponseParameterData::WorkshopCard(data) => data.serialize(serializer),
             CardResponseParameterData::ControlCard(data) => data.serialize(serializer),
-            CardResponseParameterData::Unsupported => serializer.serialize_str("Unsupported"),
+            CardResponseParameterData::Unsupported => serializer.serialize_str(UNSUPPORTED),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for CardResponseParameterData {
+    /// Deserializes the card of the type stored in its application identification, the
+    /// cards of the different types are not distinguishable by their fields alone.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        if value.as_str() == Some(UNSUPPORTED) {
+            return Ok(CardResponseParameterData::Unsupported);
+        }
+        match card_type_of_json_value(&value) {
+            Some(EquipmentType::DriverCard) => Ok(CardResponseParameterData::DriverCard(from_json_value(value)?)),
+            Some(EquipmentType::CompanyCard) => Ok(CardResponseParameterData::CompanyCard(from_json_value(value)?)),
+            Some(EquipmentType::WorkshopCard) => Ok(CardResponseParameterData::WorkshopCard(from_json_value(value)?)),
+            Some(EquipmentType::ControlCard) => Ok(CardResponseParameterData::ControlCard(from_json_value(value)?)),
+            _ => Err(de::Error::custom("unknown card type of the card data")),
         }
     }
 }
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for CardResponseParameterData {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "CardResponseParameterData".into()
+    }
+
+    fn schema_id() -> std::borrow::Cow<'static, str> {
+        concat!(module_path!(), "::CardResponseParameterData").into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "anyOf": [
+                { "const": UNSUPPORTED },
+                generator.subschema_for::<DriverCard>(),
+                generator.subschema_for::<CompanyCard>(),
+                generator.subschema_for::<WorkshopCard>(),
+                generator.subschema_for::<ControlCard>(),
+            ]
+        })
+    }
+}
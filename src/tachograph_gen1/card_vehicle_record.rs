@@ -1,15 +1,17 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    BCDString, Readable,
+    BCDString, Readable, ReadableField, SpanRecorder, Writable,
     tacho::{OdometerShort, TimeReal, VehicleRegistrationIdentification, VehicleUse},
+    write_bcd_string,
 };
 
 const VU_DATA_BLOCK_COUNTER_LENGTH: u32 = 2;
 
 /// Information, stored in a driver or workshop card, related to a period of
 /// use of a vehicle during a calendar day (Annex 1C requirements 269, 294, 322, and 345).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardVehicleRecord {
     #[serde(rename = "vehicleOdometerBegin")]
     pub vehicle_odometer_begin: OdometerShort,
@@ -27,12 +29,14 @@ pub struct CardVehicleRecord {
 
 impl Readable<CardVehicleRecord> for CardVehicleRecord {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<CardVehicleRecord> {
-        let vehicle_odometer_begin = OdometerShort::read(reader)?;
-        let vehicle_odometer_end = OdometerShort::read(reader)?;
-        let vehicle_first_use = TimeReal::read(reader)?;
-        let vehicle_last_use = TimeReal::read(reader)?;
-        let vehicle_registration = VehicleRegistrationIdentification::read(reader)?;
-        let vu_data_block_counter = BCDString::decode(&reader.read_into_vec(VU_DATA_BLOCK_COUNTER_LENGTH)?)?;
+        let vehicle_odometer_begin = OdometerShort::read_field(reader, "vehicleOdometerBegin")?;
+        let vehicle_odometer_end = OdometerShort::read_field(reader, "vehicleOdometerEnd")?;
+        let vehicle_first_use = TimeReal::read_field(reader, "vehicleFirstUse")?;
+        let vehicle_last_use = TimeReal::read_field(reader, "vehicleLastUse")?;
+        let vehicle_registration = VehicleRegistrationIdentification::read_field(reader, "vehicleRegistration")?;
+        let vu_data_block_counter = SpanRecorder::field("vuDataBlockCounter", "BCDString", reader, |reader| {
+            BCDString::decode(&reader.read_into_vec(VU_DATA_BLOCK_COUNTER_LENGTH)?)
+        })?;
 
         Ok(Self {
             vehicle_odometer_begin,
@@ -54,3 +58,16 @@ impl VehicleUse for CardVehicleRecord {
         &self.vehicle_last_use
     }
 }
+
+impl Writable for CardVehicleRecord {
+    /// Writes a `CardVehicleRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.vehicle_odometer_begin.write(writer)?;
+        self.vehicle_odometer_end.write(writer)?;
+        self.vehicle_first_use.write(writer)?;
+        self.vehicle_last_use.write(writer)?;
+        self.vehicle_registration.write(writer)?;
+        write_bcd_string(writer, &self.vu_data_block_counter, VU_DATA_BLOCK_COUNTER_LENGTH as usize)?;
+        Ok(())
+    }
+}
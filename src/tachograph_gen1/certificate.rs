@@ -1,12 +1,13 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{CodePage, HexDisplay, Readable, bytes_to_string, tacho::NationNumeric};
+use crate::{CodePage, HexDisplay, Readable, SpanRecorder, Writable, bytes_to_string, tacho::NationNumeric, write_octet_string};
 
 const SIGNATURE_LENGTH: u32 = 128;
 const PUBLIC_KEY_REMAINDER_LENGTH: u32 = 58;
 const CERTIFICATION_AUTHORITY_REFERENCE_LENGTH: u32 = 8;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ParsedCertificationAuthorityReference {
     #[serde(rename = "nationNumericCode")]
     pub nation_numeric_code: u8,
@@ -23,7 +24,8 @@ pub struct ParsedCertificationAuthorityReference {
 }
 
 /// The certificate of a public key issued by a Certification Authority.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Certificate {
     pub signature: Vec<u8>,
     #[serde(rename = "publicKeyRemainder")]
@@ -57,12 +59,28 @@ impl Certificate {
 impl Readable<Certificate> for Certificate {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<Certificate> {
         // Size = 194
-        let signature = reader.read_into_vec(SIGNATURE_LENGTH)?;
-        let public_key_remainder = reader.read_into_vec(PUBLIC_KEY_REMAINDER_LENGTH)?;
+        let signature =
+            SpanRecorder::field("signature", "OCTET STRING", reader, |reader| Ok(reader.read_into_vec(SIGNATURE_LENGTH)?))?;
+        let public_key_remainder = SpanRecorder::field("publicKeyRemainder", "OCTET STRING", reader, |reader| {
+            Ok(reader.read_into_vec(PUBLIC_KEY_REMAINDER_LENGTH)?)
+        })?;
         // https://dtc.jrc.ec.europa.eu/dtc_public_key_certificates_dt.php.html
-        let certification_authority_reference = reader.read_into_vec(CERTIFICATION_AUTHORITY_REFERENCE_LENGTH)?;
+        let certification_authority_reference =
+            SpanRecorder::field("certificationAuthorityReference", "OCTET STRING", reader, |reader| {
+                Ok(reader.read_into_vec(CERTIFICATION_AUTHORITY_REFERENCE_LENGTH)?)
+            })?;
         let parsed_ca_reference = Certificate::parse_certification_authority_reference(&certification_authority_reference);
 
         Ok(Self { signature, public_key_remainder, certification_authority_reference, parsed_ca_reference })
     }
 }
+
+impl Writable for Certificate {
+    /// Writes a `Certificate` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        write_octet_string(writer, &self.signature, SIGNATURE_LENGTH as usize)?;
+        write_octet_string(writer, &self.public_key_remainder, PUBLIC_KEY_REMAINDER_LENGTH as usize)?;
+        write_octet_string(writer, &self.certification_authority_reference, CERTIFICATION_AUTHORITY_REFERENCE_LENGTH as usize)?;
+        Ok(())
+    }
+}
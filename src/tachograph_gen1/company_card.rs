@@ -2,18 +2,19 @@ use std::collections::HashMap;
 
 use binary_data::BinSeek;
 use log::{debug, trace};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::gen1::{CardResponseParameterData, Certificate};
 use crate::tacho::{
-    Card, CardChipIdentification, CardFileData, CardFileID, CardGeneration, CardIccIdentification, CardParser,
+    Card, CardChipIdentification, CardFileData, CardFileID, CardGeneration, CardIccIdentification, CardParser, CardWriter,
     CompanyActivityData, CompanyActivityDataParams, CompanyActivityRecord, CompanyCardApplicationIdentification, DataFiles,
-    Identification, IdentificationParams,
+    Identification, IdentificationParams, write_card_file,
 };
-use crate::{Readable, ReadableWithParams, Result};
+use crate::{Diagnostic, ReadableField, ReadableWithParamsField, Result, Writable, write_to_vec};
 
 /// Company card application generation 1
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CompanyCard {
     #[serde(rename = "cardGeneration")]
     pub card_generation: CardGeneration,
@@ -32,6 +33,9 @@ pub struct CompanyCard {
     pub ca_certificate: Option<Certificate>,
     #[serde(rename = "cardNotes")]
     pub card_notes: String,
+    /// Errors of the card files (EF) which could not be parsed in lenient mode.
+    #[serde(rename = "efErrors")]
+    pub ef_errors: HashMap<CardFileID, Diagnostic>,
     #[serde(rename = "dataFiles")]
     pub data_files: HashMap<CardFileID, CardFileData>,
 }
@@ -54,9 +58,45 @@ impl CompanyCard {
             card_certificate: None,
             ca_certificate: None,
             card_notes,
+            ef_errors: HashMap::new(),
             data_files,
         }
     }
+
+    fn parse_card_file(
+        &mut self,
+        card_file_id: &CardFileID,
+        card_file: &CardFileData,
+        application_identification: &CompanyCardApplicationIdentification,
+    ) -> Result<()> {
+        card_file.read_data_with(|reader| {
+            debug!(
+                "CompanyCard::parse - ID: {:?}, Data Length: {:?}, Has Signature: {}",
+                card_file_id,
+                reader.len()?,
+                card_file.signature.is_some()
+            );
+            match card_file_id {
+                CardFileID::Identification => {
+                    let params = IdentificationParams::new(application_identification.type_of_tachograph_card_id.clone());
+                    self.identification = Some(Identification::read_field_with(reader, &params, "identification")?);
+                }
+                CardFileID::CompanyActivityData => {
+                    let params = CompanyActivityDataParams::new(application_identification.no_of_company_activity_records);
+                    self.company_activity_data =
+                        Some(CompanyActivityData::read_field_with(reader, &params, "companyActivityData")?);
+                }
+                CardFileID::CardCertificate => {
+                    self.card_certificate = Some(Certificate::read_field(reader, "cardCertificate")?);
+                }
+                CardFileID::CACertificate => {
+                    self.ca_certificate = Some(Certificate::read_field(reader, "caCertificate")?);
+                }
+                _ => trace!("CompanyCard::parse - Not Parsed: {:?}", card_file_id),
+            }
+            Ok(())
+        })
+    }
 }
 
 impl CardParser<CompanyCard> for CompanyCard {
@@ -77,30 +117,8 @@ impl CardParser<CompanyCard> for CompanyCard {
 
         for card_item in card_data_files.iter() {
             debug!("CompanyCard::parse - ID: {:?}", card_item.0,);
-            let card_file = card_item.1;
-            let mut reader = card_file.data_into_reader()?;
-            debug!(
-                "CompanyCard::parse - ID: {:?}, Data Length: {:?}, Has Signature: {}",
-                card_item.0,
-                reader.len()?,
-                card_file.signature.is_some()
-            );
-            match card_item.0 {
-                CardFileID::Identification => {
-                    let params = IdentificationParams::new(application_identification.type_of_tachograph_card_id.clone());
-                    company_card.identification = Some(Identification::read(&mut reader, &params)?);
-                }
-                CardFileID::CompanyActivityData => {
-                    let params = CompanyActivityDataParams::new(application_identification.no_of_company_activity_records);
-                    company_card.company_activity_data = Some(CompanyActivityData::read(&mut reader, &params)?);
-                }
-                CardFileID::CardCertificate => {
-                    company_card.card_certificate = Some(Certificate::read(&mut reader)?);
-                }
-                CardFileID::CACertificate => {
-                    company_card.ca_certificate = Some(Certificate::read(&mut reader)?);
-                }
-                _ => trace!("CompanyCard::parse - Not Parsed: {:?}", card_item.0),
+            if let Err(error) = company_card.parse_card_file(card_item.0, card_item.1, &application_identification) {
+                <dyn Card<CardResponseParameterData>>::add_card_file_error(&mut company_card.ef_errors, card_item.0, error)?;
             }
         }
 
@@ -113,3 +131,19 @@ impl DataFiles for CompanyCard {
         &self.data_files
     }
 }
+
+impl CardWriter for CompanyCard {
+    fn write_card_file(&self, card_file_id: &CardFileID) -> Result<Option<Vec<u8>>> {
+        let application_identification = &self.application_identification;
+        match card_file_id {
+            CardFileID::IC => write_to_vec(|writer| self.card_chip_identification.write(writer)).map(Some),
+            CardFileID::ICC => write_to_vec(|writer| self.card_icc_identification.write(writer)).map(Some),
+            CardFileID::ApplicationIdentification => write_to_vec(|writer| application_identification.write(writer)).map(Some),
+            CardFileID::Identification => write_card_file(&self.identification),
+            CardFileID::CompanyActivityData => write_card_file(&self.company_activity_data),
+            CardFileID::CardCertificate => write_card_file(&self.card_certificate),
+            CardFileID::CACertificate => write_card_file(&self.ca_certificate),
+            _ => Ok(None),
+        }
+    }
+}
@@ -2,18 +2,19 @@ use std::collections::HashMap;
 
 use binary_data::BinSeek;
 use log::{debug, trace};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::gen1::{CardResponseParameterData, Certificate};
 use crate::tacho::{
-    Card, CardChipIdentification, CardFileData, CardFileID, CardGeneration, CardIccIdentification, CardParser,
+    Card, CardChipIdentification, CardFileData, CardFileID, CardGeneration, CardIccIdentification, CardParser, CardWriter,
     ControlCardActivityRecord, ControlCardApplicationIdentification, ControlCardControlActivityData,
-    ControlCardControlActivityDataParams, DataFiles, Identification, IdentificationParams,
+    ControlCardControlActivityDataParams, DataFiles, Identification, IdentificationParams, write_card_file,
 };
-use crate::{Readable, ReadableWithParams, Result};
+use crate::{Diagnostic, ReadableField, ReadableWithParamsField, Result, Writable, write_to_vec};
 
 /// Control Card application generation 1
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ControlCard {
     #[serde(rename = "cardGeneration")]
     pub card_generation: CardGeneration,
@@ -32,6 +33,9 @@ pub struct ControlCard {
     pub ca_certificate: Option<Certificate>,
     #[serde(rename = "cardNotes")]
     pub card_notes: String,
+    /// Errors of the card files (EF) which could not be parsed in lenient mode.
+    #[serde(rename = "efErrors")]
+    pub ef_errors: HashMap<CardFileID, Diagnostic>,
     #[serde(rename = "dataFiles")]
     pub data_files: HashMap<CardFileID, CardFileData>,
 }
@@ -54,9 +58,49 @@ impl ControlCard {
             card_certificate: None,
             ca_certificate: None,
             card_notes,
+            ef_errors: HashMap::new(),
             data_files,
         }
     }
+
+    fn parse_card_file(
+        &mut self,
+        card_file_id: &CardFileID,
+        card_file: &CardFileData,
+        application_identification: &ControlCardApplicationIdentification,
+    ) -> Result<()> {
+        card_file.read_data_with(|reader| {
+            debug!(
+                "ControlCard::parse - ID: {:?}, Data Length: {:?}, Has Signature: {}",
+                card_file_id,
+                reader.len()?,
+                card_file.signature.is_some()
+            );
+            match card_file_id {
+                CardFileID::Identification => {
+                    let params = IdentificationParams::new(application_identification.type_of_tachograph_card_id.clone());
+                    self.identification = Some(Identification::read_field_with(reader, &params, "identification")?);
+                }
+                CardFileID::ControllerActivityData => {
+                    let params =
+                        ControlCardControlActivityDataParams::new(application_identification.no_of_control_activity_records);
+                    self.controller_activity_data =
+                        Some(ControlCardControlActivityData::read_field_with(reader, &params, "controllerActivityData")?);
+                }
+                CardFileID::CardCertificate => {
+                    self.card_certificate = Some(Certificate::read_field(reader, "cardCertificate")?);
+                }
+                CardFileID::CACertificate => {
+                    self.ca_certificate = Some(Certificate::read_field(reader, "caCertificate")?);
+                }
+                CardFileID::IC | CardFileID::ICC | CardFileID::ApplicationIdentification => {
+                    trace!("ControlCard::parse - Already parsed: {:?}", card_file_id)
+                }
+                _ => trace!("ControlCard::parse - Not Parsed: {:?}", card_file_id),
+            }
+            Ok(())
+        })
+    }
 }
 
 impl CardParser<ControlCard> for ControlCard {
@@ -77,34 +121,8 @@ impl CardParser<ControlCard> for ControlCard {
 
         for card_item in card_data_files.iter() {
             debug!("ControlCard::parse - ID: {:?}", card_item.0,);
-            let card_file = card_item.1;
-            let mut reader = card_file.data_into_reader()?;
-            debug!(
-                "ControlCard::parse - ID: {:?}, Data Length: {:?}, Has Signature: {}",
-                card_item.0,
-                reader.len()?,
-                card_file.signature.is_some()
-            );
-            match card_item.0 {
-                CardFileID::Identification => {
-                    let params = IdentificationParams::new(application_identification.type_of_tachograph_card_id.clone());
-                    control_card.identification = Some(Identification::read(&mut reader, &params)?);
-                }
-                CardFileID::ControllerActivityData => {
-                    let params =
-                        ControlCardControlActivityDataParams::new(application_identification.no_of_control_activity_records);
-                    control_card.controller_activity_data = Some(ControlCardControlActivityData::read(&mut reader, &params)?);
-                }
-                CardFileID::CardCertificate => {
-                    control_card.card_certificate = Some(Certificate::read(&mut reader)?);
-                }
-                CardFileID::CACertificate => {
-                    control_card.ca_certificate = Some(Certificate::read(&mut reader)?);
-                }
-                CardFileID::IC | CardFileID::ICC | CardFileID::ApplicationIdentification => {
-                    trace!("ControlCard::parse - Already parsed: {:?}", card_item.0)
-                }
-                _ => trace!("ControlCard::parse - Not Parsed: {:?}", card_item.0),
+            if let Err(error) = control_card.parse_card_file(card_item.0, card_item.1, &application_identification) {
+                <dyn Card<CardResponseParameterData>>::add_card_file_error(&mut control_card.ef_errors, card_item.0, error)?;
             }
         }
 
@@ -117,3 +135,19 @@ impl DataFiles for ControlCard {
         &self.data_files
     }
 }
+
+impl CardWriter for ControlCard {
+    fn write_card_file(&self, card_file_id: &CardFileID) -> Result<Option<Vec<u8>>> {
+        let application_identification = &self.application_identification;
+        match card_file_id {
+            CardFileID::IC => write_to_vec(|writer| self.card_chip_identification.write(writer)).map(Some),
+            CardFileID::ICC => write_to_vec(|writer| self.card_icc_identification.write(writer)).map(Some),
+            CardFileID::ApplicationIdentification => write_to_vec(|writer| application_identification.write(writer)).map(Some),
+            CardFileID::Identification => write_card_file(&self.identification),
+            CardFileID::ControllerActivityData => write_card_file(&self.controller_activity_data),
+            CardFileID::CardCertificate => write_card_file(&self.card_certificate),
+            CardFileID::CACertificate => write_card_file(&self.ca_certificate),
+            _ => Ok(None),
+        }
+    }
+}
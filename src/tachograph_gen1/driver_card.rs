@@ -1,10 +1,10 @@
 use binary_data::BinSeek;
 use log::{debug, trace};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::{
-    Readable, ReadableWithParams, Result,
+    Diagnostic, ReadableField, ReadableWithParamsField, Result, Writable,
     gen1::{
         CardResponseParameterData, CardVehicleRecord, Certificate, DriverCardApplicationIdentification, PlaceRecord,
         SpecificConditions, SpecificConditionsParams,
@@ -13,13 +13,15 @@ use crate::{
         Card, CardChipIdentification, CardControlActivityDataRecord, CardCurrentUse, CardDriverActivity,
         CardDriverActivityParams, CardDrivingLicenceInformation, CardEventData, CardEventDataParams, CardFaultData,
         CardFaultDataParams, CardFileData, CardFileID, CardGeneration, CardIccIdentification, CardParser,
-        CardPlaceDailyWorkPeriod, CardPlaceDailyWorkPeriodParams, CardVehiclesUsed, DataFiles, Identification,
-        IdentificationParams, TimeReal, VehiclesUsedParams,
+        CardPlaceDailyWorkPeriod, CardPlaceDailyWorkPeriodParams, CardVehiclesUsed, CardWriter, DataFiles, Identification,
+        IdentificationParams, TimeReal, VehiclesUsedParams, write_card_file, write_card_file_with_params,
     },
+    write_to_vec,
 };
 
 /// Driver Card application generation 1
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DriverCard {
     #[serde(rename = "cardGeneration")]
     pub card_generation: CardGeneration,
@@ -55,6 +57,9 @@ pub struct DriverCard {
     pub ca_certificate: Option<Certificate>,
     #[serde(rename = "cardNotes")]
     pub card_notes: String,
+    /// Errors of the card files (EF) which could not be parsed in lenient mode.
+    #[serde(rename = "efErrors")]
+    pub ef_errors: HashMap<CardFileID, Diagnostic>,
     #[serde(rename = "dataFiles")]
     pub data_files: HashMap<CardFileID, CardFileData>,
 }
@@ -86,109 +91,124 @@ impl DriverCard {
             card_certificate: None,
             ca_certificate: None,
             card_notes,
+            ef_errors: HashMap::new(),
             data_files,
         }
     }
-}
-
-impl CardParser<DriverCard> for DriverCard {
-    fn parse(card_data_files: &HashMap<CardFileID, CardFileData>, card_notes: &str) -> Result<Box<DriverCard>> {
-        let card_chip_identification = <dyn Card<CardResponseParameterData>>::parse_ic(card_data_files)?;
-        let card_icc_identification = <dyn Card<CardResponseParameterData>>::parse_icc(card_data_files)?;
-        let application_identification = <dyn Card<CardResponseParameterData>>::parse_card_application_identification::<
-            DriverCardApplicationIdentification,
-        >(card_data_files)?;
-        trace!("DriverCard::parse - Application Identification: {application_identification:?}");
-
-        let mut driver_card = DriverCard::new(
-            card_chip_identification,
-            card_icc_identification,
-            application_identification.clone(),
-            card_notes.to_owned(),
-            (*card_data_files).clone(),
-        );
 
-        for card_item in card_data_files.iter() {
-            debug!("DriverCard::parse - ID: {:?}", card_item.0);
-            let card_file = card_item.1;
-            let mut reader = card_file.data_into_reader()?;
+    fn parse_card_file(
+        &mut self,
+        card_file_id: &CardFileID,
+        card_file: &CardFileData,
+        application_identification: &DriverCardApplicationIdentification,
+    ) -> Result<()> {
+        card_file.read_data_with(|reader| {
             debug!(
                 "DriverCard::parse - ID: {:?}, Data Length: {:?}, Has Signature: {}",
-                card_item.0,
+                card_file_id,
                 reader.len()?,
                 card_file.signature.is_some()
             );
-            match card_item.0 {
+            match card_file_id {
                 CardFileID::CardDownload => {
-                    driver_card.card_download = Some(TimeReal::read(&mut reader)?);
+                    self.card_download = Some(TimeReal::read_field(reader, "cardDownload")?);
                 }
                 CardFileID::EventsData => {
                     debug!(
                         "DriverCard::parse - ID: {:?}, Number Of Records: {:?}",
-                        card_item.0, application_identification.no_events_per_type,
+                        card_file_id, application_identification.no_events_per_type,
                     );
                     let params = CardEventDataParams::new(6, application_identification.no_events_per_type);
-                    driver_card.events_data = Some(CardEventData::read(&mut reader, &params)?);
+                    self.events_data = Some(CardEventData::read_field_with(reader, &params, "eventsData")?);
                 }
                 CardFileID::FaultsData => {
                     debug!(
                         "DriverCard::parse - ID: {:?}, Number Of Records: {:?}",
-                        card_item.0, application_identification.no_faults_per_type,
+                        card_file_id, application_identification.no_faults_per_type,
                     );
                     let params = CardFaultDataParams::new(application_identification.no_faults_per_type);
-                    driver_card.faults_data = Some(CardFaultData::read(&mut reader, &params)?);
+                    self.faults_data = Some(CardFaultData::read_field_with(reader, &params, "faultsData")?);
                 }
                 CardFileID::DriverActivityData => {
                     debug!(
                         "DriverCard::parse - ID: {:?}, Number Of Records: {:?}",
-                        card_item.0, application_identification.activity_structure_length,
+                        card_file_id, application_identification.activity_structure_length,
                     );
                     let params = CardDriverActivityParams::new(application_identification.activity_structure_length);
-                    driver_card.driver_activity_data = Some(CardDriverActivity::read(&mut reader, &params)?);
+                    self.driver_activity_data = Some(CardDriverActivity::read_field_with(reader, &params, "driverActivityData")?);
                 }
                 CardFileID::VehiclesUsed => {
                     debug!(
                         "DriverCard::parse - ID: {:?}, Number Of Records: {:?}",
-                        card_item.0, application_identification.no_of_card_vehicle_records,
+                        card_file_id, application_identification.no_of_card_vehicle_records,
                     );
                     let params = VehiclesUsedParams::new(application_identification.no_of_card_vehicle_records);
-                    driver_card.vehicles_used = Some(CardVehiclesUsed::<CardVehicleRecord>::read(&mut reader, &params)?);
+                    self.vehicles_used =
+                        Some(CardVehiclesUsed::<CardVehicleRecord>::read_field_with(reader, &params, "vehiclesUsed")?);
                 }
                 CardFileID::Places => {
                     debug!(
                         "DriverCard::parse - ID: {:?}, Number Of Records: {:?}",
-                        card_item.0, application_identification.no_of_card_place_records,
+                        card_file_id, application_identification.no_of_card_place_records,
                     );
                     let params = CardPlaceDailyWorkPeriodParams::new(application_identification.no_of_card_place_records, 1);
-                    driver_card.places = Some(CardPlaceDailyWorkPeriod::<PlaceRecord>::read(&mut reader, &params)?);
+                    self.places = Some(CardPlaceDailyWorkPeriod::<PlaceRecord>::read_field_with(reader, &params, "places")?);
                 }
                 CardFileID::CurrentUsage => {
-                    driver_card.current_usage = Some(CardCurrentUse::read(&mut reader)?);
+                    self.current_usage = Some(CardCurrentUse::read_field(reader, "currentUsage")?);
                 }
                 CardFileID::ControlActivityData => {
-                    driver_card.control_activity_data = Some(CardControlActivityDataRecord::read(&mut reader)?);
+                    self.control_activity_data = Some(CardControlActivityDataRecord::read_field(reader, "controlActivityData")?);
                 }
                 CardFileID::Identification => {
                     let params = IdentificationParams::new(application_identification.type_of_tachograph_card_id.clone());
-                    driver_card.identification = Some(Identification::read(&mut reader, &params)?);
+                    self.identification = Some(Identification::read_field_with(reader, &params, "identification")?);
                 }
                 CardFileID::DrivingLicenseInfo => {
-                    driver_card.driving_license_info = Some(CardDrivingLicenceInformation::read(&mut reader)?);
+                    self.driving_license_info =
+                        Some(CardDrivingLicenceInformation::read_field(reader, "drivingLicenceInformation")?);
                 }
                 CardFileID::SpecificConditions => {
                     let params = SpecificConditionsParams::new(56);
-                    driver_card.specific_conditions = Some(SpecificConditions::read(&mut reader, &params)?);
+                    self.specific_conditions = Some(SpecificConditions::read_field_with(reader, &params, "specificConditions")?);
                 }
                 CardFileID::CardCertificate => {
-                    driver_card.card_certificate = Some(Certificate::read(&mut reader)?);
+                    self.card_certificate = Some(Certificate::read_field(reader, "cardCertificate")?);
                 }
                 CardFileID::CACertificate => {
-                    driver_card.ca_certificate = Some(Certificate::read(&mut reader)?);
+                    self.ca_certificate = Some(Certificate::read_field(reader, "caCertificate")?);
                 }
                 CardFileID::IC | CardFileID::ICC | CardFileID::ApplicationIdentification => {
-                    trace!("DriverCard::parse - Already parsed: {:?}", card_item.0)
+                    trace!("DriverCard::parse - Already parsed: {:?}", card_file_id)
                 }
-                _ => trace!("DriverCard::parse - Not Parsed: {:?}", card_item.0),
+                _ => trace!("DriverCard::parse - Not Parsed: {:?}", card_file_id),
+            }
+            Ok(())
+        })
+    }
+}
+
+impl CardParser<DriverCard> for DriverCard {
+    fn parse(card_data_files: &HashMap<CardFileID, CardFileData>, card_notes: &str) -> Result<Box<DriverCard>> {
+        let card_chip_identification = <dyn Card<CardResponseParameterData>>::parse_ic(card_data_files)?;
+        let card_icc_identification = <dyn Card<CardResponseParameterData>>::parse_icc(card_data_files)?;
+        let application_identification = <dyn Card<CardResponseParameterData>>::parse_card_application_identification::<
+            DriverCardApplicationIdentification,
+        >(card_data_files)?;
+        trace!("DriverCard::parse - Application Identification: {application_identification:?}");
+
+        let mut driver_card = DriverCard::new(
+            card_chip_identification,
+            card_icc_identification,
+            application_identification.clone(),
+            card_notes.to_owned(),
+            (*card_data_files).clone(),
+        );
+
+        for card_item in card_data_files.iter() {
+            debug!("DriverCard::parse - ID: {:?}", card_item.0);
+            if let Err(error) = driver_card.parse_card_file(card_item.0, card_item.1, &application_identification) {
+                <dyn Card<CardResponseParameterData>>::add_card_file_error(&mut driver_card.ef_errors, card_item.0, error)?;
             }
         }
 
@@ -201,3 +221,40 @@ impl DataFiles for DriverCard {
         &self.data_files
     }
 }
+
+impl CardWriter for DriverCard {
+    fn write_card_file(&self, card_file_id: &CardFileID) -> Result<Option<Vec<u8>>> {
+        let application_identification = &self.application_identification;
+        match card_file_id {
+            CardFileID::IC => write_to_vec(|writer| self.card_chip_identification.write(writer)).map(Some),
+            CardFileID::ICC => write_to_vec(|writer| self.card_icc_identification.write(writer)).map(Some),
+            CardFileID::ApplicationIdentification => write_to_vec(|writer| application_identification.write(writer)).map(Some),
+            CardFileID::CardDownload => write_card_file(&self.card_download),
+            CardFileID::EventsData => {
+                let params = CardEventDataParams::new(6, application_identification.no_events_per_type);
+                write_card_file_with_params(&self.events_data, &params)
+            }
+            CardFileID::FaultsData => {
+                let params = CardFaultDataParams::new(application_identification.no_faults_per_type);
+                write_card_file_with_params(&self.faults_data, &params)
+            }
+            CardFileID::DriverActivityData => {
+                let params = CardDriverActivityParams::new(application_identification.activity_structure_length);
+                write_card_file_with_params(&self.driver_activity_data, &params)
+            }
+            CardFileID::VehiclesUsed => write_card_file(&self.vehicles_used),
+            CardFileID::Places => {
+                let params = CardPlaceDailyWorkPeriodParams::new(application_identification.no_of_card_place_records, 1);
+                write_card_file_with_params(&self.places, &params)
+            }
+            CardFileID::CurrentUsage => write_card_file(&self.current_usage),
+            CardFileID::ControlActivityData => write_card_file(&self.control_activity_data),
+            CardFileID::Identification => write_card_file(&self.identification),
+            CardFileID::DrivingLicenseInfo => write_card_file(&self.driving_license_info),
+            CardFileID::SpecificConditions => write_card_file(&self.specific_conditions),
+            CardFileID::CardCertificate => write_card_file(&self.card_certificate),
+            CardFileID::CACertificate => write_card_file(&self.ca_certificate),
+            _ => Ok(None),
+        }
+    }
+}
@@ -1,14 +1,15 @@
 use binary_data::BigEndian;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable,
+    Readable, ReadableField, SpanRecorder, Writable, field_value,
     tacho::{CardStructureVersion, EquipmentType},
 };
 
 /// Information, stored in a driver card related to the identification of the
 /// application of the card (Annex 1C requirement 253 and 278).
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DriverCardApplicationIdentification {
     #[serde(rename = "typeOfTachographCardId")]
     pub type_of_tachograph_card_id: EquipmentType,
@@ -30,13 +31,23 @@ impl Readable<DriverCardApplicationIdentification> for DriverCardApplicationIden
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(
         reader: &mut R,
     ) -> crate::Result<DriverCardApplicationIdentification> {
-        let type_of_tachograph_card_id = reader.read_u8()?.into();
-        let card_structure_version = CardStructureVersion::read(reader)?;
-        let no_events_per_type = reader.read_u8()?;
-        let no_faults_per_type = reader.read_u8()?;
-        let activity_structure_length = reader.read_u16::<BigEndian>()? as u32;
-        let no_of_card_vehicle_records = reader.read_u16::<BigEndian>()? as u32;
-        let no_of_card_place_records = reader.read_u8()? as u32;
+        let type_of_tachograph_card_id: EquipmentType =
+            SpanRecorder::field("typeOfTachographCardId", "EquipmentType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let card_structure_version = CardStructureVersion::read_field(reader, "cardStructureVersion")?;
+        let no_events_per_type = SpanRecorder::field("noOfEventsPerType", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let no_faults_per_type = SpanRecorder::field("noOfFaultsPerType", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let activity_structure_length = SpanRecorder::field("activityStructureLength", "INTEGER", reader, |reader| {
+            Ok(reader.read_u16::<BigEndian>()? as u32)
+        })?;
+        let no_of_card_vehicle_records =
+            SpanRecorder::field(
+                "noOfCardVehicleRecords",
+                "INTEGER",
+                reader,
+                |reader| Ok(reader.read_u16::<BigEndian>()? as u32),
+            )?;
+        let no_of_card_place_records =
+            SpanRecorder::field("noOfCardPlaceRecords", "INTEGER", reader, |reader| Ok(reader.read_u8()? as u32))?;
 
         Ok(Self {
             type_of_tachograph_card_id,
@@ -49,3 +60,17 @@ impl Readable<DriverCardApplicationIdentification> for DriverCardApplicationIden
         })
     }
 }
+
+impl Writable for DriverCardApplicationIdentification {
+    /// Writes a `DriverCardApplicationIdentification` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8((&self.type_of_tachograph_card_id).into())?;
+        self.card_structure_version.write(writer)?;
+        writer.write_u8(self.no_events_per_type)?;
+        writer.write_u8(self.no_faults_per_type)?;
+        writer.write_u16::<BigEndian>(field_value("activityStructureLength", self.activity_structure_length)?)?;
+        writer.write_u16::<BigEndian>(field_value("noOfCardVehicleRecords", self.no_of_card_vehicle_records)?)?;
+        writer.write_u8(field_value("noOfCardPlaceRecords", self.no_of_card_place_records)?)?;
+        Ok(())
+    }
+}
@@ -19,6 +19,7 @@ mod verification;
 mod vu_activity;
 mod vu_activity_daily_data;
 mod vu_calibration_data;
+mod vu_card_download;
 mod vu_card_iw_data;
 mod vu_company_locks_data;
 mod vu_control_activity;
@@ -55,10 +56,12 @@ pub use place_record::PlaceRecord;
 pub use previous_vehicle_info::PreviousVehicleInfo;
 pub use sensor_paired::SensorPaired;
 pub use specific_conditions::{SpecificConditions, SpecificConditionsParams};
-pub use verification::verify;
+pub use verification::{verify, verify_vu};
+pub(crate) use verification::{verify_vu_with_roots, verify_with_roots};
 pub use vu_activity::VUActivity;
 pub use vu_activity_daily_data::VuActivityDailyData;
 pub use vu_calibration_data::VUCalibrationData;
+pub use vu_card_download::VUCardDownload;
 pub use vu_card_iw_data::{VuCardIWData, VuCardIWRecord};
 pub use vu_company_locks_data::{VuCompanyLocksData, VuCompanyLocksRecord};
 pub use vu_control_activity::VuControlActivity;
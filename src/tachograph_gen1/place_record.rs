@@ -1,13 +1,14 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable,
+    Readable, ReadableField, SpanRecorder, Writable,
     tacho::{CardPlace, EntryTypeDailyWorkPeriod, NationNumeric, OdometerShort, RegionNumeric, TimeReal},
 };
 
 /// Information related to a place where a daily work period begins or ends
 /// (Annex 1C requirements 108, 271, 296, 324, and 347).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PlaceRecord {
     #[serde(rename = "entryTime")]
     pub entry_time: TimeReal,
@@ -23,11 +24,16 @@ pub struct PlaceRecord {
 
 impl Readable<PlaceRecord> for PlaceRecord {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<PlaceRecord> {
-        let entry_time = TimeReal::read(reader)?;
-        let entry_type_daily_work_period: EntryTypeDailyWorkPeriod = reader.read_u8()?.into();
-        let daily_work_period_country: NationNumeric = reader.read_u8()?.into();
-        let daily_work_period_region: RegionNumeric = reader.read_u8()?.into();
-        let vehicle_odometer_value = OdometerShort::read(reader)?;
+        let entry_time = TimeReal::read_field(reader, "entryTime")?;
+        let entry_type_daily_work_period: EntryTypeDailyWorkPeriod =
+            SpanRecorder::field("entryTypeDailyWorkPeriod", "EntryTypeDailyWorkPeriod", reader, |reader| {
+                Ok(reader.read_u8()?.into())
+            })?;
+        let daily_work_period_country: NationNumeric =
+            SpanRecorder::field("dailyWorkPeriodCountry", "NationNumeric", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let daily_work_period_region: RegionNumeric =
+            SpanRecorder::field("dailyWorkPeriodRegion", "RegionNumeric", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let vehicle_odometer_value = OdometerShort::read_field(reader, "vehicleOdometerValue")?;
 
         Ok(Self {
             entry_time,
@@ -44,3 +50,15 @@ impl CardPlace for PlaceRecord {
         &self.entry_time
     }
 }
+
+impl Writable for PlaceRecord {
+    /// Writes a `PlaceRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.entry_time.write(writer)?;
+        writer.write_u8((&self.entry_type_daily_work_period).into())?;
+        writer.write_u8((&self.daily_work_period_country).into())?;
+        writer.write_u8((&self.daily_work_period_region).into())?;
+        self.vehicle_odometer_value.write(writer)?;
+        Ok(())
+    }
+}
@@ -1,13 +1,14 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable,
+    Readable, ReadableField, Writable,
     tacho::{TimeReal, VehicleRegistrationIdentification},
 };
 
 /// Information related to the vehicle previously used by a driver when
 /// inserting his card in a vehicle unit (Annex 1B requirement 081 and Annex 1C requirement 102).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PreviousVehicleInfo {
     #[serde(rename = "vehicleRegistrationIdentification")]
     pub vehicle_registration_identification: VehicleRegistrationIdentification,
@@ -17,8 +18,18 @@ pub struct PreviousVehicleInfo {
 
 impl Readable<PreviousVehicleInfo> for PreviousVehicleInfo {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<PreviousVehicleInfo> {
-        let vehicle_registration_identification = VehicleRegistrationIdentification::read(reader)?;
-        let card_withdrawal_time = TimeReal::read(reader)?;
+        let vehicle_registration_identification =
+            VehicleRegistrationIdentification::read_field(reader, "vehicleRegistrationIdentification")?;
+        let card_withdrawal_time = TimeReal::read_field(reader, "cardWithdrawalTime")?;
         Ok(Self { vehicle_registration_identification, card_withdrawal_time })
     }
 }
+
+impl Writable for PreviousVehicleInfo {
+    /// Writes a `PreviousVehicleInfo` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.vehicle_registration_identification.write(writer)?;
+        self.card_withdrawal_time.write(writer)?;
+        Ok(())
+    }
+}
@@ -1,15 +1,17 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, bytes_to_ia5_fix_string,
+    Readable, ReadableField, SpanRecorder, Writable, bytes_to_ia5_fix_string,
     tacho::{ExtendedSerialNumber, TimeReal},
+    write_ia5_string,
 };
 
 const SENSOR_APPROVAL_NUMBER_LENGTH: u32 = 8;
 
 /// Information, stored in a vehicle unit, related to the identification of the
 /// motion sensor paired with the vehicle unit (Annex 1B requirement 079).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SensorPaired {
     #[serde(rename = "sensorSerialNumber")]
     pub sensor_serial_number: ExtendedSerialNumber,
@@ -21,10 +23,21 @@ pub struct SensorPaired {
 
 impl Readable<SensorPaired> for SensorPaired {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<SensorPaired> {
-        let sensor_serial_number = ExtendedSerialNumber::read(reader)?;
-        let sensor_approval_number = bytes_to_ia5_fix_string(&reader.read_into_vec(SENSOR_APPROVAL_NUMBER_LENGTH)?)?;
-        let sensor_pairing_date_first = TimeReal::read(reader)?;
+        let sensor_serial_number = ExtendedSerialNumber::read_field(reader, "sensorSerialNumber")?;
+        let sensor_approval_number = SpanRecorder::field("sensorApprovalNumber", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(SENSOR_APPROVAL_NUMBER_LENGTH)?)?)
+        })?;
+        let sensor_pairing_date_first = TimeReal::read_field(reader, "sensorPairingDateFirst")?;
 
         Ok(Self { sensor_serial_number, sensor_approval_number, sensor_pairing_date_first })
     }
 }
+
+impl Writable for SensorPaired {
+    /// Writes a `SensorPaired` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.sensor_serial_number.write(writer)?;
+        write_ia5_string(writer, &self.sensor_approval_number, SENSOR_APPROVAL_NUMBER_LENGTH as usize)?;
+        self.sensor_pairing_date_first.write(writer)
+    }
+}
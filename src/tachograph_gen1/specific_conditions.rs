@@ -1,7 +1,7 @@
 use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, ReadableWithParams, Result, tacho::SpecificConditionRecord};
+use crate::{ReadableField, ReadableWithParams, Result, Writable, tacho::SpecificConditionRecord};
 
 #[derive(Debug)]
 pub struct SpecificConditionsParams {
@@ -16,7 +16,8 @@ impl SpecificConditionsParams {
 
 /// Information, stored in a driver card, a workshop card or a vehicle unit,
 /// related to a specific condition (Annex 1C requirement 131, 277, 302, 329, and 356).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SpecificConditions {
     #[serde(rename = "specificConditionRecords")]
     pub specific_condition_records: Vec<SpecificConditionRecord>,
@@ -28,8 +29,9 @@ impl ReadableWithParams<SpecificConditions> for SpecificConditions {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<SpecificConditions> {
         let no_of_records = params.no_of_records;
         let mut specific_condition_records: Vec<SpecificConditionRecord> = Vec::new();
-        for _ in 0..no_of_records {
-            let specific_condition_record = SpecificConditionRecord::read(reader)?;
+        for index in 0..no_of_records {
+            let specific_condition_record =
+                SpecificConditionRecord::read_field(reader, &format!("specificConditionRecords[{index}]"))?;
             if specific_condition_record.entry_time.has_data() {
                 specific_condition_records.push(specific_condition_record);
             }
@@ -37,3 +39,14 @@ impl ReadableWithParams<SpecificConditions> for SpecificConditions {
         Ok(Self { specific_condition_records })
     }
 }
+
+impl Writable for SpecificConditions {
+    /// Writes the kept specific condition records, the card writer pads the card file with
+    /// the empty records.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        for record in self.specific_condition_records.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
@@ -5,8 +5,12 @@ use sha1::{Digest, Sha1};
 
 use crate::{
     Error, Readable, Result,
+    gen1::{VUData, VUTransferResponseParameterData},
     helpers::get_sub_array,
-    tacho::{CardFileData, CardFileID, CardFilesMap, TimeReal, VerifyItem, VerifyResult, VerifyResultStatus, VerifyStatus},
+    tacho::{
+        self, CardFileData, CardFileID, CardFilesMap, TimeReal, VUTransferResponseParameterItem, VerifyItem, VerifyResult,
+        VerifyResultStatus, VerifyStatus, VuVerifyItem, VuVerifyResult,
+    },
 };
 
 const SIG_SIZE: usize = 128;
@@ -16,6 +20,13 @@ const CR_SIZE: usize = 106;
 const HASH_SIZE: usize = 20;
 const RSA_KEY_SIZE: usize = 136;
 const DECRYPTED_CERT_SIZE: usize = 164;
+const CERT_SIZE: usize = 194;
+/// A member state certificate of the MSCA cache is the certificate followed by its
+/// recovered content.
+const CACHED_CERT_SIZE: usize = CERT_SIZE + DECRYPTED_CERT_SIZE;
+/// The signature of the VU overview is over the data after the member state and the VU
+/// certificates.
+const VU_OVERVIEW_SIGNED_DATA_START: usize = 2 * CERT_SIZE;
 
 const DATA_PATTERN: [u8; 15] = [48, 33, 48, 9, 6, 5, 43, 14, 3, 2, 26, 5, 0, 4, 20];
 const SIGNATURE_PADDING: [u8; 90] = [0xFF; 90];
@@ -66,7 +77,7 @@ struct Certificate {
 }
 
 impl Certificate {
-    fn from_bytes(data: &[u8; 194]) -> Result<Self> {
+    fn from_bytes(data: &[u8; CERT_SIZE]) -> Result<Self> {
         let signature = data[0..SIG_SIZE]
             .try_into()
             .map_err(|_| Error::VerifyError("Could not get signature from Certificate".to_string()))?;
@@ -99,6 +110,7 @@ impl Certificate {
 
 #[derive(Debug)]
 struct DecryptedCertificate {
+    pub data: [u8; DECRYPTED_CERT_SIZE],
     pub end_of_validity: TimeReal,
     pub holder_reference: [u8; CAR_SIZE],
     pub rsa_public_key: RsaPublicKey,
@@ -116,17 +128,18 @@ impl DecryptedCertificate {
             .map_err(|_| Error::VerifyError("Could not get rsa public key from DecryptedCertificate".to_string()))?;
         let rsa_public_key = RsaPublicKey::new(rsa_public_key_data);
 
-        Ok(Self { end_of_validity, holder_reference, rsa_public_key })
+        Ok(Self { data, end_of_validity, holder_reference, rsa_public_key })
     }
 }
 
 fn create_certificate_from(card_file_data: &CardFileData) -> Result<Certificate> {
     let signature = card_file_data.data.as_ref().ok_or_else(|| Error::VerifyError("Missing Certificate Data.".to_string()))?;
+    create_certificate_from_bytes(signature)
+}
 
-    let signature_array: &[u8; 194] = signature
-        .as_slice()
-        .try_into()
-        .map_err(|_| Error::VerifyError("Invalid signature length in Certificate.".to_string()))?;
+fn create_certificate_from_bytes(data: &[u8]) -> Result<Certificate> {
+    let signature_array: &[u8; CERT_SIZE] =
+        data.try_into().map_err(|_| Error::VerifyError("Invalid signature length in Certificate.".to_string()))?;
 
     Certificate::from_bytes(signature_array)
 }
@@ -171,6 +184,21 @@ fn decrypt_card_certificate(certificate: &Certificate, ca_certificate: &Decrypte
     certificate.decrypt(&cr, &h)
 }
 
+/// Returns true when `signature` is the RSA signature of the SHA-1 hash of `data`, with
+/// the PKCS #1 v1.5 padding of the signatures of Appendix 11.
+fn verify_signature(rsa_public_key: &RsaPublicKey, signature: &[u8; SIG_SIZE], data: &[u8]) -> bool {
+    let perf_ret = rsa_public_key.perform(signature);
+
+    let mut hasher = Sha1::new();
+    hasher.update(data);
+    let hash: [u8; HASH_SIZE] = hasher.finalize().into();
+
+    perf_ret.len() == 127
+        && hash.as_slice() == get_sub_array(&perf_ret, 107, 20)
+        && get_sub_array(&perf_ret, 92, 15) == DATA_PATTERN
+        && get_sub_array(&perf_ret, 1, 90) == SIGNATURE_PADDING
+}
+
 fn verify_data(data_files: &CardFilesMap, card_certificate: &DecryptedCertificate) -> Result<Vec<VerifyItem>> {
     let mut result: Vec<VerifyItem> = Vec::new();
     for data_file in data_files.iter() {
@@ -194,17 +222,7 @@ fn verify_data(data_files: &CardFilesMap, card_certificate: &DecryptedCertificat
         let raw_data = data.data.as_ref().unwrap();
         match data.signature.as_ref().unwrap()[0..SIG_SIZE].try_into() {
             Ok(signature) => {
-                let perf_ret = card_certificate.rsa_public_key.perform(&signature);
-
-                let mut hasher = Sha1::new();
-                hasher.update(raw_data);
-                let hash: [u8; HASH_SIZE] = hasher.finalize().into();
-
-                if perf_ret.len() == 127
-                    && hash.as_slice() == get_sub_array(&perf_ret, 107, 20)
-                    && get_sub_array(&perf_ret, 92, 15) == DATA_PATTERN
-                    && get_sub_array(&perf_ret, 1, 90) == SIGNATURE_PADDING
-                {
+                if verify_signature(&card_certificate.rsa_public_key, &signature, raw_data) {
                     result.push(VerifyItem { card_file_id: id.clone(), status: VerifyStatus::Valid, end_of_validity: None });
                     continue;
                 }
@@ -224,17 +242,67 @@ fn verify_data(data_files: &CardFilesMap, card_certificate: &DecryptedCertificat
     Ok(result)
 }
 
+/// Returns the ERCA public key of `erca_pks` with the holder reference `authority_reference`,
+/// the certification authority reference of a member state certificate.
+fn find_erca_public_key(erca_pks: &[[u8; 144]], authority_reference: &[u8; CAR_SIZE]) -> Result<ECPKCertificate> {
+    let erca_pk = erca_pks.iter().find(|erca_pk| erca_pk.starts_with(authority_reference)).ok_or_else(|| {
+        Error::VerifyError(format!("Missing ERCA Public Key of the certification authority reference {authority_reference:02X?}"))
+    })?;
+    ECPKCertificate::new(erca_pk)
+}
+
+/// Returns the member state certificate `certificate_data` recovered with the ERCA public
+/// key of `erca_pks` which issued it, or with `member_state_certificates`, the authenticated
+/// certificates of the MSCA cache verified before, when no key of `erca_pks` issued it. The certificate is also returned
+/// as it is cached, followed by its recovered content.
+fn decrypt_member_state_certificate(
+    certificate_data: &[u8],
+    erca_pks: &[[u8; 144]],
+    member_state_certificates: &[Vec<u8>],
+) -> Result<(DecryptedCertificate, Vec<u8>)> {
+    let certificate = create_certificate_from_bytes(certificate_data)?;
+    let decrypted = match find_erca_public_key(erca_pks, &certificate.certification_authority_reference) {
+        Ok(ec_pk_certificate) => decrypt_ca_certificate(&certificate, &ec_pk_certificate)?,
+        Err(error) => {
+            let cached = member_state_certificates
+                .iter()
+                .find(|cached| cached.len() == CACHED_CERT_SIZE && cached.starts_with(certificate_data))
+                .ok_or(error)?;
+            let decrypted_cert_data: [u8; DECRYPTED_CERT_SIZE] = cached[CERT_SIZE..]
+                .try_into()
+                .map_err(|_| Error::VerifyError("Could not get cached member state certificate".to_string()))?;
+            DecryptedCertificate::new(decrypted_cert_data)?
+        }
+    };
+    let cached = [certificate_data, &decrypted.data].concat();
+    Ok((decrypted, cached))
+}
+
+/// Verifies the certificates of a Gen1 card with the ERCA public key `erca_pk` and then
+/// the signatures of the card files with the key of the card certificate.
 pub fn verify(data_files: &CardFilesMap, erca_pk: &[u8; 144]) -> Result<VerifyResult> {
+    verify_with_roots(data_files, std::slice::from_ref(erca_pk), &[]).map(|(result, _)| result)
+}
+
+/// Verifies a Gen1 card as `verify`, with the ERCA public key of `erca_pks` which issued
+/// the CA certificate of the card, or with the cached `member_state_certificates`. The
+/// CA certificate is returned with the result, as it is cached.
+pub(crate) fn verify_with_roots(
+    data_files: &CardFilesMap,
+    erca_pks: &[[u8; 144]],
+    member_state_certificates: &[Vec<u8>],
+) -> Result<(VerifyResult, Vec<u8>)> {
     let ca_cert_file =
         data_files.get(&CardFileID::CACertificate).ok_or(Error::VerifyError("Missing CA Certificate.".to_string()))?;
     let card_cert_file =
         data_files.get(&CardFileID::CardCertificate).ok_or(Error::VerifyError("Missing Card Certificate.".to_string()))?;
 
-    let ec_pk_certificate = ECPKCertificate::new(erca_pk)?;
-    let ca_certificate = create_certificate_from(ca_cert_file)?;
+    let ca_certificate_data =
+        ca_cert_file.data.as_ref().ok_or_else(|| Error::VerifyError("Missing Certificate Data.".to_string()))?;
     let card_certificate = create_certificate_from(card_cert_file)?;
 
-    let ca_decrypted = decrypt_ca_certificate(&ca_certificate, &ec_pk_certificate)?;
+    let (ca_decrypted, cached_ca_certificate) =
+        decrypt_member_state_certificate(ca_certificate_data, erca_pks, member_state_certificates)?;
     debug!("CA Decrypted: {:?}", ca_decrypted);
     let card_decrypted = decrypt_card_certificate(&card_certificate, &ca_decrypted)?;
     debug!("Card Decrypted: {:?}", card_decrypted);
@@ -254,5 +322,79 @@ pub fn verify(data_files: &CardFilesMap, erca_pk: &[u8; 144]) -> Result<VerifyRe
     let verifed_data = verify_data(data_files, &card_decrypted)?;
     result.extend(verifed_data);
 
-    Ok(VerifyResult { status: VerifyResultStatus::Valid, result })
+    Ok((VerifyResult { status: VerifyResultStatus::Valid, result }, cached_ca_certificate))
+}
+
+/// Returns the status of the signature of a TREP, the signature is over the data of the
+/// TREP before the signature, without the certificates of the overview.
+fn verify_trep(
+    item: &VUTransferResponseParameterItem<VUTransferResponseParameterData>,
+    vu_certificate: &DecryptedCertificate,
+) -> Result<VerifyStatus> {
+    let (signature, signed_data_start) = match &item.data {
+        VUTransferResponseParameterData::Control(data) => (&data.signature, VU_OVERVIEW_SIGNED_DATA_START),
+        VUTransferResponseParameterData::Activity(data) => (&data.signature, 0),
+        VUTransferResponseParameterData::Events(data) => (&data.signature, 0),
+        VUTransferResponseParameterData::Speed(data) => (&data.signature, 0),
+        VUTransferResponseParameterData::Calibration(data) => (&data.signature, 0),
+        _ => return Ok(VerifyStatus::NotHaveSignature),
+    };
+    let Some(signature) = signature else {
+        return Ok(VerifyStatus::NotHaveSignature);
+    };
+    let Ok(signature) = <&[u8; SIG_SIZE]>::try_from(signature.as_slice()) else {
+        return Ok(VerifyStatus::InvalidSignatureSize);
+    };
+
+    let raw_data = item.get_raw_data()?;
+    let Some(signed_data) = raw_data.get(signed_data_start..raw_data.len().saturating_sub(SIG_SIZE)) else {
+        return Ok(VerifyStatus::NotHaveData);
+    };
+    if verify_signature(&vu_certificate.rsa_public_key, signature, signed_data) {
+        return Ok(VerifyStatus::Valid);
+    }
+    Ok(VerifyStatus::Invalid)
+}
+
+/// Verifies the member state and the VU certificates of the overview of a Gen1 VU
+/// download with the ERCA public key `erca_pk` and then the signature of each TREP with
+/// the key of the VU certificate.
+pub fn verify_vu(vu_data: &VUData, erca_pk: &[u8; 144]) -> Result<VuVerifyResult> {
+    verify_vu_with_roots(vu_data, std::slice::from_ref(erca_pk), &[]).map(|(result, _)| result)
+}
+
+/// Verifies a Gen1 VU download as `verify_vu`, with the ERCA public key of `erca_pks`
+/// which issued the member state certificate of the overview, or with the cached
+/// `member_state_certificates`. The member state certificate is returned with the
+/// result, as it is cached.
+pub(crate) fn verify_vu_with_roots(
+    vu_data: &VUData,
+    erca_pks: &[[u8; 144]],
+    member_state_certificates: &[Vec<u8>],
+) -> Result<(VuVerifyResult, Vec<u8>)> {
+    let treps = <dyn tacho::VUData<VUTransferResponseParameterData>>::get_data(vu_data);
+    let overview = treps
+        .iter()
+        .find_map(|item| match &item.data {
+            VUTransferResponseParameterData::Control(overview) => Some(overview),
+            _ => None,
+        })
+        .ok_or(Error::VerifyError("Missing VU Overview.".to_string()))?;
+
+    let vu_certificate = create_certificate_from_bytes(&overview.vu_certificate)?;
+
+    let (member_state_decrypted, cached_member_state_certificate) =
+        decrypt_member_state_certificate(&overview.member_state_certificate, erca_pks, member_state_certificates)?;
+    debug!("Member State Decrypted: {:?}", member_state_decrypted);
+    let vu_decrypted = decrypt_card_certificate(&vu_certificate, &member_state_decrypted)?;
+    debug!("VU Decrypted: {:?}", vu_decrypted);
+
+    let mut result = Vec::with_capacity(treps.len());
+    for item in treps.iter() {
+        let status = verify_trep(item, &vu_decrypted)?;
+        result.push(VuVerifyItem { trep_id: item.type_id.clone(), position: item.position, status });
+    }
+
+    let result = VuVerifyResult::new(Some(member_state_decrypted.end_of_validity), Some(vu_decrypted.end_of_validity), result);
+    Ok((result, cached_member_state_certificate))
 }
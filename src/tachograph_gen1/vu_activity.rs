@@ -1,14 +1,15 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::gen1::{VuActivityDailyData, VuCardIWData, VuPlaceDailyWorkPeriodData, VuSpecificConditionData};
 use crate::tacho::{OdometerShort, TimeReal, VUTransferResponseParameterID, VUTransferResponseParameterReader};
-use crate::{Readable, Result};
+use crate::{ReadableField, Result, SpanRecorder, Writable, write_octet_string};
 
 const SIGNATURE_LENGTH: u32 = 128;
 
 /// Data structure generation 1 (TREP 02 Hex)
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VUActivity {
     #[serde(rename = "dateOfDayDownloaded")]
     pub date_of_day_downloaded: TimeReal,
@@ -27,13 +28,14 @@ pub struct VUActivity {
 
 impl VUTransferResponseParameterReader<VUActivity> for VUActivity {
     fn from_data<R: ReadBytes + BinSeek>(_trep_id: VUTransferResponseParameterID, reader: &mut R) -> Result<VUActivity> {
-        let date_of_day_downloaded = TimeReal::read(reader)?;
-        let odometer_value_midnight = OdometerShort::read(reader)?;
-        let vu_card_iw_data = VuCardIWData::read(reader)?;
-        let vu_activity_daily_data = VuActivityDailyData::read(reader)?;
-        let vu_place_daily_work_period_data = VuPlaceDailyWorkPeriodData::read(reader)?;
-        let vu_specific_condition_data = VuSpecificConditionData::read(reader)?;
-        let signature: Option<Vec<u8>> = Some(reader.read_into_vec(SIGNATURE_LENGTH)?);
+        let date_of_day_downloaded = TimeReal::read_field(reader, "dateOfDayDownloaded")?;
+        let odometer_value_midnight = OdometerShort::read_field(reader, "odometerValueMidnight")?;
+        let vu_card_iw_data = VuCardIWData::read_field(reader, "vuCardIWData")?;
+        let vu_activity_daily_data = VuActivityDailyData::read_field(reader, "vuActivityDailyData")?;
+        let vu_place_daily_work_period_data = VuPlaceDailyWorkPeriodData::read_field(reader, "vuPlaceDailyWorkPeriodData")?;
+        let vu_specific_condition_data = VuSpecificConditionData::read_field(reader, "vuSpecificConditionData")?;
+        let signature: Option<Vec<u8>> =
+            SpanRecorder::field("signature", "OCTET STRING", reader, |reader| Ok(Some(reader.read_into_vec(SIGNATURE_LENGTH)?)))?;
 
         Ok(Self {
             date_of_day_downloaded,
@@ -46,3 +48,16 @@ impl VUTransferResponseParameterReader<VUActivity> for VUActivity {
         })
     }
 }
+
+impl Writable for VUActivity {
+    /// Writes the data of a `VUActivity` TREP to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.date_of_day_downloaded.write(writer)?;
+        self.odometer_value_midnight.write(writer)?;
+        self.vu_card_iw_data.write(writer)?;
+        self.vu_activity_daily_data.write(writer)?;
+        self.vu_place_daily_work_period_data.write(writer)?;
+        self.vu_specific_condition_data.write(writer)?;
+        write_octet_string(writer, self.signature.as_deref().unwrap_or_default(), SIGNATURE_LENGTH as usize)
+    }
+}
@@ -1,8 +1,8 @@
 use binary_data::BigEndian;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, ReadableWithParams,
+    Readable, ReadableWithParamsField, SpanRecorder, Writable, record_count,
     tacho::{ActivityCard, ActivityChangeInfo, ActivityChangeInfoParams},
 };
 
@@ -10,7 +10,8 @@ use crate::{
 /// changes of driving status and/or changes of card status for a given
 /// calendar day (Annex 1B requirement 084 and Annex 1C requirement
 /// 105, 106, 107) and to slots status at 00:00 that day.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuActivityDailyData {
     #[serde(rename = "noOfActivityChanges")]
     pub no_of_activity_changes: u16,
@@ -20,13 +21,25 @@ pub struct VuActivityDailyData {
 
 impl Readable<VuActivityDailyData> for VuActivityDailyData {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuActivityDailyData> {
-        let no_of_activity_changes = reader.read_u16::<BigEndian>()?;
+        let no_of_activity_changes =
+            SpanRecorder::field("noOfActivityChanges", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
         let mut activity_change_infos: Vec<ActivityChangeInfo> = Vec::with_capacity(no_of_activity_changes as usize);
         let params = ActivityChangeInfoParams::new(ActivityCard::Vu);
-        for _ in 0..no_of_activity_changes {
-            let item = ActivityChangeInfo::read(reader, &params)?;
+        for index in 0..no_of_activity_changes {
+            let item = ActivityChangeInfo::read_field_with(reader, &params, &format!("activityChangeInfos[{index}]"))?;
             activity_change_infos.push(item);
         }
         Ok(Self { no_of_activity_changes, activity_change_infos })
     }
 }
+
+impl Writable for VuActivityDailyData {
+    /// Writes the number of the activity changes followed by the activity changes.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u16::<BigEndian>(record_count(self.activity_change_infos.len())?)?;
+        for item in self.activity_change_infos.iter() {
+            item.write(writer)?;
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,10 @@
 use binary_data::BigEndian;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, bytes_to_ia5_fix_string,
+    Readable, ReadableField, SpanRecorder, Writable, bytes_to_ia5_fix_string, record_count,
     tacho::{Address, CalibrationPurpose, FullCardNumber, Name, OdometerShort, TimeReal, VehicleRegistrationIdentification},
+    write_ia5_string,
 };
 
 const VEHICLE_IDENTIFICATION_NUMBER_LENGTH: u32 = 17;
@@ -11,7 +12,8 @@ const TYRE_SIZE_LENGTH: u32 = 15;
 
 /// Information, stored in a vehicle unit, related a calibration of the
 /// recording equipment (Annex 1B requirement 098 and Annex 1C requirement 119 and 120).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuCalibrationRecord {
     #[serde(rename = "calibrationPurpose")]
     pub calibration_purpose: CalibrationPurpose,
@@ -51,24 +53,38 @@ pub struct VuCalibrationRecord {
 
 impl Readable<VuCalibrationRecord> for VuCalibrationRecord {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuCalibrationRecord> {
-        let calibration_purpose = reader.read_u8()?.into();
-        let workshop_name = Name::read(reader)?;
-        let workshop_address = Address::read(reader)?;
-        let workshop_card_number = FullCardNumber::read(reader)?;
-        let workshop_card_expiry_date = TimeReal::read(reader)?;
-        let vehicle_identification_number =
-            bytes_to_ia5_fix_string(&reader.read_into_vec(VEHICLE_IDENTIFICATION_NUMBER_LENGTH)?)?;
-        let vehicle_registration_identification = VehicleRegistrationIdentification::read(reader)?;
-        let w_vehicle_characteristic_constant: u16 = reader.read_u16::<BigEndian>()?;
-        let k_constant_of_recording_equipment: u16 = reader.read_u16::<BigEndian>()?;
-        let l_tyre_circumference: u16 = reader.read_u16::<BigEndian>()?;
-        let tyre_size = bytes_to_ia5_fix_string(&reader.read_into_vec(TYRE_SIZE_LENGTH)?)?;
-        let authorised_speed = reader.read_u8()?;
-        let old_odometer_value = OdometerShort::read(reader)?;
-        let new_odometer_value = OdometerShort::read(reader)?;
-        let old_time_value = TimeReal::read(reader)?;
-        let new_time_value = TimeReal::read(reader)?;
-        let next_calibration_date = TimeReal::read(reader)?;
+        let calibration_purpose: CalibrationPurpose =
+            SpanRecorder::field("calibrationPurpose", "CalibrationPurpose", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let workshop_name = Name::read_field(reader, "workshopName")?;
+        let workshop_address = Address::read_field(reader, "workshopAddress")?;
+        let workshop_card_number = FullCardNumber::read_field(reader, "workshopCardNumber")?;
+        let workshop_card_expiry_date = TimeReal::read_field(reader, "workshopCardExpiryDate")?;
+        let vehicle_identification_number = SpanRecorder::field("vehicleIdentificationNumber", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(VEHICLE_IDENTIFICATION_NUMBER_LENGTH)?)?)
+        })?;
+        let vehicle_registration_identification =
+            VehicleRegistrationIdentification::read_field(reader, "vehicleRegistrationIdentification")?;
+        let w_vehicle_characteristic_constant: u16 =
+            SpanRecorder::field("wVehicleCharacteristicConstant", "INTEGER", reader, |reader| {
+                Ok(reader.read_u16::<BigEndian>()?)
+            })?;
+        let k_constant_of_recording_equipment: u16 = SpanRecorder::field(
+            "kConstantOfRecordingEquipment",
+            "INTEGER",
+            reader,
+            |reader| Ok(reader.read_u16::<BigEndian>()?),
+        )?;
+        let l_tyre_circumference: u16 =
+            SpanRecorder::field("lTyreCircumference", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
+        let tyre_size = SpanRecorder::field("tyreSize", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(TYRE_SIZE_LENGTH)?)?)
+        })?;
+        let authorised_speed = SpanRecorder::field("authorisedSpeed", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let old_odometer_value = OdometerShort::read_field(reader, "oldOdometerValue")?;
+        let new_odometer_value = OdometerShort::read_field(reader, "newOdometerValue")?;
+        let old_time_value = TimeReal::read_field(reader, "oldTimeValue")?;
+        let new_time_value = TimeReal::read_field(reader, "newTimeValue")?;
+        let next_calibration_date = TimeReal::read_field(reader, "nextCalibrationDate")?;
 
         Ok(Self {
             calibration_purpose,
@@ -92,7 +108,8 @@ impl Readable<VuCalibrationRecord> for VuCalibrationRecord {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VUCalibrationData {
     pub no_of_vu_calibrations: u8,
     pub calibrations: Vec<VuCalibrationRecord>,
@@ -100,12 +117,48 @@ pub struct VUCalibrationData {
 
 impl Readable<VUCalibrationData> for VUCalibrationData {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VUCalibrationData> {
-        let no_of_vu_calibrations = reader.read_u8()?;
+        let no_of_vu_calibrations =
+            SpanRecorder::field("no_of_vu_calibrations", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
         let mut vu_calibrations: Vec<VuCalibrationRecord> = Vec::new();
-        for _ in 0..no_of_vu_calibrations {
-            vu_calibrations.push(VuCalibrationRecord::read(reader)?);
+        for index in 0..no_of_vu_calibrations {
+            vu_calibrations.push(VuCalibrationRecord::read_field(reader, &format!("calibrations[{index}]"))?);
         }
 
         Ok(Self { no_of_vu_calibrations, calibrations: vu_calibrations })
     }
 }
+
+impl Writable for VuCalibrationRecord {
+    /// Writes a `VuCalibrationRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8((&self.calibration_purpose).into())?;
+        self.workshop_name.write(writer)?;
+        self.workshop_address.write(writer)?;
+        self.workshop_card_number.write(writer)?;
+        self.workshop_card_expiry_date.write(writer)?;
+        write_ia5_string(writer, &self.vehicle_identification_number, VEHICLE_IDENTIFICATION_NUMBER_LENGTH as usize)?;
+        self.vehicle_registration_identification.write(writer)?;
+        writer.write_u16::<BigEndian>(self.w_vehicle_characteristic_constant)?;
+        writer.write_u16::<BigEndian>(self.k_constant_of_recording_equipment)?;
+        writer.write_u16::<BigEndian>(self.l_tyre_circumference)?;
+        write_ia5_string(writer, &self.tyre_size, TYRE_SIZE_LENGTH as usize)?;
+        writer.write_u8(self.authorised_speed)?;
+        self.old_odometer_value.write(writer)?;
+        self.new_odometer_value.write(writer)?;
+        self.old_time_value.write(writer)?;
+        self.new_time_value.write(writer)?;
+        self.next_calibration_date.write(writer)?;
+        Ok(())
+    }
+}
+
+impl Writable for VUCalibrationData {
+    /// Writes the number of the calibrations followed by the records.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8(record_count(self.calibrations.len())?)?;
+        for record in self.calibrations.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use binary_data::{BigEndian, BinMemoryBuffer, BinSeek, ReadBytes, WriteBytes};
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use crate::gen1::CardData;
+use crate::tacho::{TachographDataGeneration, TachographDataType, TachographHeader, VUTransferResponseParameterID};
+use crate::{Result, SpanRecorder, VU_HEADER_MAGIC_NUMBER, Writable, write_card_data_files};
+
+/// Card data downloaded through a vehicle unit generation 1. The card files
+/// follow each other in the same format as in a card download file.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct VUCardDownload {
+    pub card: CardData,
+    /// Card files as downloaded. It is not exported, the card files of `card` keep their
+    /// data and signatures for the verification and the TREP keeps the same bytes in its
+    /// exported raw data.
+    #[serde(skip)]
+    pub data: Vec<u8>,
+}
+
+impl VUCardDownload {
+    pub fn from_data<R: ReadBytes + BinSeek>(trep_id: VUTransferResponseParameterID, reader: &mut R) -> Result<VUCardDownload> {
+        debug!("VUCardDownload::from_data - Trep ID: {trep_id:?}");
+        let start = reader.pos()?;
+        // Card files end with the next TREP or with the end of data, no card file ID starts with the magic number.
+        while reader.pos()? < reader.len()? {
+            let position = reader.pos()?;
+            if reader.read_u8()? == VU_HEADER_MAGIC_NUMBER {
+                reader.seek(position)?;
+                break;
+            }
+            reader.seek(position + 3)?;
+            let size = reader.read_u16::<BigEndian>()? as u32;
+            reader.read_into_vec(size)?;
+        }
+        let end = reader.pos()?;
+        reader.seek(start)?;
+        let data = SpanRecorder::field("data", "OCTET STRING", reader, |reader| Ok(reader.read_into_vec((end - start) as u32)?))?;
+
+        let header = TachographHeader {
+            generation: TachographDataGeneration::FirstGeneration,
+            data_type: TachographDataType::Card,
+            card_in_vu_data: true,
+        };
+        let card = {
+            let _section = SpanRecorder::section("CardDownload".to_owned(), SpanRecorder::absolute(start), data.len());
+            CardData::from_data(header, &mut BinMemoryBuffer::from(data.clone()))?
+        };
+
+        Ok(Self { card, data })
+    }
+}
+
+impl Writable for VUCardDownload {
+    /// Writes the card files of the downloaded card, the TREP header is the VU header of the card data.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        write_card_data_files(writer, &self.card)
+    }
+}
@@ -1,16 +1,18 @@
 use binary_data::BigEndian;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable,
+    Readable, ReadableField, SpanRecorder, Writable,
     gen1::PreviousVehicleInfo,
+    record_count,
     tacho::{CardSlotNumber, FullCardNumber, HolderName, ManualInputFlag, OdometerShort, TimeReal},
 };
 
 /// Information, stored in a vehicle unit, related to an insertion and withdrawal
 /// cycle of a driver card or of a workshop card in the vehicle unit
 /// (Annex 1B requirement 081 and Annex 1C requirement 102).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuCardIWRecord {
     #[serde(rename = "cardHolderName")]
     pub card_holder_name: HolderName,
@@ -36,16 +38,18 @@ pub struct VuCardIWRecord {
 
 impl Readable<VuCardIWRecord> for VuCardIWRecord {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuCardIWRecord> {
-        let card_holder_name = HolderName::read(reader)?;
-        let full_card_number = FullCardNumber::read(reader)?;
-        let card_expiry_date = TimeReal::read(reader)?;
-        let card_insertion_time = TimeReal::read(reader)?;
-        let vehicle_odometer_value_at_insertion = OdometerShort::read(reader)?;
-        let card_slot_number: CardSlotNumber = reader.read_u8()?.into();
-        let card_withdrawal_time = TimeReal::read(reader)?;
-        let vehicle_odometer_value_at_withdrawal = OdometerShort::read(reader)?;
-        let previous_vehicle_info = PreviousVehicleInfo::read(reader)?;
-        let manual_input_flag: ManualInputFlag = reader.read_u8()?.into();
+        let card_holder_name = HolderName::read_field(reader, "cardHolderName")?;
+        let full_card_number = FullCardNumber::read_field(reader, "fullCardNumber")?;
+        let card_expiry_date = TimeReal::read_field(reader, "cardExpiryDate")?;
+        let card_insertion_time = TimeReal::read_field(reader, "cardInsertionTime")?;
+        let vehicle_odometer_value_at_insertion = OdometerShort::read_field(reader, "vehicleOdometerValueAtInsertion")?;
+        let card_slot_number: CardSlotNumber =
+            SpanRecorder::field("cardSlotNumber", "CardSlotNumber", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let card_withdrawal_time = TimeReal::read_field(reader, "cardWithdrawalTime")?;
+        let vehicle_odometer_value_at_withdrawal = OdometerShort::read_field(reader, "vehicleOdometerValueAtWithdrawal")?;
+        let previous_vehicle_info = PreviousVehicleInfo::read_field(reader, "previousVehicleInfo")?;
+        let manual_input_flag: ManualInputFlag =
+            SpanRecorder::field("manualInputFlag", "ManualInputFlag", reader, |reader| Ok(reader.read_u8()?.into()))?;
         Ok(Self {
             card_holder_name,
             full_card_number,
@@ -61,7 +65,8 @@ impl Readable<VuCardIWRecord> for VuCardIWRecord {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuCardIWData {
     pub no_of_iw_records: u16,
     pub vu_card_iw_records: Vec<VuCardIWRecord>,
@@ -69,12 +74,41 @@ pub struct VuCardIWData {
 
 impl Readable<VuCardIWData> for VuCardIWData {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuCardIWData> {
-        let no_of_iw_records = reader.read_u16::<BigEndian>()?;
+        let no_of_iw_records =
+            SpanRecorder::field("no_of_iw_records", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
         let mut vu_card_iw_records: Vec<VuCardIWRecord> = Vec::new();
-        for _ in 0..no_of_iw_records {
-            let record = VuCardIWRecord::read(reader)?;
+        for index in 0..no_of_iw_records {
+            let record = VuCardIWRecord::read_field(reader, &format!("vuCardIWRecords[{index}]"))?;
             vu_card_iw_records.push(record);
         }
         Ok(Self { no_of_iw_records, vu_card_iw_records })
     }
 }
+
+impl Writable for VuCardIWRecord {
+    /// Writes a `VuCardIWRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.card_holder_name.write(writer)?;
+        self.full_card_number.write(writer)?;
+        self.card_expiry_date.write(writer)?;
+        self.card_insertion_time.write(writer)?;
+        self.vehicle_odometer_value_at_insertion.write(writer)?;
+        writer.write_u8((&self.card_slot_number).into())?;
+        self.card_withdrawal_time.write(writer)?;
+        self.vehicle_odometer_value_at_withdrawal.write(writer)?;
+        self.previous_vehicle_info.write(writer)?;
+        writer.write_u8((&self.manual_input_flag).into())?;
+        Ok(())
+    }
+}
+
+impl Writable for VuCardIWData {
+    /// Writes the number of the card insertion and withdrawal records followed by the records.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u16::<BigEndian>(record_count(self.vu_card_iw_records.len())?)?;
+        for record in self.vu_card_iw_records.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
@@ -1,13 +1,14 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable,
+    Readable, ReadableField, SpanRecorder, Writable, record_count,
     tacho::{Address, FullCardNumber, Name, TimeReal},
 };
 
 /// Information, stored in a vehicle unit, related to one company lock
 /// (Annex 1B requirement 104 and Annex 1C requirement 128).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuCompanyLocksRecord {
     #[serde(rename = "lockInTime")]
     pub lock_in_time: TimeReal,
@@ -23,17 +24,18 @@ pub struct VuCompanyLocksRecord {
 
 impl Readable<VuCompanyLocksRecord> for VuCompanyLocksRecord {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuCompanyLocksRecord> {
-        let lock_in_time = TimeReal::read(reader)?;
-        let lock_out_time = TimeReal::read(reader)?;
-        let company_name = Name::read(reader)?;
-        let company_address = Address::read(reader)?;
-        let company_card_number = FullCardNumber::read(reader)?;
+        let lock_in_time = TimeReal::read_field(reader, "lockInTime")?;
+        let lock_out_time = TimeReal::read_field(reader, "lockOutTime")?;
+        let company_name = Name::read_field(reader, "companyName")?;
+        let company_address = Address::read_field(reader, "companyAddress")?;
+        let company_card_number = FullCardNumber::read_field(reader, "companyCardNumber")?;
 
         Ok(Self { lock_in_time, lock_out_time, company_name, company_address, company_card_number })
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuCompanyLocksData {
     pub no_of_locks: u8,
     pub company_locks: Vec<VuCompanyLocksRecord>,
@@ -41,12 +43,35 @@ pub struct VuCompanyLocksData {
 
 impl Readable<VuCompanyLocksData> for VuCompanyLocksData {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuCompanyLocksData> {
-        let no_of_locks = reader.read_u8()?;
+        let no_of_locks = SpanRecorder::field("no_of_locks", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
         let mut company_locks: Vec<VuCompanyLocksRecord> = Vec::new();
-        for _ in 0..no_of_locks {
-            company_locks.push(VuCompanyLocksRecord::read(reader)?);
+        for index in 0..no_of_locks {
+            company_locks.push(VuCompanyLocksRecord::read_field(reader, &format!("company_locks[{index}]"))?);
         }
 
         Ok(Self { no_of_locks, company_locks })
     }
 }
+
+impl Writable for VuCompanyLocksRecord {
+    /// Writes a `VuCompanyLocksRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.lock_in_time.write(writer)?;
+        self.lock_out_time.write(writer)?;
+        self.company_name.write(writer)?;
+        self.company_address.write(writer)?;
+        self.company_card_number.write(writer)?;
+        Ok(())
+    }
+}
+
+impl Writable for VuCompanyLocksData {
+    /// Writes the number of the company locks followed by the records.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8(record_count(self.company_locks.len())?)?;
+        for record in self.company_locks.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
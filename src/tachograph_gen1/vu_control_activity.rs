@@ -1,13 +1,14 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable,
+    Readable, ReadableField, SpanRecorder, Writable, record_count,
     tacho::{ControlType, FullCardNumber, TimeReal},
 };
 
 /// Information, stored in a vehicle unit, related to a control performed using
 /// this VU (Annex 1B requirement 102 and Annex 1C requirement 126).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuControlActivityRecord {
     #[serde(rename = "controlType")]
     pub control_type: ControlType,
@@ -23,13 +24,13 @@ pub struct VuControlActivityRecord {
 
 impl Readable<VuControlActivityRecord> for VuControlActivityRecord {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuControlActivityRecord> {
-        let ct = reader.read_u8()?;
-        let control_type: ControlType = ct.into();
-        let control_time = TimeReal::read(reader)?;
+        let control_type: ControlType =
+            SpanRecorder::field("controlType", "ControlType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let control_time = TimeReal::read_field(reader, "controlTime")?;
 
-        let full_card_number = FullCardNumber::read(reader)?;
-        let download_period_begin_time = TimeReal::read(reader)?;
-        let download_period_end_time = TimeReal::read(reader)?;
+        let full_card_number = FullCardNumber::read_field(reader, "controlCardNumber")?;
+        let download_period_begin_time = TimeReal::read_field(reader, "downloadPeriodBeginTime")?;
+        let download_period_end_time = TimeReal::read_field(reader, "downloadPeriodEndTime")?;
 
         Ok(Self {
             control_type,
@@ -43,7 +44,8 @@ impl Readable<VuControlActivityRecord> for VuControlActivityRecord {
 
 /// Collection of Information, stored in a vehicle unit, related to a control performed using
 /// this VU (Annex 1B requirement 102 and Annex 1C requirement 126).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuControlActivity {
     #[serde(rename = "noOfControls")]
     pub no_of_controls: u8,
@@ -53,12 +55,35 @@ pub struct VuControlActivity {
 
 impl Readable<VuControlActivity> for VuControlActivity {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuControlActivity> {
-        let no_of_controls = reader.read_u8()?;
+        let no_of_controls = SpanRecorder::field("noOfControls", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
         let mut vu_control_activities: Vec<VuControlActivityRecord> = Vec::new();
-        for _ in 0..no_of_controls {
-            vu_control_activities.push(VuControlActivityRecord::read(reader)?);
+        for index in 0..no_of_controls {
+            vu_control_activities.push(VuControlActivityRecord::read_field(reader, &format!("vuControlActivities[{index}]"))?);
         }
 
         Ok(Self { no_of_controls, vu_control_activities })
     }
 }
+
+impl Writable for VuControlActivityRecord {
+    /// Writes a `VuControlActivityRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8((&self.control_type).into())?;
+        self.control_time.write(writer)?;
+        self.control_card_number.write(writer)?;
+        self.download_period_begin_time.write(writer)?;
+        self.download_period_end_time.write(writer)?;
+        Ok(())
+    }
+}
+
+impl Writable for VuControlActivity {
+    /// Writes the number of the controls followed by the records.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8(record_count(self.vu_control_activities.len())?)?;
+        for record in self.vu_control_activities.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
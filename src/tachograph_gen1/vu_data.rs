@@ -1,17 +1,18 @@
 use binary_data::{BinSeek, ReadBytes};
 use log::debug;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     Export, Readable, Result,
-    gen1::{VUActivity, VUTransferResponseParameterData, VuDetailedSpeed, VuEvents, VuOverview, VuTechnicalData},
+    gen1::{VUActivity, VUCardDownload, VUTransferResponseParameterData, VuDetailedSpeed, VuEvents, VuOverview, VuTechnicalData},
     tacho::{
         self, TachographHeader, VUTransferResponseParameterID, VUTransferResponseParameterItem, VUTransferResponseParameterReader,
     },
     tachograph,
 };
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VUData {
     header: TachographHeader,
     #[serde(rename = "transferResParams")]
@@ -28,7 +29,7 @@ impl VUData {
         Ok(VUData { header, transfer_res_params })
     }
 
-    fn parse_trep<R: ReadBytes + BinSeek>(
+    pub(crate) fn parse_trep<R: ReadBytes + BinSeek>(
         trep_id: VUTransferResponseParameterID,
         reader: &mut R,
     ) -> Result<VUTransferResponseParameterData> {
@@ -54,7 +55,10 @@ impl VUData {
                 let vu_calibration = VuTechnicalData::from_data(trep_id, reader)?;
                 Ok(VUTransferResponseParameterData::Calibration(vu_calibration))
             }
-            VUTransferResponseParameterID::CardDownload => Ok(VUTransferResponseParameterData::CardDownload),
+            VUTransferResponseParameterID::CardDownload => {
+                let vu_card_download = VUCardDownload::from_data(trep_id, reader)?;
+                Ok(VUTransferResponseParameterData::CardDownload(vu_card_download))
+            }
             VUTransferResponseParameterID::OddballCrashDump => Ok(VUTransferResponseParameterData::OddballCrashDump),
             _ => Ok(VUTransferResponseParameterData::Unknown),
         }
@@ -69,6 +73,10 @@ impl tachograph::VUData<VUTransferResponseParameterData> for VUData {
     fn get_data(&self) -> &Vec<VUTransferResponseParameterItem<VUTransferResponseParameterData>> {
         &self.transfer_res_params
     }
+
+    fn get_data_mut(&mut self) -> &mut Vec<VUTransferResponseParameterItem<VUTransferResponseParameterData>> {
+        &mut self.transfer_res_params
+    }
 }
 
 impl Export for VUData {}
@@ -1,11 +1,12 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, gen1::VuDetailedSpeedData};
+use crate::{Readable, ReadableField, SpanRecorder, Writable, gen1::VuDetailedSpeedData, write_octet_string};
 
 const SIGNATURE_LENGTH: u32 = 128;
 
 /// Data structure generation 1 (TREP 04 Hex)
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuDetailedSpeed {
     #[serde(rename = "vuDetailedSpeedData")]
     pub vu_detailed_speed_data: VuDetailedSpeedData,
@@ -14,8 +15,17 @@ pub struct VuDetailedSpeed {
 
 impl Readable<VuDetailedSpeed> for VuDetailedSpeed {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuDetailedSpeed> {
-        let vu_detailed_speed_data = VuDetailedSpeedData::read(reader)?;
-        let signature = Some(reader.read_into_vec(SIGNATURE_LENGTH)?);
+        let vu_detailed_speed_data = VuDetailedSpeedData::read_field(reader, "vuDetailedSpeedData")?;
+        let signature =
+            SpanRecorder::field("signature", "OCTET STRING", reader, |reader| Ok(Some(reader.read_into_vec(SIGNATURE_LENGTH)?)))?;
         Ok(Self { vu_detailed_speed_data, signature })
     }
 }
+
+impl Writable for VuDetailedSpeed {
+    /// Writes the data of a `VuDetailedSpeed` TREP to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.vu_detailed_speed_data.write(writer)?;
+        write_octet_string(writer, self.signature.as_deref().unwrap_or_default(), SIGNATURE_LENGTH as usize)
+    }
+}
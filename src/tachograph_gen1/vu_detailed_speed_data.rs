@@ -1,10 +1,11 @@
 use binary_data::BigEndian;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, tacho::VuDetailedSpeedBlock};
+use crate::{Readable, ReadableField, SpanRecorder, Writable, record_count, tacho::VuDetailedSpeedBlock};
 
 /// Information, stored in a vehicle unit, related to the detailed speed of the vehicle.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuDetailedSpeedData {
     #[serde(rename = "noOfSpeedBlocks")]
     pub no_of_speed_blocks: u16,
@@ -14,11 +15,23 @@ pub struct VuDetailedSpeedData {
 
 impl Readable<VuDetailedSpeedData> for VuDetailedSpeedData {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuDetailedSpeedData> {
-        let no_of_speed_blocks = reader.read_u16::<BigEndian>()?;
+        let no_of_speed_blocks =
+            SpanRecorder::field("noOfSpeedBlocks", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
         let mut vu_detailed_speed_blocks: Vec<VuDetailedSpeedBlock> = Vec::new();
-        for _ in 0..no_of_speed_blocks {
-            vu_detailed_speed_blocks.push(VuDetailedSpeedBlock::read(reader)?);
+        for index in 0..no_of_speed_blocks {
+            vu_detailed_speed_blocks.push(VuDetailedSpeedBlock::read_field(reader, &format!("vuDetailedSpeedBlocks[{index}]"))?);
         }
         Ok(Self { no_of_speed_blocks, vu_detailed_speed_blocks })
     }
 }
+
+impl Writable for VuDetailedSpeedData {
+    /// Writes the number of the speed blocks followed by the records.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u16::<BigEndian>(record_count(self.vu_detailed_speed_blocks.len())?)?;
+        for record in self.vu_detailed_speed_blocks.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
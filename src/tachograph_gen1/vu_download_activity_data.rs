@@ -1,13 +1,14 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable,
+    Readable, ReadableField, Writable,
     tacho::{FullCardNumber, Name, TimeReal},
 };
 
 /// Information, stored in a vehicle unit, related to its last download (Annex
 /// 1B requirement 105 and Annex 1C requirement 129).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuDownloadActivityData {
     #[serde(rename = "downloadingTime")]
     pub downloading_time: TimeReal,
@@ -19,10 +20,20 @@ pub struct VuDownloadActivityData {
 
 impl Readable<VuDownloadActivityData> for VuDownloadActivityData {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuDownloadActivityData> {
-        let downloading_time = TimeReal::read(reader)?;
-        let full_card_number = FullCardNumber::read(reader)?;
-        let company_or_workshop_name = Name::read(reader)?;
+        let downloading_time = TimeReal::read_field(reader, "downloadingTime")?;
+        let full_card_number = FullCardNumber::read_field(reader, "fullCardNumber")?;
+        let company_or_workshop_name = Name::read_field(reader, "companyOrWorkshopName")?;
 
         Ok(Self { downloading_time, full_card_number, company_or_workshop_name })
     }
 }
+
+impl Writable for VuDownloadActivityData {
+    /// Writes a `VuDownloadActivityData` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.downloading_time.write(writer)?;
+        self.full_card_number.write(writer)?;
+        self.company_or_workshop_name.write(writer)?;
+        Ok(())
+    }
+}
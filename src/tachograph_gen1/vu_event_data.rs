@@ -1,13 +1,14 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable,
+    Readable, ReadableField, SpanRecorder, Writable, record_count,
     tacho::{EventFaultRecordPurpose, EventFaultType, FullCardNumber, TimeReal},
 };
 
 /// Information, stored in a vehicle unit, related to an event (Annex 1B
 /// requirement 094 and Annex 1C requirement 117 except over speeding event).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuEventRecord {
     #[serde(rename = "eventType")]
     pub event_type: EventFaultType,
@@ -31,15 +32,18 @@ pub struct VuEventRecord {
 
 impl Readable<VuEventRecord> for VuEventRecord {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuEventRecord> {
-        let event_type: EventFaultType = reader.read_u8()?.into();
-        let event_record_purpose: EventFaultRecordPurpose = reader.read_u8()?.into();
-        let event_begin_time = TimeReal::read(reader)?;
-        let event_end_time = TimeReal::read(reader)?;
-        let card_number_driver_slot_begin = FullCardNumber::read(reader)?;
-        let card_number_codriver_slot_begin = FullCardNumber::read(reader)?;
-        let card_number_driver_slot_end = FullCardNumber::read(reader)?;
-        let card_number_codriver_slot_end = FullCardNumber::read(reader)?;
-        let similar_events_number = reader.read_u8()?;
+        let event_type: EventFaultType =
+            SpanRecorder::field("eventType", "EventFaultType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let event_record_purpose: EventFaultRecordPurpose =
+            SpanRecorder::field("eventRecordPurpose", "EventFaultRecordPurpose", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let event_begin_time = TimeReal::read_field(reader, "eventBeginTime")?;
+        let event_end_time = TimeReal::read_field(reader, "eventEndTime")?;
+        let card_number_driver_slot_begin = FullCardNumber::read_field(reader, "cardNumberDriverSlotBegin")?;
+        let card_number_codriver_slot_begin = FullCardNumber::read_field(reader, "cardNumberCodriverSlotBegin")?;
+        let card_number_driver_slot_end = FullCardNumber::read_field(reader, "cardNumberDriverSlotEnd")?;
+        let card_number_codriver_slot_end = FullCardNumber::read_field(reader, "cardNumberCodriverSlotEnd")?;
+        let similar_events_number =
+            SpanRecorder::field("similarEventsNumber", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
 
         Ok(Self {
             event_type,
@@ -57,7 +61,8 @@ impl Readable<VuEventRecord> for VuEventRecord {
 
 /// Information, stored in a vehicle unit, related to events (Annex 1B
 /// requirement 094 except over speeding event).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuEventData {
     #[serde(rename = "noOfVuEvents")]
     pub no_of_vu_events: u8,
@@ -67,13 +72,40 @@ pub struct VuEventData {
 
 impl Readable<VuEventData> for VuEventData {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuEventData> {
-        let no_of_vu_events = reader.read_u8()?;
+        let no_of_vu_events = SpanRecorder::field("noOfVuEvents", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
         let mut vu_event_records: Vec<VuEventRecord> = Vec::new();
-        for _ in 0..no_of_vu_events {
-            let record = VuEventRecord::read(reader)?;
+        for index in 0..no_of_vu_events {
+            let record = VuEventRecord::read_field(reader, &format!("vuEventRecords[{index}]"))?;
             vu_event_records.push(record);
         }
 
         Ok(Self { no_of_vu_events, vu_event_records })
     }
 }
+
+impl Writable for VuEventRecord {
+    /// Writes a `VuEventRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8((&self.event_type).into())?;
+        writer.write_u8((&self.event_record_purpose).into())?;
+        self.event_begin_time.write(writer)?;
+        self.event_end_time.write(writer)?;
+        self.card_number_driver_slot_begin.write(writer)?;
+        self.card_number_codriver_slot_begin.write(writer)?;
+        self.card_number_driver_slot_end.write(writer)?;
+        self.card_number_codriver_slot_end.write(writer)?;
+        writer.write_u8(self.similar_events_number)?;
+        Ok(())
+    }
+}
+
+impl Writable for VuEventData {
+    /// Writes the number of the events followed by the records.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8(record_count(self.vu_event_records.len())?)?;
+        for record in self.vu_event_records.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
@@ -1,15 +1,16 @@
-use binary_data::{BinSeek, ReadBytes};
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
 use log::debug;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::gen1::{VuEventData, VuFaultData, VuOverSpeedingEventData, VuTimeAdjustmentData};
 use crate::tacho::{VUTransferResponseParameterID, VUTransferResponseParameterReader, VuOverSpeedingControlData};
-use crate::{Readable, Result};
+use crate::{ReadableField, Result, SpanRecorder, Writable, write_octet_string};
 
 const SIGNATURE_LENGTH: u32 = 128;
 
 /// Data structure generation 1,
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuEvents {
     #[serde(rename = "vuFaultData")]
     pub vu_fault_data: VuFaultData,
@@ -27,13 +28,14 @@ pub struct VuEvents {
 impl VUTransferResponseParameterReader<VuEvents> for VuEvents {
     fn from_data<R: ReadBytes + BinSeek>(trep_id: VUTransferResponseParameterID, reader: &mut R) -> Result<VuEvents> {
         debug!("VuEvents::from_data - Trep ID: {trep_id:?}");
-        let vu_fault_data = VuFaultData::read(reader)?;
-        let vu_event_data = VuEventData::read(reader)?;
-        let vu_over_speeding_control_data = VuOverSpeedingControlData::read(reader)?;
-        let vu_over_speeding_event_data = VuOverSpeedingEventData::read(reader)?;
-        let vu_time_adjustment_data = VuTimeAdjustmentData::read(reader)?;
+        let vu_fault_data = VuFaultData::read_field(reader, "vuFaultData")?;
+        let vu_event_data = VuEventData::read_field(reader, "vuEventData")?;
+        let vu_over_speeding_control_data = VuOverSpeedingControlData::read_field(reader, "vuOverSpeedingControlData")?;
+        let vu_over_speeding_event_data = VuOverSpeedingEventData::read_field(reader, "vuOverSpeedingEventData")?;
+        let vu_time_adjustment_data = VuTimeAdjustmentData::read_field(reader, "vuTimeAdjustmentData")?;
 
-        let signature = Some(reader.read_into_vec(SIGNATURE_LENGTH)?);
+        let signature =
+            SpanRecorder::field("signature", "OCTET STRING", reader, |reader| Ok(Some(reader.read_into_vec(SIGNATURE_LENGTH)?)))?;
 
         Ok(Self {
             vu_fault_data,
@@ -45,3 +47,15 @@ impl VUTransferResponseParameterReader<VuEvents> for VuEvents {
         })
     }
 }
+
+impl Writable for VuEvents {
+    /// Writes the data of a `VuEvents` TREP to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.vu_fault_data.write(writer)?;
+        self.vu_event_data.write(writer)?;
+        self.vu_over_speeding_control_data.write(writer)?;
+        self.vu_over_speeding_event_data.write(writer)?;
+        self.vu_time_adjustment_data.write(writer)?;
+        write_octet_string(writer, self.signature.as_deref().unwrap_or_default(), SIGNATURE_LENGTH as usize)
+    }
+}
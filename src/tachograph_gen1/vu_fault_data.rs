@@ -1,13 +1,14 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable,
+    Readable, ReadableField, SpanRecorder, Writable, record_count,
     tacho::{EventFaultRecordPurpose, EventFaultType, FullCardNumber, TimeReal},
 };
 
 /// Information, stored in a vehicle unit, related to a fault (Annex 1B
 /// requirement 096 and Annex 1C requirement 118).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuFaultRecord {
     #[serde(rename = "faultType")]
     pub fault_type: EventFaultType,
@@ -29,14 +30,16 @@ pub struct VuFaultRecord {
 
 impl Readable<VuFaultRecord> for VuFaultRecord {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuFaultRecord> {
-        let fault_type: EventFaultType = reader.read_u8()?.into();
-        let fault_record_purpose: EventFaultRecordPurpose = reader.read_u8()?.into();
-        let fault_begin_time = TimeReal::read(reader)?;
-        let fault_end_time = TimeReal::read(reader)?;
-        let card_number_driver_slot_begin = FullCardNumber::read(reader)?;
-        let card_number_codriver_slot_begin = FullCardNumber::read(reader)?;
-        let card_number_driver_slot_end = FullCardNumber::read(reader)?;
-        let card_number_codriver_slot_end = FullCardNumber::read(reader)?;
+        let fault_type: EventFaultType =
+            SpanRecorder::field("faultType", "EventFaultType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let fault_record_purpose: EventFaultRecordPurpose =
+            SpanRecorder::field("faultRecordPurpose", "EventFaultRecordPurpose", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let fault_begin_time = TimeReal::read_field(reader, "faultBeginTime")?;
+        let fault_end_time = TimeReal::read_field(reader, "faultEndTime")?;
+        let card_number_driver_slot_begin = FullCardNumber::read_field(reader, "cardNumberDriverSlotBegin")?;
+        let card_number_codriver_slot_begin = FullCardNumber::read_field(reader, "cardNumberCodriverSlotBegin")?;
+        let card_number_driver_slot_end = FullCardNumber::read_field(reader, "cardNumberDriverSlotEnd")?;
+        let card_number_codriver_slot_end = FullCardNumber::read_field(reader, "cardNumberCodriverSlotEnd")?;
 
         Ok(Self {
             fault_type,
@@ -52,7 +55,8 @@ impl Readable<VuFaultRecord> for VuFaultRecord {
 }
 
 /// Information, stored in a vehicle unit, related to faults (Annex 1B requirement 096).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuFaultData {
     #[serde(rename = "noOfVuFaults")]
     pub no_of_vu_faults: u8,
@@ -62,13 +66,39 @@ pub struct VuFaultData {
 
 impl Readable<VuFaultData> for VuFaultData {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuFaultData> {
-        let no_of_vu_faults = reader.read_u8()?;
+        let no_of_vu_faults = SpanRecorder::field("noOfVuFaults", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
         let mut vu_fault_records: Vec<VuFaultRecord> = Vec::new();
-        for _ in 0..no_of_vu_faults {
-            let record = VuFaultRecord::read(reader)?;
+        for index in 0..no_of_vu_faults {
+            let record = VuFaultRecord::read_field(reader, &format!("vuFaultRecords[{index}]"))?;
             vu_fault_records.push(record);
         }
 
         Ok(Self { no_of_vu_faults, vu_fault_records })
     }
 }
+
+impl Writable for VuFaultRecord {
+    /// Writes a `VuFaultRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8((&self.fault_type).into())?;
+        writer.write_u8((&self.fault_record_purpose).into())?;
+        self.fault_begin_time.write(writer)?;
+        self.fault_end_time.write(writer)?;
+        self.card_number_driver_slot_begin.write(writer)?;
+        self.card_number_codriver_slot_begin.write(writer)?;
+        self.card_number_driver_slot_end.write(writer)?;
+        self.card_number_codriver_slot_end.write(writer)?;
+        Ok(())
+    }
+}
+
+impl Writable for VuFaultData {
+    /// Writes the number of the faults followed by the records.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8(record_count(self.vu_fault_records.len())?)?;
+        for record in self.vu_fault_records.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,10 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, bytes_to_ia5_fix_string,
+    Readable, ReadableField, SpanRecorder, Writable, bytes_to_ia5_fix_string,
     gen1::VuSoftwareIdentification,
     tacho::{Address, ExtendedSerialNumber, Name, TimeReal},
+    write_ia5_string,
 };
 
 const VU_PART_NUMBER_LENGTH: u32 = 16;
@@ -11,7 +12,8 @@ const VU_APPROVAL_NUMBER_LENGTH: u32 = 8;
 
 /// Information, stored in a vehicle unit, related to the identification of the
 /// vehicle unit (Annex 1B requirement 075 and Annex 1C requirement 93 and 121).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VUIdentification {
     #[serde(rename = "vuManufacturerName")]
     pub vu_manufacturer_name: Name,
@@ -31,14 +33,18 @@ pub struct VUIdentification {
 
 impl Readable<VUIdentification> for VUIdentification {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VUIdentification> {
-        let vu_manufacturer_name = Name::read(reader)?;
-        let vu_manufacturer_address = Address::read(reader)?;
-        let vu_part_number = bytes_to_ia5_fix_string(&reader.read_into_vec(VU_PART_NUMBER_LENGTH)?)?.trim().to_string();
-        let vu_serial_number = ExtendedSerialNumber::read(reader)?;
-        let vu_software_identification = VuSoftwareIdentification::read(reader)?;
+        let vu_manufacturer_name = Name::read_field(reader, "vuManufacturerName")?;
+        let vu_manufacturer_address = Address::read_field(reader, "vuManufacturerAddress")?;
+        let vu_part_number = SpanRecorder::field("vuPartNumber", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(VU_PART_NUMBER_LENGTH)?)?.trim().to_string())
+        })?;
+        let vu_serial_number = ExtendedSerialNumber::read_field(reader, "vuSerialNumber")?;
+        let vu_software_identification = VuSoftwareIdentification::read_field(reader, "vuSoftwareIdentification")?;
 
-        let vu_manufacturing_date = TimeReal::read(reader)?;
-        let vu_approval_number = bytes_to_ia5_fix_string(&reader.read_into_vec(VU_APPROVAL_NUMBER_LENGTH)?)?.trim().to_string();
+        let vu_manufacturing_date = TimeReal::read_field(reader, "vuManufacturingDate")?;
+        let vu_approval_number = SpanRecorder::field("vuApprovalNumber", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(VU_APPROVAL_NUMBER_LENGTH)?)?.trim().to_string())
+        })?;
 
         Ok(Self {
             vu_manufacturer_name,
@@ -51,3 +57,16 @@ impl Readable<VUIdentification> for VUIdentification {
         })
     }
 }
+
+impl Writable for VUIdentification {
+    /// Writes a `VUIdentification` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.vu_manufacturer_name.write(writer)?;
+        self.vu_manufacturer_address.write(writer)?;
+        write_ia5_string(writer, &self.vu_part_number, VU_PART_NUMBER_LENGTH as usize)?;
+        self.vu_serial_number.write(writer)?;
+        self.vu_software_identification.write(writer)?;
+        self.vu_manufacturing_date.write(writer)?;
+        write_ia5_string(writer, &self.vu_approval_number, VU_APPROVAL_NUMBER_LENGTH as usize)
+    }
+}
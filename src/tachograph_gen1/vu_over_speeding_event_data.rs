@@ -1,13 +1,14 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable,
+    Readable, ReadableField, SpanRecorder, Writable, record_count,
     tacho::{EventFaultRecordPurpose, EventFaultType, FullCardNumber, TimeReal},
 };
 
 /// Information, stored in a vehicle unit, related to over speeding events
 /// (Annex 1B requirement 094 and Annex 1C requirement 117).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuOverSpeedingEventRecord {
     #[serde(rename = "eventType")]
     pub event_type: EventFaultType,
@@ -29,14 +30,17 @@ pub struct VuOverSpeedingEventRecord {
 
 impl Readable<VuOverSpeedingEventRecord> for VuOverSpeedingEventRecord {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuOverSpeedingEventRecord> {
-        let event_type: EventFaultType = reader.read_u8()?.into();
-        let event_record_purpose: EventFaultRecordPurpose = reader.read_u8()?.into();
-        let event_begin_time = TimeReal::read(reader)?;
-        let event_end_time = TimeReal::read(reader)?;
-        let max_speed_value = reader.read_u8()?;
-        let average_speed_value = reader.read_u8()?;
-        let card_number_driver_slot_begin = FullCardNumber::read(reader)?;
-        let similar_events_number = reader.read_u8()?;
+        let event_type: EventFaultType =
+            SpanRecorder::field("eventType", "EventFaultType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let event_record_purpose: EventFaultRecordPurpose =
+            SpanRecorder::field("eventRecordPurpose", "EventFaultRecordPurpose", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let event_begin_time = TimeReal::read_field(reader, "eventBeginTime")?;
+        let event_end_time = TimeReal::read_field(reader, "eventEndTime")?;
+        let max_speed_value = SpanRecorder::field("maxSpeedValue", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let average_speed_value = SpanRecorder::field("averageSpeedValue", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let card_number_driver_slot_begin = FullCardNumber::read_field(reader, "cardNumberDriverSlotBegin")?;
+        let similar_events_number =
+            SpanRecorder::field("similarEventsNumber", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
 
         Ok(Self {
             event_type,
@@ -53,7 +57,8 @@ impl Readable<VuOverSpeedingEventRecord> for VuOverSpeedingEventRecord {
 
 /// Information, stored in a vehicle unit, related to over speeding events
 /// (Annex 1B requirement 094).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuOverSpeedingEventData {
     #[serde(rename = "noOfVuOverSpeedingEvents")]
     pub no_of_vu_over_speeding_events: u8,
@@ -63,12 +68,39 @@ pub struct VuOverSpeedingEventData {
 
 impl Readable<VuOverSpeedingEventData> for VuOverSpeedingEventData {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuOverSpeedingEventData> {
-        let no_of_vu_over_speeding_events = reader.read_u8()?;
+        let no_of_vu_over_speeding_events =
+            SpanRecorder::field("noOfVuOverSpeedingEvents", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
         let mut vu_over_speeding_event_records: Vec<VuOverSpeedingEventRecord> = Vec::new();
-        for _ in 0..no_of_vu_over_speeding_events {
-            let record = VuOverSpeedingEventRecord::read(reader)?;
+        for index in 0..no_of_vu_over_speeding_events {
+            let record = VuOverSpeedingEventRecord::read_field(reader, &format!("vuOverSpeedingEventRecords[{index}]"))?;
             vu_over_speeding_event_records.push(record);
         }
         Ok(Self { no_of_vu_over_speeding_events, vu_over_speeding_event_records })
     }
 }
+
+impl Writable for VuOverSpeedingEventRecord {
+    /// Writes a `VuOverSpeedingEventRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8((&self.event_type).into())?;
+        writer.write_u8((&self.event_record_purpose).into())?;
+        self.event_begin_time.write(writer)?;
+        self.event_end_time.write(writer)?;
+        writer.write_u8(self.max_speed_value)?;
+        writer.write_u8(self.average_speed_value)?;
+        self.card_number_driver_slot_begin.write(writer)?;
+        writer.write_u8(self.similar_events_number)?;
+        Ok(())
+    }
+}
+
+impl Writable for VuOverSpeedingEventData {
+    /// Writes the number of the over speeding events followed by the records.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8(record_count(self.vu_over_speeding_event_records.len())?)?;
+        for record in self.vu_over_speeding_event_records.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
@@ -1,12 +1,12 @@
-use binary_data::{BinSeek, ReadBytes};
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
 use log::debug;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::gen1::{VuCompanyLocksData, VuControlActivity, VuDownloadActivityData, VuDownloadablePeriod};
 use crate::tacho::{
     CardSlotStatus, TimeReal, VUTransferResponseParameterID, VUTransferResponseParameterReader, VehicleRegistrationIdentification,
 };
-use crate::{Readable, Result, bytes_to_ia5_fix_string};
+use crate::{ReadableField, Result, SpanRecorder, Writable, bytes_to_ia5_fix_string, write_ia5_string, write_octet_string};
 
 const MEMBER_STATE_CERTIFICATE_LENGTH: u32 = 194;
 const VU_CERTIFICATE_LENGTH: u32 = 194;
@@ -14,7 +14,8 @@ const VEHICLE_IDENTIFICATION_NUMBER_LENGTH: u32 = 17;
 const SIGNATURE_LENGTH: u32 = 128;
 
 /// Data structure generation 1 (TREP 01 Hex)
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuOverview {
     #[serde(rename = "memberStateCertificate")]
     pub member_state_certificate: Vec<u8>,
@@ -45,19 +46,25 @@ pub struct VuOverview {
 impl VUTransferResponseParameterReader<VuOverview> for VuOverview {
     fn from_data<R: ReadBytes + BinSeek>(trep_id: VUTransferResponseParameterID, reader: &mut R) -> Result<VuOverview> {
         debug!("VuOverview::from_data - Trep ID: {trep_id:?}");
-        let member_state_certificate = reader.read_into_vec(MEMBER_STATE_CERTIFICATE_LENGTH)?;
-        let vu_certificate = reader.read_into_vec(VU_CERTIFICATE_LENGTH)?;
-        let vehicle_identification_number =
-            bytes_to_ia5_fix_string(&reader.read_into_vec(VEHICLE_IDENTIFICATION_NUMBER_LENGTH)?)?;
+        let member_state_certificate = SpanRecorder::field("memberStateCertificate", "OCTET STRING", reader, |reader| {
+            Ok(reader.read_into_vec(MEMBER_STATE_CERTIFICATE_LENGTH)?)
+        })?;
+        let vu_certificate = SpanRecorder::field("vuCertificate", "OCTET STRING", reader, |reader| {
+            Ok(reader.read_into_vec(VU_CERTIFICATE_LENGTH)?)
+        })?;
+        let vehicle_identification_number = SpanRecorder::field("vehicleIdentificationNumber", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(VEHICLE_IDENTIFICATION_NUMBER_LENGTH)?)?)
+        })?;
         let vehicle_registration_identification: VehicleRegistrationIdentification =
-            VehicleRegistrationIdentification::read(reader)?;
-        let current_date_time = TimeReal::read(reader)?;
-        let vu_downloadable_period = VuDownloadablePeriod::read(reader)?;
-        let card_slot_status = CardSlotStatus::read(reader)?;
-        let vu_download_activity_data = VuDownloadActivityData::read(reader)?;
-        let vu_company_locks_data = VuCompanyLocksData::read(reader)?;
-        let vu_control_activity = VuControlActivity::read(reader)?;
-        let signature = Some(reader.read_into_vec(SIGNATURE_LENGTH)?);
+            VehicleRegistrationIdentification::read_field(reader, "vehicleRegistrationIdentification")?;
+        let current_date_time = TimeReal::read_field(reader, "currentDateTime")?;
+        let vu_downloadable_period = VuDownloadablePeriod::read_field(reader, "vuDownloadablePeriod")?;
+        let card_slot_status = CardSlotStatus::read_field(reader, "cardSlotStatus")?;
+        let vu_download_activity_data = VuDownloadActivityData::read_field(reader, "vuDownloadActivityData")?;
+        let vu_company_locks_data = VuCompanyLocksData::read_field(reader, "vuCompanyLocksData")?;
+        let vu_control_activity = VuControlActivity::read_field(reader, "vuControlActivity")?;
+        let signature =
+            SpanRecorder::field("signature", "OCTET STRING", reader, |reader| Ok(Some(reader.read_into_vec(SIGNATURE_LENGTH)?)))?;
 
         Ok(Self {
             member_state_certificate,
@@ -74,3 +81,20 @@ impl VUTransferResponseParameterReader<VuOverview> for VuOverview {
         })
     }
 }
+
+impl Writable for VuOverview {
+    /// Writes the data of a `VuOverview` TREP to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        write_octet_string(writer, &self.member_state_certificate, MEMBER_STATE_CERTIFICATE_LENGTH as usize)?;
+        write_octet_string(writer, &self.vu_certificate, VU_CERTIFICATE_LENGTH as usize)?;
+        write_ia5_string(writer, &self.vehicle_identification_number, VEHICLE_IDENTIFICATION_NUMBER_LENGTH as usize)?;
+        self.vehicle_registration_identification.write(writer)?;
+        self.current_date_time.write(writer)?;
+        self.vu_downloadable_period.write(writer)?;
+        self.card_slot_status.write(writer)?;
+        self.vu_download_activity_data.write(writer)?;
+        self.vu_company_locks_data.write(writer)?;
+        self.vu_control_activity.write(writer)?;
+        write_octet_string(writer, self.signature.as_deref().unwrap_or_default(), SIGNATURE_LENGTH as usize)
+    }
+}
@@ -1,11 +1,12 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, gen1::PlaceRecord, tacho::FullCardNumber};
+use crate::{Readable, ReadableField, SpanRecorder, Writable, gen1::PlaceRecord, record_count, tacho::FullCardNumber};
 
 /// Information, stored in a vehicle unit, related to a place where a driver
 /// begins or ends a daily work period (Annex 1B requirement 087 and
 /// Annex 1C requirement 108 and 110).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuPlaceDailyWorkPeriodRecord {
     #[serde(rename = "fullCardNumber")]
     pub full_card_number: FullCardNumber,
@@ -15,8 +16,8 @@ pub struct VuPlaceDailyWorkPeriodRecord {
 
 impl Readable<VuPlaceDailyWorkPeriodRecord> for VuPlaceDailyWorkPeriodRecord {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuPlaceDailyWorkPeriodRecord> {
-        let full_card_number = FullCardNumber::read(reader)?;
-        let place_record = PlaceRecord::read(reader)?;
+        let full_card_number = FullCardNumber::read_field(reader, "fullCardNumber")?;
+        let place_record = PlaceRecord::read_field(reader, "placeRecord")?;
         Ok(Self { full_card_number, place_record })
     }
 }
@@ -24,7 +25,8 @@ impl Readable<VuPlaceDailyWorkPeriodRecord> for VuPlaceDailyWorkPeriodRecord {
 /// Information, stored in a vehicle unit, related to places where drivers
 /// begin or end a daily work period (Annex 1B requirement 087 and
 /// Annex 1C requirement 108 and 110).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuPlaceDailyWorkPeriodData {
     #[serde(rename = "noOfPlaceRecords")]
     pub no_of_place_records: u8,
@@ -34,13 +36,33 @@ pub struct VuPlaceDailyWorkPeriodData {
 
 impl Readable<VuPlaceDailyWorkPeriodData> for VuPlaceDailyWorkPeriodData {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuPlaceDailyWorkPeriodData> {
-        let no_of_place_records = reader.read_u8()?;
+        let no_of_place_records = SpanRecorder::field("noOfPlaceRecords", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
         let mut vu_place_daily_work_period_records: Vec<VuPlaceDailyWorkPeriodRecord> =
             Vec::with_capacity(no_of_place_records as usize);
-        for _ in 0..no_of_place_records {
-            let item = VuPlaceDailyWorkPeriodRecord::read(reader)?;
+        for index in 0..no_of_place_records {
+            let item = VuPlaceDailyWorkPeriodRecord::read_field(reader, &format!("vuPlaceDailyWorkPeriodRecords[{index}]"))?;
             vu_place_daily_work_period_records.push(item);
         }
         Ok(Self { no_of_place_records, vu_place_daily_work_period_records })
     }
 }
+
+impl Writable for VuPlaceDailyWorkPeriodRecord {
+    /// Writes a `VuPlaceDailyWorkPeriodRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.full_card_number.write(writer)?;
+        self.place_record.write(writer)?;
+        Ok(())
+    }
+}
+
+impl Writable for VuPlaceDailyWorkPeriodData {
+    /// Writes the number of the place records followed by the records.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8(record_count(self.vu_place_daily_work_period_records.len())?)?;
+        for record in self.vu_place_daily_work_period_records.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
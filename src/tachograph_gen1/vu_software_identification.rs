@@ -1,10 +1,11 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, bytes_to_ia5_fix_string, tacho::TimeReal};
+use crate::{Readable, ReadableField, SpanRecorder, Writable, bytes_to_ia5_fix_string, tacho::TimeReal, write_ia5_string};
 
 const VU_SOFTWARE_VERSION_LENGTH: u32 = 4;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuSoftwareIdentification {
     #[serde(rename = "vuSoftwareVersion")]
     pub vu_software_version: String,
@@ -14,9 +15,19 @@ pub struct VuSoftwareIdentification {
 
 impl Readable<VuSoftwareIdentification> for VuSoftwareIdentification {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuSoftwareIdentification> {
-        let vu_software_version = bytes_to_ia5_fix_string(&reader.read_into_vec(VU_SOFTWARE_VERSION_LENGTH)?)?;
-        let vu_software_installation_date = TimeReal::read(reader)?;
+        let vu_software_version = SpanRecorder::field("vuSoftwareVersion", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(VU_SOFTWARE_VERSION_LENGTH)?)?)
+        })?;
+        let vu_software_installation_date = TimeReal::read_field(reader, "vuSoftInstallationDate")?;
 
         Ok(Self { vu_software_version, vu_software_installation_date })
     }
 }
+
+impl Writable for VuSoftwareIdentification {
+    /// Writes a `VuSoftwareIdentification` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        write_ia5_string(writer, &self.vu_software_version, VU_SOFTWARE_VERSION_LENGTH as usize)?;
+        self.vu_software_installation_date.write(writer)
+    }
+}
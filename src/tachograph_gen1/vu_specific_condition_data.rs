@@ -1,10 +1,11 @@
 use binary_data::BigEndian;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, tacho::SpecificConditionRecord};
+use crate::{Readable, ReadableField, SpanRecorder, Writable, record_count, tacho::SpecificConditionRecord};
 
 /// Information, stored in a vehicle unit, related to specific conditions.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuSpecificConditionData {
     #[serde(rename = "noOfSpecificConditionRecords")]
     pub no_of_specific_condition_records: u16,
@@ -14,13 +15,25 @@ pub struct VuSpecificConditionData {
 
 impl Readable<VuSpecificConditionData> for VuSpecificConditionData {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuSpecificConditionData> {
-        let no_of_specific_condition_records = reader.read_u16::<BigEndian>()?;
+        let no_of_specific_condition_records =
+            SpanRecorder::field("noOfSpecificConditionRecords", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
         let mut specific_condition_records: Vec<SpecificConditionRecord> =
             Vec::with_capacity(no_of_specific_condition_records as usize);
-        for _ in 0..no_of_specific_condition_records {
-            let record = SpecificConditionRecord::read(reader)?;
+        for index in 0..no_of_specific_condition_records {
+            let record = SpecificConditionRecord::read_field(reader, &format!("specificConditionRecords[{index}]"))?;
             specific_condition_records.push(record);
         }
         Ok(Self { no_of_specific_condition_records, specific_condition_records })
     }
 }
+
+impl Writable for VuSpecificConditionData {
+    /// Writes the number of the specific condition records followed by the records.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u16::<BigEndian>(record_count(self.specific_condition_records.len())?)?;
+        for record in self.specific_condition_records.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
@@ -1,13 +1,14 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::gen1::{SensorPaired, VUCalibrationData, VUIdentification};
 use crate::tacho::{VUTransferResponseParameterID, VUTransferResponseParameterReader};
-use crate::{Readable, Result};
+use crate::{ReadableField, Result, SpanRecorder, Writable, write_octet_string};
 
 const SIGNATURE_LENGTH: u32 = 128;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuTechnicalData {
     #[serde(rename = "trepId")]
     pub trep_id: VUTransferResponseParameterID,
@@ -21,12 +22,23 @@ pub struct VuTechnicalData {
 
 impl VUTransferResponseParameterReader<VuTechnicalData> for VuTechnicalData {
     fn from_data<R: ReadBytes + BinSeek>(trep_id: VUTransferResponseParameterID, reader: &mut R) -> Result<VuTechnicalData> {
-        let vu_identification = VUIdentification::read(reader)?;
-        let sensor_paired = SensorPaired::read(reader)?;
-        let vu_calibration_data = VUCalibrationData::read(reader)?;
+        let vu_identification = VUIdentification::read_field(reader, "identification")?;
+        let sensor_paired = SensorPaired::read_field(reader, "sensorPaired")?;
+        let vu_calibration_data = VUCalibrationData::read_field(reader, "vuCalibrationData")?;
 
-        let signature = Some(reader.read_into_vec(SIGNATURE_LENGTH)?);
+        let signature =
+            SpanRecorder::field("signature", "OCTET STRING", reader, |reader| Ok(Some(reader.read_into_vec(SIGNATURE_LENGTH)?)))?;
 
         Ok(Self { trep_id, identification: vu_identification, sensor_paired, vu_calibration_data, signature })
     }
 }
+
+impl Writable for VuTechnicalData {
+    /// Writes the data of a `VuTechnicalData` TREP to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.identification.write(writer)?;
+        self.sensor_paired.write(writer)?;
+        self.vu_calibration_data.write(writer)?;
+        write_octet_string(writer, self.signature.as_deref().unwrap_or_default(), SIGNATURE_LENGTH as usize)
+    }
+}
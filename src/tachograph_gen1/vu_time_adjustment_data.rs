@@ -1,14 +1,15 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable,
+    Readable, ReadableField, SpanRecorder, Writable, record_count,
     tacho::{Address, FullCardNumber, Name, TimeReal},
 };
 
 /// Information, stored in a vehicle unit, related a time adjustment
 /// performed outside the frame of a regular calibration (Annex 1B
 /// requirement 101 and Annex 1C requirement 124 and 125).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuTimeAdjustmentRecord {
     #[serde(rename = "oldTimeValue")]
     pub old_time_value: TimeReal,
@@ -24,11 +25,11 @@ pub struct VuTimeAdjustmentRecord {
 
 impl Readable<VuTimeAdjustmentRecord> for VuTimeAdjustmentRecord {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuTimeAdjustmentRecord> {
-        let old_time_value = TimeReal::read(reader)?;
-        let mew_time_value = TimeReal::read(reader)?;
-        let workshop_name = Name::read(reader)?;
-        let workshop_address = Address::read(reader)?;
-        let workshop_card_number = FullCardNumber::read(reader)?;
+        let old_time_value = TimeReal::read_field(reader, "oldTimeValue")?;
+        let mew_time_value = TimeReal::read_field(reader, "newTimeValue")?;
+        let workshop_name = Name::read_field(reader, "workshopName")?;
+        let workshop_address = Address::read_field(reader, "workshopAddress")?;
+        let workshop_card_number = FullCardNumber::read_field(reader, "workshopCardNumber")?;
 
         Ok(Self { old_time_value, mew_time_value, workshop_name, workshop_address, workshop_card_number })
     }
@@ -36,7 +37,8 @@ impl Readable<VuTimeAdjustmentRecord> for VuTimeAdjustmentRecord {
 
 /// Information, stored in a vehicle unit, related to time adjustments
 /// performed outside the frame of a regular calibration (Annex 1B requirement 101).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuTimeAdjustmentData {
     #[serde(rename = "noOfVuTimeAdjRecords")]
     pub no_of_vu_time_adj_records: u8,
@@ -46,12 +48,36 @@ pub struct VuTimeAdjustmentData {
 
 impl Readable<VuTimeAdjustmentData> for VuTimeAdjustmentData {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<VuTimeAdjustmentData> {
-        let no_of_vu_time_adj_records = reader.read_u8()?;
+        let no_of_vu_time_adj_records =
+            SpanRecorder::field("noOfVuTimeAdjRecords", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
         let mut vu_time_adjustment_records: Vec<VuTimeAdjustmentRecord> = Vec::new();
-        for _ in 0..no_of_vu_time_adj_records {
-            let record = VuTimeAdjustmentRecord::read(reader)?;
+        for index in 0..no_of_vu_time_adj_records {
+            let record = VuTimeAdjustmentRecord::read_field(reader, &format!("vuTimeAdjustmentRecords[{index}]"))?;
             vu_time_adjustment_records.push(record);
         }
         Ok(Self { no_of_vu_time_adj_records, vu_time_adjustment_records })
     }
 }
+
+impl Writable for VuTimeAdjustmentRecord {
+    /// Writes a `VuTimeAdjustmentRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.old_time_value.write(writer)?;
+        self.mew_time_value.write(writer)?;
+        self.workshop_name.write(writer)?;
+        self.workshop_address.write(writer)?;
+        self.workshop_card_number.write(writer)?;
+        Ok(())
+    }
+}
+
+impl Writable for VuTimeAdjustmentData {
+    /// Writes the number of the time adjustments followed by the records.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8(record_count(self.vu_time_adjustment_records.len())?)?;
+        for record in self.vu_time_adjustment_records.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
@@ -1,8 +1,8 @@
 use std::collections::HashMap;
 
-use binary_data::{BigEndian, BinSeek, ReadBytes};
+use binary_data::{BigEndian, BinSeek, ReadBytes, WriteBytes};
 use log::{debug, trace};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::gen1::{
     CardResponseParameterData, CardVehicleRecord, Certificate, PlaceRecord, SpecificConditions, SpecificConditionsParams,
@@ -11,13 +11,15 @@ use crate::gen1::{
 use crate::tacho::{
     Card, CardChipIdentification, CardControlActivityDataRecord, CardCurrentUse, CardDriverActivity, CardDriverActivityParams,
     CardEventData, CardEventDataParams, CardFaultData, CardFaultDataParams, CardFileData, CardFileID, CardIccIdentification,
-    CardParser, CardPlaceDailyWorkPeriod, CardPlaceDailyWorkPeriodParams, CardVehiclesUsed, DataFiles, Identification,
-    IdentificationParams, VehiclesUsedParams, WorkshopCardCalibrationData, WorkshopCardCalibrationDataParams,
+    CardParser, CardPlaceDailyWorkPeriod, CardPlaceDailyWorkPeriodParams, CardVehiclesUsed, CardWriter, DataFiles,
+    Identification, IdentificationParams, VehiclesUsedParams, WorkshopCardCalibrationData, WorkshopCardCalibrationDataParams,
+    write_card_file, write_card_file_with_params,
 };
-use crate::{Readable, ReadableWithParams, Result};
+use crate::{Diagnostic, ReadableField, ReadableWithParamsField, Result, Writable, write_to_vec};
 
 /// Workshop card application generation 1
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct WorkshopCard {
     #[serde(rename = "cardChipIdentification")]
     pub card_chip_identification: CardChipIdentification,
@@ -50,6 +52,9 @@ pub struct WorkshopCard {
     pub ca_certificate: Option<Certificate>,
     #[serde(rename = "cardNotes")]
     pub card_notes: String,
+    /// Errors of the card files (EF) which could not be parsed in lenient mode.
+    #[serde(rename = "efErrors")]
+    pub ef_errors: HashMap<CardFileID, Diagnostic>,
     #[serde(rename = "dataFiles")]
     pub data_files: HashMap<CardFileID, CardFileData>,
 }
@@ -80,91 +85,108 @@ impl WorkshopCard {
             card_certificate: None,
             ca_certificate: None,
             card_notes,
+            ef_errors: HashMap::new(),
             data_files,
         }
     }
-}
-
-impl CardParser<WorkshopCard> for WorkshopCard {
-    fn parse(card_data_files: &HashMap<CardFileID, CardFileData>, card_notes: &str) -> Result<Box<WorkshopCard>> {
-        let card_chip_identification = <dyn Card<CardResponseParameterData>>::parse_ic(card_data_files)?;
-        let card_icc_identification = <dyn Card<CardResponseParameterData>>::parse_icc(card_data_files)?;
-        let application_identification = <dyn Card<CardResponseParameterData>>::parse_card_application_identification::<
-            WorkshopCardApplicationIdentification,
-        >(card_data_files)?;
-        trace!("WorkshopCard::parse - Application Identification: {application_identification:?}");
-
-        let mut workshop_card = WorkshopCard::new(
-            card_chip_identification,
-            card_icc_identification,
-            application_identification.clone(),
-            card_notes.to_owned(),
-            (*card_data_files).clone(),
-        );
 
-        for card_item in card_data_files.iter() {
-            debug!("WorkshopCard::parse - ID: {:?}", card_item.0,);
-            let card_file = card_item.1;
-            let mut reader = card_file.data_into_reader()?;
+    fn parse_card_file(
+        &mut self,
+        card_file_id: &CardFileID,
+        card_file: &CardFileData,
+        application_identification: &WorkshopCardApplicationIdentification,
+    ) -> Result<()> {
+        card_file.read_data_with(|reader| {
             debug!(
                 "WorkshopCard::parse - ID: {:?}, Data Length: {:?}, Has Signature: {}",
-                card_item.0,
+                card_file_id,
                 reader.len()?,
                 card_file.signature.is_some()
             );
-            match card_item.0 {
+            match card_file_id {
                 CardFileID::CardDownload => {
-                    workshop_card.no_of_calibrations_since_download = reader.read_u16::<BigEndian>()?;
+                    self.no_of_calibrations_since_download = reader.read_u16::<BigEndian>()?;
                 }
                 CardFileID::Calibration => {
                     let params = WorkshopCardCalibrationDataParams::new(application_identification.no_off_calibration_records);
-                    workshop_card.calibration =
-                        Some(WorkshopCardCalibrationData::<WorkshopCardCalibrationRecord>::read(&mut reader, &params)?);
+                    self.calibration = Some(WorkshopCardCalibrationData::<WorkshopCardCalibrationRecord>::read_field_with(
+                        reader,
+                        &params,
+                        "calibration",
+                    )?);
                 }
                 CardFileID::EventsData => {
                     let params = CardEventDataParams::new(6, application_identification.no_events_per_type);
-                    workshop_card.events_data = Some(CardEventData::read(&mut reader, &params)?);
+                    self.events_data = Some(CardEventData::read_field_with(reader, &params, "eventsData")?);
                 }
                 CardFileID::FaultsData => {
                     let params = CardFaultDataParams::new(application_identification.no_faults_per_type);
-                    workshop_card.faults_data = Some(CardFaultData::read(&mut reader, &params)?);
+                    self.faults_data = Some(CardFaultData::read_field_with(reader, &params, "faultsData")?);
                 }
                 CardFileID::DriverActivityData => {
                     let params = CardDriverActivityParams::new(application_identification.activity_structure_length);
-                    workshop_card.driver_activity_data = Some(CardDriverActivity::read(&mut reader, &params)?);
+                    self.driver_activity_data = Some(CardDriverActivity::read_field_with(reader, &params, "driverActivityData")?);
                 }
                 CardFileID::VehiclesUsed => {
                     let params = VehiclesUsedParams::new(application_identification.no_of_card_vehicle_records);
-                    workshop_card.vehicles_used = Some(CardVehiclesUsed::<CardVehicleRecord>::read(&mut reader, &params)?);
+                    self.vehicles_used =
+                        Some(CardVehiclesUsed::<CardVehicleRecord>::read_field_with(reader, &params, "vehiclesUsed")?);
                 }
                 CardFileID::Places => {
                     let params = CardPlaceDailyWorkPeriodParams::new(application_identification.no_of_card_place_records, 1);
-                    workshop_card.places = Some(CardPlaceDailyWorkPeriod::<PlaceRecord>::read(&mut reader, &params)?);
+                    self.places = Some(CardPlaceDailyWorkPeriod::<PlaceRecord>::read_field_with(reader, &params, "places")?);
                 }
                 CardFileID::CurrentUsage => {
-                    workshop_card.current_usage = Some(CardCurrentUse::read(&mut reader)?);
+                    self.current_usage = Some(CardCurrentUse::read_field(reader, "currentUsage")?);
                 }
                 CardFileID::ControlActivityData => {
-                    workshop_card.control_activity_data = Some(CardControlActivityDataRecord::read(&mut reader)?);
+                    self.control_activity_data = Some(CardControlActivityDataRecord::read_field(reader, "controlActivityData")?);
                 }
                 CardFileID::SpecificConditions => {
                     let params = SpecificConditionsParams::new(56);
-                    workshop_card.specific_conditions = Some(SpecificConditions::read(&mut reader, &params)?);
+                    self.specific_conditions = Some(SpecificConditions::read_field_with(reader, &params, "specificConditions")?);
                 }
                 CardFileID::Identification => {
                     let params = IdentificationParams::new(application_identification.type_of_tachograph_card_id.clone());
-                    workshop_card.identification = Some(Identification::read(&mut reader, &params)?);
+                    self.identification = Some(Identification::read_field_with(reader, &params, "identification")?);
                 }
                 CardFileID::CardCertificate => {
-                    workshop_card.card_certificate = Some(Certificate::read(&mut reader)?);
+                    self.card_certificate = Some(Certificate::read_field(reader, "cardCertificate")?);
                 }
                 CardFileID::CACertificate => {
-                    workshop_card.ca_certificate = Some(Certificate::read(&mut reader)?);
+                    self.ca_certificate = Some(Certificate::read_field(reader, "caCertificate")?);
                 }
                 CardFileID::IC | CardFileID::ICC | CardFileID::ApplicationIdentification => {
-                    trace!("WorkshopCard::parse - Already parsed: {:?}", card_item.0)
+                    trace!("WorkshopCard::parse - Already parsed: {:?}", card_file_id)
                 }
-                _ => trace!("WorkshopCard::parse - Not Parsed: {:?}", card_item.0),
+                _ => trace!("WorkshopCard::parse - Not Parsed: {:?}", card_file_id),
+            }
+            Ok(())
+        })
+    }
+}
+
+impl CardParser<WorkshopCard> for WorkshopCard {
+    fn parse(card_data_files: &HashMap<CardFileID, CardFileData>, card_notes: &str) -> Result<Box<WorkshopCard>> {
+        let card_chip_identification = <dyn Card<CardResponseParameterData>>::parse_ic(card_data_files)?;
+        let card_icc_identification = <dyn Card<CardResponseParameterData>>::parse_icc(card_data_files)?;
+        let application_identification = <dyn Card<CardResponseParameterData>>::parse_card_application_identification::<
+            WorkshopCardApplicationIdentification,
+        >(card_data_files)?;
+        trace!("WorkshopCard::parse - Application Identification: {application_identification:?}");
+
+        let mut workshop_card = WorkshopCard::new(
+            card_chip_identification,
+            card_icc_identification,
+            application_identification.clone(),
+            card_notes.to_owned(),
+            (*card_data_files).clone(),
+        );
+
+        for card_item in card_data_files.iter() {
+            debug!("WorkshopCard::parse - ID: {:?}", card_item.0,);
+            if let Err(error) = workshop_card.parse_card_file(card_item.0, card_item.1, &application_identification) {
+                <dyn Card<CardResponseParameterData>>::add_card_file_error(&mut workshop_card.ef_errors, card_item.0, error)?;
             }
         }
 
@@ -177,3 +199,42 @@ impl DataFiles for WorkshopCard {
         &self.data_files
     }
 }
+
+impl CardWriter for WorkshopCard {
+    fn write_card_file(&self, card_file_id: &CardFileID) -> Result<Option<Vec<u8>>> {
+        let application_identification = &self.application_identification;
+        match card_file_id {
+            CardFileID::IC => write_to_vec(|writer| self.card_chip_identification.write(writer)).map(Some),
+            CardFileID::ICC => write_to_vec(|writer| self.card_icc_identification.write(writer)).map(Some),
+            CardFileID::ApplicationIdentification => write_to_vec(|writer| application_identification.write(writer)).map(Some),
+            CardFileID::CardDownload => {
+                write_to_vec(|writer| Ok(writer.write_u16::<BigEndian>(self.no_of_calibrations_since_download)?)).map(Some)
+            }
+            CardFileID::Calibration => write_card_file(&self.calibration),
+            CardFileID::EventsData => {
+                let params = CardEventDataParams::new(6, application_identification.no_events_per_type);
+                write_card_file_with_params(&self.events_data, &params)
+            }
+            CardFileID::FaultsData => {
+                let params = CardFaultDataParams::new(application_identification.no_faults_per_type);
+                write_card_file_with_params(&self.faults_data, &params)
+            }
+            CardFileID::DriverActivityData => {
+                let params = CardDriverActivityParams::new(application_identification.activity_structure_length);
+                write_card_file_with_params(&self.driver_activity_data, &params)
+            }
+            CardFileID::VehiclesUsed => write_card_file(&self.vehicles_used),
+            CardFileID::Places => {
+                let params = CardPlaceDailyWorkPeriodParams::new(application_identification.no_of_card_place_records, 1);
+                write_card_file_with_params(&self.places, &params)
+            }
+            CardFileID::CurrentUsage => write_card_file(&self.current_usage),
+            CardFileID::ControlActivityData => write_card_file(&self.control_activity_data),
+            CardFileID::SpecificConditions => write_card_file(&self.specific_conditions),
+            CardFileID::Identification => write_card_file(&self.identification),
+            CardFileID::CardCertificate => write_card_file(&self.card_certificate),
+            CardFileID::CACertificate => write_card_file(&self.ca_certificate),
+            _ => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use binary_data::{BigEndian, BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
+
+use crate::gen2::write_cyclic_records;
+use crate::{
+    Readable, ReadableField, ReadableWithParams, Result, SpanRecorder, Writable, WritableWithParams,
+    gen2::{GnssPlaceAuthRecord, read_cyclic_records},
+    tacho::{NationNumeric, OdometerShort},
+};
+
+/// Information, stored in a driver or workshop card, related to a border
+/// crossing of the vehicle (generation 2, version 2).
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CardBorderCrossingRecord {
+    #[serde(rename = "countryLeft")]
+    pub country_left: NationNumeric,
+    #[serde(rename = "countryEntered")]
+    pub country_entered: NationNumeric,
+    #[serde(rename = "gnssPlaceAuthRecord")]
+    pub gnss_place_auth_record: GnssPlaceAuthRecord,
+    #[serde(rename = "vehicleOdometerValue")]
+    pub vehicle_odometer_value: OdometerShort,
+}
+
+impl Readable<CardBorderCrossingRecord> for CardBorderCrossingRecord {
+    fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<CardBorderCrossingRecord> {
+        let country_left: NationNumeric =
+            SpanRecorder::field("countryLeft", "NationNumeric", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let country_entered: NationNumeric =
+            SpanRecorder::field("countryEntered", "NationNumeric", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let gnss_place_auth_record = GnssPlaceAuthRecord::read_field(reader, "gnssPlaceAuthRecord")?;
+        let vehicle_odometer_value = OdometerShort::read_field(reader, "vehicleOdometerValue")?;
+
+        Ok(Self { country_left, country_entered, gnss_place_auth_record, vehicle_odometer_value })
+    }
+}
+
+#[derive(Debug)]
+pub struct CardBorderCrossingsParams {
+    pub no_of_border_crossing_records: u16,
+}
+
+impl CardBorderCrossingsParams {
+    pub fn new(no_of_border_crossing_records: u16) -> Self {
+        Self { no_of_border_crossing_records }
+    }
+}
+
+/// Information, stored in a driver or workshop card, related to the border
+/// crossings of the vehicle (generation 2, version 2). The records are ordered
+/// from the oldest to the newest.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CardBorderCrossings {
+    #[serde(rename = "borderCrossingPointerNewestRecord")]
+    pub border_crossing_pointer_newest_record: u16,
+    #[serde(rename = "cardBorderCrossingRecords")]
+    pub card_border_crossing_records: Vec<CardBorderCrossingRecord>,
+}
+
+impl ReadableWithParams<CardBorderCrossings> for CardBorderCrossings {
+    type P = CardBorderCrossingsParams;
+
+    fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<CardBorderCrossings> {
+        let border_crossing_pointer_newest_record =
+            SpanRecorder::field("borderCrossingPointerNewestRecord", "INTEGER", reader, |reader| {
+                Ok(reader.read_u16::<BigEndian>()?)
+            })?;
+        let card_border_crossing_records = read_cyclic_records(
+            reader,
+            params.no_of_border_crossing_records as usize,
+            border_crossing_pointer_newest_record as usize,
+            "cardBorderCrossingRecords",
+            |record: &CardBorderCrossingRecord| record.gnss_place_auth_record.time_stamp.has_data(),
+        )?;
+
+        Ok(Self { border_crossing_pointer_newest_record, card_border_crossing_records })
+    }
+}
+
+impl Writable for CardBorderCrossingRecord {
+    /// Writes a `CardBorderCrossingRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u8((&self.country_left).into())?;
+        writer.write_u8((&self.country_entered).into())?;
+        self.gnss_place_auth_record.write(writer)?;
+        self.vehicle_odometer_value.write(writer)?;
+        Ok(())
+    }
+}
+
+impl WritableWithParams for CardBorderCrossings {
+    type P = CardBorderCrossingsParams;
+
+    /// Writes the pointer and the cyclic records, the newest record at the pointer.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W, params: &Self::P) -> Result<()> {
+        writer.write_u16::<BigEndian>(self.border_crossing_pointer_newest_record)?;
+        write_cyclic_records(
+            writer,
+            &self.card_border_crossing_records,
+            params.no_of_border_crossing_records as usize,
+            self.border_crossing_pointer_newest_record as usize,
+        )?;
+        Ok(())
+    }
+}
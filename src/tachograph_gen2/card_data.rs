@@ -1,6 +1,6 @@
 use binary_data::{BinSeek, ReadBytes};
 use log::{debug, trace};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     Error, Export, Result, gen1,
@@ -11,7 +11,8 @@ use crate::{
     },
 };
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardData {
     pub header: TachographHeader,
     #[serde(rename = "cardDataResponses")]
@@ -30,6 +31,15 @@ impl CardData {
         Ok(Self { header, card_data_responses })
     }
 
+    /// Creates the card data from already read card files.
+    pub fn from_card_files(header: TachographHeader, card_data_files: &CardFilesDataByCardGeneration) -> Result<CardData> {
+        let card_data_responses = CardData::parse_card(card_data_files)?;
+
+        trace!("CardData::from_card_files - Header: {header:?}, Note: {card_data_responses:?}");
+
+        Ok(Self { header, card_data_responses })
+    }
+
     fn get_card_by_equipment_type<TGen1: CardParser<TGen1>, TGen2: CardParser<TGen2>>(
         generation: CardGeneration,
         card_files_data_gen1: &CardFilesDataByCardGenerationItem,
@@ -66,9 +76,14 @@ impl CardData {
             card_files_data_gen2.card_files_data.len(),
             card_files_data_gen2.card_notes
         );
-        let application_identification = <dyn tacho::Card<CardResponseParameterData>>::parse_application_identification(
-            &card_files_data_gen1.card_files_data,
-        )?;
+        // Both applications have the same card type, Gen2 only cards have no Gen1 application.
+        let application_card_files = if card_files_data_gen1.is_empty() {
+            &card_files_data_gen2.card_files_data
+        } else {
+            &card_files_data_gen1.card_files_data
+        };
+        let application_identification =
+            <dyn tacho::Card<CardResponseParameterData>>::parse_application_identification(application_card_files)?;
         trace!("CardData::parse_card - Application identification: {application_identification:?}");
         match application_identification.type_of_tachograph_card_id {
             EquipmentType::DriverCard => {
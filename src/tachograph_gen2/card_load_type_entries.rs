@@ -0,0 +1,98 @@
+use binary_data::{BigEndian, BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
+
+use crate::gen2::write_cyclic_records;
+use crate::{
+    Readable, ReadableField, ReadableWithParams, Result, SpanRecorder, Writable, WritableWithParams,
+    gen2::{LoadType, read_cyclic_records},
+    tacho::TimeReal,
+};
+
+/// Information, stored in a driver or workshop card, related to the entry of
+/// a load type (generation 2, version 2).
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CardLoadTypeEntryRecord {
+    #[serde(rename = "timeStamp")]
+    pub time_stamp: TimeReal,
+    #[serde(rename = "loadTypeEntered")]
+    pub load_type_entered: LoadType,
+}
+
+impl Readable<CardLoadTypeEntryRecord> for CardLoadTypeEntryRecord {
+    fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<CardLoadTypeEntryRecord> {
+        let time_stamp = TimeReal::read_field(reader, "timeStamp")?;
+        let load_type_entered: LoadType =
+            SpanRecorder::field("loadTypeEntered", "LoadType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+
+        Ok(Self { time_stamp, load_type_entered })
+    }
+}
+
+#[derive(Debug)]
+pub struct CardLoadTypeEntriesParams {
+    pub no_of_load_type_entry_records: u16,
+}
+
+impl CardLoadTypeEntriesParams {
+    pub fn new(no_of_load_type_entry_records: u16) -> Self {
+        Self { no_of_load_type_entry_records }
+    }
+}
+
+/// Information, stored in a driver or workshop card, related to the load type
+/// entries (generation 2, version 2). The records are ordered from the oldest
+/// to the newest.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CardLoadTypeEntries {
+    #[serde(rename = "loadTypeEntryPointerNewestRecord")]
+    pub load_type_entry_pointer_newest_record: u16,
+    #[serde(rename = "cardLoadTypeEntryRecords")]
+    pub card_load_type_entry_records: Vec<CardLoadTypeEntryRecord>,
+}
+
+impl ReadableWithParams<CardLoadTypeEntries> for CardLoadTypeEntries {
+    type P = CardLoadTypeEntriesParams;
+
+    fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<CardLoadTypeEntries> {
+        let load_type_entry_pointer_newest_record =
+            SpanRecorder::field("loadTypeEntryPointerNewestRecord", "INTEGER", reader, |reader| {
+                Ok(reader.read_u16::<BigEndian>()?)
+            })?;
+        let card_load_type_entry_records = read_cyclic_records(
+            reader,
+            params.no_of_load_type_entry_records as usize,
+            load_type_entry_pointer_newest_record as usize,
+            "cardLoadTypeEntryRecords",
+            |record: &CardLoadTypeEntryRecord| record.time_stamp.has_data(),
+        )?;
+
+        Ok(Self { load_type_entry_pointer_newest_record, card_load_type_entry_records })
+    }
+}
+
+impl Writable for CardLoadTypeEntryRecord {
+    /// Writes a `CardLoadTypeEntryRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.time_stamp.write(writer)?;
+        writer.write_u8((&self.load_type_entered).into())?;
+        Ok(())
+    }
+}
+
+impl WritableWithParams for CardLoadTypeEntries {
+    type P = CardLoadTypeEntriesParams;
+
+    /// Writes the pointer and the cyclic records, the newest record at the pointer.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W, params: &Self::P) -> Result<()> {
+        writer.write_u16::<BigEndian>(self.load_type_entry_pointer_newest_record)?;
+        write_cyclic_records(
+            writer,
+            &self.card_load_type_entry_records,
+            params.no_of_load_type_entry_records as usize,
+            self.load_type_entry_pointer_newest_record as usize,
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+use binary_data::{BigEndian, BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
+
+use crate::gen2::write_cyclic_records;
+use crate::{
+    Readable, ReadableField, ReadableWithParams, Result, SpanRecorder, Writable, WritableWithParams,
+    gen2::{GnssPlaceAuthRecord, OperationType, read_cyclic_records},
+    tacho::{OdometerShort, TimeReal},
+};
+
+/// Information, stored in a driver or workshop card, related to a load or
+/// unload operation of the vehicle (generation 2, version 2).
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CardLoadUnloadRecord {
+    #[serde(rename = "timeStamp")]
+    pub time_stamp: TimeReal,
+    #[serde(rename = "operationType")]
+    pub operation_type: OperationType,
+    #[serde(rename = "gnssPlaceAuthRecord")]
+    pub gnss_place_auth_record: GnssPlaceAuthRecord,
+    #[serde(rename = "vehicleOdometerValue")]
+    pub vehicle_odometer_value: OdometerShort,
+}
+
+impl Readable<CardLoadUnloadRecord> for CardLoadUnloadRecord {
+    fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<CardLoadUnloadRecord> {
+        let time_stamp = TimeReal::read_field(reader, "timeStamp")?;
+        let operation_type: OperationType =
+            SpanRecorder::field("operationType", "OperationType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let gnss_place_auth_record = GnssPlaceAuthRecord::read_field(reader, "gnssPlaceAuthRecord")?;
+        let vehicle_odometer_value = OdometerShort::read_field(reader, "vehicleOdometerValue")?;
+
+        Ok(Self { time_stamp, operation_type, gnss_place_auth_record, vehicle_odometer_value })
+    }
+}
+
+#[derive(Debug)]
+pub struct CardLoadUnloadOperationsParams {
+    pub no_of_load_unload_records: u16,
+}
+
+impl CardLoadUnloadOperationsParams {
+    pub fn new(no_of_load_unload_records: u16) -> Self {
+        Self { no_of_load_unload_records }
+    }
+}
+
+/// Information, stored in a driver or workshop card, related to the load and
+/// unload operations of the vehicle (generation 2, version 2). The records are
+/// ordered from the oldest to the newest.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CardLoadUnloadOperations {
+    #[serde(rename = "loadUnloadPointerNewestRecord")]
+    pub load_unload_pointer_newest_record: u16,
+    #[serde(rename = "cardLoadUnloadRecords")]
+    pub card_load_unload_records: Vec<CardLoadUnloadRecord>,
+}
+
+impl ReadableWithParams<CardLoadUnloadOperations> for CardLoadUnloadOperations {
+    type P = CardLoadUnloadOperationsParams;
+
+    fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<CardLoadUnloadOperations> {
+        let load_unload_pointer_newest_record = SpanRecorder::field(
+            "loadUnloadPointerNewestRecord",
+            "INTEGER",
+            reader,
+            |reader| Ok(reader.read_u16::<BigEndian>()?),
+        )?;
+        let card_load_unload_records = read_cyclic_records(
+            reader,
+            params.no_of_load_unload_records as usize,
+            load_unload_pointer_newest_record as usize,
+            "cardLoadUnloadRecords",
+            |record: &CardLoadUnloadRecord| record.time_stamp.has_data(),
+        )?;
+
+        Ok(Self { load_unload_pointer_newest_record, card_load_unload_records })
+    }
+}
+
+impl Writable for CardLoadUnloadRecord {
+    /// Writes a `CardLoadUnloadRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.time_stamp.write(writer)?;
+        writer.write_u8((&self.operation_type).into())?;
+        self.gnss_place_auth_record.write(writer)?;
+        self.vehicle_odometer_value.write(writer)?;
+        Ok(())
+    }
+}
+
+impl WritableWithParams for CardLoadUnloadOperations {
+    type P = CardLoadUnloadOperationsParams;
+
+    /// Writes the pointer and the cyclic records, the newest record at the pointer.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W, params: &Self::P) -> Result<()> {
+        writer.write_u16::<BigEndian>(self.load_unload_pointer_newest_record)?;
+        write_cyclic_records(
+            writer,
+            &self.card_load_unload_records,
+            params.no_of_load_unload_records as usize,
+            self.load_unload_pointer_newest_record as usize,
+        )?;
+        Ok(())
+    }
+}
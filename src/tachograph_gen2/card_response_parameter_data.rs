@@ -1,9 +1,18 @@
 use serde::{
-    Serialize,
+    Deserialize, Deserializer, Serialize, de,
     ser::{SerializeStruct, Serializer},
 };
+use serde_json::Value;
 
-use crate::{gen1, gen2, tacho::CardParser};
+use crate::{
+    gen1, gen2,
+    helpers::{card_type_of_json_value, from_json_value},
+    tacho::{CardParser, EquipmentType},
+};
+
+const UNSUPPORTED: &str = "Unsupported";
+const GEN1: &str = "gen1";
+const GEN2: &str = "gen2";
 
 #[derive(Debug)]
 pub enum ParsedCard<TGen1: CardParser<TGen1>, TGen2: CardParser<TGen2>> {
@@ -25,18 +34,18 @@ where
         match self {
             ParsedCard::Gen1(data) => {
                 let mut state = serializer.serialize_struct("Gen1", 1)?;
-                state.serialize_field("gen1", data)?;
+                state.serialize_field(GEN1, data)?;
                 state.end()
             }
             ParsedCard::Gen2(data) => {
                 let mut state = serializer.serialize_struct("Gen2", 1)?;
-                state.serialize_field("gen2", data)?;
+                state.serialize_field(GEN2, data)?;
                 state.end()
             }
             ParsedCard::Combined(gen1_data, gen2_data) => {
                 let mut state = serializer.serialize_struct("Combined", 2)?;
-                state.serialize_field("gen1", gen1_data)?;
-                state.serialize_field("gen2", gen2_data)?;
+                state.serialize_field(GEN1, gen1_data)?;
+                state.serialize_field(GEN2, gen2_data)?;
                 state.end()
             }
             ParsedCard::None => serializer.serialize_none(),
@@ -44,6 +53,63 @@ where
     }
 }
 
+impl<'de, TGen1, TGen2> Deserialize<'de> for ParsedCard<TGen1, TGen2>
+where
+    TGen1: CardParser<TGen1> + de::DeserializeOwned,
+    TGen2: CardParser<TGen2> + de::DeserializeOwned,
+{
+    /// Deserializes the generations present in the object, `null` is a card of no generation.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut value = Value::deserialize(deserializer)?;
+        if value.is_null() {
+            return Ok(ParsedCard::None);
+        }
+        let gen1 = value.get_mut(GEN1).map(Value::take);
+        let gen2 = value.get_mut(GEN2).map(Value::take);
+        match (gen1, gen2) {
+            (Some(gen1), None) => Ok(ParsedCard::Gen1(from_json_value(gen1)?)),
+            (None, Some(gen2)) => Ok(ParsedCard::Gen2(from_json_value(gen2)?)),
+            (Some(gen1), Some(gen2)) => Ok(ParsedCard::Combined(from_json_value(gen1)?, from_json_value(gen2)?)),
+            (None, None) => Err(de::Error::custom("the card data has no generation")),
+        }
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl<TGen1, TGen2> schemars::JsonSchema for ParsedCard<TGen1, TGen2>
+where
+    TGen1: CardParser<TGen1> + schemars::JsonSchema,
+    TGen2: CardParser<TGen2> + schemars::JsonSchema,
+{
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        format!("ParsedCard_for_{}_and_{}", TGen1::schema_name(), TGen2::schema_name()).into()
+    }
+
+    fn schema_id() -> std::borrow::Cow<'static, str> {
+        format!("{}::ParsedCard<{}, {}>", module_path!(), TGen1::schema_id(), TGen2::schema_id()).into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "anyOf": [
+                {
+                    "type": "object",
+                    "properties": {
+                        GEN1: generator.subschema_for::<TGen1>(),
+                        GEN2: generator.subschema_for::<TGen2>(),
+                    },
+                    "minProperties": 1,
+                    "additionalProperties": false
+                },
+                { "type": "null" }
+            ]
+        })
+    }
+}
+
 #[derive(Debug)]
 pub enum CardResponseParameterData {
     Unsupported,
@@ -63,7 +129,53 @@ impl Serialize for CardResponseParameterData {
             CardResponseParameterData::CompanyCard(data) => data.serialize(serializer),
             CardResponseParameterData::WorkshopCard(data) => data.serialize(serializer),
             CardResponseParameterData::ControlCard(data) => data.serialize(serializer),
-            CardResponseParameterData::Unsupported => serializer.serialize_str("Unsupported"),
+            CardResponseParameterData::Unsupported => serializer.serialize_str(UNSUPPORTED),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for CardResponseParameterData {
+    /// Deserializes the card of the type stored in the application identification of
+    /// either generation, the cards of the different types are not distinguishable by
+    /// their fields alone.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        if value.as_str() == Some(UNSUPPORTED) {
+            return Ok(CardResponseParameterData::Unsupported);
         }
+        let card_type = [GEN1, GEN2].iter().filter_map(|generation| value.get(generation)).find_map(card_type_of_json_value);
+        match card_type {
+            Some(EquipmentType::DriverCard) => Ok(CardResponseParameterData::DriverCard(from_json_value(value)?)),
+            Some(EquipmentType::CompanyCard) => Ok(CardResponseParameterData::CompanyCard(from_json_value(value)?)),
+            Some(EquipmentType::WorkshopCard) => Ok(CardResponseParameterData::WorkshopCard(from_json_value(value)?)),
+            Some(EquipmentType::ControlCard) => Ok(CardResponseParameterData::ControlCard(from_json_value(value)?)),
+            _ => Err(de::Error::custom("unknown card type of the card data")),
+        }
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for CardResponseParameterData {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "CardResponseParameterData".into()
+    }
+
+    fn schema_id() -> std::borrow::Cow<'static, str> {
+        concat!(module_path!(), "::CardResponseParameterData").into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "anyOf": [
+                { "const": UNSUPPORTED },
+                generator.subschema_for::<ParsedCard<gen1::DriverCard, gen2::DriverCard>>(),
+                generator.subschema_for::<ParsedCard<gen1::CompanyCard, gen2::CompanyCard>>(),
+                generator.subschema_for::<ParsedCard<gen1::WorkshopCard, gen2::WorkshopCard>>(),
+                generator.subschema_for::<ParsedCard<gen1::ControlCard, gen2::ControlCard>>(),
+            ]
+        })
     }
 }
@@ -1,8 +1,9 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    BCDString, Readable, bytes_to_ia5_fix_string,
+    BCDString, Readable, ReadableField, SpanRecorder, Writable, bytes_to_ia5_fix_string,
     tacho::{OdometerShort, TimeReal, VehicleRegistrationIdentification, VehicleUse},
+    write_bcd_string, write_ia5_string,
 };
 
 const VU_DATA_BLOCK_COUNTER_LENGTH: u32 = 2;
@@ -11,7 +12,8 @@ const VEHICLE_IDENTIFICATION_NUMBER_LENGTH: u32 = 17;
 /// Information, stored in a driver or workshop card, related to a period of
 /// use of a vehicle during a calendar day (Annex 1C requirements 269,
 /// 294, 322, and 345).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardVehicleRecord {
     #[serde(rename = "vehicleOdometerBegin")]
     pub vehicle_odometer_begin: OdometerShort,
@@ -31,14 +33,17 @@ pub struct CardVehicleRecord {
 
 impl Readable<CardVehicleRecord> for CardVehicleRecord {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<CardVehicleRecord> {
-        let vehicle_odometer_begin = OdometerShort::read(reader)?;
-        let vehicle_odometer_end = OdometerShort::read(reader)?;
-        let vehicle_first_use = TimeReal::read(reader)?;
-        let vehicle_last_use = TimeReal::read(reader)?;
-        let vehicle_registration = VehicleRegistrationIdentification::read(reader)?;
-        let vu_data_block_counter = BCDString::decode(&reader.read_into_vec(VU_DATA_BLOCK_COUNTER_LENGTH)?)?;
-        let vehicle_identification_number =
-            bytes_to_ia5_fix_string(&reader.read_into_vec(VEHICLE_IDENTIFICATION_NUMBER_LENGTH)?)?;
+        let vehicle_odometer_begin = OdometerShort::read_field(reader, "vehicleOdometerBegin")?;
+        let vehicle_odometer_end = OdometerShort::read_field(reader, "vehicleOdometerEnd")?;
+        let vehicle_first_use = TimeReal::read_field(reader, "vehicleFirstUse")?;
+        let vehicle_last_use = TimeReal::read_field(reader, "vehicleLastUse")?;
+        let vehicle_registration = VehicleRegistrationIdentification::read_field(reader, "vehicleRegistration")?;
+        let vu_data_block_counter = SpanRecorder::field("vuDataBlockCounter", "BCDString", reader, |reader| {
+            BCDString::decode(&reader.read_into_vec(VU_DATA_BLOCK_COUNTER_LENGTH)?)
+        })?;
+        let vehicle_identification_number = SpanRecorder::field("vehicleIdentificationNumber", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(VEHICLE_IDENTIFICATION_NUMBER_LENGTH)?)?)
+        })?;
 
         Ok(Self {
             vehicle_odometer_begin,
@@ -61,3 +66,17 @@ impl VehicleUse for CardVehicleRecord {
         &self.vehicle_last_use
     }
 }
+
+impl Writable for CardVehicleRecord {
+    /// Writes a `CardVehicleRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.vehicle_odometer_begin.write(writer)?;
+        self.vehicle_odometer_end.write(writer)?;
+        self.vehicle_first_use.write(writer)?;
+        self.vehicle_last_use.write(writer)?;
+        self.vehicle_registration.write(writer)?;
+        write_bcd_string(writer, &self.vu_data_block_counter, VU_DATA_BLOCK_COUNTER_LENGTH as usize)?;
+        write_ia5_string(writer, &self.vehicle_identification_number, VEHICLE_IDENTIFICATION_NUMBER_LENGTH as usize)?;
+        Ok(())
+    }
+}
@@ -1,13 +1,17 @@
-use binary_data::{BigEndian, BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BigEndian, BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, ReadableWithParams, Result, bytes_to_ia5_fix_string, tacho::TimeReal};
+use crate::{
+    Readable, ReadableField, ReadableWithParams, Result, SpanRecorder, Writable, bytes_to_ia5_fix_string, tacho::TimeReal,
+    write_ia5_string,
+};
 
 const VU_SOFTWARE_VERSION_LENGTH: u32 = 4;
 
 /// Information, stored in a driver or workshop card, related to a vehicle
 /// unit that was used (Annex 1C requirement 303 and 351).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardVehicleUnitRecord {
     #[serde(rename = "timeStamp")]
     pub time_stamp: TimeReal,
@@ -21,10 +25,12 @@ pub struct CardVehicleUnitRecord {
 
 impl Readable<CardVehicleUnitRecord> for CardVehicleUnitRecord {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<CardVehicleUnitRecord> {
-        let time_stamp = TimeReal::read(reader)?;
-        let manufacturer_code = reader.read_u8()?;
-        let device_id = reader.read_u8()?;
-        let vu_software_version = bytes_to_ia5_fix_string(&reader.read_into_vec(VU_SOFTWARE_VERSION_LENGTH)?)?;
+        let time_stamp = TimeReal::read_field(reader, "timeStamp")?;
+        let manufacturer_code = SpanRecorder::field("manufacturerCode", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let device_id = SpanRecorder::field("deviceID", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let vu_software_version = SpanRecorder::field("vuSoftwareVersion", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(VU_SOFTWARE_VERSION_LENGTH)?)?)
+        })?;
         Ok(Self { time_stamp, manufacturer_code, device_id, vu_software_version })
     }
 }
@@ -42,7 +48,8 @@ impl CardVehicleUnitsUsedParams {
 
 /// Information, stored in a driver or workshop card, related to the vehicle
 /// units used by the card holder (Annex IC requirements 304 and 352).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CardVehicleUnitsUsed {
     #[serde(rename = "vehicleUnitPointerNewestRecord")]
     pub vehicle_unit_pointer_newest_record: u16,
@@ -54,10 +61,13 @@ impl ReadableWithParams<CardVehicleUnitsUsed> for CardVehicleUnitsUsed {
     type P = CardVehicleUnitsUsedParams;
 
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<CardVehicleUnitsUsed> {
-        let vehicle_unit_pointer_newest_record = reader.read_u16::<BigEndian>()?;
+        let vehicle_unit_pointer_newest_record =
+            SpanRecorder::field("vehicleUnitPointerNewestRecord", "INTEGER", reader, |reader| {
+                Ok(reader.read_u16::<BigEndian>()?)
+            })?;
         let mut records: Vec<CardVehicleUnitRecord> = Vec::new();
-        for _ in 0..params.no_of_card_vehicle_unit_records {
-            let record = CardVehicleUnitRecord::read(reader)?;
+        for index in 0..params.no_of_card_vehicle_unit_records {
+            let record = CardVehicleUnitRecord::read_field(reader, &format!("cardVehicleUnitRecords[{index}]"))?;
             if record.time_stamp.has_data() {
                 records.push(record);
             }
@@ -66,3 +76,26 @@ impl ReadableWithParams<CardVehicleUnitsUsed> for CardVehicleUnitsUsed {
         Ok(Self { vehicle_unit_pointer_newest_record, card_vehicle_unit_records: records })
     }
 }
+
+impl Writable for CardVehicleUnitRecord {
+    /// Writes a `CardVehicleUnitRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.time_stamp.write(writer)?;
+        writer.write_u8(self.manufacturer_code)?;
+        writer.write_u8(self.device_id)?;
+        write_ia5_string(writer, &self.vu_software_version, VU_SOFTWARE_VERSION_LENGTH as usize)?;
+        Ok(())
+    }
+}
+
+impl Writable for CardVehicleUnitsUsed {
+    /// Writes the pointer and the kept records, the card writer pads the card file with the
+    /// empty records.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u16::<BigEndian>(self.vehicle_unit_pointer_newest_record)?;
+        for record in self.card_vehicle_unit_records.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
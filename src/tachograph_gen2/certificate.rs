@@ -1,9 +1,10 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinMemoryBuffer, BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    ReadableWithParams, Result,
+    ReadableWithParams, ReadableWithParamsField, Result, SpanRecorder, Writable, field_value,
     gen2::{CertificateProfile, CertificateProfileParams},
+    write_to_vec,
 };
 
 #[derive(Debug)]
@@ -18,11 +19,27 @@ impl CertificateParams {
 }
 
 /// The certificate of a public key issued by a Certification Authority.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Certificate {
     #[serde(rename = "certificateProfile")]
     pub certificate_profile: Option<CertificateProfile>,
     pub data: Vec<u8>,
+    /// The bytes of the certificate when its parsed profile is not written back as it is
+    /// read, e.g. of a certificate filled with zeros. They are written as they are while
+    /// the certificate is not changed.
+    #[serde(rename = "rawData", default, skip_serializing_if = "Option::is_none")]
+    pub raw_data: Option<Vec<u8>>,
+}
+
+impl Certificate {
+    fn write_profile_and_data<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        if let Some(certificate_profile) = &self.certificate_profile {
+            certificate_profile.write(writer)?;
+        }
+        writer.write_all(&self.data)?;
+        Ok(())
+    }
 }
 
 impl ReadableWithParams<Certificate> for Certificate {
@@ -30,7 +47,9 @@ impl ReadableWithParams<Certificate> for Certificate {
 
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<Certificate> {
         if let Some(size) = params.size {
-            let certificate_profile = CertificateProfile::read(reader, &CertificateProfileParams::new(size))?;
+            let position = reader.pos()?;
+            let certificate_profile =
+                CertificateProfile::read_field_with(reader, &CertificateProfileParams::new(size), "certificateProfile")?;
             let data = if !reader.is_eof() {
                 let mut buff: Vec<u8> = Vec::new();
                 let _ = reader.read_to_end(&mut buff);
@@ -38,10 +57,39 @@ impl ReadableWithParams<Certificate> for Certificate {
             } else {
                 Vec::new()
             };
-            Ok(Self { certificate_profile: Some(certificate_profile), data })
+            let end_position = reader.pos()?;
+            reader.seek(position)?;
+            let read_data = reader.read_into_vec((end_position - position) as u32)?;
+            let mut certificate = Self { certificate_profile: Some(certificate_profile), data, raw_data: None };
+            if write_to_vec(|writer| certificate.write_profile_and_data(writer)).ok().as_ref() != Some(&read_data) {
+                certificate.raw_data = Some(read_data);
+            }
+            Ok(certificate)
         } else {
-            let data = reader.read_into_vec(reader.len()? as u32)?;
-            Ok(Self { certificate_profile: None, data })
+            let data =
+                SpanRecorder::field("data", "OCTET STRING", reader, |reader| Ok(reader.read_into_vec(reader.len()? as u32)?))?;
+            Ok(Self { certificate_profile: None, data, raw_data: None })
+        }
+    }
+}
+
+impl Writable for Certificate {
+    /// Writes the certificate profile, when it is parsed, followed by the rest of the raw data.
+    /// The certificate which is not changed after parsing is written with its `raw_data`.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        let data = write_to_vec(|writer| self.write_profile_and_data(writer))?;
+        if let Some(raw_data) = &self.raw_data {
+            let params = CertificateParams::new(Some(field_value("certificate length", raw_data.len())?));
+            let parsed = Certificate::read(&mut BinMemoryBuffer::from(raw_data.clone()), &params);
+            if parsed
+                .and_then(|parsed| write_to_vec(|writer| parsed.write_profile_and_data(writer)))
+                .is_ok_and(|parsed| parsed == data)
+            {
+                writer.write_all(raw_data)?;
+                return Ok(());
+            }
         }
+        writer.write_all(&data)?;
+        Ok(())
     }
 }
@@ -1,15 +1,16 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, Result,
+    Readable, ReadableField, Result, SpanRecorder, Writable, field_value,
     tacho::{CertificateContentType, CertificationAuthorityKid},
 };
 
 /// The Certificate Authority Reference shall be used to identify the public key to be used to verify the certificate signature.
 /// The Certificate Authority Reference shall therefore be equal to the Certificate Holder Reference in the certificate of the
 /// corresponding certificate authority.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CertificateAuthorityReference {
     #[serde(rename = "recordType")]
     pub record_type: CertificateContentType,
@@ -21,9 +22,20 @@ pub struct CertificateAuthorityReference {
 
 impl Readable<CertificateAuthorityReference> for CertificateAuthorityReference {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<CertificateAuthorityReference> {
-        let record_type: CertificateContentType = (reader.read_u8()? as u16).into();
-        let record_size = reader.read_u8()? as u16;
-        let certification_authority_kid = CertificationAuthorityKid::read(reader)?;
+        let record_type: CertificateContentType =
+            SpanRecorder::field("recordType", "CertificateContentType", reader, |reader| Ok((reader.read_u8()? as u16).into()))?;
+        let record_size = SpanRecorder::field("recordSize", "INTEGER", reader, |reader| Ok(reader.read_u8()? as u16))?;
+        let certification_authority_kid = CertificationAuthorityKid::read_field(reader, "certificationAuthorityKid")?;
         Ok(Self { record_type, record_size, certification_authority_kid })
     }
 }
+
+impl Writable for CertificateAuthorityReference {
+    /// Writes a `CertificateAuthorityReference` to a binary stream, the record type is
+    /// written as a single byte, as when it is read.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u8(field_value("recordType", u16::from(&self.record_type))?)?;
+        writer.write_u8(field_value("recordSize", self.record_size)?)?;
+        self.certification_authority_kid.write(writer)
+    }
+}
@@ -1,26 +1,39 @@
-use binary_data::{BigEndian, BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BigEndian, BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, Result,
+    Readable, ReadableField, Result, SpanRecorder, Writable, field_value,
     tacho::{CertificateContentType, TimeReal},
 };
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CertificateDate {
     #[serde(rename = "recordType")]
     pub record_type: CertificateContentType,
-    #[serde(rename = "recordType")]
+    #[serde(rename = "recordSize")]
     pub record_size: u16,
     pub date: TimeReal,
 }
 
 impl Readable<CertificateDate> for CertificateDate {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<CertificateDate> {
-        let record_type: CertificateContentType = reader.read_u16::<BigEndian>()?.into();
-        let record_size = reader.read_u8()? as u16;
-        let date = TimeReal::read(reader)?;
+        let record_type: CertificateContentType =
+            SpanRecorder::field("recordType", "CertificateContentType", reader, |reader| {
+                Ok(reader.read_u16::<BigEndian>()?.into())
+            })?;
+        let record_size = SpanRecorder::field("recordSize", "INTEGER", reader, |reader| Ok(reader.read_u8()? as u16))?;
+        let date = TimeReal::read_field(reader, "date")?;
 
         Ok(Self { record_type, record_size, date })
     }
 }
+
+impl Writable for CertificateDate {
+    /// Writes a `CertificateDate` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u16::<BigEndian>((&self.record_type).into())?;
+        writer.write_u8(field_value("recordSize", self.record_size)?)?;
+        self.date.write(writer)
+    }
+}
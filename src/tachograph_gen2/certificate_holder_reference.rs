@@ -1,12 +1,13 @@
-use binary_data::{BigEndian, BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BigEndian, BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, Result, tacho::CertificateContentType};
+use crate::{Readable, Result, SpanRecorder, Writable, field_value, tacho::CertificateContentType};
 
 /// The Certificate Holder Reference is an identifier for the
 /// public key provided in the certificate. It shall be used to
 /// reference this public key in other certificates.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CertificateHolderReference {
     #[serde(rename = "recordType")]
     pub record_type: CertificateContentType,
@@ -17,10 +18,23 @@ pub struct CertificateHolderReference {
 
 impl Readable<CertificateHolderReference> for CertificateHolderReference {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<CertificateHolderReference> {
-        let record_type: CertificateContentType = reader.read_u16::<BigEndian>()?.into();
-        let record_size = reader.read_u8()? as u16;
-        let data = reader.read_into_vec(record_size as u32)?;
+        let record_type: CertificateContentType =
+            SpanRecorder::field("recordType", "CertificateContentType", reader, |reader| {
+                Ok(reader.read_u16::<BigEndian>()?.into())
+            })?;
+        let record_size = SpanRecorder::field("recordSize", "INTEGER", reader, |reader| Ok(reader.read_u8()? as u16))?;
+        let data = SpanRecorder::field("data", "OCTET STRING", reader, |reader| Ok(reader.read_into_vec(record_size as u32)?))?;
 
         Ok(Self { record_type, record_size, data })
     }
 }
+
+impl Writable for CertificateHolderReference {
+    /// Writes a `CertificateHolderReference` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u16::<BigEndian>((&self.record_type).into())?;
+        writer.write_u8(field_value("data length", self.data.len())?)?;
+        writer.write_all(&self.data)?;
+        Ok(())
+    }
+}
@@ -1,8 +1,8 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, ReadableWithParams, Result,
+    ReadableField, ReadableWithParams, ReadableWithParamsField, Result, Writable, WritableWithParams, field_value,
     gen2::{
         CertificateAuthorityReference, CertificateDate, CertificateHolderReference, CertificateParams,
         CertificateProfileIdentifier, EccCertificate,
@@ -21,7 +21,8 @@ impl CertificateProfileParams {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CertificateProfile {
     #[serde(rename = "eccCertificate")]
     pub ecc_certificate: EccCertificate,
@@ -48,23 +49,25 @@ impl ReadableWithParams<CertificateProfile> for CertificateProfile {
 
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<CertificateProfile> {
         let pk_length = params.size;
-        let ecc_certificate = EccCertificate::read(reader, &CertificateParams::new(Some(2)))?;
-        let ecc_certificate_body = EccCertificate::read(reader, &CertificateParams::new(Some(2)))?;
-        let certificate_profile_identifier = CertificateProfileIdentifier::read(reader)?;
-        let certificate_authority_reference = CertificateAuthorityReference::read(reader)?;
-        let certificate_holder_authorisation = CertificateHolderAuthorisation::read(reader)?;
+        let ecc_certificate = EccCertificate::read_field_with(reader, &CertificateParams::new(Some(2)), "eccCertificate")?;
+        let ecc_certificate_body =
+            EccCertificate::read_field_with(reader, &CertificateParams::new(Some(2)), "eccCertificateBody")?;
+        let certificate_profile_identifier = CertificateProfileIdentifier::read_field(reader, "certificateProfileIdentifier")?;
+        let certificate_authority_reference = CertificateAuthorityReference::read_field(reader, "certificateAuthorityReference")?;
+        let certificate_holder_authorisation =
+            CertificateHolderAuthorisation::read_field(reader, "certificateHolderAuthorisation")?;
         let public_key = if pk_length == 204 {
             let params = PublicKeyParams::new(Some(78));
-            Some(PublicKey::read(reader, &params)?)
+            Some(PublicKey::read_field_with(reader, &params, "publicKey")?)
         } else if pk_length == 205 {
             let params = PublicKeyParams::new(Some(79));
-            Some(PublicKey::read(reader, &params)?)
+            Some(PublicKey::read_field_with(reader, &params, "publicKey")?)
         } else {
             None
         };
-        let certificate_holder_reference = CertificateHolderReference::read(reader)?;
-        let certificate_effective_date = CertificateDate::read(reader)?;
-        let certificate_expiration_date = CertificateDate::read(reader)?;
+        let certificate_holder_reference = CertificateHolderReference::read_field(reader, "certificateHolderReference")?;
+        let certificate_effective_date = CertificateDate::read_field(reader, "certificateEffectiveDate")?;
+        let certificate_expiration_date = CertificateDate::read_field(reader, "certificateExpirationDate")?;
 
         Ok(Self {
             ecc_certificate,
@@ -79,3 +82,24 @@ impl ReadableWithParams<CertificateProfile> for CertificateProfile {
         })
     }
 }
+
+impl Writable for CertificateProfile {
+    /// Writes a `CertificateProfile` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.ecc_certificate.write(writer, &CertificateParams::new(Some(2)))?;
+        self.ecc_certificate_body.write(writer, &CertificateParams::new(Some(2)))?;
+        self.certificate_profile_identifier.write(writer)?;
+        self.certificate_authority_reference.write(writer)?;
+        self.certificate_holder_authorisation.write(writer)?;
+        if let Some(public_key) = &self.public_key {
+            public_key.write(
+                writer,
+                &PublicKeyParams::new(Some(field_value("rsaKeyModulus length", public_key.rsa_key_modulus.len())?)),
+            )?;
+        }
+        self.certificate_holder_reference.write(writer)?;
+        self.certificate_effective_date.write(writer)?;
+        self.certificate_expiration_date.write(writer)?;
+        Ok(())
+    }
+}
@@ -1,11 +1,12 @@
-use binary_data::{BigEndian, BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BigEndian, BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, Result, tacho::CertificateContentType};
+use crate::{Readable, Result, SpanRecorder, Writable, field_value, tacho::CertificateContentType};
 
 /// Certificates shall use a Certificate Profile Identifier to
 /// indicate the certificate profile used. Version 1, shall be identified by a value of ‘00’.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CertificateProfileIdentifier {
     #[serde(rename = "recordType")]
     pub record_type: CertificateContentType,
@@ -16,10 +17,23 @@ pub struct CertificateProfileIdentifier {
 
 impl Readable<CertificateProfileIdentifier> for CertificateProfileIdentifier {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<CertificateProfileIdentifier> {
-        let record_type: CertificateContentType = reader.read_u16::<BigEndian>()?.into();
-        let record_size = reader.read_u8()? as u16;
-        let data = reader.read_into_vec(record_size as u32)?;
+        let record_type: CertificateContentType =
+            SpanRecorder::field("recordType", "CertificateContentType", reader, |reader| {
+                Ok(reader.read_u16::<BigEndian>()?.into())
+            })?;
+        let record_size = SpanRecorder::field("recordSize", "INTEGER", reader, |reader| Ok(reader.read_u8()? as u16))?;
+        let data = SpanRecorder::field("data", "OCTET STRING", reader, |reader| Ok(reader.read_into_vec(record_size as u32)?))?;
 
         Ok(Self { record_type, record_size, data })
     }
 }
+
+impl Writable for CertificateProfileIdentifier {
+    /// Writes a `CertificateProfileIdentifier` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u16::<BigEndian>((&self.record_type).into())?;
+        writer.write_u8(field_value("data length", self.data.len())?)?;
+        writer.write_all(&self.data)?;
+        Ok(())
+    }
+}
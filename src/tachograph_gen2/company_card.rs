@@ -2,18 +2,19 @@ use std::collections::HashMap;
 
 use binary_data::BinSeek;
 use log::{debug, trace};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::gen2::{CardResponseParameterData, Certificate, CertificateParams, CompanyCardApplicationIdentificationV2};
 use crate::tacho::{
-    Card, CardChipIdentification, CardFileData, CardFileID, CardGeneration, CardIccIdentification, CardParser,
+    Card, CardChipIdentification, CardFileData, CardFileID, CardGeneration, CardIccIdentification, CardParser, CardWriter,
     CompanyActivityData, CompanyActivityDataParams, CompanyActivityRecord, CompanyCardApplicationIdentification, DataFiles,
-    Identification, IdentificationParams,
+    Identification, IdentificationParams, write_card_file,
 };
-use crate::{Readable, ReadableWithParams, Result};
+use crate::{Diagnostic, ReadableField, ReadableWithParamsField, Result, Writable, write_to_vec};
 
 /// Company card application generation 2
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CompanyCard {
     #[serde(rename = "cardGeneration")]
     pub card_generation: CardGeneration,
@@ -36,6 +37,9 @@ pub struct CompanyCard {
     pub link_certificate: Option<Certificate>,
     #[serde(rename = "cardNotes")]
     pub card_notes: String,
+    /// Errors of the card files (EF) which could not be parsed in lenient mode.
+    #[serde(rename = "efErrors")]
+    pub ef_errors: HashMap<CardFileID, Diagnostic>,
     #[serde(rename = "dataFiles")]
     pub data_files: HashMap<CardFileID, CardFileData>,
 }
@@ -49,7 +53,7 @@ impl CompanyCard {
         data_files: HashMap<CardFileID, CardFileData>,
     ) -> Self {
         Self {
-            card_generation: CardGeneration::Gen1,
+            card_generation: CardGeneration::from_card_files(&data_files),
             card_chip_identification,
             card_icc_identification,
             application_identification,
@@ -60,65 +64,80 @@ impl CompanyCard {
             ca_certificate: None,
             link_certificate: None,
             card_notes,
+            ef_errors: HashMap::new(),
             data_files,
         }
     }
-}
-
-impl CardParser<CompanyCard> for CompanyCard {
-    fn parse(card_data_files: &HashMap<CardFileID, CardFileData>, card_notes: &str) -> Result<Box<CompanyCard>> {
-        let card_chip_identification = <dyn Card<CardResponseParameterData>>::parse_ic(card_data_files)?;
-        let card_icc_identification = <dyn Card<CardResponseParameterData>>::parse_icc(card_data_files)?;
-        let application_identification = <dyn Card<CardResponseParameterData>>::parse_card_application_identification::<
-            CompanyCardApplicationIdentification,
-        >(card_data_files)?;
 
-        let mut company_card = CompanyCard::new(
-            card_chip_identification,
-            card_icc_identification,
-            application_identification.clone(),
-            card_notes.to_owned(),
-            (*card_data_files).clone(),
-        );
-
-        for card_item in card_data_files.iter() {
-            debug!("CompanyCard::parse - ID: {:?}", card_item.0,);
-            let card_file = card_item.1;
-            let mut reader = card_file.data_into_reader()?;
+    fn parse_card_file(
+        &mut self,
+        card_file_id: &CardFileID,
+        card_file: &CardFileData,
+        application_identification: &CompanyCardApplicationIdentification,
+    ) -> Result<()> {
+        card_file.read_data_with(|reader| {
             debug!(
                 "CompanyCard::parse - ID: {:?}, Data Length: {:?}, Has Signature: {}",
-                card_item.0,
+                card_file_id,
                 reader.len()?,
                 card_file.signature.is_some()
             );
-            match card_item.0 {
+            match card_file_id {
                 CardFileID::ApplicationIdentificationV2 => {
-                    company_card.application_identification_v2 = Some(CompanyCardApplicationIdentificationV2::read(&mut reader)?);
+                    self.application_identification_v2 =
+                        Some(CompanyCardApplicationIdentificationV2::read_field(reader, "applicationIdentificationV2")?);
                 }
                 CardFileID::Identification => {
                     let params = IdentificationParams::new(application_identification.type_of_tachograph_card_id.clone());
-                    company_card.identification = Some(Identification::read(&mut reader, &params)?);
+                    self.identification = Some(Identification::read_field_with(reader, &params, "identification")?);
                 }
                 CardFileID::CompanyActivityData => {
                     let params = CompanyActivityDataParams::new(application_identification.no_of_company_activity_records);
-                    company_card.company_activity_data = Some(CompanyActivityData::read(&mut reader, &params)?);
+                    self.company_activity_data =
+                        Some(CompanyActivityData::read_field_with(reader, &params, "companyActivityData")?);
                 }
                 CardFileID::CardCertificate => {
                     let params = CertificateParams::new(None);
-                    company_card.ca_certificate = Some(Certificate::read(&mut reader, &params)?);
+                    self.ca_certificate = Some(Certificate::read_field_with(reader, &params, "caCertificate")?);
                 }
                 CardFileID::CACertificate => {
                     let params = CertificateParams::new(None);
-                    company_card.card_certificate = Some(Certificate::read(&mut reader, &params)?);
+                    self.card_certificate = Some(Certificate::read_field_with(reader, &params, "cardCertificate")?);
                 }
                 CardFileID::LinkCertificate => {
                     let params = CertificateParams::new(None);
-                    company_card.link_certificate = Some(Certificate::read(&mut reader, &params)?);
+                    self.link_certificate = Some(Certificate::read_field_with(reader, &params, "linkCertificate")?);
                 }
                 CardFileID::IC | CardFileID::ICC | CardFileID::ApplicationIdentification => {
-                    trace!("CompanyCard::parse - Already parsed: {:?}", card_item.0)
+                    trace!("CompanyCard::parse - Already parsed: {:?}", card_file_id)
                 }
-                _ => trace!("CompanyCard::parse - Not Parsed: {:?}", card_item.0),
+                _ => trace!("CompanyCard::parse - Not Parsed: {:?}", card_file_id),
+            }
+            Ok(())
+        })
+    }
+}
+
+impl CardParser<CompanyCard> for CompanyCard {
+    fn parse(card_data_files: &HashMap<CardFileID, CardFileData>, card_notes: &str) -> Result<Box<CompanyCard>> {
+        let card_chip_identification = <dyn Card<CardResponseParameterData>>::parse_ic(card_data_files)?;
+        let card_icc_identification = <dyn Card<CardResponseParameterData>>::parse_icc(card_data_files)?;
+        let application_identification = <dyn Card<CardResponseParameterData>>::parse_card_application_identification::<
+            CompanyCardApplicationIdentification,
+        >(card_data_files)?;
+
+        let mut company_card = CompanyCard::new(
+            card_chip_identification,
+            card_icc_identification,
+            application_identification.clone(),
+            card_notes.to_owned(),
+            (*card_data_files).clone(),
+        );
+
+        for card_item in card_data_files.iter() {
+            debug!("CompanyCard::parse - ID: {:?}", card_item.0,);
+            if let Err(error) = company_card.parse_card_file(card_item.0, card_item.1, &application_identification) {
+                <dyn Card<CardResponseParameterData>>::add_card_file_error(&mut company_card.ef_errors, card_item.0, error)?;
             }
         }
 
@@ -131,3 +150,22 @@ impl DataFiles for CompanyCard {
         &self.data_files
     }
 }
+
+impl CardWriter for CompanyCard {
+    fn write_card_file(&self, card_file_id: &CardFileID) -> Result<Option<Vec<u8>>> {
+        let application_identification = &self.application_identification;
+        match card_file_id {
+            CardFileID::IC => write_to_vec(|writer| self.card_chip_identification.write(writer)).map(Some),
+            CardFileID::ICC => write_to_vec(|writer| self.card_icc_identification.write(writer)).map(Some),
+            CardFileID::ApplicationIdentification => write_to_vec(|writer| application_identification.write(writer)).map(Some),
+            CardFileID::ApplicationIdentificationV2 => write_card_file(&self.application_identification_v2),
+            CardFileID::Identification => write_card_file(&self.identification),
+            CardFileID::CompanyActivityData => write_card_file(&self.company_activity_data),
+            // The certificates are kept swapped as they are parsed.
+            CardFileID::CardCertificate => write_card_file(&self.ca_certificate),
+            CardFileID::CACertificate => write_card_file(&self.card_certificate),
+            CardFileID::LinkCertificate => write_card_file(&self.link_certificate),
+            _ => Ok(None),
+        }
+    }
+}
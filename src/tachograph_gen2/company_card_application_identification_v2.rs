@@ -1,11 +1,12 @@
 use binary_data::BigEndian;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::Readable;
+use crate::{Readable, SpanRecorder, Writable};
 
 /// Information, stored in a company card related to the identification of the
 /// application of the card (Annex IC requirement 375a).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CompanyCardApplicationIdentificationV2 {
     #[serde(rename = "lengthOfFollowingData")]
     pub length_of_following_data: u16,
@@ -17,9 +18,20 @@ impl Readable<CompanyCardApplicationIdentificationV2> for CompanyCardApplication
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(
         reader: &mut R,
     ) -> crate::Result<CompanyCardApplicationIdentificationV2> {
-        let length_of_following_data = reader.read_u16::<BigEndian>()?;
-        let vu_configuration_length_range = reader.read_u16::<BigEndian>()?;
+        let length_of_following_data =
+            SpanRecorder::field("lengthOfFollowingData", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
+        let vu_configuration_length_range =
+            SpanRecorder::field("vuConfigurationLengthRange", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
 
         Ok(Self { length_of_following_data, vu_configuration_length_range })
     }
 }
+
+impl Writable for CompanyCardApplicationIdentificationV2 {
+    /// Writes a `CompanyCardApplicationIdentificationV2` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u16::<BigEndian>(self.length_of_following_data)?;
+        writer.write_u16::<BigEndian>(self.vu_configuration_length_range)?;
+        Ok(())
+    }
+}
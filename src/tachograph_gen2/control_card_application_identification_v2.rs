@@ -1,11 +1,12 @@
 use binary_data::BigEndian;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::Readable;
+use crate::{Readable, SpanRecorder, Writable};
 
 /// Information, stored in a control card related to the identification of the
 /// application of the card (Annex IC requirement 363a).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ControlCardApplicationIdentificationV2 {
     #[serde(rename = "lengthOfFollowingData")]
     pub length_of_following_data: u16,
@@ -17,9 +18,20 @@ impl Readable<ControlCardApplicationIdentificationV2> for ControlCardApplication
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(
         reader: &mut R,
     ) -> crate::Result<ControlCardApplicationIdentificationV2> {
-        let length_of_following_data = reader.read_u16::<BigEndian>()?;
-        let vu_configuration_length_range = reader.read_u16::<BigEndian>()?;
+        let length_of_following_data =
+            SpanRecorder::field("lengthOfFollowingData", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
+        let vu_configuration_length_range =
+            SpanRecorder::field("vuConfigurationLengthRange", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
 
         Ok(Self { length_of_following_data, vu_configuration_length_range })
     }
 }
+
+impl Writable for ControlCardApplicationIdentificationV2 {
+    /// Writes a `ControlCardApplicationIdentificationV2` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u16::<BigEndian>(self.length_of_following_data)?;
+        writer.write_u16::<BigEndian>(self.vu_configuration_length_range)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::Serialize;
+
+use crate::{Readable, ReadableField, Result, Writable, write_to_vec};
+
+/// Reads the records of a cyclic card file (EF) and returns the used ones
+/// ordered from the oldest to the newest. The record at `pointer_newest_record`
+/// is the last one written, so the oldest record follows it.
+pub(crate) fn read_cyclic_records<T: Readable<T> + Serialize, R: ReadBytes + BinSeek>(
+    reader: &mut R,
+    no_of_records: usize,
+    pointer_newest_record: usize,
+    path: &str,
+    is_used: impl Fn(&T) -> bool,
+) -> Result<Vec<T>> {
+    let mut records: Vec<T> = Vec::with_capacity(no_of_records);
+    for index in 0..no_of_records {
+        records.push(T::read_field(reader, &format!("{path}[{index}]"))?);
+    }
+    if pointer_newest_record < records.len() {
+        records.rotate_left(pointer_newest_record + 1);
+    }
+    Ok(records.into_iter().filter(|record| is_used(record)).collect())
+}
+
+/// Writes the records read by `read_cyclic_records`, the newest record is
+/// written at `pointer_newest_record` and the older ones before it. The
+/// unused records are written as zeros, without records nothing is written
+/// and the card writer pads the card file.
+pub(crate) fn write_cyclic_records<T: Writable, W: WriteBytes + BinSeek>(
+    writer: &mut W,
+    records: &[T],
+    no_of_records: usize,
+    pointer_newest_record: usize,
+) -> Result<()> {
+    let records = records.iter().map(|record| write_to_vec(|buffer| record.write(buffer))).collect::<Result<Vec<_>>>()?;
+    let Some(record_length) = records.first().map(|record| record.len()) else {
+        return Ok(());
+    };
+    let no_of_records = no_of_records.max(records.len());
+    // Without a valid pointer the records were read in the order of the card file.
+    let first_index =
+        if pointer_newest_record < no_of_records { pointer_newest_record + 1 + no_of_records - records.len() } else { 0 };
+    let mut slots = vec![vec![0; record_length]; no_of_records];
+    for (index, record) in records.into_iter().enumerate() {
+        slots[(first_index + index) % no_of_records] = record;
+    }
+    for slot in slots {
+        writer.write_all(&slot)?;
+    }
+    Ok(())
+}
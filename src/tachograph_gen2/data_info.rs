@@ -1,10 +1,13 @@
-use binary_data::{BigEndian, BinMemoryBuffer, BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BigEndian, BinMemoryBuffer, BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Error, Readable, ReadableWithParams,
+    Error, ParseContext, Readable, ReadableWithParams, SpanRecorder, SpanSection, Writable,
+    common::short_type_name,
     error::Result,
+    record_count,
     tacho::{RecordType, VUTransferResponseParameterID},
+    write_to_vec,
 };
 
 #[derive(Debug)]
@@ -13,9 +16,37 @@ pub struct DataConfig {
     pub record_type: RecordType,
     pub record_size: u16,
     pub no_of_records: u16,
+    /// Position of the first record in the source data.
+    pub offset: usize,
 }
 
-#[derive(Debug, Serialize)]
+impl DataConfig {
+    /// Reads all records with `read_record` as the fields `records[index]`. In lenient mode a record which
+    /// can not be parsed is reported and skipped, the next record starts `record_size` bytes further.
+    pub(crate) fn read_records<T: Serialize, R: ReadBytes + BinSeek>(
+        &self,
+        reader: &mut R,
+        read_record: impl Fn(&mut R) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        let mut records: Vec<T> = Vec::with_capacity(self.no_of_records as usize);
+        for index in 0..self.no_of_records as usize {
+            let position = reader.pos()?;
+            let field = format!("records[{index}]");
+            match SpanRecorder::field(&field, &short_type_name::<T>(), reader, &read_record) {
+                Ok(record) => records.push(record),
+                Err(error) => {
+                    let error = error.at_offset(self.offset + reader.pos()?).in_section(format!("{:?}", self.trep_id));
+                    ParseContext::recover(error)?;
+                    reader.seek(position + self.record_size as usize)?;
+                }
+            }
+        }
+        Ok(records)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DataInfo {
     #[serde(rename = "trepId")]
     pub trep_id: VUTransferResponseParameterID,
@@ -26,6 +57,9 @@ pub struct DataInfo {
     #[serde(rename = "noOfRecords")]
     pub no_of_records: u16,
     pub data: Vec<u8>,
+    /// Position of `data` in the source data.
+    #[serde(skip)]
+    pub offset: usize,
 }
 
 impl DataInfo {
@@ -35,6 +69,7 @@ impl DataInfo {
             record_type: self.record_type.clone(),
             record_size: self.record_size,
             no_of_records: self.no_of_records,
+            offset: self.offset,
         }
     }
 
@@ -43,28 +78,105 @@ impl DataInfo {
     }
 
     pub fn read<R: ReadBytes + BinSeek>(reader: &mut R, trep_id: VUTransferResponseParameterID) -> Result<DataInfo> {
-        let record_type = RecordType::from(reader.read_u8()?);
+        let record_type =
+            SpanRecorder::field("record_type", "RecordType", reader, |reader| Ok(RecordType::from(reader.read_u8()?)))?;
         let data_size = reader.read_u16::<BigEndian>()?;
-        let no_of_records = reader.read_u16::<BigEndian>()?;
+        let no_of_records =
+            SpanRecorder::field("no_of_records", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
         let full_data_size: u32 = data_size as u32 * no_of_records as u32;
-        let data = reader.read_into_vec(full_data_size)?;
+        let offset = SpanRecorder::absolute(reader.pos()?);
+        let data = SpanRecorder::field("data", "OCTET STRING", reader, |reader| Ok(reader.read_into_vec(full_data_size)?))?;
 
-        Ok(DataInfo { trep_id, record_type, record_size: data_size, no_of_records, data })
+        Ok(DataInfo { trep_id, record_type, record_size: data_size, no_of_records, data, offset })
     }
 
-    pub fn parse<T: DataInfoReadable<T>>(&self) -> Result<T> {
+    /// Starts the span section of the data, the positions of the data reader are relative to it.
+    pub(crate) fn span_section(&self) -> SpanSection {
+        SpanRecorder::section(format!("{:?}", self.record_type), self.offset, self.data.len())
+    }
+
+    pub fn parse<T: DataInfoReadable<T> + Serialize>(&self) -> Result<T> {
+        let _section = self.span_section();
         let config = self.create_data_config();
         let mut reader = BinMemoryBuffer::from(self.data.clone());
         T::read(&mut reader, &config)
     }
 
-    pub fn parse_with_params<T: DataInfoReadableWithParams<T>>(&self) -> Result<T> {
+    pub fn parse_with_params<T: DataInfoReadableWithParams<T> + Serialize>(&self) -> Result<T> {
+        let _section = self.span_section();
         let config = self.create_data_config();
         let mut reader = BinMemoryBuffer::from(self.data.clone());
         T::read(&mut reader, &config)
     }
 }
 
+impl Writable for DataInfo {
+    /// Writes the record type, the record size and the number of records followed by the data.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u8((&self.record_type).into())?;
+        writer.write_u16::<BigEndian>(self.record_size)?;
+        writer.write_u16::<BigEndian>(self.no_of_records)?;
+        writer.write_all(&self.data)?;
+        Ok(())
+    }
+}
+
+/// Reads the record arrays of a TREP one after the other. In lenient mode the first
+/// record array which can not be read, e.g. of a truncated TREP, is reported and it and
+/// the record arrays after it are read as empty record arrays.
+pub(crate) struct DataInfoReader<'a, R> {
+    reader: &'a mut R,
+    trep_id: VUTransferResponseParameterID,
+    is_truncated: bool,
+}
+
+impl<'a, R: ReadBytes + BinSeek> DataInfoReader<'a, R> {
+    pub(crate) fn new(reader: &'a mut R, trep_id: VUTransferResponseParameterID) -> Self {
+        Self { reader, trep_id, is_truncated: false }
+    }
+
+    /// Reads the next record array, an empty record array of `record_type` if it can not
+    /// be read in lenient mode.
+    pub(crate) fn next(&mut self, record_type: RecordType) -> Result<DataInfo> {
+        if !self.is_truncated {
+            let position = self.reader.pos()?;
+            match DataInfo::read(self.reader, self.trep_id.clone()) {
+                Ok(data_info) => return Ok(data_info),
+                Err(error) => {
+                    let error = error.at_offset(SpanRecorder::absolute(position)).in_section(format!("{:?}", self.trep_id));
+                    ParseContext::recover(error)?;
+                    self.is_truncated = true;
+                }
+            }
+        }
+        Ok(DataInfo { trep_id: self.trep_id.clone(), record_type, record_size: 0, no_of_records: 0, data: Vec::new(), offset: 0 })
+    }
+}
+
+/// Writes the header of a record array, the number of records is the number of `records`,
+/// followed by the records written with `write_record`. The records shorter than `record_size`,
+/// e.g. without the data which is not kept when the record is read, are padded with zeros.
+pub(crate) fn write_record_array<W: WriteBytes + BinSeek, T>(
+    writer: &mut W,
+    record_type: &RecordType,
+    record_size: u16,
+    records: &[T],
+    write_record: impl Fn(&mut BinMemoryBuffer, &T) -> Result<()>,
+) -> Result<()> {
+    writer.write_u8(record_type.into())?;
+    writer.write_u16::<BigEndian>(record_size)?;
+    writer.write_u16::<BigEndian>(record_count(records.len())?)?;
+    for record in records.iter() {
+        let mut data = write_to_vec(|writer| write_record(writer, record))?;
+        if data.len() > record_size as usize {
+            return Err(Error::InvalidDataEncode(format!("{record_type:?} is longer than {record_size} bytes")));
+        }
+        data.resize(record_size as usize, 0);
+        writer.write_all(&data)?;
+    }
+    Ok(())
+}
+
 pub trait DataInfoReadable<T> {
     fn read<R: ReadBytes + BinSeek>(_reader: &mut R, _config: &DataConfig) -> Result<T> {
         Err(Error::NotImplemented)
@@ -77,7 +189,8 @@ pub trait DataInfoReadableWithParams<T> {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DataInfoGenericRecordArray<T> {
     #[serde(rename = "noOfRecords")]
     pub no_of_records: u16,
@@ -88,34 +201,33 @@ pub struct DataInfoGenericRecordArray<T> {
     pub records: Vec<T>,
 }
 
-impl<T: Readable<T>> DataInfoReadable<DataInfoGenericRecordArray<T>> for DataInfoGenericRecordArray<T> {
+impl<T: Readable<T> + Serialize> DataInfoReadable<DataInfoGenericRecordArray<T>> for DataInfoGenericRecordArray<T> {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, config: &DataConfig) -> Result<DataInfoGenericRecordArray<T>> {
         let no_of_records = config.no_of_records;
         let record_size = config.record_size;
         let record_type = config.record_type.clone();
 
-        let mut records: Vec<T> = Vec::with_capacity(no_of_records as usize);
-        for _ in 0..no_of_records {
-            let record = T::read(reader)?;
-            records.push(record);
-        }
+        let records: Vec<T> = config.read_records(reader, |reader| T::read(reader))?;
         Ok(Self { no_of_records, record_size, record_type, records })
     }
 }
 
-impl<T: ReadableWithParams<T, P = VUTransferResponseParameterID>> DataInfoReadableWithParams<DataInfoGenericRecordArray<T>>
-    for DataInfoGenericRecordArray<T>
+impl<T: ReadableWithParams<T, P = VUTransferResponseParameterID> + Serialize>
+    DataInfoReadableWithParams<DataInfoGenericRecordArray<T>> for DataInfoGenericRecordArray<T>
 {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, config: &DataConfig) -> Result<DataInfoGenericRecordArray<T>> {
         let no_of_records = config.no_of_records;
         let record_size = config.record_size;
         let record_type = config.record_type.clone();
 
-        let mut records: Vec<T> = Vec::with_capacity(no_of_records as usize);
-        for _ in 0..no_of_records {
-            let record = T::read(reader, &config.trep_id)?;
-            records.push(record);
-        }
+        let records: Vec<T> = config.read_records(reader, |reader| T::read(reader, &config.trep_id))?;
         Ok(Self { no_of_records, record_size, record_type, records })
     }
 }
+
+impl<T: Writable> Writable for DataInfoGenericRecordArray<T> {
+    /// Writes the record array with the records written by `Writable`.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        write_record_array(writer, &self.record_type, self.record_size, &self.records, |writer, record| record.write(writer))
+    }
+}
@@ -2,26 +2,30 @@ use std::collections::HashMap;
 
 use binary_data::BinSeek;
 use log::{debug, trace};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, ReadableWithParams, Result,
+    Diagnostic, Error, ReadableField, ReadableWithParamsField, Result, Writable,
     gen2::{
-        CardResponseParameterData, CardVehicleRecord, CardVehicleUnitsUsed, CardVehicleUnitsUsedParams, Certificate,
-        CertificateParams, DriverCardApplicationIdentification, DriverCardApplicationIdentificationV2, GnssAccumulatedDriving,
-        GnssAccumulatedDrivingParams, PlaceRecord, SpecificConditions, SpecificConditionsParams,
+        CardBorderCrossings, CardBorderCrossingsParams, CardLoadTypeEntries, CardLoadTypeEntriesParams, CardLoadUnloadOperations,
+        CardLoadUnloadOperationsParams, CardResponseParameterData, CardVehicleRecord, CardVehicleUnitsUsed,
+        CardVehicleUnitsUsedParams, Certificate, CertificateParams, DriverCardApplicationIdentification,
+        DriverCardApplicationIdentificationV2, GnssAccumulatedDriving, GnssAccumulatedDrivingParams, PlaceRecord,
+        SpecificConditions, SpecificConditionsParams, VuConfigurations, VuConfigurationsParams,
     },
     tacho::{
         Card, CardChipIdentification, CardControlActivityDataRecord, CardCurrentUse, CardDriverActivity,
         CardDriverActivityParams, CardDrivingLicenceInformation, CardEventData, CardEventDataParams, CardFaultData,
         CardFaultDataParams, CardFileData, CardFileID, CardGeneration, CardIccIdentification, CardParser,
-        CardPlaceDailyWorkPeriod, CardPlaceDailyWorkPeriodParams, CardVehiclesUsed, DataFiles, Identification,
-        IdentificationParams, TimeReal, VehiclesUsedParams,
+        CardPlaceDailyWorkPeriod, CardPlaceDailyWorkPeriodParams, CardVehiclesUsed, CardWriter, DataFiles, Identification,
+        IdentificationParams, TimeReal, VehiclesUsedParams, write_card_file, write_card_file_with_params,
     },
+    write_to_vec,
 };
 
 /// Driver card application generation 2
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DriverCard {
     #[serde(rename = "cardGeneration")]
     pub card_generation: CardGeneration,
@@ -57,6 +61,14 @@ pub struct DriverCard {
     pub vehicle_units_used: Option<CardVehicleUnitsUsed>,
     #[serde(rename = "gnssPlaces")]
     pub gnss_places: Option<GnssAccumulatedDriving>,
+    #[serde(rename = "borderCrossings")]
+    pub border_crossings: Option<CardBorderCrossings>,
+    #[serde(rename = "loadUnloadOperations")]
+    pub load_unload_operations: Option<CardLoadUnloadOperations>,
+    #[serde(rename = "loadTypeEntries")]
+    pub load_type_entries: Option<CardLoadTypeEntries>,
+    #[serde(rename = "vuConfigurations")]
+    pub vu_configurations: Option<VuConfigurations>,
     #[serde(rename = "cardCertificate")]
     pub card_certificate: Option<Certificate>,
     #[serde(rename = "caCertificate")]
@@ -67,6 +79,9 @@ pub struct DriverCard {
     pub link_certificate: Option<Certificate>,
     #[serde(rename = "cardNotes")]
     pub card_notes: String,
+    /// Errors of the card files (EF) which could not be parsed in lenient mode.
+    #[serde(rename = "efErrors")]
+    pub ef_errors: HashMap<CardFileID, Diagnostic>,
     #[serde(rename = "dataFiles")]
     pub data_files: HashMap<CardFileID, CardFileData>,
 }
@@ -80,7 +95,7 @@ impl DriverCard {
         data_files: HashMap<CardFileID, CardFileData>,
     ) -> Self {
         Self {
-            card_generation: CardGeneration::Gen2,
+            card_generation: CardGeneration::from_card_files(&data_files),
             card_chip_identification,
             card_icc_identification,
             application_identification,
@@ -97,136 +112,194 @@ impl DriverCard {
             driving_license_info: None,
             specific_conditions: None,
             vehicle_units_used: None,
+            border_crossings: None,
+            load_unload_operations: None,
+            load_type_entries: None,
+            vu_configurations: None,
             card_certificate: None,
             gnss_places: None,
             ca_certificate: None,
             card_sign_certificate: None,
             link_certificate: None,
             card_notes,
+            ef_errors: HashMap::new(),
             data_files,
         }
     }
-}
 
-impl CardParser<DriverCard> for DriverCard {
-    fn parse(card_data_files: &HashMap<CardFileID, CardFileData>, card_notes: &str) -> Result<Box<DriverCard>> {
-        let card_chip_identification = <dyn Card<CardResponseParameterData>>::parse_ic(card_data_files)?;
-        let card_icc_identification = <dyn Card<CardResponseParameterData>>::parse_icc(card_data_files)?;
-        let application_identification = <dyn Card<CardResponseParameterData>>::parse_card_application_identification::<
-            DriverCardApplicationIdentification,
-        >(card_data_files)?;
-        debug!("DriverCard::parse - Application Identification: {application_identification:?}");
-
-        let mut driver_card = DriverCard::new(
-            card_chip_identification,
-            card_icc_identification,
-            application_identification.clone(),
-            card_notes.to_owned(),
-            (*card_data_files).clone(),
-        );
+    fn application_identification_v2(&self) -> Result<&DriverCardApplicationIdentificationV2> {
+        self.application_identification_v2
+            .as_ref()
+            .ok_or_else(|| Error::MissingCardFile(CardFileID::ApplicationIdentificationV2.to_string()))
+    }
 
-        for card_item in card_data_files.iter() {
-            debug!("DriverCard::parse - ID: {:?}", card_item.0,);
-            let card_file = card_item.1;
-            let mut reader = card_file.data_into_reader()?;
+    fn parse_card_file(
+        &mut self,
+        card_file_id: &CardFileID,
+        card_file: &CardFileData,
+        application_identification: &DriverCardApplicationIdentification,
+    ) -> Result<()> {
+        card_file.read_data_with(|reader| {
             debug!(
                 "DriverCard::parse - ID: {:?}, Data Length: {:?}, Has Signature: {}",
-                card_item.0,
+                card_file_id,
                 reader.len()?,
                 card_file.signature.is_some()
             );
-            match card_item.0 {
+            match card_file_id {
                 CardFileID::ApplicationIdentificationV2 => {
-                    driver_card.application_identification_v2 = Some(DriverCardApplicationIdentificationV2::read(&mut reader)?);
+                    self.application_identification_v2 =
+                        Some(DriverCardApplicationIdentificationV2::read_field(reader, "applicationIdentificationV2")?);
                 }
                 CardFileID::CardDownload => {
-                    driver_card.card_download = Some(TimeReal::read(&mut reader)?);
+                    self.card_download = Some(TimeReal::read_field(reader, "cardDownload")?);
                 }
                 CardFileID::EventsData => {
                     debug!(
                         "DriverCard::parse - ID: {:?}, Number Of Records: {:?}",
-                        card_item.0, application_identification.no_events_per_type,
+                        card_file_id, application_identification.no_events_per_type,
                     );
                     let params = CardEventDataParams::new(11, application_identification.no_events_per_type);
-                    driver_card.events_data = Some(CardEventData::read(&mut reader, &params)?);
+                    self.events_data = Some(CardEventData::read_field_with(reader, &params, "eventsData")?);
                 }
                 CardFileID::FaultsData => {
                     debug!(
                         "DriverCard::parse - ID: {:?}, Number Of Records: {:?}",
-                        card_item.0, application_identification.no_faults_per_type,
+                        card_file_id, application_identification.no_faults_per_type,
                     );
                     let params = CardFaultDataParams::new(application_identification.no_faults_per_type);
-                    driver_card.faults_data = Some(CardFaultData::read(&mut reader, &params)?);
+                    self.faults_data = Some(CardFaultData::read_field_with(reader, &params, "faultsData")?);
                 }
                 CardFileID::DriverActivityData => {
                     debug!(
                         "DriverCard::parse - ID: {:?}, Number Of Records: {:?}",
-                        card_item.0, application_identification.activity_structure_length,
+                        card_file_id, application_identification.activity_structure_length,
                     );
                     let params = CardDriverActivityParams::new(application_identification.activity_structure_length);
-                    driver_card.driver_activity_data = Some(CardDriverActivity::read(&mut reader, &params)?);
+                    self.driver_activity_data = Some(CardDriverActivity::read_field_with(reader, &params, "driverActivityData")?);
                 }
                 CardFileID::VehiclesUsed => {
                     debug!(
                         "DriverCard::parse - ID: {:?}, Number Of Records: {:?}",
-                        card_item.0, application_identification.no_of_card_vehicle_records,
+                        card_file_id, application_identification.no_of_card_vehicle_records,
                     );
                     let params = VehiclesUsedParams::new(application_identification.no_of_card_vehicle_records);
-                    driver_card.vehicles_used = Some(CardVehiclesUsed::<CardVehicleRecord>::read(&mut reader, &params)?);
+                    self.vehicles_used =
+                        Some(CardVehiclesUsed::<CardVehicleRecord>::read_field_with(reader, &params, "vehiclesUsed")?);
                 }
                 CardFileID::Places => {
                     debug!(
                         "DriverCard::parse - ID: {:?}, Number Of Records: {:?}",
-                        card_item.0, application_identification.no_of_card_place_records,
+                        card_file_id, application_identification.no_of_card_place_records,
                     );
                     let params = CardPlaceDailyWorkPeriodParams::new(application_identification.no_of_card_place_records, 2);
-                    driver_card.places = Some(CardPlaceDailyWorkPeriod::<PlaceRecord>::read(&mut reader, &params)?);
+                    self.places = Some(CardPlaceDailyWorkPeriod::<PlaceRecord>::read_field_with(reader, &params, "places")?);
                 }
                 CardFileID::CurrentUsage => {
-                    driver_card.current_usage = Some(CardCurrentUse::read(&mut reader)?);
+                    self.current_usage = Some(CardCurrentUse::read_field(reader, "currentUsage")?);
                 }
                 CardFileID::ControlActivityData => {
-                    driver_card.control_activity_data = Some(CardControlActivityDataRecord::read(&mut reader)?);
+                    self.control_activity_data = Some(CardControlActivityDataRecord::read_field(reader, "controlActivityData")?);
                 }
                 CardFileID::Identification => {
                     let params = IdentificationParams::new(application_identification.type_of_tachograph_card_id.clone());
-                    driver_card.identification = Some(Identification::read(&mut reader, &params)?);
+                    self.identification = Some(Identification::read_field_with(reader, &params, "identification")?);
                 }
                 CardFileID::DrivingLicenseInfo => {
-                    driver_card.driving_license_info = Some(CardDrivingLicenceInformation::read(&mut reader)?);
+                    self.driving_license_info =
+                        Some(CardDrivingLicenceInformation::read_field(reader, "drivingLicenceInformation")?);
                 }
                 CardFileID::SpecificConditions => {
                     let params = SpecificConditionsParams::new(application_identification.no_of_specific_condition_records as u8);
-                    driver_card.specific_conditions = Some(SpecificConditions::read(&mut reader, &params)?);
+                    self.specific_conditions = Some(SpecificConditions::read_field_with(reader, &params, "specificConditions")?);
                 }
                 CardFileID::VehicleUnitsUsed => {
                     let params = CardVehicleUnitsUsedParams::new(application_identification.no_card_vehicle_units_records);
-                    driver_card.vehicle_units_used = Some(CardVehicleUnitsUsed::read(&mut reader, &params)?);
+                    self.vehicle_units_used = Some(CardVehicleUnitsUsed::read_field_with(reader, &params, "vehicleUnitsUsed")?);
                 }
                 CardFileID::GnssPlaces => {
                     let params = GnssAccumulatedDrivingParams::new(application_identification.no_gnssad_records);
-                    driver_card.gnss_places = Some(GnssAccumulatedDriving::read(&mut reader, &params)?);
+                    self.gnss_places = Some(GnssAccumulatedDriving::read_field_with(reader, &params, "gnssPlaces")?);
+                }
+                CardFileID::BorderCrossings => {
+                    let params =
+                        CardBorderCrossingsParams::new(self.application_identification_v2()?.no_of_border_crossing_records);
+                    self.border_crossings = Some(CardBorderCrossings::read_field_with(reader, &params, "borderCrossings")?);
+                }
+                CardFileID::LoadUnloadOperations => {
+                    let params =
+                        CardLoadUnloadOperationsParams::new(self.application_identification_v2()?.no_of_load_unload_records);
+                    self.load_unload_operations =
+                        Some(CardLoadUnloadOperations::read_field_with(reader, &params, "loadUnloadOperations")?);
+                }
+                CardFileID::LoadTypeEntries => {
+                    let params =
+                        CardLoadTypeEntriesParams::new(self.application_identification_v2()?.no_of_load_type_entry_records);
+                    self.load_type_entries = Some(CardLoadTypeEntries::read_field_with(reader, &params, "loadTypeEntries")?);
+                }
+                CardFileID::VUConfiguration => {
+                    let params = VuConfigurationsParams::new(self.application_identification_v2()?.vu_configuration_length_range);
+                    self.vu_configurations = Some(VuConfigurations::read_field_with(reader, &params, "vuConfigurations")?);
                 }
                 CardFileID::CardCertificate => {
                     let params = CertificateParams::new(None);
-                    driver_card.card_certificate = Some(Certificate::read(&mut reader, &params)?);
+                    self.card_certificate = Some(Certificate::read_field_with(reader, &params, "cardCertificate")?);
                 }
                 CardFileID::CACertificate => {
                     let params = CertificateParams::new(None);
-                    driver_card.ca_certificate = Some(Certificate::read(&mut reader, &params)?);
+                    self.ca_certificate = Some(Certificate::read_field_with(reader, &params, "caCertificate")?);
                 }
                 CardFileID::CardSignCertificate => {
                     let params = CertificateParams::new(None);
-                    driver_card.card_sign_certificate = Some(Certificate::read(&mut reader, &params)?);
+                    self.card_sign_certificate = Some(Certificate::read_field_with(reader, &params, "cardSignCertificate")?);
                 }
                 CardFileID::LinkCertificate => {
                     let params = CertificateParams::new(None);
-                    driver_card.link_certificate = Some(Certificate::read(&mut reader, &params)?);
+                    self.link_certificate = Some(Certificate::read_field_with(reader, &params, "linkCertificate")?);
                 }
                 CardFileID::IC | CardFileID::ICC | CardFileID::ApplicationIdentification => {
-                    trace!("DriverCard::parse - Already parsed: {:?}", card_item.0)
+                    trace!("DriverCard::parse - Already parsed: {:?}", card_file_id)
                 }
-                _ => trace!("DriverCard::parse - Not Parsed: {:?}", card_item.0),
+                _ => trace!("DriverCard::parse - Not Parsed: {:?}", card_file_id),
+            }
+            Ok(())
+        })
+    }
+}
+
+impl CardParser<DriverCard> for DriverCard {
+    fn parse(card_data_files: &HashMap<CardFileID, CardFileData>, card_notes: &str) -> Result<Box<DriverCard>> {
+        let card_chip_identification = <dyn Card<CardResponseParameterData>>::parse_ic(card_data_files)?;
+        let card_icc_identification = <dyn Card<CardResponseParameterData>>::parse_icc(card_data_files)?;
+        let application_identification = <dyn Card<CardResponseParameterData>>::parse_card_application_identification::<
+            DriverCardApplicationIdentification,
+        >(card_data_files)?;
+        debug!("DriverCard::parse - Application Identification: {application_identification:?}");
+
+        let mut driver_card = DriverCard::new(
+            card_chip_identification,
+            card_icc_identification,
+            application_identification.clone(),
+            card_notes.to_owned(),
+            (*card_data_files).clone(),
+        );
+
+        // The version 2 card files are sized from ApplicationIdentificationV2, so it is parsed first.
+        if let Some(card_file) = card_data_files.get(&CardFileID::ApplicationIdentificationV2)
+            && let Err(error) =
+                driver_card.parse_card_file(&CardFileID::ApplicationIdentificationV2, card_file, &application_identification)
+        {
+            <dyn Card<CardResponseParameterData>>::add_card_file_error(
+                &mut driver_card.ef_errors,
+                &CardFileID::ApplicationIdentificationV2,
+                error,
+            )?;
+        }
+
+        for card_item in card_data_files.iter().filter(|card_item| *card_item.0 != CardFileID::ApplicationIdentificationV2) {
+            debug!("DriverCard::parse - ID: {:?}", card_item.0,);
+            if let Err(error) = driver_card.parse_card_file(card_item.0, card_item.1, &application_identification) {
+                <dyn Card<CardResponseParameterData>>::add_card_file_error(&mut driver_card.ef_errors, card_item.0, error)?;
             }
         }
 
@@ -239,3 +312,58 @@ impl DataFiles for DriverCard {
         &self.data_files
     }
 }
+
+impl CardWriter for DriverCard {
+    fn write_card_file(&self, card_file_id: &CardFileID) -> Result<Option<Vec<u8>>> {
+        let application_identification = &self.application_identification;
+        match card_file_id {
+            CardFileID::IC => write_to_vec(|writer| self.card_chip_identification.write(writer)).map(Some),
+            CardFileID::ICC => write_to_vec(|writer| self.card_icc_identification.write(writer)).map(Some),
+            CardFileID::ApplicationIdentification => write_to_vec(|writer| application_identification.write(writer)).map(Some),
+            CardFileID::ApplicationIdentificationV2 => write_card_file(&self.application_identification_v2),
+            CardFileID::CardDownload => write_card_file(&self.card_download),
+            CardFileID::EventsData => {
+                let params = CardEventDataParams::new(11, application_identification.no_events_per_type);
+                write_card_file_with_params(&self.events_data, &params)
+            }
+            CardFileID::FaultsData => {
+                let params = CardFaultDataParams::new(application_identification.no_faults_per_type);
+                write_card_file_with_params(&self.faults_data, &params)
+            }
+            CardFileID::DriverActivityData => {
+                let params = CardDriverActivityParams::new(application_identification.activity_structure_length);
+                write_card_file_with_params(&self.driver_activity_data, &params)
+            }
+            CardFileID::VehiclesUsed => write_card_file(&self.vehicles_used),
+            CardFileID::Places => {
+                let params = CardPlaceDailyWorkPeriodParams::new(application_identification.no_of_card_place_records, 2);
+                write_card_file_with_params(&self.places, &params)
+            }
+            CardFileID::CurrentUsage => write_card_file(&self.current_usage),
+            CardFileID::Identification => write_card_file(&self.identification),
+            CardFileID::SpecificConditions => write_card_file(&self.specific_conditions),
+            CardFileID::VehicleUnitsUsed => write_card_file(&self.vehicle_units_used),
+            CardFileID::GnssPlaces => write_card_file(&self.gnss_places),
+            CardFileID::ControlActivityData => write_card_file(&self.control_activity_data),
+            CardFileID::DrivingLicenseInfo => write_card_file(&self.driving_license_info),
+            CardFileID::BorderCrossings => {
+                let params = CardBorderCrossingsParams::new(self.application_identification_v2()?.no_of_border_crossing_records);
+                write_card_file_with_params(&self.border_crossings, &params)
+            }
+            CardFileID::LoadUnloadOperations => {
+                let params = CardLoadUnloadOperationsParams::new(self.application_identification_v2()?.no_of_load_unload_records);
+                write_card_file_with_params(&self.load_unload_operations, &params)
+            }
+            CardFileID::LoadTypeEntries => {
+                let params = CardLoadTypeEntriesParams::new(self.application_identification_v2()?.no_of_load_type_entry_records);
+                write_card_file_with_params(&self.load_type_entries, &params)
+            }
+            CardFileID::VUConfiguration => write_card_file(&self.vu_configurations),
+            CardFileID::CardCertificate => write_card_file(&self.card_certificate),
+            CardFileID::CACertificate => write_card_file(&self.ca_certificate),
+            CardFileID::CardSignCertificate => write_card_file(&self.card_sign_certificate),
+            CardFileID::LinkCertificate => write_card_file(&self.link_certificate),
+            _ => Ok(None),
+        }
+    }
+}
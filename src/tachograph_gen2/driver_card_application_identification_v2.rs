@@ -1,11 +1,12 @@
 use binary_data::BigEndian;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::Readable;
+use crate::{Readable, SpanRecorder, Writable};
 
 /// Information, stored in a driver card related to the identification of the
 /// application of the card (Annex IC requirement 375a).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DriverCardApplicationIdentificationV2 {
     #[serde(rename = "lengthOfFollowingData")]
     pub length_of_following_data: u16,
@@ -23,11 +24,16 @@ impl Readable<DriverCardApplicationIdentificationV2> for DriverCardApplicationId
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(
         reader: &mut R,
     ) -> crate::Result<DriverCardApplicationIdentificationV2> {
-        let length_of_following_data = reader.read_u16::<BigEndian>()?;
-        let no_of_border_crossing_records = reader.read_u16::<BigEndian>()?;
-        let no_of_load_unload_records = reader.read_u16::<BigEndian>()?;
-        let no_of_load_type_entry_records = reader.read_u16::<BigEndian>()?;
-        let vu_configuration_length_range = reader.read_u16::<BigEndian>()?;
+        let length_of_following_data =
+            SpanRecorder::field("lengthOfFollowingData", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
+        let no_of_border_crossing_records =
+            SpanRecorder::field("noOfBorderCrossingRecords", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
+        let no_of_load_unload_records =
+            SpanRecorder::field("noOfLoadUnloadRecords", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
+        let no_of_load_type_entry_records =
+            SpanRecorder::field("noOfLoadTypeEntryRecords", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
+        let vu_configuration_length_range =
+            SpanRecorder::field("vuConfigurationLengthRange", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
 
         Ok(Self {
             length_of_following_data,
@@ -38,3 +44,15 @@ impl Readable<DriverCardApplicationIdentificationV2> for DriverCardApplicationId
         })
     }
 }
+
+impl Writable for DriverCardApplicationIdentificationV2 {
+    /// Writes a `DriverCardApplicationIdentificationV2` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u16::<BigEndian>(self.length_of_following_data)?;
+        writer.write_u16::<BigEndian>(self.no_of_border_crossing_records)?;
+        writer.write_u16::<BigEndian>(self.no_of_load_unload_records)?;
+        writer.write_u16::<BigEndian>(self.no_of_load_type_entry_records)?;
+        writer.write_u16::<BigEndian>(self.vu_configuration_length_range)?;
+        Ok(())
+    }
+}
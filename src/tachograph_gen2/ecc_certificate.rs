@@ -1,9 +1,13 @@
-use binary_data::{BigEndian, BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BigEndian, BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
-use crate::{ReadableWithParams, Result, gen2::CertificateParams, tacho::CertificateContentType};
+use crate::{
+    ReadableWithParams, Result, SpanRecorder, WritableWithParams, field_value, gen2::CertificateParams,
+    tacho::CertificateContentType,
+};
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct EccCertificate {
     #[serde(rename = "recordType")]
     pub record_type: CertificateContentType,
@@ -17,10 +21,28 @@ impl ReadableWithParams<EccCertificate> for EccCertificate {
 
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<EccCertificate> {
         let pk_length = params.size;
-        let record_type: CertificateContentType = reader.read_u16::<BigEndian>()?.into();
+        let record_type: CertificateContentType =
+            SpanRecorder::field("recordType", "CertificateContentType", reader, |reader| {
+                Ok(reader.read_u16::<BigEndian>()?.into())
+            })?;
         let record_size = if let Some(size) = pk_length { size } else { reader.read_u8()? as u16 };
-        let data = reader.read_into_vec(record_size as u32)?;
+        let data = SpanRecorder::field("data", "OCTET STRING", reader, |reader| Ok(reader.read_into_vec(record_size as u32)?))?;
 
         Ok(Self { record_type, record_size, data })
     }
 }
+
+impl WritableWithParams for EccCertificate {
+    type P = CertificateParams;
+
+    /// Writes an `EccCertificate` to a binary stream, the record size is written only
+    /// when it is not given by the parameters, as when it is read.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W, params: &Self::P) -> Result<()> {
+        writer.write_u16::<BigEndian>((&self.record_type).into())?;
+        if params.size.is_none() {
+            writer.write_u8(field_value("data length", self.data.len())?)?;
+        }
+        writer.write_all(&self.data)?;
+        Ok(())
+    }
+}
@@ -1,13 +1,14 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, Result};
+use crate::{Readable, Result, SpanRecorder, Writable, write_octet_string};
 
 const MANUFACTURES_CODE_LENGTH: u32 = 2;
 const SEAL_IDENTIFIER_LENGTH: u32 = 8;
 
 /// The extended seal identifier uniquely identifies a seal (Annex IC requirement 401).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ExtendedSealIdentifier {
     /// see database registration to be managed by the European Commission (see <https://dtc.jrc.ec.europa.eu>).
     #[serde(rename = "manufacturerCode")]
@@ -18,8 +19,21 @@ pub struct ExtendedSealIdentifier {
 
 impl Readable<ExtendedSealIdentifier> for ExtendedSealIdentifier {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<ExtendedSealIdentifier> {
-        let manufacturer_code = reader.read_into_vec(MANUFACTURES_CODE_LENGTH)?;
-        let seal_identifier = reader.read_into_vec(SEAL_IDENTIFIER_LENGTH)?;
+        let manufacturer_code = SpanRecorder::field("manufacturerCode", "OCTET STRING", reader, |reader| {
+            Ok(reader.read_into_vec(MANUFACTURES_CODE_LENGTH)?)
+        })?;
+        let seal_identifier = SpanRecorder::field("sealIdentifier", "OCTET STRING", reader, |reader| {
+            Ok(reader.read_into_vec(SEAL_IDENTIFIER_LENGTH)?)
+        })?;
         Ok(Self { manufacturer_code, seal_identifier })
     }
 }
+
+impl Writable for ExtendedSealIdentifier {
+    /// Writes a `ExtendedSealIdentifier` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        write_octet_string(writer, &self.manufacturer_code, MANUFACTURES_CODE_LENGTH as usize)?;
+        write_octet_string(writer, &self.seal_identifier, SEAL_IDENTIFIER_LENGTH as usize)?;
+        Ok(())
+    }
+}
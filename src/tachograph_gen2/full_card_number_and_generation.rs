@@ -1,9 +1,10 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, tacho::FullCardNumber};
+use crate::{Readable, ReadableField, SpanRecorder, Writable, tacho::FullCardNumber};
 
 /// Code fully identifying a tachograph card and its generation.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FullCardNumberAndGeneration {
     #[serde(rename = "fullcardNumber")]
     pub full_card_number: FullCardNumber,
@@ -13,8 +14,17 @@ pub struct FullCardNumberAndGeneration {
 
 impl Readable<FullCardNumberAndGeneration> for FullCardNumberAndGeneration {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<FullCardNumberAndGeneration> {
-        let full_card_number = FullCardNumber::read(reader)?;
-        let generation = reader.read_u8()?;
+        let full_card_number = FullCardNumber::read_field(reader, "fullcardNumber")?;
+        let generation = SpanRecorder::field("generation", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
         Ok(Self { full_card_number, generation })
     }
 }
+
+impl Writable for FullCardNumberAndGeneration {
+    /// Writes a `FullCardNumberAndGeneration` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        self.full_card_number.write(writer)?;
+        writer.write_u8(self.generation)?;
+        Ok(())
+    }
+}
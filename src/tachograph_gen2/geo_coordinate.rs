@@ -1,14 +1,15 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, Result};
+use crate::{Readable, Result, SpanRecorder, Writable};
 
 /// The geo-coordinates are encoded as integers. These integers are multiples
 /// of the ±DDMM.M encoding for the latitude and ±DDDMM.M for the
 /// longitude. Here ±DD respectively ±DDD denotes the degrees and
 /// MM.M the minutes. Longitude and latitude of an unknown position
 /// shall be represented as Hex ‘7FFFFF’ (Decimal 8388607).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GeoCoordinate {
     pub latitude: f64,
     pub longitude: f64,
@@ -26,15 +27,33 @@ impl GeoCoordinate {
 
         sign * decimal_degrees
     }
+
+    fn coordinate_to_bytes(coordinate: f64) -> [u8; 3] {
+        let value = (coordinate.abs() * 60.0 * 10000.0).round() as u32 & 0x7FFFFF;
+        let raw = if coordinate.is_sign_negative() { value | 0x800000 } else { value };
+        [(raw >> 16) as u8, (raw >> 8) as u8, raw as u8]
+    }
 }
 
 impl Readable<GeoCoordinate> for GeoCoordinate {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<GeoCoordinate> {
-        let latitude_bytes = reader.read_bytes::<3>()?;
-        let longitude_bytes = reader.read_bytes::<3>()?;
-        let latitude = GeoCoordinate::bytes_to_coordinate(latitude_bytes);
-        let longitude = GeoCoordinate::bytes_to_coordinate(longitude_bytes);
+        let latitude = SpanRecorder::field("latitude", "INTEGER", reader, |reader| {
+            Ok(GeoCoordinate::bytes_to_coordinate(reader.read_bytes::<3>()?))
+        })?;
+        let longitude = SpanRecorder::field("longitude", "INTEGER", reader, |reader| {
+            Ok(GeoCoordinate::bytes_to_coordinate(reader.read_bytes::<3>()?))
+        })?;
 
         Ok(Self { latitude, longitude })
     }
 }
+
+impl Writable for GeoCoordinate {
+    /// Writes a `GeoCoordinate` to a binary stream as the signed minutes of the latitude and the
+    /// longitude.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&GeoCoordinate::coordinate_to_bytes(self.latitude))?;
+        writer.write_all(&GeoCoordinate::coordinate_to_bytes(self.longitude))?;
+        Ok(())
+    }
+}
@@ -1,8 +1,8 @@
-use binary_data::{BigEndian, BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BigEndian, BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, ReadableWithParams, Result,
+    Readable, ReadableField, ReadableWithParams, Result, SpanRecorder, Writable,
     gen2::GnssPlaceRecord,
     tacho::{OdometerShort, TimeReal},
 };
@@ -10,7 +10,8 @@ use crate::{
 /// Information, stored in a driver or workshop card, related to the GNSS
 /// position of the vehicle if the accumulated driving time reaches a multiple
 /// of three hours (Annex IC requirement 305 and 353)
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GnssAccumulatedDrivingRecord {
     #[serde(rename = "timeStamp")]
     pub time_stamp: TimeReal,
@@ -22,9 +23,9 @@ pub struct GnssAccumulatedDrivingRecord {
 
 impl Readable<GnssAccumulatedDrivingRecord> for GnssAccumulatedDrivingRecord {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<GnssAccumulatedDrivingRecord> {
-        let time_stamp = TimeReal::read(reader)?;
-        let gnss_place_record = GnssPlaceRecord::read(reader)?;
-        let vehicle_odometer_value = OdometerShort::read(reader)?;
+        let time_stamp = TimeReal::read_field(reader, "timeStamp")?;
+        let gnss_place_record = GnssPlaceRecord::read_field(reader, "gnssPlaceRecord")?;
+        let vehicle_odometer_value = OdometerShort::read_field(reader, "vehicleOdometerValue")?;
         Ok(Self { time_stamp, gnss_place_record, vehicle_odometer_value })
     }
 }
@@ -43,7 +44,8 @@ impl GnssAccumulatedDrivingParams {
 /// Information, stored in a driver or workshop card, related to the GNSS
 /// position of the vehicle if the accumulated driving time reaches a multiple
 /// of three hours (Annex IC requirement 306 and 354).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GnssAccumulatedDriving {
     #[serde(rename = "gnssADPointerNewestRecord")]
     pub gnss_ad_pointer_newest_record: u16,
@@ -55,10 +57,11 @@ impl ReadableWithParams<GnssAccumulatedDriving> for GnssAccumulatedDriving {
     type P = GnssAccumulatedDrivingParams;
 
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<GnssAccumulatedDriving> {
-        let gnss_ad_pointer_newest_record = reader.read_u16::<BigEndian>()?;
+        let gnss_ad_pointer_newest_record =
+            SpanRecorder::field("gnssADPointerNewestRecord", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
         let mut records: Vec<GnssAccumulatedDrivingRecord> = Vec::new();
-        for _ in 0..params.no_of_gnss_ad_records {
-            let record = GnssAccumulatedDrivingRecord::read(reader)?;
+        for index in 0..params.no_of_gnss_ad_records {
+            let record = GnssAccumulatedDrivingRecord::read_field(reader, &format!("gnssAccumulatedDrivingRecords[{index}]"))?;
             if record.time_stamp.has_data() {
                 records.push(record);
             }
@@ -67,3 +70,25 @@ impl ReadableWithParams<GnssAccumulatedDriving> for GnssAccumulatedDriving {
         Ok(Self { gnss_ad_pointer_newest_record, gnss_accumulated_driving_records: records })
     }
 }
+
+impl Writable for GnssAccumulatedDrivingRecord {
+    /// Writes a `GnssAccumulatedDrivingRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.time_stamp.write(writer)?;
+        self.gnss_place_record.write(writer)?;
+        self.vehicle_odometer_value.write(writer)?;
+        Ok(())
+    }
+}
+
+impl Writable for GnssAccumulatedDriving {
+    /// Writes the pointer and the kept records, the card writer pads the card file with the
+    /// empty records.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u16::<BigEndian>(self.gnss_ad_pointer_newest_record)?;
+        for record in self.gnss_accumulated_driving_records.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
@@ -1,11 +1,12 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, Result, gen2::GeoCoordinate, tacho::TimeReal};
+use crate::{Readable, ReadableField, Result, SpanRecorder, Writable, gen2::GeoCoordinate, tacho::TimeReal};
 
 /// Information related to the GNSS position of the vehicle (Annex IC
 /// requirements 108, 109, 110, 296, 306a, 306c, 306e, 306g, 356a, 356c, 356e and 356g).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GnssPlaceAuthRecord {
     #[serde(rename = "timeStamp")]
     pub time_stamp: TimeReal,
@@ -19,11 +20,23 @@ pub struct GnssPlaceAuthRecord {
 
 impl Readable<GnssPlaceAuthRecord> for GnssPlaceAuthRecord {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<GnssPlaceAuthRecord> {
-        let time_stamp = TimeReal::read(reader)?;
-        let gnss_accuracy = reader.read_u8()?;
-        let geo_coordinates = GeoCoordinate::read(reader)?;
-        let authentication_status = reader.read_u8()?;
+        let time_stamp = TimeReal::read_field(reader, "timeStamp")?;
+        let gnss_accuracy = SpanRecorder::field("gnssAccuracy", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let geo_coordinates = GeoCoordinate::read_field(reader, "geoCoordinates")?;
+        let authentication_status =
+            SpanRecorder::field("authenticationStatus", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
 
         Ok(Self { time_stamp, gnss_accuracy, geo_coordinates, authentication_status })
     }
 }
+
+impl Writable for GnssPlaceAuthRecord {
+    /// Writes a `GnssPlaceAuthRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.time_stamp.write(writer)?;
+        writer.write_u8(self.gnss_accuracy)?;
+        self.geo_coordinates.write(writer)?;
+        writer.write_u8(self.authentication_status)?;
+        Ok(())
+    }
+}
@@ -1,11 +1,12 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, Result, gen2::GeoCoordinate, tacho::TimeReal};
+use crate::{Readable, ReadableField, Result, SpanRecorder, Writable, gen2::GeoCoordinate, tacho::TimeReal};
 
 /// Information related to the GNSS position of the vehicle (Annex 1C
 /// requirements 108, 109, 110, 296, 305, 347, and 353).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GnssPlaceRecord {
     #[serde(rename = "timeStamp")]
     pub time_stamp: TimeReal,
@@ -17,10 +18,20 @@ pub struct GnssPlaceRecord {
 
 impl Readable<GnssPlaceRecord> for GnssPlaceRecord {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<GnssPlaceRecord> {
-        let time_stamp = TimeReal::read(reader)?;
-        let gnss_accuracy = reader.read_u8()?;
-        let geo_coordinates = GeoCoordinate::read(reader)?;
+        let time_stamp = TimeReal::read_field(reader, "timeStamp")?;
+        let gnss_accuracy = SpanRecorder::field("gnssAccuracy", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let geo_coordinates = GeoCoordinate::read_field(reader, "geoCoordinates")?;
 
         Ok(Self { time_stamp, gnss_accuracy, geo_coordinates })
     }
 }
+
+impl Writable for GnssPlaceRecord {
+    /// Writes a `GnssPlaceRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.time_stamp.write(writer)?;
+        writer.write_u8(self.gnss_accuracy)?;
+        self.geo_coordinates.write(writer)?;
+        Ok(())
+    }
+}
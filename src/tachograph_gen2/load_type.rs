@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+use crate::impl_enum_from_u8;
+
+/// Type of the load of a vehicle (generation 2, version 2).
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[repr(u8)]
+pub enum LoadType {
+    /// The load type is not defined.
+    NotDefined = 0,
+    /// Goods.
+    Goods = 1,
+    /// Passengers.
+    Passengers = 2,
+    /// The load type is unknown.
+    Unknown(u8) = 255,
+}
+
+impl_enum_from_u8!(
+    LoadType {
+        NotDefined = 0,
+        Goods = 1,
+        Passengers = 2,
+    }
+);
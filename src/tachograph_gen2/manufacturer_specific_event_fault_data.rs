@@ -1,13 +1,14 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, Result};
+use crate::{Readable, Result, SpanRecorder, Writable, write_octet_string};
 
 const MANUFACTURES_SPECIFIC_ERROR_CODE_LENGTH: u32 = 3;
 
 /// Manufacturer specific error codes simplify the error analysis and main-tenance
 /// of vehicle units.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ManufacturerSpecificEventFaultData {
     #[serde(rename = "manufacturerCode")]
     pub manufacturer_code: u8,
@@ -17,8 +18,19 @@ pub struct ManufacturerSpecificEventFaultData {
 
 impl Readable<ManufacturerSpecificEventFaultData> for ManufacturerSpecificEventFaultData {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<ManufacturerSpecificEventFaultData> {
-        let manufacturer_code = reader.read_u8()?;
-        let manufacturer_specific_error_code = reader.read_into_vec(MANUFACTURES_SPECIFIC_ERROR_CODE_LENGTH)?;
+        let manufacturer_code = SpanRecorder::field("manufacturerCode", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let manufacturer_specific_error_code =
+            SpanRecorder::field("manufacturerSpecificErrorCode", "OCTET STRING", reader, |reader| {
+                Ok(reader.read_into_vec(MANUFACTURES_SPECIFIC_ERROR_CODE_LENGTH)?)
+            })?;
         Ok(Self { manufacturer_code, manufacturer_specific_error_code })
     }
 }
+
+impl Writable for ManufacturerSpecificEventFaultData {
+    /// Writes a `ManufacturerSpecificEventFaultData` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u8(self.manufacturer_code)?;
+        write_octet_string(writer, &self.manufacturer_specific_error_code, MANUFACTURES_SPECIFIC_ERROR_CODE_LENGTH as usize)
+    }
+}
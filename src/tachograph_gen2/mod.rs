@@ -4,7 +4,10 @@
 //! for the second generation (Gen2) of digital tachograph data. It includes
 //! support for new features like GNSS data and updated record formats.
 
+mod card_border_crossings;
 mod card_data;
+mod card_load_type_entries;
+mod card_load_unload_operations;
 mod card_response_parameter_data;
 mod card_vehicle_record;
 mod card_vehicle_units_used;
@@ -18,7 +21,8 @@ mod company_card;
 mod company_card_application_identification_v2;
 mod control_card;
 mod control_card_application_identification_v2;
-mod data_info;
+mod cyclic_records;
+pub(crate) mod data_info;
 mod driver_card;
 mod driver_card_application_identification;
 mod driver_card_application_identification_v2;
@@ -29,8 +33,10 @@ mod geo_coordinate;
 mod gnss_accumulated_driving;
 mod gnss_place_auth_record;
 mod gnss_place_record;
+mod load_type;
 mod manufacturer_specific_event_fault_data;
 mod member_state_certificate_record_array;
+mod operation_type;
 mod place_auth_record;
 mod place_record;
 mod previous_vehicle_info;
@@ -47,12 +53,14 @@ mod vehicle_registration_number_record_array;
 mod verification;
 mod vu_activity;
 mod vu_activity_daily_record_array;
+mod vu_border_crossing_record;
 mod vu_calibration_record;
 mod vu_card_download;
 mod vu_card_iw_record;
 mod vu_card_record;
 mod vu_certificate_record_array;
 mod vu_company_locks_record;
+mod vu_configurations;
 mod vu_control_activity_record;
 mod vu_data;
 mod vu_download_activity_data;
@@ -63,6 +71,7 @@ mod vu_fault_record;
 mod vu_gnssad_record;
 mod vu_identification;
 mod vu_its_consent_record;
+mod vu_load_unload_record;
 mod vu_over_speeding_event_record;
 mod vu_overview;
 mod vu_place_daily_work_period_record_array;
@@ -74,9 +83,13 @@ mod vu_time_adjustment_record;
 mod vu_transfer_response_parameter_data;
 mod workshop_card;
 mod workshop_card_application_identification;
+mod workshop_card_calibration_add_data;
 mod workshop_card_calibration_record;
 
+pub use card_border_crossings::{CardBorderCrossingRecord, CardBorderCrossings, CardBorderCrossingsParams};
 pub use card_data::CardData;
+pub use card_load_type_entries::{CardLoadTypeEntries, CardLoadTypeEntriesParams, CardLoadTypeEntryRecord};
+pub use card_load_unload_operations::{CardLoadUnloadOperations, CardLoadUnloadOperationsParams, CardLoadUnloadRecord};
 pub use card_response_parameter_data::{CardResponseParameterData, ParsedCard};
 pub use card_vehicle_record::CardVehicleRecord;
 pub use card_vehicle_units_used::{CardVehicleUnitRecord, CardVehicleUnitsUsed, CardVehicleUnitsUsedParams};
@@ -90,6 +103,7 @@ pub use company_card::CompanyCard;
 pub use company_card_application_identification_v2::CompanyCardApplicationIdentificationV2;
 pub use control_card::ControlCard;
 pub use control_card_application_identification_v2::ControlCardApplicationIdentificationV2;
+pub(crate) use cyclic_records::{read_cyclic_records, write_cyclic_records};
 pub use data_info::{DataInfo, DataInfoGenericRecordArray, DataInfoReadable};
 pub use driver_card::DriverCard;
 pub use driver_card_application_identification::DriverCardApplicationIdentification;
@@ -101,8 +115,10 @@ pub use geo_coordinate::GeoCoordinate;
 pub use gnss_accumulated_driving::{GnssAccumulatedDriving, GnssAccumulatedDrivingParams, GnssAccumulatedDrivingRecord};
 pub use gnss_place_auth_record::GnssPlaceAuthRecord;
 pub use gnss_place_record::GnssPlaceRecord;
+pub use load_type::LoadType;
 pub use manufacturer_specific_event_fault_data::ManufacturerSpecificEventFaultData;
 pub use member_state_certificate_record_array::MemberStateCertificateRecordArray;
+pub use operation_type::OperationType;
 pub use place_auth_record::PlaceAuthRecord;
 pub use place_record::PlaceRecord;
 pub use previous_vehicle_info::PreviousVehicleInfo;
@@ -116,15 +132,18 @@ pub use specific_conditions::{SpecificConditions, SpecificConditionsParams};
 pub use vehicle_identification_number_record_array::VehicleIdentificationNumberRecordArray;
 pub use vehicle_registration_identification_record_array::VehicleRegistrationIdentificationRecordArray;
 pub use vehicle_registration_number_record_array::VehicleRegistrationNumberRecordArray;
-pub use verification::verify;
+pub(crate) use verification::{certificate_references, verify_vu_with_roots, verify_with_roots};
+pub use verification::{verify, verify_vu};
 pub use vu_activity::VUActivity;
 pub use vu_activity_daily_record_array::VuActivityDailyRecordArray;
+pub use vu_border_crossing_record::VuBorderCrossingRecord;
 pub use vu_calibration_record::VuCalibrationRecord;
-pub use vu_card_download::VUCardDownload;
+pub use vu_card_download::{VUCardDownload, VUCardDownloadData};
 pub use vu_card_iw_record::VuCardIWRecord;
 pub use vu_card_record::VuCardRecord;
 pub use vu_certificate_record_array::VuCertificateRecordArray;
 pub use vu_company_locks_record::VuCompanyLocksRecord;
+pub use vu_configurations::{VuConfigurations, VuConfigurationsParams};
 pub use vu_control_activity_record::VuControlActivityRecord;
 pub use vu_data::VUData;
 pub use vu_download_activity_data::VuDownloadActivityData;
@@ -135,6 +154,7 @@ pub use vu_fault_record::VuFaultRecord;
 pub use vu_gnssad_record::VuGnssadRecord;
 pub use vu_identification::VuIdentification;
 pub use vu_its_consent_record::VuItsConsentRecord;
+pub use vu_load_unload_record::VuLoadUnloadRecord;
 pub use vu_over_speeding_event_record::VuOverSpeedingEventRecord;
 pub use vu_overview::VUOverview;
 pub use vu_place_daily_work_period_record_array::{VuPlaceDailyWorkPeriodRecord, VuPlaceDailyWorkPeriodRecordArray};
@@ -146,4 +166,7 @@ pub use vu_time_adjustment_record::VuTimeAdjustmentRecord;
 pub use vu_transfer_response_parameter_data::*;
 pub use workshop_card::WorkshopCard;
 pub use workshop_card_application_identification::WorkshopCardApplicationIdentification;
+pub use workshop_card_calibration_add_data::{
+    WorkshopCardCalibrationAddData, WorkshopCardCalibrationAddDataParams, WorkshopCardCalibrationAddDataRecord,
+};
 pub use workshop_card_calibration_record::WorkshopCardCalibrationRecord;
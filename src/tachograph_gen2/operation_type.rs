@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+use crate::impl_enum_from_u8;
+
+/// Type of a load/unload operation of a vehicle (generation 2, version 2).
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[repr(u8)]
+pub enum OperationType {
+    /// Reserved for future use.
+    Rfu = 0,
+    /// Load operation.
+    LoadOperation = 1,
+    /// Unload operation.
+    UnloadOperation = 2,
+    /// Simultaneous load and unload operation.
+    SimultaneousLoadUnloadOperation = 3,
+    /// A value reserved for future use, it keeps the value.
+    Unknown(u8) = 255,
+}
+
+impl_enum_from_u8!(
+    OperationType {
+        Rfu = 0,
+        LoadOperation = 1,
+        UnloadOperation = 2,
+        SimultaneousLoadUnloadOperation = 3,
+    }
+);
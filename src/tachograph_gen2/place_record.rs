@@ -1,15 +1,16 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, Result,
+    Readable, ReadableField, Result, SpanRecorder, Writable,
     gen2::GnssPlaceRecord,
     tacho::{CardPlace, EntryTypeDailyWorkPeriod, NationNumeric, OdometerShort, RegionNumeric, TimeReal},
 };
 
 /// Information related to a place where a daily work period begins or ends
 /// (Annex 1C requirements 108, 271, 296, 324, and 347).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PlaceRecord {
     #[serde(rename = "entryTime")]
     pub entry_time: TimeReal,
@@ -27,12 +28,17 @@ pub struct PlaceRecord {
 
 impl Readable<PlaceRecord> for PlaceRecord {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<PlaceRecord> {
-        let entry_time = TimeReal::read(reader)?;
-        let entry_type_daily_work_period: EntryTypeDailyWorkPeriod = reader.read_u8()?.into();
-        let daily_work_period_country: NationNumeric = reader.read_u8()?.into();
-        let daily_work_period_region: RegionNumeric = reader.read_u8()?.into();
-        let vehicle_odometer_value = OdometerShort::read(reader)?;
-        let entry_gnns_place_record = GnssPlaceRecord::read(reader)?;
+        let entry_time = TimeReal::read_field(reader, "entryTime")?;
+        let entry_type_daily_work_period: EntryTypeDailyWorkPeriod =
+            SpanRecorder::field("entryTypeDailyWorkPeriod", "EntryTypeDailyWorkPeriod", reader, |reader| {
+                Ok(reader.read_u8()?.into())
+            })?;
+        let daily_work_period_country: NationNumeric =
+            SpanRecorder::field("dailyWorkPeriodCountry", "NationNumeric", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let daily_work_period_region: RegionNumeric =
+            SpanRecorder::field("dailyWorkPeriodRegion", "RegionNumeric", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let vehicle_odometer_value = OdometerShort::read_field(reader, "vehicleOdometerValue")?;
+        let entry_gnns_place_record = GnssPlaceRecord::read_field(reader, "entryGnssPlaceRecord")?;
 
         Ok(Self {
             entry_time,
@@ -50,3 +56,16 @@ impl CardPlace for PlaceRecord {
         &self.entry_time
     }
 }
+
+impl Writable for PlaceRecord {
+    /// Writes a `PlaceRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.entry_time.write(writer)?;
+        writer.write_u8((&self.entry_type_daily_work_period).into())?;
+        writer.write_u8((&self.daily_work_period_country).into())?;
+        writer.write_u8((&self.daily_work_period_region).into())?;
+        self.vehicle_odometer_value.write(writer)?;
+        self.entry_gnns_place_record.write(writer)?;
+        Ok(())
+    }
+}
@@ -1,15 +1,16 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, Result,
+    Readable, ReadableField, Result, SpanRecorder, Writable,
     tacho::{TimeReal, VehicleRegistrationIdentification},
 };
 
 /// Information related to the vehicle previously used by a driver when
 /// inserting his card in a vehicle unit (Annex 1B requirement 081 and
 /// Annex 1C requirement 102).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PreviousVehicleInfo {
     #[serde(rename = "vehicleRegistrationIdentification")]
     pub vehicle_registration_identification: VehicleRegistrationIdentification,
@@ -21,10 +22,21 @@ pub struct PreviousVehicleInfo {
 
 impl Readable<PreviousVehicleInfo> for PreviousVehicleInfo {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<PreviousVehicleInfo> {
-        let vehicle_registration_identification = VehicleRegistrationIdentification::read(reader)?;
-        let card_withdrawal_time = TimeReal::read(reader)?;
-        let vu_generation = reader.read_u8()?;
+        let vehicle_registration_identification =
+            VehicleRegistrationIdentification::read_field(reader, "vehicleRegistrationIdentification")?;
+        let card_withdrawal_time = TimeReal::read_field(reader, "cardWithdrawalTime")?;
+        let vu_generation = SpanRecorder::field("vuGeneration", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
 
         Ok(Self { vehicle_registration_identification, card_withdrawal_time, vu_generation })
     }
 }
+
+impl Writable for PreviousVehicleInfo {
+    /// Writes a `PreviousVehicleInfo` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.vehicle_registration_identification.write(writer)?;
+        self.card_withdrawal_time.write(writer)?;
+        writer.write_u8(self.vu_generation)?;
+        Ok(())
+    }
+}
@@ -1,12 +1,13 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, Result, gen2::SealRecord};
+use crate::{Readable, ReadableField, Result, SpanRecorder, Writable, gen2::SealRecord};
 
 /// This data type stores information about the seals that are attached to the
 /// different components of a vehicle and is intended for storage on a card.
 /// This data type is related to Annex 1C requirement 337.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SealDataCard {
     #[serde(rename = "noOfSealRecords")]
     pub no_of_seal_records: u8,
@@ -16,12 +17,23 @@ pub struct SealDataCard {
 
 impl Readable<SealDataCard> for SealDataCard {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<SealDataCard> {
-        let no_of_seal_records = reader.read_u8()?;
+        let no_of_seal_records = SpanRecorder::field("noOfSealRecords", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
         let mut records: Vec<SealRecord> = Vec::new();
-        for _ in 0..no_of_seal_records {
-            let record = SealRecord::read(reader)?;
+        for index in 0..no_of_seal_records {
+            let record = SealRecord::read_field(reader, &format!("sealRecords[{index}]"))?;
             records.push(record);
         }
         Ok(Self { no_of_seal_records, seal_records: records })
     }
 }
+
+impl Writable for SealDataCard {
+    /// Writes a `SealDataCard` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u8(self.no_of_seal_records)?;
+        for record in self.seal_records.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
@@ -1,11 +1,12 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, Result, gen2::SealRecord};
+use crate::{Readable, ReadableField, Result, Writable, gen2::SealRecord};
 
 /// This data type stores information about the seals that are attached to the
 /// different components of a vehicle and is intended for storage in a Vehicle Unit.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SealDataVu {
     #[serde(rename = "sealRecords")]
     pub seal_records: Vec<SealRecord>,
@@ -14,10 +15,20 @@ pub struct SealDataVu {
 impl Readable<SealDataVu> for SealDataVu {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<SealDataVu> {
         let mut seal_records: Vec<SealRecord> = Vec::with_capacity(5);
-        for _ in 0..5 {
-            let record = SealRecord::read(reader)?;
+        for index in 0..5 {
+            let record = SealRecord::read_field(reader, &format!("sealRecords[{index}]"))?;
             seal_records.push(record);
         }
         Ok(Self { seal_records })
     }
 }
+
+impl Writable for SealDataVu {
+    /// Writes a `SealDataVu` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        for record in self.seal_records.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
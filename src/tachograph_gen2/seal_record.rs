@@ -1,11 +1,12 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, Result, gen2::ExtendedSealIdentifier, tacho::EquipmentType};
+use crate::{Readable, ReadableField, Result, SpanRecorder, Writable, gen2::ExtendedSealIdentifier, tacho::EquipmentType};
 
 /// This data type stores information about a seal that is attached to a
 /// component. This data type is related to Annex 1C requirement 337.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SealRecord {
     #[serde(rename = "equipmentType")]
     pub equipment_type: EquipmentType,
@@ -15,9 +16,19 @@ pub struct SealRecord {
 
 impl Readable<SealRecord> for SealRecord {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<SealRecord> {
-        let equipment_type: EquipmentType = reader.read_u8()?.into();
-        let extended_seal_identitfier = ExtendedSealIdentifier::read(reader)?;
+        let equipment_type: EquipmentType =
+            SpanRecorder::field("equipmentType", "EquipmentType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let extended_seal_identitfier = ExtendedSealIdentifier::read_field(reader, "extendedSealIdentifier")?;
 
         Ok(Self { equipment_type, extended_seal_identitfier })
     }
 }
+
+impl Writable for SealRecord {
+    /// Writes a `SealRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u8((&self.equipment_type).into())?;
+        self.extended_seal_identitfier.write(writer)?;
+        Ok(())
+    }
+}
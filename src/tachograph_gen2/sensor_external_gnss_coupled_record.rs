@@ -1,14 +1,18 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, Result, bytes_to_ia5_fix_string,
+    Readable, ReadableField, Result, SpanRecorder, Writable, bytes_to_ia5_fix_string,
     tacho::{ExtendedSerialNumber, TimeReal},
+    write_ia5_string,
 };
 
+const SENSOR_APPROVAL_NUMBER_LENGTH: u32 = 16;
+
 /// Information, stored in a vehicle unit, related to the identification of the
 /// external GNSS facility coupled with the vehicle unit (Annex 1C requirement 100).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SensorExternalGNSSCoupledRecord {
     #[serde(rename = "sensorSerialNumber")]
     pub sensor_serial_number: ExtendedSerialNumber,
@@ -20,10 +24,22 @@ pub struct SensorExternalGNSSCoupledRecord {
 
 impl Readable<SensorExternalGNSSCoupledRecord> for SensorExternalGNSSCoupledRecord {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<SensorExternalGNSSCoupledRecord> {
-        let sensor_serial_number = ExtendedSerialNumber::read(reader)?;
-        let sensor_approval_number = bytes_to_ia5_fix_string(&reader.read_into_vec(16)?)?;
-        let sensor_coupling_date = TimeReal::read(reader)?;
+        let sensor_serial_number = ExtendedSerialNumber::read_field(reader, "sensorSerialNumber")?;
+        let sensor_approval_number = SpanRecorder::field("sensorApprovalNumber", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(SENSOR_APPROVAL_NUMBER_LENGTH)?)?)
+        })?;
+        let sensor_coupling_date = TimeReal::read_field(reader, "sensorCouplingDate")?;
 
         Ok(Self { sensor_serial_number, sensor_approval_number, sensor_coupling_date })
     }
 }
+
+impl Writable for SensorExternalGNSSCoupledRecord {
+    /// Writes a `SensorExternalGNSSCoupledRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.sensor_serial_number.write(writer)?;
+        write_ia5_string(writer, &self.sensor_approval_number, SENSOR_APPROVAL_NUMBER_LENGTH as usize)?;
+        self.sensor_coupling_date.write(writer)?;
+        Ok(())
+    }
+}
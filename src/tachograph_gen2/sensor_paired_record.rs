@@ -1,14 +1,18 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, Result, bytes_to_ia5_fix_string,
+    Readable, ReadableField, Result, SpanRecorder, Writable, bytes_to_ia5_fix_string,
     tacho::{ExtendedSerialNumber, TimeReal},
+    write_ia5_string,
 };
 
+const SENSOR_APPROVAL_NUMBER_LENGTH: u32 = 16;
+
 /// Information, stored in a vehicle unit, related to the identification of a
 /// motion sensor paired with the vehicle unit (Annex 1C requirement 97).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SensorPairedRecord {
     #[serde(rename = "sensorSerialNumber")]
     pub sensor_serial_number: ExtendedSerialNumber,
@@ -20,9 +24,21 @@ pub struct SensorPairedRecord {
 
 impl Readable<SensorPairedRecord> for SensorPairedRecord {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<SensorPairedRecord> {
-        let sensor_serial_number = ExtendedSerialNumber::read(reader)?;
-        let sensor_approval_number = bytes_to_ia5_fix_string(&reader.read_into_vec(16)?)?;
-        let sensor_pairing_date = TimeReal::read(reader)?;
+        let sensor_serial_number = ExtendedSerialNumber::read_field(reader, "sensorSerialNumber")?;
+        let sensor_approval_number = SpanRecorder::field("sensorApprovalNumber", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(SENSOR_APPROVAL_NUMBER_LENGTH)?)?)
+        })?;
+        let sensor_pairing_date = TimeReal::read_field(reader, "sensorPairingDate")?;
         Ok(Self { sensor_serial_number, sensor_approval_number, sensor_pairing_date })
     }
 }
+
+impl Writable for SensorPairedRecord {
+    /// Writes a `SensorPairedRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.sensor_serial_number.write(writer)?;
+        write_ia5_string(writer, &self.sensor_approval_number, SENSOR_APPROVAL_NUMBER_LENGTH as usize)?;
+        self.sensor_pairing_date.write(writer)?;
+        Ok(())
+    }
+}
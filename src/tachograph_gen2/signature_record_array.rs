@@ -1,10 +1,17 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
-use crate::{Result, gen2::DataInfoReadable, tacho::RecordType, tachograph_gen2::data_info::DataConfig};
+use crate::{
+    Result, Writable,
+    gen2::DataInfoReadable,
+    tacho::RecordType,
+    tachograph_gen2::data_info::{DataConfig, write_record_array},
+    write_octet_string,
+};
 
 /// A set of signatures plus metadata used in the download protocol.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SignatureRecordArray {
     #[serde(rename = "noOfRecords")]
     pub no_of_records: u16,
@@ -29,3 +36,12 @@ impl DataInfoReadable<SignatureRecordArray> for SignatureRecordArray {
         Ok(Self { no_of_records, record_size, record_type, records })
     }
 }
+
+impl Writable for SignatureRecordArray {
+    /// Writes the record array of the signatures.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        write_record_array(writer, &self.record_type, self.record_size, &self.records, |writer, record| {
+            write_octet_string(writer, record, record.len())
+        })
+    }
+}
@@ -1,7 +1,7 @@
-use binary_data::{BigEndian, BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BigEndian, BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, ReadableWithParams, Result, tacho::SpecificConditionRecord};
+use crate::{ReadableField, ReadableWithParams, Result, SpanRecorder, Writable, tacho::SpecificConditionRecord};
 
 #[derive(Debug)]
 pub struct SpecificConditionsParams {
@@ -16,7 +16,8 @@ impl SpecificConditionsParams {
 
 /// Information, stored in a driver card, a workshop card or a vehicle unit,
 /// related to a specific condition (Annex 1C requirement 131, 277, 302, 329, and 356).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SpecificConditions {
     #[serde(rename = "conditionPointerNewestRecord")]
     pub condition_pointer_newest_record: u16,
@@ -30,9 +31,11 @@ impl ReadableWithParams<SpecificConditions> for SpecificConditions {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<SpecificConditions> {
         let no_of_records = params.no_of_records;
         let mut specific_condition_records: Vec<SpecificConditionRecord> = Vec::new();
-        let condition_pointer_newest_record = reader.read_u16::<BigEndian>()?;
-        for _ in 0..no_of_records {
-            let specific_condition_record = SpecificConditionRecord::read(reader)?;
+        let condition_pointer_newest_record =
+            SpanRecorder::field("conditionPointerNewestRecord", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
+        for index in 0..no_of_records {
+            let specific_condition_record =
+                SpecificConditionRecord::read_field(reader, &format!("specificConditionRecords[{index}]"))?;
             if specific_condition_record.entry_time.has_data() {
                 specific_condition_records.push(specific_condition_record);
             }
@@ -40,3 +43,15 @@ impl ReadableWithParams<SpecificConditions> for SpecificConditions {
         Ok(Self { condition_pointer_newest_record, specific_condition_records })
     }
 }
+
+impl Writable for SpecificConditions {
+    /// Writes the pointer and the kept specific condition records, the card writer pads the card
+    /// file with the empty records.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u16::<BigEndian>(self.condition_pointer_newest_record)?;
+        for record in self.specific_condition_records.iter() {
+            record.write(writer)?;
+        }
+        Ok(())
+    }
+}
@@ -1,12 +1,17 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    CodePage, Result, bytes_to_string, gen2::DataInfoReadable, tacho::RecordType, tachograph_gen2::data_info::DataConfig,
+    CodePage, Result, Writable, bytes_to_string,
+    gen2::DataInfoReadable,
+    tacho::RecordType,
+    tachograph_gen2::data_info::{DataConfig, write_record_array},
+    write_string,
 };
 
 /// The Vehicle Idenification Number plus metadata as used in the download protocol.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VehicleIdentificationNumberRecordArray {
     #[serde(rename = "noOfRecords")]
     pub no_of_records: u16,
@@ -31,3 +36,12 @@ impl DataInfoReadable<VehicleIdentificationNumberRecordArray> for VehicleIdentif
         Ok(Self { no_of_records, record_size, record_type, records })
     }
 }
+
+impl Writable for VehicleIdentificationNumberRecordArray {
+    /// Writes the record array of the vehicle identification numbers, padded with spaces to the record size.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        write_record_array(writer, &self.record_type, self.record_size, &self.records, |writer, record| {
+            write_string(writer, record, &CodePage::IsoIec8859_1, self.record_size as usize)
+        })
+    }
+}
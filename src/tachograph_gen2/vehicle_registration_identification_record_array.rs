@@ -1,15 +1,16 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, Result,
+    ReadableField, Result, Writable,
     gen2::DataInfoReadable,
     tacho::{RecordType, VehicleRegistrationIdentification},
-    tachograph_gen2::data_info::DataConfig,
+    tachograph_gen2::data_info::{DataConfig, write_record_array},
 };
 
 /// The Vehicle Registration Identification plus metadata as used in the download protocol.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VehicleRegistrationIdentificationRecordArray {
     #[serde(rename = "noOfRecords")]
     pub no_of_records: u16,
@@ -27,10 +28,17 @@ impl DataInfoReadable<VehicleRegistrationIdentificationRecordArray> for VehicleR
         let record_type = config.record_type.clone();
 
         let mut records: Vec<VehicleRegistrationIdentification> = Vec::with_capacity(no_of_records as usize);
-        for _ in 0..no_of_records {
-            let record = VehicleRegistrationIdentification::read(reader)?;
+        for index in 0..no_of_records {
+            let record = VehicleRegistrationIdentification::read_field(reader, &format!("records[{index}]"))?;
             records.push(record);
         }
         Ok(Self { no_of_records, record_size, record_type, records })
     }
 }
+
+impl Writable for VehicleRegistrationIdentificationRecordArray {
+    /// Writes the record array of the vehicle registration identifications.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        write_record_array(writer, &self.record_type, self.record_size, &self.records, |writer, record| record.write(writer))
+    }
+}
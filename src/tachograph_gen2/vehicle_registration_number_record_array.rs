@@ -1,16 +1,18 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, Result,
+    ReadableField, Result, Writable,
     gen2::DataInfoReadable,
     tacho::{RecordType, VehicleRegistrationNumber},
     tachograph_gen2::{
-        data_info::DataConfig, vehicle_registration_identification_record_array::VehicleRegistrationIdentificationRecordArray,
+        data_info::{DataConfig, write_record_array},
+        vehicle_registration_identification_record_array::VehicleRegistrationIdentificationRecordArray,
     },
 };
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VehicleRegistrationNumberRecordArray {
     #[serde(rename = "noOfRecords")]
     pub no_of_records: u16,
@@ -28,21 +30,28 @@ impl DataInfoReadable<VehicleRegistrationNumberRecordArray> for VehicleRegistrat
         let record_type = config.record_type.clone();
 
         let mut records: Vec<VehicleRegistrationNumber> = Vec::with_capacity(no_of_records as usize);
-        for _ in 0..no_of_records {
-            let record = VehicleRegistrationNumber::read(reader)?;
+        for index in 0..no_of_records {
+            let record = VehicleRegistrationNumber::read_field(reader, &format!("records[{index}]"))?;
             records.push(record);
         }
         Ok(Self { no_of_records, record_size, record_type, records })
     }
 }
 
-impl From<VehicleRegistrationIdentificationRecordArray> for VehicleRegistrationNumberRecordArray {
-    fn from(value: VehicleRegistrationIdentificationRecordArray) -> Self {
+impl From<&VehicleRegistrationIdentificationRecordArray> for VehicleRegistrationNumberRecordArray {
+    fn from(value: &VehicleRegistrationIdentificationRecordArray) -> Self {
         Self {
-            record_type: value.record_type,
+            record_type: value.record_type.clone(),
             no_of_records: value.no_of_records,
             record_size: value.record_size,
-            records: value.records.into_iter().map(|item| item.vehicle_registration_number).collect(),
+            records: value.records.iter().map(|item| item.vehicle_registration_number.clone()).collect(),
         }
     }
 }
+
+impl Writable for VehicleRegistrationNumberRecordArray {
+    /// Writes the record array of the vehicle registration numbers.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        write_record_array(writer, &self.record_type, self.record_size, &self.records, |writer, record| record.write(writer))
+    }
+}
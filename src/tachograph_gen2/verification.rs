@@ -2,18 +2,23 @@ use log::debug;
 
 use crate::{
     Error, Result,
-    tacho::{CardFileID, CardFilesMap, TimeReal, VerifyResult},
+    gen2::{VUData, VUTransferResponseParameterData},
+    helpers::{EccCurve, EccPoint, RECORD_ARRAY_HEADER_LENGTH, split_record_arrays},
+    tacho::{
+        self, CardFileData, CardFileID, CardFilesMap, RecordType, TimeReal, VUTransferResponseParameterItem, VerifyItem,
+        VerifyResult, VerifyResultStatus, VerifyStatus, VuVerifyItem, VuVerifyResult,
+    },
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u32)]
 pub enum CertificateTag {
-    ApplicationTemplate = 0x7F81,
-    CertificateBody = 0x7FAE,
-    CertificateProfileIdentifier = 0x5F19,
+    Certificate = 0x7F21,
+    CertificateBody = 0x7F4E,
+    CertificateProfileIdentifier = 0x5F29,
     CertificateAuthorityReference = 0x42,
-    CertificateHolderAuthorisation = 0x5F3C,
-    Extensions = 0x7FA9,
+    CertificateHolderAuthorisation = 0x5F4C,
+    PublicKey = 0x7F49,
     DomainParameters = 0x06,
     PublicPoint = 0x86,
     CertificateHolderReference = 0x5F20,
@@ -26,12 +31,12 @@ pub enum CertificateTag {
 impl From<u32> for CertificateTag {
     fn from(value: u32) -> Self {
         match value {
-            0x7F81 => Self::ApplicationTemplate,
-            0x7FAE => Self::CertificateBody,
-            0x5F19 => Self::CertificateProfileIdentifier,
+            0x7F21 => Self::Certificate,
+            0x7F4E => Self::CertificateBody,
+            0x5F29 => Self::CertificateProfileIdentifier,
             0x42 => Self::CertificateAuthorityReference,
-            0x5F3C => Self::CertificateHolderAuthorisation,
-            0x7FA9 => Self::Extensions,
+            0x5F4C => Self::CertificateHolderAuthorisation,
+            0x7F49 => Self::PublicKey,
             0x06 => Self::DomainParameters,
             0x86 => Self::PublicPoint,
             0x5F20 => Self::CertificateHolderReference,
@@ -43,12 +48,15 @@ impl From<u32> for CertificateTag {
     }
 }
 
-#[derive(Debug, Clone)]
+/// The ECC certificate of Appendix 11, with the encoded certificate body signed by the
+/// certificate authority.
+#[derive(Debug, Clone, Default)]
 pub struct Certificate {
     pub certificate_profile_identifier: u32,
     pub certificate_authority_reference: Option<Vec<u8>>,
     pub certificate_holder_authorisation: Option<Vec<u8>>,
     pub domain_parameters: Option<String>,
+    pub domain_parameters_oid: Option<Vec<u8>>,
     pub public_point: Option<Vec<u8>>,
     pub certificate_holder_reference: Option<Vec<u8>>,
     pub certificate_effective_date: Option<TimeReal>,
@@ -58,161 +66,516 @@ pub struct Certificate {
 }
 
 impl Certificate {
-    pub fn from_bytes(data: &[u8; 205]) -> Result<Self> {
-        let mut cert = Certificate {
-            certificate_profile_identifier: 0,
-            certificate_authority_reference: None,
-            certificate_holder_authorisation: None,
-            domain_parameters: None,
-            public_point: None,
-            certificate_holder_reference: None,
-            certificate_effective_date: None,
-            certificate_expiration_date: None,
-            certificate_body: None,
-            certificate_signature: None,
-        };
-        Ok(Certificate::parse(data, &mut cert))
-    }
-
-    #[allow(dead_code)]
-    fn read_tlv(data: &[u8; 205], pos: &mut usize) -> Option<(u32, usize)> {
-        if *pos >= data.len() {
-            return None;
+    /// Reads the certificate from the start of `data`, the bytes after the certificate,
+    /// e.g. the padding of a card file, are ignored.
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let mut cert = Certificate::default();
+        let mut pos = 0;
+        let (tag, start, end) = Certificate::read_tlv(data, &mut pos)?;
+        if CertificateTag::from(tag) != CertificateTag::Certificate {
+            return Err(Error::VerifyError(format!(
+                "Certificate need to start with tag {:#X} but starts with {:#X}",
+                0x7F21, tag
+            )));
         }
+        Certificate::parse(data, start, end, &mut cert)?;
+        Ok(cert)
+    }
 
-        let mut tag = data[*pos] as u32;
+    /// Reads the tag and the length of the data object at `pos` and returns the tag and
+    /// the range of its value, `pos` is moved after the value.
+    fn read_tlv(data: &[u8], pos: &mut usize) -> Result<(u32, usize, usize)> {
+        let byte = |index: usize| {
+            data.get(index).copied().ok_or_else(|| Error::VerifyError("Certificate data object is truncated.".to_string()))
+        };
 
+        let mut tag = byte(*pos)? as u32;
         // Handle multi-byte tag
         if tag & 0x1F == 0x1F {
             *pos += 1;
-            tag = (tag << 8) + data[*pos] as u32;
+            tag = (tag << 8) + byte(*pos)? as u32;
         }
         *pos += 1;
 
         // Parse length
-        let data_part_len: usize;
-        if data[*pos] & 0x80 == 0x80 {
-            let int_len = (data[*pos] & 0x7F) as usize;
-            data_part_len = Certificate::to_u32(data.as_slice(), *pos + 1, int_len) as usize;
-            *pos += int_len;
-        } else {
-            data_part_len = data[*pos] as usize;
-        }
+        let length_byte = byte(*pos)?;
         *pos += 1;
-
-        Some((tag, data_part_len))
-    }
-
-    fn parse(data: &[u8; 205], cert: &mut Certificate) -> Certificate {
-        let mut i = 0;
-        // while let Some((tag, data_part_len)) = Certificate::read_tlv(&data, &mut i) {
-        //     let tag_enum = CertificateTag::from(tag);
-        // }
-
-        while i < data.len() {
-            let start = i;
-            let mut tag = data[i] as u32;
-
-            // Handle multi-byte tag
-            if tag & 0x1F == 0x1F {
-                i += 1;
-                tag = (tag << 8) + data[i] as u32;
+        let data_part_len = if length_byte & 0x80 == 0x80 {
+            let int_len = (length_byte & 0x7F) as usize;
+            if int_len == 0 || int_len > 2 {
+                return Err(Error::VerifyError(format!("Certificate data object length of {int_len} bytes is not supported.")));
             }
-            i += 1;
-
-            // Parse length
-            let data_part_len: usize;
-            if data[i] & 0x80 == 0x80 {
-                let int_len = (data[i] & 0x7F) as usize;
-                data_part_len = Certificate::to_u32(data.as_slice(), i + 1, int_len) as usize;
-                i += int_len;
-            } else {
-                data_part_len = data[i] as usize;
+            let mut len = 0usize;
+            for _ in 0..int_len {
+                len = (len << 8) | byte(*pos)? as usize;
+                *pos += 1;
             }
-            i += 1;
+            len
+        } else {
+            length_byte as usize
+        };
+
+        let start = *pos;
+        let end = start + data_part_len;
+        if end > data.len() {
+            return Err(Error::VerifyError("Certificate data object is truncated.".to_string()));
+        }
+        *pos = end;
+        Ok((tag, start, end))
+    }
 
-            // Use enum instead of raw numbers
-            let tag_enum = CertificateTag::from(tag);
+    fn parse(data: &[u8], start: usize, end: usize, cert: &mut Certificate) -> Result<()> {
+        let mut i = start;
+        while i < end {
+            let tag_start = i;
+            let (tag, value_start, value_end) = Certificate::read_tlv(&data[..end], &mut i)?;
+            let value = &data[value_start..value_end];
 
-            match tag_enum {
-                CertificateTag::ApplicationTemplate => { /* ignore */ }
-                CertificateTag::Extensions => { /* ignore */ }
+            match CertificateTag::from(tag) {
+                CertificateTag::Certificate => {
+                    Certificate::parse(data, value_start, value_end, cert)?;
+                }
                 CertificateTag::CertificateBody => {
-                    cert.certificate_body = Some(data[start..i + data_part_len].to_vec());
+                    cert.certificate_body = Some(data[tag_start..value_end].to_vec());
+                    Certificate::parse(data, value_start, value_end, cert)?;
+                }
+                CertificateTag::PublicKey => {
+                    Certificate::parse(data, value_start, value_end, cert)?;
                 }
                 CertificateTag::CertificateProfileIdentifier => {
-                    cert.certificate_profile_identifier = Certificate::to_u32(data.as_slice(), i, data_part_len);
+                    cert.certificate_profile_identifier = Certificate::to_u32(value);
                 }
                 CertificateTag::CertificateAuthorityReference => {
-                    cert.certificate_authority_reference = Some(data[i..i + data_part_len].to_vec());
+                    cert.certificate_authority_reference = Some(value.to_vec());
                 }
                 CertificateTag::CertificateHolderAuthorisation => {
-                    cert.certificate_holder_authorisation = Some(data[i..i + data_part_len].to_vec());
+                    cert.certificate_holder_authorisation = Some(value.to_vec());
                 }
                 CertificateTag::DomainParameters => {
-                    cert.domain_parameters = Some(Certificate::to_object_identifier(&data[i..i + data_part_len]));
+                    cert.domain_parameters = Some(Certificate::to_object_identifier(value));
+                    cert.domain_parameters_oid = Some(value.to_vec());
                 }
                 CertificateTag::PublicPoint => {
-                    cert.public_point = Some(data[i..i + data_part_len].to_vec());
+                    cert.public_point = Some(value.to_vec());
                 }
                 CertificateTag::CertificateHolderReference => {
-                    cert.certificate_holder_reference = Some(data[i..i + data_part_len].to_vec());
+                    cert.certificate_holder_reference = Some(value.to_vec());
                 }
                 CertificateTag::CertificateEffectiveDate => {
-                    cert.certificate_effective_date = Some(Certificate::to_time_real(&data[i..i + data_part_len]));
+                    cert.certificate_effective_date = Some(TimeReal::new(Certificate::to_u32(value)));
                 }
                 CertificateTag::CertificateExpirationDate => {
-                    cert.certificate_expiration_date = Some(Certificate::to_time_real(&data[i..i + data_part_len]));
+                    cert.certificate_expiration_date = Some(TimeReal::new(Certificate::to_u32(value)));
                 }
                 CertificateTag::CertificateSignature => {
-                    cert.certificate_signature = Some(data[i..i + data_part_len].to_vec());
+                    cert.certificate_signature = Some(value.to_vec());
                 }
                 CertificateTag::Unknown => {
                     debug!("Unknown tag: {:#X}", tag);
                 }
             }
+        }
+        Ok(())
+    }
 
-            i += data_part_len;
+    fn to_u32(data: &[u8]) -> u32 {
+        data.iter().fold(0u32, |acc, &b| (acc << 8) | b as u32)
+    }
+
+    /// Returns the dotted notation of the DER encoded object identifier `bytes`.
+    fn to_object_identifier(bytes: &[u8]) -> String {
+        let mut arcs: Vec<u64> = Vec::new();
+        let mut value = 0u64;
+        for &byte in bytes {
+            value = (value << 7) | (byte & 0x7F) as u64;
+            if byte & 0x80 == 0 {
+                if arcs.is_empty() {
+                    let first = (value / 40).min(2);
+                    arcs.push(first);
+                    arcs.push(value - first * 40);
+                } else {
+                    arcs.push(value);
+                }
+                value = 0;
+            }
         }
+        arcs.iter().map(|arc| arc.to_string()).collect::<Vec<_>>().join(".")
+    }
 
-        cert.clone()
+    /// Returns the curve and the public point of the key of the certificate holder.
+    fn public_key(&self) -> Result<(EccCurve, EccPoint)> {
+        let oid = self
+            .domain_parameters_oid
+            .as_ref()
+            .ok_or_else(|| Error::VerifyError("Missing domain parameters in Certificate.".to_string()))?;
+        let curve = EccCurve::from_oid(oid).ok_or_else(|| {
+            Error::VerifyError(format!("Domain parameters {} are not supported.", Certificate::to_object_identifier(oid)))
+        })?;
+        let public_point = self
+            .public_point
+            .as_ref()
+            .and_then(|public_point| curve.decode_point(public_point))
+            .ok_or_else(|| Error::VerifyError("Invalid public point in Certificate.".to_string()))?;
+        Ok((curve, public_point))
     }
+}
+
+fn create_certificate_from(card_file_data: &CardFileData) -> Result<Certificate> {
+    let data = card_file_data.data.as_ref().ok_or_else(|| Error::VerifyError("Missing Certificate Data.".to_string()))?;
+    Certificate::from_bytes(data)
+}
 
-    fn to_u32(data: &[u8], offset: usize, len: usize) -> u32 {
-        data[offset..offset + len].iter().fold(0u32, |acc, &b| (acc << 8) | b as u32)
+/// Verifies that `certificate` is issued by the holder of `authority`, the certificate
+/// authority reference is the authority holder reference and the certificate body is
+/// signed with the key of the authority.
+fn verify_certificate(certificate: &Certificate, authority: &Certificate, name: &str) -> Result<()> {
+    if certificate.certificate_authority_reference.is_none()
+        || certificate.certificate_authority_reference != authority.certificate_holder_reference
+    {
+        return Err(Error::VerifyError(format!(
+            "{name} Certification authority reference and authority holder reference are not same"
+        )));
     }
 
-    fn to_object_identifier(bytes: &[u8]) -> String {
-        // basic OID decode placeholder
-        bytes.iter().map(|b| b.to_string()).collect::<Vec<_>>().join(".")
+    let (curve, public_point) = authority.public_key()?;
+    let body = certificate
+        .certificate_body
+        .as_ref()
+        .ok_or_else(|| Error::VerifyError(format!("Missing certificate body in {name} Certificate.")))?;
+    let signature = certificate
+        .certificate_signature
+        .as_ref()
+        .ok_or_else(|| Error::VerifyError(format!("Missing signature in {name} Certificate.")))?;
+    if !curve.verify(&public_point, body, signature) {
+        return Err(Error::VerifyError(format!("{name} Certificate signature is not valid")));
     }
+    Ok(())
+}
 
-    fn to_time_real(data: &[u8]) -> TimeReal {
-        TimeReal::new(Certificate::to_u32(data, 0, data.len()))
+fn verify_data(data_files: &CardFilesMap, card_certificate: &Certificate) -> Result<Vec<VerifyItem>> {
+    let (curve, public_point) = card_certificate.public_key()?;
+    let mut result: Vec<VerifyItem> = Vec::new();
+    for data_file in data_files.iter() {
+        let id = data_file.0;
+        if matches!(
+            id,
+            CardFileID::CACertificate
+                | CardFileID::CardCertificate
+                | CardFileID::CardSignCertificate
+                | CardFileID::LinkCertificate
+        ) {
+            continue;
+        }
+
+        let data = data_file.1;
+
+        let Some(raw_data) = data.data.as_ref() else {
+            result.push(VerifyItem { card_file_id: id.clone(), status: VerifyStatus::NotHaveData, end_of_validity: None });
+            continue;
+        };
+
+        let Some(signature) = data.signature.as_ref() else {
+            result.push(VerifyItem { card_file_id: id.clone(), status: VerifyStatus::NotHaveSignature, end_of_validity: None });
+            continue;
+        };
+
+        let status = if signature.len() != 2 * curve.size {
+            VerifyStatus::InvalidSignatureSize
+        } else if curve.verify(&public_point, raw_data, signature) {
+            VerifyStatus::Valid
+        } else {
+            VerifyStatus::Invalid
+        };
+        result.push(VerifyItem { card_file_id: id.clone(), status, end_of_validity: None });
     }
+
+    Ok(result)
 }
 
-pub fn verify(data_files: &CardFilesMap, _erca_pk: &[u8; 205]) -> Result<VerifyResult> {
+/// Returns the certificate of the European root which issued the certificates of the
+/// authority `authority_reference`, a self-signed root certificate of `roots` or a link
+/// certificate of `link_certificates` issued, directly or through other link
+/// certificates, by a root certificate. Each certificate of the chain is verified.
+fn find_root_certificate(
+    authority_reference: &[u8],
+    roots: &[Certificate],
+    link_certificates: &[Certificate],
+) -> Result<Certificate> {
+    let mut links: Vec<&Certificate> = Vec::new();
+    let mut reference = authority_reference;
+    loop {
+        if let Some(root) = roots.iter().find(|root| root.certificate_holder_reference.as_deref() == Some(reference)) {
+            verify_certificate(root, root, "ERCA")?;
+            let mut authority = root.clone();
+            for link in links.into_iter().rev() {
+                verify_certificate(link, &authority, "Link")?;
+                authority = link.clone();
+            }
+            return Ok(authority);
+        }
+
+        let link = link_certificates
+            .iter()
+            .filter(|link| !links.iter().any(|walked| std::ptr::eq(*walked, *link)))
+            .find(|link| link.certificate_holder_reference.as_deref() == Some(reference))
+            .ok_or_else(|| {
+                Error::VerifyError(format!("Missing ERCA Certificate of the certification authority reference {reference:02X?}"))
+            })?;
+        reference = link
+            .certificate_authority_reference
+            .as_deref()
+            .ok_or_else(|| Error::VerifyError("Missing certification authority reference in Link Certificate.".to_string()))?;
+        links.push(link);
+    }
+}
+
+/// Returns the certification authority reference and the certificate holder reference of
+/// the certificate `data`, the references of a self-signed root certificate are the same.
+pub(crate) fn certificate_references(data: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+    let certificate = Certificate::from_bytes(data)?;
+    Ok((
+        certificate.certificate_authority_reference.unwrap_or_default(),
+        certificate.certificate_holder_reference.unwrap_or_default(),
+    ))
+}
+
+/// Returns the certificate of the authority which issued `member_state_certificate`, from
+/// a root of `roots` as `find_root_certificate`, or `member_state_certificate` itself when
+/// no root issued it and it is one of `member_state_certificates`, the authenticated
+/// certificates of the MSCA cache verified before.
+fn find_member_state_authority(
+    member_state_certificate: &Certificate,
+    roots: &[Certificate],
+    link_certificates: &[Certificate],
+    member_state_certificates: &[Vec<u8>],
+) -> Result<Option<Certificate>> {
+    let authority_reference = member_state_certificate.certificate_authority_reference.as_deref().unwrap_or_default();
+    match find_root_certificate(authority_reference, roots, link_certificates) {
+        Ok(root) => Ok(Some(root)),
+        Err(error) => {
+            let is_cached =
+                member_state_certificates.iter().filter_map(|cached| Certificate::from_bytes(cached).ok()).any(|cached| {
+                    cached.certificate_body == member_state_certificate.certificate_body
+                        && cached.certificate_signature == member_state_certificate.certificate_signature
+                });
+            if is_cached { Ok(None) } else { Err(error) }
+        }
+    }
+}
+
+/// Verifies the certificate chain of a Gen2 card from the ERCA root certificate `erca_pk`
+/// and then the ECDSA signatures of the card files with the key of the card signature
+/// certificate, or of the card certificate for cards without a signature certificate.
+pub fn verify(data_files: &CardFilesMap, erca_pk: &[u8; 205]) -> Result<VerifyResult> {
+    verify_with_roots(data_files, &[erca_pk.to_vec()], &[], &[]).map(|(result, _)| result)
+}
+
+/// Verifies a Gen2 card as `verify`, from the root certificate of `roots` which issued the
+/// CA certificate of the card, from a root through the link certificates of
+/// `link_certificates` and the link certificate of the card, or from the cached
+/// `member_state_certificates`. The CA certificate is returned with the result, as it is
+/// cached.
+pub(crate) fn verify_with_roots(
+    data_files: &CardFilesMap,
+    roots: &[Vec<u8>],
+    link_certificates: &[Vec<u8>],
+    member_state_certificates: &[Vec<u8>],
+) -> Result<(VerifyResult, Vec<u8>)> {
     let ic = data_files.get(&CardFileID::IC);
     let icc = data_files.get(&CardFileID::ICC);
     if ic.is_none() || icc.is_none() {
-        return Ok(VerifyResult { status: crate::tacho::VerifyResultStatus::Unsigned, result: Vec::new() });
+        return Ok((VerifyResult { status: VerifyResultStatus::Unsigned, result: Vec::new() }, Vec::new()));
     }
     let ca_cert_file =
         data_files.get(&CardFileID::CACertificate).ok_or(Error::VerifyError("Missing CA Certificate.".to_string()))?;
-    let _card_cert_file = data_files
-        .get(&CardFileID::CardSignCertificate)
+    let (card_cert_id, card_cert_file) = [CardFileID::CardSignCertificate, CardFileID::CardCertificate]
+        .into_iter()
+        .find_map(|id| data_files.get(&id).filter(|file| file.data.is_some()).map(|file| (id, file)))
         .ok_or(Error::VerifyError("Missing Card Sign Certificate.".to_string()))?;
 
-    let ca_signature = ca_cert_file.data.as_ref().ok_or_else(|| Error::VerifyError("Missing Certificate Data.".to_string()))?;
+    let ca_certificate = create_certificate_from(ca_cert_file)?;
+    let card_certificate = create_certificate_from(card_cert_file)?;
+    let roots: Vec<Certificate> = roots.iter().map(|root| Certificate::from_bytes(root)).collect::<Result<_>>()?;
+    // The link certificate file of a card is empty until the card receives a link certificate.
+    let card_link_certificate = data_files
+        .get(&CardFileID::LinkCertificate)
+        .and_then(|file| file.data.as_ref())
+        .and_then(|data| Certificate::from_bytes(data).ok());
+    let link_certificates: Vec<Certificate> = link_certificates
+        .iter()
+        .map(|link| Certificate::from_bytes(link))
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .chain(card_link_certificate)
+        .collect();
+    if let Some(erca_certificate) =
+        find_member_state_authority(&ca_certificate, &roots, &link_certificates, member_state_certificates)?
+    {
+        verify_certificate(&ca_certificate, &erca_certificate, "CA")?;
+    }
+    debug!("verify - CA Certificate: {:?}", ca_certificate);
+    verify_certificate(&card_certificate, &ca_certificate, "Card")?;
+    debug!("verify - Card Certificate: {:?}", card_certificate);
+
+    let mut result = vec![
+        VerifyItem {
+            status: VerifyStatus::Valid,
+            card_file_id: CardFileID::CACertificate,
+            end_of_validity: ca_certificate.certificate_expiration_date.clone(),
+        },
+        VerifyItem {
+            status: VerifyStatus::Valid,
+            card_file_id: card_cert_id,
+            end_of_validity: card_certificate.certificate_expiration_date.clone(),
+        },
+    ];
+    let verifed_data = verify_data(data_files, &card_certificate)?;
+    result.extend(verifed_data);
 
-    let ca_signature_array: &[u8; 205] = ca_signature
-        .as_slice()
-        .try_into()
-        .map_err(|_| Error::VerifyError("Invalid signature length in Certificate.".to_string()))?;
+    let cached_ca_certificate = ca_cert_file.data.clone().unwrap_or_default();
+    Ok((VerifyResult { status: VerifyResultStatus::Valid, result }, cached_ca_certificate))
+}
 
-    let ca_certificate = Certificate::from_bytes(ca_signature_array);
-    debug!("verify - CA Certificate: {:?}", ca_certificate);
+/// Returns true when `record_array` is a record array of `record_type`.
+fn is_record_array_of(record_array: &[u8], record_type: &RecordType) -> bool {
+    record_array.first() == Some(&u8::from(record_type))
+}
 
-    Err(Error::NotImplemented)
+/// Returns the first record of `record_array`.
+fn first_record(record_array: &[u8]) -> Option<&[u8]> {
+    let record_size = u16::from_be_bytes([*record_array.get(1)?, *record_array.get(2)?]) as usize;
+    record_array.get(RECORD_ARRAY_HEADER_LENGTH..RECORD_ARRAY_HEADER_LENGTH + record_size)
+}
+
+/// Returns the status of the signature of a TREP, the signature is over the record
+/// arrays of the TREP before the signature record array, without the certificates of
+/// the overview.
+fn verify_trep(
+    item: &VUTransferResponseParameterItem<VUTransferResponseParameterData>,
+    curve: &EccCurve,
+    public_point: &EccPoint,
+) -> Result<VerifyStatus> {
+    let signature_record_array = match &item.data {
+        VUTransferResponseParameterData::Control(data) => Some(&data.signature_record_array),
+        VUTransferResponseParameterData::Activity(data) => data.signature_record_array.as_ref(),
+        VUTransferResponseParameterData::Events(data) => data.signature_record_array.as_ref(),
+        VUTransferResponseParameterData::Speed(data) => data.signature_record_array.as_ref(),
+        VUTransferResponseParameterData::Calibration(data) => data.signature_record_array.as_ref(),
+        VUTransferResponseParameterData::CardDownload(data) => data.signature_record_array.as_ref(),
+        _ => return Ok(VerifyStatus::NotHaveSignature),
+    };
+    let Some(signature) = signature_record_array.and_then(|signature_record_array| signature_record_array.records.first()) else {
+        return Ok(VerifyStatus::NotHaveSignature);
+    };
+    if signature.len() != 2 * curve.size {
+        return Ok(VerifyStatus::InvalidSignatureSize);
+    }
+
+    let raw_data = item.get_raw_data()?;
+    let record_arrays = split_record_arrays(&raw_data)?;
+    let Some((signature_record_array, record_arrays)) = record_arrays.split_last() else {
+        return Ok(VerifyStatus::NotHaveData);
+    };
+    if !is_record_array_of(signature_record_array, &RecordType::Signature) {
+        return Ok(VerifyStatus::NotHaveData);
+    }
+    let signed_data = record_arrays
+        .iter()
+        .filter(|record_array| {
+            !is_record_array_of(record_array, &RecordType::MemberStateCertificate)
+                && !is_record_array_of(record_array, &RecordType::VuCertificate)
+        })
+        .copied()
+        .collect::<Vec<&[u8]>>()
+        .concat();
+    if curve.verify(public_point, &signed_data, signature) {
+        return Ok(VerifyStatus::Valid);
+    }
+    Ok(VerifyStatus::Invalid)
+}
+
+/// Verifies the certificate chain of a Gen2 VU download from the ERCA root certificate
+/// `erca_pk` to the member state and the VU certificates of the overview, and then the
+/// ECDSA signature of each TREP with the key of the VU certificate.
+pub fn verify_vu(vu_data: &VUData, erca_pk: &[u8; 205]) -> Result<VuVerifyResult> {
+    verify_vu_with_roots(vu_data, &[erca_pk.to_vec()], &[], &[]).map(|(result, _)| result)
+}
+
+/// Verifies a Gen2 VU download as `verify_vu`, from the root certificate of `roots` which
+/// issued the member state certificate of the overview, from a root through the link
+/// certificates of `link_certificates`, or from the cached `member_state_certificates`.
+/// The member state certificate is returned with the result, as it is cached.
+pub(crate) fn verify_vu_with_roots(
+    vu_data: &VUData,
+    roots: &[Vec<u8>],
+    link_certificates: &[Vec<u8>],
+    member_state_certificates: &[Vec<u8>],
+) -> Result<(VuVerifyResult, Vec<u8>)> {
+    let treps = <dyn tacho::VUData<VUTransferResponseParameterData>>::get_data(vu_data);
+    let overview = treps
+        .iter()
+        .find(|item| matches!(item.data, VUTransferResponseParameterData::Control(_)))
+        .ok_or(Error::VerifyError("Missing VU Overview.".to_string()))?;
+    let overview_data = overview.get_raw_data()?;
+    let overview_record_arrays = split_record_arrays(&overview_data)?;
+    let certificate_data_of = |record_type: &RecordType, name: &str| {
+        overview_record_arrays
+            .iter()
+            .find(|record_array| is_record_array_of(record_array, record_type))
+            .and_then(|record_array| first_record(record_array))
+            .ok_or_else(|| Error::VerifyError(format!("Missing {name} Certificate.")))
+    };
+
+    let member_state_certificate_data = certificate_data_of(&RecordType::MemberStateCertificate, "Member State")?;
+    let member_state_certificate = Certificate::from_bytes(member_state_certificate_data)?;
+    let vu_certificate = Certificate::from_bytes(certificate_data_of(&RecordType::VuCertificate, "VU")?)?;
+    let roots: Vec<Certificate> = roots.iter().map(|root| Certificate::from_bytes(root)).collect::<Result<_>>()?;
+    let link_certificates: Vec<Certificate> =
+        link_certificates.iter().map(|link| Certificate::from_bytes(link)).collect::<Result<_>>()?;
+    if let Some(erca_certificate) =
+        find_member_state_authority(&member_state_certificate, &roots, &link_certificates, member_state_certificates)?
+    {
+        verify_certificate(&member_state_certificate, &erca_certificate, "Member State")?;
+    }
+    debug!("verify_vu - Member State Certificate: {:?}", member_state_certificate);
+    verify_certificate(&vu_certificate, &member_state_certificate, "VU")?;
+    debug!("verify_vu - VU Certificate: {:?}", vu_certificate);
+
+    let (curve, public_point) = vu_certificate.public_key()?;
+    let mut result = Vec::with_capacity(treps.len());
+    for item in treps.iter() {
+        let status = verify_trep(item, &curve, &public_point)?;
+        result.push(VuVerifyItem { trep_id: item.type_id.clone(), position: item.position, status });
+    }
+
+    let result = VuVerifyResult::new(
+        member_state_certificate.certificate_expiration_date,
+        vu_certificate.certificate_expiration_date,
+        result,
+    );
+    Ok((result, member_state_certificate_data.to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::Gen2TestPki;
+    use crate::tacho::EquipmentType;
+
+    #[test]
+    fn test_certificate_from_bytes() {
+        let pki = Gen2TestPki::new(1);
+        let mut data = pki.card_sign_certificate(&EquipmentType::DriverCard);
+        data.extend_from_slice(&[0x00; 16]);
+
+        let certificate = Certificate::from_bytes(&data).unwrap();
+        assert_eq!(certificate.domain_parameters.as_deref(), Some("1.3.36.3.3.2.8.1.1.7"));
+        assert_eq!(certificate.certificate_body.as_ref().map(|body| &body[..2]), Some(&[0x7F, 0x4E][..]));
+        assert_eq!(certificate.certificate_signature.as_ref().map(Vec::len), Some(64));
+        assert!(certificate.certificate_expiration_date.is_some());
+        verify_certificate(&certificate, &Certificate::from_bytes(&pki.msca_certificate()).unwrap(), "Card").unwrap();
+
+        assert!(verify_certificate(&certificate, &Certificate::from_bytes(&pki.erca_certificate()).unwrap(), "Card").is_err());
+        assert!(Certificate::from_bytes(&data[..100]).is_err());
+    }
 }
@@ -1,16 +1,18 @@
-use binary_data::{BinSeek, ReadBytes};
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
 use log::debug;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::Result;
 use crate::gen2::{
-    DataInfo, DataInfoGenericRecordArray, SignatureRecordArray, VuActivityDailyRecordArray, VuCardIWRecord, VuGnssadRecord,
-    VuPlaceDailyWorkPeriodRecordArray,
+    DataInfoGenericRecordArray, SignatureRecordArray, VuActivityDailyRecordArray, VuBorderCrossingRecord, VuCardIWRecord,
+    VuGnssadRecord, VuLoadUnloadRecord, VuPlaceDailyWorkPeriodRecordArray,
 };
-use crate::tacho::{OdometerShort, SpecificConditionRecord, TimeReal, VUTransferResponseParameterID};
+use crate::tacho::{OdometerShort, RecordType, SpecificConditionRecord, TimeReal, VUTransferResponseParameterID};
+use crate::tachograph_gen2::data_info::DataInfoReader;
+use crate::{Result, Writable};
 
-/// Data structure generation 2, version 1 (TREP 22 Hex)
-#[derive(Debug, Serialize)]
+/// Data structure generation 2, version 1 (TREP 22 Hex) and version 2 (TREP 32 Hex)
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VUActivity {
     #[serde(rename = "dateOfDayDownloadedRecordArray")]
     pub date_of_day_downloaded_record_array: DataInfoGenericRecordArray<TimeReal>,
@@ -26,6 +28,12 @@ pub struct VUActivity {
     pub vu_gnssad_record_array: DataInfoGenericRecordArray<VuGnssadRecord>,
     #[serde(rename = "vuSpecificConditionRecordArray")]
     pub vu_specific_condition_record_array: DataInfoGenericRecordArray<SpecificConditionRecord>,
+    /// Only in generation 2, version 2.
+    #[serde(rename = "vuBorderCrossingRecordArray")]
+    pub vu_border_crossing_record_array: Option<DataInfoGenericRecordArray<VuBorderCrossingRecord>>,
+    /// Only in generation 2, version 2.
+    #[serde(rename = "vuLoadUnloadRecordArray")]
+    pub vu_load_unload_record_array: Option<DataInfoGenericRecordArray<VuLoadUnloadRecord>>,
     #[serde(rename = "signatureRecordArray")]
     pub signature_record_array: Option<SignatureRecordArray>,
 }
@@ -33,26 +41,30 @@ pub struct VUActivity {
 impl VUActivity {
     pub fn from_data<R: ReadBytes + BinSeek>(trep_id: VUTransferResponseParameterID, reader: &mut R) -> Result<VUActivity> {
         debug!("VUControlActivity::from_data - Trep ID: {trep_id:?}");
+        // In lenient mode a truncated TREP keeps the record arrays read before the truncation.
+        let mut data_infos = DataInfoReader::new(reader, trep_id.clone());
         let date_of_day_downloaded_record_array: DataInfoGenericRecordArray<TimeReal> =
-            DataInfo::read(reader, trep_id.clone())?.parse()?;
+            data_infos.next(RecordType::DateOfDayDownloaded)?.parse()?;
         let odometer_value_midnight_record_array: DataInfoGenericRecordArray<OdometerShort> =
-            DataInfo::read(reader, trep_id.clone())?.parse()?;
+            data_infos.next(RecordType::OdometerValueMidnight)?.parse()?;
         let vu_card_iw_record_array: DataInfoGenericRecordArray<VuCardIWRecord> =
-            DataInfo::read(reader, trep_id.clone())?.parse()?;
-        let vu_activity_daily_record_array: VuActivityDailyRecordArray = DataInfo::read(reader, trep_id.clone())?.parse()?;
+            data_infos.next(RecordType::VuCardIWRecord)?.parse()?;
+        let vu_activity_daily_record_array: VuActivityDailyRecordArray =
+            data_infos.next(RecordType::ActivityChangeInfo)?.parse()?;
         let vu_place_daily_work_period_record_array: VuPlaceDailyWorkPeriodRecordArray =
-            DataInfo::read(reader, trep_id.clone())?.parse()?;
+            data_infos.next(RecordType::VuPlaceDailyWorkPeriodRecord)?.parse()?;
         let vu_gnssad_record_array: DataInfoGenericRecordArray<VuGnssadRecord> =
-            DataInfo::read(reader, trep_id.clone())?.parse_with_params()?;
+            data_infos.next(RecordType::VuGNSSADRecord)?.parse_with_params()?;
         let vu_specific_condition_record_array: DataInfoGenericRecordArray<SpecificConditionRecord> =
-            DataInfo::read(reader, trep_id.clone())?.parse()?;
+            data_infos.next(RecordType::SpecificConditionRecord)?.parse()?;
 
+        let mut vu_border_crossing_record_array: Option<DataInfoGenericRecordArray<VuBorderCrossingRecord>> = None;
+        let mut vu_load_unload_record_array: Option<DataInfoGenericRecordArray<VuLoadUnloadRecord>> = None;
         if trep_id == VUTransferResponseParameterID::Gen2v2Activities {
-            // Two record are not in use
-            DataInfo::read(reader, trep_id.clone())?;
-            DataInfo::read(reader, trep_id.clone())?;
+            vu_border_crossing_record_array = Some(data_infos.next(RecordType::VuBorderCrossingRecord)?.parse()?);
+            vu_load_unload_record_array = Some(data_infos.next(RecordType::VuLoadUnloadRecord)?.parse()?);
         }
-        let signature_record_array: Option<SignatureRecordArray> = Some(DataInfo::read(reader, trep_id.clone())?.parse()?);
+        let signature_record_array: Option<SignatureRecordArray> = Some(data_infos.next(RecordType::Signature)?.parse()?);
 
         Ok(Self {
             date_of_day_downloaded_record_array,
@@ -62,7 +74,32 @@ impl VUActivity {
             vu_place_daily_work_period_record_array,
             vu_gnssad_record_array,
             vu_specific_condition_record_array,
+            vu_border_crossing_record_array,
+            vu_load_unload_record_array,
             signature_record_array,
         })
     }
 }
+
+impl Writable for VUActivity {
+    /// Writes the record arrays of a `VUActivity` TREP to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.date_of_day_downloaded_record_array.write(writer)?;
+        self.odometer_value_midnight_record_array.write(writer)?;
+        self.vu_card_iw_record_array.write(writer)?;
+        self.vu_activity_daily_record_array.write(writer)?;
+        self.vu_place_daily_work_period_record_array.write(writer)?;
+        self.vu_gnssad_record_array.write(writer)?;
+        self.vu_specific_condition_record_array.write(writer)?;
+        if let Some(vu_border_crossing_record_array) = &self.vu_border_crossing_record_array {
+            vu_border_crossing_record_array.write(writer)?;
+        }
+        if let Some(vu_load_unload_record_array) = &self.vu_load_unload_record_array {
+            vu_load_unload_record_array.write(writer)?;
+        }
+        if let Some(signature_record_array) = &self.signature_record_array {
+            signature_record_array.write(writer)?;
+        }
+        Ok(())
+    }
+}
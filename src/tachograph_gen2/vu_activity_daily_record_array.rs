@@ -1,17 +1,18 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    ReadableWithParams, Result,
+    ReadableWithParams, Result, Writable,
     gen2::DataInfoReadable,
     tacho::{ActivityCard, ActivityChangeInfo, ActivityChangeInfoParams, RecordType},
-    tachograph_gen2::data_info::DataConfig,
+    tachograph_gen2::data_info::{DataConfig, write_record_array},
 };
 
 /// Information, stored in a VU, related to changes of activity and/or
 /// changes of driving status and/or changes of card status for a given
 /// calendar day (Annex 1C requirement 105, 106, 107) and to slots status at 00:00 that day.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuActivityDailyRecordArray {
     #[serde(rename = "noOfRecords")]
     pub no_of_records: u16,
@@ -28,12 +29,15 @@ impl DataInfoReadable<VuActivityDailyRecordArray> for VuActivityDailyRecordArray
         let record_size = config.record_size;
         let record_type = config.record_type.clone();
 
-        let mut records: Vec<ActivityChangeInfo> = Vec::with_capacity(no_of_records as usize);
         let params = ActivityChangeInfoParams::new(ActivityCard::Vu);
-        for _ in 0..no_of_records {
-            let record = ActivityChangeInfo::read(reader, &params)?;
-            records.push(record);
-        }
+        let records: Vec<ActivityChangeInfo> = config.read_records(reader, |reader| ActivityChangeInfo::read(reader, &params))?;
         Ok(Self { no_of_records, record_size, record_type, records })
     }
 }
+
+impl Writable for VuActivityDailyRecordArray {
+    /// Writes the record array of the activity changes.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        write_record_array(writer, &self.record_type, self.record_size, &self.records, |writer, record| record.write(writer))
+    }
+}
@@ -0,0 +1,62 @@
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Readable, ReadableField, Result, SpanRecorder, Writable,
+    gen2::{FullCardNumberAndGeneration, GnssPlaceAuthRecord},
+    tacho::{NationNumeric, OdometerShort},
+};
+
+/// Information, stored in a vehicle unit, related to a border crossing of the
+/// vehicle (Annex IC requirement 133a, generation 2, version 2).
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct VuBorderCrossingRecord {
+    #[serde(rename = "cardNumberAndGenDriverSlot")]
+    pub card_number_and_gen_driver_slot: FullCardNumberAndGeneration,
+    #[serde(rename = "cardNumberAndGenCodriverSlot")]
+    pub card_number_and_gen_co_driver_slot: FullCardNumberAndGeneration,
+    #[serde(rename = "countryLeft")]
+    pub country_left: NationNumeric,
+    #[serde(rename = "countryEntered")]
+    pub country_entered: NationNumeric,
+    #[serde(rename = "gnssPlaceAuthRecord")]
+    pub gnss_place_auth_record: GnssPlaceAuthRecord,
+    #[serde(rename = "vehicleOdometerValue")]
+    pub vehicle_odometer_value: OdometerShort,
+}
+
+impl Readable<VuBorderCrossingRecord> for VuBorderCrossingRecord {
+    fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<VuBorderCrossingRecord> {
+        let card_number_and_gen_driver_slot = FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGenDriverSlot")?;
+        let card_number_and_gen_co_driver_slot = FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGenCodriverSlot")?;
+        let country_left: NationNumeric =
+            SpanRecorder::field("countryLeft", "NationNumeric", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let country_entered: NationNumeric =
+            SpanRecorder::field("countryEntered", "NationNumeric", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let gnss_place_auth_record = GnssPlaceAuthRecord::read_field(reader, "gnssPlaceAuthRecord")?;
+        let vehicle_odometer_value = OdometerShort::read_field(reader, "vehicleOdometerValue")?;
+
+        Ok(Self {
+            card_number_and_gen_driver_slot,
+            card_number_and_gen_co_driver_slot,
+            country_left,
+            country_entered,
+            gnss_place_auth_record,
+            vehicle_odometer_value,
+        })
+    }
+}
+
+impl Writable for VuBorderCrossingRecord {
+    /// Writes a `VuBorderCrossingRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.card_number_and_gen_driver_slot.write(writer)?;
+        self.card_number_and_gen_co_driver_slot.write(writer)?;
+        writer.write_u8((&self.country_left).into())?;
+        writer.write_u8((&self.country_entered).into())?;
+        self.gnss_place_auth_record.write(writer)?;
+        self.vehicle_odometer_value.write(writer)?;
+        Ok(())
+    }
+}
@@ -1,18 +1,20 @@
-use binary_data::{BigEndian, BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BigEndian, BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, ReadableWithParams, Result, bytes_to_ia5_fix_string,
-    gen2::SealDataVu,
+    ReadableField, ReadableWithParams, Result, SpanRecorder, Writable, bytes_to_ia5_fix_string,
+    gen2::{LoadType, SealDataVu},
     tacho::{
-        Address, CalibrationPurpose, FullCardNumber, Name, OdometerShort, TimeReal, VUTransferResponseParameterID,
-        VehicleRegistrationIdentification,
+        Address, CalibrationPurpose, ExtendedSerialNumber, FullCardNumber, Name, NationNumeric, OdometerShort, TimeReal,
+        VUTransferResponseParameterID, VehicleRegistrationIdentification,
     },
+    write_ia5_string,
 };
 
 /// Information, stored in a vehicle unit, related a calibration of the
 /// recording equipment (Annex 1B requirement 098 and Annex 1C requirement 119 and 120).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuCalibrationRecord {
     #[serde(rename = "isGen2V2")]
     pub is_gen2_v2: bool,
@@ -52,6 +54,24 @@ pub struct VuCalibrationRecord {
     pub next_calibration_date: TimeReal,
     #[serde(rename = "sealDataVu")]
     pub seal_data_vu: SealDataVu,
+    /// Default load type of the vehicle (only present in version 2).
+    #[serde(rename = "byDefaultLoadType")]
+    pub by_default_load_type: Option<LoadType>,
+    /// Country in which the calibration was performed (only present in version 2).
+    #[serde(rename = "calibrationCountry")]
+    pub calibration_country: Option<NationNumeric>,
+    /// Date and time when the calibration country was provided by the GNSS receiver (only present in version 2).
+    #[serde(rename = "calibrationCountryTimestamp")]
+    pub calibration_country_timestamp: Option<TimeReal>,
+    /// Serial number of the motion sensor paired with the vehicle unit (only present in version 2).
+    #[serde(rename = "sensorSerialNumber")]
+    pub sensor_serial_number: Option<ExtendedSerialNumber>,
+    /// Serial number of the external GNSS facility coupled with the vehicle unit (only present in version 2).
+    #[serde(rename = "sensorGNSSSerialNumber")]
+    pub sensor_gnss_serial_number: Option<ExtendedSerialNumber>,
+    /// Serial number of the remote communication module (only present in version 2).
+    #[serde(rename = "rcmSerialNumber")]
+    pub rcm_serial_number: Option<ExtendedSerialNumber>,
 }
 
 const VEHICLE_IDENTIFICATION_NUMBER_LENGTH: u32 = 17;
@@ -61,31 +81,64 @@ impl ReadableWithParams<VuCalibrationRecord> for VuCalibrationRecord {
     type P = VUTransferResponseParameterID;
 
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<VuCalibrationRecord> {
-        let calibration_purpose: CalibrationPurpose = reader.read_u8()?.into();
-        let workshop_name = Name::read(reader)?;
-        let workshop_address = Address::read(reader)?;
-        let workshop_card_number = FullCardNumber::read(reader)?;
-        let workshop_card_expiry_date = TimeReal::read(reader)?;
-        let vehicle_identification_number =
-            bytes_to_ia5_fix_string(&reader.read_into_vec(VEHICLE_IDENTIFICATION_NUMBER_LENGTH)?)?;
-        let vehicle_registration_identification = VehicleRegistrationIdentification::read(reader)?;
-        let w_vehicle_characteristic_constant = reader.read_u16::<BigEndian>()?;
-        let k_constant_of_recording_equipment = reader.read_u16::<BigEndian>()?;
-        let l_tyre_circumference = reader.read_u16::<BigEndian>()?;
-        let tyre_size = bytes_to_ia5_fix_string(&reader.read_into_vec(TYRE_SIZE_LENGTH)?)?;
-        let authorised_speed = reader.read_u8()?;
-        let old_odometer_value = OdometerShort::read(reader)?;
-        let new_odometer_value = OdometerShort::read(reader)?;
-        let old_time_value = TimeReal::read(reader)?;
-        let new_time_value = TimeReal::read(reader)?;
-        let next_calibration_date = TimeReal::read(reader)?;
-        let seal_data_vu = SealDataVu::read(reader)?;
+        let calibration_purpose: CalibrationPurpose =
+            SpanRecorder::field("calibrationPurpose", "CalibrationPurpose", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let workshop_name = Name::read_field(reader, "workshopName")?;
+        let workshop_address = Address::read_field(reader, "workshopAddress")?;
+        let workshop_card_number = FullCardNumber::read_field(reader, "workshopCardNumber")?;
+        let workshop_card_expiry_date = TimeReal::read_field(reader, "workshopCardExpiryDate")?;
+        let vehicle_identification_number = SpanRecorder::field("vehicleIdentificationNumber", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(VEHICLE_IDENTIFICATION_NUMBER_LENGTH)?)?)
+        })?;
+        let vehicle_registration_identification =
+            VehicleRegistrationIdentification::read_field(reader, "vehicleRegistrationIdentification")?;
+        let w_vehicle_characteristic_constant =
+            SpanRecorder::field("wVehicleCharacteristicConstant", "INTEGER", reader, |reader| {
+                Ok(reader.read_u16::<BigEndian>()?)
+            })?;
+        let k_constant_of_recording_equipment = SpanRecorder::field(
+            "kConstantOfRecordingEquipment",
+            "INTEGER",
+            reader,
+            |reader| Ok(reader.read_u16::<BigEndian>()?),
+        )?;
+        let l_tyre_circumference =
+            SpanRecorder::field("lTyreCircumference", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
+        let tyre_size = SpanRecorder::field("tyreSize", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(TYRE_SIZE_LENGTH)?)?)
+        })?;
+        let authorised_speed = SpanRecorder::field("authorisedSpeed", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let old_odometer_value = OdometerShort::read_field(reader, "oldOdometerValue")?;
+        let new_odometer_value = OdometerShort::read_field(reader, "newOdometerValue")?;
+        let old_time_value = TimeReal::read_field(reader, "oldTimeValue")?;
+        let new_time_value = TimeReal::read_field(reader, "newTimeValue")?;
+        let next_calibration_date = TimeReal::read_field(reader, "nextCalibrationDate")?;
+        let seal_data_vu = SealDataVu::read_field(reader, "sealDataVu")?;
 
-        let is_gen2_v2: bool = *params == VUTransferResponseParameterID::Gen2v2Activities;
-        if is_gen2_v2 {
-            // TODO: not implemented for now.
-            let _ = reader.read_bytes::<30>()?;
-        }
+        let is_gen2_v2: bool = *params == VUTransferResponseParameterID::Gen2v2TechnicalData;
+        let (
+            by_default_load_type,
+            calibration_country,
+            calibration_country_timestamp,
+            sensor_serial_number,
+            sensor_gnss_serial_number,
+            rcm_serial_number,
+        ) = if is_gen2_v2 {
+            let by_default_load_type: LoadType =
+                SpanRecorder::field("byDefaultLoadType", "LoadType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+            let calibration_country: NationNumeric =
+                SpanRecorder::field("calibrationCountry", "NationNumeric", reader, |reader| Ok(reader.read_u8()?.into()))?;
+            (
+                Some(by_default_load_type),
+                Some(calibration_country),
+                Some(TimeReal::read_field(reader, "calibrationCountryTimestamp")?),
+                Some(ExtendedSerialNumber::read_field(reader, "sensorSerialNumber")?),
+                Some(ExtendedSerialNumber::read_field(reader, "sensorGNSSSerialNumber")?),
+                Some(ExtendedSerialNumber::read_field(reader, "rcmSerialNumber")?),
+            )
+        } else {
+            (None, None, None, None, None, None)
+        };
 
         Ok(Self {
             is_gen2_v2,
@@ -107,6 +160,51 @@ impl ReadableWithParams<VuCalibrationRecord> for VuCalibrationRecord {
             new_time_value,
             next_calibration_date,
             seal_data_vu,
+            by_default_load_type,
+            calibration_country,
+            calibration_country_timestamp,
+            sensor_serial_number,
+            sensor_gnss_serial_number,
+            rcm_serial_number,
         })
     }
 }
+
+impl Writable for VuCalibrationRecord {
+    /// Writes a `VuCalibrationRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u8((&self.calibration_purpose).into())?;
+        self.workshop_name.write(writer)?;
+        self.workshop_address.write(writer)?;
+        self.workshop_card_number.write(writer)?;
+        self.workshop_card_expiry_date.write(writer)?;
+        write_ia5_string(writer, &self.vehicle_identification_number, VEHICLE_IDENTIFICATION_NUMBER_LENGTH as usize)?;
+        self.vehicle_registration_identification.write(writer)?;
+        writer.write_u16::<BigEndian>(self.w_vehicle_characteristic_constant)?;
+        writer.write_u16::<BigEndian>(self.k_constant_of_recording_equipment)?;
+        writer.write_u16::<BigEndian>(self.l_tyre_circumference)?;
+        write_ia5_string(writer, &self.tyre_size, TYRE_SIZE_LENGTH as usize)?;
+        writer.write_u8(self.authorised_speed)?;
+        self.old_odometer_value.write(writer)?;
+        self.new_odometer_value.write(writer)?;
+        self.old_time_value.write(writer)?;
+        self.new_time_value.write(writer)?;
+        self.next_calibration_date.write(writer)?;
+        self.seal_data_vu.write(writer)?;
+        if let Some(by_default_load_type) = &self.by_default_load_type {
+            writer.write_u8(by_default_load_type.into())?;
+        }
+        if let Some(calibration_country) = &self.calibration_country {
+            writer.write_u8(calibration_country.into())?;
+        }
+        if let Some(calibration_country_timestamp) = &self.calibration_country_timestamp {
+            calibration_country_timestamp.write(writer)?;
+        }
+        for serial_number in
+            [&self.sensor_serial_number, &self.sensor_gnss_serial_number, &self.rcm_serial_number].into_iter().flatten()
+        {
+            serial_number.write(writer)?;
+        }
+        Ok(())
+    }
+}
@@ -1,29 +1,95 @@
-use binary_data::{BinSeek, ReadBytes};
+use binary_data::{BigEndian, BinSeek, ReadBytes, WriteBytes};
 use log::debug;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::Result;
-use crate::gen2::{CardData, DataInfo, SignatureRecordArray};
-use crate::tacho::{TachographHeader, VUTransferResponseParameterID};
+use crate::gen2::{CardData, CardResponseParameterData, DataInfo, SignatureRecordArray};
+use crate::tacho::{
+    self, RecordType, TachographDataGeneration, TachographDataType, TachographHeader, VUTransferResponseParameterID,
+};
+use crate::{Error, Result, Writable, field_value, gen1, record_count, write_card_data_files, write_to_vec};
 
-#[derive(Debug, Serialize)]
+/// Card data downloaded through a vehicle unit, a Gen1 card downloaded
+/// through a Gen2 vehicle unit contains Gen1 card files.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum VUCardDownloadData {
+    Gen1(gen1::CardData),
+    Gen2(CardData),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VUCardDownload {
-    pub card: CardData,
+    #[serde(rename = "recordType")]
+    pub record_type: RecordType,
+    #[serde(rename = "recordSize")]
+    pub record_size: u16,
+    #[serde(rename = "noOfRecords")]
+    pub no_of_records: u16,
+    pub card: VUCardDownloadData,
     #[serde(rename = "signatureRecordArray")]
     pub signature_record_array: Option<SignatureRecordArray>,
 }
 
 impl VUCardDownload {
-    pub fn from_data<R: ReadBytes + BinSeek>(
-        header: TachographHeader,
-        trep_id: VUTransferResponseParameterID,
-        reader: &mut R,
-    ) -> Result<VUCardDownload> {
+    pub fn from_data<R: ReadBytes + BinSeek>(trep_id: VUTransferResponseParameterID, reader: &mut R) -> Result<VUCardDownload> {
         debug!("VUCardDownload::from_data - Trep ID: {trep_id:?}");
         let data_info = DataInfo::read(reader, trep_id.clone())?;
-        let card: CardData = CardData::from_data(header, &mut data_info.data_into_mem_reader()?)?;
+        let card_data_files = {
+            let _section = data_info.span_section();
+            <dyn tacho::Card<CardResponseParameterData>>::read_card_files(&mut data_info.data_into_mem_reader()?)?
+        };
+        let generation = card_data_files.get_data_generation();
+        debug!("VUCardDownload::from_data - Card Generation: {:?}", card_data_files.get_card_generation());
+        let is_gen1 = matches!(generation, TachographDataGeneration::FirstGeneration);
+        let header = TachographHeader { generation, data_type: TachographDataType::Card, card_in_vu_data: true };
+        let card = if is_gen1 {
+            VUCardDownloadData::Gen1(gen1::CardData::from_card_files(header, &card_data_files)?)
+        } else {
+            VUCardDownloadData::Gen2(CardData::from_card_files(header, &card_data_files)?)
+        };
         let signature_record_array: Option<SignatureRecordArray> = Some(DataInfo::read(reader, trep_id.clone())?.parse()?);
 
-        Ok(Self { card, signature_record_array })
+        Ok(Self {
+            record_type: data_info.record_type,
+            record_size: data_info.record_size,
+            no_of_records: data_info.no_of_records,
+            card,
+            signature_record_array,
+        })
+    }
+}
+
+impl Writable for VUCardDownload {
+    /// Writes a `VUCardDownload` TREP to a binary stream, conforming to the DDD file format.
+    /// The card files are written with the parsed record type, record size and number of records.
+    /// When the length of the card files changed, a single record gets the new length as its size,
+    /// otherwise the number of records is derived from the record size.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        let data = write_to_vec(|writer| match &self.card {
+            VUCardDownloadData::Gen1(card) => write_card_data_files(writer, card),
+            VUCardDownloadData::Gen2(card) => write_card_data_files(writer, card),
+        })?;
+        let (record_size, no_of_records) = if self.record_size as usize * self.no_of_records as usize == data.len() {
+            (self.record_size, self.no_of_records)
+        } else if self.no_of_records == 1 {
+            (field_value("recordSize", data.len())?, 1)
+        } else if self.record_size > 0 && data.len() % self.record_size as usize == 0 {
+            (self.record_size, record_count(data.len() / self.record_size as usize)?)
+        } else {
+            return Err(Error::InvalidDataEncode(format!(
+                "card files of {} bytes do not fit in records of {} bytes",
+                data.len(),
+                self.record_size
+            )));
+        };
+        writer.write_u8((&self.record_type).into())?;
+        writer.write_u16::<BigEndian>(record_size)?;
+        writer.write_u16::<BigEndian>(no_of_records)?;
+        writer.write_all(&data)?;
+        if let Some(signature_record_array) = &self.signature_record_array {
+            signature_record_array.write(writer)?;
+        }
+        Ok(())
     }
 }
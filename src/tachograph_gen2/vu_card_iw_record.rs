@@ -1,8 +1,8 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, Result,
+    Readable, ReadableField, Result, SpanRecorder, Writable,
     gen2::{FullCardNumberAndGeneration, PreviousVehicleInfo},
     tacho::{CardSlotNumber, HolderName, ManualInputFlag, OdometerShort, TimeReal},
 };
@@ -10,7 +10,8 @@ use crate::{
 /// Information, stored in a vehicle unit, related to an insertion and with­
 /// drawal cycle of a driver card or of a workshop card in the vehicle unit
 /// (Annex 1B requirement 081 and Annex 1C requirement 102).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuCardIWRecord {
     #[serde(rename = "cardHolderName")]
     pub card_holder_name: HolderName,
@@ -36,16 +37,18 @@ pub struct VuCardIWRecord {
 
 impl Readable<VuCardIWRecord> for VuCardIWRecord {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<VuCardIWRecord> {
-        let card_holder_name = HolderName::read(reader)?;
-        let full_card_number_and_generation = FullCardNumberAndGeneration::read(reader)?;
-        let card_expiry_date = TimeReal::read(reader)?;
-        let card_insertion_time = TimeReal::read(reader)?;
-        let vehicle_odometer_value_at_insertion = OdometerShort::read(reader)?;
-        let card_slot_number: CardSlotNumber = reader.read_u8()?.into();
-        let card_withdrawal_time = TimeReal::read(reader)?;
-        let vehicle_odometer_value_at_withdrawal = OdometerShort::read(reader)?;
-        let previous_wehicle_info = PreviousVehicleInfo::read(reader)?;
-        let manual_input_flag: ManualInputFlag = reader.read_u8()?.into();
+        let card_holder_name = HolderName::read_field(reader, "cardHolderName")?;
+        let full_card_number_and_generation = FullCardNumberAndGeneration::read_field(reader, "fullCardNumberAndGeneration")?;
+        let card_expiry_date = TimeReal::read_field(reader, "cardExpiryDate")?;
+        let card_insertion_time = TimeReal::read_field(reader, "cardInsertionTime")?;
+        let vehicle_odometer_value_at_insertion = OdometerShort::read_field(reader, "vehicleOdometerValueAtInsertion")?;
+        let card_slot_number: CardSlotNumber =
+            SpanRecorder::field("cardSlotNumber", "CardSlotNumber", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let card_withdrawal_time = TimeReal::read_field(reader, "cardWithdrawalTime")?;
+        let vehicle_odometer_value_at_withdrawal = OdometerShort::read_field(reader, "vehicleOdometerValueAtWithdrawal")?;
+        let previous_wehicle_info = PreviousVehicleInfo::read_field(reader, "previousVehicleInfo")?;
+        let manual_input_flag: ManualInputFlag =
+            SpanRecorder::field("manualInputFlag", "ManualInputFlag", reader, |reader| Ok(reader.read_u8()?.into()))?;
 
         Ok(Self {
             card_holder_name,
@@ -61,3 +64,20 @@ impl Readable<VuCardIWRecord> for VuCardIWRecord {
         })
     }
 }
+
+impl Writable for VuCardIWRecord {
+    /// Writes a `VuCardIWRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.card_holder_name.write(writer)?;
+        self.full_card_number_and_generation.write(writer)?;
+        self.card_expiry_date.write(writer)?;
+        self.card_insertion_time.write(writer)?;
+        self.vehicle_odometer_value_at_insertion.write(writer)?;
+        writer.write_u8((&self.card_slot_number).into())?;
+        self.card_withdrawal_time.write(writer)?;
+        self.vehicle_odometer_value_at_withdrawal.write(writer)?;
+        self.previous_wehicle_info.write(writer)?;
+        writer.write_u8((&self.manual_input_flag).into())?;
+        Ok(())
+    }
+}
@@ -1,14 +1,15 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, ReadableWithParams, Result,
+    Readable, ReadableField, ReadableWithParamsField, Result, Writable,
     gen2::FullCardNumberAndGeneration,
     tacho::{CardNumber, CardNumberParams, CardStructureVersion, EquipmentType, ExtendedSerialNumber},
 };
 
 /// Information, stored in a vehicle unit, about a tachograph card used (Annex IC requirement 132).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuCardRecord {
     #[serde(rename = "cardNumberAndGenerationInformation")]
     pub full_card_number_and_generation: FullCardNumberAndGeneration,
@@ -22,11 +23,23 @@ pub struct VuCardRecord {
 
 impl Readable<VuCardRecord> for VuCardRecord {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<VuCardRecord> {
-        let full_card_number_and_generation = FullCardNumberAndGeneration::read(reader)?;
-        let card_extended_serial_number = ExtendedSerialNumber::read(reader)?;
-        let card_structure_version = CardStructureVersion::read(reader)?;
+        let full_card_number_and_generation =
+            FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGenerationInformation")?;
+        let card_extended_serial_number = ExtendedSerialNumber::read_field(reader, "cardExtendedSerialNumber")?;
+        let card_structure_version = CardStructureVersion::read_field(reader, "cardStructureVersion")?;
         let params = CardNumberParams::new(EquipmentType::DriverCard);
-        let card_number = CardNumber::read(reader, &params)?;
+        let card_number = CardNumber::read_field_with(reader, &params, "cardNumber")?;
         Ok(Self { full_card_number_and_generation, card_extended_serial_number, card_structure_version, card_number })
     }
 }
+
+impl Writable for VuCardRecord {
+    /// Writes a `VuCardRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.full_card_number_and_generation.write(writer)?;
+        self.card_extended_serial_number.write(writer)?;
+        self.card_structure_version.write(writer)?;
+        self.card_number.write(writer)?;
+        Ok(())
+    }
+}
@@ -1,15 +1,16 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    ReadableWithParams, Result,
+    ReadableWithParamsField, Result, Writable,
     gen2::{Certificate, CertificateParams, DataInfoReadable},
     tacho::RecordType,
-    tachograph_gen2::data_info::DataConfig,
+    tachograph_gen2::data_info::{DataConfig, write_record_array},
 };
 
 /// The VU certificate plus metadata as used in the download protocol.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuCertificateRecordArray {
     #[serde(rename = "noOfRecords")]
     pub no_of_records: u16,
@@ -28,10 +29,17 @@ impl DataInfoReadable<VuCertificateRecordArray> for VuCertificateRecordArray {
 
         let mut records: Vec<Certificate> = Vec::with_capacity(no_of_records as usize);
         let params = CertificateParams::new(Some(record_size));
-        for _ in 0..no_of_records {
-            let record = Certificate::read(reader, &params)?;
+        for index in 0..no_of_records {
+            let record = Certificate::read_field_with(reader, &params, &format!("records[{index}]"))?;
             records.push(record);
         }
         Ok(Self { no_of_records, record_size, record_type, records })
     }
 }
+
+impl Writable for VuCertificateRecordArray {
+    /// Writes the record array of the certificates.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        write_record_array(writer, &self.record_type, self.record_size, &self.records, |writer, record| record.write(writer))
+    }
+}
@@ -1,15 +1,16 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, Result,
+    Readable, ReadableField, Result, Writable,
     gen2::FullCardNumberAndGeneration,
     tacho::{Address, Name, TimeReal},
 };
 
 /// Information, stored in a vehicle unit, related to one company lock
 /// (Annex 1B requirement 104 and Annex 1C requirement 128).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuCompanyLocksRecord {
     #[serde(rename = "lockInTime")]
     pub lock_in_time: TimeReal,
@@ -25,11 +26,24 @@ pub struct VuCompanyLocksRecord {
 
 impl Readable<VuCompanyLocksRecord> for VuCompanyLocksRecord {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<VuCompanyLocksRecord> {
-        let lock_in_time = TimeReal::read(reader)?;
-        let lock_out_time = TimeReal::read(reader)?;
-        let company_name = Name::read(reader)?;
-        let company_address = Address::read(reader)?;
-        let company_card_number_and_generation = FullCardNumberAndGeneration::read(reader)?;
+        let lock_in_time = TimeReal::read_field(reader, "lockInTime")?;
+        let lock_out_time = TimeReal::read_field(reader, "lockOutTime")?;
+        let company_name = Name::read_field(reader, "companyName")?;
+        let company_address = Address::read_field(reader, "companyAddress")?;
+        let company_card_number_and_generation =
+            FullCardNumberAndGeneration::read_field(reader, "companyCardNumberAndGeneration")?;
         Ok(Self { lock_in_time, lock_out_time, company_name, company_address, company_card_number_and_generation })
     }
 }
+
+impl Writable for VuCompanyLocksRecord {
+    /// Writes a `VuCompanyLocksRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.lock_in_time.write(writer)?;
+        self.lock_out_time.write(writer)?;
+        self.company_name.write(writer)?;
+        self.company_address.write(writer)?;
+        self.company_card_number_and_generation.write(writer)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
+
+use crate::{ReadableWithParams, Result, SpanRecorder, Writable};
+
+#[derive(Debug)]
+pub struct VuConfigurationsParams {
+    pub vu_configuration_length_range: u16,
+}
+
+impl VuConfigurationsParams {
+    pub fn new(vu_configuration_length_range: u16) -> Self {
+        Self { vu_configuration_length_range }
+    }
+}
+
+/// Manufacturer specific configuration of the vehicle units, stored in a
+/// driver or workshop card (generation 2, version 2). The content is not
+/// specified, so it is kept as raw bytes.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct VuConfigurations {
+    #[serde(rename = "vuConfigurations")]
+    pub vu_configurations: Vec<u8>,
+}
+
+impl ReadableWithParams<VuConfigurations> for VuConfigurations {
+    type P = VuConfigurationsParams;
+
+    fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<VuConfigurations> {
+        let length = (params.vu_configuration_length_range as usize).min(reader.len()? - reader.pos()?);
+        let vu_configurations =
+            SpanRecorder::field("vuConfigurations", "OCTET STRING", reader, |reader| Ok(reader.read_into_vec(length as u32)?))?;
+
+        Ok(Self { vu_configurations })
+    }
+}
+
+impl Writable for VuConfigurations {
+    /// Writes a `VuConfigurations` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.vu_configurations)?;
+        Ok(())
+    }
+}
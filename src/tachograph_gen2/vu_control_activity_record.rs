@@ -1,15 +1,16 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, Result,
+    Readable, ReadableField, Result, SpanRecorder, Writable,
     gen2::FullCardNumberAndGeneration,
     tacho::{ControlType, TimeReal},
 };
 
 /// Information, stored in a vehicle unit, related to a control performed using
 /// this VU (Annex 1B requirement 102 and Annex 1C requirement 126).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuControlActivityRecord {
     #[serde(rename = "controlType")]
     pub control_type: ControlType,
@@ -25,11 +26,13 @@ pub struct VuControlActivityRecord {
 
 impl Readable<VuControlActivityRecord> for VuControlActivityRecord {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<VuControlActivityRecord> {
-        let control_type: ControlType = reader.read_u8()?.into();
-        let control_time = TimeReal::read(reader)?;
-        let control_card_number_and_generation = FullCardNumberAndGeneration::read(reader)?;
-        let download_period_begin_time = TimeReal::read(reader)?;
-        let download_period_end_time = TimeReal::read(reader)?;
+        let control_type: ControlType =
+            SpanRecorder::field("controlType", "ControlType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let control_time = TimeReal::read_field(reader, "controlTime")?;
+        let control_card_number_and_generation =
+            FullCardNumberAndGeneration::read_field(reader, "controlCardNumberAndGeneration")?;
+        let download_period_begin_time = TimeReal::read_field(reader, "downloadPeriodBeginTime")?;
+        let download_period_end_time = TimeReal::read_field(reader, "downloadPeriodEndTime")?;
         Ok(Self {
             control_type,
             control_time,
@@ -39,3 +42,15 @@ impl Readable<VuControlActivityRecord> for VuControlActivityRecord {
         })
     }
 }
+
+impl Writable for VuControlActivityRecord {
+    /// Writes a `VuControlActivityRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u8((&self.control_type).into())?;
+        self.control_time.write(writer)?;
+        self.control_card_number_and_generation.write(writer)?;
+        self.download_period_begin_time.write(writer)?;
+        self.download_period_end_time.write(writer)?;
+        Ok(())
+    }
+}
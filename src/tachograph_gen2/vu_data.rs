@@ -1,6 +1,6 @@
 use binary_data::{BinSeek, ReadBytes};
 use log::debug;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     Export, Result,
@@ -11,7 +11,8 @@ use crate::{
     tachograph,
 };
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VUData {
     header: TachographHeader,
     #[serde(rename = "transferResParams")]
@@ -22,7 +23,7 @@ impl VUData {
     pub fn from_data<R: ReadBytes + BinSeek>(header: TachographHeader, reader: &mut R) -> Result<VUData> {
         let transfer_res_params = <dyn tacho::VUData<VUTransferResponseParameterData>>::from_data(
             reader,
-            &|trep_id: VUTransferResponseParameterID, reader: &mut R| VUData::parse_trep(header.clone(), trep_id, reader),
+            &|trep_id: VUTransferResponseParameterID, reader: &mut R| VUData::parse_trep(trep_id, reader),
         )?;
 
         Ok(VUData { header, transfer_res_params })
@@ -74,17 +75,15 @@ impl VUData {
     }
 
     fn parse_card_download<R: ReadBytes + BinSeek>(
-        header: TachographHeader,
         trep_id: VUTransferResponseParameterID,
         reader: &mut R,
     ) -> Result<VUTransferResponseParameterData> {
         debug!("VUData::parse_card_download - Trep ID: {trep_id:?}");
-        let vu_card_download = VUCardDownload::from_data(header, trep_id, reader)?;
+        let vu_card_download = VUCardDownload::from_data(trep_id, reader)?;
         Ok(VUTransferResponseParameterData::CardDownload(vu_card_download))
     }
 
-    fn parse_trep<R: ReadBytes + BinSeek>(
-        header: TachographHeader,
+    pub(crate) fn parse_trep<R: ReadBytes + BinSeek>(
         trep_id: VUTransferResponseParameterID,
         reader: &mut R,
     ) -> Result<VUTransferResponseParameterData> {
@@ -105,7 +104,9 @@ impl VUData {
             VUTransferResponseParameterID::Speed
             | VUTransferResponseParameterID::Gen2Speed
             | VUTransferResponseParameterID::Gen2v2Speed => VUData::parse_speed(trep_id, reader),
-            VUTransferResponseParameterID::CardDownload => VUData::parse_card_download(header, trep_id, reader),
+            VUTransferResponseParameterID::CardDownload | VUTransferResponseParameterID::Gen2CardDownload => {
+                VUData::parse_card_download(trep_id, reader)
+            }
             VUTransferResponseParameterID::OddballCrashDump => Ok(VUTransferResponseParameterData::OddballCrashDump),
             _ => {
                 let data_info = DataInfo::read(reader, trep_id)?;
@@ -124,6 +125,10 @@ impl tachograph::VUData<VUTransferResponseParameterData> for VUData {
     fn get_data(&self) -> &Vec<VUTransferResponseParameterItem<VUTransferResponseParameterData>> {
         &self.transfer_res_params
     }
+
+    fn get_data_mut(&mut self) -> &mut Vec<VUTransferResponseParameterItem<VUTransferResponseParameterData>> {
+        &mut self.transfer_res_params
+    }
 }
 
 impl Export for VUData {}
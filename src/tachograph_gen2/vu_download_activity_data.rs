@@ -1,15 +1,16 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, Result,
+    Readable, ReadableField, Result, Writable,
     gen2::FullCardNumberAndGeneration,
     tacho::{Name, TimeReal},
 };
 
 /// Information, stored in a vehicle unit, related to its last download (Annex
 /// 1B requirement 105 and Annex 1C requirement 129).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuDownloadActivityData {
     #[serde(rename = "downloadingTime")]
     pub downloading_time: TimeReal,
@@ -21,9 +22,19 @@ pub struct VuDownloadActivityData {
 
 impl Readable<VuDownloadActivityData> for VuDownloadActivityData {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<VuDownloadActivityData> {
-        let downloading_time = TimeReal::read(reader)?;
-        let full_card_number_and_generation = FullCardNumberAndGeneration::read(reader)?;
-        let company_or_workshop_name = Name::read(reader)?;
+        let downloading_time = TimeReal::read_field(reader, "downloadingTime")?;
+        let full_card_number_and_generation = FullCardNumberAndGeneration::read_field(reader, "fullCardNumberAndGeneration")?;
+        let company_or_workshop_name = Name::read_field(reader, "companyOrWorkshopName")?;
         Ok(Self { downloading_time, full_card_number_and_generation, company_or_workshop_name })
     }
 }
+
+impl Writable for VuDownloadActivityData {
+    /// Writes a `VuDownloadActivityData` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.downloading_time.write(writer)?;
+        self.full_card_number_and_generation.write(writer)?;
+        self.company_or_workshop_name.write(writer)?;
+        Ok(())
+    }
+}
@@ -1,12 +1,13 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, Result, tacho::TimeReal};
+use crate::{Readable, ReadableField, Result, Writable, tacho::TimeReal};
 
 /// Oldest and latest dates for which a vehicle unit holds data related to
 /// drivers activities (Annex 1B requirements 081, 084 or 087 and
 /// Annex 1C requirements 102, 105, 108).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuDownloadablePeriod {
     #[serde(rename = "minDownloadableTime")]
     pub min_downloadable_time: TimeReal,
@@ -16,8 +17,17 @@ pub struct VuDownloadablePeriod {
 
 impl Readable<VuDownloadablePeriod> for VuDownloadablePeriod {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<VuDownloadablePeriod> {
-        let min_downloadable_time = TimeReal::read(reader)?;
-        let max_downloadable_time = TimeReal::read(reader)?;
+        let min_downloadable_time = TimeReal::read_field(reader, "minDownloadableTime")?;
+        let max_downloadable_time = TimeReal::read_field(reader, "maxDownloadableTime")?;
         Ok(Self { min_downloadable_time, max_downloadable_time })
     }
 }
+
+impl Writable for VuDownloadablePeriod {
+    /// Writes a `VuDownloadablePeriod` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.min_downloadable_time.write(writer)?;
+        self.max_downloadable_time.write(writer)?;
+        Ok(())
+    }
+}
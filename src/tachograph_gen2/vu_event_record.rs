@@ -1,15 +1,16 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, Result,
+    Readable, ReadableField, Result, SpanRecorder, Writable,
     gen2::{FullCardNumberAndGeneration, ManufacturerSpecificEventFaultData},
     tacho::{EventFaultRecordPurpose, EventFaultType, TimeReal},
 };
 
 /// Information, stored in a vehicle unit, related to an event (Annex 1B
 /// requirement 094 and Annex 1C requirement 117 except over speeding event).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuEventRecord {
     #[serde(rename = "eventType")]
     pub event_type: EventFaultType,
@@ -35,16 +36,21 @@ pub struct VuEventRecord {
 
 impl Readable<VuEventRecord> for VuEventRecord {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<VuEventRecord> {
-        let event_type: EventFaultType = reader.read_u8()?.into();
-        let event_record_purpose: EventFaultRecordPurpose = reader.read_u8()?.into();
-        let event_begin_time = TimeReal::read(reader)?;
-        let event_end_time = TimeReal::read(reader)?;
-        let card_number_driver_slot_begin = FullCardNumberAndGeneration::read(reader)?;
-        let card_number_codriver_slot_begin = FullCardNumberAndGeneration::read(reader)?;
-        let card_number_driver_slot_end = FullCardNumberAndGeneration::read(reader)?;
-        let card_number_codriver_slot_end = FullCardNumberAndGeneration::read(reader)?;
-        let similar_events_number = reader.read_u8()?;
-        let manufacturer_specific_event_fault_data = ManufacturerSpecificEventFaultData::read(reader)?;
+        let event_type: EventFaultType =
+            SpanRecorder::field("eventType", "EventFaultType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let event_record_purpose: EventFaultRecordPurpose =
+            SpanRecorder::field("eventRecordPurpose", "EventFaultRecordPurpose", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let event_begin_time = TimeReal::read_field(reader, "eventBeginTime")?;
+        let event_end_time = TimeReal::read_field(reader, "eventEndTime")?;
+        let card_number_driver_slot_begin = FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGenDriverSlotBegin")?;
+        let card_number_codriver_slot_begin =
+            FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGenCodriverSlotBegin")?;
+        let card_number_driver_slot_end = FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGenDriverSlotEnd")?;
+        let card_number_codriver_slot_end = FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGenCodriverSlotEnd")?;
+        let similar_events_number =
+            SpanRecorder::field("similarEventsNumber", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let manufacturer_specific_event_fault_data =
+            ManufacturerSpecificEventFaultData::read_field(reader, "manufacturerSpecificEventFaultData")?;
 
         Ok(Self {
             event_type,
@@ -60,3 +66,20 @@ impl Readable<VuEventRecord> for VuEventRecord {
         })
     }
 }
+
+impl Writable for VuEventRecord {
+    /// Writes a `VuEventRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u8((&self.event_type).into())?;
+        writer.write_u8((&self.event_record_purpose).into())?;
+        self.event_begin_time.write(writer)?;
+        self.event_end_time.write(writer)?;
+        self.card_number_driver_slot_begin.write(writer)?;
+        self.card_number_codriver_slot_begin.write(writer)?;
+        self.card_number_driver_slot_end.write(writer)?;
+        self.card_number_codriver_slot_end.write(writer)?;
+        writer.write_u8(self.similar_events_number)?;
+        self.manufacturer_specific_event_fault_data.write(writer)?;
+        Ok(())
+    }
+}
@@ -1,16 +1,17 @@
-use binary_data::{BinSeek, ReadBytes};
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
 use log::debug;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::Result;
 use crate::gen2::{
     DataInfo, DataInfoGenericRecordArray, SignatureRecordArray, VuEventRecord, VuFaultRecord, VuOverSpeedingEventRecord,
     VuTimeAdjustmentRecord,
 };
 use crate::tacho::{VUTransferResponseParameterID, VuOverSpeedingControlData};
+use crate::{Result, Writable};
 
 /// Data structure generation 2, version 2 (TREP 33 Hex)
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VUEvents {
     #[serde(rename = "vuFaultRecordArray")]
     pub vu_fault_record_array: DataInfoGenericRecordArray<VuFaultRecord>,
@@ -49,3 +50,18 @@ impl VUEvents {
         })
     }
 }
+
+impl Writable for VUEvents {
+    /// Writes the record arrays of a `VUEvents` TREP to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.vu_fault_record_array.write(writer)?;
+        self.vu_event_record_array.write(writer)?;
+        self.vu_over_speeding_control_data_record_array.write(writer)?;
+        self.vu_over_speeding_event_record_array.write(writer)?;
+        self.vu_time_adjustment_record_array.write(writer)?;
+        if let Some(signature_record_array) = &self.signature_record_array {
+            signature_record_array.write(writer)?;
+        }
+        Ok(())
+    }
+}
@@ -1,15 +1,16 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, Result,
+    Readable, ReadableField, Result, SpanRecorder, Writable,
     gen2::{FullCardNumberAndGeneration, ManufacturerSpecificEventFaultData},
     tacho::{EventFaultRecordPurpose, EventFaultType, TimeReal},
 };
 
 /// Information, stored in a vehicle unit, related to a fault (Annex 1B
 /// requirement 096 and Annex 1C requirement 118).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuFaultRecord {
     #[serde(rename = "faultType")]
     pub fault_type: EventFaultType,
@@ -33,15 +34,19 @@ pub struct VuFaultRecord {
 
 impl Readable<VuFaultRecord> for VuFaultRecord {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<VuFaultRecord> {
-        let fault_type: EventFaultType = reader.read_u8()?.into();
-        let fault_record_purpose: EventFaultRecordPurpose = reader.read_u8()?.into();
-        let fault_begin_time = TimeReal::read(reader)?;
-        let fault_end_time = TimeReal::read(reader)?;
-        let card_number_driver_slot_begin = FullCardNumberAndGeneration::read(reader)?;
-        let card_number_codriver_slot_begin = FullCardNumberAndGeneration::read(reader)?;
-        let card_number_driver_slot_end = FullCardNumberAndGeneration::read(reader)?;
-        let card_number_codriver_slot_end = FullCardNumberAndGeneration::read(reader)?;
-        let manufacturer_specific_event_fault_data = ManufacturerSpecificEventFaultData::read(reader)?;
+        let fault_type: EventFaultType =
+            SpanRecorder::field("faultType", "EventFaultType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let fault_record_purpose: EventFaultRecordPurpose =
+            SpanRecorder::field("faultRecordPurpose", "EventFaultRecordPurpose", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let fault_begin_time = TimeReal::read_field(reader, "faultBeginTime")?;
+        let fault_end_time = TimeReal::read_field(reader, "faultEndTime")?;
+        let card_number_driver_slot_begin = FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGenDriverSlotBegin")?;
+        let card_number_codriver_slot_begin =
+            FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGenCodriverSlotBegin")?;
+        let card_number_driver_slot_end = FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGenDriverSlotEnd")?;
+        let card_number_codriver_slot_end = FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGenCodriverSlotEnd")?;
+        let manufacturer_specific_event_fault_data =
+            ManufacturerSpecificEventFaultData::read_field(reader, "manufacturerSpecificEventFaultData")?;
 
         Ok(Self {
             fault_type,
@@ -56,3 +61,19 @@ impl Readable<VuFaultRecord> for VuFaultRecord {
         })
     }
 }
+
+impl Writable for VuFaultRecord {
+    /// Writes a `VuFaultRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u8((&self.fault_type).into())?;
+        writer.write_u8((&self.fault_record_purpose).into())?;
+        self.fault_begin_time.write(writer)?;
+        self.fault_end_time.write(writer)?;
+        self.card_number_driver_slot_begin.write(writer)?;
+        self.card_number_codriver_slot_begin.write(writer)?;
+        self.card_number_driver_slot_end.write(writer)?;
+        self.card_number_codriver_slot_end.write(writer)?;
+        self.manufacturer_specific_event_fault_data.write(writer)?;
+        Ok(())
+    }
+}
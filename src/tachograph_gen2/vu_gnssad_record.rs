@@ -1,16 +1,17 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, ReadableWithParams, Result,
-    gen2::{FullCardNumberAndGeneration, GnssPlaceRecord},
+    ReadableField, ReadableWithParams, Result, Writable,
+    gen2::{FullCardNumberAndGeneration, GnssPlaceAuthRecord, GnssPlaceRecord},
     tacho::{OdometerShort, TimeReal, VUTransferResponseParameterID},
 };
 
 /// Information, stored in a vehicle unit, related to the GNSS position of the
 /// vehicle if the accumulated driving time reaches a multiple of three hours
 /// (Annex IC requirement 108, 110).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuGnssadRecord {
     #[serde(rename = "isGen2V2")]
     pub is_gen2_v2: bool,
@@ -21,7 +22,10 @@ pub struct VuGnssadRecord {
     #[serde(rename = "cardNumberAndGenCodriverSlot")]
     pub card_number_and_gen_co_driver_slot: FullCardNumberAndGeneration,
     #[serde(rename = "gnssPlaceRecord")]
-    pub gnss_place_record: GnssPlaceRecord,
+    pub gnss_place_record: Option<GnssPlaceRecord>,
+    /// Only in generation 2, version 2, instead of `gnss_place_record`.
+    #[serde(rename = "gnssPlaceAuthRecord")]
+    pub gnss_place_auth_record: Option<GnssPlaceAuthRecord>,
     #[serde(rename = "vehicleOdometerValue")]
     pub vehicle_odometer_value: OdometerShort,
 }
@@ -30,16 +34,16 @@ impl ReadableWithParams<VuGnssadRecord> for VuGnssadRecord {
     type P = VUTransferResponseParameterID;
 
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<VuGnssadRecord> {
-        let time_stamp = TimeReal::read(reader)?;
-        let card_number_and_gen_driver_slot = FullCardNumberAndGeneration::read(reader)?;
-        let card_number_and_gen_co_driver_slot = FullCardNumberAndGeneration::read(reader)?;
-        let gnss_place_record = GnssPlaceRecord::read(reader)?;
-        let vehicle_odometer_value = OdometerShort::read(reader)?;
-
+        let time_stamp = TimeReal::read_field(reader, "timeStamp")?;
+        let card_number_and_gen_driver_slot = FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGenDriverSlot")?;
+        let card_number_and_gen_co_driver_slot = FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGenCodriverSlot")?;
         let is_gen2_v2: bool = *params == VUTransferResponseParameterID::Gen2v2Activities;
-        if is_gen2_v2 {
-            let _ = reader.read_u8()?;
-        }
+        let gnss_place_record = if !is_gen2_v2 { Some(GnssPlaceRecord::read_field(reader, "gnssPlaceRecord")?) } else { None };
+        // The generation 2 version 2 data structure contains the GNSS authentication status
+        // of the position, after the coordinates.
+        let gnss_place_auth_record =
+            if is_gen2_v2 { Some(GnssPlaceAuthRecord::read_field(reader, "gnssPlaceAuthRecord")?) } else { None };
+        let vehicle_odometer_value = OdometerShort::read_field(reader, "vehicleOdometerValue")?;
 
         Ok(Self {
             is_gen2_v2,
@@ -47,7 +51,49 @@ impl ReadableWithParams<VuGnssadRecord> for VuGnssadRecord {
             card_number_and_gen_driver_slot,
             card_number_and_gen_co_driver_slot,
             gnss_place_record,
+            gnss_place_auth_record,
             vehicle_odometer_value,
         })
     }
 }
+
+impl Writable for VuGnssadRecord {
+    /// Writes a `VuGnssadRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.time_stamp.write(writer)?;
+        self.card_number_and_gen_driver_slot.write(writer)?;
+        self.card_number_and_gen_co_driver_slot.write(writer)?;
+        if let Some(gnss_place_record) = &self.gnss_place_record {
+            gnss_place_record.write(writer)?;
+        }
+        if let Some(gnss_place_auth_record) = &self.gnss_place_auth_record {
+            gnss_place_auth_record.write(writer)?;
+        }
+        self.vehicle_odometer_value.write(writer)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use binary_data::BinMemoryBuffer;
+
+    use super::*;
+    use crate::write_to_vec;
+
+    #[test]
+    fn test_gen2_v2_vu_gnssad_record_keeps_authentication_status() {
+        let mut data = vec![0x65, 0x53, 0x4C, 0x00];
+        data.extend_from_slice(&[0; 2 * 19]); // Card numbers of the driver and the co-driver slot
+        data.extend_from_slice(&[0x65, 0x53, 0x4C, 0x00, 0x05, 0x00, 0x12, 0x34, 0x00, 0x56, 0x78]);
+        data.push(0x32); // Authentication status
+        data.extend_from_slice(&[0x01, 0x02, 0x03]); // Odometer
+
+        let record =
+            VuGnssadRecord::read(&mut BinMemoryBuffer::from(data.clone()), &VUTransferResponseParameterID::Gen2v2Activities)
+                .expect("Record should be read");
+        assert_eq!(record.gnss_place_auth_record.as_ref().map(|record| record.authentication_status), Some(0x32));
+        assert_eq!(record.vehicle_odometer_value.data, Some(0x010203));
+        assert_eq!(write_to_vec(|writer| record.write(writer)).unwrap(), data);
+    }
+}
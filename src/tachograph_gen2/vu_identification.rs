@@ -1,18 +1,21 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    CodePage, Readable, ReadableWithParams, Result, bytes_to_string,
+    CodePage, ReadableField, ReadableWithParams, Result, SpanRecorder, Writable, bytes_to_ia5_fix_string, bytes_to_string,
     gen2::VuSoftwareIdentification,
     tacho::{Address, ExtendedSerialNumber, Name, TimeReal, VUTransferResponseParameterID},
+    write_ia5_string, write_string,
 };
 
 const VU_PART_NUMBER_LENGTH: u32 = 16;
 const VU_APPROVAL_NUMBER_LENGTH: u32 = 16;
+const VU_DIGITAL_MAP_VERSION_LENGTH: u32 = 12;
 
 /// Information, stored in a vehicle unit, related to the identification of the
 /// vehicle unit (Annex 1B requirement 075 and Annex 1C requirement 93 and 121).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuIdentification {
     #[serde(rename = "isGen2V2")]
     pub is_gen2_v2: bool,
@@ -34,28 +37,37 @@ pub struct VuIdentification {
     pub vu_generation: u8,
     #[serde(rename = "vuAbility")]
     pub vu_ability: u8,
+    /// Version of the digital map stored in the vehicle unit (only present in version 2).
+    #[serde(rename = "vuDigitalMapVersion")]
+    pub vu_digital_map_version: Option<String>,
 }
 
 impl ReadableWithParams<VuIdentification> for VuIdentification {
     type P = VUTransferResponseParameterID;
 
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<VuIdentification> {
-        let vu_manufacturer_name = Name::read(reader)?;
-        let vu_manufacturer_address = Address::read(reader)?;
-        let vu_part_number = bytes_to_string(&reader.read_into_vec(VU_PART_NUMBER_LENGTH)?, &CodePage::IsoIec8859_1); // Code Page 1
-        let vu_serial_number = ExtendedSerialNumber::read(reader)?;
-        let vu_software_identification = VuSoftwareIdentification::read(reader)?;
-        let vu_manufacturing_date = TimeReal::read(reader)?;
-        let vu_approval_number = bytes_to_string(&reader.read_into_vec(VU_APPROVAL_NUMBER_LENGTH)?, &CodePage::IsoIec8859_1); // Code Page 1
-        let vu_generation = reader.read_u8()?;
-        let vu_ability = reader.read_u8()?;
+        let vu_manufacturer_name = Name::read_field(reader, "vuManufacturerName")?;
+        let vu_manufacturer_address = Address::read_field(reader, "vuManufacturerAddress")?;
+        let vu_part_number = SpanRecorder::field("vuPartNumber", "IA5String", reader, |reader| {
+            Ok(bytes_to_string(&reader.read_into_vec(VU_PART_NUMBER_LENGTH)?, &CodePage::IsoIec8859_1))
+        })?; // Code Page 1
+        let vu_serial_number = ExtendedSerialNumber::read_field(reader, "vuSerialNumber")?;
+        let vu_software_identification = VuSoftwareIdentification::read_field(reader, "vuSoftwareIdentification")?;
+        let vu_manufacturing_date = TimeReal::read_field(reader, "vuManufacturingDate")?;
+        let vu_approval_number = SpanRecorder::field("vuApprovalNumber", "IA5String", reader, |reader| {
+            Ok(bytes_to_string(&reader.read_into_vec(VU_APPROVAL_NUMBER_LENGTH)?, &CodePage::IsoIec8859_1))
+        })?; // Code Page 1
+        let vu_generation = SpanRecorder::field("vuGeneration", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let vu_ability = SpanRecorder::field("vuAbility", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
 
-        let is_gen2_v2: bool = *params == VUTransferResponseParameterID::Gen2v2Activities;
-        if is_gen2_v2 {
-            // TODO: not implemented for now.
-            // vuDigitalMapVersion is the version of the digital map stored in the vehicle unit (only present in version 2).
-            let _ = reader.read_bytes::<12>()?;
-        }
+        let is_gen2_v2: bool = *params == VUTransferResponseParameterID::Gen2v2TechnicalData;
+        let vu_digital_map_version = if is_gen2_v2 {
+            Some(SpanRecorder::field("vuDigitalMapVersion", "IA5String", reader, |reader| {
+                Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(VU_DIGITAL_MAP_VERSION_LENGTH)?)?)
+            })?)
+        } else {
+            None
+        };
 
         Ok(Self {
             is_gen2_v2,
@@ -68,6 +80,26 @@ impl ReadableWithParams<VuIdentification> for VuIdentification {
             vu_approval_number,
             vu_generation,
             vu_ability,
+            vu_digital_map_version,
         })
     }
 }
+
+impl Writable for VuIdentification {
+    /// Writes a `VuIdentification` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.vu_manufacturer_name.write(writer)?;
+        self.vu_manufacturer_address.write(writer)?;
+        write_string(writer, &self.vu_part_number, &CodePage::IsoIec8859_1, VU_PART_NUMBER_LENGTH as usize)?;
+        self.vu_serial_number.write(writer)?;
+        self.vu_software_identification.write(writer)?;
+        self.vu_manufacturing_date.write(writer)?;
+        write_string(writer, &self.vu_approval_number, &CodePage::IsoIec8859_1, VU_APPROVAL_NUMBER_LENGTH as usize)?;
+        writer.write_u8(self.vu_generation)?;
+        writer.write_u8(self.vu_ability)?;
+        if let Some(vu_digital_map_version) = &self.vu_digital_map_version {
+            write_ia5_string(writer, vu_digital_map_version, VU_DIGITAL_MAP_VERSION_LENGTH as usize)?;
+        }
+        Ok(())
+    }
+}
@@ -1,21 +1,60 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
-use crate::{Readable, Result, gen2::FullCardNumberAndGeneration};
+use crate::{Readable, ReadableField, Result, SpanRecorder, Writable, gen2::FullCardNumberAndGeneration};
 
 /// Information stored in a vehicle unit, related to the consent of a driver to
 /// use Intelligent Transport Systems.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuItsConsentRecord {
     #[serde(rename = "cardNumberAndGen")]
     pub card_number_and_generation: FullCardNumberAndGeneration,
-    pub consent: bool,
+    /// The BOOLEAN of the consent as it is read, 1 is the consent given and 0 is the
+    /// consent not given. Other values are kept, see `is_consent_given`.
+    pub consent: u8,
+}
+
+impl VuItsConsentRecord {
+    /// Returns true when the driver gave the consent to use Intelligent Transport Systems.
+    pub fn is_consent_given(&self) -> bool {
+        self.consent == 1
+    }
 }
 
 impl Readable<VuItsConsentRecord> for VuItsConsentRecord {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<VuItsConsentRecord> {
-        let card_number_and_generation = FullCardNumberAndGeneration::read(reader)?;
-        let consent: bool = reader.read_u8()? == 1;
+        let card_number_and_generation = FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGen")?;
+        let consent = SpanRecorder::field("consent", "BOOLEAN", reader, |reader| Ok(reader.read_u8()?))?;
         Ok(Self { card_number_and_generation, consent })
     }
 }
+
+impl Writable for VuItsConsentRecord {
+    /// Writes a `VuItsConsentRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.card_number_and_generation.write(writer)?;
+        writer.write_u8(self.consent)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use binary_data::BinMemoryBuffer;
+
+    use super::*;
+    use crate::write_to_vec;
+
+    #[test]
+    fn test_vu_its_consent_record_keeps_consent_value() {
+        let mut data = vec![0; 19]; // Card number and generation
+        data.push(0x05);
+
+        let record = VuItsConsentRecord::read(&mut BinMemoryBuffer::from(data.clone())).expect("Record should be read");
+        let json = serde_json::to_string(&record).unwrap();
+        let deserialized: VuItsConsentRecord = serde_json::from_str(&json).expect("Record should be deserialized");
+        assert!(!deserialized.is_consent_given());
+        assert_eq!(write_to_vec(|writer| deserialized.write(writer)).unwrap(), data);
+    }
+}
@@ -0,0 +1,61 @@
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Readable, ReadableField, Result, SpanRecorder, Writable,
+    gen2::{FullCardNumberAndGeneration, GnssPlaceAuthRecord, OperationType},
+    tacho::{OdometerShort, TimeReal},
+};
+
+/// Information, stored in a vehicle unit, related to a load or unload
+/// operation of the vehicle (Annex IC requirement 133d, generation 2, version 2).
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct VuLoadUnloadRecord {
+    #[serde(rename = "timeStamp")]
+    pub time_stamp: TimeReal,
+    #[serde(rename = "operationType")]
+    pub operation_type: OperationType,
+    #[serde(rename = "cardNumberAndGenDriverSlot")]
+    pub card_number_and_gen_driver_slot: FullCardNumberAndGeneration,
+    #[serde(rename = "cardNumberAndGenCodriverSlot")]
+    pub card_number_and_gen_co_driver_slot: FullCardNumberAndGeneration,
+    #[serde(rename = "gnssPlaceAuthRecord")]
+    pub gnss_place_auth_record: GnssPlaceAuthRecord,
+    #[serde(rename = "vehicleOdometerValue")]
+    pub vehicle_odometer_value: OdometerShort,
+}
+
+impl Readable<VuLoadUnloadRecord> for VuLoadUnloadRecord {
+    fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<VuLoadUnloadRecord> {
+        let time_stamp = TimeReal::read_field(reader, "timeStamp")?;
+        let operation_type: OperationType =
+            SpanRecorder::field("operationType", "OperationType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let card_number_and_gen_driver_slot = FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGenDriverSlot")?;
+        let card_number_and_gen_co_driver_slot = FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGenCodriverSlot")?;
+        let gnss_place_auth_record = GnssPlaceAuthRecord::read_field(reader, "gnssPlaceAuthRecord")?;
+        let vehicle_odometer_value = OdometerShort::read_field(reader, "vehicleOdometerValue")?;
+
+        Ok(Self {
+            time_stamp,
+            operation_type,
+            card_number_and_gen_driver_slot,
+            card_number_and_gen_co_driver_slot,
+            gnss_place_auth_record,
+            vehicle_odometer_value,
+        })
+    }
+}
+
+impl Writable for VuLoadUnloadRecord {
+    /// Writes a `VuLoadUnloadRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.time_stamp.write(writer)?;
+        writer.write_u8((&self.operation_type).into())?;
+        self.card_number_and_gen_driver_slot.write(writer)?;
+        self.card_number_and_gen_co_driver_slot.write(writer)?;
+        self.gnss_place_auth_record.write(writer)?;
+        self.vehicle_odometer_value.write(writer)?;
+        Ok(())
+    }
+}
@@ -1,15 +1,16 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, Result,
+    Readable, ReadableField, Result, SpanRecorder, Writable,
     gen2::FullCardNumberAndGeneration,
     tacho::{EventFaultRecordPurpose, EventFaultType, TimeReal},
 };
 
 /// Information, stored in a vehicle unit, related to over speeding events
 /// (Annex 1B requirement 094 and Annex 1C requirement 117).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuOverSpeedingEventRecord {
     #[serde(rename = "cardNumberAndGen")]
     pub event_type: EventFaultType,
@@ -31,14 +32,17 @@ pub struct VuOverSpeedingEventRecord {
 
 impl Readable<VuOverSpeedingEventRecord> for VuOverSpeedingEventRecord {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<VuOverSpeedingEventRecord> {
-        let event_type: EventFaultType = reader.read_u8()?.into();
-        let event_record_purpose: EventFaultRecordPurpose = reader.read_u8()?.into();
-        let event_begin_time = TimeReal::read(reader)?;
-        let event_end_time = TimeReal::read(reader)?;
-        let max_speed_value = reader.read_u8()?;
-        let average_speed_value = reader.read_u8()?;
-        let card_number_driver_slot_begin = FullCardNumberAndGeneration::read(reader)?;
-        let similar_events_number = reader.read_u8()?;
+        let event_type: EventFaultType =
+            SpanRecorder::field("cardNumberAndGen", "EventFaultType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let event_record_purpose: EventFaultRecordPurpose =
+            SpanRecorder::field("eventRecordPurpose", "EventFaultRecordPurpose", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let event_begin_time = TimeReal::read_field(reader, "eventBeginTime")?;
+        let event_end_time = TimeReal::read_field(reader, "eventEndTime")?;
+        let max_speed_value = SpanRecorder::field("maxSpeedValue", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let average_speed_value = SpanRecorder::field("averageSpeedValue", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let card_number_driver_slot_begin = FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGenDriverSlotBegin")?;
+        let similar_events_number =
+            SpanRecorder::field("similarEventsNumber", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
 
         Ok(Self {
             event_type,
@@ -52,3 +56,18 @@ impl Readable<VuOverSpeedingEventRecord> for VuOverSpeedingEventRecord {
         })
     }
 }
+
+impl Writable for VuOverSpeedingEventRecord {
+    /// Writes a `VuOverSpeedingEventRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u8((&self.event_type).into())?;
+        writer.write_u8((&self.event_record_purpose).into())?;
+        self.event_begin_time.write(writer)?;
+        self.event_end_time.write(writer)?;
+        writer.write_u8(self.max_speed_value)?;
+        writer.write_u8(self.average_speed_value)?;
+        self.card_number_driver_slot_begin.write(writer)?;
+        writer.write_u8(self.similar_events_number)?;
+        Ok(())
+    }
+}
@@ -1,24 +1,25 @@
-use binary_data::{BinSeek, ReadBytes};
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
 use log::debug;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Result,
+    Result, Writable,
     gen2::{
         DataInfo, DataInfoGenericRecordArray, MemberStateCertificateRecordArray, SignatureRecordArray,
-        VehicleIdentificationNumberRecordArray, VehicleRegistrationNumberRecordArray, VuCertificateRecordArray,
-        VuCompanyLocksRecord, VuControlActivityRecord, VuDownloadActivityData, VuDownloadablePeriod,
+        VehicleIdentificationNumberRecordArray, VehicleRegistrationIdentificationRecordArray,
+        VehicleRegistrationNumberRecordArray, VuCertificateRecordArray, VuCompanyLocksRecord, VuControlActivityRecord,
+        VuDownloadActivityData, VuDownloadablePeriod,
     },
     tacho::{CardSlotStatus, TimeReal, VUTransferResponseParameterID},
-    tachograph_gen2::vehicle_registration_identification_record_array::VehicleRegistrationIdentificationRecordArray,
 };
 
 /// Data structure generation 2, version 2 (TREP 31 Hex)
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VUOverview {
     #[serde(rename = "trepId")]
     pub trep_id: VUTransferResponseParameterID,
-    #[serde(rename = "nemberStateCertificateRecordArray")]
+    #[serde(rename = "memberStateCertificateRecordArray", alias = "nemberStateCertificateRecordArray")]
     pub member_state_certificate_record_array: MemberStateCertificateRecordArray,
     #[serde(rename = "vuCertificateRecordArray")]
     pub vu_certificate_record_array: VuCertificateRecordArray,
@@ -26,6 +27,9 @@ pub struct VUOverview {
     pub vehicle_identification_number_record_array: VehicleIdentificationNumberRecordArray,
     #[serde(rename = "vehicleRegistrationNumberRecordArray")]
     pub vehicle_registration_number_record_array: VehicleRegistrationNumberRecordArray,
+    /// Only in generation 2, version 2, the vehicle registration numbers are taken from it.
+    #[serde(rename = "vehicleRegistrationIdentificationRecordArray")]
+    pub vehicle_registration_identification_record_array: Option<VehicleRegistrationIdentificationRecordArray>,
     #[serde(rename = "CurrentDateTimeRecordArray")]
     pub current_date_time_record_array: DataInfoGenericRecordArray<TimeReal>,
     #[serde(rename = "vuDownloadablePeriodRecordArray")]
@@ -51,12 +55,12 @@ impl VUOverview {
         let vehicle_identification_number_record_array: VehicleIdentificationNumberRecordArray =
             DataInfo::read(reader, trep_id.clone())?.parse()?;
 
-        let vehicle_registration_number_record_array: VehicleRegistrationNumberRecordArray =
+        let (vehicle_registration_number_record_array, vehicle_registration_identification_record_array) =
             if trep_id == VUTransferResponseParameterID::Gen2v2Overview {
                 let records: VehicleRegistrationIdentificationRecordArray = DataInfo::read(reader, trep_id.clone())?.parse()?;
-                VehicleRegistrationNumberRecordArray::from(records)
+                (VehicleRegistrationNumberRecordArray::from(&records), Some(records))
             } else {
-                DataInfo::read(reader, trep_id.clone())?.parse()?
+                (DataInfo::read(reader, trep_id.clone())?.parse()?, None)
             };
 
         let current_date_time_record_array: DataInfoGenericRecordArray<TimeReal> =
@@ -79,6 +83,7 @@ impl VUOverview {
             vu_certificate_record_array,
             vehicle_identification_number_record_array,
             vehicle_registration_number_record_array,
+            vehicle_registration_identification_record_array,
             current_date_time_record_array,
             vu_downloadale_period_record_array,
             card_slot_status_record_array,
@@ -89,3 +94,25 @@ impl VUOverview {
         })
     }
 }
+
+impl Writable for VUOverview {
+    /// Writes the record arrays of a `VUOverview` TREP to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.member_state_certificate_record_array.write(writer)?;
+        self.vu_certificate_record_array.write(writer)?;
+        self.vehicle_identification_number_record_array.write(writer)?;
+        match &self.vehicle_registration_identification_record_array {
+            Some(vehicle_registration_identification_record_array) => {
+                vehicle_registration_identification_record_array.write(writer)?
+            }
+            None => self.vehicle_registration_number_record_array.write(writer)?,
+        }
+        self.current_date_time_record_array.write(writer)?;
+        self.vu_downloadale_period_record_array.write(writer)?;
+        self.card_slot_status_record_array.write(writer)?;
+        self.vu_download_activity_data_record_array.write(writer)?;
+        self.vu_company_locks_record_array.write(writer)?;
+        self.vu_control_activity_record_array.write(writer)?;
+        self.signature_record_array.write(writer)
+    }
+}
@@ -1,11 +1,11 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, ReadableWithParams, Result,
+    ReadableField, ReadableWithParams, Result, Writable,
     gen2::{DataInfoReadable, FullCardNumberAndGeneration, PlaceAuthRecord, PlaceRecord},
     tacho::{RecordType, VUTransferResponseParameterID},
-    tachograph_gen2::data_info::DataConfig,
+    tachograph_gen2::data_info::{DataConfig, write_record_array},
 };
 
 pub struct VuPlaceDailyWorkPeriodRecordParams {
@@ -15,7 +15,8 @@ pub struct VuPlaceDailyWorkPeriodRecordParams {
 /// Information, stored in a vehicle unit, related to a place where a driver
 /// begins or ends a daily work period (Annex 1B requirement 087 and
 /// Annex 1C requirement 108 and 110).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuPlaceDailyWorkPeriodRecord {
     #[serde(rename = "fullCardNumberAndGeneration")]
     pub full_card_number: FullCardNumberAndGeneration,
@@ -29,14 +30,14 @@ impl ReadableWithParams<VuPlaceDailyWorkPeriodRecord> for VuPlaceDailyWorkPeriod
     type P = VuPlaceDailyWorkPeriodRecordParams;
 
     fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<VuPlaceDailyWorkPeriodRecord> {
-        let full_card_number = FullCardNumberAndGeneration::read(reader)?;
-        let place_record = if !params.is_gen2_v2 { Some(PlaceRecord::read(reader)?) } else { None };
+        let full_card_number = FullCardNumberAndGeneration::read_field(reader, "fullCardNumberAndGeneration")?;
+        let place_record = if !params.is_gen2_v2 { Some(PlaceRecord::read_field(reader, "placeRecord")?) } else { None };
         let place_auth_record = if params.is_gen2_v2 {
             // Instead of placeRecord, the generation 2 version 2 data structure makes
             // use of the following data element:
             // placeAuthRecord contains the information related to the place entered,
             // the recorded position, GNSS authentication status and position determination time.
-            Some(PlaceAuthRecord::read(reader)?)
+            Some(PlaceAuthRecord::read_field(reader, "placeAuthRecord")?)
         } else {
             None
         };
@@ -44,7 +45,22 @@ impl ReadableWithParams<VuPlaceDailyWorkPeriodRecord> for VuPlaceDailyWorkPeriod
     }
 }
 
-#[derive(Debug, Serialize)]
+impl Writable for VuPlaceDailyWorkPeriodRecord {
+    /// Writes a `VuPlaceDailyWorkPeriodRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.full_card_number.write(writer)?;
+        if let Some(place_record) = &self.place_record {
+            place_record.write(writer)?;
+        }
+        if let Some(place_auth_record) = &self.place_auth_record {
+            place_auth_record.write(writer)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuPlaceDailyWorkPeriodRecordArray {
     #[serde(rename = "isGen2V2")]
     pub is_gen2_v2: bool,
@@ -63,12 +79,17 @@ impl DataInfoReadable<VuPlaceDailyWorkPeriodRecordArray> for VuPlaceDailyWorkPer
         let record_size = config.record_size;
         let record_type = config.record_type.clone();
 
-        let mut records: Vec<VuPlaceDailyWorkPeriodRecord> = Vec::with_capacity(no_of_records as usize);
         let is_gen2_v2: bool = config.trep_id == VUTransferResponseParameterID::Gen2v2Activities;
-        for _ in 0..no_of_records {
-            let record = VuPlaceDailyWorkPeriodRecord::read(reader, &VuPlaceDailyWorkPeriodRecordParams { is_gen2_v2 })?;
-            records.push(record);
-        }
+        let params = VuPlaceDailyWorkPeriodRecordParams { is_gen2_v2 };
+        let records: Vec<VuPlaceDailyWorkPeriodRecord> =
+            config.read_records(reader, |reader| VuPlaceDailyWorkPeriodRecord::read(reader, &params))?;
         Ok(Self { is_gen2_v2, no_of_records, record_size, record_type, records })
     }
 }
+
+impl Writable for VuPlaceDailyWorkPeriodRecordArray {
+    /// Writes the record array of the place records.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        write_record_array(writer, &self.record_type, self.record_size, &self.records, |writer, record| record.write(writer))
+    }
+}
@@ -1,15 +1,16 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, Result,
+    Readable, ReadableField, Result, SpanRecorder, Writable,
     gen2::FullCardNumberAndGeneration,
     tacho::{EventFaultRecordPurpose, EventFaultType, TimeReal},
 };
 
 /// Information, stored in a vehicle unit, related to Power Supply Interruption
 /// events (Annex 1C requirement 117).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuPowerSupplyInterruptionRecord {
     #[serde(rename = "eventType")]
     pub event_type: EventFaultType,
@@ -33,15 +34,21 @@ pub struct VuPowerSupplyInterruptionRecord {
 
 impl Readable<VuPowerSupplyInterruptionRecord> for VuPowerSupplyInterruptionRecord {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<VuPowerSupplyInterruptionRecord> {
-        let event_type: EventFaultType = reader.read_u8()?.into();
-        let event_record_purpose: EventFaultRecordPurpose = reader.read_u8()?.into();
-        let event_begin_time = TimeReal::read(reader)?;
-        let event_end_time = TimeReal::read(reader)?;
-        let card_num_and_gen_driver_slot_begin = FullCardNumberAndGeneration::read(reader)?;
-        let card_num_and_gen_driver_slot_end = FullCardNumberAndGeneration::read(reader)?;
-        let card_num_and_gen_codriver_slot_begin = FullCardNumberAndGeneration::read(reader)?;
-        let card_num_and_gen_codriver_slot_end = FullCardNumberAndGeneration::read(reader)?;
-        let similar_events_number = reader.read_u8()?;
+        let event_type: EventFaultType =
+            SpanRecorder::field("eventType", "EventFaultType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let event_record_purpose: EventFaultRecordPurpose =
+            SpanRecorder::field("eventRecordPurpose", "EventFaultRecordPurpose", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let event_begin_time = TimeReal::read_field(reader, "eventBeginTime")?;
+        let event_end_time = TimeReal::read_field(reader, "eventEndTime")?;
+        let card_num_and_gen_driver_slot_begin =
+            FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGenDriverSlotBegin")?;
+        let card_num_and_gen_driver_slot_end = FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGenDriverSlotEnd")?;
+        let card_num_and_gen_codriver_slot_begin =
+            FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGenCodriverSlotBegin")?;
+        let card_num_and_gen_codriver_slot_end =
+            FullCardNumberAndGeneration::read_field(reader, "cardNumberAndGenCodriverSlotEnd")?;
+        let similar_events_number =
+            SpanRecorder::field("similarEventsNumber", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
 
         Ok(Self {
             event_type,
@@ -56,3 +63,19 @@ impl Readable<VuPowerSupplyInterruptionRecord> for VuPowerSupplyInterruptionReco
         })
     }
 }
+
+impl Writable for VuPowerSupplyInterruptionRecord {
+    /// Writes a `VuPowerSupplyInterruptionRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        writer.write_u8((&self.event_type).into())?;
+        writer.write_u8((&self.event_record_purpose).into())?;
+        self.event_begin_time.write(writer)?;
+        self.event_end_time.write(writer)?;
+        self.card_num_and_gen_driver_slot_begin.write(writer)?;
+        self.card_num_and_gen_driver_slot_end.write(writer)?;
+        self.card_num_and_gen_codriver_slot_begin.write(writer)?;
+        self.card_num_and_gen_codriver_slot_end.write(writer)?;
+        writer.write_u8(self.similar_events_number)?;
+        Ok(())
+    }
+}
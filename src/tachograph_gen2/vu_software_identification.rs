@@ -1,12 +1,13 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
-use crate::{CodePage, Readable, Result, bytes_to_string, tacho::TimeReal};
+use crate::{CodePage, Readable, ReadableField, Result, SpanRecorder, Writable, bytes_to_string, tacho::TimeReal, write_string};
 
 const VU_SOFTWARE_VERSION_LENGTH: u32 = 4;
 
 /// Information, stored in a vehicle unit, related to the software installed.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuSoftwareIdentification {
     #[serde(rename = "vuSoftwareVersion")]
     pub vu_software_version: String,
@@ -16,9 +17,20 @@ pub struct VuSoftwareIdentification {
 
 impl Readable<VuSoftwareIdentification> for VuSoftwareIdentification {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<VuSoftwareIdentification> {
-        let vu_software_version = bytes_to_string(&reader.read_into_vec(VU_SOFTWARE_VERSION_LENGTH)?, &CodePage::IsoIec8859_1);
-        let vu_soft_installation_date = TimeReal::read(reader)?;
+        let vu_software_version = SpanRecorder::field("vuSoftwareVersion", "IA5String", reader, |reader| {
+            Ok(bytes_to_string(&reader.read_into_vec(VU_SOFTWARE_VERSION_LENGTH)?, &CodePage::IsoIec8859_1))
+        })?;
+        let vu_soft_installation_date = TimeReal::read_field(reader, "vuSoftInstallationDate")?;
 
         Ok(Self { vu_software_version, vu_soft_installation_date })
     }
 }
+
+impl Writable for VuSoftwareIdentification {
+    /// Writes a `VuSoftwareIdentification` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        write_string(writer, &self.vu_software_version, &CodePage::IsoIec8859_1, VU_SOFTWARE_VERSION_LENGTH as usize)?;
+        self.vu_soft_installation_date.write(writer)?;
+        Ok(())
+    }
+}
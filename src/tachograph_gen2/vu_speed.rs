@@ -1,13 +1,14 @@
-use binary_data::{BinSeek, ReadBytes};
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
 use log::debug;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::Result;
 use crate::gen2::{DataInfo, DataInfoGenericRecordArray, SignatureRecordArray};
 use crate::tacho::{VUTransferResponseParameterID, VuDetailedSpeedBlock};
+use crate::{Result, Writable};
 
 /// Data structure generation 2 (TREP 24 Hex)
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VUSpeed {
     #[serde(rename = "vuDetailedSpeedBlockRecordArray")]
     pub vu_detailed_speed_block_record_array: DataInfoGenericRecordArray<VuDetailedSpeedBlock>,
@@ -25,3 +26,14 @@ impl VUSpeed {
         Ok(Self { vu_detailed_speed_block_record_array, signature_record_array })
     }
 }
+
+impl Writable for VUSpeed {
+    /// Writes the record arrays of a `VUSpeed` TREP to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.vu_detailed_speed_block_record_array.write(writer)?;
+        if let Some(signature_record_array) = &self.signature_record_array {
+            signature_record_array.write(writer)?;
+        }
+        Ok(())
+    }
+}
@@ -1,17 +1,18 @@
-use binary_data::{BinSeek, ReadBytes};
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
 use log::debug;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::Result;
 use crate::gen2::{
     DataInfo, DataInfoGenericRecordArray, SensorExternalGNSSCoupledRecord, SensorPairedRecord, SignatureRecordArray,
     VuCalibrationRecord, VuCardRecord, VuIdentification, VuItsConsentRecord, VuPowerSupplyInterruptionRecord,
 };
 use crate::tacho::VUTransferResponseParameterID;
+use crate::{Result, Writable};
 
 /// Data structure generation 2, version 1 (TREP 25 Hex)
 /// Data structure generation 2, version 2 (TREP 35 Hex)
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VUTechnicalData {
     #[serde(rename = "vuIdentificationRecordArray")]
     pub vu_identification_record_array: DataInfoGenericRecordArray<VuIdentification>,
@@ -62,3 +63,20 @@ impl VUTechnicalData {
         })
     }
 }
+
+impl Writable for VUTechnicalData {
+    /// Writes the record arrays of a `VUTechnicalData` TREP to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.vu_identification_record_array.write(writer)?;
+        self.vu_sensor_paired_record_array.write(writer)?;
+        self.vu_sensor_external_gnss_coupled_record_array.write(writer)?;
+        self.vu_calibration_record_array.write(writer)?;
+        self.vu_card_record_array.write(writer)?;
+        self.vu_its_consent_record_array.write(writer)?;
+        self.vu_power_supply_interruption_record_array.write(writer)?;
+        if let Some(signature_record_array) = &self.signature_record_array {
+            signature_record_array.write(writer)?;
+        }
+        Ok(())
+    }
+}
@@ -1,8 +1,8 @@
-use binary_data::{BinSeek, ReadBytes};
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, Result,
+    Readable, ReadableField, Result, Writable,
     gen2::FullCardNumberAndGeneration,
     tacho::{Address, Name, TimeReal},
 };
@@ -10,7 +10,8 @@ use crate::{
 /// Information, stored in a vehicle unit, related a time adjustment
 /// performed outside the frame of a regular calibration (Annex 1B
 /// requirement 101 and Annex 1C requirement 124 and 125).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VuTimeAdjustmentRecord {
     #[serde(rename = "oldTimeValue")]
     pub old_time_value: TimeReal,
@@ -26,12 +27,25 @@ pub struct VuTimeAdjustmentRecord {
 
 impl Readable<VuTimeAdjustmentRecord> for VuTimeAdjustmentRecord {
     fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<VuTimeAdjustmentRecord> {
-        let old_time_value = TimeReal::read(reader)?;
-        let new_time_value = TimeReal::read(reader)?;
-        let workshop_name = Name::read(reader)?;
-        let workshop_address = Address::read(reader)?;
-        let workshop_card_number_and_generation = FullCardNumberAndGeneration::read(reader)?;
+        let old_time_value = TimeReal::read_field(reader, "oldTimeValue")?;
+        let new_time_value = TimeReal::read_field(reader, "newTimeValue")?;
+        let workshop_name = Name::read_field(reader, "workshopName")?;
+        let workshop_address = Address::read_field(reader, "workshopAddress")?;
+        let workshop_card_number_and_generation =
+            FullCardNumberAndGeneration::read_field(reader, "workshopCardNumberAndGeneration")?;
 
         Ok(Self { old_time_value, new_time_value, workshop_name, workshop_address, workshop_card_number_and_generation })
     }
 }
+
+impl Writable for VuTimeAdjustmentRecord {
+    /// Writes a `VuTimeAdjustmentRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.old_time_value.write(writer)?;
+        self.new_time_value.write(writer)?;
+        self.workshop_name.write(writer)?;
+        self.workshop_address.write(writer)?;
+        self.workshop_card_number_and_generation.write(writer)?;
+        Ok(())
+    }
+}
@@ -1,11 +1,14 @@
-use serde::Serialize;
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    gen2::{DataInfo, VUActivity, VUCardDownload, VUEvents, VUOverview, VUSpeed, VUTechnicalData},
-    tacho::VUTransferResponseParameter,
+    Result, Writable, WritableWithParams,
+    gen2::{DataInfo, VUActivity, VUCardDownload, VUData, VUEvents, VUOverview, VUSpeed, VUTechnicalData},
+    tacho::{RawTrepBlock, VUTransferResponseParameter, VUTransferResponseParameterID},
 };
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum VUTransferResponseParameterData {
     Unknown(DataInfo),
     Control(VUOverview),
@@ -15,10 +18,45 @@ pub enum VUTransferResponseParameterData {
     Calibration(VUTechnicalData),
     CardDownload(VUCardDownload),
     OddballCrashDump,
+    /// TREP which is not known to the parser.
+    Raw(RawTrepBlock),
 }
 
 impl VUTransferResponseParameter for VUTransferResponseParameterData {
     fn is_oddball_crash_dump(&self) -> bool {
         matches!(self, VUTransferResponseParameterData::OddballCrashDump)
     }
+
+    fn from_raw(block: RawTrepBlock) -> Self {
+        VUTransferResponseParameterData::Raw(block)
+    }
+
+    fn parse<R: ReadBytes + BinSeek>(trep_id: VUTransferResponseParameterID, reader: &mut R) -> Result<Self> {
+        VUData::parse_trep(trep_id, reader)
+    }
+}
+
+impl WritableWithParams for VUTransferResponseParameterData {
+    type P = VUTransferResponseParameterID;
+
+    /// Writes the TREP id followed by the data of the TREP, the TREP which is not known
+    /// to the parser is written with its own TREP id and raw data.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W, params: &Self::P) -> Result<()> {
+        if let VUTransferResponseParameterData::Raw(block) = self {
+            writer.write_u8(block.trep)?;
+            writer.write_all(&block.data)?;
+            return Ok(());
+        }
+        writer.write_u8(params.into())?;
+        match self {
+            VUTransferResponseParameterData::Unknown(data) => data.write(writer),
+            VUTransferResponseParameterData::Control(data) => data.write(writer),
+            VUTransferResponseParameterData::Activity(data) => data.write(writer),
+            VUTransferResponseParameterData::Events(data) => data.write(writer),
+            VUTransferResponseParameterData::Speed(data) => data.write(writer),
+            VUTransferResponseParameterData::Calibration(data) => data.write(writer),
+            VUTransferResponseParameterData::CardDownload(data) => data.write(writer),
+            VUTransferResponseParameterData::OddballCrashDump | VUTransferResponseParameterData::Raw(_) => Ok(()),
+        }
+    }
 }
@@ -2,24 +2,30 @@ use std::collections::HashMap;
 
 use binary_data::BinSeek;
 use log::{debug, trace};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, ReadableWithParams, Result,
+    Diagnostic, Error, ReadableField, ReadableWithParamsField, Result, Writable,
     gen2::{
-        CardResponseParameterData, CardVehicleRecord, CardVehicleUnitsUsed, CardVehicleUnitsUsedParams, Certificate,
-        CertificateParams, GnssAccumulatedDriving, GnssAccumulatedDrivingParams, PlaceRecord, SpecificConditions,
-        SpecificConditionsParams, WorkshopCardApplicationIdentification, WorkshopCardCalibrationRecord,
+        CardBorderCrossings, CardBorderCrossingsParams, CardLoadTypeEntries, CardLoadTypeEntriesParams, CardLoadUnloadOperations,
+        CardLoadUnloadOperationsParams, CardResponseParameterData, CardVehicleRecord, CardVehicleUnitsUsed,
+        CardVehicleUnitsUsedParams, Certificate, CertificateParams, DriverCardApplicationIdentificationV2,
+        GnssAccumulatedDriving, GnssAccumulatedDrivingParams, PlaceRecord, SpecificConditions, SpecificConditionsParams,
+        VuConfigurations, VuConfigurationsParams, WorkshopCardApplicationIdentification, WorkshopCardCalibrationAddData,
+        WorkshopCardCalibrationAddDataParams, WorkshopCardCalibrationRecord,
     },
     tacho::{
         Card, CardChipIdentification, CardCurrentUse, CardDriverActivity, CardDriverActivityParams, CardEventData,
         CardEventDataParams, CardFaultData, CardFaultDataParams, CardFileData, CardFileID, CardGeneration, CardIccIdentification,
-        CardParser, CardPlaceDailyWorkPeriod, CardPlaceDailyWorkPeriodParams, CardVehiclesUsed, DataFiles, Identification,
-        IdentificationParams, VehiclesUsedParams, WorkshopCardCalibrationData, WorkshopCardCalibrationDataParams,
+        CardParser, CardPlaceDailyWorkPeriod, CardPlaceDailyWorkPeriodParams, CardVehiclesUsed, CardWriter, DataFiles,
+        Identification, IdentificationParams, VehiclesUsedParams, WorkshopCardCalibrationData, WorkshopCardCalibrationDataParams,
+        write_card_file, write_card_file_with_params,
     },
+    write_to_vec,
 };
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct WorkshopCard {
     #[serde(rename = "cardGeneration")]
     pub card_generation: CardGeneration,
@@ -29,6 +35,8 @@ pub struct WorkshopCard {
     pub card_icc_identification: CardIccIdentification,
     #[serde(rename = "applicationIdentification")]
     pub application_identification: WorkshopCardApplicationIdentification,
+    #[serde(rename = "applicationIdentificationV2")]
+    pub application_identification_v2: Option<DriverCardApplicationIdentificationV2>,
     #[serde(rename = "eventsData")]
     pub events_data: Option<CardEventData>,
     #[serde(rename = "faultsData")]
@@ -48,6 +56,16 @@ pub struct WorkshopCard {
     pub identification: Option<Identification>,
     #[serde(rename = "gnssPlaces")]
     pub gnss_places: Option<GnssAccumulatedDriving>,
+    #[serde(rename = "borderCrossings")]
+    pub border_crossings: Option<CardBorderCrossings>,
+    #[serde(rename = "loadUnloadOperations")]
+    pub load_unload_operations: Option<CardLoadUnloadOperations>,
+    #[serde(rename = "loadTypeEntries")]
+    pub load_type_entries: Option<CardLoadTypeEntries>,
+    #[serde(rename = "vuConfigurations")]
+    pub vu_configurations: Option<VuConfigurations>,
+    #[serde(rename = "calibrationAddData")]
+    pub calibration_add_data: Option<WorkshopCardCalibrationAddData>,
     #[serde(rename = "cardCertificate")]
     pub card_certificate: Option<Certificate>,
     #[serde(rename = "caCertificate")]
@@ -58,6 +76,9 @@ pub struct WorkshopCard {
     pub link_certificate: Option<Certificate>,
     #[serde(rename = "cardNotes")]
     pub card_notes: String,
+    /// Errors of the card files (EF) which could not be parsed in lenient mode.
+    #[serde(rename = "efErrors")]
+    pub ef_errors: HashMap<CardFileID, Diagnostic>,
     #[serde(rename = "dataFiles")]
     pub data_files: HashMap<CardFileID, CardFileData>,
 }
@@ -71,10 +92,11 @@ impl WorkshopCard {
         data_files: HashMap<CardFileID, CardFileData>,
     ) -> Self {
         Self {
-            card_generation: CardGeneration::Gen2,
+            card_generation: CardGeneration::from_card_files(&data_files),
             card_chip_identification,
             card_icc_identification,
             application_identification,
+            application_identification_v2: None,
             events_data: None,
             faults_data: None,
             current_usage: None,
@@ -86,107 +108,176 @@ impl WorkshopCard {
             vehicle_units_used: None,
             identification: None,
             gnss_places: None,
+            border_crossings: None,
+            load_unload_operations: None,
+            load_type_entries: None,
+            vu_configurations: None,
+            calibration_add_data: None,
             card_certificate: None,
             ca_certificate: None,
             card_sign_certificate: None,
             link_certificate: None,
             card_notes,
+            ef_errors: HashMap::new(),
             data_files,
         }
     }
-}
 
-impl CardParser<WorkshopCard> for WorkshopCard {
-    fn parse(card_data_files: &HashMap<CardFileID, CardFileData>, card_notes: &str) -> Result<Box<WorkshopCard>> {
-        let card_chip_identification = <dyn Card<CardResponseParameterData>>::parse_ic(card_data_files)?;
-        let card_icc_identification = <dyn Card<CardResponseParameterData>>::parse_icc(card_data_files)?;
-        let application_identification = <dyn Card<CardResponseParameterData>>::parse_card_application_identification::<
-            WorkshopCardApplicationIdentification,
-        >(card_data_files)?;
-
-        let mut workshop_card = WorkshopCard::new(
-            card_chip_identification,
-            card_icc_identification,
-            application_identification.clone(),
-            card_notes.to_owned(),
-            (*card_data_files).clone(),
-        );
+    fn application_identification_v2(&self) -> Result<&DriverCardApplicationIdentificationV2> {
+        self.application_identification_v2
+            .as_ref()
+            .ok_or_else(|| Error::MissingCardFile(CardFileID::ApplicationIdentificationV2.to_string()))
+    }
 
-        for card_item in card_data_files.iter() {
-            debug!("WorkshopCard::parse - ID: {:?}", card_item.0,);
-            let card_file = card_item.1;
-            let mut reader = card_file.data_into_reader()?;
+    fn parse_card_file(
+        &mut self,
+        card_file_id: &CardFileID,
+        card_file: &CardFileData,
+        application_identification: &WorkshopCardApplicationIdentification,
+    ) -> Result<()> {
+        card_file.read_data_with(|reader| {
             debug!(
                 "WorkshopCard::parse - ID: {:?}, Data Length: {:?}, Has Signature: {}",
-                card_item.0,
+                card_file_id,
                 reader.len()?,
                 card_file.signature.is_some()
             );
-            match card_item.0 {
+            match card_file_id {
+                CardFileID::ApplicationIdentificationV2 => {
+                    self.application_identification_v2 =
+                        Some(DriverCardApplicationIdentificationV2::read_field(reader, "applicationIdentificationV2")?);
+                }
                 CardFileID::Calibration => {
                     let params = WorkshopCardCalibrationDataParams::new(application_identification.no_off_calibration_records);
-                    workshop_card.calibration =
-                        Some(WorkshopCardCalibrationData::<WorkshopCardCalibrationRecord>::read(&mut reader, &params)?);
+                    self.calibration = Some(WorkshopCardCalibrationData::<WorkshopCardCalibrationRecord>::read_field_with(
+                        reader,
+                        &params,
+                        "calibration",
+                    )?);
                 }
                 CardFileID::EventsData => {
                     let params = CardEventDataParams::new(11, application_identification.no_events_per_type);
-                    workshop_card.events_data = Some(CardEventData::read(&mut reader, &params)?);
+                    self.events_data = Some(CardEventData::read_field_with(reader, &params, "eventsData")?);
                 }
                 CardFileID::FaultsData => {
                     let params = CardFaultDataParams::new(application_identification.no_faults_per_type);
-                    workshop_card.faults_data = Some(CardFaultData::read(&mut reader, &params)?);
+                    self.faults_data = Some(CardFaultData::read_field_with(reader, &params, "faultsData")?);
                 }
                 CardFileID::VehiclesUsed => {
                     let params = VehiclesUsedParams::new(application_identification.no_of_card_vehicle_records);
-                    workshop_card.vehicles_used = Some(CardVehiclesUsed::<CardVehicleRecord>::read(&mut reader, &params)?);
+                    self.vehicles_used =
+                        Some(CardVehiclesUsed::<CardVehicleRecord>::read_field_with(reader, &params, "vehiclesUsed")?);
                 }
                 CardFileID::Places => {
                     let params = CardPlaceDailyWorkPeriodParams::new(application_identification.no_of_card_place_records, 2);
-                    workshop_card.places = Some(CardPlaceDailyWorkPeriod::<PlaceRecord>::read(&mut reader, &params)?);
+                    self.places = Some(CardPlaceDailyWorkPeriod::<PlaceRecord>::read_field_with(reader, &params, "places")?);
                 }
                 CardFileID::CurrentUsage => {
-                    workshop_card.current_usage = Some(CardCurrentUse::read(&mut reader)?);
+                    self.current_usage = Some(CardCurrentUse::read_field(reader, "currentUsage")?);
                 }
                 CardFileID::DriverActivityData => {
                     let params = CardDriverActivityParams::new(application_identification.activity_structure_length);
-                    workshop_card.driver_activity_data = Some(CardDriverActivity::read(&mut reader, &params)?);
+                    self.driver_activity_data = Some(CardDriverActivity::read_field_with(reader, &params, "driverActivityData")?);
                 }
                 CardFileID::Identification => {
                     let params = IdentificationParams::new(application_identification.type_of_tachograph_card_id.clone());
-                    workshop_card.identification = Some(Identification::read(&mut reader, &params)?);
+                    self.identification = Some(Identification::read_field_with(reader, &params, "identification")?);
                 }
                 CardFileID::SpecificConditions => {
                     let params = SpecificConditionsParams::new(application_identification.no_of_specific_condition_records as u8);
-                    workshop_card.specific_conditions = Some(SpecificConditions::read(&mut reader, &params)?);
+                    self.specific_conditions = Some(SpecificConditions::read_field_with(reader, &params, "specificConditions")?);
                 }
                 CardFileID::VehicleUnitsUsed => {
                     let params = CardVehicleUnitsUsedParams::new(application_identification.no_of_card_vehicle_unit_records);
-                    workshop_card.vehicle_units_used = Some(CardVehicleUnitsUsed::read(&mut reader, &params)?);
+                    self.vehicle_units_used = Some(CardVehicleUnitsUsed::read_field_with(reader, &params, "vehicleUnitsUsed")?);
                 }
                 CardFileID::GnssPlaces => {
                     let params = GnssAccumulatedDrivingParams::new(application_identification.no_of_gnssad_records);
-                    workshop_card.gnss_places = Some(GnssAccumulatedDriving::read(&mut reader, &params)?);
+                    self.gnss_places = Some(GnssAccumulatedDriving::read_field_with(reader, &params, "gnssPlaces")?);
+                }
+                CardFileID::BorderCrossings => {
+                    let params =
+                        CardBorderCrossingsParams::new(self.application_identification_v2()?.no_of_border_crossing_records);
+                    self.border_crossings = Some(CardBorderCrossings::read_field_with(reader, &params, "borderCrossings")?);
+                }
+                CardFileID::LoadUnloadOperations => {
+                    let params =
+                        CardLoadUnloadOperationsParams::new(self.application_identification_v2()?.no_of_load_unload_records);
+                    self.load_unload_operations =
+                        Some(CardLoadUnloadOperations::read_field_with(reader, &params, "loadUnloadOperations")?);
+                }
+                CardFileID::LoadTypeEntries => {
+                    let params =
+                        CardLoadTypeEntriesParams::new(self.application_identification_v2()?.no_of_load_type_entry_records);
+                    self.load_type_entries = Some(CardLoadTypeEntries::read_field_with(reader, &params, "loadTypeEntries")?);
+                }
+                CardFileID::VUConfiguration => {
+                    let params = VuConfigurationsParams::new(self.application_identification_v2()?.vu_configuration_length_range);
+                    self.vu_configurations = Some(VuConfigurations::read_field_with(reader, &params, "vuConfigurations")?);
+                }
+                CardFileID::CalibrationAddData => {
+                    let params = WorkshopCardCalibrationAddDataParams::new(application_identification.no_off_calibration_records);
+                    self.calibration_add_data =
+                        Some(WorkshopCardCalibrationAddData::read_field_with(reader, &params, "calibrationAddData")?);
                 }
                 CardFileID::CardCertificate => {
                     let params = CertificateParams::new(None);
-                    workshop_card.card_certificate = Some(Certificate::read(&mut reader, &params)?);
+                    self.card_certificate = Some(Certificate::read_field_with(reader, &params, "cardCertificate")?);
                 }
                 CardFileID::CACertificate => {
                     let params = CertificateParams::new(None);
-                    workshop_card.ca_certificate = Some(Certificate::read(&mut reader, &params)?);
+                    self.ca_certificate = Some(Certificate::read_field_with(reader, &params, "caCertificate")?);
                 }
                 CardFileID::CardSignCertificate => {
                     let params = CertificateParams::new(None);
-                    workshop_card.card_sign_certificate = Some(Certificate::read(&mut reader, &params)?);
+                    self.card_sign_certificate = Some(Certificate::read_field_with(reader, &params, "cardSignCertificate")?);
                 }
                 CardFileID::LinkCertificate => {
                     let params = CertificateParams::new(None);
-                    workshop_card.link_certificate = Some(Certificate::read(&mut reader, &params)?);
+                    self.link_certificate = Some(Certificate::read_field_with(reader, &params, "linkCertificate")?);
                 }
                 CardFileID::IC | CardFileID::ICC | CardFileID::ApplicationIdentification => {
-                    trace!("WorkshopCard::parse - Already parsed: {:?}", card_item.0)
+                    trace!("WorkshopCard::parse - Already parsed: {:?}", card_file_id)
                 }
-                _ => trace!("WorkshopCard::parse - Not Parsed: {:?}", card_item.0),
+                _ => trace!("WorkshopCard::parse - Not Parsed: {:?}", card_file_id),
+            }
+            Ok(())
+        })
+    }
+}
+
+impl CardParser<WorkshopCard> for WorkshopCard {
+    fn parse(card_data_files: &HashMap<CardFileID, CardFileData>, card_notes: &str) -> Result<Box<WorkshopCard>> {
+        let card_chip_identification = <dyn Card<CardResponseParameterData>>::parse_ic(card_data_files)?;
+        let card_icc_identification = <dyn Card<CardResponseParameterData>>::parse_icc(card_data_files)?;
+        let application_identification = <dyn Card<CardResponseParameterData>>::parse_card_application_identification::<
+            WorkshopCardApplicationIdentification,
+        >(card_data_files)?;
+
+        let mut workshop_card = WorkshopCard::new(
+            card_chip_identification,
+            card_icc_identification,
+            application_identification.clone(),
+            card_notes.to_owned(),
+            (*card_data_files).clone(),
+        );
+
+        // The version 2 card files are sized from ApplicationIdentificationV2, so it is parsed first.
+        if let Some(card_file) = card_data_files.get(&CardFileID::ApplicationIdentificationV2)
+            && let Err(error) =
+                workshop_card.parse_card_file(&CardFileID::ApplicationIdentificationV2, card_file, &application_identification)
+        {
+            <dyn Card<CardResponseParameterData>>::add_card_file_error(
+                &mut workshop_card.ef_errors,
+                &CardFileID::ApplicationIdentificationV2,
+                error,
+            )?;
+        }
+
+        for card_item in card_data_files.iter().filter(|card_item| *card_item.0 != CardFileID::ApplicationIdentificationV2) {
+            debug!("WorkshopCard::parse - ID: {:?}", card_item.0,);
+            if let Err(error) = workshop_card.parse_card_file(card_item.0, card_item.1, &application_identification) {
+                <dyn Card<CardResponseParameterData>>::add_card_file_error(&mut workshop_card.ef_errors, card_item.0, error)?;
             }
         }
         Ok(Box::new(workshop_card))
@@ -198,3 +289,60 @@ impl DataFiles for WorkshopCard {
         &self.data_files
     }
 }
+
+impl CardWriter for WorkshopCard {
+    fn write_card_file(&self, card_file_id: &CardFileID) -> Result<Option<Vec<u8>>> {
+        let application_identification = &self.application_identification;
+        match card_file_id {
+            CardFileID::IC => write_to_vec(|writer| self.card_chip_identification.write(writer)).map(Some),
+            CardFileID::ICC => write_to_vec(|writer| self.card_icc_identification.write(writer)).map(Some),
+            CardFileID::ApplicationIdentification => write_to_vec(|writer| application_identification.write(writer)).map(Some),
+            CardFileID::ApplicationIdentificationV2 => write_card_file(&self.application_identification_v2),
+            CardFileID::Calibration => write_card_file(&self.calibration),
+            CardFileID::EventsData => {
+                let params = CardEventDataParams::new(11, application_identification.no_events_per_type);
+                write_card_file_with_params(&self.events_data, &params)
+            }
+            CardFileID::FaultsData => {
+                let params = CardFaultDataParams::new(application_identification.no_faults_per_type);
+                write_card_file_with_params(&self.faults_data, &params)
+            }
+            CardFileID::DriverActivityData => {
+                let params = CardDriverActivityParams::new(application_identification.activity_structure_length);
+                write_card_file_with_params(&self.driver_activity_data, &params)
+            }
+            CardFileID::VehiclesUsed => write_card_file(&self.vehicles_used),
+            CardFileID::Places => {
+                let params = CardPlaceDailyWorkPeriodParams::new(application_identification.no_of_card_place_records, 2);
+                write_card_file_with_params(&self.places, &params)
+            }
+            CardFileID::CurrentUsage => write_card_file(&self.current_usage),
+            CardFileID::Identification => write_card_file(&self.identification),
+            CardFileID::SpecificConditions => write_card_file(&self.specific_conditions),
+            CardFileID::VehicleUnitsUsed => write_card_file(&self.vehicle_units_used),
+            CardFileID::GnssPlaces => write_card_file(&self.gnss_places),
+            CardFileID::BorderCrossings => {
+                let params = CardBorderCrossingsParams::new(self.application_identification_v2()?.no_of_border_crossing_records);
+                write_card_file_with_params(&self.border_crossings, &params)
+            }
+            CardFileID::LoadUnloadOperations => {
+                let params = CardLoadUnloadOperationsParams::new(self.application_identification_v2()?.no_of_load_unload_records);
+                write_card_file_with_params(&self.load_unload_operations, &params)
+            }
+            CardFileID::LoadTypeEntries => {
+                let params = CardLoadTypeEntriesParams::new(self.application_identification_v2()?.no_of_load_type_entry_records);
+                write_card_file_with_params(&self.load_type_entries, &params)
+            }
+            CardFileID::VUConfiguration => write_card_file(&self.vu_configurations),
+            CardFileID::CalibrationAddData => {
+                let params = WorkshopCardCalibrationAddDataParams::new(application_identification.no_off_calibration_records);
+                write_card_file_with_params(&self.calibration_add_data, &params)
+            }
+            CardFileID::CardCertificate => write_card_file(&self.card_certificate),
+            CardFileID::CACertificate => write_card_file(&self.ca_certificate),
+            CardFileID::CardSignCertificate => write_card_file(&self.card_sign_certificate),
+            CardFileID::LinkCertificate => write_card_file(&self.link_certificate),
+            _ => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use binary_data::{BinSeek, ReadBytes, WriteBytes};
+use serde::{Deserialize, Serialize};
+
+use crate::gen2::write_cyclic_records;
+use crate::{
+    Readable, ReadableField, ReadableWithParams, Result, SpanRecorder, Writable, WritableWithParams, bytes_to_ia5_fix_string,
+    gen2::{LoadType, read_cyclic_records},
+    tacho::{NationNumeric, TimeReal},
+    write_ia5_string,
+};
+
+const VEHICLE_IDENTIFICATION_NUMBER_LENGTH: u32 = 17;
+
+/// Additional information, stored in a workshop card, related to a
+/// calibration (generation 2, version 2).
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WorkshopCardCalibrationAddDataRecord {
+    #[serde(rename = "calibrationDateTime")]
+    pub calibration_date_time: TimeReal,
+    #[serde(rename = "vehicleIdentificationNumber")]
+    pub vehicle_identification_number: String,
+    #[serde(rename = "byDefaultLoadType")]
+    pub by_default_load_type: LoadType,
+    #[serde(rename = "calibrationCountry")]
+    pub calibration_country: NationNumeric,
+    #[serde(rename = "calibrationCountryTimestamp")]
+    pub calibration_country_timestamp: TimeReal,
+}
+
+impl Readable<WorkshopCardCalibrationAddDataRecord> for WorkshopCardCalibrationAddDataRecord {
+    fn read<R: ReadBytes + BinSeek>(reader: &mut R) -> Result<WorkshopCardCalibrationAddDataRecord> {
+        let calibration_date_time = TimeReal::read_field(reader, "calibrationDateTime")?;
+        let vehicle_identification_number = SpanRecorder::field("vehicleIdentificationNumber", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(VEHICLE_IDENTIFICATION_NUMBER_LENGTH)?)?)
+        })?;
+        let by_default_load_type: LoadType =
+            SpanRecorder::field("byDefaultLoadType", "LoadType", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let calibration_country: NationNumeric =
+            SpanRecorder::field("calibrationCountry", "NationNumeric", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let calibration_country_timestamp = TimeReal::read_field(reader, "calibrationCountryTimestamp")?;
+
+        Ok(Self {
+            calibration_date_time,
+            vehicle_identification_number,
+            by_default_load_type,
+            calibration_country,
+            calibration_country_timestamp,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct WorkshopCardCalibrationAddDataParams {
+    pub no_of_calibration_records: u8,
+}
+
+impl WorkshopCardCalibrationAddDataParams {
+    pub fn new(no_of_calibration_records: u8) -> Self {
+        Self { no_of_calibration_records }
+    }
+}
+
+/// Additional information, stored in a workshop card, related to the
+/// calibrations performed with the card (generation 2, version 2). The records
+/// are ordered from the oldest to the newest.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WorkshopCardCalibrationAddData {
+    #[serde(rename = "calibrationPointerNewestRecord")]
+    pub calibration_pointer_newest_record: u8,
+    #[serde(rename = "workshopCardCalibrationAddDataRecords")]
+    pub workshop_card_calibration_add_data_records: Vec<WorkshopCardCalibrationAddDataRecord>,
+}
+
+impl ReadableWithParams<WorkshopCardCalibrationAddData> for WorkshopCardCalibrationAddData {
+    type P = WorkshopCardCalibrationAddDataParams;
+
+    fn read<R: ReadBytes + BinSeek>(reader: &mut R, params: &Self::P) -> Result<WorkshopCardCalibrationAddData> {
+        let calibration_pointer_newest_record =
+            SpanRecorder::field("calibrationPointerNewestRecord", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let workshop_card_calibration_add_data_records = read_cyclic_records(
+            reader,
+            params.no_of_calibration_records as usize,
+            calibration_pointer_newest_record as usize,
+            "workshopCardCalibrationAddDataRecords",
+            |record: &WorkshopCardCalibrationAddDataRecord| record.calibration_date_time.has_data(),
+        )?;
+
+        Ok(Self { calibration_pointer_newest_record, workshop_card_calibration_add_data_records })
+    }
+}
+
+impl Writable for WorkshopCardCalibrationAddDataRecord {
+    /// Writes a `WorkshopCardCalibrationAddDataRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W) -> Result<()> {
+        self.calibration_date_time.write(writer)?;
+        write_ia5_string(writer, &self.vehicle_identification_number, VEHICLE_IDENTIFICATION_NUMBER_LENGTH as usize)?;
+        writer.write_u8((&self.by_default_load_type).into())?;
+        writer.write_u8((&self.calibration_country).into())?;
+        self.calibration_country_timestamp.write(writer)?;
+        Ok(())
+    }
+}
+
+impl WritableWithParams for WorkshopCardCalibrationAddData {
+    type P = WorkshopCardCalibrationAddDataParams;
+
+    /// Writes the pointer and the cyclic records, the newest record at the pointer.
+    fn write<W: WriteBytes + BinSeek>(&self, writer: &mut W, params: &Self::P) -> Result<()> {
+        writer.write_u8(self.calibration_pointer_newest_record)?;
+        write_cyclic_records(
+            writer,
+            &self.workshop_card_calibration_add_data_records,
+            params.no_of_calibration_records as usize,
+            self.calibration_pointer_newest_record as usize,
+        )?;
+        Ok(())
+    }
+}
@@ -1,10 +1,11 @@
 use binary_data::BigEndian;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Readable, bytes_to_ia5_fix_string,
+    Readable, ReadableField, SpanRecorder, Writable, bytes_to_ia5_fix_string,
     gen2::SealDataCard,
     tacho::{CalibrationPurpose, ExtendedSerialNumber, OdometerShort, TimeReal, VehicleRegistrationIdentification},
+    write_ia5_string,
 };
 
 const VEHICLE_IDENTIFICATION_NUMBER_LENGTH: u32 = 17;
@@ -13,7 +14,8 @@ const VU_PART_NUMBER_LENGTH: u32 = 16;
 
 /// Information, stored in a workshop card, related to a calibration
 /// performed with the card (Annex 1C requirement 314 and 337).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct WorkshopCardCalibrationRecord {
     #[serde(rename = "calibrationPurpose")]
     pub calibration_purpose: CalibrationPurpose,
@@ -57,27 +59,42 @@ pub struct WorkshopCardCalibrationRecord {
 
 impl Readable<WorkshopCardCalibrationRecord> for WorkshopCardCalibrationRecord {
     fn read<R: binary_data::ReadBytes + binary_data::BinSeek>(reader: &mut R) -> crate::Result<WorkshopCardCalibrationRecord> {
-        let calibration_purpose: CalibrationPurpose = reader.read_u8()?.into();
-        let vehicle_identification_number =
-            bytes_to_ia5_fix_string(&reader.read_into_vec(VEHICLE_IDENTIFICATION_NUMBER_LENGTH)?)?;
-        let vehicle_registration = VehicleRegistrationIdentification::read(reader)?;
-        let w_vehicle_characteristic_constant = reader.read_u16::<BigEndian>()?;
-        let k_constant_of_recording_equipment = reader.read_u16::<BigEndian>()?;
-        let l_tyre_circumference = reader.read_u16::<BigEndian>()?;
-        let tyre_size = bytes_to_ia5_fix_string(&reader.read_into_vec(TYRE_SIZE_LENGTH)?)?;
-        let authorised_speed = reader.read_u8()?;
-        let old_odometer_value = OdometerShort::read(reader)?;
-        let new_odometer_value = OdometerShort::read(reader)?;
-        let old_time_value = TimeReal::read(reader)?;
-        let new_time_value = TimeReal::read(reader)?;
-        let next_calibration_date = TimeReal::read(reader)?;
-        let vu_part_number = bytes_to_ia5_fix_string(&reader.read_into_vec(VU_PART_NUMBER_LENGTH)?)?;
-        let vu_serial_number = ExtendedSerialNumber::read(reader)?;
-        let sensor_serial_number = ExtendedSerialNumber::read(reader)?;
+        let calibration_purpose: CalibrationPurpose =
+            SpanRecorder::field("calibrationPurpose", "CalibrationPurpose", reader, |reader| Ok(reader.read_u8()?.into()))?;
+        let vehicle_identification_number = SpanRecorder::field("vehicleIdentificationNumber", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(VEHICLE_IDENTIFICATION_NUMBER_LENGTH)?)?)
+        })?;
+        let vehicle_registration = VehicleRegistrationIdentification::read_field(reader, "vehicleRegistration")?;
+        let w_vehicle_characteristic_constant =
+            SpanRecorder::field("wVehicleCharacteristicConstant", "INTEGER", reader, |reader| {
+                Ok(reader.read_u16::<BigEndian>()?)
+            })?;
+        let k_constant_of_recording_equipment = SpanRecorder::field(
+            "kConstantOfRecordingEquipment",
+            "INTEGER",
+            reader,
+            |reader| Ok(reader.read_u16::<BigEndian>()?),
+        )?;
+        let l_tyre_circumference =
+            SpanRecorder::field("lTyreCircumference", "INTEGER", reader, |reader| Ok(reader.read_u16::<BigEndian>()?))?;
+        let tyre_size = SpanRecorder::field("tyreSize", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(TYRE_SIZE_LENGTH)?)?)
+        })?;
+        let authorised_speed = SpanRecorder::field("authorisedSpeed", "INTEGER", reader, |reader| Ok(reader.read_u8()?))?;
+        let old_odometer_value = OdometerShort::read_field(reader, "oldOdometerValue")?;
+        let new_odometer_value = OdometerShort::read_field(reader, "newOdometerValue")?;
+        let old_time_value = TimeReal::read_field(reader, "oldTimeValue")?;
+        let new_time_value = TimeReal::read_field(reader, "newTimeValue")?;
+        let next_calibration_date = TimeReal::read_field(reader, "nextCalibrationDate")?;
+        let vu_part_number = SpanRecorder::field("vuPartNumber", "IA5String", reader, |reader| {
+            Ok(bytes_to_ia5_fix_string(&reader.read_into_vec(VU_PART_NUMBER_LENGTH)?)?)
+        })?;
+        let vu_serial_number = ExtendedSerialNumber::read_field(reader, "vuSerialNumber")?;
+        let sensor_serial_number = ExtendedSerialNumber::read_field(reader, "sensorSerialNumber")?;
 
-        let sensor_gnss_serial_number = ExtendedSerialNumber::read(reader)?;
-        let rcm_serial_number = ExtendedSerialNumber::read(reader)?;
-        let seal_data_card = SealDataCard::read(reader)?;
+        let sensor_gnss_serial_number = ExtendedSerialNumber::read_field(reader, "sensorGnssSerialNumber")?;
+        let rcm_serial_number = ExtendedSerialNumber::read_field(reader, "rcmSerialNumber")?;
+        let seal_data_card = SealDataCard::read_field(reader, "sealDataCard")?;
 
         Ok(Self {
             calibration_purpose,
@@ -102,3 +119,29 @@ impl Readable<WorkshopCardCalibrationRecord> for WorkshopCardCalibrationRecord {
         })
     }
 }
+
+impl Writable for WorkshopCardCalibrationRecord {
+    /// Writes a `WorkshopCardCalibrationRecord` to a binary stream, conforming to the DDD file format.
+    fn write<W: binary_data::WriteBytes + binary_data::BinSeek>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_u8((&self.calibration_purpose).into())?;
+        write_ia5_string(writer, &self.vehicle_identification_number, VEHICLE_IDENTIFICATION_NUMBER_LENGTH as usize)?;
+        self.vehicle_registration.write(writer)?;
+        writer.write_u16::<BigEndian>(self.w_vehicle_characteristic_constant)?;
+        writer.write_u16::<BigEndian>(self.k_constant_of_recording_equipment)?;
+        writer.write_u16::<BigEndian>(self.l_tyre_circumference)?;
+        write_ia5_string(writer, &self.tyre_size, TYRE_SIZE_LENGTH as usize)?;
+        writer.write_u8(self.authorised_speed)?;
+        self.old_odometer_value.write(writer)?;
+        self.new_odometer_value.write(writer)?;
+        self.old_time_value.write(writer)?;
+        self.new_time_value.write(writer)?;
+        self.next_calibration_date.write(writer)?;
+        write_ia5_string(writer, &self.vu_part_number, VU_PART_NUMBER_LENGTH as usize)?;
+        self.vu_serial_number.write(writer)?;
+        self.sensor_serial_number.write(writer)?;
+        self.sensor_gnss_serial_number.write(writer)?;
+        self.rcm_serial_number.write(writer)?;
+        self.seal_data_card.write(writer)?;
+        Ok(())
+    }
+}
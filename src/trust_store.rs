@@ -0,0 +1,347 @@
+//! Trust store of the European root keys for the verification of tachograph files.
+//!
+//! The certificates of a card or of a VU download are issued by one of several roots, the
+//! Gen1 ERCA key, the Gen2 ERCA root certificates and the roots of the non-EU AETR
+//! countries. A new Gen2 root is linked to the previous one with a link certificate, so
+//! the certificates issued by the new root are verified from the previous root.
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use log::debug;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    Error, HexDisplay, Result, TachographData, gen1, gen2,
+    tacho::{CardFilesMap, CardGeneration, VerifyResult, VuVerifyResult},
+};
+
+const GEN1_ERCA_PK_SIZE: usize = 144;
+const KEY_IDENTIFIER_SIZE: usize = 8;
+const ROOT_FILE_EXTENSION: &str = "bin";
+const MSCA_CACHE_FILE_EXTENSION: &str = "msca";
+const GEN1_MSCA_CACHE_PREFIX: &str = "gen1";
+const GEN2_MSCA_CACHE_PREFIX: &str = "gen2";
+const HMAC_BLOCK_SIZE: usize = 64;
+const HMAC_TAG_SIZE: usize = 32;
+
+/// Returns the key identifier of the root `data`, the holder reference of a Gen1 ERCA
+/// public key or the certificate holder reference of a Gen2 root certificate.
+fn root_key_identifier(data: &[u8]) -> Vec<u8> {
+    if data.len() == GEN1_ERCA_PK_SIZE {
+        return data[..KEY_IDENTIFIER_SIZE].to_vec();
+    }
+    gen2::certificate_references(data).map(|(_, holder_reference)| holder_reference).unwrap_or_default()
+}
+
+/// Returns the HMAC-SHA256 of `data` with `key`.
+fn hmac_sha256(key: &[u8], data: &[&[u8]]) -> [u8; HMAC_TAG_SIZE] {
+    let mut key_block = [0u8; HMAC_BLOCK_SIZE];
+    if key.len() > HMAC_BLOCK_SIZE {
+        key_block[..HMAC_TAG_SIZE].copy_from_slice(&Sha256::digest(key));
+    } else {
+        key_block[..key.len()].copy_from_slice(key);
+    }
+    let mut inner = Sha256::new();
+    inner.update(key_block.map(|byte| byte ^ 0x36));
+    for part in data {
+        inner.update(part);
+    }
+    let mut outer = Sha256::new();
+    outer.update(key_block.map(|byte| byte ^ 0x5C));
+    outer.update(inner.finalize());
+    outer.finalize().into()
+}
+
+/// The MSCA cache directory and the secret key authenticating its entries.
+#[derive(Clone)]
+struct MscaCache {
+    directory: PathBuf,
+    key: Vec<u8>,
+}
+
+impl MscaCache {
+    /// Returns the tag of the cache entry of `member_state_certificate` of the generation
+    /// with the file name prefix `prefix`.
+    fn tag(&self, prefix: &str, member_state_certificate: &[u8]) -> [u8; HMAC_TAG_SIZE] {
+        hmac_sha256(&self.key, &[prefix.as_bytes(), member_state_certificate])
+    }
+
+    /// Returns the member state certificate of the cache entry `entry`, when the entry ends
+    /// with the tag of the certificate.
+    fn authenticated<'a>(&self, prefix: &str, entry: &'a [u8]) -> Option<&'a [u8]> {
+        let (member_state_certificate, tag) = entry.split_at_checked(entry.len().checked_sub(HMAC_TAG_SIZE)?)?;
+        let expected_tag = self.tag(prefix, member_state_certificate);
+        // The tags are compared in constant time.
+        let difference = tag.iter().zip(expected_tag.iter()).fold(0, |difference, (a, b)| difference | (a ^ b));
+        (difference == 0).then_some(member_state_certificate)
+    }
+}
+
+impl fmt::Debug for MscaCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MscaCache").field("directory", &self.directory).finish_non_exhaustive()
+    }
+}
+
+/// # Trust Store of European Root Keys
+/// The Gen1 ERCA public keys, the Gen2 root certificates and the Gen2 link certificates
+/// from which cards and VU downloads are verified.
+///
+/// The root of a verification is selected by the certification authority reference of
+/// the member state certificate. A Gen2 member state certificate issued by a root which
+/// is not in the store is verified through the link certificates of the store, and of
+/// the card, up to a root of the store.
+///
+/// With an MSCA cache, the member state certificates of the verified files are stored
+/// in the cache directory, and a member state certificate which is not issued by a root
+/// of the store is trusted when it is in the cache. Each entry of the cache is
+/// authenticated with an HMAC of the secret key of the cache, the entries which are not
+/// written with the key are ignored. The cache directory is read on each verification,
+/// so it can be shared by several trust stores with the same key. A store with pinned
+/// roots does not trust the cache.
+///
+/// ```rust,no_run
+/// use esm_parser::{TrustStore, parse_from_file};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let trust_store = TrustStore::from_directory("path/to/erca")?;
+///     let data = parse_from_file("path/to/vu_file.ddd")?;
+///     let result = trust_store.verify_vu(&data)?;
+///     println!("Verification status: {:?}", result.status);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TrustStore {
+    gen1_roots: Vec<[u8; GEN1_ERCA_PK_SIZE]>,
+    gen2_roots: Vec<Vec<u8>>,
+    gen2_link_certificates: Vec<Vec<u8>>,
+    msca_cache: Option<MscaCache>,
+    roots_pinned: bool,
+}
+
+impl TrustStore {
+    /// Creates an empty trust store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the root keys and the link certificates of the `*.bin` files of `path`, the
+    /// other files of the directory are ignored. Each file is added with `add`.
+    pub fn from_directory(path: impl AsRef<Path>) -> Result<Self> {
+        let mut file_paths = Vec::new();
+        for entry in std::fs::read_dir(path)? {
+            let file_path = entry?.path();
+            let is_root_file = file_path
+                .extension()
+                .is_some_and(|extension| extension.to_string_lossy().eq_ignore_ascii_case(ROOT_FILE_EXTENSION));
+            if file_path.is_file() && is_root_file {
+                file_paths.push(file_path);
+            }
+        }
+        file_paths.sort();
+
+        let mut trust_store = Self::new();
+        for file_path in file_paths {
+            let data = std::fs::read(&file_path)?;
+            trust_store
+                .add(&data)
+                .map_err(|error| Error::VerifyError(format!("{} is not a root key: {error}", file_path.display())))?;
+            debug!("TrustStore::from_directory - Added: {}", file_path.display());
+        }
+        Ok(trust_store)
+    }
+
+    /// Sets the directory of the MSCA cache, the directory is created when the first
+    /// member state certificate is cached. The entries of the cache are authenticated
+    /// with `key`, a secret of the application which can not be read from the directory.
+    pub fn with_msca_cache(mut self, directory: impl Into<PathBuf>, key: &[u8]) -> Self {
+        self.msca_cache = Some(MscaCache { directory: directory.into(), key: key.to_vec() });
+        self
+    }
+
+    /// Adds a 144 byte Gen1 ERCA public key, a self-signed Gen2 root certificate or a Gen2
+    /// link certificate.
+    pub fn add(&mut self, data: &[u8]) -> Result<()> {
+        if data.len() != GEN1_ERCA_PK_SIZE {
+            let (authority_reference, holder_reference) = gen2::certificate_references(data)?;
+            if authority_reference != holder_reference {
+                return self.add_link_certificate(data);
+            }
+        }
+        self.add_root(data)
+    }
+
+    /// Adds a root, a 144 byte Gen1 ERCA public key or a Gen2 root certificate. The root
+    /// replaces a root of the store with the same key identifier.
+    pub fn add_root(&mut self, data: &[u8]) -> Result<()> {
+        if data.is_empty() {
+            return Err(Error::EmptyInputData("ERCA Public Key are not provided.".to_owned()));
+        }
+        let key_identifier = root_key_identifier(data);
+        if let Ok(erca_pk) = <[u8; GEN1_ERCA_PK_SIZE]>::try_from(data) {
+            self.gen1_roots.retain(|root| !root.starts_with(&key_identifier));
+            self.gen1_roots.push(erca_pk);
+            return Ok(());
+        }
+        let (authority_reference, holder_reference) = gen2::certificate_references(data)?;
+        if authority_reference != holder_reference {
+            return Err(Error::VerifyError("Root Certificate is not self-signed.".to_owned()));
+        }
+        self.gen2_roots.retain(|root| root_key_identifier(root) != key_identifier);
+        self.gen2_roots.push(data.to_vec());
+        Ok(())
+    }
+
+    /// Adds a Gen2 link certificate, the certificate of a new root issued by a previous root.
+    pub fn add_link_certificate(&mut self, data: &[u8]) -> Result<()> {
+        let (authority_reference, holder_reference) = gen2::certificate_references(data)?;
+        if authority_reference == holder_reference {
+            return Err(Error::VerifyError("Link Certificate is self-signed.".to_owned()));
+        }
+        self.gen2_link_certificates.push(data.to_vec());
+        Ok(())
+    }
+
+    /// Keeps only the roots with a key identifier of `key_identifiers`, the other roots of
+    /// the store are removed. The member state certificates of the MSCA cache are no longer
+    /// trusted, only the certificates issued by the pinned roots are.
+    pub fn pin_roots(&mut self, key_identifiers: &[[u8; KEY_IDENTIFIER_SIZE]]) {
+        let is_pinned = |root: &[u8]| key_identifiers.iter().any(|key_identifier| root_key_identifier(root) == key_identifier);
+        self.gen1_roots.retain(|root| is_pinned(root));
+        self.gen2_roots.retain(|root| is_pinned(root));
+        self.roots_pinned = true;
+    }
+
+    /// Returns true when the store has no root.
+    pub fn is_empty(&self) -> bool {
+        self.gen1_roots.is_empty() && self.gen2_roots.is_empty()
+    }
+
+    /// Verifies the signature of tachograph card data files as `verify_card`, from the
+    /// root of the store which issued the CA certificate of the card. The files of a Gen1
+    /// card, or of the Gen1 application of a combined card, are verified with a Gen1 root.
+    pub fn verify_card(&self, generation: &CardGeneration, data_files: &CardFilesMap) -> Result<VerifyResult> {
+        if data_files.is_empty() {
+            return Err(Error::EmptyInputData("Data for verification are not provided.".to_owned()));
+        }
+        if *generation == CardGeneration::Gen1 {
+            let member_state_certificates = self.cached_member_state_certificates(GEN1_MSCA_CACHE_PREFIX)?;
+            let (result, ca_certificate) = gen1::verify_with_roots(data_files, &self.gen1_roots, &member_state_certificates)?;
+            self.cache_member_state_certificate(GEN1_MSCA_CACHE_PREFIX, &ca_certificate)?;
+            return Ok(result);
+        }
+        let member_state_certificates = self.cached_member_state_certificates(GEN2_MSCA_CACHE_PREFIX)?;
+        let (result, ca_certificate) =
+            gen2::verify_with_roots(data_files, &self.gen2_roots, &self.gen2_link_certificates, &member_state_certificates)?;
+        self.cache_member_state_certificate(GEN2_MSCA_CACHE_PREFIX, &ca_certificate)?;
+        Ok(result)
+    }
+
+    /// Verifies the certificates and the signatures of the TREPs of a VU download as
+    /// `verify_vu`, from the root of the store which issued the member state certificate
+    /// of the overview.
+    pub fn verify_vu(&self, data: &TachographData) -> Result<VuVerifyResult> {
+        let (prefix, (result, member_state_certificate)) = match data {
+            TachographData::VUGen1(vu_data) => {
+                let member_state_certificates = self.cached_member_state_certificates(GEN1_MSCA_CACHE_PREFIX)?;
+                (GEN1_MSCA_CACHE_PREFIX, gen1::verify_vu_with_roots(vu_data, &self.gen1_roots, &member_state_certificates)?)
+            }
+            TachographData::VUGen2(vu_data) => {
+                let member_state_certificates = self.cached_member_state_certificates(GEN2_MSCA_CACHE_PREFIX)?;
+                let verified = gen2::verify_vu_with_roots(
+                    vu_data,
+                    &self.gen2_roots,
+                    &self.gen2_link_certificates,
+                    &member_state_certificates,
+                )?;
+                (GEN2_MSCA_CACHE_PREFIX, verified)
+            }
+            _ => return Err(Error::VerifyError("Data for verification are not a VU download.".to_owned())),
+        };
+        self.cache_member_state_certificate(prefix, &member_state_certificate)?;
+        Ok(result)
+    }
+
+    /// Returns the authenticated member state certificates of the MSCA cache with the file
+    /// name prefix `prefix` of their generation, none when the roots are pinned.
+    fn cached_member_state_certificates(&self, prefix: &str) -> Result<Vec<Vec<u8>>> {
+        let Some(msca_cache) = self.msca_cache.as_ref().filter(|msca_cache| msca_cache.directory.is_dir()) else {
+            return Ok(Vec::new());
+        };
+        if self.roots_pinned {
+            return Ok(Vec::new());
+        }
+        let mut member_state_certificates = Vec::new();
+        for entry in std::fs::read_dir(&msca_cache.directory)? {
+            let file_path = entry?.path();
+            let is_cache_file = file_path.extension().is_some_and(|extension| extension == MSCA_CACHE_FILE_EXTENSION)
+                && file_path.file_name().is_some_and(|file_name| file_name.to_string_lossy().starts_with(prefix));
+            if !file_path.is_file() || !is_cache_file {
+                continue;
+            }
+            let entry = std::fs::read(&file_path)?;
+            match msca_cache.authenticated(prefix, &entry) {
+                Some(member_state_certificate) => member_state_certificates.push(member_state_certificate.to_vec()),
+                None => debug!("TrustStore::cached_member_state_certificates - Not authenticated: {}", file_path.display()),
+            }
+        }
+        Ok(member_state_certificates)
+    }
+
+    /// Stores the verified `member_state_certificate` in the MSCA cache, followed by its
+    /// HMAC. The file name is the SHA-256 hash of the certificate after the prefix `prefix`
+    /// of its generation.
+    fn cache_member_state_certificate(&self, prefix: &str, member_state_certificate: &[u8]) -> Result<()> {
+        let Some(msca_cache) = self.msca_cache.as_ref() else {
+            return Ok(());
+        };
+        if member_state_certificate.is_empty() {
+            return Ok(());
+        }
+        let hash = Sha256::digest(member_state_certificate).as_slice().to_hex().to_lower_hex_string();
+        let file_path = msca_cache.directory.join(format!("{prefix}-{hash}.{MSCA_CACHE_FILE_EXTENSION}"));
+        let entry = [member_state_certificate, &msca_cache.tag(prefix, member_state_certificate)].concat();
+        if std::fs::read(&file_path).ok().as_ref() != Some(&entry) {
+            std::fs::create_dir_all(&msca_cache.directory)?;
+            std::fs::write(&file_path, entry)?;
+            debug!("TrustStore::cache_member_state_certificate - Cached: {}", file_path.display());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::Gen2TestPki;
+
+    #[test]
+    fn test_add_classifies_roots_and_link_certificates() {
+        let previous_pki = Gen2TestPki::new(1);
+        let pki = Gen2TestPki::new(3).with_erca_key_identifier([0xFD, 0x45, 0x43, 0x20, 0x02, 0xFF, 0xFF, 0x02]);
+        let mut trust_store = TrustStore::new();
+        assert!(trust_store.is_empty());
+
+        trust_store.add(&previous_pki.erca_certificate()).unwrap();
+        trust_store.add(&previous_pki.link_certificate(&pki)).unwrap();
+        trust_store.add(&pki.erca_certificate()).unwrap();
+        trust_store.add(&Gen2TestPki::new(2).erca_certificate()).unwrap();
+        assert_eq!(trust_store.gen2_roots.len(), 2);
+        assert_eq!(trust_store.gen2_link_certificates.len(), 1);
+        assert!(matches!(trust_store.add(&[0x00; 32]), Err(Error::VerifyError(_))));
+
+        trust_store.pin_roots(&[[0xFD, 0x45, 0x43, 0x20, 0x02, 0xFF, 0xFF, 0x02]]);
+        assert_eq!(trust_store.gen2_roots, vec![pki.erca_certificate()]);
+    }
+
+    #[test]
+    fn test_hmac_sha256() {
+        // RFC 4231, test case 2
+        let tag = hmac_sha256(b"Jefe", &[b"what do ya ", b"want for nothing?"]);
+        assert_eq!(tag.to_hex().to_lower_hex_string(), "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+    }
+}
@@ -1,16 +1,18 @@
 //! Signature verification for tachograph files.
 //!
-//! This module provides functionality to verify the digital signatures of tachograph data files.
-//! It supports both Gen1 and Gen2 tachograph data, dispatching to the appropriate verification
-//! logic based on the provided European Root Certification Authority (ERCA) public key size.
+//! This module provides functionality to verify the digital signatures of tachograph data files
+//! and of VU downloads. It supports both Gen1 and Gen2 tachograph data, dispatching to the
+//! appropriate verification logic based on the provided European Root Certification Authority
+//! (ERCA) public key size. The verification from several root keys and link certificates is
+//! provided by `TrustStore`.
 
 use std::io::Read;
 
 use binary_data::{BinReader, BinSeek};
 
 use crate::{
-    Error, Result, gen1, gen2,
-    tacho::{CardFilesMap, CardGeneration, VerifyResult},
+    Error, Result, TachographData, gen1, gen2,
+    tacho::{CardFilesMap, CardGeneration, VerifyResult, VuVerifyResult},
 };
 
 /// Verifies the signature of tachograph card data files.
@@ -59,7 +61,7 @@ pub fn verify_card(generation: &CardGeneration, data_files: &CardFilesMap, erca_
             erca_pk.len()
         )));
     }
-    if *generation == CardGeneration::Gen2 && erca_pk.len() != 205 {
+    if (*generation == CardGeneration::Gen2 || *generation == CardGeneration::Gen2v2) && erca_pk.len() != 205 {
         return Err(Error::VerifyError(format!(
             "ERCA Public Key for Card Gen2 need to be 205 bytes but is: {}, this are not supported (Gen1 = 144 bytes, Gen2 = 205 bytes).",
             erca_pk.len()
@@ -103,6 +105,71 @@ pub fn verify_card_with_erca_path(
     verify_card(&generation, data_files, &erca_pk)
 }
 
+/// Verifies the certificates and the signatures of the TREPs of a VU download.
+///
+/// The member state and VU certificates of the overview are verified up to the ERCA public
+/// key, then the signature of each TREP is verified with the key of the VU certificate, over
+/// the data of the TREP as it was read from the DDD file. A VU download imported from JSON
+/// has no data as read, its TREPs are written again for the verification.
+///
+/// # Arguments
+///
+/// * `data` - The parsed VU download, `TachographData::VUGen1` or `TachographData::VUGen2`.
+/// * `erca_pk` - A byte slice representing the European Root Certification Authority (ERCA) public key.
+///   - For `VUGen1`, this must be 144 bytes.
+///   - For `VUGen2`, this must be 205 bytes.
+///
+/// # Returns
+///
+/// A `Result` containing a `VuVerifyResult` on success, which details the outcome of the
+/// signature verification for each TREP.
+///
+/// # Errors
+///
+/// This function will return an `Error` if:
+/// * `erca_pk` is empty (`Error::EmptyInputData`).
+/// * `data` is not a VU download or the length of `erca_pk` does not match its generation (`Error::VerifyError`).
+/// * The certificates of the overview are missing or are not signed by the ERCA (`Error::VerifyError`).
+pub fn verify_vu(data: &TachographData, erca_pk: &[u8]) -> Result<VuVerifyResult> {
+    if erca_pk.is_empty() {
+        return Err(Error::EmptyInputData("ERCA Public Key are not provided.".to_owned()));
+    }
+    match data {
+        TachographData::VUGen1(vu_data) => {
+            let erca_pk = erca_pk.try_into().map_err(|_| {
+                Error::VerifyError(format!("ERCA Public Key for VU Gen1 need to be 144 bytes but is: {}.", erca_pk.len()))
+            })?;
+            gen1::verify_vu(vu_data, erca_pk)
+        }
+        TachographData::VUGen2(vu_data) => {
+            let erca_pk = erca_pk.try_into().map_err(|_| {
+                Error::VerifyError(format!("ERCA Public Key for VU Gen2 need to be 205 bytes but is: {}.", erca_pk.len()))
+            })?;
+            gen2::verify_vu(vu_data, erca_pk)
+        }
+        _ => Err(Error::VerifyError("Data for verification are not a VU download.".to_owned())),
+    }
+}
+
+/// Verifies the signatures of a VU download by loading the ERCA public key from a file path.
+///
+/// # Arguments
+///
+/// * `data` - The parsed VU download.
+/// * `erca_pk_file_path` - The file system path to the ERCA public key file.
+///
+/// # Errors
+///
+/// This function can fail if:
+/// * The file at `erca_pk_file_path` cannot be opened or read.
+/// * Any of the conditions for an error in the `verify_vu` function are met.
+pub fn verify_vu_with_erca_path(data: &TachographData, erca_pk_file_path: &str) -> Result<VuVerifyResult> {
+    let mut file = BinReader::open(erca_pk_file_path)?;
+    let mut erca_pk = Vec::<u8>::with_capacity(file.len()?);
+    file.read_to_end(&mut erca_pk)?;
+    verify_vu(data, &erca_pk)
+}
+
 #[cfg(target_arch = "wasm32")]
 mod wasm_support {
     use super::*;
@@ -142,4 +209,22 @@ mod wasm_support {
             Err(e) => Err(to_value(&e.to_string()).unwrap_or(JsValue::NULL)),
         }
     }
+
+    /// A WASM-bindgen wrapper for the `verify_vu` function.
+    ///
+    /// The VU download is parsed from `esm_data`, the content of the DDD file, because the
+    /// signatures are verified over the data as it was read from the file.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which, on success, contains a `JsValue` representing the serialized `VuVerifyResult`.
+    /// On failure, it returns a `JsValue` containing the error message as a string.
+    #[wasm_bindgen(js_name = verify_vu)]
+    pub fn verify_vu_wasm(esm_data: &[u8], erca_pk: &[u8]) -> std::result::Result<JsValue, JsValue> {
+        let result = crate::parse_from_memory(esm_data).and_then(|data| verify_vu(&data, erca_pk));
+        match result {
+            Ok(data) => to_value(&data).map_err(|e| e.into()),
+            Err(e) => Err(to_value(&e.to_string()).unwrap_or(JsValue::NULL)),
+        }
+    }
 }
@@ -0,0 +1,219 @@
+use binary_data::{BinSeek, WriteBytes};
+use log::debug;
+
+use crate::{
+    CARD_HEADER_VU_DATA, Error, ParseContext, ParseOptions, Result, TachographData, Writable, WritableWithParams,
+    export_envelope::tachograph_data_from_json,
+    gen1,
+    gen2::{self, ParsedCard},
+    tacho::{
+        CardFileData, CardParser, CardWriter, DataFiles, TachographHeader, VUData, VUTransferResponseParameter,
+        VUTransferResponseParameterID,
+    },
+    write_to_vec,
+};
+
+/// Parsed card data which can be written back to a DDD file with `write_card_ddd`.
+pub trait CardDataWriter {
+    fn get_header(&self) -> &TachographHeader;
+
+    /// Returns the card files (EF) of all card applications, with the data written
+    /// from the parsed structures.
+    fn write_card_files(&self) -> Result<Vec<CardFileData>>;
+}
+
+/// Returns the card files of a card application, the data of a card file is only
+/// written from the parsed structure when it differs from the parsed card file.
+/// The unchanged card files keep their original bytes, e.g. unused records, so the
+/// written DDD file is byte identical to the parsed one.
+fn write_card_application_files<C: CardParser<C> + CardWriter + DataFiles>(card: &C) -> Result<Vec<CardFileData>> {
+    let data_files = card.get_data_files();
+    let (parsed, _) = ParseContext::run(&ParseOptions::lenient(), || C::parse(data_files, ""));
+    let parsed = parsed?;
+
+    let mut card_files: Vec<CardFileData> = Vec::with_capacity(data_files.len());
+    for (card_file_id, card_file) in data_files.iter() {
+        let mut card_file = card_file.clone();
+        let data = match card.write_card_file(card_file_id) {
+            Ok(data) => data,
+            // The card file which could not be parsed, e.g. without its record counts, is kept as it is.
+            Err(_) if parsed.write_card_file(card_file_id).is_err() => None,
+            Err(error) => return Err(error),
+        };
+        if let Some(mut data) = data
+            && Some(&data) != parsed.write_card_file(card_file_id).ok().flatten().as_ref()
+        {
+            debug!("CardDataWriter::write_card_files - Changed: {card_file_id:?}");
+            // The card file keeps its size, the bytes after the written data, e.g. the unused
+            // records, are kept from the parsed card file.
+            let original = card_file.data.as_deref().unwrap_or_default();
+            if data.len() > original.len() && !original.is_empty() {
+                return Err(Error::InvalidDataEncode(format!(
+                    "{card_file_id:?} data of {} bytes is longer than the card file of {} bytes",
+                    data.len(),
+                    original.len()
+                )));
+            }
+            data.extend_from_slice(&original[data.len().min(original.len())..]);
+            card_file.data = Some(data);
+        }
+        card_files.push(card_file);
+    }
+    Ok(card_files)
+}
+
+fn write_parsed_card_files<TGen1, TGen2>(parsed_card: &ParsedCard<TGen1, TGen2>) -> Result<Vec<CardFileData>>
+where
+    TGen1: CardParser<TGen1> + CardWriter + DataFiles,
+    TGen2: CardParser<TGen2> + CardWriter + DataFiles,
+{
+    match parsed_card {
+        ParsedCard::Gen1(card) => write_card_application_files(card.as_ref()),
+        ParsedCard::Gen2(card) => write_card_application_files(card.as_ref()),
+        ParsedCard::Combined(gen1_card, gen2_card) => {
+            let mut card_files = write_card_application_files(gen1_card.as_ref())?;
+            card_files.extend(write_card_application_files(gen2_card.as_ref())?);
+            Ok(card_files)
+        }
+        ParsedCard::None => Err(Error::UnknownCardType),
+    }
+}
+
+impl CardDataWriter for gen1::CardData {
+    fn get_header(&self) -> &TachographHeader {
+        &self.header
+    }
+
+    fn write_card_files(&self) -> Result<Vec<CardFileData>> {
+        match &self.card_data_responses {
+            gen1::CardResponseParameterData::DriverCard(card) => write_card_application_files(card.as_ref()),
+            gen1::CardResponseParameterData::CompanyCard(card) => write_card_application_files(card.as_ref()),
+            gen1::CardResponseParameterData::WorkshopCard(card) => write_card_application_files(card.as_ref()),
+            gen1::CardResponseParameterData::ControlCard(card) => write_card_application_files(card.as_ref()),
+            gen1::CardResponseParameterData::Unsupported => Err(Error::UnknownCardType),
+        }
+    }
+}
+
+impl CardDataWriter for gen2::CardData {
+    fn get_header(&self) -> &TachographHeader {
+        &self.header
+    }
+
+    fn write_card_files(&self) -> Result<Vec<CardFileData>> {
+        match &self.card_data_responses {
+            gen2::CardResponseParameterData::DriverCard(card) => write_parsed_card_files(card),
+            gen2::CardResponseParameterData::CompanyCard(card) => write_parsed_card_files(card),
+            gen2::CardResponseParameterData::WorkshopCard(card) => write_parsed_card_files(card),
+            gen2::CardResponseParameterData::ControlCard(card) => write_parsed_card_files(card),
+            gen2::CardResponseParameterData::Unsupported => Err(Error::UnknownCardType),
+        }
+    }
+}
+
+/// Writes parsed card data (`gen1::CardData` or `gen2::CardData`) to a DDD file.
+///
+/// The card files are written in the order of the parsed DDD file, each followed by its
+/// signature. The card files which were not changed after parsing keep their original
+/// bytes, so a parsed DDD file is written byte identical. A changed card file is written
+/// from its parsed structure, the bytes after the written data keep the original bytes.
+/// A value which does not fit in its field or a card file which gets longer than the
+/// parsed one is an error.
+///
+/// # Arguments
+///
+/// * `card_data` - The parsed card data.
+///
+/// # Returns
+///
+/// A `Result` containing the binary data of the DDD file or an `Error` if writing fails.
+pub fn write_card_ddd<T: CardDataWriter>(card_data: &T) -> Result<Vec<u8>> {
+    write_to_vec(|writer| write_card_data(writer, card_data))
+}
+
+fn write_card_data<W: WriteBytes + BinSeek, T: CardDataWriter>(writer: &mut W, card_data: &T) -> Result<()> {
+    // The card data downloaded through a VU starts with the VU header.
+    if card_data.get_header().card_in_vu_data {
+        writer.write_all(&CARD_HEADER_VU_DATA)?;
+    }
+    write_card_data_files(writer, card_data)
+}
+
+/// Writes the card files of `card_data` in the order of the parsed data, without the VU header.
+pub(crate) fn write_card_data_files<W: WriteBytes + BinSeek, T: CardDataWriter>(writer: &mut W, card_data: &T) -> Result<()> {
+    let mut card_files = card_data.write_card_files()?;
+    card_files.sort_by_key(|card_file| card_file.offset);
+    for card_file in card_files.iter() {
+        card_file.write(writer)?;
+    }
+    Ok(())
+}
+
+/// Writes parsed VU data (`gen1::VUData` or `gen2::VUData`) to a DDD file.
+///
+/// Each TREP is written as the `0x76` magic number, the TREP id and the data of the TREP,
+/// in the order of the parsed DDD file. The TREPs which were not changed after parsing
+/// keep their original bytes, so a parsed DDD file is written byte identical. A changed
+/// TREP is written from its parsed structure, the TREPs which are not known to the parser
+/// are written with their raw data and the card data downloaded through the VU is written
+/// as with `write_card_ddd`.
+///
+/// # Arguments
+///
+/// * `vu_data` - The parsed VU data.
+///
+/// # Returns
+///
+/// A `Result` containing the binary data of the DDD file or an `Error` if writing fails.
+pub fn write_vu_ddd<D, T>(vu_data: &T) -> Result<Vec<u8>>
+where
+    D: VUTransferResponseParameter + WritableWithParams<P = VUTransferResponseParameterID>,
+    T: VUData<D>,
+{
+    write_to_vec(|writer| {
+        for item in vu_data.get_data().iter() {
+            item.write(writer)?;
+        }
+        Ok(())
+    })
+}
+
+/// Writes parsed tachograph data of any data type and generation to a DDD file, with
+/// `write_card_ddd` for a card and `write_vu_ddd` for a VU.
+///
+/// # Arguments
+///
+/// * `data` - The parsed tachograph data.
+///
+/// # Returns
+///
+/// A `Result` containing the binary data of the DDD file or an `Error` if writing fails.
+pub fn write_ddd(data: &TachographData) -> Result<Vec<u8>> {
+    match data {
+        TachographData::CardGen1(card_data) => write_card_ddd(card_data),
+        TachographData::CardGen2(card_data) => write_card_ddd(card_data),
+        TachographData::VUGen1(vu_data) => write_vu_ddd(vu_data),
+        TachographData::VUGen2(vu_data) => write_vu_ddd(vu_data),
+    }
+}
+
+/// Writes the tachograph data exported with `Export::to_json` back to a DDD file, the
+/// JSON is either the exported `TachographData` or its `ExportEnvelope`.
+///
+/// The exported data keeps the raw data of the card files and TREPs, the certificates
+/// and the signatures, so a DDD file exported without changes is written with its
+/// original bytes and valid signatures. A signature is not computed again, the card file
+/// or TREP changed in the JSON keeps the signature of its original data and no longer
+/// passes verification.
+///
+/// # Arguments
+///
+/// * `json` - The JSON of a card or VU download, as produced by `Export::to_json`.
+///
+/// # Returns
+///
+/// A `Result` containing the binary data of the DDD file or an `Error` if the JSON can
+/// not be read or writing fails.
+pub fn write_ddd_from_json(json: &str) -> Result<Vec<u8>> {
+    write_ddd(&tachograph_data_from_json(json)?)
+}
@@ -1,6 +1,17 @@
 use std::sync::OnceLock;
 
-use esm_parser::{TachographData, gen1, parse_from_file, tacho::EquipmentType};
+use esm_parser::{
+    DddStreamItem, DddStreamReader, Error, Export, ParseOptions, Severity, TachographData, TrustStore, dissect_from_memory,
+    fixtures::{
+        ActivityDay, CardDddBuilder, ControlRecord, EventRecord, Gen1TestPki, Gen2TestPki, GnssRecord, SpeedBlock, VuDddBuilder,
+    },
+    gen1, gen2, parse_from_file, parse_from_memory, parse_from_memory_with_options,
+    tacho::{
+        ActivityCard, ActivityChangeInfo, ActivityType, CardFileID, CardGeneration, EquipmentType, EventFaultType,
+        Identification, VUData, VUTransferResponseParameterID, VerifyResultStatus, VerifyStatus,
+    },
+    verify_card, verify_vu, write_card_ddd, write_ddd, write_ddd_from_json, write_vu_ddd,
+};
 
 #[derive(Debug)]
 struct TestConfig {
@@ -61,3 +72,1631 @@ fn test_parse_non_existent_file() {
     // Check that the error is a file I/O error
     matches!(error, esm_parser::Error::File(_));
 }
+
+#[test]
+fn test_stream_card_files_with_signature() {
+    // --- Arrange ---
+    // ICC (data + signature) followed by IC (data only)
+    let data: Vec<u8> = vec![
+        0x00, 0x02, 0x00, 0x00, 0x02, 0xAA, 0xBB, // ICC
+        0x00, 0x02, 0x01, 0x00, 0x03, 0x01, 0x02, 0x03, // ICC signature
+        0x00, 0x05, 0x00, 0x00, 0x01, 0xCC, // IC
+    ];
+
+    // --- Act ---
+    let stream_reader = DddStreamReader::new(data.as_slice()).expect("Header should be valid");
+    let items: Vec<DddStreamItem> = stream_reader.collect::<Result<Vec<_>, _>>().expect("Stream should be valid");
+
+    // --- Assert ---
+    assert_eq!(items.len(), 2);
+    match &items[0] {
+        DddStreamItem::CardFile(card_file) => {
+            assert_eq!(card_file.card_file_id, CardFileID::ICC);
+            assert_eq!(card_file.data, Some(vec![0xAA, 0xBB]));
+            assert_eq!(card_file.signature, Some(vec![0x01, 0x02, 0x03]));
+        }
+        _ => panic!("Expected DddStreamItem::CardFile"),
+    }
+    match &items[1] {
+        DddStreamItem::CardFile(card_file) => {
+            assert_eq!(card_file.card_file_id, CardFileID::IC);
+            assert_eq!(card_file.signature, None);
+        }
+        _ => panic!("Expected DddStreamItem::CardFile"),
+    }
+}
+
+#[test]
+fn test_stream_gen1_vu_speed() {
+    // --- Arrange ---
+    // Overview without company locks and controls, followed by two speed blocks
+    let mut data: Vec<u8> = vec![0x76, 0x01];
+    data.extend_from_slice(&[0x00; 491 + 1 + 1 + 128]);
+    data.extend_from_slice(&[0x76, 0x04, 0x00, 0x02]);
+    data.extend_from_slice(&[0x00; 2 * 64]);
+    data.extend_from_slice(&[0x00; 128]);
+
+    // --- Act ---
+    let mut stream_reader = DddStreamReader::new(data.as_slice()).expect("Header should be valid");
+    let overview = stream_reader.next().expect("Expected overview").expect("Overview should be valid");
+    let item = stream_reader.next().expect("Expected speed").expect("Speed should be valid");
+
+    // --- Assert ---
+    assert!(matches!(overview, DddStreamItem::VUGen1(_)));
+    match item {
+        DddStreamItem::VUGen1(trep) => match trep.data {
+            gen1::VUTransferResponseParameterData::Speed(speed) => {
+                assert_eq!(speed.vu_detailed_speed_data.no_of_speed_blocks, 2);
+                assert_eq!(speed.vu_detailed_speed_data.vu_detailed_speed_blocks.len(), 2);
+            }
+            _ => panic!("Expected VUTransferResponseParameterData::Speed"),
+        },
+        _ => panic!("Expected DddStreamItem::VUGen1"),
+    }
+    assert!(stream_reader.next().is_none());
+    assert_eq!(stream_reader.offset(), data.len());
+}
+
+#[test]
+fn test_stream_gen1_vu_reports_skipped_bytes() {
+    // --- Arrange ---
+    // Overview without company locks and controls, three garbage bytes, speed without blocks and two garbage bytes
+    let mut data: Vec<u8> = vec![0x76, 0x01];
+    data.extend_from_slice(&[0x00; 491 + 1 + 1 + 128]);
+    let garbage_offset = data.len();
+    data.extend_from_slice(&[0x01, 0x76, 0x7A]);
+    data.extend_from_slice(&[0x76, 0x04, 0x00, 0x00]);
+    data.extend_from_slice(&[0x00; 128]);
+    data.extend_from_slice(&[0x02, 0x03]);
+
+    // --- Act ---
+    let mut stream_reader = DddStreamReader::new(data.as_slice()).expect("Header should be valid");
+    let items: Vec<DddStreamItem> = stream_reader.by_ref().collect::<Result<_, _>>().expect("Items should be valid");
+
+    // --- Assert ---
+    assert_eq!(items.len(), 2);
+    let diagnostics = stream_reader.diagnostics();
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics.iter().all(|diagnostic| diagnostic.severity == Severity::Warning));
+    assert_eq!(diagnostics[0].offset, Some(garbage_offset));
+    assert_eq!(diagnostics[0].message, "skipped 3 bytes while searching for the next TREP");
+    assert_eq!(diagnostics[1].offset, Some(data.len() - 2));
+    assert_eq!(diagnostics[1].message, "skipped 2 bytes while searching for the next TREP");
+}
+
+#[test]
+fn test_parse_truncated_vu_lenient() {
+    // --- Arrange ---
+    // Overview without company locks and controls, followed by a speed TREP with missing blocks
+    let mut data: Vec<u8> = vec![0x76, 0x01];
+    data.extend_from_slice(&[0x00; 491 + 1 + 1 + 128]);
+    data.extend_from_slice(&[0x76, 0x04, 0x00, 0x05]);
+    data.extend_from_slice(&[0x00; 64]);
+
+    // --- Act ---
+    let strict_result = parse_from_memory(&data);
+    let lenient_result = parse_from_memory_with_options(&data, &ParseOptions::lenient());
+
+    // --- Assert ---
+    assert!(strict_result.is_err(), "Strict parsing should fail for a truncated TREP.");
+    let outcome = lenient_result.expect("Lenient parsing should return the data read so far");
+    assert!(matches!(outcome.data, TachographData::VUGen1(_)));
+    assert_eq!(outcome.diagnostics.len(), 2);
+    assert_eq!(outcome.diagnostics[0].severity, Severity::Error);
+    assert_eq!(outcome.diagnostics[0].offset, Some(data.len()));
+    assert_eq!(outcome.diagnostics[0].section.as_deref(), Some("Speed"));
+    // The rest of the broken TREP is skipped while searching for the next one.
+    assert_eq!(outcome.diagnostics[1].severity, Severity::Warning);
+    assert_eq!(outcome.diagnostics[1].offset, Some(data.len() - 66));
+}
+
+#[test]
+fn test_parse_truncated_gen2_vu_activities_lenient() {
+    // --- Arrange ---
+    // The download ends in the signature of the activities TREP
+    let data = VuDddBuilder::gen2()
+        .with_activity_day(ActivityDay::new(1_699_920_000).with_distance(100).with_activity(ActivityType::Driving, 10))
+        .build()
+        .expect("VU fixture should be built");
+    let TachographData::VUGen2(vu_data) = parse_from_memory(&data).expect("VU fixture should be parsed") else {
+        panic!("Expected TachographData::VUGen2, but found a different variant.");
+    };
+    let transfer_res_params = <dyn VUData<gen2::VUTransferResponseParameterData>>::get_data(&vu_data);
+    let activities_end = transfer_res_params.iter().take(2).map(|item| 2 + item.raw_data.len()).sum::<usize>();
+    let data = &data[..activities_end - 10];
+
+    // --- Act ---
+    let strict_result = parse_from_memory(data);
+    let lenient_result = parse_from_memory_with_options(data, &ParseOptions::lenient());
+
+    // --- Assert ---
+    assert!(strict_result.is_err(), "Strict parsing should fail for a truncated TREP.");
+    let outcome = lenient_result.expect("Lenient parsing should return the data read so far");
+    let TachographData::VUGen2(vu_data) = outcome.data else {
+        panic!("Expected TachographData::VUGen2, but found a different variant.");
+    };
+    // The record arrays before the signature are kept
+    let transfer_res_params = <dyn VUData<gen2::VUTransferResponseParameterData>>::get_data(&vu_data);
+    assert_eq!(transfer_res_params.len(), 2);
+    let gen2::VUTransferResponseParameterData::Activity(activity) = &transfer_res_params[1].data else {
+        panic!("Expected VUTransferResponseParameterData::Activity");
+    };
+    assert_eq!(activity.vu_activity_daily_record_array.records.len(), 1);
+    assert!(activity.signature_record_array.as_ref().is_some_and(|signatures| signatures.records.is_empty()));
+    assert_eq!(outcome.diagnostics.len(), 1);
+    assert_eq!(outcome.diagnostics[0].severity, Severity::Error);
+    assert_eq!(outcome.diagnostics[0].offset, Some(data.len() + 10 - 5 - 64));
+    assert_eq!(outcome.diagnostics[0].section.as_deref(), Some("Gen2Activities"));
+}
+
+#[test]
+fn test_parse_vu_keeps_unknown_treps() {
+    // --- Arrange ---
+    // Overview, an unknown TREP, a TREP with a registered handler, speed without blocks and two garbage bytes
+    let mut data: Vec<u8> = vec![0x76, 0x01];
+    data.extend_from_slice(&[0x00; 491 + 1 + 1 + 128]);
+    let unknown_offset = data.len();
+    data.extend_from_slice(&[0x76, 0x7A, 0x01, 0x02, 0x03]);
+    data.extend_from_slice(&[0x76, 0x7B, 0x04, 0x05]);
+    data.extend_from_slice(&[0x76, 0x04, 0x00, 0x00]);
+    data.extend_from_slice(&[0x00; 128]);
+    let garbage_offset = data.len();
+    data.extend_from_slice(&[0xAA, 0xBB]);
+    let options =
+        ParseOptions::lenient().with_trep_handler(0x7B, |_trep: u8, data: &[u8]| Ok(serde_json::json!({ "length": data.len() })));
+
+    // --- Act ---
+    let result = parse_from_memory_with_options(&data, &options);
+
+    // --- Assert ---
+    let outcome = result.expect("VU data with unknown TREPs should be parsed");
+    assert_eq!(outcome.diagnostics.len(), 1);
+    assert_eq!(outcome.diagnostics[0].severity, Severity::Warning);
+    assert_eq!(outcome.diagnostics[0].offset, Some(garbage_offset));
+    let TachographData::VUGen1(vu_data) = outcome.data else {
+        panic!("Expected TachographData::VUGen1, but found a different variant.");
+    };
+    let transfer_res_params = <dyn VUData<gen1::VUTransferResponseParameterData>>::get_data(&vu_data);
+    assert_eq!(transfer_res_params.len(), 4);
+    match &transfer_res_params[1].data {
+        gen1::VUTransferResponseParameterData::Raw(block) => {
+            assert_eq!(block.trep, 0x7A);
+            assert_eq!(block.offset, unknown_offset);
+            assert_eq!(block.data, vec![0x01, 0x02, 0x03]);
+            assert!(block.parsed.is_none());
+        }
+        _ => panic!("Expected VUTransferResponseParameterData::Raw"),
+    }
+    match &transfer_res_params[2].data {
+        gen1::VUTransferResponseParameterData::Raw(block) => {
+            assert_eq!(block.trep, 0x7B);
+            assert_eq!(block.parsed, Some(serde_json::json!({ "length": 2 })));
+        }
+        _ => panic!("Expected VUTransferResponseParameterData::Raw"),
+    }
+    assert!(matches!(transfer_res_params[3].data, gen1::VUTransferResponseParameterData::Speed(_)));
+}
+
+#[test]
+fn test_parse_card_keeps_valid_card_files() {
+    // --- Arrange ---
+    // Gen1 control card with a truncated Identification card file
+    let mut data: Vec<u8> = vec![0x00, 0x02, 0x00, 0x00, 0x19];
+    data.extend_from_slice(&[0x00; 25]); // ICC
+    data.extend_from_slice(&[0x00, 0x05, 0x00, 0x00, 0x08]);
+    data.extend_from_slice(&[0x00; 8]); // IC
+    data.extend_from_slice(&[0x05, 0x01, 0x00, 0x00, 0x05, 0x03, 0x00, 0x00, 0x00, 0x00]); // Application Identification
+    data.extend_from_slice(&[0x05, 0x20, 0x00, 0x00, 0x02, 0x00, 0x00]); // Identification
+
+    // --- Act ---
+    let strict_result = parse_from_memory(&data);
+    let lenient_result = parse_from_memory_with_options(&data, &ParseOptions::lenient());
+
+    // --- Assert ---
+    let error = strict_result.expect_err("Strict parse should fail on a broken card file");
+    let context = error.context().expect("Error should have a location");
+    assert_eq!(context.section.as_deref(), Some("Identification"));
+    assert_eq!(context.path, vec!["Identification", "identification", "cardIdentification", "cardNumber", "number"]);
+    // The offset is in the DDD data, the Identification data starts at 58.
+    assert!(context.offset.is_some_and(|offset| offset >= 58));
+    let outcome = lenient_result.expect("Card with a broken card file should be parsed in lenient mode");
+    assert_eq!(outcome.diagnostics.len(), 1);
+    match outcome.data {
+        TachographData::CardGen1(card_data) => match &card_data.card_data_responses {
+            gen1::CardResponseParameterData::ControlCard(card) => {
+                assert_eq!(card.application_identification.type_of_tachograph_card_id, EquipmentType::ControlCard);
+                assert!(card.identification.is_none());
+                assert_eq!(card.ef_errors.len(), 1);
+                let ef_error = &card.ef_errors[&CardFileID::Identification];
+                assert_eq!(ef_error.severity, Severity::Error);
+                assert_eq!(ef_error.section.as_deref(), Some("Identification"));
+            }
+            _ => panic!("Expected CardResponseParameterData::ControlCard, but found a different variant."),
+        },
+        _ => panic!("Expected TachographData::CardGen1, but found a different variant."),
+    }
+}
+
+#[test]
+fn test_parse_card_records_byte_spans() {
+    // --- Arrange ---
+    let mut data: Vec<u8> = vec![0x00, 0x02, 0x00, 0x00, 0x19];
+    data.extend_from_slice(&[0x00; 25]); // ICC
+    data.extend_from_slice(&[0x00, 0x05, 0x00, 0x00, 0x08]);
+    data.extend_from_slice(&[0x00; 8]); // IC
+    data.extend_from_slice(&[0x05, 0x01, 0x00, 0x00, 0x05, 0x03, 0x00, 0x00, 0x00, 0x00]); // Application Identification
+
+    // --- Act ---
+    let outcome = parse_from_memory_with_options(&data, &ParseOptions::strict().with_spans());
+    let without_spans = parse_from_memory_with_options(&data, &ParseOptions::strict());
+
+    // --- Assert ---
+    let spans = outcome.expect("Card should be parsed").spans.expect("Spans should be recorded");
+    assert_eq!(spans.len, data.len());
+    let icc = spans.children.iter().find(|span| span.name == "ICC").expect("ICC section should be recorded");
+    assert_eq!((icc.offset, icc.len), (5, 25));
+    assert_eq!(icc.children[0].name, "CardIccIdentification");
+    assert_eq!(icc.children[0].offset, 5);
+    let ic = spans.children.iter().find(|span| span.name == "IC").expect("IC section should be recorded");
+    assert_eq!((ic.children[0].name.as_str(), ic.children[0].offset, ic.children[0].len), ("CardChipIdentification", 35, 8));
+    let references = spans.find(40).expect("Offset should be inside the spans");
+    assert_eq!((references.name.as_str(), references.offset, references.len), ("OCTET STRING", 39, 4));
+    assert_eq!(references.field.as_deref(), Some("icManufacturingReferences"));
+    assert!(without_spans.expect("Card should be parsed").spans.is_none());
+}
+
+#[test]
+fn test_dissect_card_labels_fields() {
+    // --- Arrange ---
+    let mut data: Vec<u8> = vec![0x00, 0x02, 0x00, 0x00, 0x19];
+    data.push(0x02); // Clock Stop, the same value as the Card Personaliser ID
+    data.extend_from_slice(&[0x00, 0x00, 0x30, 0x39, 0x01, 0x24, 0x01, 0x07]); // Card Extended Serial Number
+    data.extend_from_slice(b"APPR0001"); // Card Approval Number
+    data.push(0x02); // Card Personaliser ID
+    data.extend_from_slice(&[0x44, 0x45, 0x00, 0x01, 0x02]); // Embedder IC Assembler ID
+    data.extend_from_slice(&[0xAB, 0xCD]); // IC Identifier
+    data.extend_from_slice(&[0x00, 0x05, 0x00, 0x00, 0x08]);
+    data.extend_from_slice(&[0x00; 8]); // IC
+    data.extend_from_slice(&[0x05, 0x01, 0x00, 0x00, 0x05, 0x03, 0x00, 0x00, 0x00, 0x00]); // Application Identification
+
+    // --- Act ---
+    let result = dissect_from_memory(&data, &ParseOptions::strict());
+
+    // --- Assert ---
+    let dissection = result.expect("Card should be dissected");
+    let field = |offset: usize| dissection.find(offset).and_then(|node| node.field.clone());
+    assert_eq!(field(5).as_deref(), Some("clockStop"));
+    assert_eq!(field(14).as_deref(), Some("cardApprovalNumber"));
+    assert_eq!(field(22).as_deref(), Some("cardPersonaliserID"));
+    assert_eq!(field(28).as_deref(), Some("icIdentifier"));
+    let serial_number = dissection.find(6).expect("Offset should be dissected");
+    assert_eq!((serial_number.field.as_deref(), serial_number.hex.as_str()), (Some("serialNumber"), "00003039"));
+    assert_eq!(dissection.find(22).and_then(|node| node.value.clone()), Some(serde_json::json!(2)));
+    let text = dissection.to_string();
+    assert!(text.contains("clockStop: INTEGER  [02] = 2"));
+    assert!(text.contains("cardPersonaliserID: INTEGER  [02] = 2"));
+    assert!(dissection.to_json().expect("Dissection should be exported").contains("\"type\":\"CardIccIdentification\""));
+}
+
+#[test]
+fn test_parse_small_gen2_card_by_appendix() {
+    // --- Arrange ---
+    // Gen2 control card, much smaller than a usual Gen2 card, with the V2 application identification
+    let mut data: Vec<u8> = vec![0x00, 0x02, 0x02, 0x00, 0x19];
+    data.extend_from_slice(&[0x00; 25]); // ICC
+    data.extend_from_slice(&[0x00, 0x05, 0x02, 0x00, 0x08]);
+    data.extend_from_slice(&[0x00; 8]); // IC
+    data.extend_from_slice(&[0x05, 0x01, 0x02, 0x00, 0x05, 0x03, 0x00, 0x01, 0x00, 0x00]); // Application Identification
+    data.extend_from_slice(&[0x05, 0x25, 0x02, 0x00, 0x04, 0x00, 0x02, 0x00, 0x00]); // Application Identification V2
+
+    // --- Act ---
+    let result = parse_from_memory(&data);
+
+    // --- Assert ---
+    let data = result.expect("Gen2 card should be detected from the card files");
+    match data {
+        TachographData::CardGen2(card_data) => match &card_data.card_data_responses {
+            gen2::CardResponseParameterData::ControlCard(gen2::ParsedCard::Gen2(card)) => {
+                assert_eq!(card.card_generation, CardGeneration::Gen2v2);
+                assert!(card.application_identification_v2.is_some());
+            }
+            _ => panic!("Expected Gen2 CardResponseParameterData::ControlCard, but found a different variant."),
+        },
+        _ => panic!("Expected TachographData::CardGen2, but found a different variant."),
+    }
+}
+
+#[test]
+fn test_parse_gen2v2_driver_card_border_crossings_and_load_types() {
+    // --- Arrange ---
+    // Gen2v2 driver card with three border crossings (newest at index 1) and two load type entries (one unused)
+    let border_crossing = |time_stamp: u8, odometer: u8| {
+        let mut record: Vec<u8> = vec![0x01, 0x02, 0x00, 0x00, 0x00, time_stamp];
+        record.extend_from_slice(&[0x00; 8]); // GNSS accuracy, coordinates and authentication status
+        record.extend_from_slice(&[0x00, 0x00, odometer]);
+        record
+    };
+    let mut data: Vec<u8> = vec![0x00, 0x02, 0x02, 0x00, 0x19];
+    data.extend_from_slice(&[0x00; 25]); // ICC
+    data.extend_from_slice(&[0x00, 0x05, 0x02, 0x00, 0x08]);
+    data.extend_from_slice(&[0x00; 8]); // IC
+    data.extend_from_slice(&[0x05, 0x01, 0x02, 0x00, 0x11, 0x01, 0x00, 0x01]);
+    data.extend_from_slice(&[0x00; 14]); // Application Identification
+    data.extend_from_slice(&[0x05, 0x25, 0x02, 0x00, 0x0A, 0x00, 0x08, 0x00, 0x03, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00]); // Application Identification V2
+    data.extend_from_slice(&[0x05, 0x28, 0x02, 0x00, 0x35, 0x00, 0x01]); // Border Crossings
+    data.extend_from_slice(&border_crossing(0x20, 10));
+    data.extend_from_slice(&border_crossing(0x30, 20));
+    data.extend_from_slice(&border_crossing(0x10, 5));
+    data.extend_from_slice(&[0x05, 0x30, 0x02, 0x00, 0x0C, 0x00, 0x00]); // Load Type Entries
+    data.extend_from_slice(&[0x00, 0x00, 0x00, 0x40, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00]);
+
+    // --- Act ---
+    let result = parse_from_memory(&data);
+
+    // --- Assert ---
+    let data = result.expect("Gen2v2 driver card should be parsed");
+    let TachographData::CardGen2(card_data) = data else {
+        panic!("Expected TachographData::CardGen2, but found a different variant.");
+    };
+    let gen2::CardResponseParameterData::DriverCard(gen2::ParsedCard::Gen2(card)) = &card_data.card_data_responses else {
+        panic!("Expected Gen2 CardResponseParameterData::DriverCard, but found a different variant.");
+    };
+    assert!(card.ef_errors.is_empty());
+    let border_crossings = card.border_crossings.as_ref().expect("Border crossings should be parsed");
+    let odometers: Vec<Option<u32>> =
+        border_crossings.card_border_crossing_records.iter().map(|record| record.vehicle_odometer_value.data).collect();
+    assert_eq!(odometers, vec![Some(5), Some(10), Some(20)]);
+    let load_type_entries = card.load_type_entries.as_ref().expect("Load type entries should be parsed");
+    assert_eq!(load_type_entries.card_load_type_entry_records.len(), 1);
+    assert_eq!(load_type_entries.card_load_type_entry_records[0].load_type_entered, gen2::LoadType::Goods);
+}
+
+#[test]
+fn test_write_card_ddd_round_trip() {
+    // --- Arrange ---
+    // Gen1 control card with a truncated Identification card file, followed by a signature
+    let mut gen1_data: Vec<u8> = vec![0x00, 0x02, 0x00, 0x00, 0x19];
+    gen1_data.extend_from_slice(&[0x00; 25]); // ICC
+    gen1_data.extend_from_slice(&[0x00, 0x05, 0x00, 0x00, 0x08]);
+    gen1_data.extend_from_slice(&[0x00; 8]); // IC
+    gen1_data.extend_from_slice(&[0x05, 0x01, 0x00, 0x00, 0x05, 0x03, 0x00, 0x00, 0x00, 0x00]); // Application Identification
+    gen1_data.extend_from_slice(&[0x05, 0x01, 0x01, 0x00, 0x02, 0xAB, 0xCD]); // Application Identification Signature
+    gen1_data.extend_from_slice(&[0x05, 0x20, 0x00, 0x00, 0x02, 0x00, 0x00]); // Identification
+    let mut gen2_data = gen1_data.clone();
+    gen2_data.truncate(gen2_data.len() - 14);
+    gen2_data[2] = 0x02;
+    gen2_data[32] = 0x02;
+    gen2_data[45] = 0x02;
+    gen2_data.extend_from_slice(&[0x05, 0x25, 0x02, 0x00, 0x04, 0x00, 0x02, 0x00, 0x00]); // Application Identification V2
+
+    // --- Act ---
+    let gen1_card =
+        parse_from_memory_with_options(&gen1_data, &ParseOptions::lenient()).expect("Gen1 card should be parsed").data;
+    let gen2_card = parse_from_memory(&gen2_data).expect("Gen2 card should be parsed");
+
+    // --- Assert ---
+    let TachographData::CardGen1(gen1_card) = gen1_card else {
+        panic!("Expected TachographData::CardGen1, but found a different variant.");
+    };
+    let TachographData::CardGen2(gen2_card) = gen2_card else {
+        panic!("Expected TachographData::CardGen2, but found a different variant.");
+    };
+    assert_eq!(write_card_ddd(&gen1_card).expect("Gen1 card should be written"), gen1_data);
+    assert_eq!(write_card_ddd(&gen2_card).expect("Gen2 card should be written"), gen2_data);
+}
+
+#[test]
+fn test_write_card_ddd_changed_cyclic_card_file() {
+    // --- Arrange ---
+    // Gen2v2 driver card with three border crossings, the newest at index 1
+    let border_crossing = |time_stamp: u8, odometer: u8| {
+        let mut record: Vec<u8> = vec![0x01, 0x02, 0x00, 0x00, 0x00, time_stamp];
+        record.extend_from_slice(&[0x00; 8]); // GNSS accuracy, coordinates and authentication status
+        record.extend_from_slice(&[0x00, 0x00, odometer]);
+        record
+    };
+    let mut data: Vec<u8> = vec![0x00, 0x02, 0x02, 0x00, 0x19];
+    data.extend_from_slice(&[0x00; 25]); // ICC
+    data.extend_from_slice(&[0x00, 0x05, 0x02, 0x00, 0x08]);
+    data.extend_from_slice(&[0x00; 8]); // IC
+    data.extend_from_slice(&[0x05, 0x01, 0x02, 0x00, 0x11, 0x01, 0x00, 0x01]);
+    data.extend_from_slice(&[0x00; 14]); // Application Identification
+    data.extend_from_slice(&[0x05, 0x25, 0x02, 0x00, 0x0A, 0x00, 0x08, 0x00, 0x03, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00]); // Application Identification V2
+    data.extend_from_slice(&[0x05, 0x28, 0x02, 0x00, 0x35, 0x00, 0x01]); // Border Crossings
+    data.extend_from_slice(&border_crossing(0x20, 10));
+    data.extend_from_slice(&border_crossing(0x30, 20));
+    data.extend_from_slice(&border_crossing(0x10, 5));
+    let TachographData::CardGen2(mut card_data) = parse_from_memory(&data).expect("Gen2v2 driver card should be parsed") else {
+        panic!("Expected TachographData::CardGen2, but found a different variant.");
+    };
+
+    // --- Act ---
+    let gen2::CardResponseParameterData::DriverCard(gen2::ParsedCard::Gen2(card)) = &mut card_data.card_data_responses else {
+        panic!("Expected Gen2 CardResponseParameterData::DriverCard, but found a different variant.");
+    };
+    let border_crossings = card.border_crossings.as_mut().expect("Border crossings should be parsed");
+    border_crossings.card_border_crossing_records[2].vehicle_odometer_value.data = Some(30);
+    let written = write_card_ddd(&card_data).expect("Gen2v2 driver card should be written");
+
+    // --- Assert ---
+    let mut expected = data.clone();
+    expected[120] = 30; // Odometer of the newest record at index 1
+    assert_eq!(written, expected);
+}
+
+#[test]
+fn test_write_card_ddd_derives_activity_record_lengths() {
+    // --- Arrange ---
+    let day = 1_699_920_000;
+    let data = CardDddBuilder::driver_gen1()
+        .with_activity_day(ActivityDay::new(day).with_activity(ActivityType::Rest, 0))
+        .with_activity_day(ActivityDay::new(day + 86400).with_activity(ActivityType::Work, 60))
+        .build()
+        .expect("Gen1 driver card fixture should be built");
+    let TachographData::CardGen1(mut card_data) = parse_from_memory(&data).expect("Gen1 driver card should be parsed") else {
+        panic!("Expected TachographData::CardGen1, but found a different variant.");
+    };
+
+    // --- Act ---
+    let gen1::CardResponseParameterData::DriverCard(card) = &mut card_data.card_data_responses else {
+        panic!("Expected CardResponseParameterData::DriverCard, but found a different variant.");
+    };
+    let driver_activity = card.driver_activity_data.as_mut().expect("Driver activity should be parsed");
+    driver_activity.activity_daily_records[0]
+        .activity_change_info
+        .push(ActivityChangeInfo::new(ActivityCard::Card, 0x1800 | 480));
+    let written = write_card_ddd(&card_data).expect("Gen1 driver card should be written");
+
+    // --- Assert ---
+    let TachographData::CardGen1(written_card) = parse_from_memory(&written).expect("Written card should be parsed") else {
+        panic!("Expected TachographData::CardGen1, but found a different variant.");
+    };
+    let gen1::CardResponseParameterData::DriverCard(card) = written_card.card_data_responses else {
+        panic!("Expected CardResponseParameterData::DriverCard, but found a different variant.");
+    };
+    let records = card.driver_activity_data.expect("Driver activity should be parsed").activity_daily_records;
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].activity_record_length, 12 + 2 * 2);
+    assert_eq!(records[0].activity_change_info.len(), 2);
+    assert_eq!(records[1].activity_previous_record_length, 12 + 2 * 2);
+    assert_eq!(records[1].activity_change_info.len(), 1);
+}
+
+#[test]
+fn test_write_card_ddd_rejects_values_which_do_not_fit() {
+    // --- Arrange ---
+    let data = CardDddBuilder::driver_gen1().build().expect("Gen1 driver card fixture should be built");
+    let TachographData::CardGen1(mut card_data) = parse_from_memory(&data).expect("Gen1 driver card should be parsed") else {
+        panic!("Expected TachographData::CardGen1, but found a different variant.");
+    };
+
+    // --- Act ---
+    // The holder surname is a Name with 35 bytes of text
+    let mut write_with_surname = |surname: String| {
+        let gen1::CardResponseParameterData::DriverCard(card) = &mut card_data.card_data_responses else {
+            panic!("Expected CardResponseParameterData::DriverCard, but found a different variant.");
+        };
+        let Some(Identification::DriverCard(identification)) = card.identification.as_mut() else {
+            panic!("Expected Identification::DriverCard, but found a different variant.");
+        };
+        identification.driver_card_holder_identification.card_holder_name.holder_surname.name = surname;
+        write_card_ddd(&card_data)
+    };
+    let fitting = write_with_surname("X".repeat(35));
+    let too_long = write_with_surname("X".repeat(36));
+
+    // --- Assert ---
+    assert!(fitting.is_ok());
+    assert!(matches!(too_long, Err(Error::InvalidDataEncode(_))));
+}
+
+#[test]
+fn test_parse_gen1_vu_card_download() {
+    // --- Arrange ---
+    // Overview, card download with a Gen1 control card and speed without blocks
+    let mut data: Vec<u8> = vec![0x76, 0x01];
+    data.extend_from_slice(&[0x00; 491 + 1 + 1 + 128]);
+    data.extend_from_slice(&[0x76, 0x06]);
+    data.extend_from_slice(&[0x00, 0x02, 0x00, 0x00, 0x19]);
+    data.extend_from_slice(&[0x00; 25]); // ICC
+    data.extend_from_slice(&[0x00, 0x05, 0x00, 0x00, 0x08]);
+    data.extend_from_slice(&[0x00; 8]); // IC
+    data.extend_from_slice(&[0x05, 0x01, 0x00, 0x00, 0x05, 0x03, 0x00, 0x00, 0x00, 0x00]); // Application Identification
+    data.extend_from_slice(&[0x76, 0x04, 0x00, 0x00]);
+    data.extend_from_slice(&[0x00; 128]);
+
+    // --- Act ---
+    let result = parse_from_memory(&data);
+
+    // --- Assert ---
+    let data = result.expect("VU data with card download should be parsed");
+    let TachographData::VUGen1(vu_data) = data else {
+        panic!("Expected TachographData::VUGen1, but found a different variant.");
+    };
+    let transfer_res_params = <dyn VUData<gen1::VUTransferResponseParameterData>>::get_data(&vu_data);
+    assert_eq!(transfer_res_params.len(), 3);
+    match &transfer_res_params[1].data {
+        gen1::VUTransferResponseParameterData::CardDownload(card_download) => {
+            assert_eq!(card_download.data.len(), 5 + 25 + 5 + 8 + 10);
+            assert!(matches!(card_download.card.card_data_responses, gen1::CardResponseParameterData::ControlCard(_)));
+        }
+        _ => panic!("Expected VUTransferResponseParameterData::CardDownload"),
+    }
+    assert!(matches!(transfer_res_params[2].data, gen1::VUTransferResponseParameterData::Speed(_)));
+}
+
+#[test]
+fn test_parse_gen1_card_download_through_gen2_vu() {
+    // --- Arrange ---
+    // Broken overview, followed by a Gen2 card download with a Gen1 control card
+    let mut card_files: Vec<u8> = vec![0x00, 0x02, 0x00, 0x00, 0x19];
+    card_files.extend_from_slice(&[0x00; 25]); // ICC
+    card_files.extend_from_slice(&[0x00, 0x05, 0x00, 0x00, 0x08]);
+    card_files.extend_from_slice(&[0x00; 8]); // IC
+    card_files.extend_from_slice(&[0x05, 0x01, 0x00, 0x00, 0x05, 0x03, 0x00, 0x00, 0x00, 0x00]); // Application Identification
+    let mut data: Vec<u8> = vec![0x76, 0x21, 0xFF, 0x00, 0xFF, 0x00, 0xFF];
+    data.extend_from_slice(&[0x76, 0x26, 0x00, 0x00, 0x01, 0x00, card_files.len() as u8]);
+    data.extend_from_slice(&card_files);
+    data.extend_from_slice(&[0x08, 0x00, 0x40, 0x00, 0x01]);
+    data.extend_from_slice(&[0x00; 64]);
+
+    // --- Act ---
+    let result = parse_from_memory_with_options(&data, &ParseOptions::lenient());
+
+    // --- Assert ---
+    let outcome = result.expect("VU data with card download should be parsed");
+    let TachographData::VUGen2(vu_data) = outcome.data else {
+        panic!("Expected TachographData::VUGen2, but found a different variant.");
+    };
+    let transfer_res_params = <dyn VUData<gen2::VUTransferResponseParameterData>>::get_data(&vu_data);
+    let card_download = transfer_res_params
+        .iter()
+        .find_map(|item| match &item.data {
+            gen2::VUTransferResponseParameterData::CardDownload(card_download) => Some(card_download),
+            _ => None,
+        })
+        .expect("Expected VUTransferResponseParameterData::CardDownload");
+    match &card_download.card {
+        gen2::VUCardDownloadData::Gen1(card_data) => {
+            assert!(matches!(card_data.card_data_responses, gen1::CardResponseParameterData::ControlCard(_)));
+        }
+        _ => panic!("Expected VUCardDownloadData::Gen1"),
+    }
+    assert_eq!(card_download.signature_record_array.as_ref().map(|signature| signature.no_of_records), Some(1));
+}
+
+#[test]
+fn test_parse_gen2v2_border_crossing_and_load_unload() {
+    // --- Arrange ---
+    let mut card_number_and_generation: Vec<u8> = vec![0x01, 0x0D];
+    card_number_and_generation.extend_from_slice(b"1234567890123456");
+    card_number_and_generation.push(0x02);
+    let gnss_place_auth_record: Vec<u8> = vec![0x60, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x10, 0x00, 0x00, 0x20, 0x01];
+
+    let mut border_crossing: Vec<u8> = card_number_and_generation.clone();
+    border_crossing.extend_from_slice(&card_number_and_generation);
+    border_crossing.extend_from_slice(&[0x0D, 0x12]);
+    border_crossing.extend_from_slice(&gnss_place_auth_record);
+    border_crossing.extend_from_slice(&[0x00, 0x01, 0x00]);
+
+    let mut load_unload: Vec<u8> = vec![0x60, 0x00, 0x00, 0x00, 0x02];
+    load_unload.extend_from_slice(&card_number_and_generation);
+    load_unload.extend_from_slice(&card_number_and_generation);
+    load_unload.extend_from_slice(&gnss_place_auth_record);
+    load_unload.extend_from_slice(&[0x00, 0x02, 0x00]);
+
+    // Broken overview, followed by activities with empty record arrays except border crossing and load/unload
+    let mut data: Vec<u8> = vec![0x76, 0x31, 0xFF, 0x00, 0xFF, 0x00, 0xFF];
+    data.extend_from_slice(&[0x76, 0x32]);
+    for record_type in [0x06, 0x05, 0x0D, 0x01, 0x1C, 0x16, 0x09] {
+        data.extend_from_slice(&[record_type, 0x00, 0x01, 0x00, 0x00]);
+    }
+    data.extend_from_slice(&[0x22, 0x00, border_crossing.len() as u8, 0x00, 0x01]);
+    data.extend_from_slice(&border_crossing);
+    data.extend_from_slice(&[0x23, 0x00, load_unload.len() as u8, 0x00, 0x01]);
+    data.extend_from_slice(&load_unload);
+    data.extend_from_slice(&[0x08, 0x00, 0x40, 0x00, 0x01]);
+    data.extend_from_slice(&[0x00; 64]);
+
+    // --- Act ---
+    let result = parse_from_memory_with_options(&data, &ParseOptions::lenient());
+
+    // --- Assert ---
+    let outcome = result.expect("VU data with activities should be parsed");
+    let TachographData::VUGen2(vu_data) = outcome.data else {
+        panic!("Expected TachographData::VUGen2, but found a different variant.");
+    };
+    let transfer_res_params = <dyn VUData<gen2::VUTransferResponseParameterData>>::get_data(&vu_data);
+    let activity = transfer_res_params
+        .iter()
+        .find_map(|item| match &item.data {
+            gen2::VUTransferResponseParameterData::Activity(activity) => Some(activity),
+            _ => None,
+        })
+        .expect("Expected VUTransferResponseParameterData::Activity");
+
+    let border_crossings = &activity.vu_border_crossing_record_array.as_ref().expect("Expected border crossings").records;
+    assert_eq!(border_crossings.len(), 1);
+    assert_eq!(u8::from(&border_crossings[0].country_left), 0x0D);
+    assert_eq!(u8::from(&border_crossings[0].country_entered), 0x12);
+    assert_eq!(border_crossings[0].card_number_and_gen_driver_slot.full_card_number.card_number, "1234567890123456");
+
+    let load_unloads = &activity.vu_load_unload_record_array.as_ref().expect("Expected load/unload operations").records;
+    assert_eq!(load_unloads.len(), 1);
+    assert_eq!(load_unloads[0].operation_type, gen2::OperationType::UnloadOperation);
+}
+
+#[test]
+fn test_parse_gen2v2_technical_data() {
+    // --- Arrange ---
+    let mut name: Vec<u8> = vec![0x01];
+    name.extend_from_slice(&[b' '; 35]);
+
+    let mut vu_identification: Vec<u8> = name.clone();
+    vu_identification.extend_from_slice(&name); // Manufacturer address
+    vu_identification.extend_from_slice(&[b' '; 16]); // Part number
+    vu_identification.extend_from_slice(&[0x00; 8]); // Serial number
+    vu_identification.extend_from_slice(&[b' '; 4]); // Software version
+    vu_identification.extend_from_slice(&[0x00; 4 + 4]); // Software installation and manufacturing date
+    vu_identification.extend_from_slice(&[b' '; 16]); // Approval number
+    vu_identification.extend_from_slice(&[0x02, 0x01]); // Generation and ability
+    vu_identification.extend_from_slice(b"MAP-01.02.03");
+
+    let mut vu_calibration: Vec<u8> = vec![0x03];
+    vu_calibration.extend_from_slice(&name); // Workshop name
+    vu_calibration.extend_from_slice(&name); // Workshop address
+    vu_calibration.extend_from_slice(&[0x02, 0x0D]);
+    vu_calibration.extend_from_slice(b"1234567890123456");
+    vu_calibration.extend_from_slice(&[0x00; 4]); // Workshop card expiry date
+    vu_calibration.extend_from_slice(b"VF1234567890ABCDE");
+    vu_calibration.extend_from_slice(&[0x0D, 0x01]);
+    vu_calibration.extend_from_slice(&[b' '; 13]); // Vehicle registration number
+    vu_calibration.extend_from_slice(&[0x00; 6]); // W, K and L constants
+    vu_calibration.extend_from_slice(&[b' '; 15]); // Tyre size
+    vu_calibration.extend_from_slice(&[0x5A]);
+    vu_calibration.extend_from_slice(&[0x00; 3 + 3 + 4 + 4 + 4 + 5 * 11]); // Odometers, times and seals
+    vu_calibration.extend_from_slice(&[0x01, 0x0D, 0x60, 0x00, 0x00, 0x00]); // Load type, country and its timestamp
+    vu_calibration.extend_from_slice(&[0x00, 0x00, 0x00, 0x01, 0x01, 0x25, 0x01, 0x02]); // Sensor serial number
+    vu_calibration.extend_from_slice(&[0x00, 0x00, 0x00, 0x02, 0x01, 0x25, 0x08, 0x02]); // GNSS serial number
+    vu_calibration.extend_from_slice(&[0x00, 0x00, 0x00, 0x03, 0x01, 0x25, 0x09, 0x02]); // RCM serial number
+
+    // Broken overview, followed by technical data with empty record arrays except identification and calibration
+    let mut data: Vec<u8> = vec![0x76, 0x31, 0xFF, 0x00, 0xFF, 0x00, 0xFF];
+    data.extend_from_slice(&[0x76, 0x35]);
+    data.extend_from_slice(&[0x19, 0x00, vu_identification.len() as u8, 0x00, 0x01]);
+    data.extend_from_slice(&vu_identification);
+    data.extend_from_slice(&[0x20, 0x00, 0x00, 0x00, 0x00, 0x21, 0x00, 0x00, 0x00, 0x00]);
+    data.extend_from_slice(&[0x0C, 0x00, vu_calibration.len() as u8, 0x00, 0x01]);
+    data.extend_from_slice(&vu_calibration);
+    data.extend_from_slice(&[0x0E, 0x00, 0x00, 0x00, 0x00, 0x17, 0x00, 0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00, 0x00]);
+    data.extend_from_slice(&[0x08, 0x00, 0x40, 0x00, 0x01]);
+    data.extend_from_slice(&[0x00; 64]);
+
+    // --- Act ---
+    let result = parse_from_memory_with_options(&data, &ParseOptions::lenient());
+
+    // --- Assert ---
+    let outcome = result.expect("VU technical data should be parsed");
+    assert!(outcome.diagnostics.iter().all(|diagnostic| diagnostic.section.as_deref() != Some("Gen2v2TechnicalData")));
+    let TachographData::VUGen2(vu_data) = outcome.data else {
+        panic!("Expected TachographData::VUGen2, but found a different variant.");
+    };
+    let transfer_res_params = <dyn VUData<gen2::VUTransferResponseParameterData>>::get_data(&vu_data);
+    let technical_data = transfer_res_params
+        .iter()
+        .find_map(|item| match &item.data {
+            gen2::VUTransferResponseParameterData::Calibration(technical_data) => Some(technical_data),
+            _ => None,
+        })
+        .expect("Expected VUTransferResponseParameterData::Calibration");
+
+    let vu_identification = &technical_data.vu_identification_record_array.records[0];
+    assert!(vu_identification.is_gen2_v2);
+    assert_eq!(vu_identification.vu_digital_map_version.as_deref(), Some("MAP-01.02.03"));
+
+    let vu_calibration = &technical_data.vu_calibration_record_array.records[0];
+    assert!(vu_calibration.is_gen2_v2);
+    assert_eq!(vu_calibration.vehicle_identification_number, "VF1234567890ABCDE");
+    assert_eq!(vu_calibration.by_default_load_type, Some(gen2::LoadType::Goods));
+    assert_eq!(vu_calibration.calibration_country.as_ref().map(u8::from), Some(0x0D));
+    assert_eq!(vu_calibration.sensor_serial_number.as_ref().map(|serial| serial.serial_number), Some(1));
+    assert_eq!(vu_calibration.rcm_serial_number.as_ref().map(|serial| serial.serial_number), Some(3));
+}
+
+#[test]
+fn test_write_vu_ddd_round_trip() {
+    // --- Arrange ---
+    // Overview without company locks and controls, an unknown TREP and speed with one block
+    let mut data: Vec<u8> = vec![0x76, 0x01];
+    data.extend_from_slice(&[0x00; 2 * 194]); // Member state and VU certificates
+    data.extend_from_slice(b"VF1234567890ABCDE");
+    data.extend_from_slice(&[0x0D, 0x01]);
+    data.extend_from_slice(b"AB 123 CD    ");
+    data.extend_from_slice(&[0x00; 4 + 8 + 1 + 4]); // Current time, downloadable period, card slots and downloading time
+    data.extend_from_slice(&[0x01, 0x0D]);
+    data.extend_from_slice(b"1234567890123456");
+    data.push(0x01);
+    data.extend_from_slice(&[b' '; 35]); // Company or workshop name
+    data.extend_from_slice(&[0x00, 0x00]);
+    data.extend_from_slice(&[0x00; 128]); // Signature
+    data.extend_from_slice(&[0x76, 0x7A, 0x01, 0x02, 0x03]);
+    data.extend_from_slice(&[0x76, 0x04, 0x00, 0x01]);
+    data.extend_from_slice(&[0x00; 64]);
+    data.extend_from_slice(&[0x00; 128]);
+    let TachographData::VUGen1(vu_data) = parse_from_memory(&data).expect("VU data should be parsed") else {
+        panic!("Expected TachographData::VUGen1, but found a different variant.");
+    };
+
+    // --- Act ---
+    let written = write_vu_ddd(&vu_data);
+
+    // --- Assert ---
+    assert_eq!(written.expect("VU data should be written"), data);
+}
+
+#[test]
+fn test_write_vu_ddd_changed_speed() {
+    // --- Arrange ---
+    // Overview without company locks and controls, followed by speed with one block
+    let mut data: Vec<u8> = vec![0x76, 0x01];
+    data.extend_from_slice(&[0x00; 2 * 194]); // Member state and VU certificates
+    data.extend_from_slice(b"VF1234567890ABCDE");
+    data.extend_from_slice(&[0x0D, 0x01]);
+    data.extend_from_slice(b"AB 123 CD    ");
+    data.extend_from_slice(&[0x00; 4 + 8 + 1 + 4]); // Current time, downloadable period, card slots and downloading time
+    data.extend_from_slice(&[0x01, 0x0D]);
+    data.extend_from_slice(b"1234567890123456");
+    data.push(0x01);
+    data.extend_from_slice(&[b' '; 35]); // Company or workshop name
+    data.extend_from_slice(&[0x00, 0x00]);
+    data.extend_from_slice(&[0x00; 128]); // Signature
+    let speed_offset = data.len() + 4 + 4;
+    data.extend_from_slice(&[0x76, 0x04, 0x00, 0x01]);
+    data.extend_from_slice(&[0x00; 64]);
+    data.extend_from_slice(&[0x00; 128]);
+    let TachographData::VUGen1(mut vu_data) = parse_from_memory(&data).expect("VU data should be parsed") else {
+        panic!("Expected TachographData::VUGen1, but found a different variant.");
+    };
+
+    // --- Act ---
+    let transfer_res_params = <dyn VUData<gen1::VUTransferResponseParameterData>>::get_data_mut(&mut vu_data);
+    let gen1::VUTransferResponseParameterData::Speed(speed) = &mut transfer_res_params[1].data else {
+        panic!("Expected VUTransferResponseParameterData::Speed");
+    };
+    speed.vu_detailed_speed_data.vu_detailed_speed_blocks[0].speeds_per_second[0] = 90;
+    let written = write_vu_ddd(&vu_data).expect("VU data should be written");
+
+    // --- Assert ---
+    let mut expected = data.clone();
+    expected[speed_offset] = 90;
+    assert_eq!(written, expected);
+}
+
+#[test]
+fn test_write_gen2_vu_ddd_round_trip() {
+    // --- Arrange ---
+    let mut card_files: Vec<u8> = vec![0x00, 0x02, 0x00, 0x00, 0x19];
+    card_files.extend_from_slice(&[0x00; 25]); // ICC
+    card_files.extend_from_slice(&[0x00, 0x05, 0x00, 0x00, 0x08]);
+    card_files.extend_from_slice(&[0x00; 8]); // IC
+    card_files.extend_from_slice(&[0x05, 0x01, 0x00, 0x00, 0x05, 0x03, 0x00, 0x00, 0x00, 0x00]); // Application Identification
+
+    // Broken overview, followed by activities with empty record arrays and a card download
+    let mut data: Vec<u8> = vec![0x76, 0x31, 0xFF, 0x00, 0xFF, 0x00, 0xFF];
+    let trep_offset = data.len();
+    data.extend_from_slice(&[0x76, 0x32]);
+    for record_type in [0x06, 0x05, 0x0D, 0x01, 0x1C, 0x16, 0x09, 0x22, 0x23] {
+        data.extend_from_slice(&[record_type, 0x00, 0x01, 0x00, 0x00]);
+    }
+    data.extend_from_slice(&[0x08, 0x00, 0x40, 0x00, 0x01]);
+    data.extend_from_slice(&[0x00; 64]);
+    data.extend_from_slice(&[0x76, 0x26, 0x00, 0x00, 0x01, 0x00, card_files.len() as u8]);
+    data.extend_from_slice(&card_files);
+    data.extend_from_slice(&[0x08, 0x00, 0x40, 0x00, 0x01]);
+    data.extend_from_slice(&[0x00; 64]);
+    let outcome = parse_from_memory_with_options(&data, &ParseOptions::lenient()).expect("VU data should be parsed");
+    let TachographData::VUGen2(vu_data) = outcome.data else {
+        panic!("Expected TachographData::VUGen2, but found a different variant.");
+    };
+
+    let transfer_res_params = <dyn VUData<gen2::VUTransferResponseParameterData>>::get_data(&vu_data);
+    assert!(matches!(transfer_res_params[0].data, gen2::VUTransferResponseParameterData::Activity(_)));
+    assert!(matches!(transfer_res_params[1].data, gen2::VUTransferResponseParameterData::CardDownload(_)));
+
+    // --- Act ---
+    let written = write_vu_ddd(&vu_data);
+
+    // --- Assert ---
+    // The broken overview is skipped by the parser
+    assert_eq!(written.expect("VU data should be written"), data[trep_offset..]);
+}
+
+#[test]
+fn test_write_gen2_vu_card_download_keeps_record_array() {
+    // --- Arrange ---
+    let mut card_files: Vec<u8> = vec![0x00, 0x02, 0x00, 0x00, 0x19];
+    card_files.extend_from_slice(&[0x00; 25]); // ICC
+    card_files.extend_from_slice(&[0x00, 0x05, 0x00, 0x00, 0x08]);
+    card_files.extend_from_slice(&[0x00; 8]); // IC
+    card_files.extend_from_slice(&[0x05, 0x01, 0x00, 0x00, 0x05, 0x03, 0x00, 0x00, 0x00, 0x00]); // Application Identification
+
+    // Broken overview, followed by a card download with the card files in a single record
+    let mut data: Vec<u8> = vec![0x76, 0x31, 0xFF, 0x00, 0xFF, 0x00, 0xFF];
+    let trep_offset = data.len();
+    data.extend_from_slice(&[0x76, 0x26, 0x00, 0x00, card_files.len() as u8, 0x00, 0x01]);
+    data.extend_from_slice(&card_files);
+    data.extend_from_slice(&[0x08, 0x00, 0x40, 0x00, 0x01]);
+    data.extend_from_slice(&[0x00; 64]);
+    let outcome = parse_from_memory_with_options(&data, &ParseOptions::lenient()).expect("VU data should be parsed");
+    let TachographData::VUGen2(vu_data) = outcome.data else {
+        panic!("Expected TachographData::VUGen2, but found a different variant.");
+    };
+
+    // --- Act ---
+    let written = write_vu_ddd(&vu_data);
+
+    // --- Assert ---
+    assert_eq!(written.expect("VU data should be written"), data[trep_offset..]);
+}
+
+#[test]
+fn test_write_vu_ddd_round_trip_of_vu_fixtures() {
+    for builder in [VuDddBuilder::gen1(), VuDddBuilder::gen2(), VuDddBuilder::gen2v2()] {
+        // --- Arrange ---
+        // The card numbers of the events and faults are of empty card slots, the certificates are zeros
+        let data = builder
+            .with_vehicle_identification_number("VF1234567890ABCDE")
+            .with_vehicle_registration(0x0D, "AB 123 CD")
+            .with_activity_day(ActivityDay::new(1_699_920_000).with_distance(100).with_activity(ActivityType::Driving, 10))
+            .with_control(ControlRecord::new(0xC0, 1_699_930_000, 0x0D, "1234567890123456"))
+            .with_event(EventRecord::new(EventFaultType::OverSpeeding, 1_699_930_000, 1_699_930_100))
+            .with_fault(EventRecord::new(EventFaultType::PowerSupplyInterruption, 1_699_940_000, 1_699_940_100))
+            .with_speed_block(SpeedBlock::constant(1_699_930_000, 80))
+            .build()
+            .expect("VU fixture should be built");
+        let parsed = parse_from_memory(&data).expect("VU fixture should be parsed");
+
+        // --- Act ---
+        let written = write_ddd(&parsed);
+
+        // --- Assert ---
+        assert_eq!(written.expect("VU fixture should be written"), data);
+    }
+}
+
+#[test]
+fn test_write_vu_ddd_of_vu_fixtures_without_raw_data() {
+    for builder in [VuDddBuilder::gen1(), VuDddBuilder::gen2(), VuDddBuilder::gen2v2()] {
+        // --- Arrange ---
+        let data = builder
+            .with_activity_day(
+                ActivityDay::new(1_699_920_000)
+                    .with_distance(100)
+                    .with_activity(ActivityType::Driving, 10)
+                    .with_gnss_record(GnssRecord::new(1_699_930_000, -2_890_000, 140_000).with_authentication_status(2)),
+            )
+            .with_control(ControlRecord::new(0xC0, 1_699_930_000, 0x0D, "1234567890123456"))
+            .with_event(EventRecord::new(EventFaultType::OverSpeeding, 1_699_930_000, 1_699_930_100))
+            .with_speed_block(SpeedBlock::constant(1_699_930_000, 80))
+            .build()
+            .expect("VU fixture should be built");
+        let parsed = parse_from_memory(&data).expect("VU fixture should be parsed");
+
+        // --- Act ---
+        // Without the raw data every TREP is written from its parsed records
+        let written = match parsed {
+            TachographData::VUGen1(mut vu_data) => {
+                let transfer_res_params = <dyn VUData<gen1::VUTransferResponseParameterData>>::get_data_mut(&mut vu_data);
+                transfer_res_params.iter_mut().for_each(|item| item.raw_data.clear());
+                write_vu_ddd(&vu_data)
+            }
+            TachographData::VUGen2(mut vu_data) => {
+                let transfer_res_params = <dyn VUData<gen2::VUTransferResponseParameterData>>::get_data_mut(&mut vu_data);
+                transfer_res_params.iter_mut().for_each(|item| item.raw_data.clear());
+                write_vu_ddd(&vu_data)
+            }
+            _ => panic!("Expected VU data, but found a different variant."),
+        };
+
+        // --- Assert ---
+        assert_eq!(written.expect("VU fixture should be written"), data);
+    }
+}
+
+#[test]
+fn test_write_vu_ddd_changed_event_keeps_null_card_numbers() {
+    // --- Arrange ---
+    let mut data = VuDddBuilder::gen1()
+        .with_event(EventRecord::new(EventFaultType::OverSpeeding, 1_699_930_000, 1_699_930_100))
+        .build()
+        .expect("VU fixture should be built");
+    // The card in the driver slot at the begin of the event is a NullCard of an empty card slot, with zeros after the card type
+    let event_times = [1_699_930_000_u32.to_be_bytes(), 1_699_930_100_u32.to_be_bytes()].concat();
+    let card_number_offset = data.windows(8).position(|window| window == event_times).unwrap() + 8;
+    data[card_number_offset] = 0xFF;
+    let TachographData::VUGen1(mut vu_data) = parse_from_memory(&data).expect("VU fixture should be parsed") else {
+        panic!("Expected TachographData::VUGen1, but found a different variant.");
+    };
+
+    // --- Act ---
+    let transfer_res_params = <dyn VUData<gen1::VUTransferResponseParameterData>>::get_data_mut(&mut vu_data);
+    let position = transfer_res_params.iter().position(|item| item.type_id == VUTransferResponseParameterID::EventsAndFaults);
+    let gen1::VUTransferResponseParameterData::Events(events) = &mut transfer_res_params[position.unwrap()].data else {
+        panic!("Expected VUTransferResponseParameterData::Events");
+    };
+    let event = &mut events.vu_event_data.vu_event_records[0];
+    assert_eq!(event.card_number_driver_slot_begin.card_type, EquipmentType::NullCard);
+    event.similar_events_number = 2;
+    let written = write_vu_ddd(&vu_data).expect("VU data should be written");
+
+    // --- Assert ---
+    // Only the number of similar events differs, the card numbers of the empty card slots keep their zeros
+    let differences: Vec<usize> = (0..data.len()).filter(|index| written.get(*index) != data.get(*index)).collect();
+    assert_eq!(written.len(), data.len());
+    assert_eq!(differences.len(), 1);
+    assert_eq!(written[differences[0]], 2);
+}
+
+#[test]
+fn test_write_gen2_vu_ddd_changed_overview_keeps_certificates() {
+    // --- Arrange ---
+    // The member state and VU certificates are filled with zeros
+    let data =
+        VuDddBuilder::gen2().with_vehicle_identification_number("VF1234567890ABCDE").build().expect("VU fixture should be built");
+    let TachographData::VUGen2(mut vu_data) = parse_from_memory(&data).expect("VU fixture should be parsed") else {
+        panic!("Expected TachographData::VUGen2, but found a different variant.");
+    };
+
+    // --- Act ---
+    let transfer_res_params = <dyn VUData<gen2::VUTransferResponseParameterData>>::get_data_mut(&mut vu_data);
+    let gen2::VUTransferResponseParameterData::Control(overview) = &mut transfer_res_params[0].data else {
+        panic!("Expected VUTransferResponseParameterData::Control");
+    };
+    overview.vehicle_identification_number_record_array.records[0] = "VF1234567890ABCDF".to_owned();
+    let written = write_vu_ddd(&vu_data).expect("VU data should be written");
+
+    // --- Assert ---
+    let mut expected = data.clone();
+    let vin_offset = data.windows(17).position(|window| window == b"VF1234567890ABCDE").unwrap();
+    expected[vin_offset + 16] = b'F';
+    assert_eq!(written, expected);
+}
+
+#[test]
+fn test_parse_gen1_driver_card_fixture() {
+    // --- Arrange ---
+    let day = 1_699_920_000;
+    let data = CardDddBuilder::driver_gen1()
+        .with_activity_day(
+            ActivityDay::new(day)
+                .with_distance(120)
+                .with_activity(ActivityType::Rest, 0)
+                .with_activity(ActivityType::Driving, 480),
+        )
+        .with_activity_day(ActivityDay::new(day + 86400).with_activity(ActivityType::Work, 60))
+        .with_event(EventRecord::new(EventFaultType::OverSpeeding, day + 3600, day + 3700))
+        .build()
+        .expect("Gen1 driver card fixture should be built");
+
+    // --- Act ---
+    let outcome = parse_from_memory_with_options(&data, &ParseOptions::strict());
+
+    // --- Assert ---
+    let outcome = outcome.expect("Gen1 driver card fixture should be parsed");
+    assert!(outcome.diagnostics.is_empty());
+    let TachographData::CardGen1(card_data) = outcome.data else {
+        panic!("Expected TachographData::CardGen1, but found a different variant.");
+    };
+    let gen1::CardResponseParameterData::DriverCard(card) = &card_data.card_data_responses else {
+        panic!("Expected CardResponseParameterData::DriverCard, but found a different variant.");
+    };
+    assert!(card.ef_errors.is_empty());
+    let driver_activity_data = card.driver_activity_data.as_ref().expect("Driver activity data should be parsed");
+    let days: Vec<(u32, usize)> = driver_activity_data
+        .activity_daily_records
+        .iter()
+        .map(|record| (record.activity_record_date.data, record.activity_change_info.len()))
+        .collect();
+    assert_eq!(days, vec![(day, 2), (day + 86400, 1)]);
+    let events_data = card.events_data.as_ref().expect("Events data should be parsed");
+    assert_eq!(events_data.card_event_records[0].len(), 1);
+    assert_eq!(events_data.card_event_records[0][0].event_type, EventFaultType::OverSpeeding);
+}
+
+#[test]
+fn test_parse_gen2v2_driver_card_fixture() {
+    // --- Arrange ---
+    let day = 1_699_920_000;
+    let data = CardDddBuilder::driver_gen2v2()
+        .with_activity_day(ActivityDay::new(day).with_daily_presence_counter(12).with_activity(ActivityType::Driving, 30))
+        .with_fault(EventRecord::new(EventFaultType::PowerSupplyInterruption, day + 60, day + 120))
+        .build()
+        .expect("Gen2v2 driver card fixture should be built");
+
+    // --- Act ---
+    let outcome = parse_from_memory_with_options(&data, &ParseOptions::strict());
+
+    // --- Assert ---
+    let outcome = outcome.expect("Gen2v2 driver card fixture should be parsed");
+    assert!(outcome.diagnostics.is_empty());
+    let TachographData::CardGen2(card_data) = outcome.data else {
+        panic!("Expected TachographData::CardGen2, but found a different variant.");
+    };
+    let gen2::CardResponseParameterData::DriverCard(gen2::ParsedCard::Gen2(card)) = &card_data.card_data_responses else {
+        panic!("Expected Gen2 CardResponseParameterData::DriverCard, but found a different variant.");
+    };
+    assert_eq!(card.card_generation, CardGeneration::Gen2v2);
+    assert!(card.ef_errors.is_empty());
+    let driver_activity_data = card.driver_activity_data.as_ref().expect("Driver activity data should be parsed");
+    assert_eq!(driver_activity_data.activity_daily_records.len(), 1);
+    assert_eq!(driver_activity_data.activity_daily_records[0].activity_daily_presence_counter, "0012");
+    assert!(card.border_crossings.is_some());
+}
+
+#[test]
+fn test_parse_card_fixtures_of_all_card_types() {
+    for card_type in
+        [EquipmentType::DriverCard, EquipmentType::WorkshopCard, EquipmentType::ControlCard, EquipmentType::CompanyCard]
+    {
+        for generation in [CardGeneration::Gen1, CardGeneration::Gen2, CardGeneration::Gen2v2] {
+            // --- Arrange ---
+            let data = CardDddBuilder::new(card_type.clone(), generation.clone())
+                .with_holder_name("Doe", "Jane")
+                .build()
+                .expect("Card fixture should be built");
+
+            // --- Act ---
+            let outcome = parse_from_memory_with_options(&data, &ParseOptions::strict());
+
+            // --- Assert ---
+            let outcome = outcome.unwrap_or_else(|error| panic!("{card_type} {generation:?} fixture should be parsed: {error}"));
+            assert!(outcome.diagnostics.is_empty(), "{card_type} {generation:?}: {:?}", outcome.diagnostics);
+        }
+    }
+}
+
+#[test]
+fn test_parse_vu_fixtures() {
+    for builder in [VuDddBuilder::gen1(), VuDddBuilder::gen2(), VuDddBuilder::gen2v2()] {
+        // --- Arrange ---
+        let data = builder
+            .with_vehicle_identification_number("VF1234567890ABCDE")
+            .with_vehicle_registration(0x0D, "AB 123 CD")
+            .with_activity_day(ActivityDay::new(1_699_920_000).with_distance(100).with_activity(ActivityType::Driving, 10))
+            .with_event(EventRecord::new(EventFaultType::OverSpeeding, 1_699_930_000, 1_699_930_100))
+            .with_speed_block(SpeedBlock::constant(1_699_930_000, 80))
+            .build()
+            .expect("VU fixture should be built");
+
+        // --- Act ---
+        let outcome = parse_from_memory_with_options(&data, &ParseOptions::strict());
+
+        // --- Assert ---
+        let outcome = outcome.expect("VU fixture should be parsed");
+        assert!(outcome.diagnostics.is_empty());
+        // Overview, activities, events and faults, speed and technical data
+        let trep_count = match &outcome.data {
+            TachographData::VUGen1(vu_data) => <dyn VUData<gen1::VUTransferResponseParameterData>>::get_data(vu_data).len(),
+            TachographData::VUGen2(vu_data) => <dyn VUData<gen2::VUTransferResponseParameterData>>::get_data(vu_data).len(),
+            _ => panic!("Expected VU data, but found a different variant."),
+        };
+        assert_eq!(trep_count, 5);
+    }
+}
+
+#[test]
+fn test_deserialize_exported_json() {
+    // --- Arrange ---
+    let mut fixtures = Vec::new();
+    for card_type in
+        [EquipmentType::DriverCard, EquipmentType::WorkshopCard, EquipmentType::ControlCard, EquipmentType::CompanyCard]
+    {
+        for generation in [CardGeneration::Gen1, CardGeneration::Gen2, CardGeneration::Gen2v2] {
+            let mut builder = CardDddBuilder::new(card_type.clone(), generation).with_holder_name("Doe", "Jane");
+            // Only driver and workshop cards store events
+            if matches!(card_type, EquipmentType::DriverCard | EquipmentType::WorkshopCard) {
+                builder = builder.with_event(EventRecord::new(EventFaultType::OverSpeeding, 1_699_930_000, 1_699_930_100));
+            }
+            fixtures.push(builder.build().expect("Card fixture should be built"));
+        }
+    }
+    // The card numbers of the events and the GNSS records are of empty card slots
+    let gnss_record = GnssRecord::new(1_699_930_000, 2_890_000, -140_000).with_authentication_status(1).with_odometer(10_100);
+    for builder in [VuDddBuilder::gen1(), VuDddBuilder::gen2(), VuDddBuilder::gen2v2()] {
+        fixtures.push(
+            builder
+                .with_vehicle_registration(0x0D, "AB 123 CD")
+                .with_activity_day(
+                    ActivityDay::new(1_699_920_000)
+                        .with_distance(100)
+                        .with_activity(ActivityType::Driving, 10)
+                        .with_gnss_record(gnss_record.clone()),
+                )
+                .with_event(EventRecord::new(EventFaultType::OverSpeeding, 1_699_930_000, 1_699_930_100))
+                .with_fault(EventRecord::new(EventFaultType::PowerSupplyInterruption, 1_699_940_000, 1_699_940_100))
+                .with_speed_block(SpeedBlock::constant(1_699_930_000, 80))
+                .build()
+                .expect("VU fixture should be built"),
+        );
+    }
+
+    for data in fixtures {
+        let parsed = parse_from_memory(&data).expect("Fixture should be parsed");
+        let json = parsed.to_json().expect("Data should be exported");
+
+        // --- Act ---
+        let deserialized: TachographData = serde_json::from_str(&json).expect("Exported JSON should be deserialized");
+
+        // --- Assert ---
+        // The card files are kept in a hash map, only the order of their keys may differ
+        let exported_again = deserialized.to_json().expect("Deserialized data should be exported");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&exported_again).expect("JSON should be valid"),
+            serde_json::from_str::<serde_json::Value>(&json).expect("JSON should be valid")
+        );
+        assert_eq!(write_ddd_from_json(&json).expect("Exported JSON should be written"), data);
+    }
+}
+
+/// The keys of the Gen1 test PKI are generated once, RSA key generation is slow.
+fn gen1_test_pki() -> &'static Gen1TestPki {
+    static GEN1_TEST_PKI: OnceLock<Gen1TestPki> = OnceLock::new();
+    GEN1_TEST_PKI.get_or_init(|| Gen1TestPki::new(1))
+}
+
+#[test]
+fn test_verify_gen1_card_fixture_signed_by_test_pki() {
+    // --- Arrange ---
+    let pki = gen1_test_pki();
+    let data = CardDddBuilder::driver_gen1()
+        .with_holder_name("Doe", "Jane")
+        .with_activity_day(ActivityDay::new(1_699_920_000).with_distance(80).with_activity(ActivityType::Driving, 360))
+        .build()
+        .expect("Gen1 driver card fixture should be built");
+    let signed = pki.sign_card_ddd(&data).expect("Gen1 driver card fixture should be signed");
+    let TachographData::CardGen1(card_data) = parse_from_memory(&signed).expect("Signed fixture should be parsed") else {
+        panic!("Expected TachographData::CardGen1, but found a different variant.");
+    };
+    let gen1::CardResponseParameterData::DriverCard(card) = card_data.card_data_responses else {
+        panic!("Expected CardResponseParameterData::DriverCard, but found a different variant.");
+    };
+    let mut tampered_files = card.data_files.clone();
+    let identification = tampered_files.get_mut(&CardFileID::Identification).expect("Identification should be read");
+    identification.data.as_mut().expect("Identification should have data")[0] ^= 0x01;
+
+    // --- Act ---
+    let result = verify_card(&CardGeneration::Gen1, &card.data_files, &pki.erca_public_key());
+    let tampered_result = verify_card(&CardGeneration::Gen1, &tampered_files, &pki.erca_public_key());
+
+    // --- Assert ---
+    let result = result.expect("Signed fixture should be verified");
+    for item in result.result.iter() {
+        match item.card_file_id {
+            CardFileID::ICC | CardFileID::IC => assert!(matches!(item.status, VerifyStatus::NotHaveSignature)),
+            _ => assert!(matches!(item.status, VerifyStatus::Valid), "{} should be valid", item.card_file_id),
+        }
+    }
+    assert!(result.result.iter().any(|item| item.card_file_id == CardFileID::DriverActivityData));
+    let tampered_result = tampered_result.expect("Tampered fixture should be verified");
+    let identification = tampered_result
+        .result
+        .iter()
+        .find(|item| item.card_file_id == CardFileID::Identification)
+        .expect("Identification should be verified");
+    assert!(matches!(identification.status, VerifyStatus::Invalid));
+}
+
+#[test]
+fn test_sign_gen2_card_fixture_with_test_pki() {
+    // --- Arrange ---
+    let pki = Gen2TestPki::new(1);
+    let data = CardDddBuilder::driver_gen2()
+        .with_activity_day(ActivityDay::new(1_699_920_000).with_activity(ActivityType::Driving, 30))
+        .build()
+        .expect("Gen2 driver card fixture should be built");
+
+    // --- Act ---
+    let signed = pki.sign_card_ddd(&data).expect("Gen2 driver card fixture should be signed");
+
+    // --- Assert ---
+    assert_eq!(signed, pki.sign_card_ddd(&data).expect("Gen2 driver card fixture should be signed"));
+    assert_eq!(pki.erca_certificate().len(), 205);
+    let outcome = parse_from_memory_with_options(&signed, &ParseOptions::strict()).expect("Signed fixture should be parsed");
+    assert!(outcome.diagnostics.is_empty());
+    let TachographData::CardGen2(card_data) = outcome.data else {
+        panic!("Expected TachographData::CardGen2, but found a different variant.");
+    };
+    let gen2::CardResponseParameterData::DriverCard(gen2::ParsedCard::Gen2(card)) = &card_data.card_data_responses else {
+        panic!("Expected Gen2 CardResponseParameterData::DriverCard, but found a different variant.");
+    };
+    let certificate = |card_file_id: &CardFileID| card.data_files[card_file_id].data.clone();
+    assert_eq!(certificate(&CardFileID::CACertificate), Some(pki.msca_certificate()));
+    assert_eq!(certificate(&CardFileID::CardCertificate), Some(pki.card_certificate(&EquipmentType::DriverCard)));
+    assert_eq!(certificate(&CardFileID::CardSignCertificate), Some(pki.card_sign_certificate(&EquipmentType::DriverCard)));
+    let activity_data = &card.data_files[&CardFileID::DriverActivityData];
+    assert_eq!(
+        activity_data.signature,
+        Some(pki.sign_card_data(&EquipmentType::DriverCard, activity_data.data.as_ref().expect("Activity data should be read")))
+    );
+}
+
+#[test]
+fn test_verify_gen2_card_fixtures_signed_by_test_pki() {
+    let pki = Gen2TestPki::new(1);
+    for (builder, card_type, card_certificate_id) in [
+        (CardDddBuilder::driver_gen2(), EquipmentType::DriverCard, CardFileID::CardSignCertificate),
+        (
+            CardDddBuilder::new(EquipmentType::CompanyCard, CardGeneration::Gen2),
+            EquipmentType::CompanyCard,
+            CardFileID::CardCertificate,
+        ),
+    ] {
+        // --- Arrange ---
+        let data = builder.build().expect("Gen2 card fixture should be built");
+        let signed = pki.sign_card_ddd(&data).expect("Gen2 card fixture should be signed");
+        let TachographData::CardGen2(card_data) = parse_from_memory(&signed).expect("Signed fixture should be parsed") else {
+            panic!("Expected TachographData::CardGen2, but found a different variant.");
+        };
+        let data_files = match card_data.card_data_responses {
+            gen2::CardResponseParameterData::DriverCard(gen2::ParsedCard::Gen2(card)) => card.data_files,
+            gen2::CardResponseParameterData::CompanyCard(gen2::ParsedCard::Gen2(card)) => card.data_files,
+            _ => panic!("Expected the Gen2 application of the {card_type:?} card, but found a different variant."),
+        };
+        let mut tampered_files = data_files.clone();
+        let identification = tampered_files.get_mut(&CardFileID::Identification).expect("Identification should be read");
+        identification.data.as_mut().expect("Identification should have data")[0] ^= 0x01;
+        let mut forged_files = data_files.clone();
+        forged_files.get_mut(&CardFileID::CACertificate).expect("CA certificate should be read").data =
+            Some(Gen2TestPki::new(2).msca_certificate());
+
+        // --- Act ---
+        let result = verify_card(&CardGeneration::Gen2, &data_files, &pki.erca_certificate());
+        let tampered_result = verify_card(&CardGeneration::Gen2, &tampered_files, &pki.erca_certificate());
+        let forged_result = verify_card(&CardGeneration::Gen2, &forged_files, &pki.erca_certificate());
+
+        // --- Assert ---
+        let result = result.expect("Signed fixture should be verified");
+        assert!(matches!(result.status, VerifyResultStatus::Valid));
+        assert!(result.result.iter().any(|item| item.card_file_id == card_certificate_id && item.end_of_validity.is_some()));
+        for item in result.result.iter() {
+            match item.card_file_id {
+                CardFileID::ICC | CardFileID::IC => assert!(matches!(item.status, VerifyStatus::NotHaveSignature)),
+                _ => assert!(matches!(item.status, VerifyStatus::Valid), "{} should be valid", item.card_file_id),
+            }
+        }
+        assert!(result.result.iter().any(|item| item.card_file_id == CardFileID::Identification));
+        let tampered_result = tampered_result.expect("Tampered fixture should be verified");
+        let identification = tampered_result
+            .result
+            .iter()
+            .find(|item| item.card_file_id == CardFileID::Identification)
+            .expect("Identification should be verified");
+        assert!(matches!(identification.status, VerifyStatus::Invalid));
+        assert!(matches!(forged_result, Err(Error::VerifyError(_))));
+    }
+}
+
+#[test]
+fn test_write_ddd_from_json_of_signed_card_fixtures() {
+    // --- Arrange ---
+    let gen2_pki = Gen2TestPki::new(1);
+    let gen1_data = CardDddBuilder::driver_gen1().build().expect("Gen1 driver card fixture should be built");
+    let gen1_signed = gen1_test_pki().sign_card_ddd(&gen1_data).expect("Gen1 driver card fixture should be signed");
+    let gen2_data = CardDddBuilder::driver_gen2().build().expect("Gen2 driver card fixture should be built");
+    let gen2_signed = gen2_pki.sign_card_ddd(&gen2_data).expect("Gen2 driver card fixture should be signed");
+
+    for signed in [gen1_signed, gen2_signed] {
+        let json =
+            parse_from_memory(&signed).expect("Signed fixture should be parsed").to_json().expect("Data should be exported");
+
+        // --- Act ---
+        let written = write_ddd_from_json(&json);
+
+        // --- Assert ---
+        assert_eq!(written.expect("JSON should be written"), signed);
+    }
+    assert!(matches!(write_ddd_from_json("{\"header\":{}}"), Err(Error::Import(_))));
+}
+
+#[test]
+fn test_write_ddd_from_json_of_vu_fixtures_with_combined_control_types() {
+    let gen2_pki = Gen2TestPki::new(1);
+    for builder in [VuDddBuilder::gen1(), VuDddBuilder::gen2(), VuDddBuilder::gen2v2()] {
+        // --- Arrange ---
+        let data = builder
+            .with_vehicle_registration(13, "B AB 123")
+            .with_current_date_time(1_700_000_000)
+            // Card and VU downloaded
+            .with_control(ControlRecord::new(0xC0, 1_699_990_000, 13, "D000000000000001"))
+            // Printing, display and calibration parameters, with a reserved bit
+            .with_control(ControlRecord::new(0x39, 1_699_995_000, 13, "D000000000000001"))
+            .build()
+            .expect("VU fixture should be built");
+        let signed = match parse_from_memory(&data) {
+            Ok(TachographData::VUGen1(_)) => gen1_test_pki().sign_vu_ddd(&data),
+            _ => gen2_pki.sign_vu_ddd(&data),
+        }
+        .expect("VU fixture should be signed");
+        let json =
+            parse_from_memory(&signed).expect("Signed fixture should be parsed").to_json().expect("Data should be exported");
+
+        // --- Act ---
+        let written = write_ddd_from_json(&json);
+
+        // --- Assert ---
+        assert!(json.contains(r#""cardDownloaded":true,"vuDownloaded":true"#));
+        assert_eq!(written.expect("JSON should be written"), signed);
+    }
+}
+
+#[test]
+fn test_verify_gen1_vu_fixture_signed_by_test_pki() {
+    // --- Arrange ---
+    let pki = gen1_test_pki();
+    let data = VuDddBuilder::gen1()
+        .with_activity_day(ActivityDay::new(1_699_920_000).with_distance(100).with_activity(ActivityType::Driving, 10))
+        .build()
+        .expect("Gen1 VU fixture should be built");
+    let signed = pki.sign_vu_ddd(&data).expect("Gen1 VU fixture should be signed");
+    let mut tampered = signed.clone();
+    *tampered.last_mut().expect("Signed fixture should not be empty") ^= 0x01;
+    let parse_vu = |data: &[u8]| match parse_from_memory(data) {
+        Ok(TachographData::VUGen1(vu_data)) => vu_data,
+        _ => panic!("Expected TachographData::VUGen1, but found a different variant."),
+    };
+
+    // --- Act ---
+    let result = gen1::verify_vu(&parse_vu(&signed), &pki.erca_public_key().try_into().unwrap());
+    let tampered_result = gen1::verify_vu(&parse_vu(&tampered), &pki.erca_public_key().try_into().unwrap());
+    let unsigned_result = gen1::verify_vu(&parse_vu(&data), &pki.erca_public_key().try_into().unwrap());
+
+    // --- Assert ---
+    let result = result.expect("Signed fixture should be verified");
+    assert!(matches!(result.status, VerifyResultStatus::Valid));
+    assert!(result.vu_certificate_end_of_validity.is_some());
+    assert_eq!(result.result.len(), 5);
+    for item in result.result.iter() {
+        assert!(matches!(item.status, VerifyStatus::Valid), "{:?} should be valid", item.trep_id);
+    }
+    let tampered_result = tampered_result.expect("Tampered fixture should be verified");
+    assert!(matches!(tampered_result.status, VerifyResultStatus::PartialyValid));
+    let last = tampered_result.result.last().expect("TREPs should be verified");
+    assert!(matches!(last.status, VerifyStatus::Invalid));
+    assert!(matches!(unsigned_result, Err(Error::VerifyError(_))));
+}
+
+#[test]
+fn test_verify_vu_fixtures_imported_from_json() {
+    let gen2_pki = Gen2TestPki::new(1);
+    for builder in [VuDddBuilder::gen1(), VuDddBuilder::gen2(), VuDddBuilder::gen2v2()] {
+        // --- Arrange ---
+        let data = builder
+            .with_activity_day(ActivityDay::new(1_699_920_000).with_distance(100).with_activity(ActivityType::Driving, 10))
+            .with_event(EventRecord::new(EventFaultType::OverSpeeding, 1_699_930_000, 1_699_930_100))
+            .build()
+            .expect("VU fixture should be built");
+        let (signed, root) = match parse_from_memory(&data).expect("VU fixture should be parsed") {
+            TachographData::VUGen1(_) => (gen1_test_pki().sign_vu_ddd(&data).unwrap(), gen1_test_pki().erca_public_key()),
+            _ => (gen2_pki.sign_vu_ddd(&data).unwrap(), gen2_pki.erca_certificate()),
+        };
+        let json = parse_from_memory(&signed).unwrap().to_json().expect("VU data should be exported");
+
+        // --- Act ---
+        let imported: TachographData = serde_json::from_str(&json).expect("Exported JSON should be deserialized");
+        let result = verify_vu(&imported, &root);
+
+        // --- Assert ---
+        // The TREPs are verified over the exported raw data of the TREPs
+        let result = result.expect("Imported VU data should be verified");
+        assert!(matches!(result.status, VerifyResultStatus::Valid));
+        for item in result.result.iter() {
+            assert!(matches!(item.status, VerifyStatus::Valid), "{:?} should be valid", item.trep_id);
+        }
+    }
+}
+
+#[test]
+fn test_verify_gen2_vu_fixtures_signed_by_test_pki() {
+    let pki = Gen2TestPki::new(1);
+    for builder in [VuDddBuilder::gen2(), VuDddBuilder::gen2v2()] {
+        // --- Arrange ---
+        let data = builder
+            .with_activity_day(ActivityDay::new(1_699_920_000).with_distance(100).with_activity(ActivityType::Driving, 10))
+            .build()
+            .expect("Gen2 VU fixture should be built");
+        let signed = pki.sign_vu_ddd(&data).expect("Gen2 VU fixture should be signed");
+        let mut tampered = signed.clone();
+        *tampered.last_mut().expect("Signed fixture should not be empty") ^= 0x01;
+        let parse = |data: &[u8]| parse_from_memory(data).expect("VU fixture should be parsed");
+
+        // --- Act ---
+        let result = verify_vu(&parse(&signed), &pki.erca_certificate());
+        let tampered_result = verify_vu(&parse(&tampered), &pki.erca_certificate());
+        let other_root_result = verify_vu(&parse(&signed), &Gen2TestPki::new(2).erca_certificate());
+
+        // --- Assert ---
+        let result = result.expect("Signed fixture should be verified");
+        assert!(matches!(result.status, VerifyResultStatus::Valid));
+        assert!(result.member_state_certificate_end_of_validity.is_some());
+        assert_eq!(result.result.len(), 5);
+        for item in result.result.iter() {
+            assert!(matches!(item.status, VerifyStatus::Valid), "{:?} should be valid", item.trep_id);
+        }
+        let tampered_result = tampered_result.expect("Tampered fixture should be verified");
+        assert!(matches!(tampered_result.status, VerifyResultStatus::PartialyValid));
+        assert!(matches!(tampered_result.result.last().expect("TREPs should be verified").status, VerifyStatus::Invalid));
+        assert!(matches!(other_root_result, Err(Error::VerifyError(_))));
+    }
+    assert!(matches!(
+        verify_vu(&parse_from_memory(&VuDddBuilder::gen2().build().unwrap()).unwrap(), &[0; 144]),
+        Err(Error::VerifyError(_))
+    ));
+}
+
+#[test]
+fn test_verify_with_trust_store_of_roots_and_link_certificates() {
+    // --- Arrange ---
+    let previous_pki = Gen2TestPki::new(1);
+    let pki = Gen2TestPki::new(3).with_erca_key_identifier([0xFD, 0x45, 0x43, 0x20, 0x02, 0xFF, 0xFF, 0x02]);
+    let link_certificate = previous_pki.link_certificate(&pki);
+    let directory = std::env::temp_dir().join(format!("esm-parser-trust-store-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).expect("Trust store directory should be created");
+    std::fs::write(directory.join("EC_PK.bin"), gen1_test_pki().erca_public_key()).unwrap();
+    std::fs::write(directory.join("ERCA Gen2 (1) Root Certificate.bin"), previous_pki.erca_certificate()).unwrap();
+    std::fs::write(directory.join("ERCA Gen2 (2) Link Certificate.BIN"), &link_certificate).unwrap();
+    std::fs::write(directory.join("README.txt"), "Not a root key").unwrap();
+
+    let gen1_card = CardDddBuilder::driver_gen1().build().expect("Gen1 driver card fixture should be built");
+    let gen1_card = gen1_test_pki().sign_card_ddd(&gen1_card).expect("Gen1 driver card fixture should be signed");
+    let gen1_files = match parse_from_memory(&gen1_card) {
+        Ok(TachographData::CardGen1(card_data)) => match card_data.card_data_responses {
+            gen1::CardResponseParameterData::DriverCard(card) => card.data_files,
+            _ => panic!("Expected the Gen1 driver card, but found a different variant."),
+        },
+        _ => panic!("Expected TachographData::CardGen1, but found a different variant."),
+    };
+    let gen2_card = pki.sign_card_ddd(&CardDddBuilder::driver_gen2().build().unwrap()).expect("Gen2 card should be signed");
+    let gen2_files = match parse_from_memory(&gen2_card) {
+        Ok(TachographData::CardGen2(card_data)) => match card_data.card_data_responses {
+            gen2::CardResponseParameterData::DriverCard(gen2::ParsedCard::Gen2(card)) => card.data_files,
+            _ => panic!("Expected the Gen2 application of the driver card, but found a different variant."),
+        },
+        _ => panic!("Expected TachographData::CardGen2, but found a different variant."),
+    };
+    let mut link_certificate_file = gen2_files[&CardFileID::Identification].clone();
+    link_certificate_file.card_file_id = CardFileID::LinkCertificate;
+    link_certificate_file.data = Some(link_certificate.clone());
+    link_certificate_file.signature = None;
+    let mut gen2_files_with_link = gen2_files.clone();
+    gen2_files_with_link.insert(CardFileID::LinkCertificate, link_certificate_file);
+    let vu = parse_from_memory(&pki.sign_vu_ddd(&VuDddBuilder::gen2().build().unwrap()).unwrap()).unwrap();
+    let mut previous_root_store = TrustStore::new();
+    previous_root_store.add_root(&previous_pki.erca_certificate()).expect("Root certificate should be added");
+
+    // --- Act ---
+    let trust_store = TrustStore::from_directory(&directory);
+    std::fs::remove_dir_all(&directory).unwrap();
+    let trust_store = trust_store.expect("Trust store should be loaded");
+
+    // --- Assert ---
+    let gen1_result = trust_store.verify_card(&CardGeneration::Gen1, &gen1_files).expect("Gen1 card should be verified");
+    assert!(matches!(gen1_result.status, VerifyResultStatus::Valid));
+    let gen2_result = trust_store.verify_card(&CardGeneration::Gen2, &gen2_files).expect("Gen2 card should be verified");
+    assert!(matches!(gen2_result.status, VerifyResultStatus::Valid));
+    let vu_result = trust_store.verify_vu(&vu).expect("Gen2 VU should be verified");
+    assert!(matches!(vu_result.status, VerifyResultStatus::Valid));
+
+    let card_link_result = previous_root_store.verify_card(&CardGeneration::Gen2, &gen2_files_with_link);
+    assert!(matches!(card_link_result.expect("Card link certificate should be walked").status, VerifyResultStatus::Valid));
+    assert!(matches!(previous_root_store.verify_card(&CardGeneration::Gen2, &gen2_files), Err(Error::VerifyError(_))));
+    assert!(matches!(previous_root_store.verify_vu(&vu), Err(Error::VerifyError(_))));
+    assert!(matches!(previous_root_store.add_root(&link_certificate), Err(Error::VerifyError(_))));
+    assert!(matches!(previous_root_store.add_link_certificate(&previous_pki.erca_certificate()), Err(Error::VerifyError(_))));
+}
+
+#[test]
+fn test_verify_with_msca_cache_and_pinned_roots() {
+    // --- Arrange ---
+    let pki = Gen2TestPki::new(1);
+    let other_pki = Gen2TestPki::new(4).with_erca_key_identifier([0xFD, 0x45, 0x43, 0x20, 0x03, 0xFF, 0xFF, 0x02]);
+    let gen1_vu = VuDddBuilder::gen1().build().expect("Gen1 VU fixture should be built");
+    let gen1_vu = parse_from_memory(&gen1_test_pki().sign_vu_ddd(&gen1_vu).unwrap()).unwrap();
+    let gen2_vu = parse_from_memory(&pki.sign_vu_ddd(&VuDddBuilder::gen2().build().unwrap()).unwrap()).unwrap();
+    let other_vu = parse_from_memory(&other_pki.sign_vu_ddd(&VuDddBuilder::gen2().build().unwrap()).unwrap()).unwrap();
+    let cache_directory = std::env::temp_dir().join(format!("esm-parser-msca-cache-{}", std::process::id()));
+    let cache_key = b"msca cache key of the test";
+    let mut trust_store = TrustStore::new().with_msca_cache(&cache_directory, cache_key);
+    trust_store.add_root(&gen1_test_pki().erca_public_key()).unwrap();
+    trust_store.add_root(&pki.erca_certificate()).unwrap();
+    trust_store.add_root(&other_pki.erca_certificate()).unwrap();
+
+    // --- Act ---
+    let gen1_result = trust_store.verify_vu(&gen1_vu);
+    let gen2_result = trust_store.verify_vu(&gen2_vu);
+    let cached_files = std::fs::read_dir(&cache_directory).map(|entries| entries.count()).unwrap_or_default();
+    let cache_only_store = TrustStore::new().with_msca_cache(&cache_directory, cache_key);
+    let cached_gen1_result = cache_only_store.verify_vu(&gen1_vu);
+    let cached_gen2_result = cache_only_store.verify_vu(&gen2_vu);
+    let not_cached_result = cache_only_store.verify_vu(&other_vu);
+    let other_key_result = TrustStore::new().with_msca_cache(&cache_directory, b"other key").verify_vu(&gen2_vu);
+    let mut pinned_cache_store = TrustStore::new().with_msca_cache(&cache_directory, cache_key);
+    pinned_cache_store.pin_roots(&[]);
+    let pinned_cache_result = pinned_cache_store.verify_vu(&gen2_vu);
+    // An entry of the cache which is changed, e.g. the recovered key of a Gen1 certificate, is not trusted.
+    for entry in std::fs::read_dir(&cache_directory).unwrap() {
+        let file_path = entry.unwrap().path();
+        let mut data = std::fs::read(&file_path).unwrap();
+        let middle = data.len() / 2;
+        data[middle] ^= 0x01;
+        std::fs::write(&file_path, data).unwrap();
+    }
+    let changed_gen1_result = cache_only_store.verify_vu(&gen1_vu);
+    let changed_gen2_result = cache_only_store.verify_vu(&gen2_vu);
+    std::fs::remove_dir_all(&cache_directory).unwrap();
+
+    let mut pinned_store = TrustStore::new();
+    pinned_store.add_root(&pki.erca_certificate()).unwrap();
+    pinned_store.add_root(&other_pki.erca_certificate()).unwrap();
+    pinned_store.pin_roots(&[[0xFD, 0x45, 0x43, 0x20, 0x03, 0xFF, 0xFF, 0x02]]);
+    let mut overridden_store = TrustStore::new();
+    overridden_store.add_root(&pki.erca_certificate()).unwrap();
+    overridden_store.add_root(&Gen2TestPki::new(2).erca_certificate()).unwrap();
+
+    // --- Assert ---
+    assert!(matches!(gen1_result.expect("Gen1 VU should be verified").status, VerifyResultStatus::Valid));
+    assert!(matches!(gen2_result.expect("Gen2 VU should be verified").status, VerifyResultStatus::Valid));
+    assert_eq!(cached_files, 2);
+    assert!(matches!(cached_gen1_result.expect("Cached Gen1 MSCA should be trusted").status, VerifyResultStatus::Valid));
+    assert!(matches!(cached_gen2_result.expect("Cached Gen2 MSCA should be trusted").status, VerifyResultStatus::Valid));
+    assert!(matches!(not_cached_result, Err(Error::VerifyError(_))));
+    assert!(matches!(other_key_result, Err(Error::VerifyError(_))));
+    assert!(matches!(pinned_cache_result, Err(Error::VerifyError(_))));
+    assert!(matches!(changed_gen1_result, Err(Error::VerifyError(_))));
+    assert!(matches!(changed_gen2_result, Err(Error::VerifyError(_))));
+    assert!(matches!(pinned_store.verify_vu(&other_vu).expect("Pinned root should be used").status, VerifyResultStatus::Valid));
+    assert!(matches!(pinned_store.verify_vu(&gen2_vu), Err(Error::VerifyError(_))));
+    assert!(matches!(overridden_store.verify_vu(&gen2_vu), Err(Error::VerifyError(_))));
+}
+
+#[test]
+fn test_sign_vu_fixtures_with_test_pki() {
+    let gen2_pki = Gen2TestPki::new(1);
+    for builder in [VuDddBuilder::gen1(), VuDddBuilder::gen2(), VuDddBuilder::gen2v2()] {
+        // --- Arrange ---
+        let data = builder
+            .with_activity_day(ActivityDay::new(1_699_920_000).with_distance(100).with_activity(ActivityType::Driving, 10))
+            .build()
+            .expect("VU fixture should be built");
+
+        // --- Act ---
+        let signed = match parse_from_memory(&data) {
+            Ok(TachographData::VUGen1(_)) => gen1_test_pki().sign_vu_ddd(&data),
+            _ => gen2_pki.sign_vu_ddd(&data),
+        };
+
+        // --- Assert ---
+        let signed = signed.expect("VU fixture should be signed");
+        let outcome = parse_from_memory_with_options(&signed, &ParseOptions::strict()).expect("Signed fixture should be parsed");
+        assert!(outcome.diagnostics.is_empty());
+        match &outcome.data {
+            TachographData::VUGen1(vu_data) => {
+                let treps = <dyn VUData<gen1::VUTransferResponseParameterData>>::get_data(vu_data);
+                let gen1::VUTransferResponseParameterData::Control(overview) = &treps[0].data else {
+                    panic!("Expected the overview TREP, but found a different TREP.");
+                };
+                assert_eq!(overview.member_state_certificate, gen1_test_pki().msca_certificate());
+                assert_eq!(overview.vu_certificate, gen1_test_pki().vu_certificate());
+            }
+            TachographData::VUGen2(vu_data) => {
+                let treps = <dyn VUData<gen2::VUTransferResponseParameterData>>::get_data(vu_data);
+                let gen2::VUTransferResponseParameterData::Control(overview) = &treps[0].data else {
+                    panic!("Expected the overview TREP, but found a different TREP.");
+                };
+                assert_eq!(overview.vu_certificate_record_array.record_size, 205);
+                assert!(overview.vu_certificate_record_array.records[0].certificate_profile.is_some());
+                assert_eq!(overview.signature_record_array.records[0].len(), 64);
+            }
+            _ => panic!("Expected VU data, but found a different variant."),
+        }
+    }
+}